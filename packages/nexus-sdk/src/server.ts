@@ -150,6 +150,15 @@ export class NexusServer {
     return this._get("/api/v1/settings");
   }
 
+  /**
+   * GET /api/v1/settings/language — the current Nexus UI language.
+   * NEXUS_LANGUAGE is fixed at container creation; subscribe to
+   * `nexus.settings.language_changed` events to follow changes live.
+   */
+  async getLanguage(): Promise<{ language: string }> {
+    return this._get("/api/v1/settings/language");
+  }
+
   /** PUT /api/v1/settings — update plugin settings. */
   async saveSettings(values: Record<string, unknown>): Promise<void> {
     const res = await this.fetch("/api/v1/settings", {
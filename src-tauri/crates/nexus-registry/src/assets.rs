@@ -0,0 +1,329 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::Duration;
+
+/// Directory under the registry root that `build` copies assets into.
+pub const ASSETS_DIR: &str = "assets";
+
+const ICON_MAX_BYTES: usize = 256 * 1024;
+const ICON_MIN_PX: u32 = 64;
+const ICON_MAX_PX: u32 = 1024;
+
+const SCREENSHOT_MAX_BYTES: usize = 2 * 1024 * 1024;
+const SCREENSHOT_MIN: (u32, u32) = (640, 360);
+const SCREENSHOT_MAX: (u32, u32) = (3840, 2160);
+
+#[derive(Clone, Copy)]
+pub enum AssetKind {
+    Icon,
+    Screenshot,
+}
+
+impl AssetKind {
+    fn max_bytes(self) -> usize {
+        match self {
+            AssetKind::Icon => ICON_MAX_BYTES,
+            AssetKind::Screenshot => SCREENSHOT_MAX_BYTES,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Asset {
+    pub bytes: Vec<u8>,
+    /// File extension for the detected format.
+    pub format: &'static str,
+    /// None for SVG icons.
+    pub size: Option<(u32, u32)>,
+}
+
+pub fn http_client() -> Result<reqwest::blocking::Client> {
+    reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(20))
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+pub fn is_remote(reference: &str) -> bool {
+    reference.starts_with("http://") || reference.starts_with("https://")
+}
+
+/// Load an asset from a URL or a path relative to the registry root, and
+/// check its format, file size, and dimensions.
+pub fn check(
+    root: &Path,
+    reference: &str,
+    kind: AssetKind,
+    http: &reqwest::blocking::Client,
+) -> std::result::Result<Asset, String> {
+    let bytes = if is_remote(reference) {
+        let resp = http.get(reference).send().map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!("returned status {}", resp.status()));
+        }
+        resp.bytes().map_err(|e| e.to_string())?.to_vec()
+    } else {
+        std::fs::read(root.join(reference)).map_err(|e| e.to_string())?
+    };
+    inspect(bytes, kind)
+}
+
+fn inspect(bytes: Vec<u8>, kind: AssetKind) -> std::result::Result<Asset, String> {
+    if bytes.len() > kind.max_bytes() {
+        return Err(format!(
+            "{} KB exceeds the {} KB limit",
+            bytes.len() / 1024,
+            kind.max_bytes() / 1024
+        ));
+    }
+
+    let format = match imagesize::image_type(&bytes) {
+        Ok(imagesize::ImageType::Png) => "png",
+        Ok(imagesize::ImageType::Jpeg) => "jpg",
+        Ok(imagesize::ImageType::Webp) => "webp",
+        _ if matches!(kind, AssetKind::Icon) && is_svg(&bytes) => {
+            return Ok(Asset {
+                bytes,
+                format: "svg",
+                size: None,
+            });
+        }
+        _ => return Err("unsupported format; use PNG, JPEG, or WebP".to_string()),
+    };
+    let size = imagesize::blob_size(&bytes).map_err(|e| format!("unreadable image: {e}"))?;
+    let (width, height) = (size.width as u32, size.height as u32);
+
+    match kind {
+        AssetKind::Icon => {
+            if width != height {
+                return Err(format!("icon is {width}x{height}; it must be square"));
+            }
+            if !(ICON_MIN_PX..=ICON_MAX_PX).contains(&width) {
+                return Err(format!(
+                    "icon is {width}x{height}; it must be {ICON_MIN_PX} to {ICON_MAX_PX} px"
+                ));
+            }
+        }
+        AssetKind::Screenshot => {
+            if width < SCREENSHOT_MIN.0
+                || height < SCREENSHOT_MIN.1
+                || width > SCREENSHOT_MAX.0
+                || height > SCREENSHOT_MAX.1
+            {
+                return Err(format!(
+                    "screenshot is {width}x{height}; it must be between {}x{} and {}x{}",
+                    SCREENSHOT_MIN.0, SCREENSHOT_MIN.1, SCREENSHOT_MAX.0, SCREENSHOT_MAX.1
+                ));
+            }
+        }
+    }
+
+    Ok(Asset {
+        bytes,
+        format,
+        size: Some((width, height)),
+    })
+}
+
+fn is_svg(bytes: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]);
+    let head = head.trim_start();
+    head.starts_with("<svg") || (head.starts_with("<?xml") && head.contains("<svg"))
+}
+
+/// Check every asset an entry references. Returns one message per problem.
+pub fn check_entry(root: &Path, entry: &Value, http: &reqwest::blocking::Client) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (field, reference, kind) in references(entry) {
+        if let Err(e) = check(root, reference, kind, http) {
+            errors.push(format!("{field} {reference}: {e}"));
+        }
+    }
+    errors
+}
+
+/// Copy an entry's assets into `assets/` under content-addressed names and
+/// point the entry at the copies, so the index only references files the
+/// registry serves itself. Screenshots gain their dimensions. With
+/// `assets_url`, references are absolute; otherwise they are relative to
+/// the index.
+pub fn bundle_entry(
+    root: &Path,
+    entry: &mut Value,
+    assets_url: Option<&str>,
+    http: &reqwest::blocking::Client,
+) -> Result<()> {
+    if let Some(icon) = entry.get("icon").and_then(|v| v.as_str()).map(str::to_string) {
+        let (url, _) = store(root, &icon, AssetKind::Icon, assets_url, http)?;
+        entry["icon"] = Value::String(url);
+    }
+
+    if let Some(screenshots) = entry.get("screenshots").and_then(|v| v.as_array()).cloned() {
+        let mut bundled = Vec::new();
+        for shot in screenshots {
+            let Some(reference) = shot.get("url").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let (url, size) = store(root, reference, AssetKind::Screenshot, assets_url, http)?;
+            let mut out = json!({ "url": url });
+            if let Some(caption) = shot.get("caption") {
+                out["caption"] = caption.clone();
+            }
+            if let Some((width, height)) = size {
+                out["width"] = json!(width);
+                out["height"] = json!(height);
+            }
+            bundled.push(out);
+        }
+        entry["screenshots"] = Value::Array(bundled);
+    }
+    Ok(())
+}
+
+/// Check an asset and write it to the assets directory. Returns the URL the
+/// index should use and the asset's dimensions.
+fn store(
+    root: &Path,
+    reference: &str,
+    kind: AssetKind,
+    assets_url: Option<&str>,
+    http: &reqwest::blocking::Client,
+) -> Result<(String, Option<(u32, u32)>)> {
+    let asset =
+        check(root, reference, kind, http).map_err(|e| anyhow::anyhow!("{reference}: {e}"))?;
+    let digest = format!("{:x}", Sha256::digest(&asset.bytes));
+    let name = format!("{}.{}", &digest[..16], asset.format);
+
+    let dir = root.join(ASSETS_DIR);
+    std::fs::create_dir_all(&dir).context("Failed to create assets directory")?;
+    std::fs::write(dir.join(&name), &asset.bytes)
+        .with_context(|| format!("Failed to write {ASSETS_DIR}/{name}"))?;
+
+    let url = match assets_url {
+        Some(base) => format!("{}/{ASSETS_DIR}/{name}", base.trim_end_matches('/')),
+        None => format!("{ASSETS_DIR}/{name}"),
+    };
+    Ok((url, asset.size))
+}
+
+/// The assets an entry references, with the field each came from.
+pub fn references(entry: &Value) -> Vec<(&'static str, &str, AssetKind)> {
+    let mut refs = Vec::new();
+    if let Some(icon) = entry.get("icon").and_then(|v| v.as_str()) {
+        refs.push(("icon", icon, AssetKind::Icon));
+    }
+    if let Some(screenshots) = entry.get("screenshots").and_then(|v| v.as_array()) {
+        for shot in screenshots {
+            if let Some(url) = shot.get("url").and_then(|v| v.as_str()) {
+                refs.push(("screenshot", url, AssetKind::Screenshot));
+            }
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A PNG signature and IHDR header, padded to `len` bytes. Enough for
+    /// format and size detection.
+    fn png(width: u32, height: u32, len: usize) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes.resize(len.max(bytes.len()), 0);
+        bytes
+    }
+
+    fn icon(width: u32, height: u32) -> std::result::Result<Asset, String> {
+        inspect(png(width, height, 0), AssetKind::Icon)
+    }
+
+    fn screenshot(width: u32, height: u32) -> std::result::Result<Asset, String> {
+        inspect(png(width, height, 0), AssetKind::Screenshot)
+    }
+
+    #[test]
+    fn icon_dimensions() {
+        let asset = icon(ICON_MIN_PX, ICON_MIN_PX).unwrap();
+        assert_eq!(asset.format, "png");
+        assert_eq!(asset.size, Some((64, 64)));
+        assert!(icon(ICON_MAX_PX, ICON_MAX_PX).is_ok());
+
+        assert!(icon(ICON_MIN_PX - 1, ICON_MIN_PX - 1)
+            .unwrap_err()
+            .contains("64 to 1024 px"));
+        assert!(icon(ICON_MAX_PX + 1, ICON_MAX_PX + 1).is_err());
+        assert!(icon(128, 129).unwrap_err().contains("must be square"));
+    }
+
+    #[test]
+    fn screenshot_dimensions() {
+        assert!(screenshot(SCREENSHOT_MIN.0, SCREENSHOT_MIN.1).is_ok());
+        assert!(screenshot(SCREENSHOT_MAX.0, SCREENSHOT_MAX.1).is_ok());
+
+        for (width, height) in [
+            (SCREENSHOT_MIN.0 - 1, SCREENSHOT_MIN.1),
+            (SCREENSHOT_MIN.0, SCREENSHOT_MIN.1 - 1),
+            (SCREENSHOT_MAX.0 + 1, SCREENSHOT_MAX.1),
+            (SCREENSHOT_MAX.0, SCREENSHOT_MAX.1 + 1),
+        ] {
+            let err = screenshot(width, height).unwrap_err();
+            assert!(err.contains("between 640x360 and 3840x2160"), "{err}");
+        }
+    }
+
+    #[test]
+    fn file_size_limits() {
+        assert!(inspect(png(128, 128, ICON_MAX_BYTES), AssetKind::Icon).is_ok());
+        let err = inspect(png(128, 128, ICON_MAX_BYTES + 1), AssetKind::Icon).unwrap_err();
+        assert_eq!(err, "256 KB exceeds the 256 KB limit");
+
+        assert!(inspect(png(1280, 720, SCREENSHOT_MAX_BYTES), AssetKind::Screenshot).is_ok());
+        let err = inspect(
+            png(1280, 720, SCREENSHOT_MAX_BYTES + 1),
+            AssetKind::Screenshot,
+        )
+        .unwrap_err();
+        assert_eq!(err, "2048 KB exceeds the 2048 KB limit");
+    }
+
+    #[test]
+    fn formats() {
+        let svg = br#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"/>"#.to_vec();
+        let asset = inspect(svg.clone(), AssetKind::Icon).unwrap();
+        assert_eq!((asset.format, asset.size), ("svg", None));
+        // Screenshots need dimensions
+        assert!(inspect(svg, AssetKind::Screenshot).is_err());
+
+        let gif = b"GIF89a\x80\0\x80\0\0\0\0".to_vec();
+        assert_eq!(
+            inspect(gif, AssetKind::Icon).unwrap_err(),
+            "unsupported format; use PNG, JPEG, or WebP"
+        );
+    }
+
+    #[test]
+    fn entry_references() {
+        let entry = json!({
+            "icon": "icon.png",
+            "screenshots": [{ "url": "one.png" }, { "caption": "no url" }, { "url": "two.png" }]
+        });
+        let refs: Vec<_> = references(&entry)
+            .into_iter()
+            .map(|(field, reference, _)| (field, reference))
+            .collect();
+        assert_eq!(
+            refs,
+            [
+                ("icon", "icon.png"),
+                ("screenshot", "one.png"),
+                ("screenshot", "two.png")
+            ]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use jsonschema::Validator;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::schema;
+
+/// Curated collections file at the registry root.
+pub const FILE: &str = "collections.yaml";
+
+/// Load `collections.yaml` and check it against the schema. A registry
+/// without one has no collections.
+pub fn load(root: &Path) -> Result<Vec<Value>> {
+    let path = root.join(FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {FILE}"))?;
+    let value: Value =
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid YAML in {FILE}"))?;
+
+    let collections_schema = schema::collections_schema();
+    let validator =
+        Validator::new(&collections_schema).context("Failed to compile collections schema")?;
+    let errors: Vec<String> = validator
+        .iter_errors(&value)
+        .map(|e| format!("{}: {}", e.instance_path, e))
+        .collect();
+    if !errors.is_empty() {
+        anyhow::bail!("Validation failed for {FILE}: {}", errors.join("; "));
+    }
+
+    Ok(value.as_array().cloned().unwrap_or_default())
+}
+
+/// Duplicate collection IDs and package IDs the registry doesn't have.
+pub fn check(collections: &[Value], package_ids: &HashSet<String>) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for collection in collections {
+        let id = collection.get("id").and_then(|v| v.as_str()).unwrap_or("");
+        if !seen.insert(id) {
+            errors.push(format!("duplicate collection id '{id}'"));
+        }
+        for package in packages(collection) {
+            if !package_ids.contains(package) {
+                errors.push(format!("collection '{id}' lists unknown package '{package}'"));
+            }
+        }
+    }
+    errors
+}
+
+/// Package IDs a collection lists.
+pub fn packages(collection: &Value) -> impl Iterator<Item = &str> {
+    collection
+        .get("packages")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+}
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use crate::schema;
+use crate::publishers::{self, Publisher};
+use crate::{assets, collections, schema};
 
 pub fn run(path: &Path) -> Result<()> {
     println!("Building index.json from: {}", path.display());
@@ -13,17 +16,41 @@ pub fn run(path: &Path) -> Result<()> {
     let registry_meta: Value =
         serde_yaml::from_str(&registry_yaml).context("Invalid registry.yaml")?;
 
-    // Validate and collect plugins
+    // Validate and collect plugins, copying their icons and screenshots
+    // into assets/
     let plugin_schema = schema::plugin_schema();
     let plugin_validator = jsonschema::Validator::new(&plugin_schema)
         .context("Failed to compile plugin schema")?;
-    let plugins = collect_entries(&path.join("plugins"), &plugin_validator)?;
+    let mut package_ids = HashSet::new();
+    let mut plugins = collect_entries(&path.join("plugins"), &plugin_validator, &mut package_ids)?;
+    let assets_url = registry_meta.get("assets_url").and_then(|v| v.as_str());
+    let http = assets::http_client()?;
+    for plugin in &mut plugins {
+        let id = plugin.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        assets::bundle_entry(path, plugin, assets_url, &http)
+            .with_context(|| format!("Invalid asset for {id}"))?;
+    }
 
     // Validate and collect extensions
     let extension_schema = schema::extension_schema();
     let extension_validator = jsonschema::Validator::new(&extension_schema)
         .context("Failed to compile extension schema")?;
-    let extensions = collect_entries(&path.join("extensions"), &extension_validator)?;
+    let mut extensions =
+        collect_entries(&path.join("extensions"), &extension_validator, &mut package_ids)?;
+
+    // Install counts come from stats.yaml, which the registry's own tooling
+    // maintains, so publishers can't inflate them in their entries
+    let stats = load_stats(path)?;
+    apply_install_counts(&mut plugins, &stats.plugins);
+    apply_install_counts(&mut extensions, &stats.extensions);
+
+    // Entries are marked verified only when their publisher proves control of
+    // its domain or GitHub account and the entry's ID is in its namespace
+    let publishers = publishers::load(path)?;
+    let verified = verify_publishers(&publishers, &http);
+    for entry in plugins.iter_mut().chain(extensions.iter_mut()) {
+        apply_verification(entry, &publishers, &verified)?;
+    }
 
     // Build registry object
     let mut registry_obj = serde_json::json!({
@@ -37,13 +64,36 @@ pub fn run(path: &Path) -> Result<()> {
         registry_obj["maintainer"] = maintainer.clone();
     }
 
-    let index = serde_json::json!({
+    let mut collections = collections::load(path)?;
+    let errors = collections::check(&collections, &package_ids);
+    if !errors.is_empty() {
+        anyhow::bail!("Invalid {}: {}", collections::FILE, errors.join("; "));
+    }
+    // Unlisted packages stay out of the index, so they can't be listed here
+    let listed: HashSet<&str> = plugins
+        .iter()
+        .chain(&extensions)
+        .filter_map(|e| e.get("id").and_then(|v| v.as_str()))
+        .collect();
+    for collection in &mut collections {
+        let kept: Vec<Value> = collections::packages(collection)
+            .filter(|id| listed.contains(id))
+            .map(Value::from)
+            .collect();
+        collection["packages"] = Value::Array(kept);
+    }
+    collections.retain(|c| collections::packages(c).next().is_some());
+
+    let mut index = serde_json::json!({
         "version": 2,
         "registry": registry_obj,
         "updated_at": chrono::Utc::now().to_rfc3339(),
         "plugins": plugins,
         "extensions": extensions
     });
+    if !collections.is_empty() {
+        index["collections"] = Value::Array(collections);
+    }
 
     let output_path = path.join("index.json");
     std::fs::write(&output_path, serde_json::to_string_pretty(&index)?)
@@ -58,7 +108,99 @@ pub fn run(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn collect_entries(dir: &Path, validator: &jsonschema::Validator) -> Result<Vec<Value>> {
+/// IDs of the publishers whose attestation checks out.
+fn verify_publishers(
+    publishers: &[Publisher],
+    http: &reqwest::blocking::Client,
+) -> HashSet<String> {
+    let mut verified = HashSet::new();
+    for publisher in publishers {
+        match publisher.verify(http) {
+            Ok(()) => {
+                println!("Verified publisher {} ({})", publisher.name, publisher.identity());
+                verified.insert(publisher.id.clone());
+            }
+            Err(e) => eprintln!("  WARN: publisher {} is not verified: {e}", publisher.id),
+        }
+    }
+    verified
+}
+
+/// Set `verified` and `verified_identity` on an entry from its publisher,
+/// replacing anything the entry claimed itself.
+fn apply_verification(
+    entry: &mut Value,
+    publishers: &[Publisher],
+    verified: &HashSet<String>,
+) -> Result<()> {
+    let id = entry.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    if let Some(error) = publishers::check_entry(entry, publishers) {
+        anyhow::bail!("{id}: {error}");
+    }
+    let publisher = entry
+        .get("publisher")
+        .and_then(|v| v.as_str())
+        .and_then(|id| publishers.iter().find(|p| p.id == id))
+        .filter(|p| verified.contains(&p.id));
+    let Some(obj) = entry.as_object_mut() else {
+        return Ok(());
+    };
+    obj.remove("verified");
+    obj.remove("verified_identity");
+    if let Some(publisher) = publisher {
+        obj.insert("verified".into(), Value::Bool(true));
+        obj.insert("verified_identity".into(), Value::from(publisher.identity()));
+    }
+    Ok(())
+}
+
+/// Install counts by package ID, from `stats.yaml` at the registry root.
+#[derive(Default, Deserialize)]
+struct Stats {
+    #[serde(default)]
+    plugins: HashMap<String, u64>,
+    #[serde(default)]
+    extensions: HashMap<String, u64>,
+}
+
+fn load_stats(path: &Path) -> Result<Stats> {
+    let stats_path = path.join("stats.yaml");
+    if !stats_path.exists() {
+        return Ok(Stats::default());
+    }
+    let content = std::fs::read_to_string(&stats_path).context("Failed to read stats.yaml")?;
+    serde_yaml::from_str(&content).context("Invalid stats.yaml")
+}
+
+/// Replace each entry's `install_count` with the one in `counts`; entries
+/// without a count lose theirs.
+fn apply_install_counts(entries: &mut [Value], counts: &HashMap<String, u64>) {
+    for entry in entries {
+        let count = entry
+            .get("id")
+            .and_then(|v| v.as_str())
+            .and_then(|id| counts.get(id));
+        let Some(obj) = entry.as_object_mut() else {
+            continue;
+        };
+        match count {
+            Some(count) => {
+                obj.insert("install_count".into(), Value::from(*count));
+            }
+            None => {
+                obj.remove("install_count");
+            }
+        }
+    }
+}
+
+/// Valid entries in `dir`, except unlisted ones. `ids` gains every entry's
+/// ID, unlisted or not.
+fn collect_entries(
+    dir: &Path,
+    validator: &jsonschema::Validator,
+    ids: &mut HashSet<String>,
+) -> Result<Vec<Value>> {
     let mut entries = Vec::new();
 
     if !dir.exists() {
@@ -86,6 +228,10 @@ fn collect_entries(dir: &Path, validator: &jsonschema::Validator) -> Result<Vec<
             );
         }
 
+        if let Some(id) = value.get("id").and_then(|v| v.as_str()) {
+            ids.insert(id.to_string());
+        }
+
         // Skip unlisted entries
         if value.get("status").and_then(|v| v.as_str()) == Some("unlisted") {
             continue;
@@ -103,3 +249,58 @@ fn collect_entries(dir: &Path, validator: &jsonschema::Validator) -> Result<Vec<
 
     Ok(entries)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn counts(entries: &[Value]) -> Vec<Option<u64>> {
+        entries
+            .iter()
+            .map(|e| e.get("install_count").and_then(|v| v.as_u64()))
+            .collect()
+    }
+
+    #[test]
+    fn install_counts_come_only_from_stats() {
+        let mut entries = vec![
+            json!({ "id": "com.example.counted" }),
+            json!({ "id": "com.example.zero", "install_count": 7 }),
+            json!({ "id": "com.example.uncounted", "install_count": 99 }),
+            json!({ "name": "no id" }),
+        ];
+        let stats = HashMap::from([
+            ("com.example.counted".to_string(), 1200),
+            ("com.example.zero".to_string(), 0),
+            ("com.example.unknown".to_string(), 5),
+        ]);
+        apply_install_counts(&mut entries, &stats);
+        // A zero count is kept; a missing one drops whatever the entry claimed
+        assert_eq!(counts(&entries), [Some(1200), Some(0), None, None]);
+        assert!(entries[2].get("install_count").is_none());
+    }
+
+    #[test]
+    fn stats_file_is_optional() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = load_stats(dir.path()).unwrap();
+        assert!(stats.plugins.is_empty() && stats.extensions.is_empty());
+
+        std::fs::write(
+            dir.path().join("stats.yaml"),
+            "plugins:\n  com.example.weather: 42\n",
+        )
+        .unwrap();
+        let stats = load_stats(dir.path()).unwrap();
+        assert_eq!(stats.plugins["com.example.weather"], 42);
+        assert!(stats.extensions.is_empty());
+
+        std::fs::write(
+            dir.path().join("stats.yaml"),
+            "plugins:\n  com.example.weather: -1\n",
+        )
+        .unwrap();
+        assert!(load_stats(dir.path()).is_err());
+    }
+}
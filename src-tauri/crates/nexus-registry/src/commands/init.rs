@@ -26,6 +26,8 @@ pub fn run(path: &Path) -> Result<()> {
 description: "A community plugin and extension registry for Nexus"
 # homepage: "https://example.com"
 # maintainer: "Your Name <you@example.com>"
+# Where the registry is served, for absolute icon and screenshot URLs in index.json
+# assets_url: "https://raw.githubusercontent.com/you/registry/main"
 "#,
     )?;
 
@@ -38,6 +40,14 @@ description: "A community plugin and extension registry for Nexus"
         path.join("schema/extension.schema.json"),
         schema::extension_schema_pretty(),
     )?;
+    fs::write(
+        path.join("schema/collections.schema.json"),
+        schema::collections_schema_pretty(),
+    )?;
+    fs::write(
+        path.join("schema/publishers.schema.json"),
+        schema::publishers_schema_pretty(),
+    )?;
     fs::write(
         path.join("schema/registry.schema.json"),
         schema::registry_schema_pretty(),
@@ -152,6 +162,8 @@ on:
       - "plugins/**"
       - "extensions/**"
       - "registry.yaml"
+      - "collections.yaml"
+      - "publishers.yaml"
 
 jobs:
   validate:
@@ -172,6 +184,9 @@ on:
       - "plugins/**"
       - "extensions/**"
       - "registry.yaml"
+      - "collections.yaml"
+      - "publishers.yaml"
+      - "stats.yaml"
 
 jobs:
   build:
@@ -205,6 +220,9 @@ extensions/       — Extension YAML definitions
 schema/           — JSON Schema files for validation
 index.json        — Compiled registry index (auto-generated)
 registry.yaml     — Registry metadata
+collections.yaml  — Curated groups of packages (optional)
+publishers.yaml   — Publisher identities entries can claim (optional)
+stats.yaml        — Install counts by package ID (optional)
 ```
 
 ## Adding a Package
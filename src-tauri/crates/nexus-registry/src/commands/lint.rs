@@ -0,0 +1,723 @@
+use anyhow::{bail, Context, Result};
+use jsonschema::Validator;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+use crate::publishers::{self, Publisher};
+use crate::{assets, collections, schema};
+
+/// Full semver 2.0. The schema only checks for a leading `X.Y.Z`.
+const SEMVER_PATTERN: &str = r"^(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable name of the check, e.g. `broken-manifest-url`.
+    pub code: &'static str,
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub message: String,
+}
+
+pub struct LintArgs {
+    pub json: bool,
+    pub offline: bool,
+    pub deny_warnings: bool,
+}
+
+pub fn run(path: &Path, args: &LintArgs) -> Result<()> {
+    if !args.json {
+        println!("Linting registry at: {}", path.display());
+    }
+
+    let mut linter = Linter::new(args.offline)?;
+    linter.lint_registry(path)?;
+
+    let errors = linter
+        .diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = linter.diagnostics.len() - errors;
+
+    if args.json {
+        let report = serde_json::json!({
+            "diagnostics": linter.diagnostics,
+            "errors": errors,
+            "warnings": warnings,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for d in &linter.diagnostics {
+            let level = match d.severity {
+                Severity::Error => "ERROR",
+                Severity::Warning => "WARN",
+            };
+            match &d.id {
+                Some(id) => eprintln!("  {level} [{}] {}: {id}: {}", d.code, d.file, d.message),
+                None => eprintln!("  {level} [{}] {}: {}", d.code, d.file, d.message),
+            }
+        }
+        println!("{errors} error(s), {warnings} warning(s)");
+    }
+
+    if errors > 0 || (args.deny_warnings && warnings > 0) {
+        bail!("lint failed with {errors} error(s), {warnings} warning(s)");
+    }
+    Ok(())
+}
+
+struct Linter {
+    http: reqwest::blocking::Client,
+    /// Checks that need the network are skipped.
+    offline: bool,
+    /// Whether the docker CLI can be run, checked on first use.
+    docker: Option<bool>,
+    semver: Regex,
+    /// Plugin and extension IDs seen so far, for checking collections.
+    package_ids: HashSet<String>,
+    /// From publishers.yaml; None when it is invalid.
+    publishers: Option<Vec<Publisher>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The entry being linted, for attaching diagnostics to it.
+struct Entry<'a> {
+    file: &'a str,
+    id: Option<&'a str>,
+}
+
+impl Linter {
+    fn new(offline: bool) -> Result<Self> {
+        Ok(Self {
+            http: assets::http_client()?,
+            offline,
+            docker: None,
+            semver: Regex::new(SEMVER_PATTERN).expect("valid semver pattern"),
+            package_ids: HashSet::new(),
+            publishers: None,
+            diagnostics: Vec::new(),
+        })
+    }
+
+    fn report(&mut self, severity: Severity, code: &'static str, entry: &Entry, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            code,
+            file: entry.file.to_string(),
+            id: entry.id.map(str::to_string),
+            message,
+        });
+    }
+
+    fn lint_registry(&mut self, path: &Path) -> Result<()> {
+        let root = Entry {
+            file: "registry.yaml",
+            id: None,
+        };
+        if !path.join("registry.yaml").exists() {
+            self.report(
+                Severity::Error,
+                "missing-registry-metadata",
+                &root,
+                "registry.yaml not found".into(),
+            );
+        }
+        if path.join("registry.json").exists() {
+            let legacy = Entry {
+                file: "registry.json",
+                id: None,
+            };
+            self.report(
+                Severity::Warning,
+                "deprecated-file",
+                &legacy,
+                "registry.json is the legacy index format; build index.json with `nexus-registry build`".into(),
+            );
+        }
+
+        self.lint_publishers(path);
+
+        for (kind, dir, schema) in [
+            ("plugin", "plugins", schema::plugin_schema()),
+            ("extension", "extensions", schema::extension_schema()),
+        ] {
+            let dir = path.join(dir);
+            if dir.exists() {
+                self.lint_directory(path, &dir, kind, &schema)?;
+            }
+        }
+
+        let file = Entry {
+            file: collections::FILE,
+            id: None,
+        };
+        match collections::load(path) {
+            Ok(list) => {
+                for error in collections::check(&list, &self.package_ids) {
+                    self.report(Severity::Error, "invalid-collection", &file, error);
+                }
+            }
+            Err(e) => self.report(Severity::Error, "invalid-collection", &file, format!("{e:#}")),
+        }
+        Ok(())
+    }
+
+    fn lint_directory(
+        &mut self,
+        root: &Path,
+        dir: &Path,
+        kind: &str,
+        schema: &Value,
+    ) -> Result<()> {
+        let validator =
+            Validator::new(schema).with_context(|| format!("Failed to compile {kind} schema"))?;
+        let mut seen_ids: HashSet<String> = HashSet::new();
+
+        let mut files = Vec::new();
+        for ext in ["yaml", "yml"] {
+            let pattern = dir.join(format!("*.{ext}")).to_string_lossy().to_string();
+            for file in glob::glob(&pattern)? {
+                files.push(file?);
+            }
+        }
+        files.sort();
+
+        for file_path in files {
+            let file_name = file_path
+                .strip_prefix(root)
+                .unwrap_or(&file_path)
+                .display()
+                .to_string();
+            let content = std::fs::read_to_string(&file_path)
+                .with_context(|| format!("Failed to read {file_name}"))?;
+
+            let value: Value = match serde_yaml::from_str(&content) {
+                Ok(v) => v,
+                Err(e) => {
+                    let entry = Entry {
+                        file: &file_name,
+                        id: None,
+                    };
+                    self.report(Severity::Error, "invalid-yaml", &entry, e.to_string());
+                    continue;
+                }
+            };
+            let entry = Entry {
+                file: &file_name,
+                id: value.get("id").and_then(|v| v.as_str()),
+            };
+
+            let schema_errors: Vec<String> = validator
+                .iter_errors(&value)
+                .map(|e| format!("{}: {}", e.instance_path, e))
+                .collect();
+            for error in schema_errors {
+                self.report(Severity::Error, "schema", &entry, error);
+            }
+
+            if let Some(id) = entry.id {
+                self.package_ids.insert(id.to_string());
+                if !seen_ids.insert(id.to_string()) {
+                    self.report(
+                        Severity::Error,
+                        "duplicate-id",
+                        &entry,
+                        format!("duplicate {kind} id"),
+                    );
+                }
+            }
+
+            self.check_deprecated_fields(&entry, &value, schema);
+            for field in ["install_count", "verified", "verified_identity"] {
+                if value.get(field).is_some() {
+                    self.report(
+                        Severity::Warning,
+                        "build-managed-field",
+                        &entry,
+                        format!("{field} is set by build; remove it"),
+                    );
+                }
+            }
+            if let Some(publishers) = &self.publishers {
+                if let Some(error) = publishers::check_entry(&value, publishers) {
+                    self.report(Severity::Error, "invalid-publisher", &entry, error);
+                }
+            }
+            self.check_version(&entry, &value);
+            if kind == "plugin" {
+                self.check_assets(root, &entry, &value);
+                self.check_image(&entry, &value);
+            }
+            self.check_manifest(root, &entry, &value);
+        }
+        Ok(())
+    }
+
+    /// publishers.yaml must be valid, and each publisher's attestation should
+    /// check out, or its entries won't be marked verified.
+    fn lint_publishers(&mut self, root: &Path) {
+        let file = Entry {
+            file: publishers::FILE,
+            id: None,
+        };
+        let list = match publishers::load(root) {
+            Ok(list) => list,
+            Err(e) => {
+                self.report(Severity::Error, "invalid-publisher", &file, format!("{e:#}"));
+                return;
+            }
+        };
+        if !self.offline {
+            for publisher in &list {
+                if let Err(e) = publisher.verify(&self.http) {
+                    let entry = Entry {
+                        file: publishers::FILE,
+                        id: Some(&publisher.id),
+                    };
+                    self.report(Severity::Warning, "unverified-publisher", &entry, e);
+                }
+            }
+        }
+        self.publishers = Some(list);
+    }
+
+    /// Fields the schema marks `"deprecated": true`.
+    fn check_deprecated_fields(&mut self, entry: &Entry, value: &Value, schema: &Value) {
+        let (Some(fields), Some(properties)) =
+            (value.as_object(), schema["properties"].as_object())
+        else {
+            return;
+        };
+        for field in fields.keys() {
+            let Some(property) = properties.get(field) else {
+                continue;
+            };
+            if property["deprecated"].as_bool() == Some(true) {
+                let hint = property["description"].as_str().unwrap_or("remove it");
+                self.report(
+                    Severity::Warning,
+                    "deprecated-field",
+                    entry,
+                    format!("'{field}' is deprecated: {hint}"),
+                );
+            }
+        }
+    }
+
+    fn check_version(&mut self, entry: &Entry, value: &Value) {
+        if let Some(version) = value.get("version").and_then(|v| v.as_str()) {
+            if !self.semver.is_match(version) {
+                self.report(
+                    Severity::Error,
+                    "non-semver-version",
+                    entry,
+                    format!("version '{version}' is not valid semver"),
+                );
+            }
+        }
+    }
+
+    fn check_assets(&mut self, root: &Path, entry: &Entry, value: &Value) {
+        if value.get("icon").is_none() {
+            self.report(
+                Severity::Warning,
+                "missing-icon",
+                entry,
+                "no icon; the marketplace shows a placeholder".into(),
+            );
+        }
+        for (field, reference, kind) in assets::references(value) {
+            if self.offline && assets::is_remote(reference) {
+                continue;
+            }
+            if let Err(e) = assets::check(root, reference, kind, &self.http) {
+                self.report(
+                    Severity::Error,
+                    "invalid-asset",
+                    entry,
+                    format!("{field} {reference}: {e}"),
+                );
+            }
+        }
+    }
+
+    fn check_image(&mut self, entry: &Entry, value: &Value) {
+        let Some(image) = value.get("image").and_then(|v| v.as_str()) else {
+            return;
+        };
+        if value.get("image_digest").is_none() {
+            self.report(
+                Severity::Warning,
+                "missing-image-digest",
+                entry,
+                "no image_digest; installs can't verify the pulled image".into(),
+            );
+        }
+        if self.offline {
+            return;
+        }
+        if self.docker.is_none() {
+            let available = Command::new("docker")
+                .arg("--version")
+                .output()
+                .is_ok_and(|o| o.status.success());
+            if !available {
+                eprintln!("  docker not found; skipping image checks");
+            }
+            self.docker = Some(available);
+        }
+        if self.docker != Some(true) {
+            return;
+        }
+        let output = Command::new("docker")
+            .args(["manifest", "inspect", image])
+            .output();
+        match output {
+            Ok(o) if o.status.success() => {}
+            Ok(o) => {
+                let stderr = String::from_utf8_lossy(&o.stderr);
+                self.report(
+                    Severity::Error,
+                    "unreachable-image",
+                    entry,
+                    format!("image {image}: {}", stderr.trim()),
+                );
+            }
+            Err(e) => {
+                self.report(
+                    Severity::Error,
+                    "unreachable-image",
+                    entry,
+                    format!("image {image}: {e}"),
+                );
+            }
+        }
+    }
+
+    /// The manifest must be reachable, match its hash, and describe the
+    /// same package and version as the entry.
+    fn check_manifest(&mut self, root: &Path, entry: &Entry, value: &Value) {
+        let Some(url) = value.get("manifest_url").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let body = if url.starts_with("http://") || url.starts_with("https://") {
+            if self.offline {
+                return;
+            }
+            self.get(url)
+        } else {
+            // Local registries point at manifests relative to the root
+            let path = url
+                .strip_prefix("file://")
+                .map(Path::new)
+                .map(Path::to_path_buf);
+            std::fs::read(path.unwrap_or_else(|| root.join(url))).map_err(|e| e.to_string())
+        };
+        let body = match body {
+            Ok(body) => body,
+            Err(e) => {
+                self.report(
+                    Severity::Error,
+                    "broken-manifest-url",
+                    entry,
+                    format!("{url}: {e}"),
+                );
+                return;
+            }
+        };
+
+        if let Some(expected) = value.get("manifest_sha256").and_then(|v| v.as_str()) {
+            let actual = format!("{:x}", Sha256::digest(&body));
+            if actual != expected {
+                self.report(
+                    Severity::Error,
+                    "manifest-hash-mismatch",
+                    entry,
+                    format!("manifest_sha256 is {expected} but the manifest hashes to {actual}"),
+                );
+            }
+        }
+
+        let manifest: Value = match serde_json::from_slice(&body) {
+            Ok(m) => m,
+            Err(e) => {
+                self.report(
+                    Severity::Error,
+                    "invalid-manifest",
+                    entry,
+                    format!("{url}: {e}"),
+                );
+                return;
+            }
+        };
+        for field in ["id", "version"] {
+            let listed = value.get(field).and_then(|v| v.as_str());
+            let declared = manifest.get(field).and_then(|v| v.as_str());
+            if listed.is_some() && listed != declared {
+                self.report(
+                    Severity::Error,
+                    "manifest-mismatch",
+                    entry,
+                    format!(
+                        "{field} is '{}' but the manifest declares '{}'",
+                        listed.unwrap_or_default(),
+                        declared.unwrap_or("(none)")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn get(&self, url: &str) -> std::result::Result<Vec<u8>, String> {
+        let resp = self.http.get(url).send().map_err(|e| e.to_string())?;
+        if !resp.status().is_success() {
+            return Err(format!("returned status {}", resp.status()));
+        }
+        resp.bytes().map(|b| b.to_vec()).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const MANIFEST: &str = r#"{"id":"com.example.weather","version":"1.0.0"}"#;
+    const ICON: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 64 64"/>"#;
+
+    /// A registry that passes every offline check once a plugin from
+    /// `weather` is added.
+    fn registry() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("registry.yaml"),
+            "name: Test\ndescription: Fixture\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join(publishers::FILE),
+            "- id: example\n  name: Example\n  domain: example.com\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("icon.svg"), ICON).unwrap();
+        std::fs::create_dir(root.join("manifests")).unwrap();
+        std::fs::write(root.join("manifests/weather.json"), MANIFEST).unwrap();
+        std::fs::create_dir(root.join("plugins")).unwrap();
+        dir
+    }
+
+    fn write_plugin(root: &Path, name: &str, entry: &Value) {
+        let yaml = serde_yaml::to_string(entry).unwrap();
+        std::fs::write(root.join(format!("plugins/{name}.yaml")), yaml).unwrap();
+    }
+
+    fn weather(root: &Path) -> Value {
+        json!({
+            "id": "com.example.weather",
+            "name": "Weather",
+            "version": "1.0.0",
+            "description": "Forecasts",
+            "author": "Example",
+            "image": "example/weather:1.0.0",
+            "image_digest": format!("sha256:{}", "a".repeat(64)),
+            "manifest_url": format!("file://{}", root.join("manifests/weather.json").display()),
+            "manifest_sha256": format!("{:x}", Sha256::digest(MANIFEST)),
+            "status": "active",
+            "icon": "icon.svg",
+            "publisher": "example"
+        })
+    }
+
+    fn lint(root: &Path) -> Vec<&'static str> {
+        let mut linter = Linter::new(true).unwrap();
+        linter.lint_registry(root).unwrap();
+        linter.diagnostics.iter().map(|d| d.code).collect()
+    }
+
+    /// Lint the fixture registry with `change` applied to the plugin entry.
+    fn lint_entry(change: impl FnOnce(&mut Value)) -> Vec<&'static str> {
+        let dir = registry();
+        let mut entry = weather(dir.path());
+        change(&mut entry);
+        write_plugin(dir.path(), "weather", &entry);
+        lint(dir.path())
+    }
+
+    #[test]
+    fn valid_registry_is_clean() {
+        assert_eq!(lint_entry(|_| {}), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn registry_metadata_rules() {
+        let dir = registry();
+        write_plugin(dir.path(), "weather", &weather(dir.path()));
+        std::fs::remove_file(dir.path().join("registry.yaml")).unwrap();
+        std::fs::write(dir.path().join("registry.json"), "{}").unwrap();
+        assert_eq!(
+            lint(dir.path()),
+            ["missing-registry-metadata", "deprecated-file"]
+        );
+    }
+
+    #[test]
+    fn entry_must_be_yaml_and_match_the_schema() {
+        let dir = registry();
+        std::fs::write(dir.path().join("plugins/weather.yaml"), "id: [unclosed").unwrap();
+        assert_eq!(lint(dir.path()), ["invalid-yaml"]);
+
+        let codes = lint_entry(|e| {
+            e.as_object_mut().unwrap().remove("name");
+        });
+        assert_eq!(codes, ["schema"]);
+    }
+
+    #[test]
+    fn ids_must_be_unique() {
+        let dir = registry();
+        let entry = weather(dir.path());
+        write_plugin(dir.path(), "weather", &entry);
+        write_plugin(dir.path(), "weather-copy", &entry);
+        assert_eq!(lint(dir.path()), ["duplicate-id"]);
+    }
+
+    #[test]
+    fn build_managed_fields_are_flagged() {
+        let codes = lint_entry(|e| {
+            e["install_count"] = json!(5);
+            e["verified"] = json!(true);
+        });
+        assert_eq!(codes, ["build-managed-field", "build-managed-field"]);
+    }
+
+    #[test]
+    fn publisher_must_exist_and_cover_the_id() {
+        assert_eq!(
+            lint_entry(|e| e["publisher"] = json!("nobody")),
+            ["invalid-publisher"]
+        );
+
+        let dir = registry();
+        std::fs::write(
+            dir.path().join(publishers::FILE),
+            "- id: example\n  name: Example\n  domain: example.org\n",
+        )
+        .unwrap();
+        write_plugin(dir.path(), "weather", &weather(dir.path()));
+        assert_eq!(lint(dir.path()), ["invalid-publisher"]);
+    }
+
+    #[test]
+    fn version_must_be_semver() {
+        // Passes the schema's X.Y.Z prefix check but not semver
+        let codes = lint_entry(|e| e["version"] = json!("1.0.0.1"));
+        assert!(codes.contains(&"non-semver-version"), "{codes:?}");
+        assert!(!codes.contains(&"schema"), "{codes:?}");
+    }
+
+    #[test]
+    fn icon_rules() {
+        let codes = lint_entry(|e| {
+            e.as_object_mut().unwrap().remove("icon");
+        });
+        assert_eq!(codes, ["missing-icon"]);
+
+        let codes = lint_entry(|e| e["icon"] = json!("missing.png"));
+        assert_eq!(codes, ["invalid-asset"]);
+    }
+
+    #[test]
+    fn image_digest_is_expected() {
+        let codes = lint_entry(|e| {
+            e.as_object_mut().unwrap().remove("image_digest");
+        });
+        assert_eq!(codes, ["missing-image-digest"]);
+    }
+
+    #[test]
+    fn manifest_rules() {
+        let codes = lint_entry(|e| e["manifest_url"] = json!("file:///nonexistent/manifest.json"));
+        assert_eq!(codes, ["broken-manifest-url"]);
+
+        let codes = lint_entry(|e| e["manifest_sha256"] = json!("0".repeat(64)));
+        assert_eq!(codes, ["manifest-hash-mismatch"]);
+
+        let codes = lint_entry(|e| e["version"] = json!("1.0.1"));
+        assert_eq!(codes, ["manifest-mismatch"]);
+
+        let dir = registry();
+        std::fs::write(dir.path().join("manifests/weather.json"), "not json").unwrap();
+        let mut entry = weather(dir.path());
+        entry.as_object_mut().unwrap().remove("manifest_sha256");
+        write_plugin(dir.path(), "weather", &entry);
+        assert_eq!(lint(dir.path()), ["invalid-manifest"]);
+    }
+
+    #[test]
+    fn manifest_url_may_be_relative_to_the_root() {
+        let codes = lint_entry(|e| e["manifest_url"] = json!("manifests/weather.json"));
+        // The schema wants a URI, but the manifest itself still resolves
+        assert_eq!(codes, ["schema"]);
+    }
+
+    #[test]
+    fn collections_must_list_known_packages() {
+        let dir = registry();
+        write_plugin(dir.path(), "weather", &weather(dir.path()));
+        let collections = dir.path().join(collections::FILE);
+
+        std::fs::write(
+            &collections,
+            "- id: starter\n  name: Starter\n  packages: [com.example.weather]\n",
+        )
+        .unwrap();
+        assert_eq!(lint(dir.path()), Vec::<&str>::new());
+
+        std::fs::write(
+            &collections,
+            "- id: starter\n  name: Starter\n  packages: [com.example.missing]\n",
+        )
+        .unwrap();
+        assert_eq!(lint(dir.path()), ["invalid-collection"]);
+    }
+
+    #[test]
+    fn deprecated_fields_come_from_the_schema() {
+        let schema = json!({
+            "properties": {
+                "old": { "deprecated": true, "description": "use new" },
+                "new": {}
+            }
+        });
+        let entry = Entry {
+            file: "plugins/weather.yaml",
+            id: None,
+        };
+        let mut linter = Linter::new(true).unwrap();
+        linter.check_deprecated_fields(&entry, &json!({ "new": 1 }), &schema);
+        assert!(linter.diagnostics.is_empty());
+
+        linter.check_deprecated_fields(&entry, &json!({ "old": 1 }), &schema);
+        assert_eq!(linter.diagnostics[0].code, "deprecated-field");
+        assert_eq!(
+            linter.diagnostics[0].message,
+            "'old' is deprecated: use new"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub mod add;
 pub mod build;
 pub mod init;
+pub mod lint;
 pub mod publish;
 pub mod update;
 pub mod validate;
+pub mod yank;
@@ -1,6 +1,11 @@
 use anyhow::{bail, Context, Result};
+use base64::Engine;
+use serde_json::{json, Value};
 use std::path::Path;
 use std::process::Command;
+use std::time::Duration;
+
+const GITHUB_API: &str = "https://api.github.com";
 
 pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
     // Verify the package file exists
@@ -8,41 +13,10 @@ pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
         bail!("Package file not found: {}", package_path.display());
     }
 
-    // Determine if it's a plugin or extension from the path
-    let parent = package_path
-        .parent()
-        .and_then(|p| p.file_name())
-        .and_then(|n| n.to_str())
-        .unwrap_or("");
-    let kind = match parent {
-        "plugins" => "plugins",
-        "extensions" => "extensions",
-        _ => {
-            bail!(
-                "Package must be in a plugins/ or extensions/ directory. Got: {}",
-                package_path.display()
-            );
-        }
-    };
-
     // Read package metadata for branch naming
     let content = std::fs::read_to_string(package_path)
         .context("Failed to read package file")?;
-    let meta: serde_json::Value =
-        serde_yaml::from_str(&content).context("Invalid package YAML")?;
-    let package_id = meta
-        .get("id")
-        .and_then(|v| v.as_str())
-        .context("Package missing 'id' field")?;
-    let package_version = meta
-        .get("version")
-        .and_then(|v| v.as_str())
-        .unwrap_or("0.0.0");
-
-    let file_name = package_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .context("Invalid package file name")?;
+    let submission = Submission::new(package_path, &content)?;
 
     // Create temp directory and clone
     let temp_dir = tempdir()?;
@@ -52,9 +26,9 @@ pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
     run_git(&["clone", registry_url, &clone_path.to_string_lossy()])?;
 
     // Copy package into the clone
-    let dest_dir = clone_path.join(kind);
+    let dest_dir = clone_path.join(submission.kind);
     std::fs::create_dir_all(&dest_dir)?;
-    let dest_file = dest_dir.join(file_name);
+    let dest_file = dest_dir.join(&submission.file_name);
     std::fs::copy(package_path, &dest_file)
         .context("Failed to copy package file")?;
 
@@ -63,7 +37,7 @@ pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
     crate::commands::validate::run(&clone_path)?;
 
     // Create branch (index.json is built by CI after merge, not here)
-    let branch_name = format!("add/{package_id}-{package_version}");
+    let branch_name = submission.branch_name();
     println!("Creating branch: {branch_name}");
     run_git_in(
         &clone_path,
@@ -71,19 +45,149 @@ pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
     )?;
 
     // Stage and commit — only the YAML file
-    run_git_in(&clone_path, &["add", &format!("{kind}/{file_name}")])?;
-    run_git_in(
-        &clone_path,
-        &[
-            "commit",
-            "-m",
-            &format!("Add {kind}/{package_id} {package_version}"),
-        ],
-    )?;
+    run_git_in(&clone_path, &["add", &submission.path()])?;
+    run_git_in(&clone_path, &["commit", "-m", &submission.commit_message()])?;
+
+    let title = submission.title();
+    let body = submission.body();
+
+    // With a token, open the PR through the GitHub API, from a fork when
+    // we can't push to the registry itself
+    let token = ["GITHUB_TOKEN", "GH_TOKEN"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|t| !t.is_empty()));
+    match (github_repo(registry_url), token) {
+        (Some((owner, repo)), Some(token)) => {
+            let github = GitHub::new(token)?;
+            let url = github.open_pull_request(
+                &clone_path,
+                &owner,
+                &repo,
+                &branch_name,
+                &title,
+                &body,
+            )?;
+            println!("Pull request created: {url}");
+        }
+        _ => push_and_open_with_gh(&clone_path, &branch_name, &title, &body)?,
+    }
+
+    Ok(())
+}
+
+/// The package file being published, and how its branch, commit, and pull
+/// request describe it.
+struct Submission {
+    /// `plugins` or `extensions`, from the directory the file is in.
+    kind: &'static str,
+    file_name: String,
+    id: String,
+    version: String,
+}
+
+impl Submission {
+    fn new(package_path: &Path, content: &str) -> Result<Self> {
+        // Determine if it's a plugin or extension from the path
+        let parent = package_path
+            .parent()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or("");
+        let kind = match parent {
+            "plugins" => "plugins",
+            "extensions" => "extensions",
+            _ => {
+                bail!(
+                    "Package must be in a plugins/ or extensions/ directory. Got: {}",
+                    package_path.display()
+                );
+            }
+        };
 
+        let meta: Value = serde_yaml::from_str(content).context("Invalid package YAML")?;
+        let id = meta
+            .get("id")
+            .and_then(|v| v.as_str())
+            .context("Package missing 'id' field")?;
+        let version = meta
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("0.0.0");
+
+        let file_name = package_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .context("Invalid package file name")?;
+
+        Ok(Self {
+            kind,
+            file_name: file_name.to_string(),
+            id: id.to_string(),
+            version: version.to_string(),
+        })
+    }
+
+    /// Path of the package file within the registry.
+    fn path(&self) -> String {
+        format!("{}/{}", self.kind, self.file_name)
+    }
+
+    fn branch_name(&self) -> String {
+        format!("add/{}-{}", self.id, self.version)
+    }
+
+    fn commit_message(&self) -> String {
+        format!("Add {}/{} {}", self.kind, self.id, self.version)
+    }
+
+    fn title(&self) -> String {
+        format!("Add {} {}", self.id, self.version)
+    }
+
+    fn body(&self) -> String {
+        format!(
+            "Adds `{}` to the registry.\n\n\
+             - **ID**: {}\n\
+             - **Version**: {}\n\
+             - **Type**: {}",
+            self.path(),
+            self.id,
+            self.version,
+            self.kind
+        )
+    }
+}
+
+/// Body of the GitHub API request that opens a pull request from
+/// `head_owner`'s branch, which is a fork unless it is `owner`.
+fn pull_request(
+    owner: &str,
+    head_owner: &str,
+    branch_name: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Value {
+    let head = if head_owner == owner {
+        branch_name.to_string()
+    } else {
+        format!("{head_owner}:{branch_name}")
+    };
+    json!({
+        "title": title,
+        "body": body,
+        "head": head,
+        "base": base,
+        "maintainer_can_modify": true,
+    })
+}
+
+/// Push the branch to the registry and open a PR with the GitHub CLI, if
+/// installed.
+fn push_and_open_with_gh(clone_path: &Path, branch_name: &str, title: &str, body: &str) -> Result<()> {
     // Push
     println!("Pushing branch...");
-    run_git_in(&clone_path, &["push", "-u", "origin", &branch_name])?;
+    run_git_in(clone_path, &["push", "-u", "origin", branch_name])?;
 
     // Try to create a PR via gh CLI
     if gh_available() {
@@ -93,16 +197,11 @@ pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
                 "pr",
                 "create",
                 "--title",
-                &format!("Add {package_id} {package_version}"),
+                title,
                 "--body",
-                &format!(
-                    "Adds `{kind}/{file_name}` to the registry.\n\n\
-                     - **ID**: {package_id}\n\
-                     - **Version**: {package_version}\n\
-                     - **Type**: {kind}"
-                ),
+                body,
             ])
-            .current_dir(&clone_path)
+            .current_dir(clone_path)
             .output();
 
         match pr_result {
@@ -113,7 +212,7 @@ pub fn run(registry_url: &str, package_path: &Path) -> Result<()> {
                 // Enable auto-merge so it merges once checks pass
                 let merge_result = Command::new("gh")
                     .args(["pr", "merge", "--auto", "--squash", "--delete-branch", &url])
-                    .current_dir(&clone_path)
+                    .current_dir(clone_path)
                     .output();
                 match merge_result {
                     Ok(o) if o.status.success() => {
@@ -183,3 +282,235 @@ fn gh_available() -> bool {
         .map(|o| o.status.success())
         .unwrap_or(false)
 }
+
+/// `(owner, repo)` of a GitHub repository URL, in HTTPS or SSH form.
+fn github_repo(url: &str) -> Option<(String, String)> {
+    let path = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("git@github.com:"))
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
+
+struct GitHub {
+    http: reqwest::blocking::Client,
+    token: String,
+}
+
+impl GitHub {
+    fn new(token: String) -> Result<Self> {
+        let http = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("nexus-registry/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self { http, token })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str, body: Option<Value>) -> Result<Value> {
+        let mut request = self
+            .http
+            .request(method.clone(), format!("{GITHUB_API}{path}"))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(body) = body {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body.to_string());
+        }
+        let resp = request
+            .send()
+            .with_context(|| format!("GitHub API request {method} {path} failed"))?;
+        let status = resp.status();
+        let text = resp.text().unwrap_or_default();
+        if !status.is_success() {
+            let message = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(str::to_string))
+                .unwrap_or(text);
+            bail!("GitHub API {method} {path} returned {status}: {message}");
+        }
+        serde_json::from_str(&text).context("Invalid GitHub API response")
+    }
+
+    /// Push the committed branch and open a PR against the registry's default
+    /// branch. Without push access to the registry, the branch goes to the
+    /// user's fork, which is created if needed.
+    fn open_pull_request(
+        &self,
+        clone_path: &Path,
+        owner: &str,
+        repo: &str,
+        branch_name: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<String> {
+        let login = self.request(reqwest::Method::GET, "/user", None)?["login"]
+            .as_str()
+            .context("GitHub API returned no user login")?
+            .to_string();
+        let upstream = self.request(reqwest::Method::GET, &format!("/repos/{owner}/{repo}"), None)?;
+        let base = upstream["default_branch"].as_str().unwrap_or("main").to_string();
+        let can_push = upstream["permissions"]["push"].as_bool().unwrap_or(false);
+
+        let (head_owner, head_repo) = if can_push {
+            (owner.to_string(), repo.to_string())
+        } else {
+            println!("Forking {owner}/{repo}...");
+            let fork =
+                self.request(reqwest::Method::POST, &format!("/repos/{owner}/{repo}/forks"), None)?;
+            let fork_name = fork["name"].as_str().unwrap_or(repo).to_string();
+            self.wait_for_repo(&login, &fork_name)?;
+            (login.clone(), fork_name)
+        };
+
+        println!("Pushing branch to {head_owner}/{head_repo}...");
+        self.push(clone_path, &head_owner, &head_repo, branch_name)?;
+
+        println!("Creating pull request...");
+        let pr = self.request(
+            reqwest::Method::POST,
+            &format!("/repos/{owner}/{repo}/pulls"),
+            Some(pull_request(
+                owner,
+                &head_owner,
+                branch_name,
+                &base,
+                title,
+                body,
+            )),
+        )?;
+        pr["html_url"]
+            .as_str()
+            .map(str::to_string)
+            .context("GitHub API returned no pull request URL")
+    }
+
+    /// Forks are created asynchronously; wait until the fork can be pushed to.
+    fn wait_for_repo(&self, owner: &str, repo: &str) -> Result<()> {
+        for _ in 0..30 {
+            let path = format!("/repos/{owner}/{repo}/branches");
+            if matches!(self.request(reqwest::Method::GET, &path, None), Ok(Value::Array(b)) if !b.is_empty()) {
+                return Ok(());
+            }
+            std::thread::sleep(Duration::from_secs(2));
+        }
+        bail!("Fork {owner}/{repo} was not ready in time; try publishing again")
+    }
+
+    /// Push with the token in an auth header, so it never appears in a remote
+    /// URL or in error messages.
+    fn push(&self, clone_path: &Path, owner: &str, repo: &str, branch_name: &str) -> Result<()> {
+        let remote = format!("https://github.com/{owner}/{repo}.git");
+        let credentials = base64::engine::general_purpose::STANDARD
+            .encode(format!("x-access-token:{}", self.token));
+        let header =
+            format!("http.https://github.com/.extraheader=Authorization: Basic {credentials}");
+        let output = Command::new("git")
+            .args(["-c", &header, "push", &remote, &format!("HEAD:refs/heads/{branch_name}")])
+            .current_dir(clone_path)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr)
+                .replace(&self.token, "***")
+                .replace(&credentials, "***");
+            bail!("git push to {remote} failed: {}", stderr.trim());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = "\
+id: com.example.weather
+name: Weather
+version: 1.2.0
+";
+
+    fn submission(path: &str) -> Result<Submission> {
+        Submission::new(Path::new(path), PACKAGE)
+    }
+
+    #[test]
+    fn submission_describes_the_package() {
+        let s = submission("registry/plugins/weather.yaml").unwrap();
+        assert_eq!(s.path(), "plugins/weather.yaml");
+        assert_eq!(s.branch_name(), "add/com.example.weather-1.2.0");
+        assert_eq!(s.commit_message(), "Add plugins/com.example.weather 1.2.0");
+        assert_eq!(s.title(), "Add com.example.weather 1.2.0");
+        assert_eq!(
+            s.body(),
+            "Adds `plugins/weather.yaml` to the registry.\n\n\
+             - **ID**: com.example.weather\n\
+             - **Version**: 1.2.0\n\
+             - **Type**: plugins"
+        );
+
+        let s = submission("extensions/weather.yaml").unwrap();
+        assert_eq!(s.path(), "extensions/weather.yaml");
+    }
+
+    #[test]
+    fn submission_needs_a_package_directory_and_id() {
+        let err = submission("weather.yaml").err().unwrap();
+        assert!(err.to_string().contains("plugins/ or extensions/"));
+
+        let err = Submission::new(Path::new("plugins/weather.yaml"), "name: Weather\n")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "Package missing 'id' field");
+
+        let s = Submission::new(
+            Path::new("plugins/weather.yaml"),
+            "id: com.example.weather\n",
+        )
+        .unwrap();
+        assert_eq!(s.branch_name(), "add/com.example.weather-0.0.0");
+    }
+
+    #[test]
+    fn pull_request_heads_from_the_fork() {
+        let pr = pull_request("nexus", "nexus", "add/x-1.0.0", "main", "Add x", "body");
+        assert_eq!(
+            pr,
+            json!({
+                "title": "Add x",
+                "body": "body",
+                "head": "add/x-1.0.0",
+                "base": "main",
+                "maintainer_can_modify": true,
+            })
+        );
+
+        let pr = pull_request("nexus", "someone", "add/x-1.0.0", "main", "Add x", "body");
+        assert_eq!(pr["head"], "someone:add/x-1.0.0");
+    }
+
+    #[test]
+    fn github_repo_urls() {
+        for url in [
+            "https://github.com/nexus/registry",
+            "https://github.com/nexus/registry.git",
+            "git@github.com:nexus/registry.git",
+            "ssh://git@github.com/nexus/registry",
+        ] {
+            assert_eq!(
+                github_repo(url),
+                Some(("nexus".to_string(), "registry".to_string())),
+                "{url}"
+            );
+        }
+        assert_eq!(github_repo("https://gitlab.com/nexus/registry"), None);
+        assert_eq!(github_repo("https://github.com/nexus"), None);
+    }
+}
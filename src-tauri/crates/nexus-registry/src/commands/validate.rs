@@ -4,7 +4,8 @@ use serde_json::Value;
 use std::collections::HashSet;
 use std::path::Path;
 
-use crate::schema;
+use crate::publishers::{self, Publisher};
+use crate::{assets, collections, schema};
 
 pub fn run(path: &Path) -> Result<()> {
     println!("Validating registry at: {}", path.display());
@@ -12,6 +13,17 @@ pub fn run(path: &Path) -> Result<()> {
     let mut errors: Vec<String> = Vec::new();
     let mut plugin_ids: HashSet<String> = HashSet::new();
     let mut extension_ids: HashSet<String> = HashSet::new();
+    let http = assets::http_client()?;
+
+    // Entries may only claim known publishers, within their namespaces.
+    // If publishers.yaml itself is invalid, entries aren't checked against it.
+    let publishers = match publishers::load(path) {
+        Ok(list) => Some(list),
+        Err(e) => {
+            errors.push(format!("{e:#}"));
+            None
+        }
+    };
 
     // Validate plugins
     let plugin_schema = schema::plugin_schema();
@@ -21,10 +33,13 @@ pub fn run(path: &Path) -> Result<()> {
     let plugin_dir = path.join("plugins");
     if plugin_dir.exists() {
         errors.extend(validate_directory(
+            path,
             &plugin_dir,
             &plugin_validator,
             "plugin",
             &mut plugin_ids,
+            publishers.as_deref(),
+            &http,
         )?);
     }
 
@@ -36,13 +51,28 @@ pub fn run(path: &Path) -> Result<()> {
     let extension_dir = path.join("extensions");
     if extension_dir.exists() {
         errors.extend(validate_directory(
+            path,
             &extension_dir,
             &extension_validator,
             "extension",
             &mut extension_ids,
+            publishers.as_deref(),
+            &http,
         )?);
     }
 
+    // Collections may only list packages the registry has
+    match collections::load(path) {
+        Ok(list) => {
+            let package_ids: HashSet<String> =
+                plugin_ids.union(&extension_ids).cloned().collect();
+            for error in collections::check(&list, &package_ids) {
+                errors.push(format!("{}: {error}", collections::FILE));
+            }
+        }
+        Err(e) => errors.push(format!("{e:#}")),
+    }
+
     // Validate registry.yaml exists
     let registry_path = path.join("registry.yaml");
     if !registry_path.exists() {
@@ -65,10 +95,13 @@ pub fn run(path: &Path) -> Result<()> {
 }
 
 fn validate_directory(
+    root: &Path,
     dir: &Path,
     validator: &Validator,
     kind: &str,
     seen_ids: &mut HashSet<String>,
+    publishers: Option<&[Publisher]>,
+    http: &reqwest::blocking::Client,
 ) -> Result<Vec<String>> {
     let mut errors = Vec::new();
     let pattern = dir.join("*.yaml").to_string_lossy().to_string();
@@ -108,6 +141,15 @@ fn validate_directory(
                 ));
             }
         }
+
+        if let Some(error) = publishers.and_then(|p| publishers::check_entry(&yaml_value, p)) {
+            errors.push(format!("{file_name}: {error}"));
+        }
+
+        // Icons and screenshots must load and meet the size limits
+        for error in assets::check_entry(root, &yaml_value, http) {
+            errors.push(format!("{file_name}: {error}"));
+        }
     }
 
     Ok(errors)
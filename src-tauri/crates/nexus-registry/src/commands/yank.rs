@@ -0,0 +1,78 @@
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+pub struct YankArgs {
+    pub id: String,
+    pub version: String,
+    pub reason: Option<String>,
+    pub undo: bool,
+}
+
+pub fn run(args: YankArgs) -> Result<()> {
+    // Find the YAML file — try plugins/ first, then extensions/
+    let plugin_path = PathBuf::from("plugins").join(format!("{}.yaml", args.id));
+    let extension_path = PathBuf::from("extensions").join(format!("{}.yaml", args.id));
+
+    let path = if plugin_path.exists() {
+        plugin_path
+    } else if extension_path.exists() {
+        extension_path
+    } else {
+        bail!(
+            "No entry found for '{}'. Looked in plugins/ and extensions/",
+            args.id
+        );
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut entry: serde_json::Map<String, Value> =
+        serde_yaml::from_str(&content).context("Invalid YAML")?;
+
+    let mut yanked = match entry.remove("yanked") {
+        Some(Value::Array(list)) => list,
+        Some(_) => bail!("'yanked' in {} is not a list", path.display()),
+        None => Vec::new(),
+    };
+    let position = yanked
+        .iter()
+        .position(|y| y.get("version").and_then(|v| v.as_str()) == Some(&args.version));
+
+    if args.undo {
+        match position {
+            Some(i) => {
+                yanked.remove(i);
+            }
+            None => bail!("{} {} is not yanked", args.id, args.version),
+        }
+    } else {
+        let mut record = json!({ "version": args.version });
+        if let Some(reason) = &args.reason {
+            record["reason"] = Value::String(reason.clone());
+        }
+        match position {
+            // Re-yanking updates the reason
+            Some(i) => yanked[i] = record,
+            None => yanked.push(record),
+        }
+    }
+
+    if !yanked.is_empty() {
+        entry.insert("yanked".into(), Value::Array(yanked));
+    }
+
+    let yaml_content = serde_yaml::to_string(&entry)?;
+    std::fs::write(&path, &yaml_content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    if args.undo {
+        println!("Restored {} {} in {}", args.id, args.version, path.display());
+    } else {
+        println!("Yanked {} {} in {}", args.id, args.version, path.display());
+        if entry.get("version").and_then(|v| v.as_str()) == Some(&args.version) {
+            println!("  This is the listed version; clients won't offer it until a new version is published.");
+        }
+    }
+    Ok(())
+}
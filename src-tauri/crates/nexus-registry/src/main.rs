@@ -1,4 +1,7 @@
+mod assets;
+mod collections;
 mod commands;
+mod publishers;
 mod schema;
 
 use clap::{Parser, Subcommand};
@@ -27,6 +30,21 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
     },
+    /// Run validation plus network, image, and style checks for CI
+    Lint {
+        /// Path to the registry root (default: current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Print diagnostics as JSON on stdout
+        #[arg(long)]
+        json: bool,
+        /// Skip checks that need the network (manifest URLs, icons, images)
+        #[arg(long)]
+        offline: bool,
+        /// Fail on warnings as well as errors
+        #[arg(long)]
+        deny_warnings: bool,
+    },
     /// Compile YAML files into index.json
     Build {
         /// Path to the registry root (default: current directory)
@@ -62,7 +80,24 @@ enum Commands {
         #[arg(long)]
         status: Option<String>,
     },
-    /// Publish a package to a remote registry
+    /// Withdraw a published version so clients stop offering it
+    Yank {
+        /// Package ID (e.g. com.nexus.cookie-jar)
+        #[arg(long)]
+        id: String,
+        /// Version to withdraw
+        #[arg(long)]
+        version: String,
+        /// Why the version was withdrawn, shown to users running it
+        #[arg(long)]
+        reason: Option<String>,
+        /// Restore a yanked version instead
+        #[arg(long)]
+        undo: bool,
+    },
+    /// Publish a package to a remote registry by pull request. With
+    /// GITHUB_TOKEN set, a GitHub registry is forked if needed and the PR
+    /// opened through the API; otherwise the branch is pushed directly.
     Publish {
         /// Git URL of the target registry
         #[arg(long)]
@@ -161,6 +196,19 @@ fn main() -> anyhow::Result<()> {
     match cli.command {
         Commands::Init { path } => commands::init::run(&path),
         Commands::Validate { path } => commands::validate::run(&path),
+        Commands::Lint {
+            path,
+            json,
+            offline,
+            deny_warnings,
+        } => commands::lint::run(
+            &path,
+            &commands::lint::LintArgs {
+                json,
+                offline,
+                deny_warnings,
+            },
+        ),
         Commands::Build { path } => commands::build::run(&path),
         Commands::Add { kind } => match kind {
             AddKind::Plugin {
@@ -235,8 +283,17 @@ fn main() -> anyhow::Result<()> {
             manifest_url,
             status,
         }),
-        Commands::Publish { registry, package } => {
-            commands::publish::run(&registry, &package)
-        }
+        Commands::Yank {
+            id,
+            version,
+            reason,
+            undo,
+        } => commands::yank::run(commands::yank::YankArgs {
+            id,
+            version,
+            reason,
+            undo,
+        }),
+        Commands::Publish { registry, package } => commands::publish::run(&registry, &package),
     }
 }
@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use jsonschema::Validator;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::schema;
+
+/// Publisher identity records at the registry root.
+pub const FILE: &str = "publishers.yaml";
+
+/// Attestation file a publisher serves to prove control of its identity.
+const ATTESTATION_FILE: &str = "nexus-publisher.json";
+
+#[derive(Debug, Deserialize)]
+pub struct Publisher {
+    pub id: String,
+    pub name: String,
+    /// Domain the publisher serves `/.well-known/nexus-publisher.json` from.
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// GitHub user or organization whose `.github` repository holds
+    /// `nexus-publisher.json`.
+    #[serde(default)]
+    pub github: Option<String>,
+    /// Package ID prefixes the publisher may use. Defaults to the reversed
+    /// domain, or `io.github.<owner>`.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+}
+
+impl Publisher {
+    /// The domain or `github.com/<owner>` the publisher claims.
+    pub fn identity(&self) -> String {
+        match (&self.domain, &self.github) {
+            (Some(domain), _) => domain.clone(),
+            (None, Some(owner)) => format!("github.com/{owner}"),
+            (None, None) => String::new(),
+        }
+    }
+
+    pub fn namespaces(&self) -> Vec<String> {
+        if !self.namespaces.is_empty() {
+            return self.namespaces.clone();
+        }
+        match (&self.domain, &self.github) {
+            (Some(domain), _) => vec![domain.rsplit('.').collect::<Vec<_>>().join(".")],
+            (None, Some(owner)) => vec![format!("io.github.{}", owner.to_lowercase())],
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Whether `package_id` falls under one of the publisher's namespaces.
+    pub fn covers(&self, package_id: &str) -> bool {
+        self.namespaces()
+            .iter()
+            .any(|ns| package_id.starts_with(&format!("{ns}.")))
+    }
+
+    /// Fetch the publisher's attestation and check it names this publisher.
+    pub fn verify(&self, http: &reqwest::blocking::Client) -> std::result::Result<(), String> {
+        let url = match (&self.domain, &self.github) {
+            (Some(domain), _) => format!("https://{domain}/.well-known/{ATTESTATION_FILE}"),
+            (None, Some(owner)) => format!(
+                "https://raw.githubusercontent.com/{owner}/.github/HEAD/{ATTESTATION_FILE}"
+            ),
+            (None, None) => return Err("no domain or github identity".to_string()),
+        };
+        let resp = http.get(&url).send().map_err(|e| format!("{url}: {e}"))?;
+        if !resp.status().is_success() {
+            return Err(format!("{url} returned status {}", resp.status()));
+        }
+        let text = resp.text().map_err(|e| format!("{url}: {e}"))?;
+        let attestation: Value =
+            serde_json::from_str(&text).map_err(|e| format!("{url}: invalid JSON: {e}"))?;
+        match attestation.get("publisher").and_then(|v| v.as_str()) {
+            Some(id) if id == self.id => Ok(()),
+            Some(id) => Err(format!("{url} attests publisher '{id}', not '{}'", self.id)),
+            None => Err(format!("{url} has no 'publisher' field")),
+        }
+    }
+}
+
+/// Load `publishers.yaml` and check it against the schema. A registry
+/// without one has no publishers.
+pub fn load(root: &Path) -> Result<Vec<Publisher>> {
+    let path = root.join(FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {FILE}"))?;
+    let value: Value =
+        serde_yaml::from_str(&content).with_context(|| format!("Invalid YAML in {FILE}"))?;
+
+    let publishers_schema = schema::publishers_schema();
+    let validator =
+        Validator::new(&publishers_schema).context("Failed to compile publishers schema")?;
+    let errors: Vec<String> = validator
+        .iter_errors(&value)
+        .map(|e| format!("{}: {}", e.instance_path, e))
+        .collect();
+    if !errors.is_empty() {
+        anyhow::bail!("Validation failed for {FILE}: {}", errors.join("; "));
+    }
+
+    let publishers: Vec<Publisher> =
+        serde_json::from_value(value).with_context(|| format!("Invalid {FILE}"))?;
+    let mut seen = HashSet::new();
+    for publisher in &publishers {
+        if !seen.insert(publisher.id.as_str()) {
+            anyhow::bail!("{FILE}: duplicate publisher id '{}'", publisher.id);
+        }
+    }
+    Ok(publishers)
+}
+
+/// Problems with an entry's `publisher`: unknown publishers and package IDs
+/// outside the publisher's namespaces.
+pub fn check_entry(entry: &Value, publishers: &[Publisher]) -> Option<String> {
+    let publisher_id = entry.get("publisher").and_then(|v| v.as_str())?;
+    let package_id = entry.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    match publishers.iter().find(|p| p.id == publisher_id) {
+        None => Some(format!("unknown publisher '{publisher_id}'")),
+        Some(p) if !p.covers(package_id) => Some(format!(
+            "id is outside publisher '{publisher_id}' namespaces ({})",
+            p.namespaces().join(", ")
+        )),
+        Some(_) => None,
+    }
+}
@@ -44,7 +44,26 @@ pub fn plugin_schema() -> Value {
                 "format": "uri"
             },
             "icon": {
-                "type": "string"
+                "type": "string",
+                "description": "URL or path relative to the registry root; square PNG, JPEG, WebP, or SVG"
+            },
+            "screenshots": {
+                "type": "array",
+                "maxItems": 8,
+                "items": {
+                    "type": "object",
+                    "required": ["url"],
+                    "properties": {
+                        "url": {
+                            "type": "string",
+                            "description": "URL or path relative to the registry root"
+                        },
+                        "caption": { "type": "string" },
+                        "width": { "type": "integer", "description": "Set by build" },
+                        "height": { "type": "integer", "description": "Set by build" }
+                    },
+                    "additionalProperties": false
+                }
             },
             "image": {
                 "type": "string",
@@ -70,6 +89,57 @@ pub fn plugin_schema() -> Value {
             "categories": {
                 "type": "array",
                 "items": { "type": "string" }
+            },
+            "build_context": {
+                "type": "string",
+                "description": "Directory with a Dockerfile, for local registries"
+            },
+            "channel": {
+                "type": "string",
+                "enum": ["stable", "beta"]
+            },
+            "release_notes": {
+                "type": "string"
+            },
+            "changelog_url": {
+                "type": "string"
+            },
+            "publisher": {
+                "type": "string",
+                "description": "Publisher id from publishers.yaml"
+            },
+            "verified": {
+                "type": "boolean",
+                "description": "Set by build when the publisher's identity checks out"
+            },
+            "verified_identity": {
+                "type": "string",
+                "description": "Set by build"
+            },
+            "install_count": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Set by build from stats.yaml"
+            },
+            "yanked": {
+                "type": "array",
+                "description": "Withdrawn versions, which clients no longer offer",
+                "items": {
+                    "type": "object",
+                    "required": ["version"],
+                    "properties": {
+                        "version": { "type": "string", "pattern": "^\\d+\\.\\d+\\.\\d+" },
+                        "reason": { "type": "string" }
+                    },
+                    "additionalProperties": false
+                }
+            },
+            "author_public_key": {
+                "type": "string",
+                "description": "Base64 Ed25519 key; requires manifest_signature"
+            },
+            "manifest_signature": {
+                "type": "string"
             }
         },
         "additionalProperties": false
@@ -141,6 +211,42 @@ pub fn extension_schema() -> Value {
             "categories": {
                 "type": "array",
                 "items": { "type": "string" }
+            },
+            "release_notes": {
+                "type": "string"
+            },
+            "changelog_url": {
+                "type": "string"
+            },
+            "publisher": {
+                "type": "string",
+                "description": "Publisher id from publishers.yaml"
+            },
+            "verified": {
+                "type": "boolean",
+                "description": "Set by build when the publisher's identity checks out"
+            },
+            "verified_identity": {
+                "type": "string",
+                "description": "Set by build"
+            },
+            "install_count": {
+                "type": "integer",
+                "minimum": 0,
+                "description": "Set by build from stats.yaml"
+            },
+            "yanked": {
+                "type": "array",
+                "description": "Withdrawn versions, which clients no longer offer",
+                "items": {
+                    "type": "object",
+                    "required": ["version"],
+                    "properties": {
+                        "version": { "type": "string", "pattern": "^\\d+\\.\\d+\\.\\d+" },
+                        "reason": { "type": "string" }
+                    },
+                    "additionalProperties": false
+                }
             }
         },
         "additionalProperties": false
@@ -180,16 +286,107 @@ pub fn registry_schema() -> Value {
             "extensions": {
                 "type": "array",
                 "items": { "$ref": "#/definitions/extension" }
+            },
+            "collections": {
+                "type": "array",
+                "items": { "$ref": "#/definitions/collection" }
             }
         },
         "additionalProperties": false,
         "definitions": {
             "plugin": plugin_schema(),
-            "extension": extension_schema()
+            "extension": extension_schema(),
+            "collection": collection_schema()
         }
     })
 }
 
+pub fn collection_schema() -> Value {
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Nexus Collection",
+        "type": "object",
+        "required": ["id", "name", "packages"],
+        "properties": {
+            "id": {
+                "type": "string",
+                "pattern": "^[a-z0-9][a-z0-9-]*$"
+            },
+            "name": {
+                "type": "string",
+                "minLength": 1
+            },
+            "description": {
+                "type": "string"
+            },
+            "packages": {
+                "type": "array",
+                "minItems": 1,
+                "description": "Plugin and extension IDs, in display order",
+                "items": { "type": "string" }
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
+/// Schema for `publishers.yaml`: publisher identities entries can claim.
+pub fn publishers_schema() -> Value {
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Nexus Publishers",
+        "type": "array",
+        "items": {
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "pattern": "^[a-z0-9][a-z0-9-]*$"
+                },
+                "name": {
+                    "type": "string",
+                    "minLength": 1
+                },
+                "domain": {
+                    "type": "string",
+                    "pattern": "^[a-z0-9-]+(\\.[a-z0-9-]+)+$",
+                    "description": "Serves /.well-known/nexus-publisher.json"
+                },
+                "github": {
+                    "type": "string",
+                    "pattern": "^[A-Za-z0-9-]+$",
+                    "description": "User or organization whose .github repository holds nexus-publisher.json"
+                },
+                "namespaces": {
+                    "type": "array",
+                    "description": "Package ID prefixes; default to the reversed domain or io.github.<owner>",
+                    "items": { "type": "string" }
+                }
+            },
+            "oneOf": [
+                { "required": ["domain"] },
+                { "required": ["github"] }
+            ],
+            "additionalProperties": false
+        }
+    })
+}
+
+pub fn publishers_schema_pretty() -> String {
+    serde_json::to_string_pretty(&publishers_schema()).unwrap()
+}
+
+/// Schema for `collections.yaml`: a list of collections.
+pub fn collections_schema() -> Value {
+    serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Nexus Collections",
+        "type": "array",
+        "items": collection_schema()
+    })
+}
+
 pub fn plugin_schema_pretty() -> String {
     serde_json::to_string_pretty(&plugin_schema()).unwrap()
 }
@@ -198,6 +395,10 @@ pub fn extension_schema_pretty() -> String {
     serde_json::to_string_pretty(&extension_schema()).unwrap()
 }
 
+pub fn collections_schema_pretty() -> String {
+    serde_json::to_string_pretty(&collections_schema()).unwrap()
+}
+
 pub fn registry_schema_pretty() -> String {
     serde_json::to_string_pretty(&registry_schema()).unwrap()
 }
@@ -15,4 +15,4 @@ pub mod store;
 pub mod types;
 
 pub use store::ApiKeyStore;
-pub use types::ApiKey;
+pub use types::{ApiKey, ApiKeyScope, ApiKeyUsage, ApiKeyValidation};
@@ -21,17 +21,20 @@
 //! - **Constant-time validation**: Hash comparison uses XOR-accumulation to prevent
 //!   timing side-channel attacks. See [`constant_time_eq`].
 //!
-//! - **Atomic persistence**: Writes use temp-file + rename to prevent partial writes
-//!   on crash (same pattern as [`crate::oauth::OAuthStore`]).
+//! - **Encrypted persistence**: Both files are encrypted at rest with the key in the
+//!   OS keychain, and written via temp-file + rename to prevent partial writes on
+//!   crash (same as [`crate::oauth::OAuthStore`]). See [`StoreCipher`].
 
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use sha2::{Digest, Sha256};
 
-use super::types::ApiKey;
+use super::types::{ApiKey, ApiKeyScope, ApiKeyUsage, ApiKeyValidation};
+use crate::secrets::StoreCipher;
 
 /// Fixed prefix for all Nexus API keys. Enables O(1) routing in the auth middleware
 /// (see module docs).
@@ -92,6 +95,7 @@ pub struct ApiKeyStore {
     keys: Arc<RwLock<Vec<ApiKey>>>,
     keys_path: PathBuf,
     default_key_path: PathBuf,
+    cipher: Arc<StoreCipher>,
 }
 
 impl ApiKeyStore {
@@ -99,29 +103,29 @@ impl ApiKeyStore {
     pub fn load(data_dir: &Path) -> Self {
         let keys_path = data_dir.join("mcp_api_keys.json");
         let default_key_path = data_dir.join("mcp_default_key");
+        let cipher = Arc::new(StoreCipher::load(data_dir));
 
-        let keys: Vec<ApiKey> = if keys_path.exists() {
-            std::fs::read_to_string(&keys_path)
-                .ok()
-                .and_then(|s| serde_json::from_str(&s).ok())
-                .unwrap_or_default()
-        } else {
-            Vec::new()
-        };
+        let keys: Vec<ApiKey> = cipher
+            .read(&keys_path)
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        // Encrypts a default key file written before stores were encrypted
+        let _ = cipher.read(&default_key_path);
 
         let store = Self {
             keys: Arc::new(RwLock::new(keys)),
             keys_path,
             default_key_path,
+            cipher,
         };
 
         // Create default key on first launch
         if store.keys.read().unwrap().is_empty() {
-            let (key, raw) = store.generate_inner("Default");
+            let (key, raw) = store.generate_inner("Default", None);
             store.keys.write().unwrap().push(key);
             store.save();
             // Persist the raw default key so the UI can display it
-            let _ = std::fs::write(&store.default_key_path, &raw);
+            store.save_default_raw(&raw);
             log::info!("Generated default MCP API key");
         }
 
@@ -129,14 +133,14 @@ impl ApiKeyStore {
     }
 
     /// Generate a new API key. Returns the stored key + raw key string (shown once).
-    pub fn generate(&self, name: &str) -> (ApiKey, String) {
-        let (key, raw) = self.generate_inner(name);
+    pub fn generate(&self, name: &str, expires_at: Option<DateTime<Utc>>) -> (ApiKey, String) {
+        let (key, raw) = self.generate_inner(name, expires_at);
         self.keys.write().unwrap().push(key.clone());
         self.save();
         (key, raw)
     }
 
-    fn generate_inner(&self, name: &str) -> (ApiKey, String) {
+    fn generate_inner(&self, name: &str, expires_at: Option<DateTime<Utc>>) -> (ApiKey, String) {
         let raw = generate_raw_key();
         let key = ApiKey {
             id: uuid::Uuid::new_v4().to_string(),
@@ -145,23 +149,108 @@ impl ApiKeyStore {
             prefix: raw[..8].to_string(),
             created_at: Utc::now(),
             last_used_at: None,
+            last_used_ip: None,
+            call_count: 0,
+            expires_at,
+            rotated_at: None,
+            scope: ApiKeyScope::default(),
         };
         (key, raw)
     }
 
-    /// Validate a raw key against stored hashes. Returns the matching [`ApiKey`] if valid.
+    /// Validate a raw key against stored hashes. Returns the matching [`ApiKey`] if
+    /// valid and unexpired. See [`Self::check`] to tell expired keys apart.
+    pub fn validate(&self, raw_key: &str) -> Option<ApiKey> {
+        match self.check(raw_key, None) {
+            ApiKeyValidation::Valid(key) => Some(key),
+            ApiKeyValidation::Expired(_) | ApiKeyValidation::Invalid => None,
+        }
+    }
+
+    /// Check a raw key against stored hashes.
     ///
     /// Uses constant-time comparison on SHA-256 digests to prevent timing side-channels.
-    /// Updates `last_used_at` on success (for audit/display in the UI).
-    pub fn validate(&self, raw_key: &str) -> Option<ApiKey> {
+    /// Updates `last_used_at` and `last_used_ip` on success (for audit/display in the
+    /// UI); expired keys are not counted as used.
+    pub fn check(&self, raw_key: &str, peer: Option<IpAddr>) -> ApiKeyValidation {
         let hash = hash_key(raw_key);
         let mut keys = self.keys.write().unwrap();
-        let key = keys.iter_mut().find(|k| constant_time_eq(&k.key_hash, &hash))?;
+        let Some(key) = keys.iter_mut().find(|k| constant_time_eq(&k.key_hash, &hash)) else {
+            return ApiKeyValidation::Invalid;
+        };
+        if key.is_expired() {
+            return ApiKeyValidation::Expired(key.clone());
+        }
+        key.last_used_at = Some(Utc::now());
+        if let Some(ip) = peer {
+            key.last_used_ip = Some(ip.to_string());
+        }
+        let result = key.clone();
+        drop(keys);
+        self.save();
+        ApiKeyValidation::Valid(result)
+    }
+
+    /// Count a tool call made with the key `id`.
+    pub fn record_call(&self, id: &str) {
+        let mut keys = self.keys.write().unwrap();
+        let Some(key) = keys.iter_mut().find(|k| k.id == id) else {
+            return;
+        };
+        key.call_count += 1;
         key.last_used_at = Some(Utc::now());
+        drop(keys);
+        self.save();
+    }
+
+    /// Usage summary for every key, least recently used first.
+    pub fn usage(&self) -> Vec<ApiKeyUsage> {
+        let mut usage: Vec<ApiKeyUsage> = self.keys.read().unwrap().iter().map(ApiKeyUsage::from).collect();
+        usage.sort_by(|a, b| b.idle_days.cmp(&a.idle_days).then_with(|| a.last_used_at.cmp(&b.last_used_at)));
+        usage
+    }
+
+    /// Replace a key's secret, keeping its ID, name, and expiry. The old secret
+    /// stops working immediately. Returns the updated key + new raw key (shown
+    /// once), or `None` if no key has this ID.
+    pub fn rotate(&self, id: &str) -> Option<(ApiKey, String)> {
+        let raw = generate_raw_key();
+        let mut keys = self.keys.write().unwrap();
+        let key = keys.iter_mut().find(|k| k.id == id)?;
+        key.key_hash = hash_key(&raw);
+        key.prefix = raw[..8].to_string();
+        key.rotated_at = Some(Utc::now());
         let result = key.clone();
         drop(keys);
         self.save();
-        Some(result)
+        if result.name == "Default" {
+            self.save_default_raw(&raw);
+        }
+        Some((result, raw))
+    }
+
+    /// Replace a key's tool scope. Returns false if no key has this ID.
+    pub fn set_scope(&self, id: &str, scope: ApiKeyScope) -> bool {
+        let mut keys = self.keys.write().unwrap();
+        let Some(key) = keys.iter_mut().find(|k| k.id == id) else {
+            return false;
+        };
+        key.scope = scope;
+        drop(keys);
+        self.save();
+        true
+    }
+
+    /// Set or clear a key's expiry. Returns false if no key has this ID.
+    pub fn set_expiry(&self, id: &str, expires_at: Option<DateTime<Utc>>) -> bool {
+        let mut keys = self.keys.write().unwrap();
+        let Some(key) = keys.iter_mut().find(|k| k.id == id) else {
+            return false;
+        };
+        key.expires_at = expires_at;
+        drop(keys);
+        self.save();
+        true
     }
 
     /// Revoke (remove) an API key by ID.
@@ -177,6 +266,11 @@ impl ApiKeyStore {
         removed
     }
 
+    /// Look up a key by ID.
+    pub fn get(&self, id: &str) -> Option<ApiKey> {
+        self.keys.read().unwrap().iter().find(|k| k.id == id).cloned()
+    }
+
     /// List all keys (no raw values).
     pub fn list(&self) -> Vec<ApiKey> {
         self.keys.read().unwrap().clone()
@@ -184,8 +278,8 @@ impl ApiKeyStore {
 
     /// Get the raw default key for UI display. Returns None if deleted.
     pub fn get_default_raw(&self) -> Option<String> {
-        std::fs::read_to_string(&self.default_key_path)
-            .ok()
+        self.cipher
+            .read(&self.default_key_path)
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
     }
@@ -198,32 +292,28 @@ impl ApiKeyStore {
             keys.retain(|k| k.name != "Default");
         }
 
-        let (key, raw) = self.generate_inner("Default");
+        let (key, raw) = self.generate_inner("Default", None);
         self.keys.write().unwrap().push(key.clone());
         self.save();
-        let _ = std::fs::write(&self.default_key_path, &raw);
+        self.save_default_raw(&raw);
         (key, raw)
     }
 
     fn save(&self) {
         if let Ok(keys) = self.keys.read() {
             if let Ok(json) = serde_json::to_string_pretty(&*keys) {
-                let _ = atomic_write(&self.keys_path, json.as_bytes());
+                if let Err(e) = self.cipher.write(&self.keys_path, json.as_bytes()) {
+                    log::error!("Failed to save API keys: {}", e);
+                }
             }
         }
     }
-}
 
-/// Write atomically via temp-file + rename.
-///
-/// Guarantees that readers see either the old or new content, never a partial write.
-/// `rename(2)` is atomic on POSIX when src and dst are on the same filesystem
-/// (which they are — both are in the Tauri data directory).
-fn atomic_write(path: &Path, data: &[u8]) -> std::io::Result<()> {
-    let tmp = path.with_extension("tmp");
-    std::fs::write(&tmp, data)?;
-    std::fs::rename(&tmp, path)?;
-    Ok(())
+    fn save_default_raw(&self, raw: &str) {
+        if let Err(e) = self.cipher.write(&self.default_key_path, raw.as_bytes()) {
+            log::error!("Failed to save the default API key: {}", e);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -235,7 +325,7 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let store = ApiKeyStore::load(tmp.path());
 
-        let (key, raw) = store.generate("Test Key");
+        let (key, raw) = store.generate("Test Key", None);
         assert!(raw.starts_with("nxk_"));
         assert_eq!(raw.len(), 44); // "nxk_" + 40 chars
         assert_eq!(key.prefix, &raw[..8]);
@@ -259,7 +349,7 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let store = ApiKeyStore::load(tmp.path());
 
-        let (key, raw) = store.generate("Revoke Me");
+        let (key, raw) = store.generate("Revoke Me", None);
         assert!(store.validate(&raw).is_some());
 
         assert!(store.revoke(&key.id));
@@ -273,7 +363,7 @@ mod tests {
         let raw;
         {
             let store = ApiKeyStore::load(tmp.path());
-            let (_, r) = store.generate("Persist Test");
+            let (_, r) = store.generate("Persist Test", None);
             raw = r;
         }
 
@@ -303,13 +393,55 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let store = ApiKeyStore::load(tmp.path());
 
-        let (key, raw) = store.generate("Usage Test");
+        let (key, raw) = store.generate("Usage Test", None);
         assert!(key.last_used_at.is_none());
 
         let validated = store.validate(&raw).unwrap();
         assert!(validated.last_used_at.is_some());
     }
 
+    #[test]
+    fn check_records_peer_address() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let (key, raw) = store.generate("Peer", None);
+        let peer: IpAddr = "127.0.0.1".parse().unwrap();
+        assert!(matches!(store.check(&raw, Some(peer)), ApiKeyValidation::Valid(_)));
+        assert_eq!(store.get(&key.id).unwrap().last_used_ip.as_deref(), Some("127.0.0.1"));
+    }
+
+    #[test]
+    fn usage_counts_calls_and_orders_idle_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let (busy, _) = store.generate("Busy", None);
+        let (idle, _) = store.generate("Idle", None);
+        {
+            let mut keys = store.keys.write().unwrap();
+            let idle_key = keys.iter_mut().find(|k| k.id == idle.id).unwrap();
+            idle_key.created_at = Utc::now() - chrono::Duration::days(10);
+        }
+        store.record_call(&busy.id);
+        store.record_call(&busy.id);
+        store.record_call("missing");
+
+        let usage = store.usage();
+        assert_eq!(usage[0].id, idle.id);
+        assert_eq!(usage[0].idle_days, 10);
+        assert_eq!(usage[0].call_count, 0);
+        let busy_usage = usage.iter().find(|u| u.id == busy.id).unwrap();
+        assert_eq!(busy_usage.call_count, 2);
+        assert_eq!(busy_usage.idle_days, 0);
+        assert!(busy_usage.last_used_at.is_some());
+        assert_eq!(busy_usage.calls_per_day, 2.0);
+
+        // Counts survive a reload
+        let reloaded = ApiKeyStore::load(tmp.path());
+        assert_eq!(reloaded.get(&busy.id).unwrap().call_count, 2);
+    }
+
     #[test]
     fn regenerate_default_replaces_key() {
         let tmp = tempfile::tempdir().unwrap();
@@ -330,4 +462,95 @@ mod tests {
         let defaults: Vec<_> = store.list().into_iter().filter(|k| k.name == "Default").collect();
         assert_eq!(defaults.len(), 1);
     }
+
+    #[test]
+    fn expired_key_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let past = Utc::now() - chrono::Duration::minutes(1);
+        let (key, raw) = store.generate("Expired", Some(past));
+        assert!(store.validate(&raw).is_none());
+        assert!(matches!(store.check(&raw, None), ApiKeyValidation::Expired(k) if k.id == key.id));
+        // Expired keys are not counted as used
+        assert!(store.list().iter().find(|k| k.id == key.id).unwrap().last_used_at.is_none());
+    }
+
+    #[test]
+    fn set_expiry_extends_and_clears() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let (key, raw) = store.generate("Expiring", Some(Utc::now() - chrono::Duration::minutes(1)));
+        assert!(store.validate(&raw).is_none());
+
+        assert!(store.set_expiry(&key.id, Some(Utc::now() + chrono::Duration::days(1))));
+        assert!(store.validate(&raw).is_some());
+        assert!(store.set_expiry(&key.id, None));
+        assert!(store.validate(&raw).is_some());
+        assert!(!store.set_expiry("missing", None));
+    }
+
+    #[test]
+    fn rotate_replaces_secret_and_keeps_metadata() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let expiry = Some(Utc::now() + chrono::Duration::days(7));
+        let (key, old_raw) = store.generate("Rotate Me", expiry);
+        let (rotated, new_raw) = store.rotate(&key.id).unwrap();
+
+        assert_ne!(old_raw, new_raw);
+        assert_eq!(rotated.id, key.id);
+        assert_eq!(rotated.name, key.name);
+        assert_eq!(rotated.created_at, key.created_at);
+        assert_eq!(rotated.expires_at, expiry);
+        assert!(rotated.rotated_at.is_some());
+        assert_eq!(rotated.prefix, &new_raw[..8]);
+
+        assert!(store.validate(&old_raw).is_none());
+        assert_eq!(store.validate(&new_raw).unwrap().id, key.id);
+        assert!(store.rotate("missing").is_none());
+    }
+
+    #[test]
+    fn rotate_default_updates_displayed_key() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let default = store.list().into_iter().find(|k| k.name == "Default").unwrap();
+        let (_, raw) = store.rotate(&default.id).unwrap();
+        assert_eq!(store.get_default_raw(), Some(raw));
+    }
+
+    #[test]
+    fn scope_persists_and_defaults_to_unrestricted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = ApiKeyStore::load(tmp.path());
+
+        let (key, _) = store.generate("Agent", None);
+        assert!(!key.scope.is_restricted());
+
+        let scope = ApiKeyScope { namespaces: vec!["com.example.notes".into()], read_only: true };
+        assert!(store.set_scope(&key.id, scope.clone()));
+        assert!(!store.set_scope("missing", ApiKeyScope::default()));
+
+        let reloaded = ApiKeyStore::load(tmp.path());
+        assert_eq!(reloaded.get(&key.id).unwrap().scope, scope);
+    }
+
+    #[test]
+    fn scope_allows_namespaces_and_read_only() {
+        let scope = ApiKeyScope { namespaces: vec!["com.example".into(), "nexus".into()], read_only: false };
+        assert!(scope.allows("com.example.search", false));
+        assert!(scope.allows("nexus.list_plugins", true));
+        // Namespace must match a whole segment
+        assert!(!scope.allows("com.example2.search", false));
+        assert!(!scope.allows("com.other.search", false));
+
+        let read_only = ApiKeyScope { namespaces: vec![], read_only: true };
+        assert!(read_only.allows("com.other.search", false));
+        assert!(!read_only.allows("nexus.write_file", true));
+        assert!(ApiKeyScope::default().allows("anything.at_all", true));
+    }
 }
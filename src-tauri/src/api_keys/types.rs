@@ -28,6 +28,106 @@ pub struct ApiKey {
     pub prefix: String,
     /// When the key was created.
     pub created_at: DateTime<Utc>,
-    /// Updated on each successful validation. `None` if never used.
+    /// Updated on each successful validation and tool call. `None` if never used.
     pub last_used_at: Option<DateTime<Utc>>,
+    /// Peer address of the last successful authentication.
+    #[serde(default)]
+    pub last_used_ip: Option<String>,
+    /// MCP tool calls made with this key.
+    #[serde(default)]
+    pub call_count: u64,
+    /// After this instant the key is rejected by the gateway. `None` never expires.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the secret was last replaced via [`super::store::ApiKeyStore::rotate`].
+    #[serde(default)]
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// Which tools the key may list and call. Defaults to unrestricted.
+    #[serde(default)]
+    pub scope: ApiKeyScope,
+}
+
+impl ApiKey {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= Utc::now())
+    }
+}
+
+/// Restricts an API key to a subset of the gateway's tools, so a
+/// low-privilege key can be handed to an experimental agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyScope {
+    /// Tool namespaces the key may use: plugin or extension IDs, or `nexus`
+    /// for built-in tools. Empty allows every namespace.
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+    /// Only allow tools that don't change host state.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl ApiKeyScope {
+    pub fn is_restricted(&self) -> bool {
+        !self.namespaces.is_empty() || self.read_only
+    }
+
+    /// Whether `tool` (a namespaced name like `com.example.plugin.search`)
+    /// is within scope. `mutating` is the gateway's classification of the tool.
+    pub fn allows(&self, tool: &str, mutating: bool) -> bool {
+        if self.read_only && mutating {
+            return false;
+        }
+        self.namespaces.is_empty()
+            || self
+                .namespaces
+                .iter()
+                .any(|ns| tool.strip_prefix(ns.as_str()).is_some_and(|rest| rest.starts_with('.')))
+    }
+}
+
+/// Per-key usage for the key management UI, so stale or unexpectedly busy
+/// keys stand out.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyUsage {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    pub call_count: u64,
+    /// Whole days since last use (or since creation if never used).
+    pub idle_days: i64,
+    /// Average tool calls per day since creation.
+    pub calls_per_day: f64,
+}
+
+impl From<&ApiKey> for ApiKeyUsage {
+    fn from(k: &ApiKey) -> Self {
+        let now = Utc::now();
+        let age_days = ((now - k.created_at).num_seconds() as f64 / 86_400.0).max(1.0);
+        Self {
+            id: k.id.clone(),
+            name: k.name.clone(),
+            prefix: k.prefix.clone(),
+            created_at: k.created_at,
+            last_used_at: k.last_used_at,
+            last_used_ip: k.last_used_ip.clone(),
+            call_count: k.call_count,
+            idle_days: (now - k.last_used_at.unwrap_or(k.created_at)).num_days(),
+            calls_per_day: k.call_count as f64 / age_days,
+        }
+    }
+}
+
+/// Outcome of checking a raw key (see [`super::store::ApiKeyStore::check`]).
+#[derive(Debug, Clone)]
+pub enum ApiKeyValidation {
+    /// Known, unexpired key.
+    Valid(ApiKey),
+    /// Known key past its `expires_at`. Reported separately so clients get a
+    /// clear error instead of a generic invalid-token response.
+    Expired(ApiKey),
+    /// No key matches.
+    Invalid,
 }
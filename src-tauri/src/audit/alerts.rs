@@ -0,0 +1,363 @@
+//! Alerting rules over the audit write path, e.g. "any critical failure" or
+//! "more than five denied approvals from one plugin in ten minutes". Rules
+//! are evaluated here; [`super::writer::AlertWatcher`] feeds them entries and
+//! fires the OS notifications and CloudEvents.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{AuditEntry, AuditResult, AuditSeverity};
+
+/// Source of alert events.
+pub const ALERT_SOURCE: &str = "nexus://audit";
+
+/// Type of alert events.
+pub const ALERT_EVENT_TYPE: &str = "nexus.audit.alert";
+
+/// A user-defined alert. Stored in settings as `audit_alerts`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Unique name, shown in notifications.
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Entries the rule counts.
+    #[serde(default)]
+    pub filter: AlertFilter,
+    /// Matching entries needed to fire.
+    #[serde(default = "default_threshold")]
+    pub threshold: u32,
+    /// Window the threshold is counted over. Ignored when the threshold is 1.
+    #[serde(default)]
+    pub window_secs: u64,
+    /// Count separately per actor, source, or subject, e.g. per plugin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<AlertGroupBy>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_threshold() -> u32 {
+    1
+}
+
+/// Conditions an entry must meet to count toward a rule. `action`, `actor`,
+/// `source_id`, and `subject` accept `*` globs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Entries at this severity or above.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<AuditSeverity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<AuditResult>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertGroupBy {
+    Actor,
+    SourceId,
+    Subject,
+}
+
+/// Check a set of rules before saving them.
+pub fn validate_rules(rules: &[AlertRule]) -> Result<(), String> {
+    let mut names = std::collections::HashSet::new();
+    for rule in rules {
+        if rule.name.trim().is_empty() {
+            return Err("Alert rules need a name".to_string());
+        }
+        if !names.insert(rule.name.as_str()) {
+            return Err(format!("Duplicate alert rule '{}'", rule.name));
+        }
+        if rule.threshold == 0 {
+            return Err(format!("Alert rule '{}': threshold must be at least 1", rule.name));
+        }
+        if rule.threshold > 1 && rule.window_secs == 0 {
+            return Err(format!(
+                "Alert rule '{}': a threshold above 1 needs a window",
+                rule.name
+            ));
+        }
+        CompiledFilter::new(&rule.filter)
+            .map_err(|e| format!("Alert rule '{}': {}", rule.name, e))?;
+    }
+    Ok(())
+}
+
+/// A fired alert. Published as the data of a [`ALERT_EVENT_TYPE`] event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditAlert {
+    pub rule: String,
+    /// The actor, source, or subject the entries were counted for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Matching entries within the window.
+    pub count: usize,
+    pub window_secs: u64,
+    /// The entry that tripped the rule.
+    pub action: String,
+    pub actor: String,
+    pub subject: Option<String>,
+    pub severity: String,
+    pub result: String,
+}
+
+impl AuditAlert {
+    /// One-line description for notifications.
+    pub fn summary(&self) -> String {
+        let mut summary = match &self.subject {
+            Some(subject) => format!("{} {} on {}", self.actor, self.action, subject),
+            None => format!("{} {}", self.actor, self.action),
+        };
+        if self.count > 1 {
+            summary.push_str(&format!(
+                " ({} matching entries in {}s)",
+                self.count, self.window_secs
+            ));
+        }
+        summary
+    }
+}
+
+struct CompiledFilter {
+    action: Option<glob::Pattern>,
+    actor: Option<glob::Pattern>,
+    source_id: Option<glob::Pattern>,
+    subject: Option<glob::Pattern>,
+    min_severity: Option<AuditSeverity>,
+    result: Option<AuditResult>,
+}
+
+impl CompiledFilter {
+    fn new(filter: &AlertFilter) -> Result<Self, String> {
+        let pattern = |p: &Option<String>| {
+            p.as_deref()
+                .map(glob::Pattern::new)
+                .transpose()
+                .map_err(|e| format!("invalid pattern: {}", e))
+        };
+        Ok(Self {
+            action: pattern(&filter.action)?,
+            actor: pattern(&filter.actor)?,
+            source_id: pattern(&filter.source_id)?,
+            subject: pattern(&filter.subject)?,
+            min_severity: filter.min_severity.clone(),
+            result: filter.result.clone(),
+        })
+    }
+
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        let text = |pattern: &Option<glob::Pattern>, value: Option<&str>| match pattern {
+            Some(p) => value.is_some_and(|v| p.matches(v)),
+            None => true,
+        };
+        text(&self.action, Some(&entry.action))
+            && text(&self.actor, Some(&entry.actor.as_str()))
+            && text(&self.source_id, entry.source_id.as_deref())
+            && text(&self.subject, entry.subject.as_deref())
+            && self.min_severity.as_ref().map_or(true, |min| entry.severity >= *min)
+            && self.result.as_ref().map_or(true, |r| entry.result == *r)
+    }
+}
+
+struct RuleState {
+    rule: AlertRule,
+    filter: CompiledFilter,
+    /// Times of matching entries within the window, per group.
+    hits: HashMap<Option<String>, VecDeque<DateTime<Utc>>>,
+}
+
+/// Evaluates the enabled rules against entries as they're written.
+#[derive(Default)]
+pub struct AlertEvaluator {
+    rules: Vec<RuleState>,
+}
+
+impl AlertEvaluator {
+    /// Rules that fail to compile are skipped; [`validate_rules`] rejects
+    /// them before they're saved.
+    pub fn new(rules: &[AlertRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter_map(|rule| match CompiledFilter::new(&rule.filter) {
+                Ok(filter) => Some(RuleState {
+                    rule: rule.clone(),
+                    filter,
+                    hits: HashMap::new(),
+                }),
+                Err(e) => {
+                    log::warn!("Skipping alert rule '{}': {}", rule.name, e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Count `entry`, written at `now`, toward each rule it matches. Returns
+    /// the alerts it fires. A group's count restarts after it fires.
+    pub fn observe(&mut self, entry: &AuditEntry, now: DateTime<Utc>) -> Vec<AuditAlert> {
+        let mut fired = Vec::new();
+        for state in &mut self.rules {
+            if !state.filter.matches(entry) {
+                continue;
+            }
+            let group = state.rule.group_by.map(|by| match by {
+                AlertGroupBy::Actor => entry.actor.as_str(),
+                AlertGroupBy::SourceId => entry.source_id.clone().unwrap_or_default(),
+                AlertGroupBy::Subject => entry.subject.clone().unwrap_or_default(),
+            });
+            let window = Duration::seconds(state.rule.window_secs.min(i64::MAX as u64) as i64);
+            let hits = state.hits.entry(group.clone()).or_default();
+            while hits.front().is_some_and(|t| *t <= now - window) {
+                hits.pop_front();
+            }
+            hits.push_back(now);
+            if hits.len() < state.rule.threshold as usize {
+                continue;
+            }
+
+            fired.push(AuditAlert {
+                rule: state.rule.name.clone(),
+                group: group.clone(),
+                count: hits.len(),
+                window_secs: state.rule.window_secs,
+                action: entry.action.clone(),
+                actor: entry.actor.as_str(),
+                subject: entry.subject.clone(),
+                severity: entry.severity.as_str().to_string(),
+                result: entry.result.as_str().to_string(),
+            });
+            state.hits.remove(&group);
+        }
+        // Groups that went quiet
+        for state in &mut self.rules {
+            let window = Duration::seconds(state.rule.window_secs.min(i64::MAX as u64) as i64);
+            state
+                .hits
+                .retain(|_, hits| hits.back().is_some_and(|t| *t > now - window));
+        }
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditActor;
+
+    fn entry(plugin: &str, action: &str, severity: AuditSeverity, result: AuditResult) -> AuditEntry {
+        AuditEntry {
+            actor: AuditActor::Plugin { id: plugin.into() },
+            source_id: None,
+            severity,
+            action: action.into(),
+            subject: None,
+            result,
+            details: None,
+        }
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn fires_on_every_match_with_threshold_one() {
+        let mut eval = AlertEvaluator::new(&[AlertRule {
+            name: "critical failures".into(),
+            enabled: true,
+            filter: AlertFilter {
+                min_severity: Some(AuditSeverity::Critical),
+                result: Some(AuditResult::Failure),
+                ..Default::default()
+            },
+            threshold: 1,
+            window_secs: 0,
+            group_by: None,
+        }]);
+
+        let ok = entry("a", "fs.write", AuditSeverity::Critical, AuditResult::Success);
+        assert!(eval.observe(&ok, at(0)).is_empty());
+        let warn = entry("a", "fs.write", AuditSeverity::Warn, AuditResult::Failure);
+        assert!(eval.observe(&warn, at(0)).is_empty());
+
+        let bad = entry("a", "fs.write", AuditSeverity::Critical, AuditResult::Failure);
+        let alerts = eval.observe(&bad, at(1));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].summary(), "plugin:a fs.write");
+        assert_eq!(eval.observe(&bad, at(2)).len(), 1);
+    }
+
+    #[test]
+    fn counts_per_group_within_the_window() {
+        let mut eval = AlertEvaluator::new(&[AlertRule {
+            name: "denied approvals".into(),
+            enabled: true,
+            filter: AlertFilter {
+                action: Some("approval.*".into()),
+                result: Some(AuditResult::Failure),
+                ..Default::default()
+            },
+            threshold: 3,
+            window_secs: 600,
+            group_by: Some(AlertGroupBy::Actor),
+        }]);
+        let denied = |plugin: &str| {
+            entry(plugin, "approval.fs", AuditSeverity::Warn, AuditResult::Failure)
+        };
+
+        assert!(eval.observe(&denied("a"), at(0)).is_empty());
+        assert!(eval.observe(&denied("b"), at(10)).is_empty());
+        assert!(eval.observe(&denied("a"), at(20)).is_empty());
+        // The first hit from a has left the window
+        assert!(eval.observe(&denied("a"), at(600)).is_empty());
+
+        let alerts = eval.observe(&denied("a"), at(601));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].group.as_deref(), Some("plugin:a"));
+        assert_eq!(alerts[0].count, 3);
+
+        // The count restarts after firing
+        assert!(eval.observe(&denied("a"), at(602)).is_empty());
+    }
+
+    #[test]
+    fn validates_rules() {
+        let rule = |name: &str, threshold: u32, window_secs: u64| AlertRule {
+            name: name.into(),
+            enabled: true,
+            filter: AlertFilter::default(),
+            threshold,
+            window_secs,
+            group_by: None,
+        };
+        assert!(validate_rules(&[rule("a", 1, 0), rule("b", 5, 60)]).is_ok());
+        assert!(validate_rules(&[rule("a", 1, 0), rule("a", 1, 0)]).is_err());
+        assert!(validate_rules(&[rule(" ", 1, 0)]).is_err());
+        assert!(validate_rules(&[rule("a", 5, 0)]).is_err());
+
+        let mut bad_glob = rule("a", 1, 0);
+        bad_glob.filter.action = Some("[".into());
+        assert!(validate_rules(&[bad_glob]).is_err());
+    }
+}
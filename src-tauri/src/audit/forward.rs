@@ -0,0 +1,339 @@
+//! Forwarding of audit entries to a syslog server or an HTTPS collector, for
+//! users who centralize logs off the machine. The queue and retry loop live
+//! in [`super::writer::AuditForwarder`]; this module holds the settings and
+//! the wire formats.
+
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+
+use super::{AuditEntry, AuditSeverity};
+
+/// Where to forward audit entries. Stored in settings as `audit_forward`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForwardConfig {
+    pub enabled: bool,
+    pub sink: ForwardSink,
+    /// Entries held in memory while the sink is unreachable. The oldest are
+    /// dropped beyond this; they remain in the local audit log.
+    pub max_pending: usize,
+}
+
+impl Default for ForwardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sink: ForwardSink::Syslog {
+                address: "localhost:514".to_string(),
+                protocol: SyslogProtocol::Udp,
+            },
+            max_pending: 10_000,
+        }
+    }
+}
+
+/// Destination of forwarded entries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ForwardSink {
+    /// RFC 5424 messages with the entry as JSON, facility `log audit`.
+    Syslog {
+        /// `host:port` of the syslog server.
+        address: String,
+        #[serde(default)]
+        protocol: SyslogProtocol,
+    },
+    /// Batches POSTed as a JSON array.
+    Http {
+        /// Must be https, except for loopback collectors.
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bearer_token: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogProtocol {
+    #[default]
+    Udp,
+    /// Octet-counted framing (RFC 6587).
+    Tcp,
+}
+
+impl ForwardConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_pending == 0 {
+            return Err("max_pending must be at least 1".to_string());
+        }
+        match &self.sink {
+            ForwardSink::Syslog { address, .. } => {
+                let port = address
+                    .rsplit_once(':')
+                    .filter(|(host, _)| !host.is_empty())
+                    .and_then(|(_, port)| port.parse::<u16>().ok());
+                if port.is_none() {
+                    return Err(format!(
+                        "Invalid syslog address '{}': expected host:port",
+                        address
+                    ));
+                }
+            }
+            ForwardSink::Http { url, .. } => {
+                let parsed =
+                    url::Url::parse(url).map_err(|e| format!("Invalid collector URL: {}", e))?;
+                let loopback = matches!(
+                    parsed.host_str(),
+                    Some("localhost" | "127.0.0.1" | "[::1]")
+                );
+                match parsed.scheme() {
+                    "https" => {}
+                    "http" if loopback => {}
+                    _ => return Err("Collector URL must be https".to_string()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An audit entry as forwarded. The local log assigns its own timestamp on
+/// insert; this one is taken when the entry is written.
+#[derive(Debug, Clone, Serialize)]
+pub struct ForwardRecord {
+    pub timestamp: DateTime<Utc>,
+    pub actor: String,
+    pub source_id: Option<String>,
+    pub severity: String,
+    pub action: String,
+    pub subject: Option<String>,
+    pub result: String,
+    pub details: Option<serde_json::Value>,
+}
+
+impl ForwardRecord {
+    pub fn new(entry: &AuditEntry, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            actor: entry.actor.as_str(),
+            source_id: entry.source_id.clone(),
+            severity: entry.severity.as_str().to_string(),
+            action: entry.action.clone(),
+            subject: entry.subject.clone(),
+            result: entry.result.as_str().to_string(),
+            details: entry.details.clone(),
+        }
+    }
+
+    /// RFC 5424 message: facility 13 (log audit), the action as MSGID, and
+    /// the record as JSON.
+    fn syslog_message(&self, hostname: &str) -> String {
+        let severity = match self.severity.as_str() {
+            s if s == AuditSeverity::Critical.as_str() => 2,
+            s if s == AuditSeverity::Warn.as_str() => 4,
+            _ => 6,
+        };
+        let msg_id: String = self
+            .action
+            .chars()
+            .filter(|c| c.is_ascii_graphic())
+            .take(32)
+            .collect();
+        format!(
+            "<{}>1 {} {} nexus {} {} - {}",
+            13 * 8 + severity,
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            hostname,
+            std::process::id(),
+            if msg_id.is_empty() { "-" } else { &msg_id },
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// Connected sink. TCP connections are opened on first use and dropped on
+/// error, so the next attempt reconnects.
+pub(super) enum Sink {
+    Udp {
+        socket: Option<UdpSocket>,
+        address: String,
+        hostname: String,
+    },
+    Tcp {
+        stream: Option<TcpStream>,
+        address: String,
+        hostname: String,
+    },
+    Http {
+        client: reqwest::Client,
+        url: String,
+        bearer_token: Option<String>,
+    },
+}
+
+impl Sink {
+    pub(super) fn new(sink: &ForwardSink) -> Result<Self, String> {
+        let hostname = sysinfo::System::host_name()
+            .filter(|h| !h.is_empty() && h.is_ascii() && !h.contains(' '))
+            .unwrap_or_else(|| "-".to_string());
+        Ok(match sink {
+            ForwardSink::Syslog {
+                address,
+                protocol: SyslogProtocol::Udp,
+            } => Sink::Udp {
+                socket: None,
+                address: address.clone(),
+                hostname,
+            },
+            ForwardSink::Syslog {
+                address,
+                protocol: SyslogProtocol::Tcp,
+            } => Sink::Tcp {
+                stream: None,
+                address: address.clone(),
+                hostname,
+            },
+            ForwardSink::Http { url, bearer_token } => Sink::Http {
+                client: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(30))
+                    .build()
+                    .map_err(|e| e.to_string())?,
+                url: url.clone(),
+                bearer_token: bearer_token.clone(),
+            },
+        })
+    }
+
+    pub(super) async fn send(&mut self, batch: &[ForwardRecord]) -> Result<(), String> {
+        match self {
+            Sink::Udp {
+                socket,
+                address,
+                hostname,
+            } => {
+                if socket.is_none() {
+                    let bind = if address.starts_with('[') { "[::]:0" } else { "0.0.0.0:0" };
+                    let s = UdpSocket::bind(bind).await.map_err(|e| e.to_string())?;
+                    s.connect(address.as_str())
+                        .await
+                        .map_err(|e| format!("Syslog connect failed: {}", e))?;
+                    *socket = Some(s);
+                }
+                let s = socket.as_ref().expect("socket was just set");
+                for record in batch {
+                    if let Err(e) = s.send(record.syslog_message(hostname).as_bytes()).await {
+                        *socket = None;
+                        return Err(format!("Syslog send failed: {}", e));
+                    }
+                }
+                Ok(())
+            }
+            Sink::Tcp {
+                stream,
+                address,
+                hostname,
+            } => {
+                if stream.is_none() {
+                    let s = TcpStream::connect(address.as_str())
+                        .await
+                        .map_err(|e| format!("Syslog connect failed: {}", e))?;
+                    *stream = Some(s);
+                }
+                let mut frames = String::new();
+                for record in batch {
+                    let msg = record.syslog_message(hostname);
+                    frames.push_str(&format!("{} {}", msg.len(), msg));
+                }
+                let s = stream.as_mut().expect("stream was just set");
+                if let Err(e) = s.write_all(frames.as_bytes()).await {
+                    *stream = None;
+                    return Err(format!("Syslog send failed: {}", e));
+                }
+                Ok(())
+            }
+            Sink::Http {
+                client,
+                url,
+                bearer_token,
+            } => {
+                let mut req = client.post(url.as_str()).json(batch);
+                if let Some(token) = bearer_token {
+                    req = req.bearer_auth(token);
+                }
+                let resp = req
+                    .send()
+                    .await
+                    .map_err(|e| format!("Collector request failed: {}", e))?;
+                if !resp.status().is_success() {
+                    return Err(format!("Collector returned {}", resp.status()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditActor, AuditResult};
+
+    fn record(severity: AuditSeverity) -> ForwardRecord {
+        ForwardRecord::new(
+            &AuditEntry {
+                actor: AuditActor::Plugin { id: "com.example".into() },
+                source_id: None,
+                severity,
+                action: "plugin.install".into(),
+                subject: Some("com.example".into()),
+                result: AuditResult::Success,
+                details: None,
+            },
+            DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[test]
+    fn formats_rfc5424_messages() {
+        let msg = record(AuditSeverity::Critical).syslog_message("laptop");
+        let expected_prefix = format!(
+            "<106>1 2026-03-10T12:00:00.000Z laptop nexus {} plugin.install - {{",
+            std::process::id()
+        );
+        assert!(msg.starts_with(&expected_prefix), "{}", msg);
+        assert!(msg.contains("\"actor\":\"plugin:com.example\""));
+
+        assert!(record(AuditSeverity::Info).syslog_message("-").starts_with("<110>1 "));
+    }
+
+    #[test]
+    fn validates_sinks() {
+        assert!(ForwardConfig::default().validate().is_ok());
+
+        let no_port = ForwardConfig {
+            sink: ForwardSink::Syslog {
+                address: "logs.example.com".into(),
+                protocol: SyslogProtocol::Tcp,
+            },
+            ..Default::default()
+        };
+        assert!(no_port.validate().is_err());
+
+        let http = |url: &str| ForwardConfig {
+            sink: ForwardSink::Http {
+                url: url.into(),
+                bearer_token: None,
+            },
+            ..Default::default()
+        };
+        assert!(http("https://logs.example.com/ingest").validate().is_ok());
+        assert!(http("http://localhost:8080/ingest").validate().is_ok());
+        assert!(http("http://logs.example.com/ingest").validate().is_err());
+    }
+}
@@ -17,8 +17,16 @@
 //!
 //! The write path is non-blocking: callers push entries into a bounded mpsc channel
 //! via [`writer::AuditWriter`], and a background task batch-inserts into SQLite.
-//! Entries are retained for 30 days and automatically pruned.
+//! Tokens, file contents, and other sensitive values in entry details are
+//! masked on the way in ([`redact`]).
+//! Entries are pruned by age and total size as configured in settings
+//! ([`retention`]). Entries can also be forwarded to a syslog server or HTTPS
+//! collector ([`forward`]).
 
+pub mod alerts;
+pub mod forward;
+pub mod redact;
+pub mod retention;
 pub mod store;
 pub mod writer;
 
@@ -61,7 +69,7 @@ impl AuditActor {
 /// A failed `plugin.install` is still `Warn` because the *attempt* is what
 /// matters for the audit trail. Severity helps operators quickly triage
 /// which entries need attention during incident investigation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditSeverity {
     /// Routine operations — listing, reading, querying, successful starts/stops,
@@ -86,7 +94,7 @@ impl AuditSeverity {
 }
 
 /// Outcome of the audited operation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditResult {
     Success,
@@ -140,7 +148,8 @@ pub struct AuditLogRow {
     pub details: Option<serde_json::Value>,
 }
 
-/// Query parameters for filtering the audit log.
+/// Query parameters for filtering the audit log. `action`, `actor`,
+/// `source_id`, and `subject` accept `*` globs.
 #[derive(Debug, Default, Deserialize)]
 pub struct AuditQuery {
     pub action: Option<String>,
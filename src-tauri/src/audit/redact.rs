@@ -0,0 +1,248 @@
+//! Masking of sensitive values in audit entry details before they're
+//! persisted, forwarded, or matched by alert rules.
+//!
+//! A field name masks the value under that key at any depth; `name.*` masks
+//! each value under the key but keeps the child keys, so `env.*` records
+//! which variables were set without their values. Names match
+//! case-insensitively.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::AuditEntry;
+
+/// Replacement for masked values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Fields to mask. Stored in settings as `audit_redaction`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionPolicy {
+    /// Masked in every entry.
+    pub fields: Vec<String>,
+    /// Masked in entries whose action matches the key, a `*` glob.
+    pub actions: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self {
+            fields: [
+                "token",
+                "access_token",
+                "refresh_token",
+                "id_token",
+                "bearer_token",
+                "client_secret",
+                "secret",
+                "password",
+                "api_key",
+                "authorization",
+                "cookie",
+                "content",
+                "contents",
+                "env.*",
+            ]
+            .map(String::from)
+            .to_vec(),
+            actions: BTreeMap::new(),
+        }
+    }
+}
+
+impl RedactionPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        for pattern in self.actions.keys() {
+            glob::Pattern::new(pattern)
+                .map_err(|e| format!("Invalid action pattern '{}': {}", pattern, e))?;
+        }
+        let fields = self.fields.iter().chain(self.actions.values().flatten());
+        for field in fields {
+            if field.trim_end_matches(".*").is_empty() {
+                return Err("Redacted field names can't be empty".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`RedactionPolicy`] ready to apply.
+#[derive(Debug, Default)]
+pub struct Redactor {
+    /// Lowercased names whose values are masked.
+    fields: Vec<Field>,
+    actions: Vec<(glob::Pattern, Vec<Field>)>,
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    /// Mask the children's values instead of the value itself.
+    children: bool,
+}
+
+impl Field {
+    fn parse(spec: &str) -> Self {
+        match spec.strip_suffix(".*") {
+            Some(name) => Self {
+                name: name.to_lowercase(),
+                children: true,
+            },
+            None => Self {
+                name: spec.to_lowercase(),
+                children: false,
+            },
+        }
+    }
+}
+
+impl Redactor {
+    /// Action patterns that don't parse are skipped; [`RedactionPolicy::validate`]
+    /// rejects them before they're saved.
+    pub fn new(policy: &RedactionPolicy) -> Self {
+        let parse = |fields: &[String]| fields.iter().map(|f| Field::parse(f)).collect();
+        Self {
+            fields: parse(&policy.fields),
+            actions: policy
+                .actions
+                .iter()
+                .filter_map(|(pattern, fields)| {
+                    glob::Pattern::new(pattern)
+                        .ok()
+                        .map(|p| (p, parse(fields)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Mask the sensitive values in `entry`'s details.
+    pub fn apply(&self, entry: &mut AuditEntry) {
+        let Some(details) = entry.details.as_mut() else {
+            return;
+        };
+        let action_fields = self
+            .actions
+            .iter()
+            .filter(|(pattern, _)| pattern.matches(&entry.action))
+            .flat_map(|(_, fields)| fields);
+        let fields: Vec<&Field> = self.fields.iter().chain(action_fields).collect();
+        if !fields.is_empty() {
+            mask(details, &fields);
+        }
+    }
+}
+
+fn mask(value: &mut Value, fields: &[&Field]) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let key = key.to_lowercase();
+                match fields.iter().find(|f| f.name == key) {
+                    Some(field) if field.children => match child {
+                        Value::Object(children) => {
+                            for v in children.values_mut() {
+                                *v = Value::String(REDACTED.into());
+                            }
+                        }
+                        Value::Array(items) => {
+                            for v in items.iter_mut() {
+                                *v = Value::String(REDACTED.into());
+                            }
+                        }
+                        Value::Null => {}
+                        other => *other = Value::String(REDACTED.into()),
+                    },
+                    Some(_) => {
+                        if !child.is_null() {
+                            *child = Value::String(REDACTED.into());
+                        }
+                    }
+                    None => mask(child, fields),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                mask(item, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{AuditActor, AuditResult, AuditSeverity};
+    use serde_json::json;
+
+    fn entry(action: &str, details: Value) -> AuditEntry {
+        AuditEntry {
+            actor: AuditActor::User,
+            source_id: None,
+            severity: AuditSeverity::Info,
+            action: action.into(),
+            subject: None,
+            result: AuditResult::Success,
+            details: Some(details),
+        }
+    }
+
+    #[test]
+    fn masks_default_fields_at_any_depth() {
+        let redactor = Redactor::new(&RedactionPolicy::default());
+        let mut e = entry(
+            "process.exec",
+            json!({
+                "command": "deploy",
+                "env": {"AWS_SECRET": "abc", "DEBUG": "1"},
+                "requests": [{"Authorization": "Bearer x", "url": "https://example.com"}],
+                "refresh_token": null,
+            }),
+        );
+        redactor.apply(&mut e);
+        assert_eq!(
+            e.details.unwrap(),
+            json!({
+                "command": "deploy",
+                "env": {"AWS_SECRET": REDACTED, "DEBUG": REDACTED},
+                "requests": [{"Authorization": REDACTED, "url": "https://example.com"}],
+                "refresh_token": null,
+            })
+        );
+    }
+
+    #[test]
+    fn applies_per_action_fields() {
+        let mut policy = RedactionPolicy {
+            fields: vec![],
+            ..Default::default()
+        };
+        policy.actions.insert("fs.*".into(), vec!["path".into()]);
+        assert!(policy.validate().is_ok());
+        let redactor = Redactor::new(&policy);
+
+        let mut write = entry("fs.write", json!({"path": "/home/me/notes.txt"}));
+        redactor.apply(&mut write);
+        assert_eq!(write.details.unwrap()["path"], REDACTED);
+
+        let mut other = entry("plugin.install", json!({"path": "/tmp/plugin"}));
+        redactor.apply(&mut other);
+        assert_eq!(other.details.unwrap()["path"], "/tmp/plugin");
+    }
+
+    #[test]
+    fn validates_policies() {
+        let mut policy = RedactionPolicy::default();
+        policy.actions.insert("[".into(), vec!["x".into()]);
+        assert!(policy.validate().is_err());
+
+        let policy = RedactionPolicy {
+            fields: vec![".*".into()],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! Background pruning of the audit log by the configured retention policy.
+//!
+//! Entries past the age limit are dropped quietly. When the size limit forces
+//! entries out before their time, the pruning is itself audited and a
+//! lifecycle event lets the UI warn the user.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::store::{AuditRetention, AuditStore};
+use super::writer::AuditWriter;
+use super::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::lifecycle_events::{self, LifecycleEvent};
+use crate::AppState;
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Spawn a background task that prunes the audit log every hour.
+pub fn spawn(app: tauri::AppHandle, state: AppState, store: Arc<AuditStore>, audit: AuditWriter) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let policy = state.read().await.settings.audit_retention.clone();
+            prune(&app, &policy, &store, &audit);
+        }
+    });
+}
+
+/// Apply `policy` to the audit log now.
+pub fn prune(app: &tauri::AppHandle, policy: &AuditRetention, store: &AuditStore, audit: &AuditWriter) {
+    let report = match store.compact(policy) {
+        Ok(report) => report,
+        Err(e) => {
+            log::error!("Audit cleanup failed: {}", e);
+            return;
+        }
+    };
+    if report.expired > 0 {
+        log::info!("Audit cleanup: purged {} old entries", report.expired);
+    }
+    if report.trimmed == 0 {
+        return;
+    }
+
+    log::warn!(
+        "Audit log exceeded {} bytes: pruned {} entries early",
+        policy.max_bytes,
+        report.trimmed
+    );
+    audit.record(AuditEntry {
+        actor: AuditActor::System, source_id: None, severity: AuditSeverity::Warn, action: "audit.prune".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"trimmed": report.trimmed, "max_bytes": policy.max_bytes})),
+    });
+    lifecycle_events::emit(
+        Some(app),
+        LifecycleEvent::AuditPruned {
+            trimmed: report.trimmed,
+            max_bytes: policy.max_bytes,
+        },
+    );
+}
@@ -1,17 +1,152 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Mutex;
 use std::time::Duration;
 
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use super::{AuditEntry, AuditLogRow, AuditQuery};
 
+/// `prev_hash` of the first entry ever written.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Approximate stored size of an entry, used for the size limit.
+const ENTRY_SIZE_SQL: &str = "length(timestamp) + length(actor) + coalesce(length(source_id), 0)
+     + length(severity) + length(action) + coalesce(length(subject), 0) + length(result)
+     + coalesce(length(details), 0) + coalesce(length(prev_hash), 0) + coalesce(length(hash), 0)";
+
+/// How long audit entries are kept and how large the log may grow. Stored in
+/// settings as `audit_retention`. A zero limit means "no limit".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditRetention {
+    /// Days to keep entries.
+    pub max_age_days: u32,
+    /// Upper bound on the size of stored entries, in bytes. The oldest
+    /// entries are pruned early to stay under it.
+    pub max_bytes: u64,
+}
+
+impl Default for AuditRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: 30,
+            max_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+impl AuditRetention {
+    /// Smallest size limit accepted, so a typo can't wipe the log.
+    const MIN_BYTES: u64 = 1024 * 1024;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_bytes > 0 && self.max_bytes < Self::MIN_BYTES {
+            return Err(format!("max_bytes must be 0 or at least {}", Self::MIN_BYTES));
+        }
+        Ok(())
+    }
+
+    /// Age limit, or `None` when entries are kept regardless of age.
+    pub fn max_age(&self) -> Option<Duration> {
+        (self.max_age_days > 0).then(|| Duration::from_secs(u64::from(self.max_age_days) * 86400))
+    }
+}
+
+/// What a compaction pass removed from the log.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditCompaction {
+    /// Entries older than the age limit.
+    pub expired: usize,
+    /// Entries pruned before their time to get under the size limit.
+    pub trimmed: usize,
+}
+
+/// Where a hash chain check failed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainIssue {
+    /// The entry's contents no longer match its hash.
+    Modified { id: i64 },
+    /// Entries before this one were deleted or reordered.
+    Missing { before_id: i64 },
+    /// The newest entries were deleted; `after_id` is the last one left, if any.
+    Truncated { after_id: Option<i64> },
+}
+
+/// Result of [`AuditStore::verify`].
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditVerification {
+    pub ok: bool,
+    /// Chained entries checked.
+    pub verified: usize,
+    /// Entries written before chaining was introduced, which can't be checked.
+    pub unchained: usize,
+    /// The first problem found, if any.
+    pub issue: Option<ChainIssue>,
+}
+
+/// Number of entries sharing a value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuditBucket {
+    pub key: String,
+    pub count: usize,
+}
+
+/// Aggregates over the entries matching a query, for the audit viewer.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditStats {
+    pub total: usize,
+    pub oldest: Option<String>,
+    pub newest: Option<String>,
+    pub by_severity: Vec<AuditBucket>,
+    pub by_result: Vec<AuditBucket>,
+    /// Most frequent actions, most frequent first.
+    pub top_actions: Vec<AuditBucket>,
+    /// Most active actors, most active first.
+    pub top_actors: Vec<AuditBucket>,
+    /// Entries per UTC day (`YYYY-MM-DD`), oldest first.
+    pub per_day: Vec<AuditBucket>,
+}
+
+/// Entries listed in the `top_*` fields of [`AuditStats`].
+const STATS_TOP: usize = 10;
+
+/// Hash of an entry, covering the previous entry's hash and every stored field.
+#[allow(clippy::too_many_arguments)]
+fn entry_hash(
+    prev_hash: &str,
+    timestamp: &str,
+    actor: &str,
+    source_id: Option<&str>,
+    severity: &str,
+    action: &str,
+    subject: Option<&str>,
+    result: &str,
+    details: Option<&str>,
+) -> String {
+    // A JSON array keeps field boundaries unambiguous
+    let canonical = serde_json::json!([
+        prev_hash, timestamp, actor, source_id, severity, action, subject, result, details
+    ]);
+    format!("{:x}", Sha256::digest(canonical.to_string().as_bytes()))
+}
+
 /// SQLite-backed durable audit store.
 ///
 /// All administrative actions (plugin lifecycle, permission changes, security
 /// events, settings changes) are persisted here for observability and compliance.
 /// The background writer inserts entries in batches; the frontend queries via
 /// Tauri commands.
+///
+/// Entries form a hash chain: each row stores the previous row's hash and a
+/// hash over its own fields, and the `audit_chain` table records the newest
+/// hash and the hash pruning last removed. [`AuditStore::verify`] uses these
+/// to detect edited, deleted, or truncated entries. Someone who rewrites the
+/// whole database consistently can't be detected locally; forwarding entries
+/// off the machine covers that.
 pub struct AuditStore {
     db: Mutex<Connection>,
 }
@@ -72,6 +207,34 @@ impl AuditStore {
         )
         .map_err(|e| format!("Failed to create severity index: {}", e))?;
 
+        // Hash chain columns. Rows from before the migration keep NULL hashes
+        // and are reported as unchained by verify().
+        {
+            let has_hash: bool = conn.prepare("SELECT hash FROM audit_log LIMIT 0").is_ok();
+            if !has_hash {
+                conn.execute_batch(
+                    "ALTER TABLE audit_log ADD COLUMN prev_hash TEXT;
+                     ALTER TABLE audit_log ADD COLUMN hash TEXT;",
+                )
+                .map_err(|e| format!("Migration error: {}", e))?;
+                log::info!("Audit store: migrated schema (added hash chain)");
+            }
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS audit_chain (
+                id         INTEGER PRIMARY KEY CHECK (id = 1),
+                anchor     TEXT    NOT NULL,
+                head_id    INTEGER,
+                head_hash  TEXT    NOT NULL
+            );",
+        )
+        .map_err(|e| format!("Failed to initialize audit chain: {}", e))?;
+        conn.execute(
+            "INSERT OR IGNORE INTO audit_chain (id, anchor, head_id, head_hash) VALUES (1, ?1, NULL, ?1)",
+            params![GENESIS_HASH],
+        )
+        .map_err(|e| format!("Failed to initialize audit chain: {}", e))?;
+
         Ok(Self {
             db: Mutex::new(conn),
         })
@@ -89,10 +252,20 @@ impl AuditStore {
             .map_err(|e| format!("Transaction error: {}", e))?;
 
         {
+            let mut prev_hash: String = tx
+                .query_row("SELECT head_hash FROM audit_chain WHERE id = 1", [], |row| {
+                    row.get(0)
+                })
+                .map_err(|e| format!("Chain head error: {}", e))?;
+            // Same format as the column default, so stored and hashed values match
+            let timestamp = chrono::Utc::now()
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string();
+
             let mut stmt = tx
                 .prepare_cached(
-                    "INSERT INTO audit_log (actor, source_id, severity, action, subject, result, details)
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    "INSERT INTO audit_log (timestamp, actor, source_id, severity, action, subject, result, details, prev_hash, hash)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
                 )
                 .map_err(|e| format!("Prepare error: {}", e))?;
 
@@ -101,18 +274,40 @@ impl AuditStore {
                     .details
                     .as_ref()
                     .map(|v| serde_json::to_string(v).unwrap_or_default());
+                let actor = entry.actor.as_str();
+                let hash = entry_hash(
+                    &prev_hash,
+                    &timestamp,
+                    &actor,
+                    entry.source_id.as_deref(),
+                    entry.severity.as_str(),
+                    &entry.action,
+                    entry.subject.as_deref(),
+                    entry.result.as_str(),
+                    details_json.as_deref(),
+                );
 
                 stmt.execute(params![
-                    entry.actor.as_str(),
+                    timestamp,
+                    actor,
                     entry.source_id,
                     entry.severity.as_str(),
                     entry.action,
                     entry.subject,
                     entry.result.as_str(),
                     details_json,
+                    prev_hash,
+                    hash,
                 ])
                 .map_err(|e| format!("Insert error: {}", e))?;
+                prev_hash = hash;
             }
+
+            tx.execute(
+                "UPDATE audit_chain SET head_id = ?1, head_hash = ?2 WHERE id = 1",
+                params![tx.last_insert_rowid(), prev_hash],
+            )
+            .map_err(|e| format!("Chain head error: {}", e))?;
         }
 
         tx.commit()
@@ -124,74 +319,11 @@ impl AuditStore {
     pub fn query(&self, q: &AuditQuery) -> Result<Vec<AuditLogRow>, String> {
         let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
 
-        let mut sql = String::from("SELECT id, timestamp, actor, source_id, severity, action, subject, result, details FROM audit_log");
-        let mut conditions: Vec<String> = Vec::new();
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-
-        if let Some(ref action) = q.action {
-            if action.contains('*') {
-                conditions.push(format!("action GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(action.clone()));
-            } else {
-                conditions.push(format!("action = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(action.clone()));
-            }
-        }
-
-        if let Some(ref actor) = q.actor {
-            if actor.contains('*') {
-                conditions.push(format!("actor GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(actor.clone()));
-            } else {
-                conditions.push(format!("actor = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(actor.clone()));
-            }
-        }
-
-        if let Some(ref source_id) = q.source_id {
-            if source_id.contains('*') {
-                conditions.push(format!("source_id GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(source_id.clone()));
-            } else {
-                conditions.push(format!("source_id = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(source_id.clone()));
-            }
-        }
-
-        if let Some(ref severity) = q.severity {
-            conditions.push(format!("severity = ?{}", param_values.len() + 1));
-            param_values.push(Box::new(severity.clone()));
-        }
-
-        if let Some(ref subject) = q.subject {
-            if subject.contains('*') {
-                conditions.push(format!("subject GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(subject.clone()));
-            } else {
-                conditions.push(format!("subject = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(subject.clone()));
-            }
-        }
-
-        if let Some(ref result) = q.result {
-            conditions.push(format!("result = ?{}", param_values.len() + 1));
-            param_values.push(Box::new(result.clone()));
-        }
-
-        if let Some(ref since) = q.since {
-            conditions.push(format!("timestamp >= ?{}", param_values.len() + 1));
-            param_values.push(Box::new(since.clone()));
-        }
-
-        if let Some(ref until) = q.until {
-            conditions.push(format!("timestamp <= ?{}", param_values.len() + 1));
-            param_values.push(Box::new(until.clone()));
-        }
-
-        if !conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            sql.push_str(&conditions.join(" AND "));
-        }
+        let (filter, param_values) = filter_sql(q);
+        let mut sql = format!(
+            "SELECT id, timestamp, actor, source_id, severity, action, subject, result, details FROM audit_log{}",
+            filter
+        );
 
         sql.push_str(" ORDER BY timestamp DESC");
 
@@ -238,100 +370,333 @@ impl AuditStore {
     pub fn count(&self, q: &AuditQuery) -> Result<usize, String> {
         let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
 
-        let mut sql = String::from("SELECT COUNT(*) FROM audit_log");
-        let mut conditions: Vec<String> = Vec::new();
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-
-        if let Some(ref action) = q.action {
-            if action.contains('*') {
-                conditions.push(format!("action GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(action.clone()));
-            } else {
-                conditions.push(format!("action = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(action.clone()));
+        let (filter, param_values) = filter_sql(q);
+        let sql = format!("SELECT COUNT(*) FROM audit_log{}", filter);
+
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+
+        let count: usize = conn
+            .query_row(&sql, params_refs.as_slice(), |row| row.get(0))
+            .map_err(|e| format!("Count error: {}", e))?;
+
+        Ok(count)
+    }
+
+    /// Aggregate the entries matching the filters in `q`. `limit` and
+    /// `offset` are ignored.
+    pub fn stats(&self, q: &AuditQuery) -> Result<AuditStats, String> {
+        let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let (filter, param_values) = filter_sql(q);
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+
+        let (total, oldest, newest) = conn
+            .query_row(
+                &format!("SELECT COUNT(*), MIN(timestamp), MAX(timestamp) FROM audit_log{}", filter),
+                params_refs.as_slice(),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| format!("Stats error: {}", e))?;
+
+        let group = |key: &str, order: &str, limit: Option<usize>| -> Result<Vec<AuditBucket>, String> {
+            let mut sql = format!(
+                "SELECT {key} AS k, COUNT(*) AS n FROM audit_log{filter} GROUP BY k ORDER BY {order}"
+            );
+            if let Some(limit) = limit {
+                sql.push_str(&format!(" LIMIT {}", limit));
             }
+            let mut stmt = conn
+                .prepare(&sql)
+                .map_err(|e| format!("Stats prepare error: {}", e))?;
+            let rows = stmt
+                .query_map(params_refs.as_slice(), |row| {
+                    Ok(AuditBucket {
+                        key: row.get(0)?,
+                        count: row.get(1)?,
+                    })
+                })
+                .map_err(|e| format!("Stats error: {}", e))?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Row error: {}", e))
+        };
+
+        Ok(AuditStats {
+            total,
+            oldest,
+            newest,
+            by_severity: group("severity", "n DESC, k", None)?,
+            by_result: group("result", "n DESC, k", None)?,
+            top_actions: group("action", "n DESC, k", Some(STATS_TOP))?,
+            top_actors: group("actor", "n DESC, k", Some(STATS_TOP))?,
+            per_day: group("substr(timestamp, 1, 10)", "k", None)?,
+        })
+    }
+
+    /// Approximate bytes of audit log per identity: the entry's `source_id`,
+    /// or its `subject` for entries with no source (e.g. a user starting a
+    /// plugin). Entries with neither are left out.
+    pub fn size_by_identity(&self) -> Result<HashMap<String, u64>, String> {
+        let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT coalesce(source_id, subject) AS k, SUM({ENTRY_SIZE_SQL})
+                 FROM audit_log WHERE k IS NOT NULL GROUP BY k"
+            ))
+            .map_err(|e| format!("Size prepare error: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))
+            .map_err(|e| format!("Size error: {}", e))?;
+        rows.map(|row| row.map(|(k, n)| (k, n.max(0) as u64)))
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Row error: {}", e))
+    }
+
+    /// Delete audit entries older than the given TTL. Returns the number of deleted rows.
+    ///
+    /// Deletes a prefix of the log by ID, and moves the chain anchor to the
+    /// last deleted entry's hash so the remaining chain still verifies.
+    pub fn cleanup_old(&self, ttl: Duration) -> Result<usize, String> {
+        let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let cutoff = chrono::Utc::now() - chrono::Duration::from_std(ttl).unwrap_or_default();
+        let cutoff_str = cutoff.to_rfc3339();
+
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Transaction error: {}", e))?;
+        let last: Option<(i64, Option<String>)> = tx
+            .query_row(
+                "SELECT id, hash FROM audit_log WHERE timestamp < ?1 ORDER BY id DESC LIMIT 1",
+                params![cutoff_str],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Cleanup error: {}", e))?;
+        let Some((last_id, last_hash)) = last else {
+            return Ok(0);
+        };
+
+        let deleted = delete_through(&tx, last_id, last_hash)?;
+        tx.commit().map_err(|e| format!("Commit error: {}", e))?;
+
+        Ok(deleted)
+    }
+
+    /// Apply a retention policy: drop entries past the age limit, then the
+    /// oldest entries until the log is within the size limit.
+    pub fn compact(&self, policy: &AuditRetention) -> Result<AuditCompaction, String> {
+        let mut report = AuditCompaction {
+            expired: match policy.max_age() {
+                Some(ttl) => self.cleanup_old(ttl)?,
+                None => 0,
+            },
+            trimmed: 0,
+        };
+        if policy.max_bytes == 0 {
+            return Ok(report);
         }
 
-        if let Some(ref actor) = q.actor {
-            if actor.contains('*') {
-                conditions.push(format!("actor GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(actor.clone()));
-            } else {
-                conditions.push(format!("actor = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(actor.clone()));
-            }
+        let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|e| format!("Transaction error: {}", e))?;
+        let cutoff: Option<(i64, Option<String>)> = tx
+            .query_row(
+                &format!(
+                    "SELECT id, hash FROM (
+                         SELECT id, hash, SUM({ENTRY_SIZE_SQL}) OVER (ORDER BY id DESC) AS total
+                         FROM audit_log
+                     ) WHERE total > ?1 ORDER BY id DESC LIMIT 1"
+                ),
+                params![policy.max_bytes as i64],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| format!("Find size cutoff: {}", e))?;
+        if let Some((last_id, last_hash)) = cutoff {
+            report.trimmed = delete_through(&tx, last_id, last_hash)?;
         }
+        tx.commit().map_err(|e| format!("Commit error: {}", e))?;
 
-        if let Some(ref source_id) = q.source_id {
-            if source_id.contains('*') {
-                conditions.push(format!("source_id GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(source_id.clone()));
-            } else {
-                conditions.push(format!("source_id = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(source_id.clone()));
-            }
+        if report.expired + report.trimmed > 0 {
+            // Fold the WAL back into the database so freed pages get reused.
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                .map_err(|e| format!("Checkpoint: {}", e))?;
         }
+        Ok(report)
+    }
+
+    /// Walk the hash chain and report the first entry that was modified,
+    /// deleted, or truncated away.
+    pub fn verify(&self) -> Result<AuditVerification, String> {
+        let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let (anchor, head_id, head_hash): (String, Option<i64>, String) = conn
+            .query_row(
+                "SELECT anchor, head_id, head_hash FROM audit_chain WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .map_err(|e| format!("Chain head error: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, timestamp, actor, source_id, severity, action, subject, result, details, prev_hash, hash
+                 FROM audit_log ORDER BY id",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let mut rows = stmt.query([]).map_err(|e| format!("Query error: {}", e))?;
 
-        if let Some(ref severity) = q.severity {
-            conditions.push(format!("severity = ?{}", param_values.len() + 1));
-            param_values.push(Box::new(severity.clone()));
+        let mut report = AuditVerification {
+            ok: true,
+            verified: 0,
+            unchained: 0,
+            issue: None,
+        };
+        let mut expected_prev = anchor;
+        let mut last: Option<(i64, String)> = None;
+        while let Some(row) = rows.next().map_err(|e| format!("Row error: {}", e))? {
+            let id: i64 = row.get(0).map_err(|e| e.to_string())?;
+            let text = |i: usize| -> Result<Option<String>, String> {
+                row.get(i).map_err(|e| e.to_string())
+            };
+            let (Some(prev_hash), Some(hash)) = (text(9)?, text(10)?) else {
+                if last.is_none() {
+                    report.unchained += 1;
+                    continue;
+                }
+                // A NULL hash after chained rows was written by something else
+                report.issue = Some(ChainIssue::Modified { id });
+                break;
+            };
+
+            if prev_hash != expected_prev {
+                report.issue = Some(ChainIssue::Missing { before_id: id });
+                break;
+            }
+            let computed = entry_hash(
+                &prev_hash,
+                &text(1)?.unwrap_or_default(),
+                &text(2)?.unwrap_or_default(),
+                text(3)?.as_deref(),
+                &text(4)?.unwrap_or_default(),
+                &text(5)?.unwrap_or_default(),
+                text(6)?.as_deref(),
+                &text(7)?.unwrap_or_default(),
+                text(8)?.as_deref(),
+            );
+            if computed != hash {
+                report.issue = Some(ChainIssue::Modified { id });
+                break;
+            }
+            report.verified += 1;
+            expected_prev = hash.clone();
+            last = Some((id, hash));
         }
 
-        if let Some(ref subject) = q.subject {
-            if subject.contains('*') {
-                conditions.push(format!("subject GLOB ?{}", param_values.len() + 1));
-                param_values.push(Box::new(subject.clone()));
-            } else {
-                conditions.push(format!("subject = ?{}", param_values.len() + 1));
-                param_values.push(Box::new(subject.clone()));
+        if report.issue.is_none() {
+            let intact = match &last {
+                Some((id, hash)) => head_id == Some(*id) && *hash == head_hash,
+                None => expected_prev == head_hash,
+            };
+            if !intact {
+                report.issue = Some(ChainIssue::Truncated {
+                    after_id: last.map(|(id, _)| id),
+                });
             }
         }
+        report.ok = report.issue.is_none();
+        Ok(report)
+    }
+}
 
-        if let Some(ref result) = q.result {
-            conditions.push(format!("result = ?{}", param_values.len() + 1));
-            param_values.push(Box::new(result.clone()));
+/// WHERE clause (empty when unfiltered) and its parameters for the filters
+/// in `q`. `limit` and `offset` are left to the caller.
+fn filter_sql(q: &AuditQuery) -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
+    let mut conditions: Vec<String> = Vec::new();
+    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+    if let Some(ref action) = q.action {
+        if action.contains('*') {
+            conditions.push(format!("action GLOB ?{}", param_values.len() + 1));
+            param_values.push(Box::new(action.clone()));
+        } else {
+            conditions.push(format!("action = ?{}", param_values.len() + 1));
+            param_values.push(Box::new(action.clone()));
         }
+    }
 
-        if let Some(ref since) = q.since {
-            conditions.push(format!("timestamp >= ?{}", param_values.len() + 1));
-            param_values.push(Box::new(since.clone()));
+    if let Some(ref actor) = q.actor {
+        if actor.contains('*') {
+            conditions.push(format!("actor GLOB ?{}", param_values.len() + 1));
+            param_values.push(Box::new(actor.clone()));
+        } else {
+            conditions.push(format!("actor = ?{}", param_values.len() + 1));
+            param_values.push(Box::new(actor.clone()));
         }
+    }
 
-        if let Some(ref until) = q.until {
-            conditions.push(format!("timestamp <= ?{}", param_values.len() + 1));
-            param_values.push(Box::new(until.clone()));
+    if let Some(ref source_id) = q.source_id {
+        if source_id.contains('*') {
+            conditions.push(format!("source_id GLOB ?{}", param_values.len() + 1));
+            param_values.push(Box::new(source_id.clone()));
+        } else {
+            conditions.push(format!("source_id = ?{}", param_values.len() + 1));
+            param_values.push(Box::new(source_id.clone()));
         }
+    }
 
-        if !conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            sql.push_str(&conditions.join(" AND "));
+    if let Some(ref severity) = q.severity {
+        conditions.push(format!("severity = ?{}", param_values.len() + 1));
+        param_values.push(Box::new(severity.clone()));
+    }
+
+    if let Some(ref subject) = q.subject {
+        if subject.contains('*') {
+            conditions.push(format!("subject GLOB ?{}", param_values.len() + 1));
+            param_values.push(Box::new(subject.clone()));
+        } else {
+            conditions.push(format!("subject = ?{}", param_values.len() + 1));
+            param_values.push(Box::new(subject.clone()));
         }
+    }
 
-        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
-            param_values.iter().map(|p| p.as_ref()).collect();
+    if let Some(ref result) = q.result {
+        conditions.push(format!("result = ?{}", param_values.len() + 1));
+        param_values.push(Box::new(result.clone()));
+    }
 
-        let count: usize = conn
-            .query_row(&sql, params_refs.as_slice(), |row| row.get(0))
-            .map_err(|e| format!("Count error: {}", e))?;
+    if let Some(ref since) = q.since {
+        conditions.push(format!("timestamp >= ?{}", param_values.len() + 1));
+        param_values.push(Box::new(since.clone()));
+    }
 
-        Ok(count)
+    if let Some(ref until) = q.until {
+        conditions.push(format!("timestamp <= ?{}", param_values.len() + 1));
+        param_values.push(Box::new(until.clone()));
     }
 
-    /// Delete audit entries older than the given TTL. Returns the number of deleted rows.
-    pub fn cleanup_old(&self, ttl: Duration) -> Result<usize, String> {
-        let conn = self.db.lock().map_err(|e| format!("Lock error: {}", e))?;
-        let cutoff = chrono::Utc::now() - chrono::Duration::from_std(ttl).unwrap_or_default();
-        let cutoff_str = cutoff.to_rfc3339();
+    if conditions.is_empty() {
+        (String::new(), param_values)
+    } else {
+        (format!(" WHERE {}", conditions.join(" AND ")), param_values)
+    }
+}
 
-        let deleted = conn
-            .execute(
-                "DELETE FROM audit_log WHERE timestamp < ?1",
-                params![cutoff_str],
-            )
+/// Delete entries up to and including `last_id`, and move the chain anchor to
+/// the last deleted entry's hash so the remaining chain still verifies.
+fn delete_through(
+    tx: &rusqlite::Transaction<'_>,
+    last_id: i64,
+    last_hash: Option<String>,
+) -> Result<usize, String> {
+    let deleted = tx
+        .execute("DELETE FROM audit_log WHERE id <= ?1", params![last_id])
+        .map_err(|e| format!("Cleanup error: {}", e))?;
+    if let Some(hash) = last_hash {
+        tx.execute("UPDATE audit_chain SET anchor = ?1 WHERE id = 1", params![hash])
             .map_err(|e| format!("Cleanup error: {}", e))?;
-
-        Ok(deleted)
     }
+    Ok(deleted)
 }
 
 #[cfg(test)]
@@ -513,6 +878,173 @@ mod tests {
         assert_eq!(store.count(&AuditQuery::default()).unwrap(), 0);
     }
 
+    fn entry(action: &str) -> AuditEntry {
+        AuditEntry {
+            actor: AuditActor::User,
+            source_id: None,
+            severity: AuditSeverity::Info,
+            action: action.into(),
+            subject: None,
+            result: AuditResult::Success,
+            details: Some(serde_json::json!({"n": 1})),
+        }
+    }
+
+    #[test]
+    fn verify_detects_tampering() {
+        let store = temp_store();
+        assert!(store.verify().unwrap().ok);
+
+        store
+            .insert_batch(&[entry("a.one"), entry("a.two")])
+            .unwrap();
+        store.insert_batch(&[entry("a.three")]).unwrap();
+        let report = store.verify().unwrap();
+        assert!(report.ok);
+        assert_eq!(report.verified, 3);
+
+        let sql = |sql: &str| {
+            store.db.lock().unwrap().execute_batch(sql).unwrap();
+        };
+
+        sql("UPDATE audit_log SET result = 'failure' WHERE id = 2");
+        assert_eq!(
+            store.verify().unwrap().issue,
+            Some(ChainIssue::Modified { id: 2 })
+        );
+        sql("UPDATE audit_log SET result = 'success' WHERE id = 2");
+        assert!(store.verify().unwrap().ok);
+
+        sql("DELETE FROM audit_log WHERE id = 3");
+        assert_eq!(
+            store.verify().unwrap().issue,
+            Some(ChainIssue::Truncated { after_id: Some(2) })
+        );
+
+        sql("DELETE FROM audit_log WHERE id = 1");
+        assert_eq!(
+            store.verify().unwrap().issue,
+            Some(ChainIssue::Missing { before_id: 2 })
+        );
+    }
+
+    #[test]
+    fn chain_survives_pruning() {
+        let store = temp_store();
+        store.insert_batch(&[entry("a.one"), entry("a.two")]).unwrap();
+        store
+            .db
+            .lock()
+            .unwrap()
+            .execute_batch("UPDATE audit_log SET timestamp = '2000-01-01T00:00:00.000Z' WHERE id = 1")
+            .unwrap();
+        // Backdating changed entry 1, but pruning removes it and anchors the
+        // chain at its stored hash
+        assert_eq!(store.cleanup_old(Duration::from_secs(3600)).unwrap(), 1);
+        store.insert_batch(&[entry("a.three")]).unwrap();
+
+        let report = store.verify().unwrap();
+        assert!(report.ok, "{:?}", report.issue);
+        assert_eq!(report.verified, 2);
+    }
+
+    #[test]
+    fn compact_trims_to_size() {
+        let store = temp_store();
+        let big = |action: &str| AuditEntry {
+            details: Some(serde_json::json!({"blob": "x".repeat(4000)})),
+            ..entry(action)
+        };
+        store.insert_batch(&[big("a.one"), big("a.two"), big("a.three")]).unwrap();
+
+        let policy = AuditRetention {
+            max_age_days: 30,
+            max_bytes: 10_000,
+        };
+        let report = store.compact(&policy).unwrap();
+        assert_eq!((report.expired, report.trimmed), (0, 1));
+
+        let rows = store.query(&AuditQuery::default()).unwrap();
+        let mut actions: Vec<&str> = rows.iter().map(|r| r.action.as_str()).collect();
+        actions.sort();
+        assert_eq!(actions, ["a.three", "a.two"]);
+        assert!(store.verify().unwrap().ok);
+
+        // Within limits, nothing more goes
+        assert_eq!(store.compact(&policy).unwrap().trimmed, 0);
+        let unlimited = AuditRetention {
+            max_age_days: 0,
+            max_bytes: 0,
+        };
+        assert_eq!(store.compact(&unlimited).unwrap().trimmed, 0);
+    }
+
+    #[test]
+    fn size_by_identity_prefers_source() {
+        let store = temp_store();
+        let by_plugin = AuditEntry {
+            actor: AuditActor::Plugin { id: "com.example.a".into() },
+            source_id: Some("com.example.a".into()),
+            subject: Some("settings".into()),
+            ..entry("storage.write")
+        };
+        let on_plugin = AuditEntry {
+            subject: Some("com.example.a".into()),
+            ..entry("plugin.start")
+        };
+        store
+            .insert_batch(&[by_plugin, on_plugin, entry("settings.update")])
+            .unwrap();
+
+        let sizes = store.size_by_identity().unwrap();
+        assert_eq!(sizes.len(), 1);
+        assert!(sizes["com.example.a"] > 0);
+    }
+
+    #[test]
+    fn stats_aggregate_matching_entries() {
+        let store = temp_store();
+        let failed = AuditEntry {
+            result: AuditResult::Failure,
+            ..entry("plugin.start")
+        };
+        store
+            .insert_batch(&[entry("plugin.start"), entry("plugin.start"), failed, entry("settings.update")])
+            .unwrap();
+
+        let stats = store.stats(&AuditQuery::default()).unwrap();
+        assert_eq!(stats.total, 4);
+        assert_eq!(
+            stats.top_actions,
+            [
+                AuditBucket { key: "plugin.start".into(), count: 3 },
+                AuditBucket { key: "settings.update".into(), count: 1 },
+            ]
+        );
+        assert_eq!(stats.by_result[0], AuditBucket { key: "success".into(), count: 3 });
+        assert_eq!(stats.per_day.len(), 1);
+        assert_eq!(stats.per_day[0].count, 4);
+        assert_eq!(stats.oldest, stats.newest);
+
+        let q = AuditQuery {
+            action: Some("plugin.*".into()),
+            result: Some("failure".into()),
+            ..Default::default()
+        };
+        let stats = store.stats(&q).unwrap();
+        assert_eq!(stats.total, 1);
+        assert_eq!(stats.top_actors, [AuditBucket { key: "user".into(), count: 1 }]);
+
+        let empty = store
+            .stats(&AuditQuery {
+                action: Some("nothing".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(empty.total, 0);
+        assert!(empty.oldest.is_none() && empty.per_day.is_empty());
+    }
+
     #[test]
     fn empty_batch() {
         let store = temp_store();
@@ -1,55 +1,84 @@
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 
-use tokio::sync::mpsc;
+use serde::Serialize;
+use tauri::Manager;
+use tokio::sync::{mpsc, Notify};
 use tokio::time::interval;
 
+use super::alerts::{AlertEvaluator, AlertRule, AuditAlert, ALERT_EVENT_TYPE, ALERT_SOURCE};
+use super::forward::{ForwardConfig, ForwardRecord, Sink};
+use super::redact::{RedactionPolicy, Redactor};
 use super::store::AuditStore;
 use super::AuditEntry;
 
 const CHANNEL_CAPACITY: usize = 1024;
 const BATCH_SIZE: usize = 50;
 const FLUSH_INTERVAL: Duration = Duration::from_millis(500);
-const CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
-const DEFAULT_TTL: Duration = Duration::from_secs(30 * 86400); // 30 days
+const FORWARD_BATCH_SIZE: usize = 100;
+const MAX_FORWARD_BACKOFF: Duration = Duration::from_secs(300);
 
 /// Cheaply cloneable handle for recording audit entries.
 ///
 /// Callers use `record()` to push entries into a bounded channel.
 /// A background task drains the channel and batch-inserts into SQLite.
+/// Sensitive details are masked before entries enter the channel.
 #[derive(Clone)]
 pub struct AuditWriter {
     tx: mpsc::Sender<AuditEntry>,
+    redactor: Arc<RwLock<Redactor>>,
 }
 
 impl AuditWriter {
     /// Record an audit entry. Non-blocking — drops the entry if the channel is full.
-    pub fn record(&self, entry: AuditEntry) {
+    pub fn record(&self, mut entry: AuditEntry) {
+        self.redactor
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .apply(&mut entry);
         if self.tx.try_send(entry).is_err() {
             log::warn!("Audit channel full, entry dropped");
         }
     }
+
+    /// Replace the redaction policy for entries recorded from now on, by
+    /// every clone of this handle.
+    pub fn set_redaction(&self, policy: &RedactionPolicy) {
+        *self.redactor.write().unwrap_or_else(|e| e.into_inner()) = Redactor::new(policy);
+    }
 }
 
 /// Create the background writer and return the AuditWriter handle.
 ///
 /// The caller is responsible for spawning `run()` with `tauri::async_runtime::spawn`.
-/// This returns both the writer handle and the future to spawn.
-pub fn create(store: Arc<AuditStore>) -> (AuditWriter, impl std::future::Future<Output = ()>) {
+/// This returns both the writer handle and the future to spawn. Every flushed
+/// batch is also handed to `forwarder` and checked against `alerts`.
+pub fn create(
+    store: Arc<AuditStore>,
+    forwarder: Arc<AuditForwarder>,
+    alerts: Arc<AlertWatcher>,
+) -> (AuditWriter, impl std::future::Future<Output = ()>) {
     let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
-    let writer = AuditWriter { tx };
-    let future = run(rx, store);
+    let writer = AuditWriter {
+        tx,
+        redactor: Arc::new(RwLock::new(Redactor::new(&RedactionPolicy::default()))),
+    };
+    let future = run(rx, store, forwarder, alerts);
     (writer, future)
 }
 
-async fn run(mut rx: mpsc::Receiver<AuditEntry>, store: Arc<AuditStore>) {
+async fn run(
+    mut rx: mpsc::Receiver<AuditEntry>,
+    store: Arc<AuditStore>,
+    forwarder: Arc<AuditForwarder>,
+    alerts: Arc<AlertWatcher>,
+) {
     let mut buffer: Vec<AuditEntry> = Vec::with_capacity(BATCH_SIZE);
     let mut flush_tick = interval(FLUSH_INTERVAL);
-    let mut cleanup_tick = interval(CLEANUP_INTERVAL);
 
-    // Consume the first immediate ticks
+    // Consume the first immediate tick
     flush_tick.tick().await;
-    cleanup_tick.tick().await;
 
     loop {
         tokio::select! {
@@ -58,13 +87,13 @@ async fn run(mut rx: mpsc::Receiver<AuditEntry>, store: Arc<AuditStore>) {
                     Some(e) => {
                         buffer.push(e);
                         if buffer.len() >= BATCH_SIZE {
-                            flush(&store, &mut buffer);
+                            flush(&store, &forwarder, &alerts, &mut buffer);
                         }
                     }
                     None => {
                         // Channel closed — flush remaining and exit
                         if !buffer.is_empty() {
-                            flush(&store, &mut buffer);
+                            flush(&store, &forwarder, &alerts, &mut buffer);
                         }
                         break;
                     }
@@ -72,23 +101,281 @@ async fn run(mut rx: mpsc::Receiver<AuditEntry>, store: Arc<AuditStore>) {
             }
             _ = flush_tick.tick() => {
                 if !buffer.is_empty() {
-                    flush(&store, &mut buffer);
-                }
-            }
-            _ = cleanup_tick.tick() => {
-                match store.cleanup_old(DEFAULT_TTL) {
-                    Ok(0) => {}
-                    Ok(n) => log::info!("Audit cleanup: purged {} old entries", n),
-                    Err(e) => log::error!("Audit cleanup failed: {}", e),
+                    flush(&store, &forwarder, &alerts, &mut buffer);
                 }
             }
         }
     }
 }
 
-fn flush(store: &AuditStore, buffer: &mut Vec<AuditEntry>) {
+fn flush(
+    store: &AuditStore,
+    forwarder: &AuditForwarder,
+    alerts: &AlertWatcher,
+    buffer: &mut Vec<AuditEntry>,
+) {
     if let Err(e) = store.insert_batch(buffer) {
         log::error!("Audit batch insert failed: {}", e);
     }
+    forwarder.enqueue(buffer);
+    alerts.observe(buffer);
     buffer.clear();
 }
+
+/// Checks written entries against the alert rules, and raises an OS
+/// notification and a [`ALERT_EVENT_TYPE`] event for each alert. Managed as
+/// Tauri state.
+pub struct AlertWatcher {
+    evaluator: Mutex<AlertEvaluator>,
+    app: tauri::AppHandle,
+}
+
+impl AlertWatcher {
+    pub fn new(app: tauri::AppHandle, rules: &[AlertRule]) -> Self {
+        Self {
+            evaluator: Mutex::new(AlertEvaluator::new(rules)),
+            app,
+        }
+    }
+
+    /// Replace the rules. Counts toward the old rules are discarded.
+    pub fn apply(&self, rules: &[AlertRule]) {
+        *self.evaluator.lock().unwrap_or_else(|e| e.into_inner()) = AlertEvaluator::new(rules);
+    }
+
+    fn observe(&self, entries: &[AuditEntry]) {
+        let now = chrono::Utc::now();
+        let alerts: Vec<AuditAlert> = {
+            let mut evaluator = self.evaluator.lock().unwrap_or_else(|e| e.into_inner());
+            if evaluator.is_empty() {
+                return;
+            }
+            entries
+                .iter()
+                .flat_map(|entry| evaluator.observe(entry, now))
+                .collect()
+        };
+        for alert in alerts {
+            self.fire(alert);
+        }
+    }
+
+    fn fire(&self, alert: AuditAlert) {
+        log::warn!("Audit alert '{}': {}", alert.rule, alert.summary());
+
+        crate::notification::notify(
+            &self.app,
+            "audit.alert",
+            format!("Nexus alert: {}", alert.rule),
+            alert.summary(),
+        );
+
+        let mut builder = crate::event_bus::cloud_event::CloudEvent::builder()
+            .source(ALERT_SOURCE)
+            .event_type(ALERT_EVENT_TYPE);
+        if let Some(group) = &alert.group {
+            builder = builder.subject(group);
+        }
+        let event = match serde_json::to_value(&alert)
+            .map_err(|e| e.to_string())
+            .and_then(|data| builder.data(data).build())
+        {
+            Ok(event) => event,
+            Err(e) => {
+                log::warn!("Failed to build audit alert event: {}", e);
+                return;
+            }
+        };
+        if let Some(dispatch) = self.app.try_state::<crate::event_bus::Dispatch>() {
+            let dispatch = dispatch.inner().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = dispatch.publish(event).await {
+                    log::warn!("Failed to publish audit alert: {}", e);
+                }
+            });
+        }
+    }
+}
+
+/// Forwarding counters shown in settings.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ForwardStatus {
+    pub running: bool,
+    /// Entries delivered since forwarding started.
+    pub forwarded: u64,
+    /// Entries waiting to be sent.
+    pub pending: usize,
+    /// Entries dropped because the queue was full.
+    pub dropped: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Default)]
+struct ForwardQueue {
+    /// None while forwarding is off, so nothing queues up.
+    capacity: Option<usize>,
+    /// Records with a sequence number, so a sent batch can be removed even if
+    /// the queue overflowed while it was in flight.
+    records: VecDeque<(u64, ForwardRecord)>,
+    next_seq: u64,
+}
+
+impl ForwardQueue {
+    fn push(&mut self, record: ForwardRecord) {
+        self.next_seq += 1;
+        self.records.push_back((self.next_seq, record));
+    }
+
+    /// Drop the oldest records beyond `capacity`. Returns how many.
+    fn trim(&mut self, capacity: usize) -> usize {
+        let excess = self.records.len().saturating_sub(capacity);
+        self.records.drain(..excess);
+        excess
+    }
+}
+
+/// Forwards audit entries to a syslog server or HTTPS collector. Entries
+/// queue in memory and go out in batches; a failed batch is retried with
+/// backoff until it succeeds, and the oldest entries are dropped once the
+/// queue is full. Managed as Tauri state.
+pub struct AuditForwarder {
+    queue: Arc<Mutex<ForwardQueue>>,
+    wake: Arc<Notify>,
+    status: Arc<RwLock<ForwardStatus>>,
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl AuditForwarder {
+    pub fn new() -> Self {
+        Self {
+            queue: Arc::new(Mutex::new(ForwardQueue::default())),
+            wake: Arc::new(Notify::new()),
+            status: Arc::new(RwLock::new(ForwardStatus::default())),
+            task: Mutex::new(None),
+        }
+    }
+
+    pub fn status(&self) -> ForwardStatus {
+        let mut status = self.status.read().expect("forward status lock poisoned").clone();
+        status.pending = self.lock_queue().records.len();
+        status
+    }
+
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, ForwardQueue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Stop forwarding, if running, and start again with `config` when
+    /// enabled. Queued entries carry over to the new sink.
+    pub fn apply(&self, config: ForwardConfig) {
+        let mut task = self.task.lock().expect("forward task lock poisoned");
+        if let Some(running) = task.take() {
+            running.abort();
+        }
+        {
+            let mut queue = self.lock_queue();
+            if config.enabled {
+                queue.capacity = Some(config.max_pending);
+                queue.trim(config.max_pending);
+            } else {
+                queue.capacity = None;
+                queue.records.clear();
+            }
+        }
+        *self.status.write().expect("forward status lock poisoned") = ForwardStatus {
+            running: config.enabled,
+            ..Default::default()
+        };
+        if config.enabled {
+            *task = Some(tauri::async_runtime::spawn(forward(
+                config,
+                self.queue.clone(),
+                self.wake.clone(),
+                self.status.clone(),
+            )));
+        }
+    }
+
+    fn enqueue(&self, entries: &[AuditEntry]) {
+        let now = chrono::Utc::now();
+        let dropped = {
+            let mut queue = self.lock_queue();
+            let Some(capacity) = queue.capacity else {
+                return;
+            };
+            for entry in entries {
+                queue.push(ForwardRecord::new(entry, now));
+            }
+            queue.trim(capacity)
+        };
+        if dropped > 0 {
+            log::warn!("Audit forwarding queue full, dropped {} entries", dropped);
+            self.status.write().expect("forward status lock poisoned").dropped += dropped as u64;
+        }
+        self.wake.notify_one();
+    }
+}
+
+impl Default for AuditForwarder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Delay before retry `attempt` (1-based): 1s, 2s, 4s, ... capped at five minutes.
+fn forward_retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.saturating_sub(1).min(9)).min(MAX_FORWARD_BACKOFF)
+}
+
+async fn forward(
+    config: ForwardConfig,
+    queue: Arc<Mutex<ForwardQueue>>,
+    wake: Arc<Notify>,
+    status: Arc<RwLock<ForwardStatus>>,
+) {
+    let set_error = |e: String| {
+        status.write().expect("forward status lock poisoned").last_error = Some(e);
+    };
+    let mut sink = match Sink::new(&config.sink) {
+        Ok(sink) => sink,
+        Err(e) => {
+            log::error!("Audit forwarding: {}", e);
+            set_error(e);
+            return;
+        }
+    };
+
+    let mut attempt = 0;
+    loop {
+        // Copy the batch out so entries recorded meanwhile aren't blocked
+        let (last_seq, batch): (u64, Vec<ForwardRecord>) = {
+            let queue = queue.lock().unwrap_or_else(|e| e.into_inner());
+            let batch: Vec<_> = queue.records.iter().take(FORWARD_BATCH_SIZE).collect();
+            match batch.last() {
+                Some((seq, _)) => (*seq, batch.iter().map(|(_, r)| r.clone()).collect()),
+                None => (0, Vec::new()),
+            }
+        };
+        if batch.is_empty() {
+            wake.notified().await;
+            continue;
+        }
+
+        match sink.send(&batch).await {
+            Ok(()) => {
+                attempt = 0;
+                let mut queue = queue.lock().unwrap_or_else(|e| e.into_inner());
+                while queue.records.front().is_some_and(|(seq, _)| *seq <= last_seq) {
+                    queue.records.pop_front();
+                }
+                status.write().expect("forward status lock poisoned").forwarded += batch.len() as u64;
+            }
+            Err(e) => {
+                attempt += 1;
+                log::warn!("Audit forwarding: attempt {} failed: {}", attempt, e);
+                set_error(e);
+                tokio::time::sleep(forward_retry_delay(attempt)).await;
+            }
+        }
+    }
+}
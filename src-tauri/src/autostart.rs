@@ -0,0 +1,221 @@
+//! Launching Nexus at login, so the MCP gateway and plugins are up right
+//! after boot.
+//!
+//! The OS entry is the source of truth, not settings: users can remove it
+//! from their system settings without Nexus knowing. Each platform uses its
+//! own mechanism:
+//!
+//! - macOS: a Launch Agent in `~/Library/LaunchAgents`
+//! - Windows: a value under the user's `Run` registry key
+//! - Linux: an XDG autostart entry in `~/.config/autostart`
+//!
+//! Login launches pass [`HIDDEN_ARG`], which keeps the window in the tray.
+
+use std::path::PathBuf;
+
+use crate::error::{NexusError, NexusResult};
+
+/// Argument on login launches. The window starts hidden.
+pub const HIDDEN_ARG: &str = "--autostart";
+
+/// Whether this process was launched at login.
+pub fn launched_at_login() -> bool {
+    std::env::args().any(|a| a == HIDDEN_ARG)
+}
+
+/// The executable to launch. AppImages run from a temporary mount, so the
+/// image itself is launched instead.
+fn executable() -> NexusResult<PathBuf> {
+    if cfg!(target_os = "linux") {
+        if let Some(image) = std::env::var_os("APPIMAGE") {
+            return Ok(PathBuf::from(image));
+        }
+    }
+    Ok(std::env::current_exe()?)
+}
+
+/// Re-register after the executable moved (e.g. a new AppImage), so the
+/// entry doesn't point at a stale path.
+pub fn refresh(identifier: &str) {
+    if is_enabled(identifier) {
+        if let Err(e) = enable(identifier) {
+            log::warn!("Failed to refresh launch at login: {}", e);
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn agent_path(identifier: &str) -> NexusResult<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| NexusError::Other("No home directory".into()))?;
+    Ok(home.join("Library/LaunchAgents").join(format!("{}.plist", identifier)))
+}
+
+#[cfg(target_os = "macos")]
+pub fn is_enabled(identifier: &str) -> bool {
+    agent_path(identifier).is_ok_and(|p| p.exists())
+}
+
+#[cfg(target_os = "macos")]
+pub fn enable(identifier: &str) -> NexusResult<()> {
+    let path = agent_path(identifier)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let plist = launch_agent(identifier, &executable()?.display().to_string());
+    crate::util::atomic_write(&path, plist.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn disable(identifier: &str) -> NexusResult<()> {
+    let path = agent_path(identifier)?;
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn launch_agent(identifier: &str, exe: &str) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{}</string>
+    <string>{}</string>
+  </array>
+  <key>RunAtLoad</key>
+  <true/>
+  <key>ProcessType</key>
+  <string>Interactive</string>
+</dict>
+</plist>
+"#,
+        escape(identifier),
+        escape(exe),
+        HIDDEN_ARG
+    )
+}
+
+#[cfg(target_os = "windows")]
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+/// Run `reg.exe` without flashing a console window.
+#[cfg(target_os = "windows")]
+fn reg(args: &[&str]) -> std::io::Result<std::process::Output> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    std::process::Command::new("reg")
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_enabled(identifier: &str) -> bool {
+    reg(&["query", RUN_KEY, "/v", identifier]).is_ok_and(|o| o.status.success())
+}
+
+#[cfg(target_os = "windows")]
+pub fn enable(identifier: &str) -> NexusResult<()> {
+    let command = format!("\"{}\" {}", executable()?.display(), HIDDEN_ARG);
+    let output = reg(&["add", RUN_KEY, "/v", identifier, "/t", "REG_SZ", "/d", &command, "/f"])?;
+    if !output.status.success() {
+        return Err(NexusError::Other(format!(
+            "Failed to add the Run key: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+pub fn disable(identifier: &str) -> NexusResult<()> {
+    if !is_enabled(identifier) {
+        return Ok(());
+    }
+    let output = reg(&["delete", RUN_KEY, "/v", identifier, "/f"])?;
+    if !output.status.success() {
+        return Err(NexusError::Other(format!(
+            "Failed to remove the Run key: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn entry_path(identifier: &str) -> NexusResult<PathBuf> {
+    let config = dirs::config_dir().ok_or_else(|| NexusError::Other("No config directory".into()))?;
+    Ok(config.join("autostart").join(format!("{}.desktop", identifier)))
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn is_enabled(identifier: &str) -> bool {
+    entry_path(identifier).is_ok_and(|p| p.exists())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn enable(identifier: &str) -> NexusResult<()> {
+    let path = entry_path(identifier)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let entry = desktop_entry(&executable()?.display().to_string());
+    crate::util::atomic_write(&path, entry.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+pub fn disable(identifier: &str) -> NexusResult<()> {
+    let path = entry_path(identifier)?;
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn desktop_entry(exe: &str) -> String {
+    // Exec arguments are quoted per the desktop entry spec
+    let mut quoted = String::with_capacity(exe.len() + 2);
+    quoted.push('"');
+    for c in exe.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=Nexus\n\
+         Exec={} {}\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n",
+        quoted, HIDDEN_ARG
+    )
+}
+
+#[cfg(all(test, not(any(target_os = "macos", target_os = "windows"))))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn desktop_entry_quotes_exec() {
+        let entry = desktop_entry("/home/me/Apps/Nexus $beta.AppImage");
+        assert!(entry.contains("Exec=\"/home/me/Apps/Nexus \\$beta.AppImage\" --autostart\n"));
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+    }
+}
@@ -0,0 +1,805 @@
+//! Full backup and restore of Nexus state.
+//!
+//! A backup is a gzipped tar holding the data directory under `data/`,
+//! optionally each plugin's data volume under `volumes/`, and a
+//! `backup.json` manifest recording every file's size and SHA-256. The
+//! manifest is written last and the finished archive is read back against
+//! it; restoring checks the whole archive the same way before anything is
+//! replaced.
+//!
+//! Restoring doesn't touch the data directory while Nexus runs, since its
+//! stores hold their state in memory and would write it back over the
+//! restored files. The archive is staged in `restore-pending/` instead, and
+//! the next launch applies it before any store loads. Volumes wait there
+//! too, so they're only replaced along with the data that goes with them.
+//!
+//! The audit and event databases stay out: they are open while Nexus runs,
+//! so a file copy could catch them mid-write. The state database (plugins,
+//! permissions, settings) goes in as a snapshot taken through SQLite, and is
+//! restored by copying its records into the live one. The registry cache is fetched
+//! again on demand. Credentials in the OS keychain aren't files and stay
+//! out too. The key the OAuth and API key stores are encrypted with never
+//! goes in as is: given a passphrase, the backup carries it encrypted with
+//! that, and restoring with the passphrase brings it back. Without it,
+//! those stores only restore onto the install that made the backup, and
+//! start empty anywhere else.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{NexusError, NexusResult};
+use crate::plugin_manager::registry;
+use crate::plugin_manager::storage::{PluginStatus, PluginStorage};
+use crate::runtime::ContainerRuntime;
+use crate::secrets::{self, RestoredKey, WrappedKey};
+use crate::state_db::{self, StateDb};
+
+/// Bumped when the archive layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Directory in the data directory a restore waits in until the next launch.
+pub const PENDING_DIR: &str = "restore-pending";
+
+const MANIFEST_FILE: &str = "backup.json";
+const DATA_DIR: &str = "data";
+const VOLUMES_DIR: &str = "volumes";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFile {
+    /// Path inside the archive.
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupVolume {
+    pub plugin_id: String,
+    pub volume: String,
+    /// Image the volume is copied through on restore.
+    pub image: String,
+    #[serde(flatten)]
+    pub file: BackupFile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub nexus_version: String,
+    pub created_at: DateTime<Utc>,
+    pub files: Vec<BackupFile>,
+    #[serde(default)]
+    pub volumes: Vec<BackupVolume>,
+    /// The store encryption key, if the backup was given a passphrase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_key: Option<WrappedKey>,
+}
+
+/// A plugin data volume to include in a backup.
+pub struct VolumeSource {
+    pub plugin_id: String,
+    pub image: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupReport {
+    pub path: String,
+    pub files: usize,
+    pub volumes: usize,
+    /// Archive size in bytes.
+    pub bytes: u64,
+    /// Plugins whose data volume couldn't be exported.
+    pub skipped_volumes: Vec<String>,
+    /// Whether the store encryption key is included.
+    pub store_key: bool,
+}
+
+/// What a backup holds, to show before restoring it.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub nexus_version: String,
+    pub created_at: DateTime<Utc>,
+    pub files: usize,
+    pub volumes: usize,
+    /// Whether it carries the store encryption key, so restoring needs its
+    /// passphrase.
+    pub store_key: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    /// Nexus version that wrote the backup.
+    pub nexus_version: String,
+    pub created_at: DateTime<Utc>,
+    pub files: usize,
+    pub volumes: usize,
+    /// Plugins whose data volume couldn't be restored.
+    pub skipped_volumes: Vec<String>,
+    /// Plugins whose image couldn't be pulled, e.g. local builds. They need
+    /// reinstalling before they can start.
+    pub missing_images: Vec<String>,
+    /// Whether the store encryption key comes back too. Without it the
+    /// backup's OAuth and API key stores can't be read unless this is the
+    /// install that made it.
+    pub store_key: bool,
+}
+
+/// Write a backup of `data_dir`, and of the data volumes of `volumes`, to
+/// `dest`, with the store encryption key wrapped in `passphrase` if given.
+/// Volumes that fail to export are reported and left out rather than
+/// failing the backup.
+pub async fn create(
+    data_dir: &Path,
+    volumes: &[VolumeSource],
+    runtime: &Arc<dyn ContainerRuntime>,
+    dest: &Path,
+    passphrase: Option<&str>,
+) -> NexusResult<BackupReport> {
+    let store_key = passphrase.map(|p| secrets::export_key(data_dir, p)).transpose()?;
+    let scratch = scratch_dir("backup")?;
+    let result = create_in(&scratch, data_dir, volumes, runtime, dest, store_key).await;
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+async fn create_in(
+    scratch: &Path,
+    data_dir: &Path,
+    volumes: &[VolumeSource],
+    runtime: &Arc<dyn ContainerRuntime>,
+    dest: &Path,
+    store_key: Option<WrappedKey>,
+) -> NexusResult<BackupReport> {
+    let mut exported = Vec::new();
+    let mut skipped_volumes = Vec::new();
+    for source in volumes {
+        let volume = crate::plugin_manager::data_volume_name(&source.plugin_id);
+        let local = scratch.join(format!("{volume}.tar"));
+        match runtime.export_volume(&volume, &source.image, &local).await {
+            Ok(_) => exported.push((source, volume, local)),
+            Err(e) => {
+                log::warn!("Backup: skipping volume of plugin={}: {}", source.plugin_id, e);
+                skipped_volumes.push(source.plugin_id.clone());
+            }
+        }
+    }
+    let volumes: Vec<(BackupVolume, PathBuf)> = exported
+        .into_iter()
+        .map(|(source, volume, local)| {
+            let (size, sha256) = hash_file(&local)?;
+            let entry = BackupVolume {
+                plugin_id: source.plugin_id.clone(),
+                file: BackupFile {
+                    path: format!("{VOLUMES_DIR}/{volume}.tar"),
+                    size,
+                    sha256,
+                },
+                volume,
+                image: source.image.clone(),
+            };
+            Ok((entry, local))
+        })
+        .collect::<NexusResult<_>>()?;
+
+    let data_dir = data_dir.to_path_buf();
+    let dest = dest.to_path_buf();
+    let snapshot = scratch.join(state_db::DB_FILE);
+    tokio::task::spawn_blocking(move || {
+        let state = if data_dir.join(state_db::DB_FILE).exists() {
+            StateDb::open(&data_dir)?.snapshot(&snapshot)?;
+            Some(snapshot)
+        } else {
+            None
+        };
+        write_archive(&data_dir, state.as_deref(), volumes, store_key, &dest, skipped_volumes)
+    })
+    .await
+        .map_err(|e| NexusError::Other(format!("Backup task failed: {e}")))?
+}
+
+fn write_archive(
+    data_dir: &Path,
+    state: Option<&Path>,
+    volumes: Vec<(BackupVolume, PathBuf)>,
+    store_key: Option<WrappedKey>,
+    dest: &Path,
+    skipped_volumes: Vec<String>,
+) -> NexusResult<BackupReport> {
+    let partial = dest.with_file_name(format!(
+        "{}.partial",
+        dest.file_name().and_then(|n| n.to_str()).unwrap_or("backup")
+    ));
+
+    let encoder = flate2::write::GzEncoder::new(File::create(&partial)?, flate2::Compression::default());
+    let mut tar = tar::Builder::new(encoder);
+    let mut files = Vec::new();
+
+    for entry in walkdir::WalkDir::new(data_dir).follow_links(false) {
+        let entry = entry.map_err(|e| NexusError::Other(format!("Failed to read data directory: {e}")))?;
+        if !entry.file_type().is_file() || entry.path() == partial || entry.path() == dest {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(data_dir) else {
+            continue;
+        };
+        if excluded(relative) {
+            continue;
+        }
+        // Hash what gets archived, so a file Nexus rewrites meanwhile can't
+        // leave the manifest out of step
+        let path = archive_path(DATA_DIR, relative);
+        let bytes = std::fs::read(entry.path())?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(&entry.metadata().map_err(std::io::Error::from)?);
+        header.set_size(bytes.len() as u64);
+        header.set_cksum();
+        tar.append_data(&mut header, &path, bytes.as_slice())?;
+        files.push(BackupFile {
+            path,
+            size: bytes.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        });
+    }
+
+    if let Some(snapshot) = state {
+        let path = archive_path(DATA_DIR, Path::new(state_db::DB_FILE));
+        let bytes = std::fs::read(snapshot)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata(&std::fs::metadata(snapshot)?);
+        header.set_size(bytes.len() as u64);
+        header.set_cksum();
+        tar.append_data(&mut header, &path, bytes.as_slice())?;
+        files.push(BackupFile {
+            path,
+            size: bytes.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(&bytes)),
+        });
+    }
+
+    for (volume, local) in &volumes {
+        tar.append_path_with_name(local, &volume.file.path)?;
+    }
+
+    let manifest = BackupManifest {
+        format_version: FORMAT_VERSION,
+        nexus_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        files,
+        volumes: volumes.into_iter().map(|(volume, _)| volume).collect(),
+        store_key,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, MANIFEST_FILE, manifest_json.as_slice())?;
+    tar.into_inner()?.finish()?.flush()?;
+
+    // Read the archive back before it replaces anything at `dest`
+    if let Err(e) = read_archive(&partial, None) {
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+    std::fs::rename(&partial, dest)?;
+
+    Ok(BackupReport {
+        path: dest.display().to_string(),
+        files: manifest.files.len(),
+        volumes: manifest.volumes.len(),
+        bytes: std::fs::metadata(dest)?.len(),
+        skipped_volumes,
+        store_key: manifest.store_key.is_some(),
+    })
+}
+
+/// Stage a backup written by [`create`] for [`apply_pending`] to restore
+/// onto `data_dir` and into the engine at the next launch. Images the
+/// plugins and their volumes need are pulled now. The store encryption key
+/// comes back if the backup has it and `passphrase` opens it; a wrong
+/// passphrase fails the restore. Nothing is staged unless the whole archive
+/// checks out; a backup staged earlier is replaced.
+pub async fn restore(
+    archive: &Path,
+    data_dir: &Path,
+    runtime: &Arc<dyn ContainerRuntime>,
+    passphrase: Option<&str>,
+) -> NexusResult<RestoreReport> {
+    let pending = data_dir.join(PENDING_DIR);
+    let partial = data_dir.join(format!("{PENDING_DIR}.partial"));
+    let _ = std::fs::remove_dir_all(&partial);
+    let result = match stage(archive, &partial, runtime, passphrase).await {
+        Ok((report, key)) => secrets::stage_restored_key(data_dir, key)
+            .and_then(|()| replace_dir(&partial, &pending))
+            .map(|()| report),
+        Err(e) => Err(e),
+    };
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&partial);
+        let _ = secrets::stage_restored_key(data_dir, None);
+    }
+    result
+}
+
+async fn stage(
+    archive: &Path,
+    staging: &Path,
+    runtime: &Arc<dyn ContainerRuntime>,
+    passphrase: Option<&str>,
+) -> NexusResult<(RestoreReport, Option<RestoredKey>)> {
+    let mut manifest = {
+        let archive = archive.to_path_buf();
+        let staging = staging.to_path_buf();
+        tokio::task::spawn_blocking(move || read_archive(&archive, Some(&staging)))
+            .await
+            .map_err(|e| NexusError::Other(format!("Restore task failed: {e}")))??
+    };
+    let key = match (&manifest.store_key, passphrase) {
+        (Some(wrapped), Some(passphrase)) => Some(wrapped.open(passphrase)?),
+        _ => None,
+    };
+    // Not needed once opened, and useless without the passphrase
+    manifest.store_key = None;
+
+    // Plugins get new containers when started, which needs their images
+    let images = staged_images(&staging.join(DATA_DIR))?;
+    let mut missing_images = Vec::new();
+    for (plugin_id, image) in &images {
+        if !ensure_image(runtime, image).await {
+            missing_images.push(plugin_id.clone());
+        }
+    }
+
+    // Volumes are copied in through their image, so drop those it's missing
+    let mut skipped_volumes = Vec::new();
+    let mut volumes = Vec::new();
+    for volume in std::mem::take(&mut manifest.volumes) {
+        if ensure_image(runtime, &volume.image).await {
+            volumes.push(volume);
+        } else {
+            let _ = std::fs::remove_file(staging.join(&volume.file.path));
+            skipped_volumes.push(volume.plugin_id);
+        }
+    }
+    manifest.volumes = volumes;
+    std::fs::write(staging.join(MANIFEST_FILE), serde_json::to_vec_pretty(&manifest)?)?;
+
+    let report = RestoreReport {
+        nexus_version: manifest.nexus_version,
+        created_at: manifest.created_at,
+        files: manifest.files.len(),
+        volumes: manifest.volumes.len(),
+        skipped_volumes,
+        missing_images,
+        store_key: key.is_some(),
+    };
+    Ok((report, key))
+}
+
+/// Plugin IDs and images of the local plugins in a staged data directory.
+/// The plugin store is read from a copy, since loading it can migrate the
+/// files it loads from.
+fn staged_images(staged: &Path) -> NexusResult<Vec<(String, String)>> {
+    let scratch = scratch_dir("restore")?;
+    let load = || -> NexusResult<Vec<(String, String)>> {
+        for name in [state_db::DB_FILE, PluginStorage::LEGACY_FILE] {
+            if staged.join(name).exists() {
+                std::fs::copy(staged.join(name), scratch.join(name))?;
+            }
+        }
+        Ok(PluginStorage::load(&scratch)?
+            .list()
+            .iter()
+            .filter(|p| p.manifest.remote_mcp_url().is_none())
+            .map(|p| (p.manifest.id.clone(), p.manifest.image.clone()))
+            .collect())
+    };
+    let result = load();
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+/// Apply a restore staged by [`restore`], if there is one: replace the
+/// files it contains, then copy its volumes into the engine `connect`
+/// returns. Runs at startup, before any store loads; `connect` is called
+/// after the files are in place, so it sees the restored engine settings.
+/// Volumes aren't touched if the files fail to apply. A staged restore is
+/// only tried once: it's removed whether or not it applies. Returns whether
+/// there was one.
+pub async fn apply_pending(
+    data_dir: &Path,
+    connect: impl FnOnce() -> Arc<dyn ContainerRuntime>,
+) -> NexusResult<bool> {
+    let pending = data_dir.join(PENDING_DIR);
+    if !pending.is_dir() {
+        return Ok(false);
+    }
+    let result = apply(&pending, data_dir, connect).await;
+    let _ = std::fs::remove_dir_all(&pending);
+    if result.is_err() {
+        if let Err(e) = secrets::stage_restored_key(data_dir, None) {
+            log::warn!("Failed to drop the restored encryption key: {}", e);
+        }
+    }
+    result.map(|()| true)
+}
+
+async fn apply(
+    pending: &Path,
+    data_dir: &Path,
+    connect: impl FnOnce() -> Arc<dyn ContainerRuntime>,
+) -> NexusResult<()> {
+    let manifest: BackupManifest =
+        serde_json::from_slice(&std::fs::read(pending.join(MANIFEST_FILE))?)?;
+    apply_files(pending, data_dir, &manifest)?;
+    if secrets::apply_restored_key(data_dir)? {
+        log::info!("Restored the store encryption key");
+    }
+
+    if !manifest.volumes.is_empty() {
+        let runtime = connect();
+        for volume in &manifest.volumes {
+            let local = pending.join(&volume.file.path);
+            if let Err(e) = runtime.import_volume(&volume.volume, &volume.image, &local).await {
+                log::warn!("Restore: failed to import volume of plugin={}: {}", volume.plugin_id, e);
+            }
+        }
+    }
+    log::info!(
+        "Restored backup from Nexus {} ({} files, {} volumes)",
+        manifest.nexus_version,
+        manifest.files.len(),
+        manifest.volumes.len()
+    );
+    Ok(())
+}
+
+fn apply_files(pending: &Path, data_dir: &Path, manifest: &BackupManifest) -> NexusResult<()> {
+    for file in &manifest.files {
+        let relative = file
+            .path
+            .strip_prefix(&format!("{DATA_DIR}/"))
+            .ok_or_else(|| NexusError::Other(format!("Unexpected path in backup: {}", file.path)))?;
+        if relative == state_db::DB_FILE {
+            StateDb::open(data_dir)?.restore(&pending.join(DATA_DIR))?;
+            continue;
+        }
+        let target = data_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        crate::util::atomic_write(&target, &std::fs::read(pending.join(&file.path))?)?;
+    }
+
+    // The backed-up containers don't exist on this engine. Plugins come back
+    // stopped and get new containers when started.
+    let mut plugins = PluginStorage::load(data_dir)?;
+    let ids: Vec<String> = plugins.list().iter().map(|p| p.manifest.id.clone()).collect();
+    for id in ids {
+        let Some(plugin) = plugins.get_mut(&id) else {
+            continue;
+        };
+        plugin.container_id = None;
+        if plugin.status == PluginStatus::Running {
+            plugin.status = PluginStatus::Stopped;
+        }
+    }
+    plugins.save()?;
+    Ok(())
+}
+
+/// Move `from` to `to`, replacing whatever is there.
+fn replace_dir(from: &Path, to: &Path) -> NexusResult<()> {
+    if to.exists() {
+        std::fs::remove_dir_all(to)?;
+    }
+    std::fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Pull `image` unless it's already local. Returns whether it's available.
+async fn ensure_image(runtime: &Arc<dyn ContainerRuntime>, image: &str) -> bool {
+    if runtime.image_exists(image).await.unwrap_or(false) {
+        return true;
+    }
+    match runtime.pull_image(image).await {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Restore: failed to pull {}: {}", image, e);
+            false
+        }
+    }
+}
+
+/// Describe `archive`, checking it the way [`restore`] does.
+pub fn inspect(archive: &Path) -> NexusResult<BackupInfo> {
+    let manifest = read_archive(archive, None)?;
+    Ok(BackupInfo {
+        nexus_version: manifest.nexus_version,
+        created_at: manifest.created_at,
+        files: manifest.files.len(),
+        volumes: manifest.volumes.len(),
+        store_key: manifest.store_key.is_some(),
+    })
+}
+
+/// Read every entry of `archive` and check it against the manifest,
+/// unpacking into `unpack_to` if given. Fails on entries outside `data/`
+/// and `volumes/`, files the manifest doesn't list, and listed files that
+/// are missing or don't match.
+pub fn read_archive(archive: &Path, unpack_to: Option<&Path>) -> NexusResult<BackupManifest> {
+    let invalid = |msg: String| NexusError::Other(format!("Invalid backup: {msg}"));
+
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(archive)?));
+    let mut seen: HashMap<String, (u64, String)> = HashMap::new();
+    let mut manifest: Option<BackupManifest> = None;
+
+    for entry in tar.entries().map_err(|e| invalid(e.to_string()))? {
+        let mut entry = entry.map_err(|e| invalid(e.to_string()))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path().map_err(|e| invalid(e.to_string()))?.into_owned();
+        let name = path.to_string_lossy().replace('\\', "/");
+        if name == MANIFEST_FILE {
+            manifest = Some(serde_json::from_reader(&mut entry).map_err(|e| invalid(e.to_string()))?);
+            continue;
+        }
+        let allowed = path.components().all(|c| matches!(c, Component::Normal(_)))
+            && (name.starts_with(&format!("{DATA_DIR}/")) || name.starts_with(&format!("{VOLUMES_DIR}/")));
+        if !allowed {
+            return Err(invalid(format!("unexpected entry {name}")));
+        }
+
+        let mut out = match unpack_to {
+            Some(dir) => {
+                let target = dir.join(&path);
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                Some(File::create(target)?)
+            }
+            None => None,
+        };
+        let mut hasher = Sha256::new();
+        let mut size = 0_u64;
+        let mut buf = [0_u8; 64 * 1024];
+        loop {
+            let n = entry.read(&mut buf).map_err(|e| invalid(e.to_string()))?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            size += n as u64;
+            if let Some(out) = out.as_mut() {
+                out.write_all(&buf[..n])?;
+            }
+        }
+        seen.insert(name, (size, format!("{:x}", hasher.finalize())));
+    }
+
+    let manifest = manifest.ok_or_else(|| invalid(format!("no {MANIFEST_FILE}")))?;
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(NexusError::Other(format!(
+            "Backup was written by Nexus {}, which uses a newer format; update Nexus to restore it",
+            manifest.nexus_version
+        )));
+    }
+
+    let listed = manifest
+        .files
+        .iter()
+        .chain(manifest.volumes.iter().map(|v| &v.file));
+    let mut expected = 0;
+    for file in listed {
+        expected += 1;
+        match seen.get(&file.path) {
+            None => return Err(invalid(format!("{} is missing", file.path))),
+            Some((size, sha256)) if *size != file.size || *sha256 != file.sha256 => {
+                return Err(invalid(format!("{} is corrupted", file.path)));
+            }
+            Some(_) => {}
+        }
+    }
+    if seen.len() != expected {
+        return Err(invalid("archive holds files the manifest doesn't list".to_string()));
+    }
+    Ok(manifest)
+}
+
+/// Files under the data directory a backup leaves out.
+fn excluded(relative: &Path) -> bool {
+    let name = relative.to_string_lossy();
+    // A staged restore, or one being staged
+    let staged = relative
+        .components()
+        .next()
+        .is_some_and(|c| c.as_os_str().to_string_lossy().starts_with(PENDING_DIR));
+    staged
+        || name == registry::CACHE_FILE
+        || name == secrets::KEY_FILE
+        || name.ends_with(".db")
+        || name.ends_with(".db-wal")
+        || name.ends_with(".db-shm")
+        || name.ends_with(state_db::IMPORTED_SUFFIX)
+        || name.ends_with(".bak")
+}
+
+fn archive_path(prefix: &str, relative: &Path) -> String {
+    let parts: Vec<_> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect();
+    format!("{prefix}/{}", parts.join("/"))
+}
+
+fn hash_file(path: &Path) -> NexusResult<(u64, String)> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let size = std::io::copy(&mut file, &mut hasher)?;
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+fn scratch_dir(kind: &str) -> NexusResult<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("nexus-{kind}-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::mock::MockRuntime;
+
+    fn runtime() -> Arc<dyn ContainerRuntime> {
+        Arc::new(MockRuntime::new())
+    }
+
+    #[tokio::test]
+    async fn backup_round_trips_onto_a_fresh_data_dir() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("plugins.json"), "{}").unwrap();
+        std::fs::write(source.path().join("audit.db"), "sqlite").unwrap();
+        std::fs::write(source.path().join(registry::CACHE_FILE), "{}").unwrap();
+        std::fs::create_dir_all(source.path().join("plugin_data/com.example.a")).unwrap();
+        std::fs::write(source.path().join("plugin_data/com.example.a/k.json"), "\"v\"").unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("nexus.tar.gz");
+        let volumes = [VolumeSource {
+            plugin_id: "com.example.a".into(),
+            image: "example/a:1".into(),
+        }];
+        let report = create(source.path(), &volumes, &runtime(), &dest, None).await.unwrap();
+        assert_eq!(report.files, 2);
+        // The mock has no volume for the plugin
+        assert_eq!(report.skipped_volumes, ["com.example.a"]);
+
+        let fresh = tempfile::tempdir().unwrap();
+        let restored = restore(&dest, fresh.path(), &runtime(), None).await.unwrap();
+        assert_eq!(restored.files, 2);
+        // Staged until the next launch applies it
+        assert!(!fresh.path().join("plugins.json").exists());
+        assert!(apply_pending(fresh.path(), runtime).await.unwrap());
+        assert!(!fresh.path().join(PENDING_DIR).exists());
+        assert!(!apply_pending(fresh.path(), runtime).await.unwrap());
+        assert_eq!(
+            std::fs::read_to_string(fresh.path().join("plugin_data/com.example.a/k.json")).unwrap(),
+            "\"v\""
+        );
+        assert!(fresh.path().join("plugins.json").exists());
+        assert!(!fresh.path().join("audit.db").exists());
+        assert!(!fresh.path().join(registry::CACHE_FILE).exists());
+    }
+
+    #[tokio::test]
+    async fn damaged_backup_is_rejected_before_restoring() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("settings.json"), "x".repeat(100_000)).unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("nexus.tar.gz");
+        create(source.path(), &[], &runtime(), &dest, None).await.unwrap();
+
+        let bytes = std::fs::read(&dest).unwrap();
+        std::fs::write(&dest, &bytes[..bytes.len() / 2]).unwrap();
+
+        let fresh = tempfile::tempdir().unwrap();
+        assert!(restore(&dest, fresh.path(), &runtime(), None).await.is_err());
+        assert!(!apply_pending(fresh.path(), runtime).await.unwrap());
+        assert!(!fresh.path().join("settings.json").exists());
+        assert_eq!(std::fs::read_dir(fresh.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn staged_restore_waits_for_the_next_launch() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("settings.json"), "restored").unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("nexus.tar.gz");
+        create(source.path(), &[], &runtime(), &dest, None).await.unwrap();
+
+        let live = tempfile::tempdir().unwrap();
+        std::fs::write(live.path().join("settings.json"), "live").unwrap();
+        restore(&dest, live.path(), &runtime(), None).await.unwrap();
+        // A store saving before the relaunch doesn't undo the restore
+        std::fs::write(live.path().join("settings.json"), "saved meanwhile").unwrap();
+
+        // Nor does a backup taken meanwhile pick up the staged files
+        let again = out.path().join("again.tar.gz");
+        let report = create(live.path(), &[], &runtime(), &again, None).await.unwrap();
+        assert_eq!(report.files, 1);
+
+        assert!(apply_pending(live.path(), runtime).await.unwrap());
+        assert_eq!(
+            std::fs::read_to_string(live.path().join("settings.json")).unwrap(),
+            "restored"
+        );
+    }
+
+    #[tokio::test]
+    async fn volumes_are_imported_with_the_staged_files() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("plugins.json"), "{}").unwrap();
+        let volume = crate::plugin_manager::data_volume_name("com.example.a");
+        let exporter: Arc<dyn ContainerRuntime> = Arc::new(MockRuntime::new().with_volume(&volume));
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("nexus.tar.gz");
+        let volumes = [VolumeSource {
+            plugin_id: "com.example.a".into(),
+            image: "example/a:1".into(),
+        }];
+        let report = create(source.path(), &volumes, &exporter, &dest, None).await.unwrap();
+        assert!(report.skipped_volumes.is_empty());
+
+        let live = Arc::new(MockRuntime::new());
+        let fresh = tempfile::tempdir().unwrap();
+        let live_runtime: Arc<dyn ContainerRuntime> = live.clone();
+        let restored = restore(&dest, fresh.path(), &live_runtime, None).await.unwrap();
+        assert_eq!(restored.volumes, 1);
+        // Left alone until the files go back too
+        assert!(!live.volume_exists(&volume));
+
+        assert!(apply_pending(fresh.path(), || live_runtime.clone()).await.unwrap());
+        assert!(live.volume_exists(&volume));
+        assert!(fresh.path().join("plugins.json").exists());
+    }
+
+    #[tokio::test]
+    async fn store_key_travels_only_with_its_passphrase() {
+        let source = tempfile::tempdir().unwrap();
+        let store = source.path().join("api_keys.json");
+        secrets::StoreCipher::load(source.path()).write(&store, b"{\"k\": 1}").unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("nexus.tar.gz");
+        let report = create(source.path(), &[], &runtime(), &dest, Some("hunter2")).await.unwrap();
+        assert!(report.store_key);
+        assert!(inspect(&dest).unwrap().store_key);
+
+        let fresh = tempfile::tempdir().unwrap();
+        let fresh_store = fresh.path().join("api_keys.json");
+        assert!(restore(&dest, fresh.path(), &runtime(), Some("wrong")).await.is_err());
+        assert!(!fresh.path().join(PENDING_DIR).exists());
+
+        // Without the passphrase the store can't be read here
+        let restored = restore(&dest, fresh.path(), &runtime(), None).await.unwrap();
+        assert!(!restored.store_key);
+        assert!(apply_pending(fresh.path(), runtime).await.unwrap());
+        assert_eq!(secrets::StoreCipher::load(fresh.path()).read(&fresh_store), None);
+
+        let restored = restore(&dest, fresh.path(), &runtime(), Some("hunter2")).await.unwrap();
+        assert!(restored.store_key);
+        assert!(apply_pending(fresh.path(), runtime).await.unwrap());
+        assert_eq!(
+            secrets::StoreCipher::load(fresh.path()).read(&fresh_store).as_deref(),
+            Some("{\"k\": 1}")
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use crate::api_keys::{ApiKey, ApiKeyStore};
+use crate::api_keys::{ApiKey, ApiKeyScope, ApiKeyStore, ApiKeyUsage};
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -16,17 +17,35 @@ pub async fn api_key_list(
     Ok(store.list())
 }
 
+/// Usage per key (last use, source address, call counts), least recently
+/// used first.
+#[tauri::command]
+pub async fn api_key_usage(
+    store: tauri::State<'_, ApiKeyStore>,
+) -> Result<Vec<ApiKeyUsage>, String> {
+    Ok(store.usage())
+}
+
 #[tauri::command]
 pub async fn api_key_generate(
     store: tauri::State<'_, ApiKeyStore>,
     audit: tauri::State<'_, AuditWriter>,
     name: String,
+    expires_at: Option<DateTime<Utc>>,
+    scope: Option<ApiKeyScope>,
 ) -> Result<GeneratedApiKey, String> {
-    let (key, raw) = store.generate(&name);
+    if expires_at.is_some_and(|at| at <= Utc::now()) {
+        return Err("Expiry must be in the future".into());
+    }
+    let (mut key, raw) = store.generate(&name, expires_at);
+    if let Some(scope) = scope {
+        store.set_scope(&key.id, scope.clone());
+        key.scope = scope;
+    }
     audit.record(AuditEntry {
         actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "security.api_key.generate".into(),
         subject: Some(key.id.clone()), result: AuditResult::Success,
-        details: Some(serde_json::json!({"name": name})),
+        details: Some(serde_json::json!({"name": name, "expires_at": expires_at, "scope": key.scope})),
     });
     Ok(GeneratedApiKey { key, raw })
 }
@@ -69,3 +88,59 @@ pub async fn api_key_regenerate_default(
     });
     Ok(GeneratedApiKey { key, raw })
 }
+
+/// Issue a new secret for an existing key. The old secret stops working
+/// immediately; name, expiry, and ID are kept.
+#[tauri::command]
+pub async fn api_key_rotate(
+    store: tauri::State<'_, ApiKeyStore>,
+    audit: tauri::State<'_, AuditWriter>,
+    id: String,
+) -> Result<GeneratedApiKey, String> {
+    let (key, raw) = store.rotate(&id).ok_or("API key not found")?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "security.api_key.rotate".into(),
+        subject: Some(id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"prefix": key.prefix})),
+    });
+    Ok(GeneratedApiKey { key, raw })
+}
+
+/// Set or clear (`None`) when a key expires.
+#[tauri::command]
+pub async fn api_key_set_expiry(
+    store: tauri::State<'_, ApiKeyStore>,
+    audit: tauri::State<'_, AuditWriter>,
+    id: String,
+    expires_at: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    if !store.set_expiry(&id, expires_at) {
+        return Err("API key not found".into());
+    }
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "security.api_key.set_expiry".into(),
+        subject: Some(id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"expires_at": expires_at})),
+    });
+    Ok(())
+}
+
+/// Restrict a key to some tool namespaces and/or read-only tools. Applies
+/// immediately, including to sessions the key already has open.
+#[tauri::command]
+pub async fn api_key_set_scope(
+    store: tauri::State<'_, ApiKeyStore>,
+    audit: tauri::State<'_, AuditWriter>,
+    id: String,
+    scope: ApiKeyScope,
+) -> Result<(), String> {
+    if !store.set_scope(&id, scope.clone()) {
+        return Err("API key not found".into());
+    }
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "security.api_key.set_scope".into(),
+        subject: Some(id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"scope": scope})),
+    });
+    Ok(())
+}
@@ -32,8 +32,11 @@ pub async fn check_app_update(
     .parse()
     .map_err(|e: url::ParseError| e.to_string())?;
 
-    let updater = app
-        .updater_builder()
+    let mut builder = app.updater_builder();
+    if let Some(proxy) = crate::proxy::active_url() {
+        builder = builder.proxy(proxy.parse().map_err(|e: url::ParseError| e.to_string())?);
+    }
+    let updater = builder
         .endpoints(vec![endpoint])
         .map_err(|e: tauri_plugin_updater::Error| e.to_string())?
         .build()
@@ -1,64 +1,40 @@
 use std::sync::Arc;
 
-use crate::audit::store::AuditStore;
-use crate::audit::{AuditLogRow, AuditQuery};
+use crate::audit::alerts::{self, AlertRule};
+use crate::audit::forward::ForwardConfig;
+use crate::audit::redact::RedactionPolicy;
+use crate::audit::retention;
+use crate::audit::store::{AuditRetention, AuditStats, AuditStore, AuditVerification};
+use crate::audit::writer::{AlertWatcher, AuditForwarder, AuditWriter, ForwardStatus};
+use crate::audit::{AuditActor, AuditEntry, AuditLogRow, AuditQuery, AuditResult, AuditSeverity};
+use crate::host_api::access_log::{AccessLogConfig, AccessLogPolicy};
+use crate::AppState;
 
+/// Entries matching `query`, newest first.
 #[tauri::command]
-#[allow(clippy::too_many_arguments)]
 pub async fn audit_query(
     store: tauri::State<'_, Arc<AuditStore>>,
-    action: Option<String>,
-    actor: Option<String>,
-    source_id: Option<String>,
-    severity: Option<String>,
-    subject: Option<String>,
-    result: Option<String>,
-    since: Option<String>,
-    until: Option<String>,
-    limit: Option<usize>,
-    offset: Option<usize>,
+    query: AuditQuery,
 ) -> Result<Vec<AuditLogRow>, String> {
-    let q = AuditQuery {
-        action,
-        actor,
-        source_id,
-        severity,
-        subject,
-        result,
-        since,
-        until,
-        limit,
-        offset,
-    };
-    store.query(&q)
+    store.query(&query)
 }
 
+/// Number of entries matching `query`, ignoring `limit` and `offset`.
 #[tauri::command]
-#[allow(clippy::too_many_arguments)]
 pub async fn audit_count(
     store: tauri::State<'_, Arc<AuditStore>>,
-    action: Option<String>,
-    actor: Option<String>,
-    source_id: Option<String>,
-    severity: Option<String>,
-    subject: Option<String>,
-    result: Option<String>,
-    since: Option<String>,
-    until: Option<String>,
+    query: AuditQuery,
 ) -> Result<usize, String> {
-    let q = AuditQuery {
-        action,
-        actor,
-        source_id,
-        severity,
-        subject,
-        result,
-        since,
-        until,
-        limit: None,
-        offset: None,
-    };
-    store.count(&q)
+    store.count(&query)
+}
+
+/// Aggregates over the entries matching `query`, for the audit viewer.
+#[tauri::command]
+pub async fn audit_stats(
+    store: tauri::State<'_, Arc<AuditStore>>,
+    query: AuditQuery,
+) -> Result<AuditStats, String> {
+    store.stats(&query)
 }
 
 /// Export audit entries as a JSON string (for download).
@@ -77,3 +53,194 @@ pub async fn audit_export(
     let rows = store.query(&q)?;
     serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())
 }
+
+/// Check the audit log's hash chain for modified, deleted, or truncated
+/// entries. The check itself is recorded, as critical when it fails.
+#[tauri::command]
+pub async fn audit_verify(
+    store: tauri::State<'_, Arc<AuditStore>>,
+    audit: tauri::State<'_, AuditWriter>,
+) -> Result<AuditVerification, String> {
+    let report = store.verify()?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None,
+        severity: if report.ok { AuditSeverity::Info } else { AuditSeverity::Critical },
+        action: "audit.verify".into(),
+        subject: None,
+        result: if report.ok { AuditResult::Success } else { AuditResult::Failure },
+        details: Some(serde_json::json!({"verified": report.verified, "issue": report.issue})),
+    });
+    Ok(report)
+}
+
+/// Get the audit forwarding settings.
+#[tauri::command]
+pub async fn audit_forward_get(state: tauri::State<'_, AppState>) -> Result<ForwardConfig, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.audit_forward.clone())
+}
+
+/// Save the audit forwarding settings and restart forwarding with them.
+#[tauri::command]
+pub async fn audit_forward_set(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    forwarder: tauri::State<'_, Arc<AuditForwarder>>,
+    config: ForwardConfig,
+) -> Result<(), String> {
+    config.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.audit_forward = config.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "settings.audit_forward".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"enabled": config.enabled})),
+    });
+    forwarder.apply(config);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn audit_forward_status(
+    forwarder: tauri::State<'_, Arc<AuditForwarder>>,
+) -> Result<ForwardStatus, String> {
+    Ok(forwarder.status())
+}
+
+/// Get how long audit entries are kept and how large the log may grow.
+#[tauri::command]
+pub async fn audit_retention_get(
+    state: tauri::State<'_, AppState>,
+) -> Result<AuditRetention, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.audit_retention.clone())
+}
+
+/// Save the audit retention settings and prune the log by them right away.
+#[tauri::command]
+pub async fn audit_retention_set(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    store: tauri::State<'_, Arc<AuditStore>>,
+    audit: tauri::State<'_, AuditWriter>,
+    policy: AuditRetention,
+) -> Result<(), String> {
+    policy.validate()?;
+    {
+        let mut mgr = state.write().await;
+        mgr.settings.audit_retention = policy.clone();
+        mgr.settings.save().map_err(|e| e.to_string())?;
+    }
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "settings.audit_retention".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"max_age_days": policy.max_age_days, "max_bytes": policy.max_bytes})),
+    });
+    retention::prune(&app, &policy, &store, &audit);
+    Ok(())
+}
+
+/// Get the audit alert rules.
+#[tauri::command]
+pub async fn audit_alerts_get(state: tauri::State<'_, AppState>) -> Result<Vec<AlertRule>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.audit_alerts.clone())
+}
+
+/// Replace the audit alert rules.
+#[tauri::command]
+pub async fn audit_alerts_set(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    watcher: tauri::State<'_, Arc<AlertWatcher>>,
+    rules: Vec<AlertRule>,
+) -> Result<(), String> {
+    alerts::validate_rules(&rules)?;
+    let mut mgr = state.write().await;
+    mgr.settings.audit_alerts = rules.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.audit_alerts".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"rules": rules.iter().map(|r| &r.name).collect::<Vec<_>>()})),
+    });
+    watcher.apply(&rules);
+    Ok(())
+}
+
+/// Get the fields masked in audit entry details.
+#[tauri::command]
+pub async fn audit_redaction_get(
+    state: tauri::State<'_, AppState>,
+) -> Result<RedactionPolicy, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.audit_redaction.clone())
+}
+
+/// Save the redaction policy. It applies to entries recorded from now on.
+#[tauri::command]
+pub async fn audit_redaction_set(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    policy: RedactionPolicy,
+) -> Result<(), String> {
+    policy.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.audit_redaction = policy.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    // Recorded under the old policy, so a change that stops masking a
+    // field is itself visible
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "settings.audit_redaction".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"fields": policy.fields, "actions": policy.actions})),
+    });
+    audit.set_redaction(&policy);
+    Ok(())
+}
+
+/// Plugins whose Host API requests are being audited.
+#[tauri::command]
+pub async fn audit_access_log_get(
+    state: tauri::State<'_, AppState>,
+) -> Result<AccessLogConfig, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.host_api_access_log.clone())
+}
+
+/// Start auditing a plugin's Host API requests with `policy`, or stop when
+/// it's None.
+#[tauri::command]
+pub async fn audit_access_log_set(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    policy: Option<AccessLogPolicy>,
+) -> Result<(), String> {
+    if let Some(policy) = &policy {
+        policy.validate()?;
+    }
+    let mut mgr = state.write().await;
+    match &policy {
+        Some(policy) => {
+            mgr.settings
+                .host_api_access_log
+                .insert(plugin_id.clone(), policy.clone());
+        }
+        None => {
+            mgr.settings.host_api_access_log.remove(&plugin_id);
+        }
+    }
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.host_api_access_log".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "enabled": policy.is_some(),
+            "sample_rate": policy.as_ref().map(|p| p.sample_rate),
+            "expires_at": policy.as_ref().and_then(|p| p.expires_at),
+        })),
+    });
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::path::PathBuf;
+
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::backup::{self, BackupInfo, BackupReport, RestoreReport, VolumeSource};
+use crate::AppState;
+
+/// Back up the data directory, and plugin data volumes if
+/// `include_volumes`, to `path`. With a `passphrase`, the key the OAuth and
+/// API key stores are encrypted with goes in too, encrypted with it.
+#[tauri::command]
+pub async fn nexus_backup(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    path: String,
+    include_volumes: bool,
+    passphrase: Option<String>,
+) -> Result<BackupReport, String> {
+    // Snapshot what the backup needs rather than holding the manager while
+    // volumes export; a plugin removed meanwhile just shows up as skipped
+    let mgr = state.read().await;
+    let volumes: Vec<VolumeSource> = if include_volumes {
+        mgr.storage
+            .list()
+            .iter()
+            .filter(|p| p.manifest.remote_mcp_url().is_none())
+            .map(|p| VolumeSource {
+                plugin_id: p.manifest.id.clone(),
+                image: p.manifest.image.clone(),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let data_dir = mgr.data_dir.clone();
+    let runtime = mgr.runtime.clone();
+    drop(mgr);
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let result = backup::create(&data_dir, &volumes, &runtime, &PathBuf::from(&path), passphrase.as_deref()).await;
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.backup".into(),
+        subject: Some(path),
+        result: if result.is_ok() { AuditResult::Success } else { AuditResult::Failure },
+        details: Some(match &result {
+            Ok(report) => serde_json::json!({
+                "files": report.files,
+                "volumes": report.volumes,
+                "skipped_volumes": report.skipped_volumes,
+                "store_key": report.store_key,
+            }),
+            Err(e) => serde_json::json!({"error": e.to_string()}),
+        }),
+    });
+    result.map_err(|e| e.to_string())
+}
+
+/// What the backup at `path` holds, checking it the way `nexus_restore`
+/// does, so the UI can ask for its passphrase or warn before restoring.
+#[tauri::command]
+pub async fn nexus_backup_info(path: String) -> Result<BackupInfo, String> {
+    tokio::task::spawn_blocking(move || backup::inspect(&PathBuf::from(path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a backup written by `nexus_backup`. Running plugins are stopped
+/// first so none starts writing again before the relaunch. The data files
+/// and volumes are staged and replace the current ones when the app
+/// relaunches, before anything loads them.
+#[tauri::command]
+pub async fn nexus_restore(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    path: String,
+    passphrase: Option<String>,
+) -> Result<RestoreReport, String> {
+    // Check the archive before stopping anything
+    let archive = PathBuf::from(&path);
+    {
+        let archive = archive.clone();
+        tokio::task::spawn_blocking(move || backup::read_archive(&archive, None))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut mgr = state.write().await;
+    let running: Vec<String> = mgr
+        .storage
+        .list()
+        .iter()
+        .filter(|p| p.status == crate::plugin_manager::storage::PluginStatus::Running)
+        .map(|p| p.manifest.id.clone())
+        .collect();
+    for plugin_id in running {
+        if let Err(e) = mgr.stop(&plugin_id).await {
+            log::warn!("Restore: failed to stop plugin={}: {}", plugin_id, e);
+        }
+    }
+    let data_dir = mgr.data_dir.clone();
+    let runtime = mgr.runtime.clone();
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    let result = backup::restore(&archive, &data_dir, &runtime, passphrase.as_deref()).await;
+    drop(mgr);
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "settings.restore".into(),
+        subject: Some(path),
+        result: if result.is_ok() { AuditResult::Success } else { AuditResult::Failure },
+        details: Some(match &result {
+            Ok(report) => serde_json::json!({
+                "backup_version": report.nexus_version,
+                "files": report.files,
+                "volumes": report.volumes,
+                "skipped_volumes": report.skipped_volumes,
+                "missing_images": report.missing_images,
+                "store_key": report.store_key,
+            }),
+            Err(e) => serde_json::json!({"error": e.to_string()}),
+        }),
+    });
+    result.map_err(|e| e.to_string())
+}
@@ -1,14 +1,27 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tauri::Emitter;
 
-use crate::event_bus::cloud_event::CloudEvent;
+use crate::event_bus::cloud_event::{CloudEvent, PublishRequest};
+use crate::event_bus::export::{EventExporter, ExportConfig, ExportStatus};
 use crate::event_bus::log::EventLogQuery;
+use crate::event_bus::metrics::BusMetricsSnapshot;
+use crate::event_bus::mqtt::{MqttBridge, MqttBridgeConfig, MqttStatus};
+use crate::event_bus::predicate::Predicate;
+use crate::event_bus::quota::PublishQuota;
 use crate::event_bus::routing::{Filter, RouteAction, RoutingRule, RoutingRuleUpdate};
-use crate::event_bus::SharedEventBus;
+use crate::event_bus::schema::EventSchema;
+use crate::event_bus::store::{
+    CompactionReport, DeadLetter, ReplayRange, RetentionPolicy, StoreStats,
+};
+use crate::event_bus::subscription::{EventReceiver, RedeliveryPolicy, SubscriberKind};
+use crate::event_bus::timer::{TimerScheduler, TimerUpdate, TimerView};
+use crate::event_bus::{Dispatch, EventBus, SharedEventBus, SharedEventStore};
+use crate::AppState;
 
 // -- Event Log commands --
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct EventLogEntry {
     pub id: String,
     pub source: String,
@@ -17,6 +30,12 @@ pub struct EventLogEntry {
     pub time: String,
     pub subject: Option<String>,
     pub data: Value,
+    /// ID of the event that started the causal chain this one belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlationid: Option<String>,
+    /// ID of the event that directly caused this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causationid: Option<String>,
 }
 
 impl From<&CloudEvent> for EventLogEntry {
@@ -28,6 +47,8 @@ impl From<&CloudEvent> for EventLogEntry {
             time: e.time.to_rfc3339(),
             subject: e.subject.clone(),
             data: e.data.clone(),
+            correlationid: e.correlation_id().map(str::to_string),
+            causationid: e.causation_id().map(str::to_string),
         }
     }
 }
@@ -63,6 +84,17 @@ pub async fn event_log_query(
     Ok(events.into_iter().map(EventLogEntry::from).collect())
 }
 
+/// Every event in the causal chain of an event, oldest first: what set it
+/// off and everything published as a result.
+#[tauri::command]
+pub async fn event_log_chain(
+    event_bus: tauri::State<'_, SharedEventBus>,
+    event_id: String,
+) -> Result<Vec<EventLogEntry>, String> {
+    let chain = event_bus.read().await.causal_chain(&event_id)?;
+    Ok(chain.iter().map(EventLogEntry::from).collect())
+}
+
 /// Get the total number of events in the log.
 #[tauri::command]
 pub async fn event_log_count(
@@ -72,6 +104,444 @@ pub async fn event_log_count(
     Ok(bus.log_len())
 }
 
+// -- Frontend bridge --
+
+/// Source of events the user publishes from the UI.
+const USER_SOURCE: &str = "nexus://user";
+
+#[derive(Debug, Serialize)]
+pub struct FrontendSubscription {
+    pub subscription_id: String,
+    /// Tauri event name the matching events are emitted on.
+    pub channel: String,
+}
+
+/// Subscribe the frontend to event bus events. Matching events are emitted
+/// as Tauri events on the returned channel until `event_unsubscribe`.
+#[tauri::command]
+pub async fn event_subscribe(
+    app: tauri::AppHandle,
+    event_bus: tauri::State<'_, SharedEventBus>,
+    type_pattern: String,
+    source_pattern: Option<String>,
+    filter: Option<String>,
+) -> Result<FrontendSubscription, String> {
+    let (subscription, mut rx) = subscribe_frontend(
+        &mut *event_bus.write().await,
+        &type_pattern,
+        source_pattern.as_deref(),
+        filter.as_deref(),
+    )?;
+
+    let emit_channel = subscription.channel.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if let Err(e) = app.emit(&emit_channel, EventLogEntry::from(&event)) {
+                log::warn!("Failed to emit event on {}: {}", emit_channel, e);
+            }
+        }
+    });
+
+    Ok(subscription)
+}
+
+/// Subscribe a fresh frontend channel to events matching the patterns and
+/// `filter` predicate.
+fn subscribe_frontend(
+    bus: &mut EventBus,
+    type_pattern: &str,
+    source_pattern: Option<&str>,
+    filter: Option<&str>,
+) -> Result<(FrontendSubscription, EventReceiver), String> {
+    let predicate = filter.map(Predicate::parse).transpose()?;
+    let channel = format!("nexus://event-bus/{}", uuid::Uuid::new_v4().simple());
+    let (subscription_id, rx) = bus.subscribe_with_ack(
+        type_pattern,
+        source_pattern,
+        predicate,
+        SubscriberKind::Frontend {
+            channel: channel.clone(),
+        },
+        false,
+    )?;
+    Ok((
+        FrontendSubscription {
+            subscription_id,
+            channel,
+        },
+        rx,
+    ))
+}
+
+/// End a frontend subscription.
+#[tauri::command]
+pub async fn event_unsubscribe(
+    event_bus: tauri::State<'_, SharedEventBus>,
+    subscription_id: String,
+) -> Result<(), String> {
+    event_bus.write().await.unsubscribe(&subscription_id);
+    Ok(())
+}
+
+/// Publish an event on the user's behalf, e.g. from a dashboard button.
+/// Routing rules run as for any other event. Returns the event ID.
+#[tauri::command]
+pub async fn event_publish(
+    dispatch: tauri::State<'_, Dispatch>,
+    event: PublishRequest,
+) -> Result<String, String> {
+    dispatch.publish(user_event(event)?).await
+}
+
+/// The event a user publishes, attributed to [`USER_SOURCE`].
+fn user_event(request: PublishRequest) -> Result<CloudEvent, String> {
+    let event = request.into_cloud_event(USER_SOURCE.to_string());
+    event.validate()?;
+    Ok(event)
+}
+
+// -- Export bridge --
+
+/// Get the event export settings.
+#[tauri::command]
+pub async fn event_export_get(state: tauri::State<'_, AppState>) -> Result<ExportConfig, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.event_export.clone())
+}
+
+/// Save the event export settings and restart the exporter with them.
+#[tauri::command]
+pub async fn event_export_set(
+    state: tauri::State<'_, AppState>,
+    dispatch: tauri::State<'_, Dispatch>,
+    exporter: tauri::State<'_, EventExporter>,
+    config: ExportConfig,
+) -> Result<(), String> {
+    config.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.event_export = config.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    exporter.apply(config, dispatch.inner().clone());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn event_export_status(
+    exporter: tauri::State<'_, EventExporter>,
+) -> Result<ExportStatus, String> {
+    Ok(exporter.status())
+}
+
+// -- MQTT bridge --
+
+/// Get the MQTT broker connection and routes.
+#[tauri::command]
+pub async fn mqtt_bridge_get(
+    state: tauri::State<'_, AppState>,
+) -> Result<MqttBridgeConfig, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.mqtt_bridge.clone())
+}
+
+/// Save the MQTT bridge settings and reconnect with them.
+#[tauri::command]
+pub async fn mqtt_bridge_set(
+    state: tauri::State<'_, AppState>,
+    dispatch: tauri::State<'_, Dispatch>,
+    bridge: tauri::State<'_, MqttBridge>,
+    config: MqttBridgeConfig,
+) -> Result<(), String> {
+    config.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.mqtt_bridge = config.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    bridge.apply(config, dispatch.inner().clone());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn mqtt_bridge_status(
+    bridge: tauri::State<'_, MqttBridge>,
+) -> Result<MqttStatus, String> {
+    Ok(bridge.status())
+}
+
+// -- Subscriber redelivery --
+
+/// Get the redelivery policy for subscribers that acknowledge events.
+#[tauri::command]
+pub async fn event_redelivery_get(
+    state: tauri::State<'_, AppState>,
+) -> Result<RedeliveryPolicy, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.event_redelivery.clone())
+}
+
+/// Set the redelivery policy. Applies to pending events on their next attempt.
+#[tauri::command]
+pub async fn event_redelivery_set(
+    state: tauri::State<'_, AppState>,
+    event_bus: tauri::State<'_, SharedEventBus>,
+    policy: RedeliveryPolicy,
+) -> Result<(), String> {
+    policy.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.event_redelivery = policy.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    event_bus.write().await.set_redelivery_policy(policy);
+    Ok(())
+}
+
+// -- Timers --
+
+/// List timers with when each fires next.
+#[tauri::command]
+pub async fn timer_list(scheduler: tauri::State<'_, TimerScheduler>) -> Result<Vec<TimerView>, String> {
+    Ok(scheduler.list())
+}
+
+/// Create a timer publishing `nexus.timer.<name>` on a cron schedule.
+#[tauri::command]
+pub async fn timer_create(
+    scheduler: tauri::State<'_, TimerScheduler>,
+    dispatch: tauri::State<'_, Dispatch>,
+    name: String,
+    schedule: String,
+    data: Option<Value>,
+) -> Result<String, String> {
+    scheduler.create(name, schedule, data.unwrap_or(Value::Null), &dispatch)
+}
+
+/// Update a timer.
+#[tauri::command]
+pub async fn timer_update(
+    scheduler: tauri::State<'_, TimerScheduler>,
+    dispatch: tauri::State<'_, Dispatch>,
+    timer_id: String,
+    name: Option<String>,
+    schedule: Option<String>,
+    data: Option<Value>,
+    enabled: Option<bool>,
+) -> Result<(), String> {
+    scheduler.update(
+        &timer_id,
+        TimerUpdate {
+            name,
+            schedule,
+            data,
+            enabled,
+        },
+        &dispatch,
+    )
+}
+
+/// Delete a timer.
+#[tauri::command]
+pub async fn timer_delete(
+    scheduler: tauri::State<'_, TimerScheduler>,
+    dispatch: tauri::State<'_, Dispatch>,
+    timer_id: String,
+) -> Result<(), String> {
+    scheduler.delete(&timer_id, &dispatch)
+}
+
+/// Publish a timer's event now, e.g. to test the automations it drives.
+#[tauri::command]
+pub async fn timer_fire(
+    scheduler: tauri::State<'_, TimerScheduler>,
+    dispatch: tauri::State<'_, Dispatch>,
+    timer_id: String,
+) -> Result<String, String> {
+    scheduler.fire(&timer_id, &dispatch).await
+}
+
+// -- Publisher quotas --
+
+/// Get the rate and payload size limits for plugins publishing events.
+#[tauri::command]
+pub async fn event_quota_get(state: tauri::State<'_, AppState>) -> Result<PublishQuota, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.event_quota.clone())
+}
+
+/// Set the publisher limits. Applies to the next event published.
+#[tauri::command]
+pub async fn event_quota_set(
+    state: tauri::State<'_, AppState>,
+    event_bus: tauri::State<'_, SharedEventBus>,
+    quota: PublishQuota,
+) -> Result<(), String> {
+    quota.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.event_quota = quota.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    event_bus.write().await.set_publish_quota(quota);
+    Ok(())
+}
+
+// -- Retention --
+
+#[derive(Debug, Serialize)]
+pub struct EventLogStats {
+    /// Events in the in-memory log.
+    pub memory_events: usize,
+    pub memory_capacity: usize,
+    pub store: StoreStats,
+}
+
+/// Get how much event history is kept.
+#[tauri::command]
+pub async fn event_retention_get(
+    state: tauri::State<'_, AppState>,
+) -> Result<RetentionPolicy, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.event_retention.clone())
+}
+
+/// Set the retention policy. The in-memory log shrinks right away; the store
+/// on the next compaction.
+#[tauri::command]
+pub async fn event_retention_set(
+    state: tauri::State<'_, AppState>,
+    event_bus: tauri::State<'_, SharedEventBus>,
+    policy: RetentionPolicy,
+) -> Result<(), String> {
+    policy.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.event_retention = policy.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    event_bus.write().await.apply_retention(&policy);
+    Ok(())
+}
+
+/// Compact the event log now instead of waiting for the hourly pass.
+#[tauri::command]
+pub async fn event_log_compact(
+    state: tauri::State<'_, AppState>,
+    dispatch: tauri::State<'_, Dispatch>,
+) -> Result<CompactionReport, String> {
+    let policy = state.read().await.settings.event_retention.clone();
+    dispatch.bus.write().await.apply_retention(&policy);
+    dispatch.store.compact(&policy)
+}
+
+/// Event bus throughput, subscriber queue depths, and route action latency.
+#[tauri::command]
+pub async fn event_bus_metrics(
+    dispatch: tauri::State<'_, Dispatch>,
+) -> Result<BusMetricsSnapshot, String> {
+    Ok(dispatch.metrics().await)
+}
+
+/// Current size of the in-memory log and the event store.
+#[tauri::command]
+pub async fn event_log_stats(dispatch: tauri::State<'_, Dispatch>) -> Result<EventLogStats, String> {
+    let store = dispatch.store.stats()?;
+    let bus = dispatch.bus.read().await;
+    Ok(EventLogStats {
+        memory_events: bus.log_len(),
+        memory_capacity: bus.log_capacity(),
+        store,
+    })
+}
+
+// -- Replay --
+
+/// Where replayed events go.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "target", rename_all = "snake_case")]
+pub enum ReplayTarget {
+    /// A plugin's or extension's connected subscriptions.
+    Subscriber { subscriber: SubscriberKind },
+    /// A routing rule, whose action runs once for each matching event.
+    Rule { rule_id: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    /// Events delivered to the subscriber, or actions queued for the rule.
+    pub replayed: usize,
+    /// Sequence number of the last stored event read, to continue from.
+    pub last_seq: Option<i64>,
+}
+
+/// Replay stored events into a subscriber or routing rule, e.g. so a newly
+/// installed automation can catch up on history.
+#[tauri::command]
+pub async fn event_replay(
+    dispatch: tauri::State<'_, Dispatch>,
+    target: ReplayTarget,
+    range: ReplayRange,
+) -> Result<ReplayReport, String> {
+    let stored = dispatch.store.events_in_range(&range)?;
+    let last_seq = stored.last().map(|&(seq, _)| seq);
+    let events: Vec<CloudEvent> = stored.into_iter().map(|(_, e)| e).collect();
+
+    let bus = dispatch.bus.read().await;
+    let replayed = match target {
+        ReplayTarget::Subscriber { subscriber } => bus.replay_to_subscriber(&subscriber, &events)?,
+        ReplayTarget::Rule { rule_id } => {
+            let matched = bus.replay_to_rule(&rule_id, events)?;
+            drop(bus);
+            for (event, action) in &matched {
+                dispatch
+                    .executor
+                    .execute_durable(&dispatch.store, vec![action.clone()], event);
+            }
+            matched.len()
+        }
+    };
+    Ok(ReplayReport { replayed, last_seq })
+}
+
+// -- Event schemas --
+
+/// List the JSON Schemas plugins and extensions registered for their events.
+#[tauri::command]
+pub async fn event_schema_list(
+    event_bus: tauri::State<'_, SharedEventBus>,
+) -> Result<Vec<EventSchema>, String> {
+    Ok(event_bus.read().await.list_event_schemas().to_vec())
+}
+
+/// Remove the schema for an event type, whoever registered it.
+#[tauri::command]
+pub async fn event_schema_delete(
+    event_bus: tauri::State<'_, SharedEventBus>,
+    event_type: String,
+) -> Result<(), String> {
+    event_bus.write().await.remove_event_schema(&event_type, None)
+}
+
+// -- Dead letter queue --
+
+/// List route action deliveries that failed on every attempt.
+#[tauri::command]
+pub async fn dead_letter_list(
+    event_store: tauri::State<'_, SharedEventStore>,
+    limit: Option<usize>,
+) -> Result<Vec<DeadLetter>, String> {
+    event_store.list_dead_letters(limit.unwrap_or(200))
+}
+
+/// Requeue dead-lettered deliveries. Returns how many were requeued.
+#[tauri::command]
+pub async fn dead_letter_retry(
+    event_store: tauri::State<'_, SharedEventStore>,
+    ids: Vec<i64>,
+) -> Result<usize, String> {
+    event_store.retry_dead_letters(&ids)
+}
+
+/// Delete dead-lettered deliveries — the given IDs, or all when omitted.
+#[tauri::command]
+pub async fn dead_letter_purge(
+    event_store: tauri::State<'_, SharedEventStore>,
+    ids: Option<Vec<i64>>,
+) -> Result<usize, String> {
+    event_store.purge_dead_letters(ids.as_deref())
+}
+
 // -- Routing Rule commands --
 
 /// List all routing rules.
@@ -145,3 +615,51 @@ pub async fn routing_rule_delete(
     let mut bus = event_bus.write().await;
     bus.delete_routing_rule(&rule_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(event_type: &str, data: Value) -> PublishRequest {
+        serde_json::from_value(serde_json::json!({ "type": event_type, "data": data })).unwrap()
+    }
+
+    #[test]
+    fn user_events_are_attributed_and_validated() {
+        let event = user_event(request("com.example.deploy", serde_json::json!({}))).unwrap();
+        assert_eq!(event.source, USER_SOURCE);
+        assert_eq!(event.event_type, "com.example.deploy");
+        assert!(user_event(request("", Value::Null)).is_err());
+    }
+
+    #[test]
+    fn frontend_subscription_receives_matching_events() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut bus = EventBus::new(tmp.path());
+        let (subscription, mut rx) =
+            subscribe_frontend(&mut bus, "com.example.*", None, Some(r#"data.env == "prod""#))
+                .unwrap();
+        assert!(subscription.channel.starts_with("nexus://event-bus/"));
+
+        let prod = user_event(request("com.example.deploy", serde_json::json!({ "env": "prod" })));
+        let dev = user_event(request("com.example.deploy", serde_json::json!({ "env": "dev" })));
+        let other = user_event(request("org.other.deploy", serde_json::json!({ "env": "prod" })));
+        for event in [dev, other, prod] {
+            bus.publish(event.unwrap());
+        }
+        let received = rx.try_recv().unwrap();
+        assert_eq!(received.data["env"], "prod");
+        assert!(rx.try_recv().is_err());
+
+        bus.unsubscribe(&subscription.subscription_id);
+        bus.publish(user_event(request("com.example.deploy", serde_json::json!({ "env": "prod" }))).unwrap());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn bad_patterns_and_filters_are_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut bus = EventBus::new(tmp.path());
+        assert!(subscribe_frontend(&mut bus, "com.example.*", None, Some("data.env ==")).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use tauri::Manager;
 
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::event_bus::subscription::SubscriberKind;
 use crate::extensions::capability::Capability;
 use crate::extensions::manifest::ResourceTypeDef;
 use crate::extensions::registry::ExtensionRegistry;
@@ -235,8 +236,11 @@ pub async fn extension_install(
     app: tauri::AppHandle,
     manifest_url: String,
 ) -> Result<InstalledExtension, String> {
+    state.read().await.check_writable().map_err(|e| e.to_string())?;
+
     // Fetch manifest
-    let manifest = crate::plugin_manager::registry::fetch_extension_manifest(&manifest_url)
+    let auth = state.read().await.manifest_auth(&manifest_url);
+    let manifest = crate::plugin_manager::registry::fetch_extension_manifest(&manifest_url, auth.as_ref())
         .await
         .map_err(|e| e.to_string())?;
 
@@ -452,6 +456,11 @@ pub async fn extension_remove(
 
     match mgr.remove_extension(&ext_id) {
         Ok(()) => {
+            if let Some(dispatch) = app.try_state::<crate::event_bus::Dispatch>() {
+                dispatch.bus.write().await.forget_subscriber(&SubscriberKind::Extension {
+                    ext_id: ext_id.clone(),
+                });
+            }
             lifecycle_events::emit(Some(&app), LifecycleEvent::ExtensionRemoved {
                 ext_id: ext_id.clone(),
             });
@@ -480,11 +489,25 @@ pub async fn extension_remove(
 /// Preview an extension from the marketplace (fetch manifest without installing).
 #[tauri::command]
 pub async fn extension_preview(
+    state: tauri::State<'_, AppState>,
     manifest_url: String,
-) -> Result<crate::extensions::manifest::ExtensionManifest, String> {
-    crate::plugin_manager::registry::fetch_extension_manifest(&manifest_url)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<
+    crate::plugin_manager::registry::ManifestPreview<crate::extensions::manifest::ExtensionManifest>,
+    String,
+> {
+    let (auth, verified_identity) = {
+        let mgr = state.read().await;
+        (mgr.manifest_auth(&manifest_url), mgr.verified_publisher(&manifest_url))
+    };
+    let manifest =
+        crate::plugin_manager::registry::fetch_extension_manifest(&manifest_url, auth.as_ref())
+            .await
+            .map_err(|e| e.to_string())?;
+    Ok(crate::plugin_manager::registry::ManifestPreview {
+        manifest,
+        verified: verified_identity.is_some(),
+        verified_identity,
+    })
 }
 
 use crate::extensions::loader::cargo_build_extension;
@@ -501,6 +524,8 @@ pub async fn extension_install_local(
 ) -> Result<InstalledExtension, String> {
     use crate::extensions::manifest::ExtensionManifest;
 
+    state.read().await.check_writable().map_err(|e| e.to_string())?;
+
     // Read the manifest to get the ext_id for events before the install
     let manifest_data = std::fs::read_to_string(&manifest_path)
         .map_err(|e| format!("Failed to read manifest: {}", e))?;
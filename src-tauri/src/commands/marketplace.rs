@@ -1,19 +1,30 @@
-use crate::plugin_manager::registry::RegistryEntry;
+use crate::plugin_manager::registry::{MarketplaceCollection, MarketplacePage, MarketplaceQuery};
 use crate::AppState;
 
+/// One page of marketplace results, with category facets.
 #[tauri::command]
 pub async fn marketplace_search(
     state: tauri::State<'_, AppState>,
-    query: String,
-) -> Result<Vec<RegistryEntry>, String> {
+    query: MarketplaceQuery,
+) -> Result<MarketplacePage, String> {
     let mgr = state.read().await;
-    Ok(mgr.search_marketplace(&query))
+    Ok(mgr.browse_marketplace(&query))
+}
+
+/// Curated collections from all enabled registries.
+#[tauri::command]
+pub async fn marketplace_collections(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<MarketplaceCollection>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.marketplace_collections())
 }
 
 #[tauri::command]
 pub async fn marketplace_refresh(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut mgr = state.write().await;
-    mgr.refresh_registry().await.map_err(|e| e.to_string())
+    let cache = state.read().await.fetch_registry().await;
+    state.write().await.apply_registry(cache);
+    Ok(())
 }
 
 /// Load registry data from the local disk cache (no network).
@@ -1,7 +1,10 @@
 use crate::api_keys::ApiKeyStore;
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
-use crate::plugin_manager::storage::{McpPluginSettings, McpSettings};
+use crate::host_api::mcp::registry::tool_timeout;
+use crate::host_api::mcp::stats::ToolStatsEntry;
+use crate::host_api::mcp::{McpSessionInfo, McpSessionStore};
+use crate::plugin_manager::storage::{McpClientLimits, McpPluginSettings, McpSettings};
 use crate::AppState;
 use serde::Serialize;
 
@@ -19,6 +22,47 @@ pub struct McpToolStatus {
     pub required_permissions: Vec<String>,
     pub permissions_granted: bool,
     pub requires_approval: bool,
+    /// Effective call timeout (user override or category default).
+    pub timeout_secs: u64,
+}
+
+/// A permanent tool approval as shown in the review UI.
+///
+/// `client_id: None` marks a legacy approval that applies to every client.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpToolApprovalView {
+    pub client_id: Option<String>,
+    pub client_name: Option<String>,
+    pub plugin_id: String,
+    pub tool: String,
+    /// Namespaced tool name (`{plugin_id}.{tool}`).
+    pub name: String,
+    pub approved_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Split a namespaced tool name into `(plugin_id, tool_name)`.
+///
+/// Plugin IDs contain dots, so the split point is found by matching against
+/// known providers: installed plugins, the virtual "nexus" plugin for built-in
+/// tools, and extensions exposing MCP operations.
+fn split_tool_name(mgr: &crate::plugin_manager::PluginManager, namespaced: &str) -> Option<(String, String)> {
+    let mut plugin_ids: Vec<String> = mgr
+        .storage
+        .list()
+        .iter()
+        .map(|p| p.manifest.id.clone())
+        .collect();
+    plugin_ids.push("nexus".to_string());
+    for ext_info in mgr.extensions.list() {
+        if ext_info.operations.iter().any(|op| op.mcp_expose) {
+            plugin_ids.push(ext_info.id);
+        }
+    }
+
+    plugin_ids.into_iter().find_map(|pid| {
+        let tool_name = namespaced.strip_prefix(&format!("{}.", pid))?;
+        (!tool_name.is_empty()).then(|| (pid, tool_name.to_string()))
+    })
 }
 
 #[tauri::command]
@@ -48,51 +92,19 @@ pub async fn mcp_set_enabled(
             .enabled = enabled;
     } else if let Some(rest) = scope.strip_prefix("tool:") {
         // Format: "tool:{plugin_id}.{tool_name}"
-        // Find the split point — plugin IDs contain dots, so find the matching plugin
-        let mut found = false;
-        let mut plugin_ids: Vec<String> = mgr
-            .storage
-            .list()
-            .iter()
-            .map(|p| p.manifest.id.clone())
-            .collect();
-        // Include the virtual "nexus" plugin for built-in MCP tools
-        plugin_ids.push("nexus".to_string());
-        // Include extension IDs for extension MCP tools
-        for ext_info in mgr.extensions.list() {
-            if ext_info.operations.iter().any(|op| op.mcp_expose) {
-                plugin_ids.push(ext_info.id);
-            }
-        }
-
-        for pid in &plugin_ids {
-            let prefix = format!("{}.", pid);
-            if let Some(tool_name) = rest.strip_prefix(&prefix) {
-                if !tool_name.is_empty() {
-                    let plugin_settings = mgr
-                        .mcp_settings
-                        .plugins
-                        .entry(pid.clone())
-                        .or_insert_with(McpPluginSettings::default);
-                    if enabled {
-                        if !plugin_settings.enabled_tools.contains(&tool_name.to_string()) {
-                            plugin_settings
-                                .enabled_tools
-                                .push(tool_name.to_string());
-                        }
-                    } else {
-                        plugin_settings
-                            .enabled_tools
-                            .retain(|t| t != tool_name);
-                    }
-                    found = true;
-                    break;
-                }
+        let (pid, tool_name) = split_tool_name(&mgr, rest)
+            .ok_or_else(|| format!("Unknown tool scope: {}", scope))?;
+        let plugin_settings = mgr
+            .mcp_settings
+            .plugins
+            .entry(pid)
+            .or_insert_with(McpPluginSettings::default);
+        if enabled {
+            if !plugin_settings.enabled_tools.contains(&tool_name) {
+                plugin_settings.enabled_tools.push(tool_name);
             }
-        }
-
-        if !found {
-            return Err(format!("Unknown tool scope: {}", scope));
+        } else {
+            plugin_settings.enabled_tools.retain(|t| *t != tool_name);
         }
     } else {
         return Err(format!("Invalid scope: {}. Expected 'global', 'plugin:{{id}}', or 'tool:{{plugin_id}}.{{tool_name}}'", scope));
@@ -152,6 +164,7 @@ pub async fn mcp_list_tools(
                 required_permissions: tool.permissions.clone(),
                 permissions_granted: all_perms_granted,
                 requires_approval: tool.requires_approval,
+                timeout_secs: tool_timeout(&mgr.mcp_settings, &format!("{}.{}", plugin.manifest.id, tool.name)).as_secs(),
             });
         }
     }
@@ -165,6 +178,7 @@ pub async fn mcp_list_tools(
         let tool_in_whitelist =
             nexus_mcp.is_some_and(|s| s.enabled_tools.contains(&local_name.to_string()));
 
+        let timeout_secs = tool_timeout(&mgr.mcp_settings, &builtin.name).as_secs();
         tools.push(McpToolStatus {
             name: builtin.name,
             description: builtin.description,
@@ -178,6 +192,7 @@ pub async fn mcp_list_tools(
             required_permissions: vec![],
             permissions_granted: true,
             requires_approval: builtin.requires_approval,
+            timeout_secs,
         });
     }
 
@@ -198,6 +213,7 @@ pub async fn mcp_list_tools(
         let tool_in_whitelist =
             ext_mcp.is_some_and(|s| s.enabled_tools.contains(&local_name.to_string()));
 
+        let timeout_secs = tool_timeout(&mgr.mcp_settings, &ext_tool.name).as_secs();
         tools.push(McpToolStatus {
             name: ext_tool.name,
             description: ext_tool.description,
@@ -211,12 +227,186 @@ pub async fn mcp_list_tools(
             required_permissions: vec![],
             permissions_granted: true,
             requires_approval: ext_tool.requires_approval,
+            timeout_secs,
         });
     }
 
     Ok(tools)
 }
 
+/// Override the call timeout for a single tool (`{plugin_id}.{tool_name}`).
+/// `timeout_secs: None` restores the category default.
+#[tauri::command]
+pub async fn mcp_set_tool_timeout(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    tool: String,
+    timeout_secs: Option<u64>,
+) -> Result<(), String> {
+    if timeout_secs == Some(0) {
+        return Err("Timeout must be at least 1 second".into());
+    }
+    let mut mgr = state.write().await;
+    let (pid, tool_name) = split_tool_name(&mgr, &tool)
+        .ok_or_else(|| format!("Unknown tool: {}", tool))?;
+    let plugin_settings = mgr
+        .mcp_settings
+        .plugins
+        .entry(pid)
+        .or_insert_with(McpPluginSettings::default);
+    match timeout_secs {
+        Some(secs) => {
+            plugin_settings.tool_timeouts.insert(tool_name, secs);
+        }
+        None => {
+            plugin_settings.tool_timeouts.remove(&tool_name);
+        }
+    }
+    mgr.mcp_settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.mcp.tool_timeout".into(),
+        subject: Some(tool), result: AuditResult::Success,
+        details: Some(serde_json::json!({"timeout_secs": timeout_secs})),
+    });
+    Ok(())
+}
+
+/// Set call limits for the MCP gateway.
+///
+/// `client_id: None` updates the defaults applied to every client. For a
+/// specific client, `limits: None` removes its override.
+#[tauri::command]
+pub async fn mcp_set_client_limits(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    client_id: Option<String>,
+    limits: Option<McpClientLimits>,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    match (&client_id, limits.clone()) {
+        (None, Some(l)) => mgr.mcp_settings.default_client_limits = l,
+        (None, None) => return Err("Default limits cannot be removed".into()),
+        (Some(id), Some(l)) => {
+            mgr.mcp_settings.client_limits.insert(id.clone(), l);
+        }
+        (Some(id), None) => {
+            mgr.mcp_settings.client_limits.remove(id);
+        }
+    }
+    mgr.mcp_settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.mcp.client_limits".into(),
+        subject: client_id, result: AuditResult::Success,
+        details: Some(serde_json::json!({"limits": limits})),
+    });
+    Ok(())
+}
+
+/// List every permanent "always allow" approval, grouped by client.
+#[tauri::command]
+pub async fn mcp_list_tool_approvals(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<McpToolApprovalView>, String> {
+    let mgr = state.read().await;
+    let settings = &mgr.mcp_settings;
+    let mut approvals: Vec<McpToolApprovalView> = settings
+        .client_approvals
+        .iter()
+        .map(|a| McpToolApprovalView {
+            client_id: Some(a.client_id.clone()),
+            client_name: Some(a.client_name.clone()),
+            plugin_id: a.plugin_id.clone(),
+            tool: a.tool.clone(),
+            name: format!("{}.{}", a.plugin_id, a.tool),
+            approved_at: Some(a.approved_at),
+        })
+        .collect();
+    for (pid, ps) in &settings.plugins {
+        for tool in &ps.approved_tools {
+            approvals.push(McpToolApprovalView {
+                client_id: None,
+                client_name: None,
+                plugin_id: pid.clone(),
+                tool: tool.clone(),
+                name: format!("{}.{}", pid, tool),
+                approved_at: None,
+            });
+        }
+    }
+    approvals.sort_by(|a, b| a.client_id.cmp(&b.client_id).then_with(|| a.name.cmp(&b.name)));
+    Ok(approvals)
+}
+
+/// Revoke one permanent approval. `client_id: None` revokes a legacy
+/// approval that applies to every client.
+#[tauri::command]
+pub async fn mcp_revoke_tool_approval(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    client_id: Option<String>,
+    plugin_id: String,
+    tool: String,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    if !mgr.mcp_settings.revoke_tool_approval(client_id.as_deref(), &plugin_id, &tool) {
+        return Err(format!("No approval found for {}.{}", plugin_id, tool));
+    }
+    mgr.mcp_settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.mcp.approval_revoked".into(),
+        subject: Some(format!("{}.{}", plugin_id, tool)), result: AuditResult::Success,
+        details: Some(serde_json::json!({"client_id": client_id})),
+    });
+    Ok(())
+}
+
+/// List live MCP gateway sessions with their client identity and call counts.
+#[tauri::command]
+pub async fn mcp_list_sessions(
+    sessions: tauri::State<'_, McpSessionStore>,
+) -> Result<Vec<McpSessionInfo>, String> {
+    Ok(sessions.list())
+}
+
+/// Disconnect an MCP session immediately.
+#[tauri::command]
+pub async fn mcp_revoke_session(
+    sessions: tauri::State<'_, McpSessionStore>,
+    audit: tauri::State<'_, AuditWriter>,
+    session_id: String,
+) -> Result<(), String> {
+    if !sessions.revoke(&session_id).await {
+        return Err(format!("Session not found: {}", session_id));
+    }
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "mcp.session.revoke".into(),
+        subject: Some(session_id), result: AuditResult::Success, details: None,
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn mcp_tool_stats(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ToolStatsEntry>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.mcp_tool_stats.snapshot())
+}
+
+#[tauri::command]
+pub async fn mcp_tool_stats_reset(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+) -> Result<(), String> {
+    let mgr = state.read().await;
+    mgr.mcp_tool_stats.reset();
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.mcp.stats_reset".into(),
+        subject: None, result: AuditResult::Success, details: None,
+    });
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn mcp_config_snippet(
     _state: tauri::State<'_, AppState>,
@@ -287,3 +477,175 @@ pub async fn mcp_config_snippet(
         "kiro_config": kiro_config
     }))
 }
+
+/// Where an MCP client keeps its server list, and how it expects entries.
+struct McpClientConfigTarget {
+    path: std::path::PathBuf,
+    /// Top-level key holding the server map.
+    servers_key: &'static str,
+    entry: serde_json::Value,
+}
+
+fn mcp_client_config_target(client: &str, bearer: &str) -> Result<McpClientConfigTarget, String> {
+    let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+    let config = dirs::config_dir().ok_or("Could not determine config directory")?;
+    let url = "http://127.0.0.1:9600/mcp";
+    let target = match client {
+        // ~/Library/Application Support/Claude, %APPDATA%\Claude, ~/.config/Claude
+        "claude_desktop" => McpClientConfigTarget {
+            path: config.join("Claude").join("claude_desktop_config.json"),
+            servers_key: "mcpServers",
+            entry: serde_json::json!({ "url": url, "headers": { "Authorization": bearer } }),
+        },
+        // User-scoped servers live at the top level of ~/.claude.json
+        "claude_code" => McpClientConfigTarget {
+            path: home.join(".claude.json"),
+            servers_key: "mcpServers",
+            entry: serde_json::json!({ "type": "http", "url": url, "headers": { "Authorization": bearer } }),
+        },
+        "cursor" => McpClientConfigTarget {
+            path: home.join(".cursor").join("mcp.json"),
+            servers_key: "mcpServers",
+            entry: serde_json::json!({ "url": url, "headers": { "Authorization": bearer } }),
+        },
+        // VS Code user-level mcp.json uses "servers", not "mcpServers"
+        "vscode" => McpClientConfigTarget {
+            path: config.join("Code").join("User").join("mcp.json"),
+            servers_key: "servers",
+            entry: serde_json::json!({ "type": "http", "url": url, "headers": { "Authorization": bearer } }),
+        },
+        other => return Err(format!("Unsupported MCP client: {}", other)),
+    };
+    Ok(target)
+}
+
+/// `root` with `entry` set as the `nexus` server under `servers_key`,
+/// replacing any earlier one and keeping everything else.
+fn merge_server_entry(
+    mut root: serde_json::Value,
+    servers_key: &str,
+    entry: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let servers = root
+        .as_object_mut()
+        .ok_or("Expected a JSON object")?
+        .entry(servers_key)
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| format!("\"{}\" is not an object", servers_key))?;
+    servers.insert("nexus".into(), entry);
+    Ok(root)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct McpConfigInstallResult {
+    pub path: String,
+    /// Copy of the previous config, if the file already existed.
+    pub backup_path: Option<String>,
+}
+
+/// Write (or merge) the Nexus server entry into an MCP client's config file.
+///
+/// Supported clients: `claude_desktop`, `claude_code`, `cursor`, `vscode`.
+/// Other servers and settings in the file are preserved, and the original is
+/// copied to `<file>.nexus-backup` first. Malformed files are left untouched.
+#[tauri::command]
+pub async fn mcp_config_install(
+    api_keys: tauri::State<'_, ApiKeyStore>,
+    audit: tauri::State<'_, AuditWriter>,
+    client: String,
+) -> Result<McpConfigInstallResult, String> {
+    let default_key = api_keys
+        .get_default_raw()
+        .ok_or("No default API key — create one first")?;
+    let target = mcp_client_config_target(&client, &format!("Bearer {}", default_key))?;
+
+    let root = match std::fs::read_to_string(&target.path) {
+        Ok(data) if !data.trim().is_empty() => serde_json::from_str::<serde_json::Value>(&data)
+            .map_err(|e| format!("{} is not valid JSON ({}); fix or remove it first", target.path.display(), e))?,
+        Ok(_) => serde_json::json!({}),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(format!("Failed to read {}: {}", target.path.display(), e)),
+    };
+    let root = merge_server_entry(root, target.servers_key, target.entry)
+        .map_err(|e| format!("{} in {}", e, target.path.display()))?;
+
+    let backup_path = if target.path.exists() {
+        let mut backup = target.path.clone().into_os_string();
+        backup.push(".nexus-backup");
+        let backup = std::path::PathBuf::from(backup);
+        std::fs::copy(&target.path, &backup)
+            .map_err(|e| format!("Failed to back up {}: {}", target.path.display(), e))?;
+        Some(backup.display().to_string())
+    } else {
+        if let Some(parent) = target.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        None
+    };
+
+    let data = serde_json::to_string_pretty(&root).map_err(|e| e.to_string())?;
+    crate::util::atomic_write(&target.path, data.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", target.path.display(), e))?;
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.mcp.config_install".into(),
+        subject: Some(client), result: AuditResult::Success,
+        details: Some(serde_json::json!({"path": target.path.display().to_string()})),
+    });
+
+    Ok(McpConfigInstallResult {
+        path: target.path.display().to_string(),
+        backup_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_keeps_other_servers_and_settings() {
+        let root = json!({
+            "theme": "dark",
+            "mcpServers": { "github": { "command": "gh-mcp" } }
+        });
+        let merged = merge_server_entry(root, "mcpServers", json!({ "url": "u" })).unwrap();
+        assert_eq!(merged["theme"], "dark");
+        assert_eq!(merged["mcpServers"]["github"]["command"], "gh-mcp");
+        assert_eq!(merged["mcpServers"]["nexus"]["url"], "u");
+
+        let merged = merge_server_entry(json!({}), "mcpServers", json!({ "url": "u" })).unwrap();
+        assert_eq!(merged, json!({ "mcpServers": { "nexus": { "url": "u" } } }));
+    }
+
+    #[test]
+    fn merge_overwrites_an_existing_nexus_entry() {
+        let root = json!({ "mcpServers": { "nexus": { "url": "old", "headers": { "Authorization": "Bearer old" } } } });
+        let merged = merge_server_entry(root, "mcpServers", json!({ "url": "new" })).unwrap();
+        assert_eq!(merged["mcpServers"]["nexus"], json!({ "url": "new" }));
+    }
+
+    #[test]
+    fn vscode_entries_go_under_servers() {
+        let target = mcp_client_config_target("vscode", "Bearer k").unwrap();
+        assert_eq!(target.servers_key, "servers");
+        let root = json!({ "servers": { "other": {} }, "inputs": [] });
+        let merged = merge_server_entry(root, target.servers_key, target.entry).unwrap();
+        assert_eq!(merged["servers"]["nexus"]["type"], "http");
+        assert_eq!(merged["servers"]["nexus"]["headers"]["Authorization"], "Bearer k");
+        assert!(merged["servers"]["other"].is_object());
+        assert!(merged.get("mcpServers").is_none());
+
+        assert!(mcp_client_config_target("zed", "Bearer k").is_err());
+    }
+
+    #[test]
+    fn merge_rejects_non_object_root_or_servers() {
+        assert!(merge_server_entry(json!([]), "mcpServers", json!({})).is_err());
+        assert!(merge_server_entry(json!("x"), "mcpServers", json!({})).is_err());
+        let err = merge_server_entry(json!({ "servers": [] }), "servers", json!({})).unwrap_err();
+        assert!(err.contains("\"servers\""));
+    }
+}
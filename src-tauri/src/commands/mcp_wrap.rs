@@ -1,19 +1,41 @@
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::commands::plugins::vet_image;
 use crate::lifecycle_events::{self, LifecycleEvent};
-use crate::mcp_wrap::{classify, discovery, generate, PluginMetadata};
+use crate::mcp_wrap::{
+    classify, discovery, docker_server_command, generate, parse_docker_run, rewrap_version,
+    McpDiscoveryResult, McpEnvVar, McpRuntime, McpWrapError, PluginMetadata, WrapSpec,
+};
 use crate::mcp_wrap::classify::ClassifiedTool;
-use crate::plugin_manager::{manifest::PluginManifest, storage::InstalledPlugin};
+use crate::plugin_manager::manifest::PluginManifest;
+use crate::plugin_manager::storage::{InstalledPlugin, PluginStatus};
+use crate::runtime::ContainerRuntime;
 use crate::permissions::Permission;
 use crate::AppState;
-use tauri::Manager;
+use std::collections::HashMap;
 
 /// Discover and classify tools from an MCP server command.
+///
+/// `env` is passed to the server. Env vars it needs but didn't get are
+/// reported in `required_env`; if it couldn't start at all, `tools` is empty
+/// and the caller should prompt for them and retry.
 #[tauri::command]
-pub async fn mcp_discover_tools(command: String) -> Result<Vec<ClassifiedTool>, String> {
-    let raw_tools = discovery::discover_tools(&command)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    Ok(classify::classify_tools(&raw_tools))
+pub async fn mcp_discover_tools(
+    command: String,
+    env: Option<HashMap<String, String>>,
+) -> Result<McpDiscoveryResult, String> {
+    let env = env.unwrap_or_default();
+    match discovery::discover_tools(&command, &env).await {
+        Ok(found) => Ok(McpDiscoveryResult {
+            tools: classify::classify_tools(&found.tools),
+            required_env: found.missing_env.iter().map(|n| McpEnvVar::new(n)).collect(),
+        }),
+        Err(McpWrapError::MissingEnv(names)) => Ok(McpDiscoveryResult {
+            tools: Vec::new(),
+            required_env: names.iter().map(|n| McpEnvVar::new(n)).collect(),
+        }),
+        Err(e) => Err(e.to_string()),
+    }
 }
 
 /// Suggest plugin metadata from an MCP server command.
@@ -22,7 +44,41 @@ pub async fn mcp_suggest_metadata(command: String) -> Result<PluginMetadata, Str
     Ok(crate::mcp_wrap::suggest_metadata(&command))
 }
 
+/// The command the bridge spawns inside the container. Docker images keep
+/// their own entrypoint, so the image is pulled and inspected to find it;
+/// its `-e KEY=VALUE` flags are merged into `env` (explicit values win).
+async fn resolve_server_command(
+    runtime: &dyn ContainerRuntime,
+    command: &str,
+    env: &mut HashMap<String, String>,
+) -> Result<String, String> {
+    if McpRuntime::detect(command).map_err(|e| e.to_string())? != McpRuntime::Docker {
+        return Ok(command.to_string());
+    }
+    let run = parse_docker_run(command)
+        .ok_or_else(|| format!("Could not determine Docker image from: {}", command))?;
+    for (key, value) in &run.env {
+        env.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+    if !runtime.image_exists(&run.image).await.unwrap_or(false) {
+        runtime
+            .pull_image(&run.image)
+            .await
+            .map_err(|e| format!("Failed to pull {}: {}", run.image, e))?;
+    }
+    let inspect = runtime
+        .inspect_image_raw(&run.image)
+        .await
+        .map_err(|e| format!("Failed to inspect {}: {}", run.image, e))?;
+    docker_server_command(&run, &inspect).map_err(|e| e.to_string())
+}
+
 /// Generate plugin artifacts, build Docker image, and install the plugin.
+/// Remote endpoints skip the build; the gateway connects to them directly.
+///
+/// `include_tools` exposes only the named subset of `tools`; `None` exposes
+/// all of them. `env` values (and `-e KEY=VALUE` flags from a `docker run`
+/// command) are stored in the plugin env store, never in the generated manifest.
 #[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn mcp_generate_and_install(
@@ -30,44 +86,78 @@ pub async fn mcp_generate_and_install(
     app_handle: tauri::AppHandle,
     command: String,
     tools: Vec<ClassifiedTool>,
+    include_tools: Option<Vec<String>>,
     metadata: PluginMetadata,
     approved_permissions: Vec<Permission>,
     deferred_permissions: Vec<Permission>,
+    env: Option<HashMap<String, String>>,
 ) -> Result<InstalledPlugin, String> {
-    let data_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    state.read().await.check_writable().map_err(|e| e.to_string())?;
+    let mut env = env.unwrap_or_default();
+    let (runtime, data_dir, locks) = {
+        let mgr = state.read().await;
+        (mgr.runtime.clone(), mgr.data_dir.clone(), mgr.plugin_locks.clone())
+    };
     let mcp_plugins_dir = data_dir.join("mcp-plugins");
     std::fs::create_dir_all(&mcp_plugins_dir)
         .map_err(|e| format!("Failed to create mcp-plugins dir: {}", e))?;
 
+    let server_command = resolve_server_command(runtime.as_ref(), &command, &mut env).await?;
+
     // 1. Generate plugin artifacts
-    let plugin_dir = generate::generate_plugin(&tools, &metadata, &command, &mcp_plugins_dir)
-        .map_err(|e| format!("Failed to generate plugin: {}", e))?;
+    let spec = WrapSpec {
+        command,
+        include_tools,
+    };
+    let plugin_dir = generate::generate_plugin(
+        &tools,
+        &metadata,
+        &spec,
+        &server_command,
+        "0.1.0",
+        &mcp_plugins_dir,
+    )
+    .map_err(|e| format!("Failed to generate plugin: {}", e))?;
 
-    // 2. Build Docker image from generated Dockerfile
+    // 2. Build Docker image from generated Dockerfile (remote endpoints have none)
     let manifest_path = plugin_dir.join("plugin.json");
     let manifest_data = std::fs::read_to_string(&manifest_path)
         .map_err(|e| format!("Failed to read generated manifest: {}", e))?;
     let manifest: PluginManifest = serde_json::from_str(&manifest_data)
         .map_err(|e| format!("Invalid generated manifest: {}", e))?;
 
-    let runtime = { state.read().await.runtime.clone() };
-    runtime
-        .build_image(&plugin_dir, &manifest.image)
-        .await
-        .map_err(|e| format!("Docker build failed: {}", e))?;
-
-    // 3. Install via PluginManager
     let plugin_id = manifest.id.clone();
+    let _plugin_lock = locks.lock(&plugin_id).await;
+    if !manifest.image.is_empty() {
+        runtime
+            .build_image(&plugin_dir, &manifest.image)
+            .await
+            .map_err(|e| format!("Docker build failed: {}", e))?;
+    }
 
+    // 3. Install via PluginManager
     lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginInstalling {
         message: "Installing MCP plugin...".into(),
+        progress: None,
     });
 
+    let vetted = match vet_image(&state, Some(&app_handle), &manifest).await {
+        Ok(vetted) => vetted,
+        Err(e) => {
+            lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginError {
+                plugin_id,
+                action: "installing".into(),
+                message: e.clone(),
+            });
+            return Err(e);
+        }
+    };
+
     let mut mgr = state.write().await;
-    match mgr.install(manifest, approved_permissions, deferred_permissions, None, None).await {
+    mgr.plugin_env
+        .set(&plugin_id, env)
+        .map_err(|e| format!("Failed to store plugin env: {}", e))?;
+    match mgr.install_vetted(manifest, vetted, approved_permissions, deferred_permissions, None, None).await {
         Ok(plugin) => {
             lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginInstalled {
                 plugin: plugin.clone(),
@@ -75,6 +165,9 @@ pub async fn mcp_generate_and_install(
             Ok(plugin)
         }
         Err(e) => {
+            if let Err(err) = mgr.plugin_env.remove(&plugin_id) {
+                log::warn!("Failed to clear env for {}: {}", plugin_id, err);
+            }
             lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginError {
                 plugin_id,
                 action: "installing".into(),
@@ -84,3 +177,117 @@ pub async fn mcp_generate_and_install(
         }
     }
 }
+
+/// Re-run tool discovery for a wrapped plugin against the latest upstream
+/// server, regenerate its artifacts, rebuild the image without cache, and
+/// apply the result through the regular plugin update path.
+///
+/// The stored tool subset is kept (minus tools that no longer exist
+/// upstream); a plugin that exposed everything picks up new tools.
+#[tauri::command]
+pub async fn mcp_rewrap(
+    state: tauri::State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+) -> Result<InstalledPlugin, String> {
+    let (runtime, data_dir, installed, mut env) = {
+        let mgr = state.read().await;
+        let plugin = mgr
+            .storage
+            .get(&plugin_id)
+            .ok_or_else(|| format!("Plugin not found: {}", plugin_id))?;
+        (
+            mgr.runtime.clone(),
+            mgr.data_dir.clone(),
+            plugin.manifest.clone(),
+            mgr.plugin_env.get(&plugin_id),
+        )
+    };
+    let plugin_dir = data_dir.join("mcp-plugins").join(&plugin_id);
+
+    // Plugins wrapped before the spec file existed: the manifest's command
+    // is the user's command for every runtime except Docker.
+    let spec = generate::load_wrap_spec(&plugin_dir)
+        .or_else(|| WrapSpec::legacy(installed.env.get("MCP_SERVER_COMMAND")?))
+        .ok_or_else(|| format!("{} is not a wrapped MCP plugin", plugin_id))?;
+
+    // 1. Re-discover against the latest upstream
+    let discover_command = spec.discovery_command().map_err(|e| e.to_string())?;
+    let found = discovery::discover_tools(&discover_command, &env)
+        .await
+        .map_err(|e| format!("Tool discovery failed: {}", e))?;
+    let tools = classify::classify_tools(&found.tools);
+    let spec = spec.retain_tools(&tools);
+
+    // 2. Regenerate with a patch bump so the update is visible
+    let server_command = resolve_server_command(runtime.as_ref(), &spec.command, &mut env).await?;
+    let version = rewrap_version(&installed.version);
+    let metadata = PluginMetadata {
+        id: installed.id.clone(),
+        name: installed.name.clone(),
+        description: installed.description.clone(),
+        author: installed.author.clone(),
+    };
+    let plugin_dir = generate::generate_plugin(
+        &tools,
+        &metadata,
+        &spec,
+        &server_command,
+        &version,
+        &data_dir.join("mcp-plugins"),
+    )
+    .map_err(|e| format!("Failed to generate plugin: {}", e))?;
+
+    let manifest_data = std::fs::read_to_string(plugin_dir.join("plugin.json"))
+        .map_err(|e| format!("Failed to read generated manifest: {}", e))?;
+    let manifest: PluginManifest = serde_json::from_str(&manifest_data)
+        .map_err(|e| format!("Invalid generated manifest: {}", e))?;
+
+    // 3. Rebuild without cache so unpinned packages resolve to their latest
+    if !manifest.image.is_empty() {
+        runtime
+            .rebuild_image(&plugin_dir, &manifest.image)
+            .await
+            .map_err(|e| format!("Docker build failed: {}", e))?;
+    }
+
+    // 4. Swap containers through the standard update path
+    let mut mgr = state.write().await;
+    match mgr.update_plugin(manifest, None, Some(&app_handle)).await {
+        Ok(result) => {
+            mgr.notify_tools_changed();
+            audit.record(AuditEntry {
+                actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "plugin.mcp_rewrap".into(),
+                subject: Some(plugin_id.clone()), result: AuditResult::Success,
+                details: Some(serde_json::json!({
+                    "version": result.manifest.version,
+                    "tools": tools.len(),
+                })),
+            });
+            if result.status == PluginStatus::Running {
+                lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginStarted {
+                    plugin: result.clone(),
+                });
+            } else {
+                lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginStopped {
+                    plugin: result.clone(),
+                });
+            }
+            Ok(result)
+        }
+        Err(e) => {
+            audit.record(AuditEntry {
+                actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "plugin.mcp_rewrap".into(),
+                subject: Some(plugin_id.clone()), result: AuditResult::Failure,
+                details: Some(serde_json::json!({"error": e.to_string()})),
+            });
+            lifecycle_events::emit(Some(&app_handle), LifecycleEvent::PluginError {
+                plugin_id,
+                action: "updating".into(),
+                message: e.to_string(),
+            });
+            Err(e.to_string())
+        }
+    }
+}
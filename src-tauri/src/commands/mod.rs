@@ -1,6 +1,7 @@
 pub mod api_keys;
 pub mod audit;
 pub mod app_updater;
+pub mod backup;
 pub mod events;
 pub mod extensions;
 pub mod marketplace;
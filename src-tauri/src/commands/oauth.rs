@@ -3,7 +3,12 @@ use std::sync::Arc;
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
 use crate::oauth::store::OAuthStore;
-use crate::oauth::types::OAuthClientInfo;
+use crate::oauth::types::{
+    AccessTokenFormat, DeviceAuthorizationInfo, OAuthClientGrants, OAuthClientInfo,
+};
+use crate::permissions::rar::{self, AuthorizationDetail};
+use crate::permissions::PermissionState;
+use crate::AppState;
 
 #[tauri::command]
 pub async fn oauth_list_clients(
@@ -26,3 +31,162 @@ pub async fn oauth_revoke_client(
     });
     Ok(())
 }
+
+/// Switch a client between opaque and JWT access tokens. Takes effect on
+/// the client's next token request.
+#[tauri::command]
+pub async fn oauth_set_token_format(
+    store: tauri::State<'_, Arc<OAuthStore>>,
+    audit: tauri::State<'_, AuditWriter>,
+    client_id: String,
+    format: AccessTokenFormat,
+) -> Result<(), String> {
+    if !store.set_access_token_format(&client_id, format) {
+        return Err(format!("Unknown client: {}", client_id));
+    }
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "security.oauth.token_format".into(),
+        subject: Some(client_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({ "format": format })),
+    });
+    Ok(())
+}
+
+/// Rotate the JWT signing key. Tokens signed with the old key stay valid
+/// until they expire. Returns the new key id.
+#[tauri::command]
+pub async fn oauth_rotate_jwt_key(
+    store: tauri::State<'_, Arc<OAuthStore>>,
+    audit: tauri::State<'_, AuditWriter>,
+) -> Result<String, String> {
+    let kid = store.rotate_jwt_key();
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "security.oauth.rotate_jwt_key".into(),
+        subject: Some(kid.clone()), result: AuditResult::Success,
+        details: None,
+    });
+    Ok(kid)
+}
+
+/// Look up a pending device authorization by the code the user typed.
+#[tauri::command]
+pub async fn oauth_device_lookup(
+    store: tauri::State<'_, Arc<OAuthStore>>,
+    user_code: String,
+) -> Result<DeviceAuthorizationInfo, String> {
+    store
+        .find_device_authorization(&user_code)
+        .ok_or_else(|| "Unknown or expired code".to_string())
+}
+
+/// Approve or deny a device authorization. The waiting client picks up the
+/// result on its next token poll.
+#[tauri::command]
+pub async fn oauth_device_resolve(
+    store: tauri::State<'_, Arc<OAuthStore>>,
+    audit: tauri::State<'_, AuditWriter>,
+    user_code: String,
+    approve: bool,
+) -> Result<(), String> {
+    let info = store
+        .resolve_device_authorization(&user_code, approve)
+        .map_err(|_| "Unknown or expired code".to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "security.oauth.device_resolve".into(),
+        subject: Some(info.client_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "client_name": info.client_name,
+            "approved": approve,
+        })),
+    });
+    Ok(())
+}
+
+/// Scopes and authorization details a client currently holds.
+#[tauri::command]
+pub async fn oauth_get_client_grants(
+    store: tauri::State<'_, Arc<OAuthStore>>,
+    client_id: String,
+) -> Result<OAuthClientGrants, String> {
+    store
+        .client_grants(&client_id)
+        .ok_or_else(|| format!("Unknown client: {}", client_id))
+}
+
+/// Narrow a client's scopes and authorization details without revoking it.
+/// For plugin clients, permission grants the new details no longer cover are
+/// revoked (or lose the dropped locations) so the PermissionStore fallback in
+/// the Host API can't grant them back.
+#[tauri::command]
+pub async fn oauth_restrict_client_grants(
+    state: tauri::State<'_, AppState>,
+    store: tauri::State<'_, Arc<OAuthStore>>,
+    audit: tauri::State<'_, AuditWriter>,
+    client_id: String,
+    scopes: Vec<String>,
+    authorization_details: Vec<AuthorizationDetail>,
+) -> Result<OAuthClientGrants, String> {
+    let before = store
+        .client_grants(&client_id)
+        .ok_or_else(|| format!("Unknown client: {}", client_id))?;
+    let mut grants = store
+        .restrict_client_grants(&client_id, &scopes, &authorization_details)
+        .map_err(|e| match e {
+            "invalid_scope" => "Scopes can only be removed, not added".to_string(),
+            "invalid_authorization_details" => {
+                "Authorization details can only be narrowed, not widened".to_string()
+            }
+            other => other.to_string(),
+        })?;
+
+    if let Some(ref plugin_id) = grants.plugin_id {
+        let mgr = state.read().await;
+        for grant in mgr.permissions.get_grants(plugin_id) {
+            if grant.effective_state() != PermissionState::Active {
+                continue;
+            }
+            let detail = rar::grant_detail(&grant);
+            if rar::details_within(std::slice::from_ref(&detail), &authorization_details) {
+                continue;
+            }
+            // Keep the grant if the user only dropped some of its locations
+            let kept_locations = authorization_details.iter().find_map(|d| {
+                let unlocated = AuthorizationDetail { locations: None, ..d.clone() };
+                let broad = AuthorizationDetail { locations: None, ..detail.clone() };
+                rar::detail_within(&broad, &unlocated).then(|| d.locations.clone()).flatten()
+            });
+            match (kept_locations, grant.approved_scopes) {
+                (Some(kept), Some(approved)) => {
+                    for scope in approved.iter().filter(|s| !kept.contains(s)) {
+                        mgr.permissions
+                            .remove_approved_scope(plugin_id, &grant.permission, scope)
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+                _ => mgr
+                    .permissions
+                    .revoke(plugin_id, &grant.permission)
+                    .map_err(|e| e.to_string())?,
+            }
+        }
+
+        // Re-issue plugin tokens from the narrowed grants, as permission_revoke does
+        store.revoke_plugin_tokens(&client_id);
+        let details = rar::build_authorization_details(&mgr.permissions.get_grants(plugin_id));
+        store.set_plugin_auth_details(&client_id, details.clone());
+        grants.authorization_details = details;
+        mgr.notify_tools_changed();
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "security.oauth.restrict_client".into(),
+        subject: Some(client_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "scopes_before": before.scopes,
+            "scopes_after": grants.scopes,
+            "authorization_details_before": before.authorization_details,
+            "authorization_details_after": grants.authorization_details,
+        })),
+    });
+    Ok(grants)
+}
@@ -3,7 +3,11 @@ use std::sync::Arc;
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
 use crate::host_api::approval::{ApprovalBridge, ApprovalDecision};
-use crate::permissions::{GrantedPermission, Permission};
+use crate::permissions::checker::{self, Simulation};
+use crate::permissions::{
+    cmd_scope, fs_scope, DenyRule, GrantedPermission, Permission, PermissionChange, PermissionExport, PermissionProfile,
+    RateCap, PERMISSION_EXPORT_VERSION,
+};
 use crate::AppState;
 
 #[tauri::command]
@@ -12,12 +16,25 @@ pub async fn permission_grant(
     audit: tauri::State<'_, AuditWriter>,
     plugin_id: String,
     permissions: Vec<Permission>,
+    duration_secs: Option<u64>,
 ) -> Result<(), String> {
+    let expires_at = match duration_secs {
+        Some(0) => return Err("Grant duration must be at least one second".into()),
+        Some(secs) => Some(
+            i64::try_from(secs)
+                .ok()
+                .and_then(chrono::Duration::try_seconds)
+                .and_then(|d| chrono::Utc::now().checked_add_signed(d))
+                .ok_or("Grant duration is too long")?,
+        ),
+        None => None,
+    };
+
     let mgr = state.read().await;
     let perm_strs: Vec<String> = permissions.iter().map(|p| p.as_str().to_string()).collect();
     for perm in permissions {
         mgr.permissions
-            .grant(&plugin_id, perm, None)
+            .grant_until(&plugin_id, perm, None, expires_at)
             .map_err(|e| e.to_string())?;
     }
 
@@ -32,7 +49,7 @@ pub async fn permission_grant(
     audit.record(AuditEntry {
         actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "permission.grant".into(),
         subject: Some(plugin_id), result: AuditResult::Success,
-        details: Some(serde_json::json!({"permissions": perm_strs})),
+        details: Some(serde_json::json!({"permissions": perm_strs, "expires_at": expires_at})),
     });
     Ok(())
 }
@@ -138,11 +155,333 @@ pub async fn permission_remove_scope(
         .map_err(|e| e.to_string())
 }
 
+/// Turn "ask every time" on or off for a granted permission. Revokes the
+/// plugin's tokens, since always-prompt grants are left out of them.
+#[tauri::command]
+pub async fn permission_set_always_prompt(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    permission: Permission,
+    always_prompt: bool,
+) -> Result<(), String> {
+    let perm_str = permission.as_str().to_string();
+    let mgr = state.read().await;
+    mgr.permissions
+        .set_always_prompt(&plugin_id, &permission, always_prompt)
+        .map_err(|e| e.to_string())?;
+
+    if let Some(client) = mgr.oauth_store.get_client_by_plugin_id(&plugin_id) {
+        mgr.oauth_store.revoke_plugin_tokens(&client.client_id);
+        let grants = mgr.permissions.get_grants(&plugin_id);
+        let details = crate::permissions::rar::build_authorization_details(&grants);
+        mgr.oauth_store.set_plugin_auth_details(&client.client_id, details);
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "permission.always_prompt".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"permission": perm_str, "always_prompt": always_prompt})),
+    });
+    Ok(())
+}
+
+/// Set or clear a usage cap on a granted permission. Going over the cap asks
+/// the user again. Revokes the plugin's tokens, since capped grants are left
+/// out of them.
+#[tauri::command]
+pub async fn permission_set_rate_cap(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    permission: Permission,
+    rate_cap: Option<RateCap>,
+) -> Result<(), String> {
+    let perm_str = permission.as_str().to_string();
+    let mgr = state.read().await;
+    mgr.permissions
+        .set_rate_cap(&plugin_id, &permission, rate_cap)
+        .map_err(|e| e.to_string())?;
+
+    if let Some(client) = mgr.oauth_store.get_client_by_plugin_id(&plugin_id) {
+        mgr.oauth_store.revoke_plugin_tokens(&client.client_id);
+        let grants = mgr.permissions.get_grants(&plugin_id);
+        let details = crate::permissions::rar::build_authorization_details(&grants);
+        mgr.oauth_store.set_plugin_auth_details(&client.client_id, details);
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "permission.rate_cap".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"permission": perm_str, "rate_cap": rate_cap})),
+    });
+    Ok(())
+}
+
+/// Every recorded grant, revoke, defer, and scope change for a plugin, oldest first.
+#[tauri::command]
+pub async fn permission_history(
+    state: tauri::State<'_, AppState>,
+    plugin_id: String,
+) -> Result<Vec<PermissionChange>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.permissions.history(&plugin_id))
+}
+
+/// Dry-run a permission check: what a plugin would get for one use of
+/// `permission` with the given scope. Nothing is prompted, persisted, or counted.
+#[tauri::command]
+pub async fn permission_simulate(
+    state: tauri::State<'_, AppState>,
+    plugin_id: String,
+    permission: Permission,
+    scope: Option<String>,
+) -> Result<Simulation, String> {
+    let mgr = state.read().await;
+    Ok(checker::simulate(&*mgr.permissions, &plugin_id, &permission, scope.as_deref()))
+}
+
+#[tauri::command]
+pub async fn permission_deny_list(
+    state: tauri::State<'_, AppState>,
+    plugin_id: String,
+) -> Result<Vec<DenyRule>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.permissions.list_denies(&plugin_id))
+}
+
+/// Add a "never allow" rule. Matching requests are refused without a prompt.
+/// Without a scope the whole permission is denied and its grant revoked.
+#[tauri::command]
+pub async fn permission_deny_add(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    permission: Permission,
+    scope: Option<String>,
+) -> Result<(), String> {
+    let perm_str = permission.as_str().to_string();
+    let mgr = state.read().await;
+    mgr.permissions
+        .add_deny(&plugin_id, permission, scope.clone())
+        .map_err(|e| e.to_string())?;
+
+    if scope.is_none() {
+        if let Some(client) = mgr.oauth_store.get_client_by_plugin_id(&plugin_id) {
+            mgr.oauth_store.revoke_plugin_tokens(&client.client_id);
+            let grants = mgr.permissions.get_grants(&plugin_id);
+            let details = crate::permissions::rar::build_authorization_details(&grants);
+            mgr.oauth_store.set_plugin_auth_details(&client.client_id, details);
+        }
+        mgr.notify_tools_changed();
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "permission.deny".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"permission": perm_str, "scope": scope})),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn permission_deny_remove(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    permission: Permission,
+    scope: Option<String>,
+) -> Result<(), String> {
+    let mgr = state.read().await;
+    mgr.permissions
+        .remove_deny(&plugin_id, &permission, scope.as_deref())
+        .map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "permission.undeny".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"permission": permission.as_str(), "scope": scope})),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn permission_profile_list(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<PermissionProfile>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.permissions.list_profiles())
+}
+
+/// Create a permission profile, or replace the one with the same name.
+#[tauri::command]
+pub async fn permission_profile_save(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    profile: PermissionProfile,
+) -> Result<(), String> {
+    let name = profile.name.trim().to_string();
+    let perm_strs: Vec<String> = profile.permissions.iter().map(|p| p.as_str().to_string()).collect();
+    let mgr = state.read().await;
+    mgr.permissions
+        .save_profile(profile)
+        .map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "permission.profile.save".into(),
+        subject: Some(name), result: AuditResult::Success,
+        details: Some(serde_json::json!({"permissions": perm_strs})),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn permission_profile_delete(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    name: String,
+) -> Result<(), String> {
+    let mgr = state.read().await;
+    mgr.permissions
+        .delete_profile(&name)
+        .map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "permission.profile.delete".into(),
+        subject: Some(name), result: AuditResult::Success,
+        details: None,
+    });
+    Ok(())
+}
+
+/// Apply a profile to an installed plugin. Returns the grants whose state changed.
+#[tauri::command]
+pub async fn permission_profile_apply(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    name: String,
+) -> Result<Vec<GrantedPermission>, String> {
+    let mgr = state.read().await;
+    let changed = mgr
+        .permissions
+        .apply_profile(&plugin_id, &name)
+        .map_err(|e| e.to_string())?;
+
+    // Deferred grants may have been active before, so drop tokens that still carry them
+    if let Some(client) = mgr.oauth_store.get_client_by_plugin_id(&plugin_id) {
+        mgr.oauth_store.revoke_plugin_tokens(&client.client_id);
+        let grants = mgr.permissions.get_grants(&plugin_id);
+        let details = crate::permissions::rar::build_authorization_details(&grants);
+        mgr.oauth_store.set_plugin_auth_details(&client.client_id, details);
+    }
+
+    mgr.notify_tools_changed();
+    let changes: Vec<serde_json::Value> = changed
+        .iter()
+        .map(|g| serde_json::json!({"permission": g.permission.as_str(), "state": g.state}))
+        .collect();
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "permission.profile.apply".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"profile": name, "changes": changes})),
+    });
+    Ok(changed)
+}
+
+/// Outcome of `permission_import`.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct PermissionImportReport {
+    /// Plugins whose grants were replaced.
+    pub imported: Vec<String>,
+    /// Plugins in the file that aren't installed here.
+    pub skipped_plugins: Vec<String>,
+    /// Entries dropped during validation, prefixed with the plugin ID.
+    pub dropped: Vec<String>,
+}
+
+/// Export every plugin's grants, approved scopes, and deny rules as pretty-printed JSON.
+#[tauri::command]
+pub async fn permission_export(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+) -> Result<String, String> {
+    let mgr = state.read().await;
+    let export = mgr.permissions.export();
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "permission.export".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"plugins": export.plugins.len()})),
+    });
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Import a file written by `permission_export`. Each installed plugin in the
+/// file has its grants and deny rules replaced; plugins that aren't installed
+/// are skipped, and entries the plugin's manifest doesn't request or whose
+/// scopes are malformed are dropped.
+#[tauri::command]
+pub async fn permission_import(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    data: String,
+) -> Result<PermissionImportReport, String> {
+    let export: PermissionExport =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid permission file: {}", e))?;
+    if export.version > PERMISSION_EXPORT_VERSION {
+        return Err(format!(
+            "Permission file version {} is newer than supported version {}",
+            export.version, PERMISSION_EXPORT_VERSION
+        ));
+    }
+
+    let mgr = state.read().await;
+    let mut report = PermissionImportReport::default();
+    for (plugin_id, mut entry) in export.plugins {
+        let Some(plugin) = mgr.storage.get(&plugin_id) else {
+            report.skipped_plugins.push(plugin_id);
+            continue;
+        };
+        let declared = plugin.manifest.all_permissions();
+        for problem in entry.retain_valid(&declared) {
+            report.dropped.push(format!("{}: {}", plugin_id, problem));
+        }
+        mgr.permissions
+            .import_plugin(&plugin_id, entry)
+            .map_err(|e| e.to_string())?;
+
+        // Imported grants may be narrower than the old ones
+        if let Some(client) = mgr.oauth_store.get_client_by_plugin_id(&plugin_id) {
+            mgr.oauth_store.revoke_plugin_tokens(&client.client_id);
+            let grants = mgr.permissions.get_grants(&plugin_id);
+            let details = crate::permissions::rar::build_authorization_details(&grants);
+            mgr.oauth_store.set_plugin_auth_details(&client.client_id, details);
+        }
+        report.imported.push(plugin_id);
+    }
+
+    mgr.notify_tools_changed();
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Critical, action: "permission.import".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "imported": report.imported,
+            "skipped_plugins": report.skipped_plugins,
+            "dropped": report.dropped,
+        })),
+    });
+    Ok(report)
+}
+
 /// Called by the frontend approval dialog when the user makes a decision.
 ///
 /// For `Approve` (persist): writes the approved scope to `PermissionService`
 /// BEFORE sending the decision on the channel, guaranteeing the scope is
 /// persisted by the time the HTTP handler resumes.
+///
+/// Filesystem approvals persist `approved_scope` from the context when the
+/// user widened it (an ancestor directory or a glob), else the parent directory.
+/// A widened scope must still cover the requested path.
+///
+/// Requests for always-prompt grants (marked by the bridge) never persist:
+/// `Approve` is handled as `ApproveOnce`.
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
 pub async fn runtime_approval_respond(
@@ -155,6 +494,14 @@ pub async fn runtime_approval_respond(
     category: String,
     context: std::collections::HashMap<String, String>,
 ) -> Result<(), String> {
+    let decision = if decision == ApprovalDecision::Approve
+        && context.get("always_prompt").is_some_and(|v| v == "true")
+    {
+        ApprovalDecision::ApproveOnce
+    } else {
+        decision
+    };
+
     if decision == ApprovalDecision::Approve {
         if category == "deferred_permission" {
             // Deferred → Active: persist the state transition before signaling the channel
@@ -168,16 +515,61 @@ pub async fn runtime_approval_respond(
                 .activate(&plugin_id, &permission)
                 .map_err(|e| e.to_string())?;
         } else if category == "filesystem" {
-            // Filesystem scope: persist the parent directory
-            if let Some(parent_dir) = context.get("parent_dir") {
+            // Filesystem scope: persist the chosen scope (parent directory by default)
+            if let Some(scope) = context.get("approved_scope").or_else(|| context.get("parent_dir")) {
                 let permission_str = context.get("permission").cloned().unwrap_or_default();
                 let permission: Permission =
                     serde_json::from_value(serde_json::Value::String(permission_str))
                         .map_err(|e| format!("invalid permission: {}", e))?;
 
+                let checked = fs_scope::validate(scope).and_then(|_| match context.get("path") {
+                    Some(path) if !fs_scope::covers(scope, std::path::Path::new(path)) => {
+                        Err(format!("scope {} does not cover {}", scope, path))
+                    }
+                    _ => Ok(()),
+                });
+                if let Err(e) = checked {
+                    // Don't leave the HTTP handler waiting for the timeout
+                    bridge.respond(&request_id, ApprovalDecision::Deny);
+                    return Err(e);
+                }
+
+                let mgr = state.read().await;
+                mgr.permissions
+                    .add_approved_scope(&plugin_id, &permission, scope.clone())
+                    .map_err(|e| e.to_string())?;
+            }
+        } else if category == "network" {
+            // Network egress: persist the host under its host-pattern grant
+            if let Some(host) = context.get("host") {
+                let permission_str = context.get("permission").cloned().unwrap_or_default();
+                let permission: Permission =
+                    serde_json::from_value(serde_json::Value::String(permission_str))
+                        .map_err(|e| format!("invalid permission: {}", e))?;
+
+                let mgr = state.read().await;
+                mgr.permissions
+                    .add_approved_scope(&plugin_id, &permission, host.clone())
+                    .map_err(|e| e.to_string())?;
+            }
+        } else if category == "process_exec" {
+            // Command execution: persist the chosen command pattern. Without
+            // one, "Allow" only lets this call through.
+            if let Some(pattern) = context.get("approved_scope") {
+                let checked = cmd_scope::validate(pattern).and_then(|_| match context.get("command_line") {
+                    Some(line) if !cmd_scope::covers_line(pattern, line) => {
+                        Err(format!("pattern {} does not cover {}", pattern, line))
+                    }
+                    _ => Ok(()),
+                });
+                if let Err(e) = checked {
+                    bridge.respond(&request_id, ApprovalDecision::Deny);
+                    return Err(e);
+                }
+
                 let mgr = state.read().await;
                 mgr.permissions
-                    .add_approved_scope(&plugin_id, &permission, parent_dir.clone())
+                    .add_approved_scope(&plugin_id, &Permission::ProcessExec, pattern.clone())
                     .map_err(|e| e.to_string())?;
             }
         } else if category.starts_with("extension_scope:") {
@@ -1,17 +1,23 @@
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::event_bus::subscription::SubscriberKind;
 use crate::lifecycle_events::{self, LifecycleEvent};
 use crate::permissions::Permission;
 use crate::plugin_manager::dev_watcher::DevWatcher;
 use crate::plugin_manager::health;
-use crate::plugin_manager::manifest::PluginManifest;
+use crate::plugin_manager::manifest::{PluginManifest, PluginSecurity};
+use crate::plugin_manager::non_root::RootUserPolicy;
 use crate::plugin_manager::registry;
+use crate::plugin_manager::PluginManager;
 use crate::plugin_manager::storage::InstalledPlugin;
+use crate::plugin_manager::vetting::VettedImage;
 use crate::runtime::docker as docker_utils;
+use crate::runtime::ContainerRuntime;
 use crate::AppState;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use tauri::Manager;
 
 #[tauri::command]
 pub async fn plugin_list(state: tauri::State<'_, AppState>) -> Result<Vec<InstalledPlugin>, String> {
@@ -20,18 +26,33 @@ pub async fn plugin_list(state: tauri::State<'_, AppState>) -> Result<Vec<Instal
 }
 
 /// Preview a manifest from a remote URL without installing.
-/// Returns the manifest so the frontend can show the permission dialog.
+/// Returns the manifest so the frontend can show the permission dialog,
+/// with whether the listing registry verified its publisher.
 #[tauri::command]
 pub async fn plugin_preview_remote(
+    state: tauri::State<'_, AppState>,
     manifest_url: String,
-) -> Result<PluginManifest, String> {
-    let manifest = registry::fetch_manifest(&manifest_url)
+) -> Result<registry::ManifestPreview<PluginManifest>, String> {
+    let (signature, auth, verified_identity) = {
+        let mgr = state.read().await;
+        let signature = mgr.manifest_signature(&manifest_url).map_err(|e| e.to_string())?;
+        (
+            signature,
+            mgr.manifest_auth(&manifest_url),
+            mgr.verified_publisher(&manifest_url),
+        )
+    };
+    let manifest = registry::fetch_verified_manifest(&manifest_url, signature.as_ref(), auth.as_ref())
         .await
         .map_err(|e| e.to_string())?;
     manifest
         .validate()
         .map_err(|e| format!("Invalid manifest: {}", e))?;
-    Ok(manifest)
+    Ok(registry::ManifestPreview {
+        manifest,
+        verified: verified_identity.is_some(),
+        verified_identity,
+    })
 }
 
 /// Preview a manifest from a local path without installing.
@@ -50,6 +71,30 @@ pub async fn plugin_preview_local(
     Ok(manifest)
 }
 
+/// Tell the user about critical vulnerabilities or a root user that an
+/// install or update let through under the warn policies.
+pub(crate) fn notify_install_warnings(app: &tauri::AppHandle, mgr: &PluginManager, plugin: &InstalledPlugin) {
+    if let Some(scan) = mgr.image_scans.get(&plugin.manifest.id) {
+        crate::notification::notify(
+            app,
+            "plugin.vulnerabilities",
+            format!("{} has critical vulnerabilities", plugin.manifest.name),
+            scan.summary(),
+        );
+    }
+    if mgr.root_plugins.contains(&plugin.manifest.id) && mgr.settings.root_user != RootUserPolicy::Allow {
+        crate::notification::notify(
+            app,
+            "plugin.root_user",
+            format!("{} runs as root", plugin.manifest.name),
+            format!(
+                "{} doesn't declare a non-root user. A compromised plugin has more to work with as root.",
+                plugin.manifest.image
+            ),
+        );
+    }
+}
+
 #[tauri::command]
 pub async fn plugin_install(
     state: tauri::State<'_, AppState>,
@@ -60,7 +105,8 @@ pub async fn plugin_install(
     deferred_permissions: Option<Vec<Permission>>,
     build_context: Option<String>,
 ) -> Result<InstalledPlugin, String> {
-    let manifest = registry::fetch_manifest(&manifest_url)
+    state.read().await.check_writable().map_err(|e| e.to_string())?;
+    let manifest = PluginManager::fetch_registry_manifest(&state, &manifest_url, false)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -69,30 +115,33 @@ pub async fn plugin_install(
 
     lifecycle_events::emit(Some(&app), LifecycleEvent::PluginInstalling {
         message: "Installing plugin...".into(),
+        progress: None,
     });
 
     // Everything after PluginInstalling must emit PluginError on failure
     let result = async {
-        if let Some(ref ctx) = build_context {
-            let ctx_path = Path::new(ctx);
-            if ctx_path.join("Dockerfile").exists() {
-                log::info!(
-                    "Building image {} from {}",
-                    manifest.image,
-                    ctx_path.display()
-                );
-                let runtime = { state.read().await.runtime.clone() };
-                runtime
-                    .build_image(ctx_path, &manifest.image)
-                    .await
-                    .map_err(|e| format!("Docker build failed: {}", e))?;
-            }
-        }
+        manifest
+            .validate()
+            .map_err(|e| format!("Invalid manifest: {}", e))?;
+        let build_dir = build_context
+            .as_deref()
+            .map(Path::new)
+            .filter(|ctx| ctx.join("Dockerfile").exists());
+        let (locks, runtime) = {
+            let mgr = state.read().await;
+            (mgr.plugin_locks.clone(), mgr.runtime.clone())
+        };
+        let _plugin_lock = locks.lock(&plugin_id).await;
+        prepare_image(Some(&app), runtime.as_ref(), &manifest, build_dir).await?;
+        let vetted = vet_image(&state, Some(&app), &manifest).await?;
 
         let mut mgr = state.write().await;
-        mgr.install(manifest, approved_permissions, deferred_permissions.unwrap_or_default(), Some(&manifest_url), None)
+        let plugin = mgr
+            .install_vetted(manifest, vetted, approved_permissions, deferred_permissions.unwrap_or_default(), Some(&manifest_url), None)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())?;
+        notify_install_warnings(&app, &mgr, &plugin);
+        Ok::<_, String>(plugin)
     }.await;
 
     match result {
@@ -136,6 +185,8 @@ pub async fn plugin_install_local(
     approved_permissions: Vec<Permission>,
     deferred_permissions: Option<Vec<Permission>>,
 ) -> Result<InstalledPlugin, String> {
+    state.read().await.check_writable().map_err(|e| e.to_string())?;
+
     // Parse manifest before emitting PluginInstalling so we have the plugin_id for errors
     let data = std::fs::read_to_string(&manifest_path)
         .map_err(|e| format!("Failed to read manifest: {}", e))?;
@@ -149,6 +200,7 @@ pub async fn plugin_install_local(
 
     lifecycle_events::emit(Some(&app), LifecycleEvent::PluginInstalling {
         message: "Installing plugin from local path...".into(),
+        progress: None,
     });
 
     // Everything after PluginInstalling must emit PluginError on failure
@@ -156,24 +208,22 @@ pub async fn plugin_install_local(
         let manifest_dir = Path::new(&manifest_path)
             .parent()
             .ok_or_else(|| "Invalid manifest path".to_string())?;
-        let dockerfile = manifest_dir.join("Dockerfile");
-        if dockerfile.exists() {
-            log::info!(
-                "Building image {} from {}",
-                manifest.image,
-                manifest_dir.display()
-            );
-            let runtime = { state.read().await.runtime.clone() };
-            runtime
-                .build_image(manifest_dir, &manifest.image)
-                .await
-                .map_err(|e| format!("Docker build failed: {}", e))?;
-        }
+        let build_dir = Some(manifest_dir).filter(|dir| dir.join("Dockerfile").exists());
+        let (locks, runtime) = {
+            let mgr = state.read().await;
+            (mgr.plugin_locks.clone(), mgr.runtime.clone())
+        };
+        let _plugin_lock = locks.lock(&plugin_id).await;
+        prepare_image(Some(&app), runtime.as_ref(), &manifest, build_dir).await?;
+        let vetted = vet_image(&state, Some(&app), &manifest).await?;
 
         let mut mgr = state.write().await;
-        mgr.install(manifest, approved_permissions, deferred_permissions.unwrap_or_default(), None, Some(manifest_path))
+        let plugin = mgr
+            .install_vetted(manifest, vetted, approved_permissions, deferred_permissions.unwrap_or_default(), None, Some(manifest_path))
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())?;
+        notify_install_warnings(&app, &mgr, &plugin);
+        Ok::<_, String>(plugin)
     }.await;
 
     match &result {
@@ -211,6 +261,63 @@ pub async fn plugin_install_local(
     }
 }
 
+/// Check the pulled image without holding the manager: a first scan can
+/// take minutes.
+pub(crate) async fn vet_image(
+    state: &AppState,
+    app: Option<&tauri::AppHandle>,
+    manifest: &PluginManifest,
+) -> Result<VettedImage, String> {
+    if manifest.remote_mcp_url().is_none() {
+        lifecycle_events::emit(app, LifecycleEvent::PluginInstalling {
+            message: "Checking image...".into(),
+            progress: None,
+        });
+    }
+    let vetter = state.read().await.image_vetter(&manifest.id);
+    vetter.vet(manifest).await.map_err(|e| e.to_string())
+}
+
+/// Build the plugin's image from `build_dir`, or pull it if it isn't present,
+/// reporting progress through `plugin:installing` events when given `app`.
+pub(crate) async fn prepare_image(
+    app: Option<&tauri::AppHandle>,
+    runtime: &dyn ContainerRuntime,
+    manifest: &PluginManifest,
+    build_dir: Option<&Path>,
+) -> Result<(), String> {
+    if let Some(dir) = build_dir {
+        log::info!("Building image {} from {}", manifest.image, dir.display());
+        let on_progress = |p| {
+            lifecycle_events::emit(app, LifecycleEvent::PluginInstalling {
+                message: "Building image...".into(),
+                progress: Some(p),
+            });
+        };
+        return runtime
+            .build_image_with_progress(dir, &manifest.image, &on_progress)
+            .await
+            .map_err(|e| format!("Docker build failed: {}", e));
+    }
+
+    if manifest.remote_mcp_url().is_some()
+        || runtime.image_exists(&manifest.image).await.unwrap_or(false)
+    {
+        return Ok(());
+    }
+    log::info!("Pulling image: {}", manifest.image);
+    let on_progress = |p| {
+        lifecycle_events::emit(app, LifecycleEvent::PluginInstalling {
+            message: "Pulling image...".into(),
+            progress: Some(p),
+        });
+    };
+    runtime
+        .pull_image_with_progress(&manifest.image, &on_progress)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn plugin_start(
     state: tauri::State<'_, AppState>,
@@ -314,6 +421,11 @@ pub async fn plugin_remove(
     match mgr.remove(&plugin_id).await {
         Ok(()) => {
             mgr.notify_tools_changed();
+            if let Some(dispatch) = app.try_state::<crate::event_bus::Dispatch>() {
+                dispatch.bus.write().await.forget_subscriber(&SubscriberKind::Plugin {
+                    plugin_id: plugin_id.clone(),
+                });
+            }
             lifecycle_events::emit(Some(&app), LifecycleEvent::PluginRemoved {
                 plugin_id: plugin_id.clone(),
             });
@@ -497,6 +609,67 @@ pub async fn plugin_dev_mode_toggle(
     Ok(())
 }
 
+/// Choose whether a plugin is offered beta releases. Takes effect on the
+/// next update check.
+#[tauri::command]
+pub async fn plugin_set_channel(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    channel: registry::ReleaseChannel,
+) -> Result<(), String> {
+    {
+        let mut mgr = state.write().await;
+        let plugin = mgr
+            .storage
+            .get_mut(&plugin_id)
+            .ok_or_else(|| format!("Plugin '{}' not found", plugin_id))?;
+        plugin.channel = channel;
+        mgr.storage.save().map_err(|e| e.to_string())?;
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "plugin.channel".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"channel": channel})),
+    });
+    Ok(())
+}
+
+/// Set the seccomp and AppArmor profiles, root filesystem access, and user
+/// used in place of the plugin's declared ones. Takes effect the next time the
+/// plugin starts.
+#[tauri::command]
+pub async fn plugin_set_security_override(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    plugin_id: String,
+    security: PluginSecurity,
+) -> Result<(), String> {
+    security.validate(true)?;
+    {
+        let mut mgr = state.write().await;
+        let plugin = mgr
+            .storage
+            .get_mut(&plugin_id)
+            .ok_or_else(|| format!("Plugin '{}' not found", plugin_id))?;
+        plugin.security_override = security.clone();
+        mgr.storage.save().map_err(|e| e.to_string())?;
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "plugin.security_override".into(),
+        subject: Some(plugin_id), result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "seccomp_profile": security.seccomp_profile.is_some(),
+            "apparmor_profile": security.apparmor_profile,
+            "writable_rootfs": security.writable_rootfs,
+            "user": security.user,
+        })),
+    });
+    Ok(())
+}
+
 /// Manually trigger a rebuild for a dev-mode plugin.
 #[tauri::command]
 pub async fn plugin_rebuild(
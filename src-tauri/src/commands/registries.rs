@@ -1,12 +1,18 @@
+use std::collections::HashMap;
+
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
-use crate::plugin_manager::registry::{RegistryKind, RegistrySource, RegistryTrust};
+use crate::plugin_manager::bundle::{self, BundleImportReport, RegistryBundle};
+use crate::plugin_manager::oci::OciReference;
+use crate::plugin_manager::registry::{
+    self, ConflictPolicy, RegistryAuth, RegistryKind, RegistrySource, RegistrySourceView, RegistryTrust,
+};
 use crate::AppState;
 
 #[tauri::command]
-pub async fn registry_list(state: tauri::State<'_, AppState>) -> Result<Vec<RegistrySource>, String> {
+pub async fn registry_list(state: tauri::State<'_, AppState>) -> Result<Vec<RegistrySourceView>, String> {
     let mgr = state.read().await;
-    Ok(mgr.registry_store.list().to_vec())
+    Ok(mgr.registry_store.views())
 }
 
 #[tauri::command]
@@ -16,26 +22,28 @@ pub async fn registry_add(
     name: String,
     kind: String,
     url: String,
+    auth: Option<RegistryAuth>,
 ) -> Result<RegistrySource, String> {
     let mut mgr = state.write().await;
 
     let registry_kind = match kind.as_str() {
         "remote" => RegistryKind::Remote,
         "local" => RegistryKind::Local,
-        _ => return Err(format!("Invalid registry kind: {}. Use 'remote' or 'local'", kind)),
+        "oci" => RegistryKind::Oci,
+        _ => return Err(format!("Invalid registry kind: {}. Use 'remote', 'local', or 'oci'", kind)),
+    };
+    let url = if registry_kind == RegistryKind::Oci {
+        let reference = OciReference::parse(&url).map_err(|e| e.to_string())?;
+        if reference.tag.is_some() || reference.digest.is_some() {
+            return Err("An OCI registry is a repository, without a tag or digest".to_string());
+        }
+        reference.repository_url()
+    } else {
+        url
     };
-
-    // Generate a slug-style ID from the name
-    let id = name
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .trim_matches('-')
-        .to_string();
 
     let source = RegistrySource {
-        id,
+        id: registry::source_id(&name),
         name,
         kind: registry_kind,
         url,
@@ -44,6 +52,19 @@ pub async fn registry_add(
     };
 
     mgr.registry_store.add(source.clone()).map_err(|e| e.to_string())?;
+    if let Some(auth) = auth {
+        let auth_kind = auth.kind();
+        if let Err(e) = mgr.registry_store.set_auth(&source.id, Some(auth)) {
+            // Don't leave behind a private registry that can't be read
+            let _ = mgr.registry_store.remove(&source.id);
+            return Err(e.to_string());
+        }
+        audit.record(AuditEntry {
+            actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.auth".into(),
+            subject: Some(source.id.clone()), result: AuditResult::Success,
+            details: Some(serde_json::json!({"auth": auth_kind})),
+        });
+    }
     audit.record(AuditEntry {
         actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.add".into(),
         subject: Some(source.id.clone()), result: AuditResult::Success,
@@ -52,6 +73,25 @@ pub async fn registry_add(
     Ok(source)
 }
 
+/// Set or clear the credentials sent to a private registry.
+#[tauri::command]
+pub async fn registry_set_auth(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    id: String,
+    auth: Option<RegistryAuth>,
+) -> Result<(), String> {
+    let auth_kind = auth.as_ref().map(RegistryAuth::kind);
+    let mut mgr = state.write().await;
+    mgr.registry_store.set_auth(&id, auth).map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.auth".into(),
+        subject: Some(id), result: AuditResult::Success,
+        details: Some(serde_json::json!({"auth": auth_kind})),
+    });
+    Ok(())
+}
+
 /// Built-in registries that cannot be removed by the user.
 const PROTECTED_REGISTRIES: &[&str] = &["nexus-community", "nexus-mcp-local"];
 
@@ -66,6 +106,7 @@ pub async fn registry_remove(
     }
     let mut mgr = state.write().await;
     mgr.registry_store.remove(&id).map_err(|e| e.to_string())?;
+    bundle::remove_files(&mgr.data_dir, &id);
     audit.record(AuditEntry {
         actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.remove".into(),
         subject: Some(id), result: AuditResult::Success,
@@ -90,3 +131,125 @@ pub async fn registry_toggle(
     });
     Ok(())
 }
+
+/// Set the registry priority order, highest first.
+#[tauri::command]
+pub async fn registry_reorder(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    ids: Vec<String>,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    mgr.registry_store.reorder(&ids).map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.reorder".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"order": ids})),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn registry_get_conflict_policy(
+    state: tauri::State<'_, AppState>,
+) -> Result<ConflictPolicy, String> {
+    let mgr = state.read().await;
+    Ok(mgr.registry_store.conflict_policy())
+}
+
+/// Choose what the marketplace shows when registries list the same plugin ID.
+#[tauri::command]
+pub async fn registry_set_conflict_policy(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    policy: ConflictPolicy,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    mgr.registry_store.set_conflict_policy(policy).map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.conflict_policy".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"policy": policy})),
+    });
+    Ok(())
+}
+
+/// Outcome of `registry_bundle_export`.
+#[derive(Debug, serde::Serialize)]
+pub struct RegistryBundleExport {
+    /// The bundle file, as JSON.
+    pub data: String,
+    pub plugins: usize,
+    pub extensions: usize,
+    /// Manifest URLs that couldn't be fetched; their entries are left out.
+    pub failed: Vec<String>,
+}
+
+/// Export the cached registry entries and the manifests they point at as one
+/// file, for importing on a machine without internet access.
+#[tauri::command]
+pub async fn registry_bundle_export(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+) -> Result<RegistryBundleExport, String> {
+    // Manifests are fetched without holding the lock
+    let (plugins, extensions, auth) = {
+        let mgr = state.read().await;
+        let auth: HashMap<String, RegistryAuth> = mgr
+            .registry_cache
+            .iter()
+            .map(|e| &e.manifest_url)
+            .chain(mgr.extension_registry_cache.iter().map(|e| &e.manifest_url))
+            .filter_map(|url| mgr.manifest_auth(url).map(|auth| (url.clone(), auth)))
+            .collect();
+        (
+            mgr.registry_cache.clone(),
+            mgr.extension_registry_cache.clone(),
+            auth,
+        )
+    };
+    let (bundle, failed) = bundle::export(plugins, extensions, &auth).await;
+    let export = RegistryBundleExport {
+        plugins: bundle.plugins.len(),
+        extensions: bundle.extensions.len(),
+        data: serde_json::to_string(&bundle).map_err(|e| e.to_string())?,
+        failed,
+    };
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.bundle_export".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "plugins": export.plugins,
+            "extensions": export.extensions,
+            "failed": export.failed,
+        })),
+    });
+    Ok(export)
+}
+
+/// Import a file written by `registry_bundle_export` as a local registry
+/// named `name`. Importing again under the same name replaces it.
+#[tauri::command]
+pub async fn registry_bundle_import(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    data: String,
+    name: String,
+) -> Result<BundleImportReport, String> {
+    let bundle: RegistryBundle =
+        serde_json::from_str(&data).map_err(|e| format!("Invalid registry bundle: {}", e))?;
+    let mut mgr = state.write().await;
+    let data_dir = mgr.data_dir.clone();
+    let report = bundle::import(bundle, &name, &data_dir, &mut mgr.registry_store)
+        .map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.registry.bundle_import".into(),
+        subject: Some(report.registry_id.clone()), result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "plugins": report.plugins,
+            "extensions": report.extensions,
+            "skipped": report.skipped,
+        })),
+    });
+    Ok(report)
+}
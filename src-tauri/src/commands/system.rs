@@ -1,9 +1,23 @@
+use crate::audit::store::AuditStore;
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
-use crate::runtime::ContainerFilters;
+use crate::data_location::{self, DataLocation};
+use crate::disk_usage::{self, DiskUsage};
+use crate::event_bus::cloud_event::CloudEvent;
+use crate::event_bus::Dispatch;
+use crate::lifecycle_events::{self, BufferedEvent, LifecycleBuffer, LifecycleEvent};
+use crate::plugin_manager::image_scan::ImageScanPolicy;
+use crate::plugin_manager::non_root::RootUserPolicy;
+use crate::plugin_manager::resource_history::{ResourceHistory, ResourceSample};
+use crate::proxy::ProxyConfig;
+use crate::secrets::CredentialStorage;
+use crate::runtime::docker::DockerRuntime;
+use crate::runtime::{ContainerFilters, ContainerRuntime, EngineConfig, EngineKind};
 use crate::ActiveTheme;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tauri::Manager;
 
 #[derive(Serialize)]
 pub struct AppVersionInfo {
@@ -21,6 +35,16 @@ pub async fn app_version() -> AppVersionInfo {
     }
 }
 
+/// Terminal lifecycle events emitted after `after`, for a webview that
+/// reloaded and missed them.
+#[tauri::command]
+pub async fn lifecycle_replay(
+    buffer: tauri::State<'_, LifecycleBuffer>,
+    after: u64,
+) -> Result<Vec<BufferedEvent>, String> {
+    Ok(buffer.replay(after))
+}
+
 #[derive(Serialize)]
 pub struct EngineStatus {
     pub engine_id: String,
@@ -31,8 +55,12 @@ pub struct EngineStatus {
     pub message: String,
 }
 
-/// Check whether a socket/pipe path exists on disk.
+/// Check whether a socket/pipe path exists on disk. Network endpoints
+/// can only be checked by connecting.
 fn socket_exists(socket: &str) -> bool {
+    if !socket.starts_with("unix://") && !socket.starts_with("npipe://") && socket.contains("://") {
+        return true;
+    }
     // Strip common URI prefixes
     let path = socket
         .strip_prefix("unix://")
@@ -89,6 +117,114 @@ pub async fn check_engine(state: tauri::State<'_, AppState>) -> Result<EngineSta
     }
 }
 
+#[tauri::command]
+pub async fn engine_config(state: tauri::State<'_, AppState>) -> Result<EngineConfig, String> {
+    Ok(state.read().await.settings.engine.clone())
+}
+
+#[derive(Default, Serialize)]
+pub struct EngineSwitchReport {
+    pub restarted: Vec<String>,
+    pub failed_to_start: Vec<String>,
+    pub missing_images: Vec<String>,
+    pub volumes_not_copied: Vec<String>,
+}
+
+/// Move plugins to another container engine without restarting Nexus.
+/// Plugins that were running are started again on the new engine.
+#[tauri::command]
+pub async fn switch_engine(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    kind: EngineKind,
+    host: Option<String>,
+) -> Result<EngineSwitchReport, String> {
+    let config = EngineConfig {
+        kind,
+        host: host.map(|h| h.trim().to_string()).filter(|h| !h.is_empty()),
+    };
+    if state.read().await.settings.engine == config {
+        return Ok(EngineSwitchReport::default());
+    }
+
+    // Refuse an engine that isn't answering rather than strand every plugin
+    let runtime = DockerRuntime::connect(&config).map_err(|e| e.to_string())?;
+    match tokio::time::timeout(std::time::Duration::from_secs(3), runtime.ping()).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Err(format!("Container engine not responding: {}", e)),
+        Err(_) => return Err("Container engine connection timed out".to_string()),
+    }
+    let runtime: Arc<dyn ContainerRuntime> = Arc::new(runtime);
+
+    let mut mgr = state.write().await;
+    let previous = mgr.settings.engine.clone();
+    let switch = mgr.switch_runtime(runtime).await;
+    mgr.settings.engine = config.clone();
+    let saved = mgr.settings.save();
+    mgr.notify_tools_changed();
+    let stopped: Vec<_> = switch
+        .was_running
+        .iter()
+        .filter_map(|id| mgr.storage.get(id).cloned())
+        .collect();
+    drop(mgr);
+    for plugin in stopped {
+        lifecycle_events::emit(Some(&app), LifecycleEvent::PluginStopped { plugin });
+    }
+
+    let mut restarted = Vec::new();
+    let mut failed_to_start = Vec::new();
+    for plugin_id in &switch.was_running {
+        lifecycle_events::emit(Some(&app), LifecycleEvent::PluginStarting {
+            plugin_id: plugin_id.clone(),
+        });
+        let mut mgr = state.write().await;
+        match mgr.start(plugin_id).await {
+            Ok(()) => {
+                mgr.notify_tools_changed();
+                let plugin = mgr.storage.get(plugin_id).cloned();
+                drop(mgr);
+                if let Some(plugin) = plugin {
+                    lifecycle_events::emit(Some(&app), LifecycleEvent::PluginStarted { plugin });
+                }
+                restarted.push(plugin_id.clone());
+            }
+            Err(e) => {
+                drop(mgr);
+                log::warn!("Engine switch: failed to start plugin={}: {}", plugin_id, e);
+                lifecycle_events::emit(Some(&app), LifecycleEvent::PluginError {
+                    plugin_id: plugin_id.clone(),
+                    action: "starting".into(),
+                    message: e.to_string(),
+                });
+                failed_to_start.push(plugin_id.clone());
+            }
+        }
+    }
+
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.engine".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "from": previous,
+            "to": config,
+            "restarted": restarted,
+            "failed_to_start": failed_to_start,
+            "missing_images": switch.missing_images,
+            "volumes_not_copied": switch.volumes_not_copied,
+        })),
+    });
+    saved.map_err(|e| e.to_string())?;
+
+    Ok(EngineSwitchReport {
+        restarted,
+        failed_to_start,
+        missing_images: switch.missing_images,
+        volumes_not_copied: switch.volumes_not_copied,
+    })
+}
+
 #[tauri::command]
 pub async fn container_resource_usage(
     state: tauri::State<'_, AppState>,
@@ -104,6 +240,31 @@ pub async fn container_resource_usage(
         .map_err(|e| e.to_string())
 }
 
+/// Disk space used by each plugin and by Nexus's own state.
+#[tauri::command]
+pub async fn system_disk_usage(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    audit_store: tauri::State<'_, Arc<AuditStore>>,
+) -> Result<DiskUsage, String> {
+    let (plugins, runtime, data_dir) = {
+        let mgr = state.read().await;
+        let plugins: Vec<_> = mgr.storage.list().into_iter().cloned().collect();
+        (plugins, mgr.runtime.clone(), mgr.data_dir.clone())
+    };
+    let log_dir = app.path().app_log_dir().ok();
+    Ok(disk_usage::collect(&plugins, &runtime, &data_dir, log_dir.as_deref(), &audit_store).await)
+}
+
+/// Recent resource samples for one plugin, oldest first.
+#[tauri::command]
+pub async fn plugin_resource_history(
+    history: tauri::State<'_, ResourceHistory>,
+    plugin_id: String,
+) -> Result<Vec<ResourceSample>, String> {
+    Ok(history.series(&plugin_id))
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ResourceQuotas {
     pub cpu_percent: Option<f64>,
@@ -170,11 +331,27 @@ pub async fn set_update_check_interval(
 pub async fn set_language(
     state: tauri::State<'_, AppState>,
     audit: tauri::State<'_, AuditWriter>,
+    dispatch: tauri::State<'_, Dispatch>,
     language: String,
 ) -> Result<(), String> {
     let mut mgr = state.write().await;
+    // Called on every launch with the stored language
+    if mgr.settings.language == language {
+        return Ok(());
+    }
     mgr.settings.language = language.clone();
     mgr.settings.save().map_err(|e| e.to_string())?;
+    drop(mgr);
+
+    // Running containers keep the NEXUS_LANGUAGE they were created with
+    let event = CloudEvent::builder()
+        .source(crate::lifecycle_events::LIFECYCLE_SOURCE)
+        .event_type(crate::host_api::settings::LANGUAGE_CHANGED_EVENT)
+        .data(serde_json::json!({ "language": language }))
+        .build()?;
+    if let Err(e) = dispatch.publish(event).await {
+        log::warn!("Failed to publish language change: {}", e);
+    }
     audit.record(AuditEntry {
         actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.language".into(),
         subject: None, result: AuditResult::Success,
@@ -202,11 +379,291 @@ pub async fn set_theme(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_read_only_mode(
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.read_only_mode)
+}
+
+/// Turn read-only mode on or off. Takes effect on the next Host API or MCP call.
+#[tauri::command]
+pub async fn set_read_only_mode(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    mgr.settings.read_only_mode = enabled;
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User,
+        source_id: None,
+        severity: AuditSeverity::Warn,
+        action: "settings.read_only_mode".into(),
+        subject: None,
+        result: AuditResult::Success,
+        details: Some(serde_json::json!({"enabled": enabled})),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_prometheus_metrics(
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.prometheus_metrics)
+}
+
+/// Turn the unauthenticated Prometheus endpoint on or off. Takes effect on
+/// the next scrape.
+#[tauri::command]
+pub async fn set_prometheus_metrics(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    mgr.settings.prometheus_metrics = enabled;
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.prometheus_metrics".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"enabled": enabled})),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_data_location(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<DataLocation, String> {
+    let default_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let current = state.read().await.data_dir.clone();
+    Ok(data_location::location(&default_dir, &current))
+}
+
+/// Move the data directory to `path` on the next launch. The frontend
+/// relaunches once this returns.
+#[tauri::command]
+pub async fn move_data_dir(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    path: String,
+) -> Result<(), String> {
+    let default_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let current = state.read().await.data_dir.clone();
+    let result = data_location::schedule_move(&default_dir, &current, std::path::Path::new(&path));
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.data_dir".into(),
+        subject: Some(path),
+        result: if result.is_ok() { AuditResult::Success } else { AuditResult::Failure },
+        details: Some(match &result {
+            Ok(()) => serde_json::json!({"from": current}),
+            Err(e) => serde_json::json!({"from": current, "error": e.to_string()}),
+        }),
+    });
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_data_dir_move(app: tauri::AppHandle) -> Result<(), String> {
+    let default_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    data_location::cancel_move(&default_dir).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_proxy_config(state: tauri::State<'_, AppState>) -> Result<ProxyConfig, String> {
+    Ok(state.read().await.settings.proxy.clone())
+}
+
+/// Save the outbound proxy. Applies to HTTP clients built from now on;
+/// the container engine keeps its own proxy settings for image pulls.
+#[tauri::command]
+pub async fn set_proxy_config(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    config: ProxyConfig,
+) -> Result<(), String> {
+    config.validate()?;
+    let mut mgr = state.write().await;
+    mgr.settings.proxy = config.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    drop(mgr);
+    crate::proxy::set(&config);
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.proxy".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({
+            "enabled": config.enabled,
+            "url": config.url(),
+            "no_proxy": config.no_proxy,
+        })),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_credential_storage(
+    state: tauri::State<'_, AppState>,
+) -> Result<CredentialStorage, String> {
+    Ok(state.read().await.settings.credential_storage)
+}
+
+/// Move registry credentials, plugin secrets, and the key encrypting the
+/// OAuth and API key stores to the OS keychain or to owner-only files.
+/// Nothing is changed when the keychain can't be reached.
+#[tauri::command]
+pub async fn set_credential_storage(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    storage: CredentialStorage,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    let previous = mgr.settings.credential_storage;
+    mgr.registry_store
+        .set_credential_storage(storage)
+        .map_err(|e| e.to_string())?;
+    if let Err(e) = mgr.plugin_env.set_credential_storage(storage) {
+        let _ = mgr.registry_store.set_credential_storage(previous);
+        return Err(e.to_string());
+    }
+    if let Err(e) = crate::secrets::set_key_storage(&mgr.data_dir, storage) {
+        let _ = mgr.registry_store.set_credential_storage(previous);
+        let _ = mgr.plugin_env.set_credential_storage(previous);
+        return Err(e.to_string());
+    }
+    mgr.settings.credential_storage = storage;
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.credential_storage".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({ "from": previous, "to": storage })),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_image_scan_policy(
+    state: tauri::State<'_, AppState>,
+) -> Result<ImageScanPolicy, String> {
+    Ok(state.read().await.settings.image_scan)
+}
+
+#[tauri::command]
+pub async fn set_image_scan_policy(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    policy: ImageScanPolicy,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    let previous = mgr.settings.image_scan;
+    mgr.settings.image_scan = policy;
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.image_scan".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({ "from": previous, "to": policy })),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_root_user_policy(
+    state: tauri::State<'_, AppState>,
+) -> Result<RootUserPolicy, String> {
+    Ok(state.read().await.settings.root_user)
+}
+
+/// Forcing a non-root user applies to each plugin's next start.
+#[tauri::command]
+pub async fn set_root_user_policy(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    policy: RootUserPolicy,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    let previous = mgr.settings.root_user;
+    mgr.settings.root_user = policy;
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.root_user".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({ "from": previous, "to": policy })),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_launch_at_login(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(crate::autostart::is_enabled(&app.config().identifier))
+}
+
+/// Register or remove the OS login item. Login launches start in the tray.
+#[tauri::command]
+pub async fn set_launch_at_login(
+    app: tauri::AppHandle,
+    audit: tauri::State<'_, AuditWriter>,
+    enabled: bool,
+) -> Result<(), String> {
+    let identifier = &app.config().identifier;
+    if enabled {
+        crate::autostart::enable(identifier).map_err(|e| e.to_string())?;
+    } else {
+        crate::autostart::disable(identifier).map_err(|e| e.to_string())?;
+    }
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.launch_at_login".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({ "enabled": enabled })),
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_global_shortcut(state: tauri::State<'_, AppState>) -> Result<Option<String>, String> {
+    Ok(state.read().await.settings.global_shortcut.clone())
+}
+
+/// Change the show/hide shortcut, or turn it off with None. The new one is
+/// registered before it's saved; if the OS refuses it, the old one stays.
+#[tauri::command]
+pub async fn set_global_shortcut(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    shortcut: Option<String>,
+) -> Result<(), String> {
+    let shortcut = shortcut.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    if let Some(accelerator) = &shortcut {
+        crate::shortcut::parse(accelerator)?;
+    }
+    let mut mgr = state.write().await;
+    let previous = mgr.settings.global_shortcut.clone();
+    if let Err(e) = crate::shortcut::apply(&app, shortcut.as_deref()) {
+        let _ = crate::shortcut::apply(&app, previous.as_deref());
+        return Err(e);
+    }
+    mgr.settings.global_shortcut = shortcut.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    drop(mgr);
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Info, action: "settings.global_shortcut".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({ "from": previous, "to": shortcut })),
+    });
+    Ok(())
+}
+
 /// HEAD a URL to check if it's reachable (2xx/3xx = true).
 /// Used by the extension marketplace to verify manifest URLs exist before enabling install.
 #[tauri::command]
 pub async fn check_url_reachable(url: String) -> Result<bool, String> {
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::apply(reqwest::Client::builder())
         .timeout(std::time::Duration::from_secs(5))
         .build()
         .map_err(|e| e.to_string())?;
@@ -1,40 +1,113 @@
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::error::NexusError;
 use crate::extensions::storage::InstalledExtension;
 use crate::lifecycle_events::{self, LifecycleEvent};
 use crate::plugin_manager::registry;
+use crate::plugin_manager::PluginManager;
 use crate::plugin_manager::storage::{InstalledPlugin, PluginStatus};
-use crate::update_checker::{self, AvailableUpdate};
+use crate::update_checker::{self, AutoUpdatePolicy, AvailableUpdate, YankedInstall};
 use crate::AppState;
 
 use super::extensions::build_extension_status;
 
 /// Check all installed plugins and extensions for available updates.
+///
+/// Updates for plugins with automatic updates on start in the background and
+/// are left out of the result; lifecycle events and a notification report
+/// their progress. Installed versions newly found withdrawn by their registry
+/// are reported with a notification too.
 #[tauri::command]
 pub async fn check_updates(
+    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
 ) -> Result<Vec<AvailableUpdate>, String> {
-    let mut mgr = state.write().await;
+    let (mut updates, mut changelogs, yanked) = {
+        let mgr = state.read().await;
+        let updates = update_checker::check_for_updates(
+            &mgr.storage,
+            &mgr.extension_loader.storage,
+            &mgr.registry_cache,
+            &mgr.extension_registry_cache,
+            &mgr.extension_loader.trusted_keys,
+            &mgr.registry_store,
+            &mgr.update_state.dismissed,
+        );
+        let yanked = update_checker::find_yanked_installs(
+            &mgr.storage,
+            &mgr.extension_loader.storage,
+            &mgr.registry_cache,
+            &mgr.extension_registry_cache,
+            &mgr.registry_store,
+        );
+        (updates, mgr.update_state.changelogs.clone(), yanked)
+    };
 
-    let updates = update_checker::check_for_updates(
-        &mgr.storage,
-        &mgr.extension_loader.storage,
-        &mgr.registry_cache,
-        &mgr.extension_registry_cache,
-        &mgr.extension_loader.trusted_keys,
-        &mgr.registry_store,
-        &mgr.update_state.dismissed,
-    );
+    // Fetched without holding the manager lock
+    update_checker::fetch_changelogs(&mut updates, &mut changelogs).await;
 
+    let mut mgr = state.write().await;
+    let newly_available: Vec<String> = updates
+        .iter()
+        .filter(|u| {
+            !mgr.update_state
+                .available_updates
+                .iter()
+                .any(|p| p.item_id == u.item_id && p.available_version == u.available_version)
+        })
+        .map(|u| format!("{} {}", u.item_name, u.available_version))
+        .collect();
     mgr.update_state.last_checked = Some(chrono::Utc::now());
     mgr.update_state.available_updates = updates.clone();
+    mgr.update_state.changelogs = changelogs;
+    let newly_yanked: Vec<String> = yanked
+        .iter()
+        .filter(|y| {
+            !mgr.update_state
+                .yanked
+                .iter()
+                .any(|p| p.item_id == y.item_id && p.version == y.version)
+        })
+        .map(|y| match &y.reason {
+            Some(reason) => format!("{} {}: {}", y.item_name, y.version, reason),
+            None => format!("{} {}", y.item_name, y.version),
+        })
+        .collect();
+    mgr.update_state.yanked = yanked;
 
     update_checker::save_update_state(&mgr.data_dir, &mgr.update_state)
         .map_err(|e| e.to_string())?;
 
-    Ok(updates)
+    let (automatic, manual): (Vec<_>, Vec<_>) = updates
+        .into_iter()
+        .partition(|u| mgr.settings.auto_update.applies_to(u));
+    drop(mgr);
+    crate::tray::refresh(&app);
+    if !newly_available.is_empty() {
+        // In-app only, like the updates badge; kept so it isn't lost
+        let title = if newly_available.len() == 1 {
+            "Update available".to_string()
+        } else {
+            format!("{} updates available", newly_available.len())
+        };
+        crate::notification::record(&app, "updates.available", &title, &newly_available.join("\n"));
+    }
+    spawn_auto_updates(app.clone(), state.inner().clone(), audit.inner().clone(), automatic);
+
+    if !newly_yanked.is_empty() {
+        let title = if newly_yanked.len() == 1 {
+            "An installed version was withdrawn".to_string()
+        } else {
+            format!("{} installed versions were withdrawn", newly_yanked.len())
+        };
+        crate::notification::notify(&app, "updates.yanked", title, newly_yanked.join("\n"));
+    }
+
+    Ok(manual)
 }
 
 /// Return cached updates without re-checking.
@@ -46,9 +119,19 @@ pub async fn get_cached_updates(
     Ok(mgr.update_state.available_updates.clone())
 }
 
+/// Installed versions their registry has withdrawn, as of the last check.
+#[tauri::command]
+pub async fn get_yanked_installs(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<YankedInstall>, String> {
+    let mgr = state.read().await;
+    Ok(mgr.update_state.yanked.clone())
+}
+
 /// Dismiss an update so it no longer appears.
 #[tauri::command]
 pub async fn dismiss_update(
+    app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     item_id: String,
     version: String,
@@ -63,6 +146,8 @@ pub async fn dismiss_update(
 
     update_checker::save_update_state(&mgr.data_dir, &mgr.update_state)
         .map_err(|e| e.to_string())?;
+    drop(mgr);
+    crate::tray::refresh(&app);
 
     Ok(())
 }
@@ -77,51 +162,162 @@ pub async fn update_plugin(
     expected_digest: Option<String>,
     build_context: Option<String>,
 ) -> Result<InstalledPlugin, String> {
-    let manifest = registry::fetch_manifest(&manifest_url)
+    run_plugin_update(
+        &app,
+        &state,
+        &audit,
+        AuditActor::User,
+        &manifest_url,
+        expected_digest,
+        build_context,
+        false,
+    )
+    .await
+}
+
+/// Update a plugin, accepting a change of the author's signing key.
+#[tauri::command]
+pub async fn update_plugin_force_key(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    manifest_url: String,
+    expected_digest: Option<String>,
+    build_context: Option<String>,
+) -> Result<InstalledPlugin, String> {
+    run_plugin_update(
+        &app,
+        &state,
+        &audit,
+        AuditActor::User,
+        &manifest_url,
+        expected_digest,
+        build_context,
+        true,
+    )
+    .await
+}
+
+/// Fetch the manifest, rebuild or pull the image, and swap the container,
+/// recording the outcome. Shared by `update_plugin` and automatic updates.
+#[allow(clippy::too_many_arguments)]
+async fn run_plugin_update(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    audit: &AuditWriter,
+    actor: AuditActor,
+    manifest_url: &str,
+    expected_digest: Option<String>,
+    build_context: Option<String>,
+    force_key: bool,
+) -> Result<InstalledPlugin, String> {
+    state.read().await.check_writable().map_err(|e| e.to_string())?;
+    let manifest = PluginManager::fetch_registry_manifest(state, manifest_url, force_key)
         .await
         .map_err(|e| e.to_string())?;
 
     let plugin_id = manifest.id.clone();
+    let (locks, runtime) = {
+        let mgr = state.read().await;
+        (mgr.plugin_locks.clone(), mgr.runtime.clone())
+    };
+    let _plugin_lock = locks.lock(&plugin_id).await;
 
+    let mut built = false;
     if let Some(ref ctx) = build_context {
         let ctx_path = Path::new(ctx);
         if ctx_path.join("Dockerfile").exists() {
             log::info!("Rebuilding image {} from {}", manifest.image, ctx_path.display());
-            let runtime = { state.read().await.runtime.clone() };
+            let on_progress = |p| {
+                lifecycle_events::emit(Some(app), LifecycleEvent::PluginUpdateStage {
+                    plugin_id: plugin_id.clone(),
+                    stage: "building".into(),
+                    progress: Some(p),
+                });
+            };
             runtime
-                .build_image(ctx_path, &manifest.image)
+                .build_image_with_progress(ctx_path, &manifest.image, &on_progress)
                 .await
                 .map_err(|e| format!("Docker build failed: {}", e))?;
+            built = true;
         }
     }
 
+    // Pull and check the image before taking the manager: the old
+    // container keeps running meanwhile, and a first scan can take minutes
+    let vetted = async {
+        let vetter = {
+            let mgr = state.read().await;
+            mgr.check_update(&manifest, expected_digest.as_deref())?;
+            mgr.image_vetter(&plugin_id)
+        };
+        if manifest.remote_mcp_url().is_none() && !built {
+            log::info!("Pulling updated image: {}", manifest.image);
+            let on_progress = |p| {
+                lifecycle_events::emit(Some(app), LifecycleEvent::PluginUpdateStage {
+                    plugin_id: plugin_id.clone(),
+                    stage: "pulling".into(),
+                    progress: Some(p),
+                });
+            };
+            runtime
+                .pull_image_with_progress(&manifest.image, &on_progress)
+                .await?;
+        }
+        vetter.vet(&manifest).await
+    }
+    .await;
+
     let mut mgr = state.write().await;
-    match mgr.update_plugin(manifest, expected_digest, Some(&app)).await {
+    let updated = match vetted {
+        Ok(vetted) => {
+            mgr.update_plugin_vetted(manifest, expected_digest, vetted, Some(app))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    match updated {
         Ok(result) => {
             mgr.notify_tools_changed();
+            super::plugins::notify_install_warnings(app, &mgr, &result);
             audit.record(AuditEntry {
-                actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "plugin.update".into(),
+                actor, source_id: None, severity: AuditSeverity::Warn, action: "plugin.update".into(),
                 subject: Some(plugin_id.clone()), result: AuditResult::Success,
                 details: Some(serde_json::json!({"version": result.manifest.version})),
             });
             if result.status == PluginStatus::Running {
-                lifecycle_events::emit(Some(&app), LifecycleEvent::PluginStarted {
+                lifecycle_events::emit(Some(app), LifecycleEvent::PluginStarted {
                     plugin: result.clone(),
                 });
             } else {
-                lifecycle_events::emit(Some(&app), LifecycleEvent::PluginStopped {
+                lifecycle_events::emit(Some(app), LifecycleEvent::PluginStopped {
                     plugin: result.clone(),
                 });
             }
             Ok(result)
         }
+        Err(NexusError::UpdateRolledBack { version, reason }) => {
+            audit.record(AuditEntry {
+                actor, source_id: None, severity: AuditSeverity::Warn, action: "plugin.update".into(),
+                subject: Some(plugin_id.clone()), result: AuditResult::Failure,
+                details: Some(serde_json::json!({"version": version, "error": reason, "rolled_back": true})),
+            });
+            if let Some(plugin) = mgr.storage.get(&plugin_id) {
+                lifecycle_events::emit(Some(app), LifecycleEvent::PluginUpdateRolledBack {
+                    plugin: plugin.clone(),
+                    failed_version: version.clone(),
+                    error: reason.clone(),
+                });
+            }
+            Err(NexusError::UpdateRolledBack { version, reason }.to_string())
+        }
         Err(e) => {
             audit.record(AuditEntry {
-                actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "plugin.update".into(),
+                actor, source_id: None, severity: AuditSeverity::Warn, action: "plugin.update".into(),
                 subject: Some(plugin_id.clone()), result: AuditResult::Failure,
                 details: Some(serde_json::json!({"error": e.to_string()})),
             });
-            lifecycle_events::emit(Some(&app), LifecycleEvent::PluginError {
+            lifecycle_events::emit(Some(app), LifecycleEvent::PluginError {
                 plugin_id,
                 action: "updating".into(),
                 message: e.to_string(),
@@ -131,6 +327,116 @@ pub async fn update_plugin(
     }
 }
 
+/// Set while automatic updates run, so overlapping checks don't start a
+/// second pass over the same plugins.
+static AUTO_UPDATING: AtomicBool = AtomicBool::new(false);
+
+/// Apply `updates` one at a time in the background, then show a
+/// notification summarizing what changed.
+fn spawn_auto_updates(
+    app: tauri::AppHandle,
+    state: AppState,
+    audit: AuditWriter,
+    updates: Vec<AvailableUpdate>,
+) {
+    if updates.is_empty() || AUTO_UPDATING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let mut changes = Vec::new();
+        for update in updates {
+            let old_permissions = {
+                let mgr = state.read().await;
+                mgr.storage
+                    .get(&update.item_id)
+                    .map(|p| p.manifest.permissions.clone())
+                    .unwrap_or_default()
+            };
+            log::info!(
+                "Auto-updating plugin {} {} -> {}",
+                update.item_id, update.installed_version, update.available_version
+            );
+            let result = run_plugin_update(
+                &app,
+                &state,
+                &audit,
+                AuditActor::System,
+                &update.manifest_url,
+                update.new_image_digest.clone(),
+                update.build_context.clone(),
+                false,
+            )
+            .await;
+            match result {
+                Ok(plugin) => {
+                    let added: Vec<String> = plugin
+                        .manifest
+                        .permissions
+                        .iter()
+                        .filter(|p| !old_permissions.contains(p))
+                        .map(|p| p.as_str().to_string())
+                        .collect();
+                    let mut line = format!(
+                        "{} {} → {}",
+                        update.item_name, update.installed_version, plugin.manifest.version
+                    );
+                    if !added.is_empty() {
+                        line.push_str(&format!(" (requests {})", added.join(", ")));
+                    }
+                    changes.push(line);
+
+                    let mut mgr = state.write().await;
+                    mgr.update_state
+                        .available_updates
+                        .retain(|u| u.item_id != update.item_id);
+                    if let Err(e) = update_checker::save_update_state(&mgr.data_dir, &mgr.update_state) {
+                        log::warn!("Failed to save update state: {}", e);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Auto-update of plugin {} failed: {}", update.item_id, e);
+                    changes.push(format!("{}: update failed ({})", update.item_name, e));
+                }
+            }
+        }
+        AUTO_UPDATING.store(false, Ordering::SeqCst);
+
+        let title = if changes.len() == 1 {
+            "Plugin updated automatically".to_string()
+        } else {
+            format!("{} plugins updated automatically", changes.len())
+        };
+        crate::notification::notify(&app, "updates.applied", title, changes.join("\n"));
+    });
+}
+
+/// Get which plugins update automatically.
+#[tauri::command]
+pub async fn get_auto_update_policy(
+    state: tauri::State<'_, AppState>,
+) -> Result<AutoUpdatePolicy, String> {
+    let mgr = state.read().await;
+    Ok(mgr.settings.auto_update.clone())
+}
+
+/// Set which plugins update automatically. Takes effect on the next check.
+#[tauri::command]
+pub async fn set_auto_update_policy(
+    state: tauri::State<'_, AppState>,
+    audit: tauri::State<'_, AuditWriter>,
+    policy: AutoUpdatePolicy,
+) -> Result<(), String> {
+    let mut mgr = state.write().await;
+    mgr.settings.auto_update = policy.clone();
+    mgr.settings.save().map_err(|e| e.to_string())?;
+    audit.record(AuditEntry {
+        actor: AuditActor::User, source_id: None, severity: AuditSeverity::Warn, action: "settings.auto_update".into(),
+        subject: None, result: AuditResult::Success,
+        details: Some(serde_json::json!({"enabled": policy.enabled, "plugins": policy.plugins})),
+    });
+    Ok(())
+}
+
 /// Update an extension to a new version. Rejects key changes.
 #[tauri::command]
 pub async fn update_extension(
@@ -139,7 +445,8 @@ pub async fn update_extension(
     audit: tauri::State<'_, AuditWriter>,
     manifest_url: String,
 ) -> Result<InstalledExtension, String> {
-    let manifest = registry::fetch_extension_manifest(&manifest_url)
+    let auth = state.read().await.manifest_auth(&manifest_url);
+    let manifest = registry::fetch_extension_manifest(&manifest_url, auth.as_ref())
         .await
         .map_err(|e| e.to_string())?;
 
@@ -182,7 +489,8 @@ pub async fn update_extension_force_key(
     app: tauri::AppHandle,
     manifest_url: String,
 ) -> Result<InstalledExtension, String> {
-    let manifest = registry::fetch_extension_manifest(&manifest_url)
+    let auth = state.read().await.manifest_auth(&manifest_url);
+    let manifest = registry::fetch_extension_manifest(&manifest_url, auth.as_ref())
         .await
         .map_err(|e| e.to_string())?;
 
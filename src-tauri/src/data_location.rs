@@ -0,0 +1,313 @@
+//! Where Nexus keeps its state.
+//!
+//! The data directory defaults to the platform's app data directory. Users
+//! can move it elsewhere, e.g. to a bigger drive; the chosen location is
+//! recorded in a small pointer file that always stays in the default
+//! directory, since that's the only place Nexus can find before it knows
+//! where its data is.
+//!
+//! Moving happens at startup, before anything opens files in the data
+//! directory: the settings UI records the destination and relaunches, and
+//! [`resolve`] copies everything over, re-points stored absolute paths,
+//! and removes the old copy.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NexusError, NexusResult};
+
+/// Pointer file in the default data directory.
+const POINTER_FILE: &str = "data-location.json";
+
+/// Files that store absolute paths into the data directory (the local MCP
+/// registry, and the manifests of wrapped MCP servers in a plugin store
+/// that hasn't been imported into `state.db` yet).
+const REPOINT_FILES: &[&str] = &["plugins.json", "registries.json"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Pointer {
+    /// Data directory in use, when it isn't the default.
+    #[serde(default)]
+    path: Option<PathBuf>,
+    /// Directory to move the data to on the next launch.
+    #[serde(default)]
+    pending_move: Option<PathBuf>,
+    /// Why the last move failed.
+    #[serde(default)]
+    last_error: Option<String>,
+}
+
+impl Pointer {
+    fn load(default_dir: &Path) -> Self {
+        std::fs::read_to_string(default_dir.join(POINTER_FILE))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, default_dir: &Path) -> NexusResult<()> {
+        let data = serde_json::to_string_pretty(self)?;
+        crate::util::atomic_write(&default_dir.join(POINTER_FILE), data.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DataLocation {
+    pub current: PathBuf,
+    pub default: PathBuf,
+    /// Destination of a move that happens on the next launch.
+    pub pending_move: Option<PathBuf>,
+    /// Why the last move failed, if it did.
+    pub last_error: Option<String>,
+}
+
+/// The data directory to use, carrying out a pending move first. A failed
+/// move leaves the data where it was, with the error kept for the UI.
+pub fn resolve(default_dir: &Path) -> PathBuf {
+    let mut pointer = Pointer::load(default_dir);
+    let current = match &pointer.path {
+        Some(path) if path.is_dir() => path.clone(),
+        Some(path) => {
+            log::error!(
+                "Data directory {} not found, using {}",
+                path.display(),
+                default_dir.display()
+            );
+            default_dir.to_path_buf()
+        }
+        None => default_dir.to_path_buf(),
+    };
+
+    let Some(target) = pointer.pending_move.take() else {
+        return current;
+    };
+    match move_data(&current, &target) {
+        Ok(files) => {
+            log::info!(
+                "Moved data directory {} -> {} ({} files)",
+                current.display(),
+                target.display(),
+                files
+            );
+            pointer.path = (target != default_dir).then(|| target.clone());
+            pointer.last_error = None;
+            if let Err(e) = pointer.save(default_dir) {
+                log::error!("Failed to record data directory: {}", e);
+            }
+            target
+        }
+        Err(e) => {
+            log::error!("Failed to move data directory to {}: {}", target.display(), e);
+            pointer.last_error = Some(e.to_string());
+            if let Err(e) = pointer.save(default_dir) {
+                log::error!("Failed to record data directory: {}", e);
+            }
+            current
+        }
+    }
+}
+
+pub fn location(default_dir: &Path, current: &Path) -> DataLocation {
+    let pointer = Pointer::load(default_dir);
+    DataLocation {
+        current: current.to_path_buf(),
+        default: default_dir.to_path_buf(),
+        pending_move: pointer.pending_move,
+        last_error: pointer.last_error,
+    }
+}
+
+/// Schedule moving the data from `current` to `target` on the next launch.
+pub fn schedule_move(default_dir: &Path, current: &Path, target: &Path) -> NexusResult<()> {
+    check_target(default_dir, current, target)?;
+    let mut pointer = Pointer::load(default_dir);
+    pointer.pending_move = Some(target.to_path_buf());
+    pointer.last_error = None;
+    pointer.save(default_dir)
+}
+
+/// Drop a move scheduled for the next launch.
+pub fn cancel_move(default_dir: &Path) -> NexusResult<()> {
+    let mut pointer = Pointer::load(default_dir);
+    pointer.pending_move = None;
+    pointer.save(default_dir)
+}
+
+fn check_target(default_dir: &Path, current: &Path, target: &Path) -> NexusResult<()> {
+    let invalid = |msg: &str| Err(NexusError::Other(msg.to_string()));
+    if !target.is_absolute() {
+        return invalid("Data directory must be an absolute path");
+    }
+    if target == current {
+        return invalid("Nexus data is already in this directory");
+    }
+    if target.starts_with(current) || current.starts_with(target) {
+        return invalid("The new data directory can't be inside the current one, or contain it");
+    }
+    if target.exists() {
+        if !target.is_dir() {
+            return invalid("The new data directory is a file");
+        }
+        // The default directory always holds the pointer file
+        let occupied = std::fs::read_dir(target)?
+            .filter_map(|e| e.ok())
+            .any(|e| !(target == default_dir && e.file_name() == POINTER_FILE));
+        if occupied {
+            return invalid("The new data directory must be empty");
+        }
+    }
+    Ok(())
+}
+
+/// Copy everything in `from` to `to`, re-point stored paths, then remove
+/// the originals. Returns the number of files moved.
+fn move_data(from: &Path, to: &Path) -> NexusResult<usize> {
+    std::fs::create_dir_all(to)?;
+
+    let mut files = 0;
+    for entry in walkdir::WalkDir::new(from).min_depth(1) {
+        let entry = entry.map_err(|e| NexusError::Other(e.to_string()))?;
+        let rel = entry
+            .path()
+            .strip_prefix(from)
+            .map_err(|e| NexusError::Other(e.to_string()))?;
+        if rel == Path::new(POINTER_FILE) {
+            continue;
+        }
+        let dest = to.join(rel);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest)?;
+        } else if entry.file_type().is_file() {
+            let len = std::fs::copy(entry.path(), &dest)?;
+            if len != entry.metadata().map_err(|e| NexusError::Other(e.to_string()))?.len() {
+                return Err(NexusError::Other(format!("Short copy of {}", rel.display())));
+            }
+            files += 1;
+        }
+    }
+
+    for name in REPOINT_FILES {
+        let path = to.join(name);
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut value: serde_json::Value = serde_json::from_str(&data)?;
+        if repoint(&mut value, from, to) {
+            crate::util::atomic_write(&path, serde_json::to_string_pretty(&value)?.as_bytes())?;
+        }
+    }
+    if to.join(crate::state_db::DB_FILE).exists() {
+        crate::state_db::StateDb::open(to)?.rewrite(|value| repoint(value, from, to))?;
+    }
+
+    // Everything is in place; a leftover original only costs disk space
+    for entry in std::fs::read_dir(from)?.filter_map(|e| e.ok()) {
+        if entry.file_name() == POINTER_FILE {
+            continue;
+        }
+        let path = entry.path();
+        let removed = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        if let Err(e) = removed {
+            log::warn!("Failed to remove old data {}: {}", path.display(), e);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Rewrite strings that are paths under `from` to the same path under
+/// `to`. Returns whether anything changed.
+fn repoint(value: &mut serde_json::Value, from: &Path, to: &Path) -> bool {
+    match value {
+        serde_json::Value::String(s) => match Path::new(s.as_str()).strip_prefix(from) {
+            Ok(rest) => {
+                *s = to.join(rest).display().to_string();
+                true
+            }
+            Err(_) => false,
+        },
+        serde_json::Value::Array(items) => {
+            let mut changed = false;
+            for v in items {
+                changed |= repoint(v, from, to);
+            }
+            changed
+        }
+        serde_json::Value::Object(map) => {
+            let mut changed = false;
+            for v in map.values_mut() {
+                changed |= repoint(v, from, to);
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_move_copies_and_repoints() {
+        let root = tempfile::tempdir().unwrap();
+        let default_dir = root.path().join("default");
+        let target = root.path().join("bigger-drive");
+        std::fs::create_dir_all(default_dir.join("mcp-plugins/com.test.wrap")).unwrap();
+        std::fs::write(default_dir.join("settings.json"), "{}").unwrap();
+        let manifest = default_dir.join("mcp-plugins/com.test.wrap/plugin.json");
+        std::fs::write(&manifest, "{}").unwrap();
+        let plugins = serde_json::json!({
+            "plugins": [{ "local_manifest_path": manifest.display().to_string() }],
+            "elsewhere": "/opt/dev/plugin.json",
+        });
+        std::fs::write(default_dir.join("plugins.json"), plugins.to_string()).unwrap();
+
+        schedule_move(&default_dir, &default_dir, &target).unwrap();
+        assert_eq!(resolve(&default_dir), target);
+
+        assert!(target.join("settings.json").exists());
+        assert!(target.join("mcp-plugins/com.test.wrap/plugin.json").exists());
+        assert!(!default_dir.join("settings.json").exists());
+        assert!(!default_dir.join("mcp-plugins").exists());
+
+        let moved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(target.join("plugins.json")).unwrap()).unwrap();
+        assert_eq!(
+            moved["plugins"][0]["local_manifest_path"],
+            target.join("mcp-plugins/com.test.wrap/plugin.json").display().to_string()
+        );
+        assert_eq!(moved["elsewhere"], "/opt/dev/plugin.json");
+
+        // Later launches go straight to the new directory
+        assert_eq!(resolve(&default_dir), target);
+        let location = location(&default_dir, &target);
+        assert!(location.pending_move.is_none());
+        assert!(location.last_error.is_none());
+    }
+
+    #[test]
+    fn rejects_nested_or_occupied_targets() {
+        let root = tempfile::tempdir().unwrap();
+        let current = root.path().join("data");
+        std::fs::create_dir_all(&current).unwrap();
+
+        assert!(schedule_move(&current, &current, &current.join("nested")).is_err());
+        assert!(schedule_move(&current, &current, Path::new("relative")).is_err());
+
+        let occupied = root.path().join("occupied");
+        std::fs::create_dir_all(&occupied).unwrap();
+        std::fs::write(occupied.join("file"), "x").unwrap();
+        assert!(schedule_move(&current, &current, &occupied).is_err());
+
+        assert!(schedule_move(&current, &current, &root.path().join("new")).is_ok());
+        cancel_move(&current).unwrap();
+        assert_eq!(resolve(&current), current);
+    }
+}
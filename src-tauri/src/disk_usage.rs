@@ -0,0 +1,144 @@
+//! Disk space breakdown, per plugin and for the state Nexus keeps itself.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::audit::store::AuditStore;
+use crate::plugin_manager::registry;
+use crate::plugin_manager::storage::InstalledPlugin;
+use crate::runtime::{ContainerRuntime, ImageInfo};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginDiskUsage {
+    pub plugin_id: String,
+    pub name: String,
+    pub image_bytes: u64,
+    /// None when the engine doesn't report volume sizes.
+    pub volume_bytes: Option<u64>,
+    /// Key-value storage under the data directory.
+    pub storage_bytes: u64,
+    /// The plugin's share of the audit database, which `audit_db_bytes`
+    /// already counts.
+    pub audit_bytes: u64,
+    /// Image, volume, and storage bytes.
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskUsage {
+    /// Largest first.
+    pub plugins: Vec<PluginDiskUsage>,
+    pub registry_cache_bytes: u64,
+    pub audit_db_bytes: u64,
+    pub logs_bytes: u64,
+    /// Everything above, with each byte counted once.
+    pub total_bytes: u64,
+}
+
+/// Measure disk usage. Engine failures leave image and volume sizes out
+/// rather than failing the whole report.
+pub async fn collect(
+    plugins: &[InstalledPlugin],
+    runtime: &Arc<dyn ContainerRuntime>,
+    data_dir: &Path,
+    log_dir: Option<&Path>,
+    audit: &AuditStore,
+) -> DiskUsage {
+    let images = runtime.list_images().await.unwrap_or_else(|e| {
+        log::warn!("Disk usage: failed to list images: {}", e);
+        Vec::new()
+    });
+    let volumes = match runtime.volume_sizes().await {
+        Ok(sizes) => Some(sizes),
+        Err(e) => {
+            log::warn!("Disk usage: failed to measure volumes: {}", e);
+            None
+        }
+    };
+    let audit_sizes = audit.size_by_identity().unwrap_or_else(|e| {
+        log::warn!("Disk usage: failed to measure audit log: {}", e);
+        HashMap::new()
+    });
+
+    let mut usage: Vec<PluginDiskUsage> = plugins
+        .iter()
+        .map(|plugin| {
+            let id = &plugin.manifest.id;
+            let image_bytes = image_size(&images, &plugin.manifest.image);
+            let volume_bytes = volumes.as_ref().map(|sizes| {
+                sizes
+                    .get(&crate::plugin_manager::data_volume_name(id))
+                    .copied()
+                    .unwrap_or(0)
+            });
+            let storage_bytes = crate::host_api::storage::plugin_storage_bytes(data_dir, id);
+            PluginDiskUsage {
+                plugin_id: id.clone(),
+                name: plugin.manifest.name.clone(),
+                image_bytes,
+                volume_bytes,
+                storage_bytes,
+                audit_bytes: audit_sizes.get(id).copied().unwrap_or(0),
+                total_bytes: image_bytes + volume_bytes.unwrap_or(0) + storage_bytes,
+            }
+        })
+        .collect();
+    usage.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then_with(|| a.name.cmp(&b.name)));
+
+    let registry_cache_bytes = file_size(&data_dir.join(registry::CACHE_FILE));
+    // SQLite keeps recent writes in the WAL until a checkpoint
+    let audit_db_bytes = ["audit.db", "audit.db-wal", "audit.db-shm"]
+        .iter()
+        .map(|name| file_size(&data_dir.join(name)))
+        .sum();
+    let logs_bytes = log_dir.map(dir_size).unwrap_or(0);
+
+    let total_bytes = usage.iter().map(|p| p.total_bytes).sum::<u64>()
+        + registry_cache_bytes
+        + audit_db_bytes
+        + logs_bytes;
+
+    DiskUsage {
+        plugins: usage,
+        registry_cache_bytes,
+        audit_db_bytes,
+        logs_bytes,
+        total_bytes,
+    }
+}
+
+/// Size of the local image `reference` names. An untagged reference means
+/// `:latest`, as it does for the engine.
+fn image_size(images: &[ImageInfo], reference: &str) -> u64 {
+    let has_tag = reference
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| name.contains(':'));
+    let tagged = if has_tag {
+        reference.to_string()
+    } else {
+        format!("{reference}:latest")
+    };
+    images
+        .iter()
+        .find(|img| img.repo_tags.contains(&tagged))
+        .map(|img| img.size.max(0) as u64)
+        .unwrap_or(0)
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
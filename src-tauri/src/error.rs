@@ -11,6 +11,9 @@ pub enum NexusError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
@@ -26,6 +29,10 @@ pub enum NexusError {
     #[error("Invalid manifest: {0}")]
     InvalidManifest(String),
 
+    /// An update failed partway and the previous version was restored.
+    #[error("Update to {version} failed and was rolled back: {reason}")]
+    UpdateRolledBack { version: String, reason: String },
+
     #[error("{0}")]
     Other(String),
 }
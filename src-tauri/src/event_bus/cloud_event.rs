@@ -5,6 +5,13 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+/// Extension attribute shared by every event in a causal chain: the ID of
+/// the event that started it.
+pub const CORRELATION_ATTR: &str = "correlationid";
+
+/// Extension attribute naming the event that directly caused this one.
+pub const CAUSATION_ATTR: &str = "causationid";
+
 /// CNCF CloudEvents v1.0 compliant event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CloudEvent {
@@ -48,6 +55,23 @@ impl CloudEvent {
         }
     }
 
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.get_attr(CORRELATION_ATTR)
+    }
+
+    pub fn causation_id(&self) -> Option<&str> {
+        self.get_attr(CAUSATION_ATTR)
+    }
+
+    /// Mark this event as a consequence of `cause`, joining its chain.
+    pub fn set_cause(&mut self, cause: &CloudEvent) {
+        let correlation = cause.correlation_id().unwrap_or(&cause.id).to_string();
+        self.extensions
+            .insert(CAUSATION_ATTR.to_string(), Value::String(cause.id.clone()));
+        self.extensions
+            .insert(CORRELATION_ATTR.to_string(), Value::String(correlation));
+    }
+
     /// Validate that all required CloudEvents v1.0 fields are present and valid.
     pub fn validate(&self) -> Result<(), String> {
         if self.specversion != "1.0" {
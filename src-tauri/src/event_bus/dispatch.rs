@@ -1,9 +1,13 @@
 use std::path::Path;
 use std::sync::Arc;
 
+use tokio::sync::RwLock;
+
+use super::cloud_event::CloudEvent;
 use super::executor::RouteActionExecutor;
+use super::metrics::BusMetricsSnapshot;
 use super::store::EventStore;
-use super::{create_event_bus, retry_worker, SharedEventBus, SharedEventStore};
+use super::{retry_worker, EventBus, SharedEventBus, SharedEventStore};
 use crate::AppState;
 
 /// Single entry point for the entire event bus system.
@@ -21,9 +25,11 @@ impl Dispatch {
         state: AppState,
         app_handle: tauri::AppHandle,
     ) -> Result<Self, String> {
-        let bus = create_event_bus(data_dir);
         let store = Arc::new(EventStore::new(data_dir)?);
-        let executor = RouteActionExecutor::new(state, app_handle);
+        let mut bus = EventBus::new(data_dir);
+        bus.attach_store(store.clone());
+        let executor = RouteActionExecutor::new(state, app_handle, bus.metrics());
+        let bus = Arc::new(RwLock::new(bus));
         Ok(Self {
             bus,
             store,
@@ -31,16 +37,46 @@ impl Dispatch {
         })
     }
 
-    /// Spawn the background retry worker.
+    /// Validate an event against its schema, publish it, and run the route
+    /// actions it matches durably. Returns the event ID.
+    pub async fn publish(&self, mut event: CloudEvent) -> Result<String, String> {
+        let actions = {
+            let mut bus = self.bus.write().await;
+            bus.check_schema(&mut event)?;
+            bus.publish(event.clone())
+        };
+        if !actions.is_empty() {
+            self.executor.execute_durable(&self.store, actions, &event);
+        }
+        Ok(event.id)
+    }
+
+    /// Throughput counters, subscriber queue depths, and pending route
+    /// actions.
+    pub async fn metrics(&self) -> BusMetricsSnapshot {
+        let (metrics, subscribers) = {
+            let bus = self.bus.read().await;
+            (bus.metrics(), bus.subscriber_queues())
+        };
+        let pending = self.store.pending_delivery_count().unwrap_or_else(|e| {
+            log::warn!("Failed to count pending deliveries: {}", e);
+            0
+        });
+        metrics.snapshot(subscribers, pending)
+    }
+
+    /// Spawn the background retry worker, which also redelivers
+    /// unacknowledged subscriber events.
     ///
     /// Uses `tauri::async_runtime::spawn` so this is safe to call from the
     /// Tauri `setup` closure (which runs on the main thread before a bare
     /// `tokio::spawn` would have runtime context).
     pub fn spawn_retry_worker(&self) {
         let store = self.store.clone();
+        let bus = self.bus.clone();
         let executor = self.executor.clone();
         tauri::async_runtime::spawn(async move {
-            retry_worker::run(store, executor).await;
+            retry_worker::run(store, bus, executor).await;
         });
     }
 }
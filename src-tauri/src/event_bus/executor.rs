@@ -1,11 +1,20 @@
 use regex::Regex;
 use serde_json::Value;
-use std::sync::OnceLock;
-use tauri::Emitter;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
 
 use super::cloud_event::CloudEvent;
-use super::routing::RouteAction;
+use super::metrics::BusMetrics;
+use super::routing::{PluginCommand, RouteAction, WebhookMode};
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::lifecycle_events::{self, LifecycleEvent};
+use crate::plugin_manager::storage::PluginStatus;
 use super::store::EventStore;
+use super::store::RetentionPolicy;
+use super::subscription::RedeliveryPolicy;
 use crate::AppState;
 
 /// Executes route actions triggered by event bus routing rules.
@@ -19,11 +28,16 @@ use crate::AppState;
 pub struct RouteActionExecutor {
     state: AppState,
     app_handle: tauri::AppHandle,
+    metrics: Arc<BusMetrics>,
 }
 
 impl RouteActionExecutor {
-    pub fn new(state: AppState, app_handle: tauri::AppHandle) -> Self {
-        Self { state, app_handle }
+    pub fn new(state: AppState, app_handle: tauri::AppHandle, metrics: Arc<BusMetrics>) -> Self {
+        Self {
+            state,
+            app_handle,
+            metrics,
+        }
     }
 
     /// Persist the event and create delivery rows for each action.
@@ -62,10 +76,11 @@ impl RouteActionExecutor {
         for action in actions {
             let state = self.state.clone();
             let app_handle = self.app_handle.clone();
+            let metrics = self.metrics.clone();
             let event = event.clone();
             tokio::spawn(async move {
                 let label = action_label(&action);
-                match execute_one(state, &app_handle, action, &event).await {
+                match timed(&metrics, execute_one(state, &app_handle, action, &event)).await {
                     Ok(()) => log::info!("Route action executed: {}", label),
                     Err(e) => log::error!("Route action failed: {} — {}", label, e),
                 }
@@ -73,16 +88,41 @@ impl RouteActionExecutor {
         }
     }
 
+    /// The configured redelivery policy for acknowledging subscribers.
+    pub async fn redelivery_policy(&self) -> RedeliveryPolicy {
+        self.state.read().await.settings.event_redelivery.clone()
+    }
+
+    /// The configured event log retention.
+    pub async fn retention_policy(&self) -> RetentionPolicy {
+        self.state.read().await.settings.event_retention.clone()
+    }
+
     /// Execute a single action and return the result. Used by the retry worker.
     pub async fn execute_single(
         &self,
         action: RouteAction,
         event: CloudEvent,
     ) -> Result<(), String> {
-        execute_one(self.state.clone(), &self.app_handle, action, &event).await
+        timed(
+            &self.metrics,
+            execute_one(self.state.clone(), &self.app_handle, action, &event),
+        )
+        .await
     }
 }
 
+/// Run an action, recording how long it took.
+async fn timed(
+    metrics: &BusMetrics,
+    action: impl std::future::Future<Output = Result<(), String>>,
+) -> Result<(), String> {
+    let started = Instant::now();
+    let result = action.await;
+    metrics.record_action(started.elapsed(), result.is_ok());
+    result
+}
+
 fn action_label(action: &RouteAction) -> String {
     match action {
         RouteAction::InvokePluginTool {
@@ -96,6 +136,10 @@ fn action_label(action: &RouteAction) -> String {
             ..
         } => format!("CallExtension({}.{})", extension_id, operation),
         RouteAction::EmitFrontend { channel } => format!("EmitFrontend({})", channel),
+        RouteAction::Webhook { url, .. } => format!("Webhook({})", url),
+        RouteAction::ControlPlugin { plugin_id, command } => {
+            format!("ControlPlugin({} {:?})", plugin_id, command)
+        }
     }
 }
 
@@ -141,6 +185,224 @@ async fn execute_one(
                 .emit(&channel, data)
                 .map_err(|e| format!("Tauri emit failed: {}", e))
         }
+        RouteAction::Webhook { url, headers, mode } => {
+            post_webhook(&url, &headers, mode, event).await
+        }
+        RouteAction::ControlPlugin { plugin_id, command } => {
+            let result = control_plugin(&state, app_handle, &plugin_id, command, event).await;
+            if let Some(audit) = app_handle.try_state::<AuditWriter>() {
+                let action = match command {
+                    PluginCommand::Start => "plugin.start",
+                    PluginCommand::Stop => "plugin.stop",
+                    PluginCommand::Restart => "plugin.restart",
+                };
+                let mut details = serde_json::json!({
+                    "trigger": "workflow",
+                    "event_id": event.id,
+                    "event_type": event.event_type,
+                });
+                if let Err(e) = &result {
+                    details["error"] = Value::String(e.clone());
+                }
+                audit.record(AuditEntry {
+                    actor: AuditActor::System,
+                    source_id: None,
+                    severity: AuditSeverity::Info,
+                    action: action.into(),
+                    subject: Some(plugin_id),
+                    result: if result.is_ok() {
+                        AuditResult::Success
+                    } else {
+                        AuditResult::Failure
+                    },
+                    details: Some(details),
+                });
+            }
+            result
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Plugin lifecycle
+// ---------------------------------------------------------------------------
+
+/// Start, stop, or restart a plugin, emitting the same lifecycle events as
+/// the UI does. Starting a running plugin or stopping a stopped one is a
+/// no-op, so a rule that fires repeatedly doesn't fail.
+async fn control_plugin(
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+    plugin_id: &str,
+    command: PluginCommand,
+    cause: &CloudEvent,
+) -> Result<(), String> {
+    let status = {
+        let mgr = state.read().await;
+        mgr.storage
+            .get(plugin_id)
+            .map(|p| p.status.clone())
+            .ok_or_else(|| format!("Plugin '{}' is not installed", plugin_id))?
+    };
+    let running = matches!(status, PluginStatus::Running);
+    let stop = running && matches!(command, PluginCommand::Stop | PluginCommand::Restart);
+    let start = matches!(command, PluginCommand::Restart)
+        || (matches!(command, PluginCommand::Start) && !running);
+
+    if stop {
+        set_running(state, app_handle, plugin_id, false, cause).await?;
+    }
+    if start {
+        set_running(state, app_handle, plugin_id, true, cause).await?;
+    }
+    Ok(())
+}
+
+async fn set_running(
+    state: &AppState,
+    app_handle: &tauri::AppHandle,
+    plugin_id: &str,
+    run: bool,
+    cause: &CloudEvent,
+) -> Result<(), String> {
+    let id = plugin_id.to_string();
+    lifecycle_events::emit_caused_by(
+        Some(app_handle),
+        if run {
+            LifecycleEvent::PluginStarting { plugin_id: id.clone() }
+        } else {
+            LifecycleEvent::PluginStopping { plugin_id: id.clone() }
+        },
+        Some(cause),
+    );
+
+    let mut mgr = state.write().await;
+    let result = if run {
+        mgr.start(plugin_id).await
+    } else {
+        mgr.stop(plugin_id).await
+    };
+    match result {
+        Ok(()) => {
+            mgr.notify_tools_changed();
+            let plugin = mgr.storage.get(plugin_id).cloned();
+            drop(mgr);
+            if let Some(plugin) = plugin {
+                lifecycle_events::emit_caused_by(
+                    Some(app_handle),
+                    if run {
+                        LifecycleEvent::PluginStarted { plugin }
+                    } else {
+                        LifecycleEvent::PluginStopped { plugin }
+                    },
+                    Some(cause),
+                );
+            }
+            Ok(())
+        }
+        Err(e) => {
+            drop(mgr);
+            lifecycle_events::emit_caused_by(
+                Some(app_handle),
+                LifecycleEvent::PluginError {
+                    plugin_id: id,
+                    action: if run { "starting" } else { "stopping" }.into(),
+                    message: e.to_string(),
+                },
+                Some(cause),
+            );
+            Err(e.to_string())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Webhooks
+// ---------------------------------------------------------------------------
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn webhook_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .user_agent(concat!("Nexus/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// POST an event per the CloudEvents HTTP binding. A non-2xx response is a
+/// failure, so the retry worker tries again.
+async fn post_webhook(
+    url: &str,
+    headers: &HashMap<String, String>,
+    mode: WebhookMode,
+    event: &CloudEvent,
+) -> Result<(), String> {
+    let mut request = webhook_client().post(url);
+    request = match mode {
+        WebhookMode::Structured => {
+            let body = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+            request
+                .header("content-type", "application/cloudevents+json")
+                .body(body)
+        }
+        WebhookMode::Binary => {
+            for (name, value) in binary_headers(event) {
+                request = request.header(name, value);
+            }
+            request
+                .header("content-type", &event.datacontenttype)
+                .body(binary_body(event))
+        }
+    };
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Webhook request to {} failed: {}", url, e))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("Webhook {} returned {}", url, status))
+    }
+}
+
+/// `ce-*` headers carrying the event's context attributes in binary mode.
+fn binary_headers(event: &CloudEvent) -> Vec<(String, String)> {
+    let mut headers = vec![
+        ("ce-specversion".to_string(), event.specversion.clone()),
+        ("ce-id".to_string(), event.id.clone()),
+        ("ce-source".to_string(), event.source.clone()),
+        ("ce-type".to_string(), event.event_type.clone()),
+        ("ce-time".to_string(), event.time.to_rfc3339()),
+    ];
+    if let Some(subject) = &event.subject {
+        headers.push(("ce-subject".to_string(), subject.clone()));
+    }
+    for (name, value) in &event.extensions {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        headers.push((format!("ce-{}", name.to_ascii_lowercase()), value));
+    }
+    headers
+}
+
+/// The binary-mode body: string data as-is unless the content type is
+/// JSON, anything else serialized as JSON.
+fn binary_body(event: &CloudEvent) -> Vec<u8> {
+    match &event.data {
+        Value::String(s) if !event.datacontenttype.contains("json") => s.clone().into_bytes(),
+        Value::Null => Vec::new(),
+        other => serde_json::to_vec(other).unwrap_or_default(),
     }
 }
 
@@ -242,7 +504,8 @@ fn resolve_path(path: &str, event: &CloudEvent) -> Value {
             }
             current.clone()
         }
-        _ => Value::Null,
+        // Extension attributes, e.g. `{{event.correlationid}}`
+        other => event.extensions.get(other).cloned().unwrap_or(Value::Null),
     }
 }
 
@@ -376,6 +639,25 @@ mod tests {
         assert_eq!(result["nothing"], Value::Null);
     }
 
+    #[test]
+    fn binary_mode_maps_attributes_to_headers() {
+        let mut event = test_event();
+        event.extensions.insert("traceparent".into(), json!("00-abc-def-01"));
+        let headers: HashMap<String, String> = binary_headers(&event).into_iter().collect();
+        assert_eq!(headers["ce-specversion"], "1.0");
+        assert_eq!(headers["ce-id"], event.id);
+        assert_eq!(headers["ce-type"], "com.github.issues.opened");
+        assert_eq!(headers["ce-source"], "nexus://extension/webhook-receiver");
+        assert_eq!(headers["ce-subject"], "wh_abc123");
+        assert_eq!(headers["ce-traceparent"], "00-abc-def-01");
+
+        let body: Value = serde_json::from_slice(&binary_body(&event)).unwrap();
+        assert_eq!(body, event.data);
+        event.datacontenttype = "text/plain".into();
+        event.data = json!("hello");
+        assert_eq!(binary_body(&event), b"hello");
+    }
+
     #[test]
     fn whitespace_in_braces_tolerated() {
         let event = test_event();
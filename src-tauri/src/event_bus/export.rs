@@ -0,0 +1,377 @@
+//! One-way export of bus events to an external pipeline: a NATS subject, or
+//! a Kafka topic through a Kafka REST Proxy. Events are sent in batches as
+//! structured CloudEvents JSON, and a failed batch is retried with backoff
+//! before it is dropped.
+
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::cloud_event::CloudEvent;
+use super::subscription::{parse_pattern, SubscriberKind};
+use super::Dispatch;
+
+/// What to export and where. Stored in settings as `event_export`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportConfig {
+    pub enabled: bool,
+    /// Event type glob to export.
+    pub event_type: String,
+    /// Event source glob to export. All sources when absent.
+    pub source: Option<String>,
+    pub sink: ExportSink,
+    /// Most events per batch.
+    pub batch_size: usize,
+    /// Longest an event waits in a partial batch before it is sent.
+    pub flush_interval_ms: u64,
+    /// Attempts after the first before a failed batch is dropped.
+    pub max_retries: u32,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            event_type: "*".to_string(),
+            source: None,
+            sink: ExportSink::Nats {
+                url: "nats://localhost:4222".to_string(),
+                subject: "nexus.{type}".to_string(),
+                token: None,
+            },
+            batch_size: 100,
+            flush_interval_ms: 1000,
+            max_retries: 5,
+        }
+    }
+}
+
+/// Destination of exported events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportSink {
+    /// Publish each event to a NATS subject. `{type}` in the subject becomes
+    /// the event type.
+    Nats {
+        url: String,
+        subject: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+    },
+    /// Produce each batch to a Kafka topic through a Kafka REST Proxy
+    /// (`POST {rest_url}/topics/{topic}`), keyed by event source.
+    Kafka { rest_url: String, topic: String },
+}
+
+impl ExportConfig {
+    const MAX_BATCH_SIZE: usize = 10_000;
+
+    pub fn validate(&self) -> Result<(), String> {
+        parse_pattern(&self.event_type)?;
+        if let Some(source) = &self.source {
+            parse_pattern(source)?;
+        }
+        if self.batch_size == 0 || self.batch_size > Self::MAX_BATCH_SIZE {
+            return Err(format!(
+                "batch_size must be between 1 and {}",
+                Self::MAX_BATCH_SIZE
+            ));
+        }
+        if self.flush_interval_ms == 0 {
+            return Err("flush_interval_ms must be at least 1".to_string());
+        }
+        match &self.sink {
+            ExportSink::Nats { url, subject, .. } => {
+                if url.trim().is_empty() {
+                    return Err("NATS server URL is empty".to_string());
+                }
+                if subject.is_empty() || subject.contains(['*', '>', ' ']) {
+                    return Err(format!("Invalid NATS subject '{}'", subject));
+                }
+            }
+            ExportSink::Kafka { rest_url, topic } => {
+                let parsed = url::Url::parse(rest_url)
+                    .map_err(|e| format!("Invalid Kafka REST Proxy URL: {}", e))?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err("Kafka REST Proxy URL must be http or https".to_string());
+                }
+                if topic.is_empty() || topic.contains('/') {
+                    return Err(format!("Invalid Kafka topic '{}'", topic));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Counters shown in settings.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExportStatus {
+    pub running: bool,
+    /// Events delivered since the exporter started.
+    pub exported: u64,
+    /// Events in batches that failed on every attempt.
+    pub dropped: u64,
+    pub last_error: Option<String>,
+}
+
+/// Handle to the running exporter. Managed as Tauri state.
+pub struct EventExporter {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+    status: Arc<RwLock<ExportStatus>>,
+}
+
+impl EventExporter {
+    pub fn new() -> Self {
+        Self {
+            task: Mutex::new(None),
+            status: Arc::new(RwLock::new(ExportStatus::default())),
+        }
+    }
+
+    pub fn status(&self) -> ExportStatus {
+        self.status.read().expect("export status lock poisoned").clone()
+    }
+
+    /// Stop the running exporter, if any, and start it again with `config`
+    /// when enabled. Events in an unsent batch are lost.
+    pub fn apply(&self, config: ExportConfig, dispatch: Dispatch) {
+        let mut task = self.task.lock().expect("export task lock poisoned");
+        if let Some(running) = task.take() {
+            running.abort();
+        }
+        *self.status.write().expect("export status lock poisoned") = ExportStatus {
+            running: config.enabled,
+            ..Default::default()
+        };
+        if config.enabled {
+            let status = self.status.clone();
+            *task = Some(tauri::async_runtime::spawn(run(config, dispatch, status)));
+        }
+    }
+}
+
+impl Default for EventExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Connected sink.
+enum Sink {
+    Nats {
+        client: async_nats::Client,
+        subject: String,
+    },
+    Kafka {
+        http: reqwest::Client,
+        endpoint: String,
+    },
+}
+
+impl Sink {
+    async fn connect(sink: &ExportSink) -> Result<Self, String> {
+        match sink {
+            ExportSink::Nats {
+                url,
+                subject,
+                token,
+            } => {
+                let mut options = async_nats::ConnectOptions::new();
+                if let Some(token) = token {
+                    options = options.token(token.clone());
+                }
+                let client = options
+                    .connect(url.as_str())
+                    .await
+                    .map_err(|e| format!("NATS connect failed: {}", e))?;
+                Ok(Sink::Nats {
+                    client,
+                    subject: subject.clone(),
+                })
+            }
+            ExportSink::Kafka { rest_url, topic } => Ok(Sink::Kafka {
+                http: reqwest::Client::builder()
+                    .timeout(Duration::from_secs(30))
+                    .build()
+                    .map_err(|e| e.to_string())?,
+                endpoint: format!("{}/topics/{}", rest_url.trim_end_matches('/'), topic),
+            }),
+        }
+    }
+
+    async fn send(&self, batch: &[CloudEvent]) -> Result<(), String> {
+        match self {
+            Sink::Nats { client, subject } => {
+                for event in batch {
+                    let payload = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+                    client
+                        .publish(nats_subject(subject, event), payload.into())
+                        .await
+                        .map_err(|e| format!("NATS publish failed: {}", e))?;
+                }
+                client
+                    .flush()
+                    .await
+                    .map_err(|e| format!("NATS flush failed: {}", e))
+            }
+            Sink::Kafka { http, endpoint } => {
+                let records: Vec<_> = batch
+                    .iter()
+                    .map(|e| json!({ "key": e.source, "value": e }))
+                    .collect();
+                let resp = http
+                    .post(endpoint)
+                    .header("content-type", "application/vnd.kafka.json.v2+json")
+                    .json(&json!({ "records": records }))
+                    .send()
+                    .await
+                    .map_err(|e| format!("Kafka REST Proxy request failed: {}", e))?;
+                if !resp.status().is_success() {
+                    return Err(format!("Kafka REST Proxy returned {}", resp.status()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Subject an event is published to.
+fn nats_subject(template: &str, event: &CloudEvent) -> String {
+    template.replace("{type}", &event.event_type)
+}
+
+/// Delay before retry `attempt` (1-based): 1s, 2s, 4s, ... capped at a minute.
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs((1u64 << attempt.saturating_sub(1).min(6)).min(60))
+}
+
+fn update(status: &RwLock<ExportStatus>, f: impl FnOnce(&mut ExportStatus)) {
+    f(&mut status.write().expect("export status lock poisoned"));
+}
+
+async fn run(config: ExportConfig, dispatch: Dispatch, status: Arc<RwLock<ExportStatus>>) {
+    let subscribed = dispatch.bus.write().await.subscribe(
+        &config.event_type,
+        config.source.as_deref(),
+        SubscriberKind::Internal,
+    );
+    let mut rx = match subscribed {
+        Ok((_sub_id, rx)) => rx,
+        Err(e) => {
+            update(&status, |s| {
+                s.running = false;
+                s.last_error = Some(e);
+            });
+            return;
+        }
+    };
+
+    let sink = loop {
+        match Sink::connect(&config.sink).await {
+            Ok(sink) => break sink,
+            Err(e) => {
+                log::warn!("Event export: {}", e);
+                update(&status, |s| s.last_error = Some(e));
+                tokio::time::sleep(Duration::from_secs(10)).await;
+            }
+        }
+    };
+
+    let mut batch = Vec::with_capacity(config.batch_size);
+    let mut flush_tick = tokio::time::interval(Duration::from_millis(config.flush_interval_ms));
+    loop {
+        let full = tokio::select! {
+            received = rx.recv() => match received {
+                Some(event) => {
+                    batch.push(event);
+                    batch.len() >= config.batch_size
+                }
+                None => return,
+            },
+            _ = flush_tick.tick() => !batch.is_empty(),
+        };
+        if full {
+            send_with_retry(&sink, &batch, config.max_retries, &status).await;
+            batch.clear();
+        }
+    }
+}
+
+/// Send a batch, retrying with backoff. Events published meanwhile queue up
+/// in the subscription channel.
+async fn send_with_retry(
+    sink: &Sink,
+    batch: &[CloudEvent],
+    max_retries: u32,
+    status: &RwLock<ExportStatus>,
+) {
+    let mut attempt = 0;
+    loop {
+        match sink.send(batch).await {
+            Ok(()) => {
+                update(status, |s| s.exported += batch.len() as u64);
+                return;
+            }
+            Err(e) if attempt < max_retries => {
+                attempt += 1;
+                log::warn!("Event export: attempt {} failed: {}", attempt, e);
+                update(status, |s| s.last_error = Some(e));
+                tokio::time::sleep(retry_delay(attempt)).await;
+            }
+            Err(e) => {
+                log::error!("Event export: dropping {} events: {}", batch.len(), e);
+                update(status, |s| {
+                    s.dropped += batch.len() as u64;
+                    s.last_error = Some(e);
+                });
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_sinks() {
+        assert!(ExportConfig::default().validate().is_ok());
+
+        let kafka = ExportConfig {
+            sink: ExportSink::Kafka {
+                rest_url: "http://localhost:8082".into(),
+                topic: "nexus-events".into(),
+            },
+            ..Default::default()
+        };
+        assert!(kafka.validate().is_ok());
+
+        let wildcard_subject = ExportConfig {
+            sink: ExportSink::Nats {
+                url: "nats://localhost:4222".into(),
+                subject: "nexus.>".into(),
+                token: None,
+            },
+            ..Default::default()
+        };
+        assert!(wildcard_subject.validate().is_err());
+
+        let empty_batch = ExportConfig {
+            batch_size: 0,
+            ..Default::default()
+        };
+        assert!(empty_batch.validate().is_err());
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_a_minute() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(3), Duration::from_secs(4));
+        assert_eq!(retry_delay(20), Duration::from_secs(60));
+    }
+}
@@ -36,6 +36,37 @@ impl EventLog {
         self.events.push_back(event);
     }
 
+    /// Change the capacity, evicting the oldest events that no longer fit.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        let excess = self.events.len().saturating_sub(capacity);
+        self.events.drain(..excess);
+        self.capacity = capacity;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Evict events published before `cutoff`. Returns how many were dropped.
+    pub fn prune_before(&mut self, cutoff: DateTime<Utc>) -> usize {
+        let before = self.events.len();
+        self.events.retain(|e| e.time >= cutoff);
+        before - self.events.len()
+    }
+
+    /// The logged event with this ID, if it hasn't been evicted.
+    pub fn get(&self, id: &str) -> Option<&CloudEvent> {
+        self.events.iter().rev().find(|e| e.id == id)
+    }
+
+    /// Logged events sharing a correlation ID, oldest first.
+    pub fn correlated(&self, correlation_id: &str) -> Vec<&CloudEvent> {
+        self.events
+            .iter()
+            .filter(|e| e.correlation_id() == Some(correlation_id))
+            .collect()
+    }
+
     /// Query events matching the given filters.
     pub fn query(&self, filter: &EventLogQuery) -> Vec<&CloudEvent> {
         let mut results: Vec<&CloudEvent> = self
@@ -146,6 +177,21 @@ mod tests {
         assert_eq!(all[2].event_type, "b");
     }
 
+    #[test]
+    fn capacity_and_age_limits_evict_oldest() {
+        let mut log = EventLog::with_capacity(5);
+        for t in ["a", "b", "c", "d"] {
+            log.push(make_event(t, "s"));
+        }
+        log.set_capacity(2);
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.query(&EventLogQuery::default())[1].event_type, "c");
+
+        assert_eq!(log.prune_before(Utc::now() - chrono::Duration::hours(1)), 0);
+        assert_eq!(log.prune_before(Utc::now() + chrono::Duration::hours(1)), 2);
+        assert!(log.is_empty());
+    }
+
     #[test]
     fn query_filters_by_type() {
         let mut log = EventLog::new();
@@ -0,0 +1,262 @@
+//! Throughput counters for the event bus and route action executor, so users
+//! can tell when an automation pipeline is backing up. Exposed through the
+//! Host API as JSON and in the Prometheus text format.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Upper bounds of the action latency histogram buckets, in milliseconds.
+const LATENCY_BUCKETS_MS: [u64; 8] = [10, 50, 100, 250, 500, 1_000, 5_000, 30_000];
+
+/// Counters shared by the bus and the executor.
+#[derive(Default)]
+pub struct BusMetrics {
+    published: AtomicU64,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    actions: Mutex<ActionLatency>,
+}
+
+#[derive(Default)]
+struct ActionLatency {
+    executed: u64,
+    failed: u64,
+    total: Duration,
+    max: Duration,
+    /// Cumulative count per bucket in [`LATENCY_BUCKETS_MS`].
+    buckets: [u64; LATENCY_BUCKETS_MS.len()],
+}
+
+impl BusMetrics {
+    pub fn record_published(&self) {
+        self.published.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_delivered(&self) {
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// An event matched a subscription whose receiver was gone.
+    pub fn record_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A route action ran for `elapsed`, successfully or not.
+    pub fn record_action(&self, elapsed: Duration, ok: bool) {
+        let mut actions = self.actions.lock().unwrap_or_else(|e| e.into_inner());
+        actions.executed += 1;
+        if !ok {
+            actions.failed += 1;
+        }
+        actions.total += elapsed;
+        actions.max = actions.max.max(elapsed);
+        let ms = elapsed.as_millis() as u64;
+        for (bucket, bound) in actions.buckets.iter_mut().zip(LATENCY_BUCKETS_MS) {
+            if ms <= bound {
+                *bucket += 1;
+            }
+        }
+    }
+
+    /// Current counters, with the subscriber queues and pending deliveries
+    /// supplied by the caller.
+    pub fn snapshot(
+        &self,
+        subscribers: Vec<SubscriberQueue>,
+        pending_deliveries: usize,
+    ) -> BusMetricsSnapshot {
+        let actions = self.actions.lock().unwrap_or_else(|e| e.into_inner());
+        let mean_ms = if actions.executed == 0 {
+            0.0
+        } else {
+            actions.total.as_secs_f64() * 1000.0 / actions.executed as f64
+        };
+        BusMetricsSnapshot {
+            published: self.published.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            subscribers,
+            pending_deliveries,
+            executor: ExecutorLatency {
+                executed: actions.executed,
+                failed: actions.failed,
+                mean_ms,
+                max_ms: actions.max.as_secs_f64() * 1000.0,
+                total_secs: actions.total.as_secs_f64(),
+                buckets: LATENCY_BUCKETS_MS
+                    .iter()
+                    .zip(actions.buckets)
+                    .map(|(&le_ms, count)| LatencyBucket { le_ms, count })
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// Events waiting in one subscription's queue.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SubscriberQueue {
+    pub subscription_id: String,
+    /// `extension:<id>`, `plugin:<id>`, `frontend:<channel>`, or `internal`.
+    pub subscriber: String,
+    pub queue_depth: usize,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LatencyBucket {
+    pub le_ms: u64,
+    /// Actions that took at most `le_ms`.
+    pub count: u64,
+}
+
+/// How long route actions take to run.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ExecutorLatency {
+    pub executed: u64,
+    pub failed: u64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+    pub total_secs: f64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BusMetricsSnapshot {
+    /// Events published since startup.
+    pub published: u64,
+    /// Deliveries to subscribers since startup.
+    pub delivered: u64,
+    /// Deliveries that failed because the subscriber had disconnected.
+    /// Durable subscribers get these events when they resubscribe.
+    pub dropped: u64,
+    pub subscribers: Vec<SubscriberQueue>,
+    /// Route actions waiting in the durable store to run or be retried.
+    pub pending_deliveries: usize,
+    pub executor: ExecutorLatency,
+}
+
+impl BusMetricsSnapshot {
+    /// Render in the Prometheus text exposition format.
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut counter = |name: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}\n# TYPE {name} counter\n{name} {value}");
+        };
+        counter("nexus_events_published_total", "Events published on the bus.", self.published);
+        counter(
+            "nexus_events_delivered_total",
+            "Events delivered to subscribers.",
+            self.delivered,
+        );
+        counter(
+            "nexus_events_dropped_total",
+            "Deliveries that failed because the subscriber had disconnected.",
+            self.dropped,
+        );
+        counter(
+            "nexus_route_actions_failed_total",
+            "Route actions that failed.",
+            self.executor.failed,
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP nexus_event_pending_deliveries Route actions waiting to run.\n\
+             # TYPE nexus_event_pending_deliveries gauge\n\
+             nexus_event_pending_deliveries {}",
+            self.pending_deliveries
+        );
+
+        let _ = writeln!(
+            out,
+            "# HELP nexus_event_subscriber_queue_depth Events waiting in a subscription's queue.\n\
+             # TYPE nexus_event_subscriber_queue_depth gauge"
+        );
+        for sub in &self.subscribers {
+            let _ = writeln!(
+                out,
+                "nexus_event_subscriber_queue_depth{{subscription=\"{}\",subscriber=\"{}\"}} {}",
+                label_value(&sub.subscription_id),
+                label_value(&sub.subscriber),
+                sub.queue_depth
+            );
+        }
+
+        let name = "nexus_route_action_duration_seconds";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Time taken to run route actions.\n# TYPE {name} histogram"
+        );
+        for bucket in &self.executor.buckets {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{le=\"{}\"}} {}",
+                bucket.le_ms as f64 / 1000.0,
+                bucket.count
+            );
+        }
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", self.executor.executed);
+        let _ = writeln!(out, "{name}_sum {}", self.executor.total_secs);
+        let _ = writeln!(out, "{name}_count {}", self.executor.executed);
+        out
+    }
+}
+
+/// Escape a Prometheus label value.
+fn label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_latency_buckets() {
+        let metrics = BusMetrics::default();
+        metrics.record_action(Duration::from_millis(5), true);
+        metrics.record_action(Duration::from_millis(300), false);
+        metrics.record_published();
+
+        let snapshot = metrics.snapshot(Vec::new(), 0);
+        assert_eq!(snapshot.published, 1);
+        assert_eq!(snapshot.executor.executed, 2);
+        assert_eq!(snapshot.executor.failed, 1);
+        let counts: Vec<u64> = snapshot.executor.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, [1, 1, 1, 1, 2, 2, 2, 2]);
+        assert!((snapshot.executor.max_ms - 300.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn renders_prometheus_text() {
+        let metrics = BusMetrics::default();
+        metrics.record_delivered();
+        metrics.record_action(Duration::from_millis(20), true);
+        let text = metrics
+            .snapshot(
+                vec![SubscriberQueue {
+                    subscription_id: "sub_0".into(),
+                    subscriber: "plugin:\"odd\"".into(),
+                    queue_depth: 3,
+                }],
+                4,
+            )
+            .to_prometheus();
+
+        assert!(text.contains("nexus_events_delivered_total 1\n"));
+        assert!(text.contains("nexus_event_pending_deliveries 4\n"));
+        assert!(text.contains(
+            "nexus_event_subscriber_queue_depth{subscription=\"sub_0\",subscriber=\"plugin:\\\"odd\\\"\"} 3\n"
+        ));
+        assert!(text.contains("nexus_route_action_duration_seconds_bucket{le=\"0.05\"} 1\n"));
+        assert!(text.contains("nexus_route_action_duration_seconds_count 1\n"));
+    }
+}
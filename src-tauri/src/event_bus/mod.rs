@@ -1,23 +1,40 @@
 pub mod cloud_event;
 pub mod dispatch;
 pub mod executor;
+pub mod export;
 pub mod log;
+pub mod metrics;
+pub mod mqtt;
+pub mod predicate;
+pub mod quota;
 pub mod retry_worker;
 pub mod routing;
+pub mod schema;
 pub mod store;
 pub mod subscription;
+pub mod timer;
 
 pub use dispatch::Dispatch;
 
 use std::path::Path;
 use std::sync::Arc;
 
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::RwLock;
 
-use cloud_event::CloudEvent;
+use cloud_event::{CloudEvent, CORRELATION_ATTR};
 use log::{EventLog, EventLogQuery};
+use metrics::{BusMetrics, SubscriberQueue};
+use predicate::Predicate;
+use quota::{PublishQuota, QuotaExceeded, QuotaTracker};
 use routing::{RouteAction, RoutingRule, RoutingRuleStore, RoutingRuleUpdate};
-use subscription::{parse_pattern, SubscriberKind, Subscription};
+use schema::{EventSchema, SchemaMode, SchemaRegistry};
+use store::RetentionPolicy;
+use subscription::{
+    event_channel, parse_pattern, EventReceiver, RedeliveryPolicy, SubscriberKind, Subscription,
+};
+
+/// Most events a causal chain query returns.
+const MAX_CHAIN_LEN: usize = 1000;
 
 /// Thread-safe shared handle to the event bus.
 pub type SharedEventBus = Arc<RwLock<EventBus>>;
@@ -31,6 +48,16 @@ pub struct EventBus {
     next_sub_id: u64,
     event_log: EventLog,
     routing_rules: RoutingRuleStore,
+    schemas: SchemaRegistry,
+    /// Without a store, every subscription is in-memory only and nothing
+    /// can be replayed.
+    store: Option<SharedEventStore>,
+    /// Ack timeout and backoff for subscribers that acknowledge events.
+    redelivery: RedeliveryPolicy,
+    /// Rate and size limits for plugin publishers.
+    quota: QuotaTracker,
+    /// Throughput counters, shared with the route action executor.
+    metrics: Arc<BusMetrics>,
 }
 
 impl EventBus {
@@ -41,20 +68,82 @@ impl EventBus {
             next_sub_id: 0,
             event_log: EventLog::new(),
             routing_rules: RoutingRuleStore::load(data_dir),
+            schemas: SchemaRegistry::load(data_dir),
+            store: None,
+            redelivery: RedeliveryPolicy::default(),
+            quota: QuotaTracker::new(PublishQuota::default()),
+            metrics: Arc::new(BusMetrics::default()),
+        }
+    }
+
+    pub fn set_redelivery_policy(&mut self, policy: RedeliveryPolicy) {
+        self.redelivery = policy;
+    }
+
+    pub fn set_publish_quota(&mut self, quota: PublishQuota) {
+        self.quota.set_quota(quota);
+    }
+
+    /// Count an event against its publisher's quota before it is published.
+    /// See [`QuotaTracker::check`].
+    pub fn check_quota(&self, publisher: &str, event: &CloudEvent) -> Result<(), QuotaExceeded> {
+        self.quota.check(publisher, event)
+    }
+
+    /// Attach the durable store. Called once at startup. From then on every
+    /// published event is persisted, so durable subscribers get the events
+    /// they missed while offline and history can be replayed.
+    pub fn attach_store(&mut self, store: SharedEventStore) {
+        match store.list_subscriptions() {
+            Ok(saved) if !saved.is_empty() => {
+                ::log::info!("{} durable event subscriptions awaiting their subscribers", saved.len());
+            }
+            Ok(_) => {}
+            Err(e) => ::log::error!("Failed to load durable subscriptions: {}", e),
         }
+        self.store = Some(store);
     }
 
     /// Publish a CloudEvent: log it, fan out to matching subscribers, return matching routing rules.
     ///
     /// Returns the list of route actions that should be executed (caller spawns tasks).
-    pub fn publish(&mut self, event: CloudEvent) -> Vec<RouteAction> {
+    pub fn publish(&mut self, mut event: CloudEvent) -> Vec<RouteAction> {
+        self.stamp_correlation(&mut event);
+        self.metrics.record_published();
+
         // Log the event
         self.event_log.push(event.clone());
 
-        // Fan out to subscribers, cleaning up dead channels
+        // Persist it for durable subscribers that are offline and for replay
+        let position = self.persist(&event);
+
+        // Fan out to subscribers, cleaning up dead channels. Durable
+        // subscribers' cursors move past events they were sent, and events
+        // sent to acknowledging subscribers wait for their ack.
+        let store = &self.store;
+        let metrics = &self.metrics;
+        let ack_deadline = chrono::Utc::now() + self.redelivery.backoff(1);
         self.subscriptions.retain(|sub| {
             if sub.matches(&event) {
-                sub.try_send(&event)
+                let sent = sub.try_send(&event);
+                if sent {
+                    metrics.record_delivered();
+                } else {
+                    metrics.record_dropped();
+                }
+                if let (true, Some(key), Some(pos), Some(store)) =
+                    (sent, &sub.durable_key, position, store)
+                {
+                    if let Err(e) = store.advance_subscription(key, pos) {
+                        ::log::error!("Failed to advance subscription {}: {}", key, e);
+                    }
+                    if sub.ack {
+                        if let Err(e) = store.track_ack(key, pos, &event.id, ack_deadline) {
+                            ::log::error!("Failed to track ack for {}: {}", key, e);
+                        }
+                    }
+                }
+                sent
             } else {
                 // Keep non-matching subscribers — they're still alive
                 true
@@ -69,34 +158,334 @@ impl EventBus {
             .collect()
     }
 
+    /// Give an event a correlation ID if it has none: its cause's, when the
+    /// publisher named one with `causationid`, or else its own ID, starting a
+    /// new chain.
+    fn stamp_correlation(&self, event: &mut CloudEvent) {
+        if event.correlation_id().is_some() {
+            return;
+        }
+        let correlation = match event.causation_id() {
+            Some(cause_id) => self
+                .find_event(cause_id)
+                .and_then(|cause| cause.correlation_id().map(str::to_string))
+                .unwrap_or_else(|| cause_id.to_string()),
+            None => event.id.clone(),
+        };
+        event
+            .extensions
+            .insert(CORRELATION_ATTR.to_string(), serde_json::Value::String(correlation));
+    }
+
+    /// A published event by ID, from the log or else the store.
+    fn find_event(&self, id: &str) -> Option<CloudEvent> {
+        if let Some(event) = self.event_log.get(id) {
+            return Some(event.clone());
+        }
+        let store = self.store.as_ref()?;
+        match store.event_by_id(id) {
+            Ok(event) => event,
+            Err(e) => {
+                ::log::warn!("Failed to look up event {}: {}", id, e);
+                None
+            }
+        }
+    }
+
+    /// Every event in the causal chain an event belongs to — its root
+    /// cause and everything that followed from it — oldest first.
+    pub fn causal_chain(&self, event_id: &str) -> Result<Vec<CloudEvent>, String> {
+        let event = self
+            .find_event(event_id)
+            .ok_or_else(|| format!("Event '{}' not found", event_id))?;
+        let correlation = event.correlation_id().unwrap_or(&event.id);
+        let chain = match &self.store {
+            Some(store) => store.events_by_correlation(correlation, MAX_CHAIN_LEN)?,
+            None => self
+                .event_log
+                .correlated(correlation)
+                .into_iter()
+                .take(MAX_CHAIN_LEN)
+                .cloned()
+                .collect(),
+        };
+        // Events published before correlation IDs existed have none
+        Ok(if chain.is_empty() { vec![event] } else { chain })
+    }
+
     /// Register a new subscription. Returns the subscription ID and a receiver for events.
+    ///
+    /// Extension and plugin subscriptions are durable once a store is
+    /// attached: subscribing again with the same patterns first delivers the
+    /// matching events published since the subscriber last received one.
     pub fn subscribe(
         &mut self,
         type_pattern: &str,
         source_pattern: Option<&str>,
         kind: SubscriberKind,
-    ) -> Result<(String, mpsc::UnboundedReceiver<CloudEvent>), String> {
+    ) -> Result<(String, EventReceiver), String> {
+        self.subscribe_with_ack(type_pattern, source_pattern, None, kind, false)
+    }
+
+    /// Register a subscription whose subscriber acknowledges each event with
+    /// [`EventBus::ack`] when `ack` is set. Unacknowledged events are
+    /// redelivered with backoff by the retry worker. Requires a durable
+    /// (extension or plugin) subscription; ignored otherwise.
+    ///
+    /// With a `predicate`, only events whose data satisfies it are delivered.
+    pub fn subscribe_with_ack(
+        &mut self,
+        type_pattern: &str,
+        source_pattern: Option<&str>,
+        predicate: Option<Predicate>,
+        kind: SubscriberKind,
+        ack: bool,
+    ) -> Result<(String, EventReceiver), String> {
         let tp = parse_pattern(type_pattern)?;
         let sp = source_pattern.map(parse_pattern).transpose()?;
 
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = event_channel();
         let sub_id = format!("sub_{}", self.next_sub_id);
         self.next_sub_id += 1;
 
-        self.subscriptions.push(Subscription {
+        let durable_key = self
+            .store
+            .as_ref()
+            .and_then(|_| kind.durable_key(type_pattern, source_pattern, predicate.as_ref()));
+        let sub = Subscription {
             id: sub_id.clone(),
             type_pattern: tp,
             source_pattern: sp,
+            predicate,
             kind,
             sender: tx,
-        });
+            ack: ack && durable_key.is_some(),
+            durable_key,
+        };
+        self.resume_durable(&sub);
+        self.subscriptions.push(sub);
 
         Ok((sub_id, rx))
     }
 
-    /// Remove a subscription by ID.
+    /// Remove a subscription by ID. An explicit unsubscribe also drops the
+    /// durable record once no connected subscription shares it.
     pub fn unsubscribe(&mut self, sub_id: &str) {
-        self.subscriptions.retain(|s| s.id != sub_id);
+        let Some(pos) = self.subscriptions.iter().position(|s| s.id == sub_id) else {
+            return;
+        };
+        let removed = self.subscriptions.remove(pos);
+        if let Some(key) = removed.durable_key {
+            if !self.subscriptions.iter().any(|s| s.durable_key.as_ref() == Some(&key)) {
+                self.forget_durable(|k| k == key);
+            }
+        }
+    }
+
+    /// Acknowledge an event for all of a subscriber's subscriptions. Returns
+    /// how many pending deliveries were cleared.
+    pub fn ack(&self, kind: &SubscriberKind, event_id: &str) -> Result<usize, String> {
+        match (&self.store, kind.durable_prefix()) {
+            (Some(store), Some(prefix)) => store.ack(&prefix, event_id),
+            _ => Ok(0),
+        }
+    }
+
+    /// Resend unacknowledged events whose ack timeout has passed to their
+    /// connected subscribers, backing off between attempts. Events of
+    /// subscribers that are offline wait until they reconnect; events that
+    /// reach the attempt limit are dropped. Returns how many were resent.
+    pub fn redeliver_unacked(&self) -> usize {
+        let Some(store) = &self.store else {
+            return 0;
+        };
+
+        let mut resent = 0;
+        for sub in self.subscriptions.iter().filter(|s| s.ack) {
+            let Some(key) = &sub.durable_key else {
+                continue;
+            };
+            let due = match store.due_acks(key, 100) {
+                Ok(due) => due,
+                Err(e) => {
+                    ::log::error!("Failed to load unacknowledged events for {}: {}", key, e);
+                    continue;
+                }
+            };
+            resent += self.redeliver_to(store, sub, due);
+        }
+        resent
+    }
+
+    fn redeliver_to(
+        &self,
+        store: &store::EventStore,
+        sub: &Subscription,
+        due: Vec<store::PendingAck>,
+    ) -> usize {
+        let mut resent = 0;
+        for pending in due {
+            if pending.attempts >= self.redelivery.max_attempts {
+                ::log::warn!(
+                    "Giving up on event {} for {} after {} attempts",
+                    pending.event_rowid,
+                    pending.key,
+                    pending.attempts
+                );
+                let _ = store.drop_ack(pending.id);
+                continue;
+            }
+            let result = match store.event_at(pending.event_rowid) {
+                // Expired from the store
+                Ok(None) => store.drop_ack(pending.id),
+                Ok(Some(event)) => {
+                    if sub.try_send(&event) {
+                        resent += 1;
+                    }
+                    let attempts = pending.attempts + 1;
+                    let next = chrono::Utc::now() + self.redelivery.backoff(attempts);
+                    store.reschedule_ack(pending.id, attempts, next)
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                ::log::error!("Redelivery to {} failed: {}", pending.key, e);
+            }
+        }
+        resent
+    }
+
+    /// Drop every durable subscription of a subscriber, e.g. when the plugin
+    /// or extension is removed. Connected subscriptions stay live.
+    pub fn forget_subscriber(&mut self, kind: &SubscriberKind) {
+        if let Some(prefix) = kind.durable_prefix() {
+            self.forget_durable(|k| k.starts_with(&prefix));
+        }
+    }
+
+    fn forget_durable(&self, matches: impl Fn(&str) -> bool) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let saved = match store.list_subscriptions() {
+            Ok(saved) => saved,
+            Err(e) => {
+                ::log::error!("Failed to load durable subscriptions: {}", e);
+                return;
+            }
+        };
+        for sub in saved.into_iter().filter(|s| matches(&s.key)) {
+            if let Err(e) = store.delete_subscription(&sub.key) {
+                ::log::error!("Failed to delete subscription {}: {}", sub.key, e);
+            }
+        }
+    }
+
+    /// Persist an event and return its position in the store.
+    fn persist(&self, event: &CloudEvent) -> Option<i64> {
+        self.store
+            .as_ref()?
+            .persist_event(event)
+            .map_err(|e| ::log::error!("Failed to persist event {}: {}", event.id, e))
+            .ok()
+    }
+
+    /// Save a durable subscription (keeping the cursor of an existing one)
+    /// and send the events stored since its cursor — those published while
+    /// the subscriber was offline.
+    fn resume_durable(&self, sub: &Subscription) {
+        let (Some(store), Some(key)) = (&self.store, &sub.durable_key) else {
+            return;
+        };
+        let cursor = match store.save_subscription(
+            key,
+            &sub.kind,
+            sub.type_pattern.as_str(),
+            sub.source_pattern.as_ref().map(|p| p.as_str()),
+        ) {
+            Ok(cursor) => cursor,
+            Err(e) => {
+                ::log::error!("Failed to save subscription {}: {}", key, e);
+                return;
+            }
+        };
+
+        let missed = match store.events_after(cursor) {
+            Ok(events) => events,
+            Err(e) => {
+                ::log::error!("Failed to load missed events for {}: {}", key, e);
+                return;
+            }
+        };
+        let last = missed.last().map(|&(rowid, _)| rowid);
+        let ack_deadline = chrono::Utc::now() + self.redelivery.backoff(1);
+        let mut replayed = 0;
+        for (rowid, event) in missed {
+            if sub.matches(&event) {
+                if sub.ack {
+                    if let Err(e) = store.track_ack(key, rowid, &event.id, ack_deadline) {
+                        ::log::error!("Failed to track ack for {}: {}", key, e);
+                    }
+                }
+                sub.try_send(&event);
+                replayed += 1;
+            }
+        }
+        if replayed > 0 {
+            ::log::info!("Replayed {} missed events to {}", replayed, key);
+        }
+        if let Some(last) = last {
+            if let Err(e) = store.advance_subscription(key, last) {
+                ::log::error!("Failed to advance subscription {}: {}", key, e);
+            }
+        }
+    }
+
+    /// Send stored events to a subscriber's connected subscriptions, each to
+    /// the subscriptions it matches. Cursors and acknowledgements are left
+    /// alone. Returns how many events were sent.
+    pub fn replay_to_subscriber(
+        &self,
+        kind: &SubscriberKind,
+        events: &[CloudEvent],
+    ) -> Result<usize, String> {
+        let subs: Vec<&Subscription> =
+            self.subscriptions.iter().filter(|s| s.kind == *kind).collect();
+        if subs.is_empty() {
+            return Err("Subscriber has no connected subscriptions".to_string());
+        }
+        Ok(events
+            .iter()
+            .filter(|event| {
+                // Every matching subscription gets it, not just the first
+                let mut sent = false;
+                for sub in subs.iter().filter(|s| s.matches(event)) {
+                    sent |= sub.try_send(event);
+                }
+                sent
+            })
+            .count())
+    }
+
+    /// Pair each stored event a routing rule matches with the rule's action,
+    /// for the caller to execute.
+    pub fn replay_to_rule(
+        &self,
+        rule_id: &str,
+        events: Vec<CloudEvent>,
+    ) -> Result<Vec<(CloudEvent, RouteAction)>, String> {
+        let rule = self
+            .routing_rules
+            .get(rule_id)
+            .ok_or_else(|| format!("Rule '{}' not found", rule_id))?;
+        if !rule.enabled {
+            return Err(format!("Rule '{}' is disabled", rule_id));
+        }
+        Ok(events
+            .into_iter()
+            .filter(|event| rule.matches(event))
+            .map(|event| (event, rule.action.clone()))
+            .collect())
     }
 
     /// Query the event log.
@@ -104,11 +493,80 @@ impl EventBus {
         self.event_log.query(query)
     }
 
+    /// Throughput counters, for the executor to record action latency in.
+    pub fn metrics(&self) -> Arc<BusMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Events waiting in each connected subscription's queue.
+    pub fn subscriber_queues(&self) -> Vec<SubscriberQueue> {
+        self.subscriptions
+            .iter()
+            .map(|sub| SubscriberQueue {
+                subscription_id: sub.id.clone(),
+                subscriber: match &sub.kind {
+                    SubscriberKind::Extension { ext_id } => format!("extension:{}", ext_id),
+                    SubscriberKind::Plugin { plugin_id } => format!("plugin:{}", plugin_id),
+                    SubscriberKind::Frontend { channel } => format!("frontend:{}", channel),
+                    SubscriberKind::Internal => "internal".to_string(),
+                },
+                queue_depth: sub.sender.queued(),
+            })
+            .collect()
+    }
+
     /// Get the number of events in the log.
     pub fn log_len(&self) -> usize {
         self.event_log.len()
     }
 
+    /// Most events the in-memory log holds.
+    pub fn log_capacity(&self) -> usize {
+        self.event_log.capacity()
+    }
+
+    /// Apply the memory side of a retention policy to the event log. Returns
+    /// the number of events evicted by age.
+    pub fn apply_retention(&mut self, policy: &RetentionPolicy) -> usize {
+        self.event_log.set_capacity(policy.memory_events);
+        match policy.max_age() {
+            Some(ttl) => self.event_log.prune_before(chrono::Utc::now() - ttl),
+            None => 0,
+        }
+    }
+
+    // -- Event schemas delegated to the registry --
+
+    /// Validate a plugin's or extension's event against the schema for its
+    /// type before it is published. See [`SchemaRegistry::check`].
+    pub fn check_schema(&self, event: &mut CloudEvent) -> Result<(), String> {
+        self.schemas.check(event)
+    }
+
+    pub fn list_event_schemas(&self) -> &[EventSchema] {
+        self.schemas.list()
+    }
+
+    pub fn get_event_schema(&self, event_type: &str) -> Option<&EventSchema> {
+        self.schemas.get(event_type)
+    }
+
+    pub fn register_event_schema(
+        &mut self,
+        event_type: &str,
+        schema: serde_json::Value,
+        mode: SchemaMode,
+        owner: &str,
+    ) -> Result<EventSchema, String> {
+        self.schemas
+            .register(event_type, schema, mode, owner)
+            .cloned()
+    }
+
+    pub fn remove_event_schema(&mut self, event_type: &str, owner: Option<&str>) -> Result<(), String> {
+        self.schemas.remove(event_type, owner)
+    }
+
     // -- Routing rule CRUD delegated to the store --
 
     pub fn list_routing_rules(&self) -> &[RoutingRule] {
@@ -192,6 +650,39 @@ mod tests {
         assert_eq!(bus.log_len(), 1);
     }
 
+    #[test]
+    fn metrics_track_deliveries_and_queue_depth() {
+        let (_tmp, mut bus) = make_bus();
+        let (_sub_id, mut rx) = bus
+            .subscribe("test.*", None, SubscriberKind::Internal)
+            .unwrap();
+        let (_gone_id, gone) = bus
+            .subscribe("test.*", None, SubscriberKind::Internal)
+            .unwrap();
+        drop(gone);
+
+        for _ in 0..2 {
+            bus.publish(
+                CloudEvent::builder()
+                    .source("nexus://core")
+                    .event_type("test.ping")
+                    .build()
+                    .unwrap(),
+            );
+        }
+        let queues = bus.subscriber_queues();
+        assert_eq!(queues.len(), 1, "the closed subscription is dropped");
+        assert_eq!(queues[0].queue_depth, 2);
+
+        rx.try_recv().unwrap();
+        assert_eq!(bus.subscriber_queues()[0].queue_depth, 1);
+
+        let snapshot = bus.metrics().snapshot(bus.subscriber_queues(), 0);
+        assert_eq!(snapshot.published, 2);
+        assert_eq!(snapshot.delivered, 2);
+        assert_eq!(snapshot.dropped, 1);
+    }
+
     #[test]
     fn non_matching_subscribers_kept_alive() {
         let (_tmp, mut bus) = make_bus();
@@ -263,6 +754,229 @@ mod tests {
         assert!(matches!(actions[0], RouteAction::EmitFrontend { .. }));
     }
 
+    #[test]
+    fn durable_subscription_replays_missed_events() {
+        let tmp = TempDir::new().unwrap();
+        let store: SharedEventStore = Arc::new(store::EventStore::new(tmp.path()).unwrap());
+        let plugin = || SubscriberKind::Plugin {
+            plugin_id: "p1".into(),
+        };
+        let event = |t: &str| {
+            CloudEvent::builder()
+                .source("nexus://core")
+                .event_type(t)
+                .build()
+                .unwrap()
+        };
+
+        {
+            let mut bus = EventBus::new(tmp.path());
+            bus.attach_store(store.clone());
+            let (_sub_id, mut rx) = bus.subscribe("com.github.*", None, plugin()).unwrap();
+            bus.publish(event("com.github.push"));
+            assert!(rx.try_recv().is_ok());
+            // Subscriber goes away; these are published while it is offline
+            drop(rx);
+            bus.publish(event("com.github.issue"));
+            bus.publish(event("nexus.other"));
+        }
+
+        // Restart: the subscription is restored and the missed event replayed
+        let mut bus = EventBus::new(tmp.path());
+        bus.attach_store(store.clone());
+        bus.publish(event("com.github.release"));
+        let (sub_id, mut rx) = bus.subscribe("com.github.*", None, plugin()).unwrap();
+        assert_eq!(rx.try_recv().unwrap().event_type, "com.github.issue");
+        assert_eq!(rx.try_recv().unwrap().event_type, "com.github.release");
+        assert!(rx.try_recv().is_err());
+
+        // Transient subscribers aren't persisted; explicit unsubscribe forgets
+        bus.subscribe("*", None, SubscriberKind::Internal).unwrap();
+        assert_eq!(store.list_subscriptions().unwrap().len(), 1);
+        bus.unsubscribe(&sub_id);
+        assert!(store.list_subscriptions().unwrap().is_empty());
+    }
+
+    #[test]
+    fn predicate_filters_live_and_replayed_events() {
+        let tmp = TempDir::new().unwrap();
+        let store: SharedEventStore = Arc::new(store::EventStore::new(tmp.path()).unwrap());
+        let mut bus = EventBus::new(tmp.path());
+        bus.attach_store(store);
+        let push = |branch: &str| {
+            CloudEvent::builder()
+                .source("nexus://core")
+                .event_type("com.github.push")
+                .data(serde_json::json!({ "ref": branch }))
+                .build()
+                .unwrap()
+        };
+        let plugin = SubscriberKind::Plugin {
+            plugin_id: "p1".into(),
+        };
+        let main = || Some(Predicate::parse(r#"data.ref == "main""#).unwrap());
+
+        let (_sub_id, rx) = bus
+            .subscribe_with_ack("com.github.*", None, main(), plugin.clone(), false)
+            .unwrap();
+        drop(rx);
+        bus.publish(push("dev"));
+        bus.publish(push("main"));
+
+        let (_sub_id, mut rx) = bus
+            .subscribe_with_ack("com.github.*", None, main(), plugin, false)
+            .unwrap();
+        assert_eq!(rx.try_recv().unwrap().data["ref"], "main");
+        assert!(rx.try_recv().is_err());
+
+        bus.publish(push("dev"));
+        assert!(rx.try_recv().is_err());
+        bus.publish(push("main"));
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn stored_events_replay_into_subscribers_and_rules() {
+        let tmp = TempDir::new().unwrap();
+        let store: SharedEventStore = Arc::new(store::EventStore::new(tmp.path()).unwrap());
+        let mut bus = EventBus::new(tmp.path());
+        bus.attach_store(store.clone());
+        let event = |t: &str| {
+            CloudEvent::builder()
+                .source("nexus://core")
+                .event_type(t)
+                .build()
+                .unwrap()
+        };
+        // Published before anyone subscribed, but still stored
+        bus.publish(event("disk.low"));
+        bus.publish(event("other"));
+        bus.publish(event("disk.ok"));
+
+        let plugin = SubscriberKind::Plugin {
+            plugin_id: "indexer".into(),
+        };
+        let history: Vec<CloudEvent> = store
+            .events_in_range(&store::ReplayRange::default())
+            .unwrap()
+            .into_iter()
+            .map(|(_, e)| e)
+            .collect();
+        assert_eq!(history.len(), 3);
+        assert!(bus.replay_to_subscriber(&plugin, &history).is_err());
+
+        let (_sub_id, mut rx) = bus.subscribe("disk.*", None, plugin.clone()).unwrap();
+        assert!(rx.try_recv().is_err(), "a new subscription starts at the latest event");
+        assert_eq!(bus.replay_to_subscriber(&plugin, &history).unwrap(), 2);
+        assert_eq!(rx.try_recv().unwrap().event_type, "disk.low");
+        assert_eq!(rx.try_recv().unwrap().event_type, "disk.ok");
+
+        let rule_id = bus
+            .create_routing_rule(RoutingRule {
+                id: String::new(),
+                name: None,
+                filters: vec![routing::Filter::Exact(std::collections::HashMap::from([(
+                    "type".into(),
+                    "disk.low".into(),
+                )]))],
+                action: RouteAction::EmitFrontend {
+                    channel: "disk".into(),
+                },
+                enabled: true,
+                created_by: "user".into(),
+            })
+            .unwrap();
+        let matched = bus.replay_to_rule(&rule_id, history).unwrap();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].0.event_type, "disk.low");
+        assert!(bus.replay_to_rule("missing", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn causal_chain_follows_correlation_ids() {
+        let tmp = TempDir::new().unwrap();
+        let store: SharedEventStore = Arc::new(store::EventStore::new(tmp.path()).unwrap());
+        let mut bus = EventBus::new(tmp.path());
+        bus.attach_store(store.clone());
+        let event = |t: &str| {
+            CloudEvent::builder()
+                .source("nexus://core")
+                .event_type(t)
+                .build()
+                .unwrap()
+        };
+
+        let push = event("com.github.push");
+        bus.publish(push.clone());
+        bus.publish(event("unrelated"));
+        let mut started = event("nexus.plugin.started");
+        started.set_cause(&push);
+        bus.publish(started.clone());
+        // A publisher that only names the cause still joins the chain
+        let built = CloudEvent::builder()
+            .source("nexus://plugin/ci")
+            .event_type("ci.build.finished")
+            .extension(cloud_event::CAUSATION_ATTR, serde_json::json!(started.id))
+            .build()
+            .unwrap();
+        bus.publish(built.clone());
+
+        let chain = bus.causal_chain(&built.id).unwrap();
+        let ids: Vec<&str> = chain.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, [push.id.as_str(), started.id.as_str(), built.id.as_str()]);
+        assert_eq!(chain[2].correlation_id(), Some(push.id.as_str()));
+        assert_eq!(chain[2].causation_id(), Some(started.id.as_str()));
+
+        // The root's chain is the same; the log is gone after a restart
+        let mut bus = EventBus::new(tmp.path());
+        bus.attach_store(store);
+        assert_eq!(bus.causal_chain(&push.id).unwrap().len(), 3);
+        assert!(bus.causal_chain("missing").is_err());
+    }
+
+    #[test]
+    fn unacked_events_are_redelivered_until_acked() {
+        let tmp = TempDir::new().unwrap();
+        let store: SharedEventStore = Arc::new(store::EventStore::new(tmp.path()).unwrap());
+        let ext = SubscriberKind::Extension {
+            ext_id: "e1".into(),
+        };
+        let mut bus = EventBus::new(tmp.path());
+        bus.attach_store(store.clone());
+        // At most three deliveries in total
+        bus.set_redelivery_policy(RedeliveryPolicy {
+            max_attempts: 3,
+            ..Default::default()
+        });
+
+        let (_sub_id, mut rx) = bus
+            .subscribe_with_ack("test.*", None, None, ext.clone(), true)
+            .unwrap();
+        let first = CloudEvent::builder().source("nexus://core").event_type("test.a").build().unwrap();
+        let second = CloudEvent::builder().source("nexus://core").event_type("test.b").build().unwrap();
+        bus.publish(first.clone());
+        bus.publish(second.clone());
+        assert_eq!(rx.try_recv().unwrap().id, first.id);
+        assert_eq!(rx.try_recv().unwrap().id, second.id);
+
+        // Only the second is acknowledged
+        assert_eq!(bus.ack(&ext, &second.id).unwrap(), 1);
+        assert_eq!(bus.redeliver_unacked(), 0, "not due before the ack timeout");
+        store.make_acks_due();
+        assert_eq!(bus.redeliver_unacked(), 1);
+        assert_eq!(rx.try_recv().unwrap().id, first.id);
+        assert!(rx.try_recv().is_err());
+
+        // Third delivery, then it is given up on
+        store.make_acks_due();
+        assert_eq!(bus.redeliver_unacked(), 1);
+        store.make_acks_due();
+        assert_eq!(bus.redeliver_unacked(), 0);
+        store.make_acks_due();
+        let key = ext.durable_key("test.*", None, None).unwrap();
+        assert!(store.due_acks(&key, 10).unwrap().is_empty());
+    }
+
     #[test]
     fn unsubscribe_removes_subscription() {
         let (_tmp, mut bus) = make_bus();
@@ -0,0 +1,377 @@
+//! Bridge between the event bus and an external MQTT broker, e.g. the one
+//! Home Assistant uses. Outbound routes publish matching bus events to a
+//! topic as structured CloudEvents JSON; inbound routes publish messages from
+//! a topic filter onto the bus.
+
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, Transport};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use super::cloud_event::CloudEvent;
+use super::subscription::{parse_pattern, SubscriberKind};
+use super::Dispatch;
+
+/// Source of events that arrive from the broker. Outbound routes skip them so
+/// a message doesn't bounce between Nexus and the broker.
+pub const MQTT_SOURCE: &str = "nexus://mqtt";
+
+/// Broker connection and routes. Stored in settings as `mqtt_bridge`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MqttBridgeConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub routes: Vec<MqttRoute>,
+}
+
+impl Default for MqttBridgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 1883,
+            tls: false,
+            client_id: "nexus".to_string(),
+            username: None,
+            password: None,
+            routes: Vec::new(),
+        }
+    }
+}
+
+/// Which way events flow between the bus and a topic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "direction", rename_all = "snake_case")]
+pub enum MqttRoute {
+    /// Publish bus events whose type matches `event_type` (glob) to `topic`.
+    /// `{type}` in the topic becomes the event type with dots as slashes.
+    Outbound {
+        event_type: String,
+        topic: String,
+        #[serde(default)]
+        qos: u8,
+        #[serde(default)]
+        retain: bool,
+    },
+    /// Publish messages on `topic` (an MQTT filter with `+` and `#`
+    /// wildcards) to the bus as `event_type`, with the topic as subject and
+    /// the payload as data — parsed as JSON when possible.
+    Inbound {
+        topic: String,
+        event_type: String,
+        #[serde(default)]
+        qos: u8,
+    },
+}
+
+impl MqttBridgeConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.host.trim().is_empty() {
+            return Err("MQTT host is empty".to_string());
+        }
+        if self.port == 0 {
+            return Err("MQTT port must be between 1 and 65535".to_string());
+        }
+        if self.client_id.is_empty() {
+            return Err("MQTT client ID is empty".to_string());
+        }
+        for route in &self.routes {
+            route.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl MqttRoute {
+    fn validate(&self) -> Result<(), String> {
+        let (qos, topic) = match self {
+            MqttRoute::Outbound {
+                event_type,
+                topic,
+                qos,
+                ..
+            } => {
+                parse_pattern(event_type)?;
+                if topic.is_empty() || topic.contains(['+', '#']) {
+                    return Err(format!("Invalid MQTT topic '{}'", topic));
+                }
+                (*qos, topic)
+            }
+            MqttRoute::Inbound {
+                topic,
+                event_type,
+                qos,
+            } => {
+                if !rumqttc::valid_filter(topic) {
+                    return Err(format!("Invalid MQTT topic filter '{}'", topic));
+                }
+                if event_type.is_empty() || event_type.contains(['*', '?', '[']) {
+                    return Err(format!("Invalid event type '{}'", event_type));
+                }
+                (*qos, topic)
+            }
+        };
+        rumqttc::qos(qos).map_err(|_| format!("Invalid QoS {} for topic '{}'", qos, topic))?;
+        Ok(())
+    }
+}
+
+/// Topic an outbound route publishes an event to.
+fn outbound_topic(template: &str, event: &CloudEvent) -> String {
+    template.replace("{type}", &event.event_type.replace('.', "/"))
+}
+
+/// Connection state shown in settings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum MqttStatus {
+    Disabled,
+    Connecting,
+    Connected,
+    /// The last connection attempt failed. Retried every few seconds.
+    Error { message: String },
+}
+
+/// Handle to the running bridge. Managed as Tauri state.
+pub struct MqttBridge {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+    status: std::sync::Arc<RwLock<MqttStatus>>,
+}
+
+impl MqttBridge {
+    pub fn new() -> Self {
+        Self {
+            task: Mutex::new(None),
+            status: std::sync::Arc::new(RwLock::new(MqttStatus::Disabled)),
+        }
+    }
+
+    pub fn status(&self) -> MqttStatus {
+        self.status.read().expect("mqtt status lock poisoned").clone()
+    }
+
+    /// Stop the running bridge, if any, and start it again with `config`
+    /// when enabled.
+    pub fn apply(&self, config: MqttBridgeConfig, dispatch: Dispatch) {
+        let mut task = self.task.lock().expect("mqtt task lock poisoned");
+        if let Some(running) = task.take() {
+            running.abort();
+        }
+        let enabled = config.enabled;
+        set_status(
+            &self.status,
+            if enabled {
+                MqttStatus::Connecting
+            } else {
+                MqttStatus::Disabled
+            },
+        );
+        if enabled {
+            let status = self.status.clone();
+            *task = Some(tauri::async_runtime::spawn(run(config, dispatch, status)));
+        }
+    }
+}
+
+impl Default for MqttBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn set_status(status: &RwLock<MqttStatus>, value: MqttStatus) {
+    *status.write().expect("mqtt status lock poisoned") = value;
+}
+
+fn to_qos(qos: u8) -> QoS {
+    rumqttc::qos(qos).unwrap_or(QoS::AtMostOnce)
+}
+
+async fn run(
+    config: MqttBridgeConfig,
+    dispatch: Dispatch,
+    status: std::sync::Arc<RwLock<MqttStatus>>,
+) {
+    let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    if let Some(username) = &config.username {
+        options.set_credentials(username, config.password.clone().unwrap_or_default());
+    }
+    if config.tls {
+        options.set_transport(Transport::tls_with_default_config());
+    }
+    let (client, mut eventloop) = AsyncClient::new(options, 64);
+
+    // One internal bus subscription per outbound route, merged into a single
+    // channel. When the bridge stops, the forwarders' sends fail and their
+    // subscriptions are dropped.
+    let (out_tx, mut out_rx) = mpsc::unbounded_channel::<(usize, CloudEvent)>();
+    {
+        let mut bus = dispatch.bus.write().await;
+        for (i, route) in config.routes.iter().enumerate() {
+            let MqttRoute::Outbound { event_type, .. } = route else {
+                continue;
+            };
+            match bus.subscribe(event_type, None, SubscriberKind::Internal) {
+                Ok((_sub_id, mut rx)) => {
+                    let out_tx = out_tx.clone();
+                    tokio::spawn(async move {
+                        while let Some(event) = rx.recv().await {
+                            if out_tx.send((i, event)).is_err() {
+                                break;
+                            }
+                        }
+                    });
+                }
+                Err(e) => log::warn!("MQTT bridge: skipping route for '{}': {}", event_type, e),
+            }
+        }
+    }
+
+    loop {
+        tokio::select! {
+            polled = eventloop.poll() => match polled {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    log::info!("MQTT bridge connected to {}:{}", config.host, config.port);
+                    set_status(&status, MqttStatus::Connected);
+                    // Clean sessions drop subscriptions, so resubscribe on every connect
+                    for route in &config.routes {
+                        if let MqttRoute::Inbound { topic, qos, .. } = route {
+                            if let Err(e) = client.try_subscribe(topic, to_qos(*qos)) {
+                                log::warn!("MQTT bridge: failed to subscribe to {}: {}", topic, e);
+                            }
+                        }
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(msg))) => {
+                    publish_inbound(&config.routes, &dispatch, &msg.topic, &msg.payload).await;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::warn!("MQTT bridge: connection error: {}", e);
+                    set_status(&status, MqttStatus::Error { message: e.to_string() });
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                }
+            },
+            Some((i, event)) = out_rx.recv() => {
+                let Some(MqttRoute::Outbound { topic, qos, retain, .. }) = config.routes.get(i) else {
+                    continue;
+                };
+                if event.source == MQTT_SOURCE {
+                    continue;
+                }
+                let payload = match serde_json::to_vec(&event) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        log::warn!("MQTT bridge: failed to serialize event {}: {}", event.id, e);
+                        continue;
+                    }
+                };
+                let topic = outbound_topic(topic, &event);
+                if let Err(e) = client.try_publish(&topic, to_qos(*qos), *retain, payload) {
+                    log::warn!("MQTT bridge: failed to publish to {}: {}", topic, e);
+                }
+            }
+        }
+    }
+}
+
+/// Publish a broker message to the bus once for each inbound route whose
+/// filter matches its topic.
+async fn publish_inbound(routes: &[MqttRoute], dispatch: &Dispatch, topic: &str, payload: &[u8]) {
+    let data = serde_json::from_slice(payload)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(payload).into_owned()));
+    for route in routes {
+        let MqttRoute::Inbound {
+            topic: filter,
+            event_type,
+            ..
+        } = route
+        else {
+            continue;
+        };
+        if !rumqttc::matches(topic, filter) {
+            continue;
+        }
+        let event = CloudEvent::builder()
+            .source(MQTT_SOURCE)
+            .event_type(event_type)
+            .subject(topic)
+            .data(data.clone())
+            .build();
+        let result = match event {
+            Ok(event) => dispatch.publish(event).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::warn!("MQTT bridge: dropped message on {}: {}", topic, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(routes: Vec<MqttRoute>) -> MqttBridgeConfig {
+        MqttBridgeConfig {
+            enabled: true,
+            routes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validates_routes() {
+        assert!(config(vec![
+            MqttRoute::Outbound {
+                event_type: "nexus.plugin.*".into(),
+                topic: "nexus/{type}".into(),
+                qos: 1,
+                retain: false,
+            },
+            MqttRoute::Inbound {
+                topic: "homeassistant/+/state".into(),
+                event_type: "home.state".into(),
+                qos: 0,
+            },
+        ])
+        .validate()
+        .is_ok());
+
+        let outbound_wildcard = MqttRoute::Outbound {
+            event_type: "*".into(),
+            topic: "nexus/#".into(),
+            qos: 0,
+            retain: false,
+        };
+        assert!(config(vec![outbound_wildcard]).validate().is_err());
+
+        let bad_qos = MqttRoute::Inbound {
+            topic: "a/b".into(),
+            event_type: "home.state".into(),
+            qos: 3,
+        };
+        assert!(config(vec![bad_qos]).validate().is_err());
+    }
+
+    #[test]
+    fn outbound_topic_expands_type() {
+        let event = CloudEvent::builder()
+            .source("nexus://core")
+            .event_type("nexus.plugin.started")
+            .build()
+            .unwrap();
+        assert_eq!(outbound_topic("nexus/{type}", &event), "nexus/nexus/plugin/started");
+        assert_eq!(outbound_topic("lights", &event), "lights");
+    }
+}
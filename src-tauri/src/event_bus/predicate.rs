@@ -0,0 +1,453 @@
+//! Content predicates over an event's `data`.
+//!
+//! A predicate compares fields of the payload with literals:
+//!
+//! ```text
+//! data.ref == "refs/heads/main" && data.commits.0.author.name != "bot"
+//! $.pull_request.changed_files > 10 || data.labels contains "urgent"
+//! ```
+//!
+//! Paths start at the payload — `data.`, `$.` and `event.data.` prefixes are
+//! optional — and numeric segments index arrays (`commits.0` or `commits[0]`).
+//! Operators are `==`, `!=`, `<`, `<=`, `>`, `>=` and `contains` (substring or
+//! array element). A bare path is true when the field exists and isn't
+//! `null` or `false`. Literals are JSON: strings in double quotes, numbers,
+//! `true`, `false`, `null`. `&&` binds tighter than `||`; `!` negates, and
+//! parentheses group.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+/// A parsed predicate. Serializes as its source text.
+#[derive(Clone)]
+pub struct Predicate {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Or(Vec<Expr>),
+    And(Vec<Expr>),
+    Not(Box<Expr>),
+    Truthy(Vec<String>),
+    Compare(Vec<String>, Op, Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl Predicate {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        if tokens.is_empty() {
+            return Err("predicate is empty".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {} in predicate", token));
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            expr,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate against an event's data. Missing fields compare unequal to
+    /// everything and fail every ordering.
+    pub fn matches(&self, data: &Value) -> bool {
+        self.expr.eval(data)
+    }
+}
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Predicate({:?})", self.source)
+    }
+}
+
+impl Serialize for Predicate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Predicate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Predicate::parse(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Expr {
+    fn eval(&self, data: &Value) -> bool {
+        match self {
+            Expr::Or(exprs) => exprs.iter().any(|e| e.eval(data)),
+            Expr::And(exprs) => exprs.iter().all(|e| e.eval(data)),
+            Expr::Not(expr) => !expr.eval(data),
+            Expr::Truthy(path) => {
+                lookup(data, path).is_some_and(|v| !v.is_null() && *v != Value::Bool(false))
+            }
+            Expr::Compare(path, op, literal) => {
+                lookup(data, path).map_or(*op == Op::Ne, |v| compare(v, *op, literal))
+            }
+        }
+    }
+}
+
+fn lookup<'a>(data: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(data, |value, segment| match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn compare(value: &Value, op: Op, literal: &Value) -> bool {
+    match op {
+        Op::Eq => equal(value, literal),
+        Op::Ne => !equal(value, literal),
+        Op::Contains => match (value, literal) {
+            (Value::String(s), Value::String(needle)) => s.contains(needle.as_str()),
+            (Value::Array(items), _) => items.iter().any(|item| equal(item, literal)),
+            _ => false,
+        },
+        Op::Lt | Op::Le | Op::Gt | Op::Ge => {
+            let ordering = match (value, literal) {
+                (Value::Number(a), Value::Number(b)) => {
+                    a.as_f64().zip(b.as_f64()).and_then(|(a, b)| a.partial_cmp(&b))
+                }
+                (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+                _ => None,
+            };
+            ordering.is_some_and(|o| match op {
+                Op::Lt => o.is_lt(),
+                Op::Le => o.is_le(),
+                Op::Gt => o.is_gt(),
+                _ => o.is_ge(),
+            })
+        }
+    }
+}
+
+/// JSON equality, except that `1` and `1.0` are equal.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => a == b,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tokenizer and parser
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Path(Vec<String>),
+    Literal(Value),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Path(path) => write!(f, "path '{}'", path.join(".")),
+            Token::Literal(value) => write!(f, "literal {}", value),
+            Token::Op(op) => write!(f, "operator {:?}", op),
+            Token::And => f.write_str("'&&'"),
+            Token::Or => f.write_str("'||'"),
+            Token::Not => f.write_str("'!'"),
+            Token::Open => f.write_str("'('"),
+            Token::Close => f.write_str("')'"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::Open);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::Close);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Op(Op::Eq));
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(Op::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '<' | '>' => {
+                let inclusive = next == Some('=');
+                tokens.push(Token::Op(match (c, inclusive) {
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    _ => Op::Ge,
+                }));
+                i += if inclusive { 2 } else { 1 };
+            }
+            '"' => {
+                // Find the closing quote, skipping escaped characters, and
+                // let serde_json handle the escapes.
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                if i >= chars.len() {
+                    return Err("unterminated string in predicate".to_string());
+                }
+                i += 1;
+                let text: String = chars[start..i].iter().collect();
+                let value = serde_json::from_str(&text)
+                    .map_err(|e| format!("invalid string {} in predicate: {}", text, e))?;
+                tokens.push(Token::Literal(value));
+            }
+            _ if c == '-' || c.is_ascii_digit() => {
+                let start = i;
+                i += 1;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '+' | '-'))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value: Value = serde_json::from_str(&text)
+                    .map_err(|_| format!("invalid number '{}' in predicate", text))?;
+                tokens.push(Token::Literal(value));
+            }
+            _ if is_path_char(c) => {
+                let start = i;
+                while i < chars.len() && is_path_char(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.as_str() {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "null" => Token::Literal(Value::Null),
+                    "contains" => Token::Op(Op::Contains),
+                    _ => Token::Path(parse_path(&word)?),
+                });
+            }
+            _ => return Err(format!("unexpected '{}' in predicate", c)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '$' | '[' | ']')
+}
+
+/// Split `data.commits[0].id` into `["commits", "0", "id"]`.
+fn parse_path(word: &str) -> Result<Vec<String>, String> {
+    let normalized = word.replace('[', ".").replace(']', "");
+    let rest = ["event.data", "data", "$"]
+        .iter()
+        .find_map(|root| {
+            normalized
+                .strip_prefix(root)
+                .filter(|r| r.is_empty() || r.starts_with('.'))
+        })
+        .unwrap_or(&normalized);
+    let segments: Vec<String> = rest
+        .split('.')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    if segments.iter().any(|s| s.contains('$')) {
+        return Err(format!("invalid path '{}' in predicate", word));
+    }
+    Ok(segments)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut exprs = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            exprs.push(self.and()?);
+        }
+        Ok(if exprs.len() == 1 { exprs.remove(0) } else { Expr::Or(exprs) })
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut exprs = vec![self.unary()?];
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            exprs.push(self.unary()?);
+        }
+        Ok(if exprs.len() == 1 { exprs.remove(0) } else { Expr::And(exprs) })
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("missing ')' in predicate".to_string()),
+                }
+            }
+            Some(Token::Path(path)) => {
+                let Some(&Token::Op(op)) = self.peek() else {
+                    return Ok(Expr::Truthy(path));
+                };
+                self.pos += 1;
+                match self.next() {
+                    Some(Token::Literal(value)) => Ok(Expr::Compare(path, op, value)),
+                    Some(token) => Err(format!(
+                        "expected a literal after {:?}, found {} (quote strings)",
+                        op, token
+                    )),
+                    None => Err(format!("expected a literal after {:?}", op)),
+                }
+            }
+            Some(token) => Err(format!("unexpected {} in predicate", token)),
+            None => Err("predicate ends unexpectedly".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(source: &str, data: &Value) -> bool {
+        Predicate::parse(source).unwrap().matches(data)
+    }
+
+    fn push() -> Value {
+        json!({
+            "ref": "refs/heads/main",
+            "forced": false,
+            "commits": [
+                { "id": "a1", "author": { "name": "alice" } },
+                { "id": "b2", "author": { "name": "bot" } }
+            ],
+            "labels": ["urgent", "infra"],
+            "size": 12
+        })
+    }
+
+    #[test]
+    fn comparisons_over_paths() {
+        let data = push();
+        assert!(check(r#"data.ref == "refs/heads/main""#, &data));
+        assert!(check(r#"$.ref == "refs/heads/main""#, &data));
+        assert!(check(r#"event.data.ref != "refs/heads/dev""#, &data));
+        assert!(check(r#"commits.1.author.name == "bot""#, &data));
+        assert!(check(r#"data.commits[0].id == "a1""#, &data));
+        assert!(check("size > 10 && size <= 12", &data));
+        assert!(check("size == 12.0", &data));
+        assert!(!check("size < 5", &data));
+        assert!(!check(r#"size > "10""#, &data), "mixed types don't order");
+    }
+
+    #[test]
+    fn missing_fields_never_match() {
+        let data = push();
+        assert!(!check(r#"data.branch == "main""#, &data));
+        assert!(!check("data.commits.9.id", &data));
+        assert!(check(r#"data.branch != "main""#, &data));
+    }
+
+    #[test]
+    fn contains_strings_and_arrays() {
+        let data = push();
+        assert!(check(r#"data.labels contains "urgent""#, &data));
+        assert!(!check(r#"data.labels contains "docs""#, &data));
+        assert!(check(r#"data.ref contains "heads/""#, &data));
+    }
+
+    #[test]
+    fn boolean_logic_and_truthiness() {
+        let data = push();
+        assert!(check("data.commits", &data));
+        assert!(!check("data.forced", &data));
+        assert!(check("!data.forced", &data));
+        assert!(check(r#"size < 5 || data.ref == "refs/heads/main" && !data.forced"#, &data));
+        assert!(!check(r#"(size < 5 || data.ref == "refs/heads/main") && data.forced"#, &data));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Predicate::parse("").is_err());
+        assert!(Predicate::parse("data.ref ==").is_err());
+        assert!(Predicate::parse("data.ref == main").is_err());
+        assert!(Predicate::parse(r#"data.ref == "main"#).is_err());
+        assert!(Predicate::parse("(data.ref").is_err());
+        assert!(Predicate::parse("data.ref = 1").is_err());
+        assert!(Predicate::parse("size > 1 size").is_err());
+    }
+
+    #[test]
+    fn serializes_as_source() {
+        let p: Predicate = serde_json::from_value(json!(r#"data.ref == "main""#)).unwrap();
+        assert_eq!(serde_json::to_value(&p).unwrap(), json!(r#"data.ref == "main""#));
+        assert!(serde_json::from_value::<Predicate>(json!("data.ref ==")).is_err());
+    }
+}
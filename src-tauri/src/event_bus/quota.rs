@@ -0,0 +1,221 @@
+//! Per-publisher limits on how many events a plugin can publish and how big
+//! they can be, so one misbehaving plugin can't flood the bus.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::cloud_event::CloudEvent;
+
+/// Publishing limits applied to each publisher separately. Stored in
+/// settings as `event_quota`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublishQuota {
+    /// Events a publisher may publish per window.
+    pub max_events: u32,
+    /// Window length in seconds.
+    pub window_secs: u64,
+    /// Largest serialized `data` payload accepted, in bytes.
+    pub max_payload_bytes: usize,
+    /// Rejections within one window before the publisher is recorded in the
+    /// audit log.
+    pub audit_after: u32,
+}
+
+impl Default for PublishQuota {
+    fn default() -> Self {
+        Self {
+            max_events: 1000,
+            window_secs: 60,
+            max_payload_bytes: 256 * 1024,
+            audit_after: 10,
+        }
+    }
+}
+
+impl PublishQuota {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_events == 0 {
+            return Err("max_events must be at least 1".to_string());
+        }
+        if self.window_secs == 0 {
+            return Err("window_secs must be at least 1".to_string());
+        }
+        if self.max_payload_bytes == 0 {
+            return Err("max_payload_bytes must be at least 1".to_string());
+        }
+        if self.audit_after == 0 {
+            return Err("audit_after must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Why an event was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuotaViolation {
+    /// The publisher used up its window. It may publish again after
+    /// `retry_after`.
+    RateLimited { retry_after: Duration },
+    PayloadTooLarge { size: usize, max: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaExceeded {
+    pub violation: QuotaViolation,
+    /// True for the rejection that reaches the quota's `audit_after` within a
+    /// window — once per window, so a flood makes one audit entry, not many.
+    pub repeated: bool,
+}
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.violation {
+            QuotaViolation::RateLimited { retry_after } => write!(
+                f,
+                "Event rate limit exceeded; retry in {}s",
+                retry_after.as_secs().max(1)
+            ),
+            QuotaViolation::PayloadTooLarge { size, max } => write!(
+                f,
+                "Event data is {} bytes, more than the {} byte limit",
+                size, max
+            ),
+        }
+    }
+}
+
+struct Window {
+    start: Instant,
+    published: u32,
+    rejected: u32,
+}
+
+/// Fixed-window counters per publisher.
+pub struct QuotaTracker {
+    quota: PublishQuota,
+    windows: Mutex<HashMap<String, Window>>,
+}
+
+impl QuotaTracker {
+    pub fn new(quota: PublishQuota) -> Self {
+        Self {
+            quota,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the limits. Counters carry over.
+    pub fn set_quota(&mut self, quota: PublishQuota) {
+        self.quota = quota;
+    }
+
+    /// Count an event against its publisher's quota, or refuse it.
+    pub fn check(&self, publisher: &str, event: &CloudEvent) -> Result<(), QuotaExceeded> {
+        let size = serde_json::to_vec(&event.data).map_or(0, |d| d.len());
+        self.check_at(publisher, size, Instant::now())
+    }
+
+    fn check_at(&self, publisher: &str, size: usize, now: Instant) -> Result<(), QuotaExceeded> {
+        let window_len = Duration::from_secs(self.quota.window_secs);
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        // Forget publishers that have gone quiet so the map doesn't grow
+        windows.retain(|_, w| now.duration_since(w.start) < window_len);
+        let window = windows.entry(publisher.to_string()).or_insert(Window {
+            start: now,
+            published: 0,
+            rejected: 0,
+        });
+
+        let violation = if size > self.quota.max_payload_bytes {
+            QuotaViolation::PayloadTooLarge {
+                size,
+                max: self.quota.max_payload_bytes,
+            }
+        } else if window.published >= self.quota.max_events {
+            QuotaViolation::RateLimited {
+                retry_after: window_len.saturating_sub(now.duration_since(window.start)),
+            }
+        } else {
+            window.published += 1;
+            return Ok(());
+        };
+
+        window.rejected += 1;
+        Err(QuotaExceeded {
+            violation,
+            repeated: window.rejected == self.quota.audit_after,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(max_events: u32, audit_after: u32) -> QuotaTracker {
+        QuotaTracker::new(PublishQuota {
+            max_events,
+            window_secs: 10,
+            max_payload_bytes: 100,
+            audit_after,
+        })
+    }
+
+    #[test]
+    fn limits_each_publisher_per_window() {
+        let quota = tracker(2, 10);
+        let start = Instant::now();
+        assert!(quota.check_at("a", 10, start).is_ok());
+        assert!(quota.check_at("a", 10, start).is_ok());
+        assert!(quota.check_at("b", 10, start).is_ok(), "publishers are independent");
+
+        let later = start + Duration::from_secs(4);
+        let refused = quota.check_at("a", 10, later).unwrap_err();
+        assert_eq!(
+            refused.violation,
+            QuotaViolation::RateLimited {
+                retry_after: Duration::from_secs(6)
+            }
+        );
+
+        // A new window starts once the old one is over
+        assert!(quota.check_at("a", 10, start + Duration::from_secs(10)).is_ok());
+    }
+
+    #[test]
+    fn refuses_large_payloads() {
+        let quota = tracker(5, 10);
+        let refused = quota.check_at("a", 101, Instant::now()).unwrap_err();
+        assert_eq!(
+            refused.violation,
+            QuotaViolation::PayloadTooLarge { size: 101, max: 100 }
+        );
+        assert!(quota.check_at("a", 100, Instant::now()).is_ok());
+    }
+
+    #[test]
+    fn flags_repeated_violations_once_per_window() {
+        let quota = tracker(1, 3);
+        let now = Instant::now();
+        quota.check_at("a", 0, now).unwrap();
+        let repeated: Vec<bool> = (0..5)
+            .map(|_| quota.check_at("a", 0, now).unwrap_err().repeated)
+            .collect();
+        assert_eq!(repeated, [false, false, true, false, false]);
+    }
+
+    #[test]
+    fn validates() {
+        assert!(PublishQuota::default().validate().is_ok());
+        let zero = PublishQuota {
+            window_secs: 0,
+            ..Default::default()
+        };
+        assert!(zero.validate().is_err());
+    }
+}
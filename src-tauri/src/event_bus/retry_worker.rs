@@ -5,27 +5,30 @@ use tokio::time::interval;
 
 use super::executor::RouteActionExecutor;
 use super::store::EventStore;
+use super::SharedEventBus;
 
-/// Run the background retry worker that processes pending deliveries
-/// and periodically cleans up expired events.
+/// Run the background retry worker that processes pending deliveries,
+/// redelivers unacknowledged subscriber events, and compacts the event log
+/// at startup and then hourly.
 ///
 /// This is an async function — the caller is responsible for spawning it
 /// (e.g. via `tauri::async_runtime::spawn`).
-pub async fn run(store: Arc<EventStore>, executor: RouteActionExecutor) {
+pub async fn run(store: Arc<EventStore>, bus: SharedEventBus, executor: RouteActionExecutor) {
     let mut retry_tick = interval(Duration::from_secs(5));
     let mut cleanup_tick = interval(Duration::from_secs(3600));
 
-    // Consume the first immediate tick
+    // Consume the first immediate tick. The cleanup tick fires right away so
+    // retention settings apply at startup.
     retry_tick.tick().await;
-    cleanup_tick.tick().await;
 
     loop {
         tokio::select! {
             _ = retry_tick.tick() => {
                 process_pending(&store, &executor).await;
+                redeliver_unacked(&bus, &executor).await;
             }
             _ = cleanup_tick.tick() => {
-                cleanup_expired(&store);
+                compact(&store, &bus, &executor).await;
             }
         }
     }
@@ -92,13 +95,31 @@ async fn process_pending(store: &Arc<EventStore>, executor: &RouteActionExecutor
     }
 }
 
-/// Clean up events and deliveries older than 7 days.
-fn cleanup_expired(store: &EventStore) {
-    let ttl = Duration::from_secs(7 * 86400);
-    match store.cleanup_old(ttl) {
-        Ok(0) => {}
-        Ok(n) => log::info!("Retry worker: purged {} expired events", n),
-        Err(e) => log::error!("Retry worker: TTL cleanup failed: {}", e),
+/// Resend subscriber events whose ack timeout passed, picking up policy
+/// changes from settings.
+async fn redeliver_unacked(bus: &SharedEventBus, executor: &RouteActionExecutor) {
+    let policy = executor.redelivery_policy().await;
+    let mut bus = bus.write().await;
+    bus.set_redelivery_policy(policy);
+    let resent = bus.redeliver_unacked();
+    if resent > 0 {
+        log::debug!("Retry worker: redelivered {} unacknowledged events", resent);
+    }
+}
+
+/// Apply the retention policy from settings to the in-memory log and the
+/// store.
+async fn compact(store: &EventStore, bus: &SharedEventBus, executor: &RouteActionExecutor) {
+    let policy = executor.retention_policy().await;
+    bus.write().await.apply_retention(&policy);
+    match store.compact(&policy) {
+        Ok(report) if report.expired + report.trimmed == 0 => {}
+        Ok(report) => log::info!(
+            "Retry worker: purged {} expired and {} excess events",
+            report.expired,
+            report.trimmed
+        ),
+        Err(e) => log::error!("Retry worker: compaction failed: {}", e),
     }
 
     // Log dead letter count for monitoring
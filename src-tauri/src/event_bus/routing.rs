@@ -6,6 +6,7 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use super::cloud_event::CloudEvent;
+use super::predicate::Predicate;
 
 /// What happens when a routing rule matches an event.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +27,66 @@ pub enum RouteAction {
     EmitFrontend {
         channel: String,
     },
+    /// POST the event to an external URL, e.g. a Slack, n8n, or Home
+    /// Assistant webhook.
+    Webhook {
+        url: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        mode: WebhookMode,
+    },
+    /// Start, stop, or restart a plugin, e.g. stop an indexer when disk
+    /// space runs low.
+    ControlPlugin {
+        plugin_id: String,
+        command: PluginCommand,
+    },
+}
+
+/// Lifecycle change applied by a [`RouteAction::ControlPlugin`] action.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginCommand {
+    Start,
+    Stop,
+    /// Stop the plugin if it's running, then start it.
+    Restart,
+}
+
+/// CloudEvents HTTP binding content mode for webhook deliveries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookMode {
+    /// The whole event as an `application/cloudevents+json` body.
+    #[default]
+    Structured,
+    /// The event data as the body, attributes as `ce-*` headers.
+    Binary,
+}
+
+impl RouteAction {
+    /// Check the parts of an action that can be checked before it runs.
+    pub fn validate(&self) -> Result<(), String> {
+        if let RouteAction::ControlPlugin { plugin_id, .. } = self {
+            if plugin_id.is_empty() {
+                return Err("plugin_id must not be empty".to_string());
+            }
+        }
+        if let RouteAction::Webhook { url, headers, .. } = self {
+            let parsed =
+                url::Url::parse(url).map_err(|e| format!("Invalid webhook URL '{}': {}", url, e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!("Webhook URL must be http or https: {}", url));
+            }
+            if let Some(name) = headers.keys().find(|name| {
+                name.is_empty() || !name.bytes().all(|b| b.is_ascii_graphic() && b != b':')
+            }) {
+                return Err(format!("Invalid webhook header name '{}'", name));
+            }
+        }
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -36,6 +97,7 @@ pub enum RouteAction {
 /// A single filter expression per the CE Subscriptions spec.
 ///
 /// All required dialects are supported: exact, prefix, suffix, all, any, not.
+/// `where` is a Nexus extension that filters on the event's `data`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Filter {
@@ -51,6 +113,9 @@ pub enum Filter {
     Any(Vec<Filter>),
     /// Nested filter must NOT match.
     Not(Box<Filter>),
+    /// Predicate over the event payload, e.g. `data.ref == "refs/heads/main"`.
+    /// See [`Predicate`] for the syntax.
+    Where(Predicate),
 }
 
 impl Filter {
@@ -69,6 +134,7 @@ impl Filter {
             Filter::All(filters) => filters.iter().all(|f| f.matches(event)),
             Filter::Any(filters) => filters.iter().any(|f| f.matches(event)),
             Filter::Not(filter) => !filter.matches(event),
+            Filter::Where(predicate) => predicate.matches(&event.data),
         }
     }
 }
@@ -118,17 +184,7 @@ impl RoutingRuleStore {
     /// Load rules from disk, or create an empty store if the file doesn't exist.
     pub fn load(data_dir: &std::path::Path) -> Self {
         let path = data_dir.join("event_rules.json");
-        let rules = if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
-                Err(e) => {
-                    log::warn!("Failed to read event rules: {}", e);
-                    Vec::new()
-                }
-            }
-        } else {
-            Vec::new()
-        };
+        let rules = crate::util::read_json_store(&path).unwrap_or_default();
 
         Self { rules, path }
     }
@@ -137,7 +193,7 @@ impl RoutingRuleStore {
     fn save(&self) -> Result<(), String> {
         let json = serde_json::to_string_pretty(&self.rules)
             .map_err(|e| format!("Failed to serialize rules: {}", e))?;
-        std::fs::write(&self.path, json)
+        crate::util::atomic_write_with_backup(&self.path, json.as_bytes())
             .map_err(|e| format!("Failed to write rules file: {}", e))?;
         Ok(())
     }
@@ -154,6 +210,7 @@ impl RoutingRuleStore {
 
     /// Add a new routing rule. Returns the assigned ID.
     pub fn create(&mut self, mut rule: RoutingRule) -> Result<String, String> {
+        rule.action.validate()?;
         if rule.id.is_empty() {
             rule.id = format!("rule_{}", Uuid::new_v4().simple());
         }
@@ -174,6 +231,9 @@ impl RoutingRuleStore {
 
     /// Update an existing rule. Returns an error if not found.
     pub fn update(&mut self, id: &str, update: RoutingRuleUpdate) -> Result<(), String> {
+        if let Some(action) = &update.action {
+            action.validate()?;
+        }
         let rule = self
             .rules
             .iter_mut()
@@ -321,6 +381,23 @@ mod tests {
         assert!(!f.matches(&make_event("t", "s")));
     }
 
+    #[test]
+    fn where_filter_matches_data() {
+        let f: Filter = serde_json::from_value(serde_json::json!({
+            "where": "data.ref == \"refs/heads/main\""
+        }))
+        .unwrap();
+        let mut event = make_event("com.github.push", "s");
+        assert!(!f.matches(&event));
+        event.data = serde_json::json!({ "ref": "refs/heads/main" });
+        assert!(f.matches(&event));
+        event.data = serde_json::json!({ "ref": "refs/heads/dev" });
+        assert!(!f.matches(&event));
+
+        let invalid = serde_json::from_value::<Filter>(serde_json::json!({ "where": "data.ref ==" }));
+        assert!(invalid.is_err());
+    }
+
     // -- RoutingRule tests --
 
     #[test]
@@ -373,6 +450,50 @@ mod tests {
         assert!(!rule.matches(&make_event("com.gitlab.push", "s")));
     }
 
+    #[test]
+    fn webhook_action_validation() {
+        let webhook = |url: &str, header: &str| RouteAction::Webhook {
+            url: url.into(),
+            headers: HashMap::from([(header.into(), "v".into())]),
+            mode: WebhookMode::default(),
+        };
+        assert!(webhook("https://hooks.slack.com/services/T0/B0/x", "Authorization").validate().is_ok());
+        assert!(webhook("ftp://example.com/hook", "X-Token").validate().is_err());
+        assert!(webhook("not a url", "X-Token").validate().is_err());
+        assert!(webhook("https://example.com/hook", "Bad Header").validate().is_err());
+
+        let parsed: RouteAction = serde_json::from_value(serde_json::json!({
+            "action": "webhook",
+            "url": "http://localhost:5678/webhook/nexus"
+        }))
+        .unwrap();
+        assert!(matches!(
+            parsed,
+            RouteAction::Webhook { mode: WebhookMode::Structured, ref headers, .. } if headers.is_empty()
+        ));
+    }
+
+    #[test]
+    fn control_plugin_action_parses() {
+        let parsed: RouteAction = serde_json::from_value(serde_json::json!({
+            "action": "control_plugin",
+            "plugin_id": "media-indexer",
+            "command": "stop"
+        }))
+        .unwrap();
+        assert!(matches!(
+            parsed,
+            RouteAction::ControlPlugin { ref plugin_id, command: PluginCommand::Stop } if plugin_id == "media-indexer"
+        ));
+        assert!(parsed.validate().is_ok());
+
+        let empty = RouteAction::ControlPlugin {
+            plugin_id: String::new(),
+            command: PluginCommand::Restart,
+        };
+        assert!(empty.validate().is_err());
+    }
+
     // -- Store tests --
 
     #[test]
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use jsonschema::Validator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use super::cloud_event::CloudEvent;
+
+/// CloudEvents extension attribute set on events published in
+/// [`SchemaMode::Flag`] mode whose data doesn't match the schema.
+pub const SCHEMA_ERROR_ATTR: &str = "schemaerror";
+
+/// What happens to a published event whose data doesn't match its schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaMode {
+    /// Refuse to publish the event.
+    #[default]
+    Reject,
+    /// Publish it with a `schemaerror` attribute describing the mismatch.
+    Flag,
+}
+
+/// JSON Schema for the `data` of one event type, registered by the plugin or
+/// extension that publishes it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EventSchema {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub schema: Value,
+    #[serde(default)]
+    pub mode: SchemaMode,
+    /// Source URI of the registering plugin or extension. Only it can
+    /// replace or remove the schema; the user can remove any.
+    pub owner: String,
+    #[schema(value_type = String)]
+    pub registered_at: DateTime<Utc>,
+}
+
+/// File-based registry of event schemas, with a compiled validator per type.
+pub struct SchemaRegistry {
+    schemas: Vec<EventSchema>,
+    validators: HashMap<String, Validator>,
+    path: PathBuf,
+}
+
+impl SchemaRegistry {
+    /// Load schemas from disk, or create an empty registry if the file
+    /// doesn't exist. Schemas that no longer compile are skipped.
+    pub fn load(data_dir: &std::path::Path) -> Self {
+        let path = data_dir.join("event_schemas.json");
+        let stored: Vec<EventSchema> = crate::util::read_json_store(&path).unwrap_or_default();
+
+        let mut schemas = Vec::with_capacity(stored.len());
+        let mut validators = HashMap::new();
+        for schema in stored {
+            match Validator::new(&schema.schema) {
+                Ok(v) => {
+                    validators.insert(schema.event_type.clone(), v);
+                    schemas.push(schema);
+                }
+                Err(e) => log::warn!("Skipping schema for '{}': {}", schema.event_type, e),
+            }
+        }
+
+        Self {
+            schemas,
+            validators,
+            path,
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.schemas)
+            .map_err(|e| format!("Failed to serialize schemas: {}", e))?;
+        crate::util::atomic_write_with_backup(&self.path, json.as_bytes())
+            .map_err(|e| format!("Failed to write schemas file: {}", e))?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> &[EventSchema] {
+        &self.schemas
+    }
+
+    pub fn get(&self, event_type: &str) -> Option<&EventSchema> {
+        self.schemas.iter().find(|s| s.event_type == event_type)
+    }
+
+    /// Register or replace the schema for an event type. Fails if the schema
+    /// doesn't compile or another publisher already registered one.
+    pub fn register(
+        &mut self,
+        event_type: &str,
+        schema: Value,
+        mode: SchemaMode,
+        owner: &str,
+    ) -> Result<&EventSchema, String> {
+        if event_type.is_empty() || event_type.contains(['*', '?', '[']) {
+            return Err(format!("Invalid event type '{}'", event_type));
+        }
+        if let Some(existing) = self.get(event_type) {
+            if existing.owner != owner {
+                return Err(format!(
+                    "The schema for '{}' belongs to {}",
+                    event_type, existing.owner
+                ));
+            }
+        }
+        let validator = Validator::new(&schema).map_err(|e| format!("Invalid schema: {}", e))?;
+
+        self.schemas.retain(|s| s.event_type != event_type);
+        self.schemas.push(EventSchema {
+            event_type: event_type.to_string(),
+            schema,
+            mode,
+            owner: owner.to_string(),
+            registered_at: Utc::now(),
+        });
+        self.validators.insert(event_type.to_string(), validator);
+        self.save()?;
+        Ok(self.schemas.last().expect("just pushed"))
+    }
+
+    /// Remove the schema for an event type. With an owner, only that
+    /// publisher's schema is removed; without one (the user), any is.
+    pub fn remove(&mut self, event_type: &str, owner: Option<&str>) -> Result<(), String> {
+        let existing = self
+            .get(event_type)
+            .ok_or_else(|| format!("No schema registered for '{}'", event_type))?;
+        if let Some(owner) = owner.filter(|o| *o != existing.owner) {
+            return Err(format!(
+                "The schema for '{}' belongs to {}, not {}",
+                event_type, existing.owner, owner
+            ));
+        }
+        self.schemas.retain(|s| s.event_type != event_type);
+        self.validators.remove(event_type);
+        self.save()
+    }
+
+    /// Validate an event's data against the schema for its type, if any. In
+    /// reject mode a mismatch is an error; in flag mode the event is marked
+    /// with a `schemaerror` attribute and passes.
+    pub fn check(&self, event: &mut CloudEvent) -> Result<(), String> {
+        let Some(validator) = self.validators.get(&event.event_type) else {
+            return Ok(());
+        };
+        let Err(e) = validator.validate(&event.data) else {
+            return Ok(());
+        };
+        let error = format!(
+            "Event data does not match the schema for '{}': {}",
+            event.event_type, e
+        );
+        match self.get(&event.event_type).map(|s| s.mode) {
+            Some(SchemaMode::Flag) => {
+                event
+                    .extensions
+                    .insert(SCHEMA_ERROR_ATTR.to_string(), Value::String(error));
+                Ok(())
+            }
+            _ => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn push_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": { "ref": { "type": "string" } },
+            "required": ["ref"]
+        })
+    }
+
+    fn make_event(data: Value) -> CloudEvent {
+        CloudEvent::builder()
+            .source("nexus://plugin/gh")
+            .event_type("com.github.push")
+            .data(data)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn reject_and_flag_modes() {
+        let tmp = TempDir::new().unwrap();
+        let mut registry = SchemaRegistry::load(tmp.path());
+        registry
+            .register("com.github.push", push_schema(), SchemaMode::Reject, "nexus://plugin/gh")
+            .unwrap();
+
+        assert!(registry.check(&mut make_event(json!({"ref": "main"}))).is_ok());
+        assert!(registry.check(&mut make_event(json!({"ref": 1}))).is_err());
+
+        registry
+            .register("com.github.push", push_schema(), SchemaMode::Flag, "nexus://plugin/gh")
+            .unwrap();
+        let mut event = make_event(json!({}));
+        assert!(registry.check(&mut event).is_ok());
+        assert!(event.extensions.contains_key(SCHEMA_ERROR_ATTR));
+    }
+
+    #[test]
+    fn only_the_owner_replaces_or_removes() {
+        let tmp = TempDir::new().unwrap();
+        let mut registry = SchemaRegistry::load(tmp.path());
+        registry
+            .register("com.github.push", push_schema(), SchemaMode::Reject, "nexus://plugin/gh")
+            .unwrap();
+
+        assert!(registry
+            .register("com.github.push", json!({}), SchemaMode::Reject, "nexus://plugin/other")
+            .is_err());
+        assert!(registry
+            .remove("com.github.push", Some("nexus://plugin/other"))
+            .is_err());
+        assert!(registry
+            .register("com.github.*", json!({}), SchemaMode::Reject, "nexus://plugin/gh")
+            .is_err());
+
+        // Survives a reload, then the user removes it.
+        let mut registry = SchemaRegistry::load(tmp.path());
+        assert_eq!(registry.list().len(), 1);
+        registry.remove("com.github.push", None).unwrap();
+        assert!(registry.check(&mut make_event(json!(null))).is_ok());
+    }
+}
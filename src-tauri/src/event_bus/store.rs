@@ -3,11 +3,12 @@ use std::sync::Mutex;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
 use super::cloud_event::CloudEvent;
 use super::routing::RouteAction;
+use super::subscription::SubscriberKind;
 
 /// A row from the `deliveries` table.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +21,134 @@ pub struct DeliveryRow {
     pub last_error: Option<String>,
 }
 
+/// A delivery in the dead letter queue: a route action that failed on every
+/// attempt, with the event that triggered it and the last failure reason.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetter {
+    pub id: i64,
+    pub event_id: String,
+    pub event_type: String,
+    pub source: String,
+    pub action: RouteAction,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub failed_at: String,
+}
+
+/// A row from the `subscriptions` table: an extension or plugin subscription
+/// that survives restarts. `cursor` is the rowid of the last event delivered.
+#[derive(Debug, Clone)]
+pub struct StoredSubscription {
+    pub key: String,
+    pub kind: SubscriberKind,
+    pub type_pattern: String,
+    pub source_pattern: Option<String>,
+    pub cursor: i64,
+}
+
+/// Which stored events to replay. Bounds combine; all are optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReplayRange {
+    /// Only events after this sequence number (exclusive).
+    #[serde(default)]
+    pub after_seq: Option<i64>,
+    /// Only events at or after this time.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Only events before this time.
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    /// At most this many events, oldest first. Defaults to 1000, capped at 10000.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// How much event history to keep, in memory and on disk. Stored in settings
+/// as `event_retention`. A zero limit on disk means "no limit".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Days to keep stored events.
+    pub max_age_days: u32,
+    /// Most stored events to keep; the oldest are dropped first.
+    pub max_events: u64,
+    /// Upper bound on the size of stored events, in bytes.
+    pub max_bytes: u64,
+    /// Recent events held in memory for the live event log.
+    pub memory_events: usize,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age_days: 7,
+            max_events: 100_000,
+            max_bytes: 256 * 1024 * 1024,
+            memory_events: 10_000,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    const MAX_MEMORY_EVENTS: usize = 1_000_000;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.memory_events == 0 || self.memory_events > Self::MAX_MEMORY_EVENTS {
+            return Err(format!(
+                "memory_events must be between 1 and {}",
+                Self::MAX_MEMORY_EVENTS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Age limit, or `None` when events are kept regardless of age.
+    pub fn max_age(&self) -> Option<Duration> {
+        (self.max_age_days > 0).then(|| Duration::from_secs(u64::from(self.max_age_days) * 86400))
+    }
+}
+
+/// What a compaction pass removed from the store.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompactionReport {
+    /// Events older than the age limit.
+    pub expired: usize,
+    /// Events dropped to get under the count and size limits.
+    pub trimmed: usize,
+}
+
+/// Current size of the event store.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreStats {
+    pub events: usize,
+    /// Size of the stored events.
+    pub event_bytes: u64,
+    /// Size of the database file, including free pages left by deletions.
+    pub file_bytes: u64,
+    pub oldest: Option<String>,
+    pub newest: Option<String>,
+    pub pending_deliveries: usize,
+    pub dead_letters: usize,
+}
+
+/// Stored size of an event row, as counted against `max_bytes`.
+const EVENT_SIZE_SQL: &str = "length(id) + length(source) + length(event_type)
+     + coalesce(length(subject), 0) + length(data) + length(time)";
+
+/// Events that count and size trimming must keep: ones with route actions
+/// still pending or dead-lettered.
+const UNSETTLED_SQL: &str =
+    "id IN (SELECT event_id FROM deliveries WHERE status != 'completed')";
+
+/// An event sent to a subscriber that must acknowledge it, awaiting the ack.
+#[derive(Debug, Clone)]
+pub struct PendingAck {
+    pub id: i64,
+    pub key: String,
+    pub event_rowid: i64,
+    pub attempts: u32,
+}
+
 /// SQLite-backed durable event store with delivery tracking.
 ///
 /// Events are persisted when published. Each matching route action becomes a
@@ -50,7 +179,9 @@ impl EventStore {
                 subject     TEXT,
                 data        TEXT NOT NULL,
                 time        TEXT NOT NULL,
-                created_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+                created_at  TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+                extensions  TEXT,
+                correlation_id TEXT
             );
 
             CREATE TABLE IF NOT EXISTS deliveries (
@@ -72,10 +203,46 @@ impl EventStore {
                 ON deliveries(event_id);
             CREATE INDEX IF NOT EXISTS idx_events_created_at
                 ON events(created_at);
+
+            CREATE TABLE IF NOT EXISTS subscriptions (
+                key            TEXT PRIMARY KEY,
+                kind           TEXT NOT NULL,
+                type_pattern   TEXT NOT NULL,
+                source_pattern TEXT,
+                cursor         INTEGER NOT NULL DEFAULT 0,
+                created_at     TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS pending_acks (
+                id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                sub_key      TEXT NOT NULL,
+                event_rowid  INTEGER NOT NULL,
+                event_id     TEXT NOT NULL,
+                attempts     INTEGER NOT NULL DEFAULT 1,
+                next_retry   TEXT NOT NULL,
+                UNIQUE(sub_key, event_rowid)
+            );
+            CREATE INDEX IF NOT EXISTS idx_pending_acks_retry
+                ON pending_acks(next_retry);
             ",
         )
         .map_err(|e| format!("Failed to initialize event store schema: {}", e))?;
 
+        // Schema migration: keep extension attributes, and index the
+        // correlation ID for causal chain queries.
+        if conn.prepare("SELECT extensions FROM events LIMIT 0").is_err() {
+            conn.execute_batch(
+                "ALTER TABLE events ADD COLUMN extensions TEXT;
+                 ALTER TABLE events ADD COLUMN correlation_id TEXT;",
+            )
+            .map_err(|e| format!("Migration error: {}", e))?;
+            log::info!("Event store: migrated schema (added extensions, correlation_id)");
+        }
+        conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_events_correlation ON events(correlation_id);",
+        )
+        .map_err(|e| format!("Failed to create correlation index: {}", e))?;
+
         Ok(Self {
             db: Mutex::new(conn),
         })
@@ -87,10 +254,19 @@ impl EventStore {
         let data_json =
             serde_json::to_string(&event.data).map_err(|e| format!("Serialize data: {}", e))?;
         let time_str = event.time.to_rfc3339();
+        let extensions_json = if event.extensions.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&event.extensions)
+                    .map_err(|e| format!("Serialize extensions: {}", e))?,
+            )
+        };
 
         db.execute(
-            "INSERT OR IGNORE INTO events (id, source, event_type, subject, data, time)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT OR IGNORE INTO events
+                 (id, source, event_type, subject, data, time, extensions, correlation_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 event.id,
                 event.source,
@@ -98,6 +274,8 @@ impl EventStore {
                 event.subject,
                 data_json,
                 time_str,
+                extensions_json,
+                event.correlation_id(),
             ],
         )
         .map_err(|e| format!("Insert event: {}", e))?;
@@ -105,6 +283,340 @@ impl EventStore {
         Ok(())
     }
 
+    /// Persist a CloudEvent and return its rowid, the position durable
+    /// subscription cursors refer to.
+    pub fn persist_event(&self, event: &CloudEvent) -> Result<i64, String> {
+        self.insert_event(event)?;
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.query_row(
+            "SELECT rowid FROM events WHERE id = ?1",
+            params![event.id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query event rowid: {}", e))
+    }
+
+    /// Events stored after the given rowid, oldest first.
+    pub fn events_after(&self, cursor: i64) -> Result<Vec<(i64, CloudEvent)>, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let mut stmt = db
+            .prepare(
+                "SELECT rowid, id, source, event_type, subject, data, time, extensions
+                 FROM events WHERE rowid > ?1 ORDER BY rowid ASC",
+            )
+            .map_err(|e| format!("Prepare events after: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![cursor], |row| {
+                let data_json: String = row.get(5)?;
+                let event_json = event_json(
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    &data_json,
+                    row.get(6)?,
+                    row.get(7)?,
+                );
+                Ok((row.get::<_, i64>(0)?, event_json))
+            })
+            .map_err(|e| format!("Query events after: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect events after: {}", e))?;
+
+        rows.into_iter()
+            .map(|(rowid, json)| Self::parse_event_json(&json).map(|event| (rowid, event)))
+            .collect()
+    }
+
+    /// Stored events in a range, oldest first, with their sequence numbers.
+    /// How far back events go depends on the retention policy.
+    pub fn events_in_range(&self, range: &ReplayRange) -> Result<Vec<(i64, CloudEvent)>, String> {
+        let limit = range.limit.unwrap_or(1000).min(10_000);
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let mut stmt = db
+            .prepare(
+                "SELECT rowid, id, source, event_type, subject, data, time, extensions
+                 FROM events
+                 WHERE rowid > ?1 AND (?2 IS NULL OR time >= ?2) AND (?3 IS NULL OR time < ?3)
+                 ORDER BY rowid ASC LIMIT ?4",
+            )
+            .map_err(|e| format!("Prepare events in range: {}", e))?;
+
+        let rows = stmt
+            .query_map(
+                params![
+                    range.after_seq.unwrap_or(0),
+                    range.since.map(|t| t.to_rfc3339()),
+                    range.until.map(|t| t.to_rfc3339()),
+                    limit as i64,
+                ],
+                |row| {
+                    let data_json: String = row.get(5)?;
+                    let event_json = event_json(
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        &data_json,
+                        row.get(6)?,
+                        row.get(7)?,
+                    );
+                    Ok((row.get::<_, i64>(0)?, event_json))
+                },
+            )
+            .map_err(|e| format!("Query events in range: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect events in range: {}", e))?;
+
+        rows.into_iter()
+            .map(|(rowid, json)| Self::parse_event_json(&json).map(|event| (rowid, event)))
+            .collect()
+    }
+
+    /// Record a durable subscription. A new one starts at the latest stored
+    /// event; an existing one keeps its cursor. Returns the cursor.
+    pub fn save_subscription(
+        &self,
+        key: &str,
+        kind: &SubscriberKind,
+        type_pattern: &str,
+        source_pattern: Option<&str>,
+    ) -> Result<i64, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let kind_json =
+            serde_json::to_string(kind).map_err(|e| format!("Serialize kind: {}", e))?;
+        db.execute(
+            "INSERT OR IGNORE INTO subscriptions (key, kind, type_pattern, source_pattern, cursor)
+             VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(rowid), 0) FROM events))",
+            params![key, kind_json, type_pattern, source_pattern],
+        )
+        .map_err(|e| format!("Insert subscription: {}", e))?;
+        db.query_row(
+            "SELECT cursor FROM subscriptions WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Query subscription cursor: {}", e))
+    }
+
+    /// All durable subscriptions.
+    pub fn list_subscriptions(&self) -> Result<Vec<StoredSubscription>, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let mut stmt = db
+            .prepare(
+                "SELECT key, kind, type_pattern, source_pattern, cursor
+                 FROM subscriptions ORDER BY created_at ASC",
+            )
+            .map_err(|e| format!("Prepare subscriptions: {}", e))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })
+            .map_err(|e| format!("Query subscriptions: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect subscriptions: {}", e))?;
+
+        // Rows with an unreadable kind are skipped rather than failing the load
+        Ok(rows
+            .into_iter()
+            .filter_map(|(key, kind_json, type_pattern, source_pattern, cursor)| {
+                let kind = serde_json::from_str(&kind_json).ok()?;
+                Some(StoredSubscription {
+                    key,
+                    kind,
+                    type_pattern,
+                    source_pattern,
+                    cursor,
+                })
+            })
+            .collect())
+    }
+
+    /// Move a subscription's cursor forward. Never moves it back.
+    pub fn advance_subscription(&self, key: &str, cursor: i64) -> Result<(), String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.execute(
+            "UPDATE subscriptions SET cursor = MAX(cursor, ?1) WHERE key = ?2",
+            params![cursor, key],
+        )
+        .map_err(|e| format!("Advance subscription: {}", e))?;
+        Ok(())
+    }
+
+    /// Delete a durable subscription and its unacknowledged events.
+    pub fn delete_subscription(&self, key: &str) -> Result<(), String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.execute("DELETE FROM subscriptions WHERE key = ?1", params![key])
+            .map_err(|e| format!("Delete subscription: {}", e))?;
+        db.execute("DELETE FROM pending_acks WHERE sub_key = ?1", params![key])
+            .map_err(|e| format!("Delete pending acks: {}", e))?;
+        Ok(())
+    }
+
+    /// A single stored event by rowid.
+    pub fn event_at(&self, rowid: i64) -> Result<Option<CloudEvent>, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let json = db
+            .query_row(
+                "SELECT id, source, event_type, subject, data, time, extensions
+                 FROM events WHERE rowid = ?1",
+                params![rowid],
+                |row| {
+                    let data_json: String = row.get(4)?;
+                    Ok(event_json(
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        &data_json,
+                        row.get(5)?,
+                        row.get(6)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| format!("Query event: {}", e))?;
+        json.map(|j| Self::parse_event_json(&j)).transpose()
+    }
+
+    /// A single stored event by ID.
+    pub fn event_by_id(&self, id: &str) -> Result<Option<CloudEvent>, String> {
+        let rowid: Option<i64> = {
+            let db = self.db.lock().map_err(|e| e.to_string())?;
+            db.query_row("SELECT rowid FROM events WHERE id = ?1", params![id], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(|e| format!("Query event: {}", e))?
+        };
+        match rowid {
+            Some(rowid) => self.event_at(rowid),
+            None => Ok(None),
+        }
+    }
+
+    /// Stored events sharing a correlation ID, oldest first.
+    pub fn events_by_correlation(
+        &self,
+        correlation_id: &str,
+        limit: usize,
+    ) -> Result<Vec<CloudEvent>, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let mut stmt = db
+            .prepare(
+                "SELECT id, source, event_type, subject, data, time, extensions
+                 FROM events WHERE correlation_id = ?1 ORDER BY rowid ASC LIMIT ?2",
+            )
+            .map_err(|e| format!("Prepare correlated events: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![correlation_id, limit as i64], |row| {
+                let data_json: String = row.get(4)?;
+                Ok(event_json(
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    &data_json,
+                    row.get(5)?,
+                    row.get(6)?,
+                ))
+            })
+            .map_err(|e| format!("Query correlated events: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect correlated events: {}", e))?;
+
+        rows.iter().map(|json| Self::parse_event_json(json)).collect()
+    }
+
+    /// Record that an event was sent to a subscriber that must acknowledge
+    /// it. Unacknowledged events are sent again from `next_retry`.
+    pub fn track_ack(
+        &self,
+        key: &str,
+        event_rowid: i64,
+        event_id: &str,
+        next_retry: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.execute(
+            "INSERT OR IGNORE INTO pending_acks (sub_key, event_rowid, event_id, next_retry)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![key, event_rowid, event_id, next_retry.to_rfc3339()],
+        )
+        .map_err(|e| format!("Insert pending ack: {}", e))?;
+        Ok(())
+    }
+
+    /// Acknowledge an event for every subscription whose key starts with
+    /// `key_prefix`. Returns how many pending deliveries were cleared.
+    pub fn ack(&self, key_prefix: &str, event_id: &str) -> Result<usize, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.execute(
+            "DELETE FROM pending_acks
+             WHERE event_id = ?1 AND substr(sub_key, 1, length(?2)) = ?2",
+            params![event_id, key_prefix],
+        )
+        .map_err(|e| format!("Ack event: {}", e))
+    }
+
+    /// A subscription's unacknowledged deliveries whose retry time has passed.
+    pub fn due_acks(&self, key: &str, limit: usize) -> Result<Vec<PendingAck>, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let now = Utc::now().to_rfc3339();
+        let mut stmt = db
+            .prepare(
+                "SELECT id, sub_key, event_rowid, attempts FROM pending_acks
+                 WHERE sub_key = ?1 AND next_retry <= ?2 ORDER BY next_retry ASC LIMIT ?3",
+            )
+            .map_err(|e| format!("Prepare due acks: {}", e))?;
+        let rows = stmt
+            .query_map(params![key, now, limit as i64], |row| {
+                Ok(PendingAck {
+                    id: row.get(0)?,
+                    key: row.get(1)?,
+                    event_rowid: row.get(2)?,
+                    attempts: row.get(3)?,
+                })
+            })
+            .map_err(|e| format!("Query due acks: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect due acks: {}", e))?;
+        Ok(rows)
+    }
+
+    /// Record another delivery attempt and when to try again.
+    pub fn reschedule_ack(
+        &self,
+        id: i64,
+        attempts: u32,
+        next_retry: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.execute(
+            "UPDATE pending_acks SET attempts = ?1, next_retry = ?2 WHERE id = ?3",
+            params![attempts, next_retry.to_rfc3339(), id],
+        )
+        .map_err(|e| format!("Reschedule ack: {}", e))?;
+        Ok(())
+    }
+
+    /// Stop waiting for an acknowledgement.
+    pub fn drop_ack(&self, id: i64) -> Result<(), String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        db.execute("DELETE FROM pending_acks WHERE id = ?1", params![id])
+            .map_err(|e| format!("Drop ack: {}", e))?;
+        Ok(())
+    }
+
     /// Create delivery rows for each route action, all with status=pending and next_retry=now.
     pub fn insert_deliveries(
         &self,
@@ -138,7 +650,7 @@ impl EventStore {
         let mut stmt = db
             .prepare(
                 "SELECT d.id, d.event_id, d.action, d.status, d.attempts, d.last_error,
-                        e.source, e.event_type, e.subject, e.data, e.time
+                        e.source, e.event_type, e.subject, e.data, e.time, e.extensions
                  FROM deliveries d
                  JOIN events e ON e.id = d.event_id
                  WHERE d.status = 'pending' AND d.next_retry <= ?1
@@ -151,21 +663,17 @@ impl EventStore {
             .query_map(params![now, batch_size as i64], |row| {
                 let action_json: String = row.get(2)?;
                 let data_json: String = row.get(9)?;
-                let time_str: String = row.get(10)?;
 
                 // Reconstruct a minimal CloudEvent JSON for the executor
-                let event_json = serde_json::json!({
-                    "specversion": "1.0",
-                    "id": row.get::<_, String>(1)?,
-                    "source": row.get::<_, String>(6)?,
-                    "type": row.get::<_, String>(7)?,
-                    "subject": row.get::<_, Option<String>>(8)?,
-                    "time": time_str,
-                    "data": serde_json::from_str::<serde_json::Value>(&data_json)
-                        .unwrap_or(serde_json::Value::Null),
-                    "datacontenttype": "application/json",
-                })
-                .to_string();
+                let event_json = event_json(
+                    row.get(1)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                    row.get(8)?,
+                    &data_json,
+                    row.get(10)?,
+                    row.get(11)?,
+                );
 
                 Ok((
                     DeliveryRow {
@@ -267,19 +775,135 @@ impl EventStore {
     pub fn cleanup_old(&self, ttl: Duration) -> Result<usize, String> {
         let db = self.db.lock().map_err(|e| e.to_string())?;
         let cutoff = (Utc::now() - ttl).to_rfc3339();
+        let deleted = delete_events(&db, "created_at < ?1", params![cutoff])?;
+        drop_orphaned_acks(&db)?;
+        Ok(deleted)
+    }
 
-        // Delete deliveries first (foreign key)
-        db.execute(
-            "DELETE FROM deliveries WHERE event_id IN (SELECT id FROM events WHERE created_at < ?1)",
-            params![cutoff],
-        )
-        .map_err(|e| format!("Cleanup deliveries: {}", e))?;
+    /// Apply a retention policy: drop events past the age limit, then the
+    /// oldest events until the store is within the count and size limits.
+    /// Events whose route actions haven't settled are only dropped by age.
+    pub fn compact(&self, policy: &RetentionPolicy) -> Result<CompactionReport, String> {
+        let mut report = CompactionReport {
+            expired: match policy.max_age() {
+                Some(ttl) => self.cleanup_old(ttl)?,
+                None => 0,
+            },
+            trimmed: 0,
+        };
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+
+        if policy.max_events > 0 {
+            let cutoff: Option<i64> = db
+                .query_row(
+                    &format!(
+                        "SELECT rowid FROM events WHERE NOT {UNSETTLED_SQL}
+                         ORDER BY rowid DESC LIMIT 1 OFFSET ?1"
+                    ),
+                    params![policy.max_events as i64],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("Find count cutoff: {}", e))?;
+            if let Some(cutoff) = cutoff {
+                report.trimmed += delete_events(
+                    &db,
+                    &format!("rowid <= ?1 AND NOT {UNSETTLED_SQL}"),
+                    params![cutoff],
+                )?;
+            }
+        }
 
-        let deleted = db
-            .execute("DELETE FROM events WHERE created_at < ?1", params![cutoff])
-            .map_err(|e| format!("Cleanup events: {}", e))?;
+        if policy.max_bytes > 0 {
+            let cutoff: Option<i64> = db
+                .query_row(
+                    &format!(
+                        "SELECT rowid FROM (
+                             SELECT rowid, SUM({EVENT_SIZE_SQL}) OVER (ORDER BY rowid DESC) AS total
+                             FROM events WHERE NOT {UNSETTLED_SQL}
+                         ) WHERE total > ?1 ORDER BY rowid DESC LIMIT 1"
+                    ),
+                    params![policy.max_bytes as i64],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("Find size cutoff: {}", e))?;
+            if let Some(cutoff) = cutoff {
+                report.trimmed += delete_events(
+                    &db,
+                    &format!("rowid <= ?1 AND NOT {UNSETTLED_SQL}"),
+                    params![cutoff],
+                )?;
+            }
+        }
 
-        Ok(deleted)
+        drop_orphaned_acks(&db)?;
+        if report.expired + report.trimmed > 0 {
+            // Fold the WAL back into the database so freed pages get reused.
+            db.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                .map_err(|e| format!("Checkpoint: {}", e))?;
+        }
+        Ok(report)
+    }
+
+    /// Current event count and size, for the retention settings screen.
+    pub fn stats(&self) -> Result<StoreStats, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let (events, event_bytes, oldest, newest) = db
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*), coalesce(SUM({EVENT_SIZE_SQL}), 0), MIN(created_at), MAX(created_at)
+                     FROM events"
+                ),
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                    ))
+                },
+            )
+            .map_err(|e| format!("Event stats: {}", e))?;
+        let (pending_deliveries, dead_letters) = db
+            .query_row(
+                "SELECT coalesce(SUM(status = 'pending'), 0), coalesce(SUM(status = 'dead_letter'), 0)
+                 FROM deliveries",
+                [],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .map_err(|e| format!("Delivery stats: {}", e))?;
+        let file_bytes: i64 = db
+            .query_row(
+                "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Database size: {}", e))?;
+
+        Ok(StoreStats {
+            events: events as usize,
+            event_bytes: event_bytes as u64,
+            file_bytes: file_bytes as u64,
+            oldest,
+            newest,
+            pending_deliveries: pending_deliveries as usize,
+            dead_letters: dead_letters as usize,
+        })
+    }
+
+    /// Count deliveries waiting to run or be retried.
+    pub fn pending_delivery_count(&self) -> Result<usize, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let count: i64 = db
+            .query_row(
+                "SELECT COUNT(*) FROM deliveries WHERE status = 'pending'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Count pending deliveries: {}", e))?;
+        Ok(count as usize)
     }
 
     /// Count deliveries in the dead_letter state.
@@ -329,12 +953,161 @@ impl EventStore {
         Ok(rows)
     }
 
+    /// Make every pending ack due now, skipping the ack timeout.
+    #[cfg(test)]
+    pub(crate) fn make_acks_due(&self) {
+        let db = self.db.lock().unwrap();
+        let past = (Utc::now() - Duration::from_secs(3600)).to_rfc3339();
+        db.execute("UPDATE pending_acks SET next_retry = ?1", params![past])
+            .unwrap();
+    }
+
+    /// List the dead letter queue, most recent failure first.
+    pub fn list_dead_letters(&self, limit: usize) -> Result<Vec<DeadLetter>, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let mut stmt = db
+            .prepare(
+                "SELECT d.id, d.event_id, e.event_type, e.source, d.action, d.attempts,
+                        d.last_error, d.updated_at
+                 FROM deliveries d
+                 JOIN events e ON e.id = d.event_id
+                 WHERE d.status = 'dead_letter'
+                 ORDER BY d.updated_at DESC LIMIT ?1",
+            )
+            .map_err(|e| format!("Prepare dead letter list: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![limit as i64], |row| {
+                let action_json: String = row.get(4)?;
+                Ok(DeadLetter {
+                    id: row.get(0)?,
+                    event_id: row.get(1)?,
+                    event_type: row.get(2)?,
+                    source: row.get(3)?,
+                    action: serde_json::from_str(&action_json).unwrap_or_else(|_| {
+                        RouteAction::EmitFrontend {
+                            channel: "__invalid__".into(),
+                        }
+                    }),
+                    attempts: row.get(5)?,
+                    last_error: row.get(6)?,
+                    failed_at: row.get(7)?,
+                })
+            })
+            .map_err(|e| format!("Query dead letter list: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect dead letter list: {}", e))?;
+
+        Ok(rows)
+    }
+
+    /// Move dead-lettered deliveries back to pending with a fresh set of
+    /// attempts. The retry worker picks them up on its next tick. Returns
+    /// how many were requeued.
+    pub fn retry_dead_letters(&self, ids: &[i64]) -> Result<usize, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        let now = Utc::now().to_rfc3339();
+        let mut requeued = 0;
+        for id in ids {
+            requeued += db
+                .execute(
+                    "UPDATE deliveries SET status = 'pending', attempts = 0, next_retry = ?1,
+                     updated_at = ?1
+                     WHERE id = ?2 AND status = 'dead_letter'",
+                    params![now, id],
+                )
+                .map_err(|e| format!("Retry dead letter: {}", e))?;
+        }
+        Ok(requeued)
+    }
+
+    /// Delete dead-lettered deliveries — the given ones, or all of them.
+    /// Returns how many were deleted.
+    pub fn purge_dead_letters(&self, ids: Option<&[i64]>) -> Result<usize, String> {
+        let db = self.db.lock().map_err(|e| e.to_string())?;
+        match ids {
+            None => db
+                .execute("DELETE FROM deliveries WHERE status = 'dead_letter'", [])
+                .map_err(|e| format!("Purge dead letters: {}", e)),
+            Some(ids) => {
+                let mut purged = 0;
+                for id in ids {
+                    purged += db
+                        .execute(
+                            "DELETE FROM deliveries WHERE id = ?1 AND status = 'dead_letter'",
+                            params![id],
+                        )
+                        .map_err(|e| format!("Purge dead letter: {}", e))?;
+                }
+                Ok(purged)
+            }
+        }
+    }
+
     /// Reconstruct a CloudEvent from a JSON string (used by the retry worker).
     pub fn parse_event_json(json: &str) -> Result<CloudEvent, String> {
         serde_json::from_str(json).map_err(|e| format!("Parse event JSON: {}", e))
     }
 }
 
+/// Delete the events matching `condition`, and their deliveries first
+/// (foreign key). Returns the number of events deleted.
+fn delete_events(
+    db: &Connection,
+    condition: &str,
+    params: impl rusqlite::Params + Clone,
+) -> Result<usize, String> {
+    db.execute(
+        &format!("DELETE FROM deliveries WHERE event_id IN (SELECT id FROM events WHERE {condition})"),
+        params.clone(),
+    )
+    .map_err(|e| format!("Cleanup deliveries: {}", e))?;
+    db.execute(&format!("DELETE FROM events WHERE {condition}"), params)
+        .map_err(|e| format!("Cleanup events: {}", e))
+}
+
+/// Drop acks awaited for events that no longer exist.
+fn drop_orphaned_acks(db: &Connection) -> Result<(), String> {
+    db.execute(
+        "DELETE FROM pending_acks WHERE event_rowid NOT IN (SELECT rowid FROM events)",
+        [],
+    )
+    .map_err(|e| format!("Cleanup pending acks: {}", e))?;
+    Ok(())
+}
+
+/// Build a minimal CloudEvent JSON document from the stored columns.
+/// Extension attributes are merged in at the top level.
+fn event_json(
+    id: String,
+    source: String,
+    event_type: String,
+    subject: Option<String>,
+    data_json: &str,
+    time: String,
+    extensions_json: Option<String>,
+) -> String {
+    let mut event = serde_json::json!({
+        "specversion": "1.0",
+        "id": id,
+        "source": source,
+        "type": event_type,
+        "subject": subject,
+        "time": time,
+        "data": serde_json::from_str::<serde_json::Value>(data_json)
+            .unwrap_or(serde_json::Value::Null),
+        "datacontenttype": "application/json",
+    });
+    let extensions = extensions_json
+        .and_then(|json| serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&json).ok());
+    if let (Some(extensions), Some(fields)) = (extensions, event.as_object_mut()) {
+        for (name, value) in extensions {
+            fields.entry(name).or_insert(value);
+        }
+    }
+    event.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -452,6 +1225,47 @@ mod tests {
         assert_eq!(dead[0].attempts, 5);
     }
 
+    #[test]
+    fn dead_letters_can_be_retried_and_purged() {
+        let (_tmp, store) = make_store();
+        for id in ["evt9", "evt10"] {
+            store.insert_event(&make_event(id, "test.event")).unwrap();
+            store
+                .insert_deliveries(
+                    id,
+                    vec![RouteAction::EmitFrontend {
+                        channel: "ch".into(),
+                    }],
+                )
+                .unwrap();
+        }
+        {
+            let db = store.db.lock().unwrap();
+            db.execute(
+                "UPDATE deliveries SET status = 'dead_letter', attempts = 5, last_error = 'boom'",
+                [],
+            )
+            .unwrap();
+        }
+
+        let dead = store.list_dead_letters(10).unwrap();
+        assert_eq!(dead.len(), 2);
+        assert_eq!(dead[0].event_type, "test.event");
+        assert_eq!(dead[0].last_error.as_deref(), Some("boom"));
+
+        // Retrying requeues with fresh attempts; already-pending IDs are ignored
+        let retry_id = dead[0].id;
+        assert_eq!(store.retry_dead_letters(&[retry_id]).unwrap(), 1);
+        assert_eq!(store.retry_dead_letters(&[retry_id]).unwrap(), 0);
+        let claimed = store.claim_ready(10).unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].0.id, retry_id);
+        assert_eq!(claimed[0].0.attempts, 0);
+
+        assert_eq!(store.purge_dead_letters(None).unwrap(), 1);
+        assert_eq!(store.dead_letter_count().unwrap(), 0);
+    }
+
     #[test]
     fn cleanup_old_removes_expired() {
         let (_tmp, store) = make_store();
@@ -482,6 +1296,45 @@ mod tests {
         assert_eq!(purged, 1);
     }
 
+    #[test]
+    fn compact_trims_to_count_and_size_but_keeps_unsettled() {
+        let (_tmp, store) = make_store();
+        for i in 0..10 {
+            store.insert_event(&make_event(&format!("e{i}"), "test.event")).unwrap();
+        }
+        store
+            .insert_deliveries(
+                "e0",
+                vec![RouteAction::EmitFrontend {
+                    channel: "ch".into(),
+                }],
+            )
+            .unwrap();
+
+        let policy = RetentionPolicy {
+            max_events: 5,
+            ..Default::default()
+        };
+        let report = store.compact(&policy).unwrap();
+        assert_eq!((report.expired, report.trimmed), (0, 4));
+        let stats = store.stats().unwrap();
+        // e0 stays: its delivery is still pending.
+        assert_eq!(stats.events, 6);
+        assert_eq!(stats.pending_deliveries, 1);
+        assert!(store.event_at(1).unwrap().is_some());
+
+        // Timestamps vary slightly in length, so leave half an event of slack.
+        let per_event = stats.event_bytes / stats.events as u64;
+        let policy = RetentionPolicy {
+            max_bytes: per_event * 5 / 2,
+            ..Default::default()
+        };
+        let report = store.compact(&policy).unwrap();
+        assert_eq!(report.trimmed, 3);
+        assert_eq!(store.stats().unwrap().events, 3);
+        assert!(store.stats().unwrap().file_bytes > 0);
+    }
+
     #[test]
     fn duplicate_event_insert_ignored() {
         let (_tmp, store) = make_store();
@@ -491,4 +1344,38 @@ mod tests {
         // Second insert should not error (INSERT OR IGNORE)
         store.insert_event(&event).unwrap();
     }
+
+    #[test]
+    fn subscription_cursor_survives_reopen() {
+        let tmp = TempDir::new().unwrap();
+        let kind = SubscriberKind::Plugin {
+            plugin_id: "p1".into(),
+        };
+        {
+            let store = EventStore::new(tmp.path()).unwrap();
+            let before = store.persist_event(&make_event("evt6", "test.event")).unwrap();
+            // A new subscription starts after everything already stored
+            assert_eq!(store.save_subscription("k", &kind, "test.*", None).unwrap(), before);
+            let missed = store.persist_event(&make_event("evt7", "test.event")).unwrap();
+            store.advance_subscription("k", missed).unwrap();
+            store.advance_subscription("k", before).unwrap();
+            store.persist_event(&make_event("evt8", "test.event")).unwrap();
+        }
+
+        let store = EventStore::new(tmp.path()).unwrap();
+        let subs = store.list_subscriptions().unwrap();
+        assert_eq!(subs.len(), 1);
+        assert!(matches!(&subs[0].kind, SubscriberKind::Plugin { plugin_id } if plugin_id == "p1"));
+        // Saving again keeps the existing cursor
+        let cursor = store.save_subscription("k", &kind, "test.*", None).unwrap();
+        assert_eq!(cursor, subs[0].cursor);
+
+        let replay = store.events_after(cursor).unwrap();
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].1.id, "evt8");
+        assert_eq!(replay[0].1.data["key"], "value");
+
+        store.delete_subscription("k").unwrap();
+        assert!(store.list_subscriptions().unwrap().is_empty());
+    }
 }
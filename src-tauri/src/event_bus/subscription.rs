@@ -1,10 +1,15 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use super::cloud_event::CloudEvent;
+use super::predicate::Predicate;
 
 /// Identifies who is subscribing to events.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum SubscriberKind {
     Extension { ext_id: String },
@@ -13,34 +18,186 @@ pub enum SubscriberKind {
     Internal,
 }
 
+impl SubscriberKind {
+    /// Identity of a durable subscription: extension and plugin subscriptions
+    /// survive restarts and are matched back up by subscriber and patterns.
+    /// Frontend and internal subscriptions are transient.
+    pub fn durable_key(
+        &self,
+        type_pattern: &str,
+        source_pattern: Option<&str>,
+        predicate: Option<&Predicate>,
+    ) -> Option<String> {
+        self.durable_prefix().map(|prefix| {
+            let mut key = format!("{}{}|{}", prefix, type_pattern, source_pattern.unwrap_or(""));
+            if let Some(predicate) = predicate {
+                key.push('|');
+                key.push_str(predicate.as_str());
+            }
+            key
+        })
+    }
+
+    /// Prefix shared by every durable key of this subscriber.
+    pub fn durable_prefix(&self) -> Option<String> {
+        match self {
+            SubscriberKind::Extension { ext_id } => Some(format!("extension:{}|", ext_id)),
+            SubscriberKind::Plugin { plugin_id } => Some(format!("plugin:{}|", plugin_id)),
+            SubscriberKind::Frontend { .. } | SubscriberKind::Internal => None,
+        }
+    }
+}
+
 /// A subscription registered on the event bus.
 pub struct Subscription {
     pub id: String,
     pub type_pattern: glob::Pattern,
     pub source_pattern: Option<glob::Pattern>,
+    /// Only events whose data satisfies this are delivered.
+    pub predicate: Option<Predicate>,
     pub kind: SubscriberKind,
-    pub sender: mpsc::UnboundedSender<CloudEvent>,
+    pub sender: EventSender,
+    /// Set for extension and plugin subscriptions persisted in the event store.
+    pub durable_key: Option<String>,
+    /// The subscriber acknowledges each event; unacknowledged events are
+    /// redelivered. Only meaningful for durable subscriptions.
+    pub ack: bool,
 }
 
-impl Subscription {
-    /// Check if a CloudEvent matches this subscription's patterns.
-    pub fn matches(&self, event: &CloudEvent) -> bool {
-        if !self.type_pattern.matches(&event.event_type) {
-            return false;
+/// How events are redelivered to subscribers that haven't acknowledged them.
+/// Stored in settings as `event_redelivery`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedeliveryPolicy {
+    /// Seconds to wait for an ack before redelivering. Doubles with each attempt.
+    pub ack_timeout_secs: u64,
+    /// Upper bound on the wait between attempts.
+    pub max_backoff_secs: u64,
+    /// Deliveries, including the first, before the event is given up on.
+    pub max_attempts: u32,
+}
+
+impl Default for RedeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            ack_timeout_secs: 30,
+            max_backoff_secs: 600,
+            max_attempts: 5,
         }
-        if let Some(ref sp) = self.source_pattern {
-            if !sp.matches(&event.source) {
-                return false;
-            }
+    }
+}
+
+impl RedeliveryPolicy {
+    /// How long to wait for an ack after the given number of deliveries.
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 1u64 << attempts.saturating_sub(1).min(20);
+        let secs = self
+            .ack_timeout_secs
+            .saturating_mul(factor)
+            .min(self.max_backoff_secs);
+        Duration::from_secs(secs.max(1))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ack_timeout_secs == 0 {
+            return Err("ack_timeout_secs must be at least 1".to_string());
+        }
+        if self.max_attempts == 0 {
+            return Err("max_attempts must be at least 1".to_string());
+        }
+        if self.max_backoff_secs < self.ack_timeout_secs {
+            return Err("max_backoff_secs must be at least ack_timeout_secs".to_string());
         }
-        true
+        Ok(())
+    }
+}
+
+impl Subscription {
+    /// Check if a CloudEvent matches this subscription's patterns and predicate.
+    pub fn matches(&self, event: &CloudEvent) -> bool {
+        patterns_match(&self.type_pattern, self.source_pattern.as_ref(), event)
+            && self.predicate.as_ref().map_or(true, |p| p.matches(&event.data))
     }
 
     /// Attempt to send an event to this subscriber. Returns false if the
     /// channel is closed (subscriber disconnected).
     pub fn try_send(&self, event: &CloudEvent) -> bool {
-        self.sender.send(event.clone()).is_ok()
+        self.sender.send(event.clone())
+    }
+}
+
+/// Create a subscription channel that counts the events waiting in it.
+pub fn event_channel() -> (EventSender, EventReceiver) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let queued = Arc::new(AtomicUsize::new(0));
+    (
+        EventSender {
+            tx,
+            queued: queued.clone(),
+        },
+        EventReceiver { rx, queued },
+    )
+}
+
+/// Sending end of a subscription channel.
+pub struct EventSender {
+    tx: mpsc::UnboundedSender<CloudEvent>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl EventSender {
+    /// Queue an event. Returns false if the receiver is gone.
+    pub fn send(&self, event: CloudEvent) -> bool {
+        let sent = self.tx.send(event).is_ok();
+        if sent {
+            self.queued.fetch_add(1, Ordering::Relaxed);
+        }
+        sent
     }
+
+    /// Events sent but not yet received.
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+}
+
+/// Receiving end of a subscription channel.
+pub struct EventReceiver {
+    rx: mpsc::UnboundedReceiver<CloudEvent>,
+    queued: Arc<AtomicUsize>,
+}
+
+impl EventReceiver {
+    pub async fn recv(&mut self) -> Option<CloudEvent> {
+        let event = self.rx.recv().await;
+        if event.is_some() {
+            self.queued.fetch_sub(1, Ordering::Relaxed);
+        }
+        event
+    }
+
+    pub fn try_recv(&mut self) -> Result<CloudEvent, mpsc::error::TryRecvError> {
+        let event = self.rx.try_recv()?;
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+        Ok(event)
+    }
+}
+
+/// Check a CloudEvent against a type pattern and optional source pattern.
+pub fn patterns_match(
+    type_pattern: &glob::Pattern,
+    source_pattern: Option<&glob::Pattern>,
+    event: &CloudEvent,
+) -> bool {
+    if !type_pattern.matches(&event.event_type) {
+        return false;
+    }
+    if let Some(sp) = source_pattern {
+        if !sp.matches(&event.source) {
+            return false;
+        }
+    }
+    true
 }
 
 /// Parse a glob pattern string, returning a descriptive error.
@@ -63,13 +220,16 @@ mod tests {
 
     #[test]
     fn type_pattern_matching() {
-        let (tx, _rx) = mpsc::unbounded_channel();
+        let (tx, _rx) = event_channel();
         let sub = Subscription {
             id: "sub_1".into(),
             type_pattern: parse_pattern("com.github.*").unwrap(),
             source_pattern: None,
+            predicate: None,
             kind: SubscriberKind::Internal,
             sender: tx,
+            durable_key: None,
+            ack: false,
         };
 
         assert!(sub.matches(&make_event("com.github.push", "nexus://core")));
@@ -83,13 +243,16 @@ mod tests {
 
     #[test]
     fn wildcard_matches_everything() {
-        let (tx, _rx) = mpsc::unbounded_channel();
+        let (tx, _rx) = event_channel();
         let sub = Subscription {
             id: "sub_2".into(),
             type_pattern: parse_pattern("*").unwrap(),
             source_pattern: None,
+            predicate: None,
             kind: SubscriberKind::Internal,
             sender: tx,
+            durable_key: None,
+            ack: false,
         };
 
         assert!(sub.matches(&make_event("anything.at.all", "nexus://core")));
@@ -97,15 +260,18 @@ mod tests {
 
     #[test]
     fn source_pattern_filters() {
-        let (tx, _rx) = mpsc::unbounded_channel();
+        let (tx, _rx) = event_channel();
         let sub = Subscription {
             id: "sub_3".into(),
             type_pattern: parse_pattern("*").unwrap(),
             source_pattern: Some(parse_pattern("nexus://extension/*").unwrap()),
+            predicate: None,
             kind: SubscriberKind::Extension {
                 ext_id: "test".into(),
             },
             sender: tx,
+            durable_key: None,
+            ack: false,
         };
 
         assert!(sub.matches(&make_event("test", "nexus://extension/webhook-receiver")));
@@ -113,16 +279,36 @@ mod tests {
         assert!(!sub.matches(&make_event("test", "nexus://plugin/agent")));
     }
 
+    #[test]
+    fn redelivery_backoff_doubles_up_to_cap() {
+        let policy = RedeliveryPolicy {
+            ack_timeout_secs: 10,
+            max_backoff_secs: 60,
+            max_attempts: 5,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(10));
+        assert_eq!(policy.backoff(2), Duration::from_secs(20));
+        assert_eq!(policy.backoff(3), Duration::from_secs(40));
+        assert_eq!(policy.backoff(4), Duration::from_secs(60));
+        assert_eq!(policy.backoff(40), Duration::from_secs(60));
+        assert!(policy.validate().is_ok());
+        assert!(RedeliveryPolicy { max_attempts: 0, ..policy.clone() }.validate().is_err());
+        assert!(RedeliveryPolicy { max_backoff_secs: 5, ..policy }.validate().is_err());
+    }
+
     #[test]
     fn closed_channel_returns_false() {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = event_channel();
         drop(rx);
         let sub = Subscription {
             id: "sub_4".into(),
             type_pattern: parse_pattern("*").unwrap(),
             source_pattern: None,
+            predicate: None,
             kind: SubscriberKind::Internal,
             sender: tx,
+            durable_key: None,
+            ack: false,
         };
 
         let event = make_event("test", "nexus://core");
@@ -0,0 +1,395 @@
+//! Built-in scheduler that publishes user-defined events on cron schedules,
+//! so routing rules and subscribers can run periodic automations without a
+//! plugin. A timer named `backup.nightly` publishes `nexus.timer.backup.nightly`
+//! from `nexus://timer`, with the timer's data as event data.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Local, Utc};
+use croner::Cron;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::cloud_event::CloudEvent;
+use super::Dispatch;
+
+/// Source of timer events.
+pub const TIMER_SOURCE: &str = "nexus://timer";
+
+/// Prefix of timer event types.
+pub const TIMER_TYPE_PREFIX: &str = "nexus.timer.";
+
+/// A user-defined event published on a schedule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timer {
+    pub id: String,
+    /// Event type suffix: dot-separated lowercase words.
+    pub name: String,
+    /// Cron expression in local time: five fields, six with seconds first,
+    /// or a nickname like `@hourly`.
+    pub schedule: String,
+    #[serde(default)]
+    pub data: Value,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Timer {
+    pub fn event_type(&self) -> String {
+        format!("{}{}", TIMER_TYPE_PREFIX, self.name)
+    }
+
+    /// First time the schedule fires after `after`.
+    pub fn next_fire(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        parse_schedule(&self.schedule)
+            .ok()?
+            .find_next_occurrence(&after, false)
+            .ok()
+    }
+
+    fn event(&self) -> Result<CloudEvent, String> {
+        CloudEvent::builder()
+            .source(TIMER_SOURCE)
+            .event_type(self.event_type())
+            .subject(&self.id)
+            .data(self.data.clone())
+            .build()
+    }
+}
+
+fn parse_schedule(schedule: &str) -> Result<Cron, String> {
+    Cron::new(schedule)
+        .with_seconds_optional()
+        .parse()
+        .map_err(|e| format!("Invalid schedule '{}': {}", schedule, e))
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.split('.').all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid timer name '{}': use dot-separated lowercase words",
+            name
+        ))
+    }
+}
+
+/// Partial update for a timer.
+#[derive(Debug, Default, Deserialize)]
+pub struct TimerUpdate {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub schedule: Option<String>,
+    #[serde(default)]
+    pub data: Option<Value>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+/// File-based store of timers.
+pub struct TimerStore {
+    timers: Vec<Timer>,
+    path: std::path::PathBuf,
+}
+
+impl TimerStore {
+    /// Load timers from disk, or create an empty store if the file doesn't exist.
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join("event_timers.json");
+        let timers = crate::util::read_json_store(&path).unwrap_or_default();
+
+        Self { timers, path }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.timers)
+            .map_err(|e| format!("Failed to serialize timers: {}", e))?;
+        crate::util::atomic_write_with_backup(&self.path, json.as_bytes())
+            .map_err(|e| format!("Failed to write timers file: {}", e))?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> &[Timer] {
+        &self.timers
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Timer> {
+        self.timers.iter().find(|t| t.id == id)
+    }
+
+    /// Add an enabled timer. Returns the assigned ID.
+    pub fn create(&mut self, name: String, schedule: String, data: Value) -> Result<String, String> {
+        validate_name(&name)?;
+        parse_schedule(&schedule)?;
+        let id = format!("timer_{}", Uuid::new_v4().simple());
+        self.timers.push(Timer {
+            id: id.clone(),
+            name,
+            schedule,
+            data,
+            enabled: true,
+            created_at: Utc::now(),
+        });
+        self.save()?;
+        Ok(id)
+    }
+
+    /// Update an existing timer. Returns an error if not found.
+    pub fn update(&mut self, id: &str, update: TimerUpdate) -> Result<(), String> {
+        if let Some(name) = &update.name {
+            validate_name(name)?;
+        }
+        if let Some(schedule) = &update.schedule {
+            parse_schedule(schedule)?;
+        }
+        let timer = self
+            .timers
+            .iter_mut()
+            .find(|t| t.id == id)
+            .ok_or_else(|| format!("Timer '{}' not found", id))?;
+
+        if let Some(name) = update.name {
+            timer.name = name;
+        }
+        if let Some(schedule) = update.schedule {
+            timer.schedule = schedule;
+        }
+        if let Some(data) = update.data {
+            timer.data = data;
+        }
+        if let Some(enabled) = update.enabled {
+            timer.enabled = enabled;
+        }
+
+        self.save()
+    }
+
+    /// Delete a timer by ID.
+    pub fn delete(&mut self, id: &str) -> Result<(), String> {
+        let len_before = self.timers.len();
+        self.timers.retain(|t| t.id != id);
+        if self.timers.len() == len_before {
+            return Err(format!("Timer '{}' not found", id));
+        }
+        self.save()
+    }
+}
+
+/// A timer with when it fires next, for the UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimerView {
+    #[serde(flatten)]
+    pub timer: Timer,
+    /// None when disabled.
+    pub next_fire: Option<DateTime<Utc>>,
+}
+
+/// Owns the timers and the task that fires them. Managed as Tauri state;
+/// every change restarts the task with the new set of timers.
+pub struct TimerScheduler {
+    store: Mutex<TimerStore>,
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl TimerScheduler {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            store: Mutex::new(TimerStore::load(data_dir)),
+            task: Mutex::new(None),
+        }
+    }
+
+    fn store(&self) -> std::sync::MutexGuard<'_, TimerStore> {
+        self.store.lock().expect("timer store lock poisoned")
+    }
+
+    pub fn list(&self) -> Vec<TimerView> {
+        let now = Local::now();
+        self.store()
+            .list()
+            .iter()
+            .map(|timer| TimerView {
+                next_fire: timer
+                    .enabled
+                    .then(|| timer.next_fire(now))
+                    .flatten()
+                    .map(|t| t.with_timezone(&Utc)),
+                timer: timer.clone(),
+            })
+            .collect()
+    }
+
+    pub fn create(
+        &self,
+        name: String,
+        schedule: String,
+        data: Value,
+        dispatch: &Dispatch,
+    ) -> Result<String, String> {
+        let id = self.store().create(name, schedule, data)?;
+        self.start(dispatch.clone());
+        Ok(id)
+    }
+
+    pub fn update(&self, id: &str, update: TimerUpdate, dispatch: &Dispatch) -> Result<(), String> {
+        self.store().update(id, update)?;
+        self.start(dispatch.clone());
+        Ok(())
+    }
+
+    pub fn delete(&self, id: &str, dispatch: &Dispatch) -> Result<(), String> {
+        self.store().delete(id)?;
+        self.start(dispatch.clone());
+        Ok(())
+    }
+
+    /// Publish a timer's event now, outside its schedule. Returns the event ID.
+    pub async fn fire(&self, id: &str, dispatch: &Dispatch) -> Result<String, String> {
+        let event = self
+            .store()
+            .get(id)
+            .ok_or_else(|| format!("Timer '{}' not found", id))?
+            .event()?;
+        dispatch.publish(event).await
+    }
+
+    /// Stop the running task, if any, and start it again with the enabled
+    /// timers.
+    pub fn start(&self, dispatch: Dispatch) {
+        let timers: Vec<Timer> = self
+            .store()
+            .list()
+            .iter()
+            .filter(|t| t.enabled)
+            .cloned()
+            .collect();
+        let mut task = self.task.lock().expect("timer task lock poisoned");
+        if let Some(running) = task.take() {
+            running.abort();
+        }
+        if !timers.is_empty() {
+            *task = Some(tauri::async_runtime::spawn(run(timers, dispatch)));
+        }
+    }
+}
+
+/// Sleep until the next timer is due, publish the events due then, repeat.
+async fn run(timers: Vec<Timer>, dispatch: Dispatch) {
+    let mut after = Local::now();
+    loop {
+        let due: Vec<(DateTime<Local>, &Timer)> = timers
+            .iter()
+            .filter_map(|t| t.next_fire(after).map(|at| (at, t)))
+            .collect();
+        let Some(next) = due.iter().map(|(at, _)| *at).min() else {
+            return;
+        };
+        let wait = (next - Local::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        for (_, timer) in due.iter().filter(|(at, _)| *at == next) {
+            let published = match timer.event() {
+                Ok(event) => dispatch.publish(event).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = published {
+                log::warn!("Timer '{}' failed to publish: {}", timer.name, e);
+            }
+        }
+        after = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn timer(schedule: &str) -> Timer {
+        Timer {
+            id: "timer_test".into(),
+            name: "backup.nightly".into(),
+            schedule: schedule.into(),
+            data: Value::Null,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn next_fire_follows_the_schedule() {
+        let at = Local.with_ymd_and_hms(2026, 3, 10, 2, 30, 0).unwrap();
+        let next = timer("0 3 * * *").next_fire(at).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2026, 3, 10, 3, 0, 0).unwrap());
+
+        // Six fields put seconds first; exactly at a fire time, the next one is later
+        let next = timer("*/15 * * * * *").next_fire(at).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2026, 3, 10, 2, 30, 15).unwrap());
+
+        assert!(timer("not a schedule").next_fire(at).is_none());
+    }
+
+    #[test]
+    fn events_carry_the_timer_name_and_data() {
+        let mut t = timer("@hourly");
+        t.data = serde_json::json!({"target": "photos"});
+        let event = t.event().unwrap();
+        assert_eq!(event.event_type, "nexus.timer.backup.nightly");
+        assert_eq!(event.source, TIMER_SOURCE);
+        assert_eq!(event.subject.as_deref(), Some("timer_test"));
+        assert_eq!(event.data["target"], "photos");
+    }
+
+    #[test]
+    fn store_validates_and_persists() {
+        let tmp = TempDir::new().unwrap();
+        let mut store = TimerStore::load(tmp.path());
+
+        assert!(store
+            .create("Backup".into(), "0 3 * * *".into(), Value::Null)
+            .is_err());
+        assert!(store
+            .create("backup".into(), "61 * * * *".into(), Value::Null)
+            .is_err());
+        let id = store
+            .create("backup.nightly".into(), "0 3 * * *".into(), Value::Null)
+            .unwrap();
+
+        store
+            .update(
+                &id,
+                TimerUpdate {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(store
+            .update(
+                &id,
+                TimerUpdate {
+                    name: Some("a..b".into()),
+                    ..Default::default()
+                },
+            )
+            .is_err());
+
+        let mut store = TimerStore::load(tmp.path());
+        assert!(!store.get(&id).unwrap().enabled);
+        store.delete(&id).unwrap();
+        assert!(store.delete(&id).is_err());
+        assert!(TimerStore::load(tmp.path()).list().is_empty());
+    }
+}
@@ -83,7 +83,7 @@ async fn fetch_binary(
 
         Ok(std::fs::read(&source)?)
     } else {
-        let client = reqwest::Client::builder()
+        let client = crate::proxy::apply(reqwest::Client::builder())
             .timeout(std::time::Duration::from_secs(120))
             .redirect(reqwest::redirect::Policy::limited(5))
             .build()
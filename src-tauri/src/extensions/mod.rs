@@ -26,6 +26,14 @@ pub enum RiskLevel {
     High,
 }
 
+impl RiskLevel {
+    /// Whether operations at this level may run in read-only mode. Only
+    /// low-risk operations are assumed not to change anything.
+    pub fn allowed_in_read_only(self) -> bool {
+        self == RiskLevel::Low
+    }
+}
+
 /// Describes a single operation that an extension exposes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationDef {
@@ -7,13 +7,12 @@ use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::sync::mpsc;
 
 use super::capability::Capability;
 use super::ipc::IpcRouter;
 use super::manifest::ExtensionManifest;
 use super::{Extension, ExtensionError, OperationDef, OperationResult};
-use crate::event_bus::cloud_event::CloudEvent;
+use crate::event_bus::subscription::EventReceiver;
 
 /// JSON-RPC 2.0 request (outgoing to extension).
 #[derive(Serialize)]
@@ -532,27 +531,40 @@ impl ProcessExtension {
                         match publish_req {
                             Ok(pr) => {
                                 let source = format!("nexus://extension/{}", self.id_str);
-                                let event = pr.into_cloud_event(source);
+                                let mut event = pr.into_cloud_event(source);
                                 let event_id = event.id.clone();
-                                let event_clone = event.clone();
-                                let actions = tokio::task::block_in_place(|| {
+                                let published = tokio::task::block_in_place(|| {
                                     tokio::runtime::Handle::current().block_on(async {
                                         let mut bus = dispatch.bus.write().await;
-                                        bus.publish(event)
+                                        bus.check_schema(&mut event)?;
+                                        Ok::<_, String>(bus.publish(event.clone()))
                                     })
                                 });
-                                if !actions.is_empty() {
-                                    dispatch.executor.execute_durable(
-                                        &dispatch.store,
-                                        actions,
-                                        &event_clone,
-                                    );
-                                }
-                                JsonRpcResponseOut {
-                                    jsonrpc: "2.0",
-                                    result: Some(serde_json::json!({"event_id": event_id})),
-                                    error: None,
-                                    id: req.id.clone(),
+                                match published {
+                                    Ok(actions) => {
+                                        if !actions.is_empty() {
+                                            dispatch.executor.execute_durable(
+                                                &dispatch.store,
+                                                actions,
+                                                &event,
+                                            );
+                                        }
+                                        JsonRpcResponseOut {
+                                            jsonrpc: "2.0",
+                                            result: Some(serde_json::json!({"event_id": event_id})),
+                                            error: None,
+                                            id: req.id.clone(),
+                                        }
+                                    }
+                                    Err(e) => JsonRpcResponseOut {
+                                        jsonrpc: "2.0",
+                                        result: None,
+                                        error: Some(JsonRpcErrorOut {
+                                            code: -32602,
+                                            message: e,
+                                        }),
+                                        id: req.id.clone(),
+                                    },
                                 }
                             }
                             Err(e) => JsonRpcResponseOut {
@@ -577,6 +589,94 @@ impl ProcessExtension {
                     },
                 }
             }
+            "event.register_schema" => {
+                let dispatch = self.dispatch.lock().expect("dispatch lock poisoned").clone();
+                let event_type = req.params.get("type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                let mode = req.params.get("mode")
+                    .cloned()
+                    .map(serde_json::from_value::<crate::event_bus::schema::SchemaMode>)
+                    .transpose();
+                match (dispatch, req.params.get("schema"), mode) {
+                    (Some(dispatch), Some(schema), Ok(mode)) if !event_type.is_empty() => {
+                        let owner = format!("nexus://extension/{}", self.id_str);
+                        let result = tokio::task::block_in_place(|| {
+                            tokio::runtime::Handle::current().block_on(async {
+                                let mut bus = dispatch.bus.write().await;
+                                bus.register_event_schema(
+                                    event_type,
+                                    schema.clone(),
+                                    mode.unwrap_or_default(),
+                                    &owner,
+                                )
+                            })
+                        });
+                        match result {
+                            Ok(schema) => JsonRpcResponseOut {
+                                jsonrpc: "2.0",
+                                result: serde_json::to_value(&schema).ok(),
+                                error: None,
+                                id: req.id.clone(),
+                            },
+                            Err(e) => JsonRpcResponseOut {
+                                jsonrpc: "2.0",
+                                result: None,
+                                error: Some(JsonRpcErrorOut {
+                                    code: -32602,
+                                    message: format!("event.register_schema failed: {}", e),
+                                }),
+                                id: req.id.clone(),
+                            },
+                        }
+                    }
+                    (Some(_), _, _) => JsonRpcResponseOut {
+                        jsonrpc: "2.0",
+                        result: None,
+                        error: Some(JsonRpcErrorOut {
+                            code: -32602,
+                            message: "event.register_schema requires 'type' and 'schema' params, and 'mode' of \"reject\" or \"flag\"".into(),
+                        }),
+                        id: req.id.clone(),
+                    },
+                    (None, _, _) => JsonRpcResponseOut {
+                        jsonrpc: "2.0",
+                        result: None,
+                        error: Some(JsonRpcErrorOut {
+                            code: -32603,
+                            message: "Event bus not available".into(),
+                        }),
+                        id: req.id.clone(),
+                    },
+                }
+            }
+            "event.schemas" => {
+                let dispatch = self.dispatch.lock().expect("dispatch lock poisoned").clone();
+                match dispatch {
+                    Some(dispatch) => {
+                        let schemas = tokio::task::block_in_place(|| {
+                            tokio::runtime::Handle::current().block_on(async {
+                                dispatch.bus.read().await.list_event_schemas().to_vec()
+                            })
+                        });
+                        JsonRpcResponseOut {
+                            jsonrpc: "2.0",
+                            result: Some(serde_json::to_value(&schemas).unwrap_or(Value::Null)),
+                            error: None,
+                            id: req.id.clone(),
+                        }
+                    }
+                    None => JsonRpcResponseOut {
+                        jsonrpc: "2.0",
+                        result: None,
+                        error: Some(JsonRpcErrorOut {
+                            code: -32603,
+                            message: "Event bus not available".into(),
+                        }),
+                        id: req.id.clone(),
+                    },
+                }
+            }
             "event.subscribe" => {
                 let dispatch = self.dispatch.lock().expect("dispatch lock poisoned").clone();
                 match dispatch {
@@ -586,16 +686,28 @@ impl ProcessExtension {
                             .unwrap_or("*");
                         let source_pattern = req.params.get("source_pattern")
                             .and_then(|v| v.as_str());
+                        // Opt in to acknowledging events with `event.ack`
+                        let ack = req.params.get("ack")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        // Optional predicate over event data
+                        let predicate = req.params.get("filter")
+                            .and_then(|v| v.as_str())
+                            .map(crate::event_bus::predicate::Predicate::parse)
+                            .transpose();
 
                         let result = tokio::task::block_in_place(|| {
                             tokio::runtime::Handle::current().block_on(async {
+                                let predicate = predicate?;
                                 let mut bus = dispatch.bus.write().await;
-                                bus.subscribe(
+                                bus.subscribe_with_ack(
                                     type_pattern,
                                     source_pattern,
+                                    predicate,
                                     crate::event_bus::subscription::SubscriberKind::Extension {
                                         ext_id: self.id_str.clone(),
                                     },
+                                    ack,
                                 )
                             })
                         });
@@ -642,6 +754,62 @@ impl ProcessExtension {
                     },
                 }
             }
+            "event.ack" => {
+                let dispatch = self.dispatch.lock().expect("dispatch lock poisoned").clone();
+                let event_id = req.params.get("event_id")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                match dispatch {
+                    Some(_) if event_id.is_empty() => JsonRpcResponseOut {
+                        jsonrpc: "2.0",
+                        result: None,
+                        error: Some(JsonRpcErrorOut {
+                            code: -32602,
+                            message: "event.ack requires 'event_id' param".into(),
+                        }),
+                        id: req.id.clone(),
+                    },
+                    Some(dispatch) => {
+                        let result = tokio::task::block_in_place(|| {
+                            tokio::runtime::Handle::current().block_on(async {
+                                let bus = dispatch.bus.read().await;
+                                bus.ack(
+                                    &crate::event_bus::subscription::SubscriberKind::Extension {
+                                        ext_id: self.id_str.clone(),
+                                    },
+                                    event_id,
+                                )
+                            })
+                        });
+                        match result {
+                            Ok(acked) => JsonRpcResponseOut {
+                                jsonrpc: "2.0",
+                                result: Some(serde_json::json!({"acked": acked})),
+                                error: None,
+                                id: req.id.clone(),
+                            },
+                            Err(e) => JsonRpcResponseOut {
+                                jsonrpc: "2.0",
+                                result: None,
+                                error: Some(JsonRpcErrorOut {
+                                    code: -32603,
+                                    message: format!("event.ack failed: {}", e),
+                                }),
+                                id: req.id.clone(),
+                            },
+                        }
+                    }
+                    None => JsonRpcResponseOut {
+                        jsonrpc: "2.0",
+                        result: None,
+                        error: Some(JsonRpcErrorOut {
+                            code: -32603,
+                            message: "Event bus not available".into(),
+                        }),
+                        id: req.id.clone(),
+                    },
+                }
+            }
             "event.unsubscribe" => {
                 let dispatch = self.dispatch.lock().expect("dispatch lock poisoned").clone();
                 match dispatch {
@@ -754,7 +922,7 @@ impl Drop for ProcessExtension {
 async fn deliver_events(
     stdin: Arc<Mutex<Option<BufWriter<ChildStdin>>>>,
     sub_id: String,
-    mut rx: mpsc::UnboundedReceiver<CloudEvent>,
+    mut rx: EventReceiver,
 ) {
     while let Some(event) = rx.recv().await {
         let notification = JsonRpcNotification {
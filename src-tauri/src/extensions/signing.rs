@@ -19,13 +19,9 @@ pub struct TrustedKeyStore {
 impl TrustedKeyStore {
     pub fn load(data_dir: &Path) -> Self {
         let path = data_dir.join("trusted_keys.json");
-        if path.exists() {
-            if let Ok(data) = std::fs::read_to_string(&path) {
-                if let Ok(mut store) = serde_json::from_str::<TrustedKeyStore>(&data) {
-                    store.path = path;
-                    return store;
-                }
-            }
+        if let Some(mut store) = crate::util::read_json_store::<TrustedKeyStore>(&path) {
+            store.path = path;
+            return store;
         }
         TrustedKeyStore {
             keys: HashMap::new(),
@@ -36,7 +32,7 @@ impl TrustedKeyStore {
     pub fn save(&self) -> Result<(), ExtensionError> {
         let data = serde_json::to_string_pretty(self)
             .map_err(|e| ExtensionError::Other(format!("Failed to serialize trusted keys: {}", e)))?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
+        crate::util::atomic_write_with_backup(&self.path, data.as_bytes())?;
         Ok(())
     }
 
@@ -121,7 +117,17 @@ pub fn verify_binary(
         )));
     }
 
-    // 2. Decode public key
+    verify_signature(public_key_b64, binary_data, signature_b64)
+}
+
+/// Verify an Ed25519 signature over the SHA-256 hash of `data`, as used for
+/// extension binaries and plugin manifests.
+pub fn verify_signature(
+    public_key_b64: &str,
+    data: &[u8],
+    signature_b64: &str,
+) -> Result<(), ExtensionError> {
+    // 1. Decode public key
     let pk_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, public_key_b64)
         .map_err(|e| ExtensionError::SignatureError(format!("Invalid public key base64: {}", e)))?;
 
@@ -132,7 +138,7 @@ pub fn verify_binary(
     let verifying_key = VerifyingKey::from_bytes(&pk_array)
         .map_err(|e| ExtensionError::SignatureError(format!("Invalid Ed25519 public key: {}", e)))?;
 
-    // 3. Decode signature
+    // 2. Decode signature
     let sig_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, signature_b64)
         .map_err(|e| ExtensionError::SignatureError(format!("Invalid signature base64: {}", e)))?;
 
@@ -142,8 +148,8 @@ pub fn verify_binary(
 
     let signature = Signature::from_bytes(&sig_array);
 
-    // 4. Verify: signature was over sha256(binary)
-    let hash_bytes = Sha256::digest(binary_data);
+    // 3. Verify: signature was over sha256(data)
+    let hash_bytes = Sha256::digest(data);
     verifying_key
         .verify(&hash_bytes, &signature)
         .map_err(|_| ExtensionError::SignatureError("Ed25519 signature verification failed".into()))?;
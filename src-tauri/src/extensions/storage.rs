@@ -35,13 +35,9 @@ impl Default for ExtensionStorage {
 impl ExtensionStorage {
     pub fn load(data_dir: &Path) -> Self {
         let path = data_dir.join("extensions.json");
-        if path.exists() {
-            if let Ok(data) = std::fs::read_to_string(&path) {
-                if let Ok(mut store) = serde_json::from_str::<ExtensionStorage>(&data) {
-                    store.path = path;
-                    return store;
-                }
-            }
+        if let Some(mut store) = crate::util::read_json_store::<ExtensionStorage>(&path) {
+            store.path = path;
+            return store;
         }
         ExtensionStorage {
             extensions: Vec::new(),
@@ -52,7 +48,7 @@ impl ExtensionStorage {
     pub fn save(&self) -> Result<(), ExtensionError> {
         let data = serde_json::to_string_pretty(self)
             .map_err(|e| ExtensionError::Other(format!("Failed to serialize extension storage: {}", e)))?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
+        crate::util::atomic_write_with_backup(&self.path, data.as_bytes())?;
         Ok(())
     }
 
@@ -0,0 +1,160 @@
+//! Opt-in verbose audit of a single plugin's Host API requests, for
+//! investigating a plugin suspected of misbehaving without logging every
+//! request from every plugin.
+//!
+//! Runs outside `auth_middleware` so denied requests are logged too; the
+//! plugin is identified from its bearer token only when some plugin has
+//! access logging enabled.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::oauth::validation::{validate_bearer, TokenValidation};
+use crate::oauth::OAuthStore;
+use crate::AppState;
+
+/// Access logging for one plugin. Stored in settings under
+/// `host_api_access_log`, keyed by plugin ID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessLogPolicy {
+    /// Fraction of requests audited, from 0 (none) to 1 (all).
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: f64,
+    /// Logging stops after this time, so it isn't left on by accident.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+fn default_sample_rate() -> f64 {
+    1.0
+}
+
+impl AccessLogPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.sample_rate) {
+            return Err("sample_rate must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+
+    fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.sample_rate > 0.0 && self.expires_at.map_or(true, |at| now < at)
+    }
+
+    /// Whether to audit a request, given a uniform random `roll` in [0, 1).
+    fn samples(&self, roll: f64) -> bool {
+        roll < self.sample_rate
+    }
+}
+
+/// Plugins with access logging on, by plugin ID.
+pub type AccessLogConfig = BTreeMap<String, AccessLogPolicy>;
+
+pub async fn access_log_middleware(
+    State(state): State<AppState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let policy = {
+        let mgr = state.read().await;
+        if mgr.settings.host_api_access_log.is_empty() {
+            drop(mgr);
+            return next.run(req).await;
+        }
+        let oauth_store = req.extensions().get::<Arc<OAuthStore>>().cloned();
+        let plugin_id = match oauth_store.map(|store| validate_bearer(req.headers(), &store)) {
+            Some(TokenValidation::Valid {
+                plugin_id: Some(pid),
+                ..
+            }) => pid,
+            _ => {
+                drop(mgr);
+                return next.run(req).await;
+            }
+        };
+        mgr.settings
+            .host_api_access_log
+            .get(&plugin_id)
+            .filter(|p| p.is_active(Utc::now()) && p.samples(rand::random()))
+            .cloned()
+            .map(|p| (plugin_id, p))
+    };
+    let Some((plugin_id, policy)) = policy else {
+        return next.run(req).await;
+    };
+
+    let audit = req.extensions().get::<AuditWriter>().cloned();
+    let method = req.method().to_string();
+    let path = req.uri().path().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_else(|| path.clone());
+
+    let started = Instant::now();
+    let response = next.run(req).await;
+    let status = response.status();
+
+    if let Some(audit) = audit {
+        audit.record(AuditEntry {
+            actor: AuditActor::Plugin { id: plugin_id },
+            source_id: None,
+            severity: AuditSeverity::Info,
+            action: "host_api.request".into(),
+            subject: Some(route),
+            result: if status.is_client_error() || status.is_server_error() {
+                AuditResult::Failure
+            } else {
+                AuditResult::Success
+            },
+            details: Some(serde_json::json!({
+                "method": method,
+                "path": path,
+                "status": status.as_u16(),
+                "latency_ms": started.elapsed().as_millis() as u64,
+                "sample_rate": policy.sample_rate,
+            })),
+        });
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_respects_rate_and_expiry() {
+        let now = Utc::now();
+        let policy = AccessLogPolicy {
+            sample_rate: 0.25,
+            expires_at: Some(now + chrono::Duration::hours(1)),
+        };
+        assert!(policy.validate().is_ok());
+        assert!(policy.is_active(now));
+        assert!(!policy.is_active(now + chrono::Duration::hours(2)));
+        assert!(policy.samples(0.1));
+        assert!(!policy.samples(0.25));
+
+        let off = AccessLogPolicy {
+            sample_rate: 0.0,
+            expires_at: None,
+        };
+        assert!(!off.is_active(now));
+        assert!(AccessLogPolicy { sample_rate: 1.5, expires_at: None }.validate().is_err());
+    }
+}
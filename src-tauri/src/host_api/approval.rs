@@ -1,9 +1,17 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use tauri::Emitter;
 use tokio::sync::oneshot;
 
+use crate::permissions::{Permission, PermissionService};
+
+const APPROVAL_EVENT: &str = "nexus://runtime-approval";
+
+/// How long the bridge waits for more requests from the same plugin and
+/// permission before showing the dialog.
+const COALESCE_WINDOW: std::time::Duration = std::time::Duration::from_millis(250);
+
 /// Decision the user makes in the runtime approval dialog.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -28,28 +36,80 @@ pub struct ApprovalRequest {
     pub context: HashMap<String, String>,
 }
 
+/// One request inside a coalesced dialog. The frontend answers each `id`
+/// separately through `runtime_approval_respond`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalBatchItem {
+    pub id: String,
+    pub context: HashMap<String, String>,
+}
+
+/// Payload emitted when several requests were coalesced: the first request,
+/// plus every request in the batch (including the first) under `batch`.
+#[derive(Debug, Clone, Serialize)]
+struct CoalescedApprovalRequest {
+    #[serde(flatten)]
+    request: ApprovalRequest,
+    batch: Vec<ApprovalBatchItem>,
+}
+
+/// Requests that arrived within the coalescing window, keyed by
+/// (plugin ID, category, permission).
+type OpenBatches = HashMap<(String, String, String), Vec<ApprovalRequest>>;
+
 /// Bridge between Axum HTTP handlers and the Tauri frontend.
 ///
 /// An HTTP handler creates a oneshot channel, emits an event to the frontend,
 /// and awaits the user's response (with a timeout). The frontend calls a Tauri
 /// command that looks up the pending channel by request ID and sends the decision.
+///
+/// Requests matching one of the plugin's deny rules are refused here, before
+/// the user is ever prompted. For always-prompt grants the bridge marks the
+/// request (`always_prompt` in the context) and treats "Allow" as "Allow once",
+/// so the approval is never persisted.
+///
+/// Scope-style requests (filesystem paths, network hosts, extension scopes,
+/// deferred permissions, rate cap breaches) from the same plugin and permission that arrive
+/// within `COALESCE_WINDOW` are shown as one multi-select dialog instead of
+/// a dialog each. Every request still gets its own decision.
 pub struct ApprovalBridge {
     pending: Mutex<HashMap<String, oneshot::Sender<ApprovalDecision>>>,
+    batches: Arc<Mutex<OpenBatches>>,
     app_handle: tauri::AppHandle,
+    permissions: Arc<dyn PermissionService>,
 }
 
 impl ApprovalBridge {
-    pub fn new(app_handle: tauri::AppHandle) -> Self {
+    pub fn new(app_handle: tauri::AppHandle, permissions: Arc<dyn PermissionService>) -> Self {
         Self {
             pending: Mutex::new(HashMap::new()),
+            batches: Arc::new(Mutex::new(HashMap::new())),
             app_handle,
+            permissions,
         }
     }
 
     /// Emit an approval request to the frontend and wait for the user's decision.
     ///
-    /// Returns `Deny` on timeout (60s) or if the receiver is dropped.
-    pub async fn request_approval(&self, request: ApprovalRequest) -> ApprovalDecision {
+    /// Returns `Deny` without prompting if a deny rule matches, and on
+    /// timeout (60s) or if the receiver is dropped.
+    pub async fn request_approval(&self, mut request: ApprovalRequest) -> ApprovalDecision {
+        let permission = parse_permission(&request.permission);
+        if self.is_denied(&request, permission.as_ref()) {
+            log::warn!(
+                "AUDIT DENIED plugin={} permission={} category={} reason=deny_rule",
+                request.plugin_id, request.permission, request.category
+            );
+            return ApprovalDecision::Deny;
+        }
+
+        let always_prompt = permission
+            .as_ref()
+            .is_some_and(|p| self.permissions.is_always_prompt(&request.plugin_id, p));
+        if always_prompt {
+            request.context.insert("always_prompt".to_string(), "true".to_string());
+        }
+
         let (tx, rx) = oneshot::channel();
         let request_id = request.id.clone();
 
@@ -58,11 +118,13 @@ impl ApprovalBridge {
             pending.insert(request_id.clone(), tx);
         }
 
-        // Emit event to frontend — if this fails the channel stays pending
-        // and will time out, which is acceptable.
-        let _ = self
-            .app_handle
-            .emit("nexus://runtime-approval", &request);
+        if coalesces(&request.category) {
+            self.enqueue(request);
+        } else {
+            // Emit event to frontend — if this fails the channel stays pending
+            // and will time out, which is acceptable.
+            let _ = self.app_handle.emit(APPROVAL_EVENT, &request);
+        }
 
         // Await decision with 60s timeout. On timeout or channel drop, deny.
         let decision = tokio::time::timeout(std::time::Duration::from_secs(60), rx).await;
@@ -74,11 +136,60 @@ impl ApprovalBridge {
         }
 
         match decision {
+            Ok(Ok(ApprovalDecision::Approve)) if always_prompt => ApprovalDecision::ApproveOnce,
             Ok(Ok(d)) => d,
             _ => ApprovalDecision::Deny,
         }
     }
 
+    /// Add the request to the open batch for its plugin and permission. The
+    /// first request opens the batch and schedules its emission after the
+    /// coalescing window, so a cancelled caller can't strand the others.
+    fn enqueue(&self, request: ApprovalRequest) {
+        let key = (
+            request.plugin_id.clone(),
+            request.category.clone(),
+            request.permission.clone(),
+        );
+        {
+            let mut batches = self.batches.lock().expect("approval lock poisoned");
+            if let Some(batch) = batches.get_mut(&key) {
+                batch.push(request);
+                return;
+            }
+            batches.insert(key.clone(), vec![request]);
+        }
+
+        let batches = self.batches.clone();
+        let app_handle = self.app_handle.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(COALESCE_WINDOW).await;
+            let batch = batches
+                .lock()
+                .expect("approval lock poisoned")
+                .remove(&key)
+                .unwrap_or_default();
+            emit_batch(&app_handle, batch);
+        });
+    }
+
+    /// Check the request against the plugin's deny rules. The scope is the
+    /// requested path (filesystem), host (network), command line (process
+    /// execution), or scope value (extensions, credentials).
+    fn is_denied(&self, request: &ApprovalRequest, permission: Option<&Permission>) -> bool {
+        let Some(permission) = permission else {
+            return false;
+        };
+        let scope = request
+            .context
+            .get("path")
+            .or_else(|| request.context.get("host"))
+            .or_else(|| request.context.get("command_line"))
+            .or_else(|| request.context.get("scope_value"))
+            .map(String::as_str);
+        self.permissions.is_denied(&request.plugin_id, permission, scope)
+    }
+
     /// Called by the Tauri command when the user clicks a button in the dialog.
     ///
     /// Returns `true` if the request was found and the decision was delivered.
@@ -94,3 +205,74 @@ impl ApprovalBridge {
         }
     }
 }
+
+/// Categories whose requests differ only in a scope value, so several of them
+/// can be answered from one dialog.
+fn coalesces(category: &str) -> bool {
+    matches!(category, "filesystem" | "network" | "deferred_permission" | "rate_cap")
+        || category.starts_with("extension_scope:")
+}
+
+/// Emit a batch: a lone request as a plain `ApprovalRequest`, several as a
+/// `CoalescedApprovalRequest`.
+fn emit_batch(app_handle: &tauri::AppHandle, mut batch: Vec<ApprovalRequest>) {
+    if batch.len() <= 1 {
+        if let Some(request) = batch.pop() {
+            let _ = app_handle.emit(APPROVAL_EVENT, &request);
+        }
+        return;
+    }
+    let items = batch
+        .iter()
+        .map(|r| ApprovalBatchItem {
+            id: r.id.clone(),
+            context: r.context.clone(),
+        })
+        .collect();
+    let payload = CoalescedApprovalRequest {
+        request: batch.swap_remove(0),
+        batch: items,
+    };
+    let _ = app_handle.emit(APPROVAL_EVENT, &payload);
+}
+
+fn parse_permission(raw: &str) -> Option<Permission> {
+    serde_json::from_value(serde_json::Value::String(raw.to_string())).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_scope_style_categories_coalesce() {
+        assert!(coalesces("filesystem"));
+        assert!(coalesces("network"));
+        assert!(coalesces("deferred_permission"));
+        assert!(coalesces("rate_cap"));
+        assert!(coalesces("extension_scope:docker:repo"));
+        assert!(!coalesces("extension:docker"));
+        assert!(!coalesces("mcp_tool"));
+        assert!(!coalesces("oauth_authorize"));
+    }
+
+    #[test]
+    fn coalesced_payload_flattens_first_request() {
+        let request = ApprovalRequest {
+            id: "a".into(),
+            plugin_id: "plug".into(),
+            plugin_name: "Plug".into(),
+            category: "filesystem".into(),
+            permission: "filesystem:read".into(),
+            context: HashMap::from([("path".to_string(), "/tmp/a".to_string())]),
+        };
+        let payload = CoalescedApprovalRequest {
+            batch: vec![ApprovalBatchItem { id: "a".into(), context: request.context.clone() }],
+            request,
+        };
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["id"], "a");
+        assert_eq!(json["category"], "filesystem");
+        assert_eq!(json["batch"][0]["context"]["path"], "/tmp/a");
+    }
+}
@@ -1,7 +1,8 @@
 use axum::{
-    extract::Query,
-    http::StatusCode,
+    extract::{Path, Query},
+    http::{header, StatusCode},
     response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
 use futures_util::Stream;
@@ -9,9 +10,15 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use utoipa::ToSchema;
 
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
 use crate::event_bus::cloud_event::{CloudEvent, PublishRequest};
 use crate::event_bus::executor::RouteActionExecutor;
 use crate::event_bus::log::EventLogQuery;
+use crate::event_bus::predicate::Predicate;
+use crate::event_bus::quota::{QuotaExceeded, QuotaViolation};
+use crate::event_bus::schema::{EventSchema, SchemaMode};
+use crate::event_bus::store::ReplayRange;
 use crate::event_bus::subscription::SubscriberKind;
 use crate::event_bus::{SharedEventBus, SharedEventStore};
 
@@ -35,6 +42,12 @@ pub struct EventLogEntry {
     pub time: String,
     pub subject: Option<String>,
     pub data: Value,
+    /// ID of the event that started the causal chain this one belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlationid: Option<String>,
+    /// ID of the event that directly caused this one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub causationid: Option<String>,
 }
 
 impl From<&CloudEvent> for EventLogEntry {
@@ -46,6 +59,8 @@ impl From<&CloudEvent> for EventLogEntry {
             time: e.time.to_rfc3339(),
             subject: e.subject.clone(),
             data: e.data.clone(),
+            correlationid: e.correlation_id().map(str::to_string),
+            causationid: e.causation_id().map(str::to_string),
         }
     }
 }
@@ -61,6 +76,58 @@ pub struct SubscribeQuery {
     #[serde(default = "default_pattern")]
     pub type_pattern: String,
     pub source_pattern: Option<String>,
+    /// Acknowledge each event via `POST /v1/events/ack`; unacknowledged
+    /// events are redelivered.
+    #[serde(default)]
+    pub ack: bool,
+    /// Predicate over event data, e.g. `data.ref == "refs/heads/main"`.
+    pub filter: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AckRequest {
+    pub event_id: String,
+}
+
+/// Stored events to replay into the caller's connected subscriptions. Bounds
+/// combine; all are optional.
+#[derive(Deserialize, ToSchema)]
+pub struct ReplayRequest {
+    /// Only events after this sequence number.
+    pub after_seq: Option<i64>,
+    /// Only events at or after this time (RFC 3339).
+    #[schema(value_type = Option<String>)]
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only events before this time (RFC 3339).
+    #[schema(value_type = Option<String>)]
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    /// At most this many events (default 1000, max 10000).
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReplayResponse {
+    /// Events sent to at least one subscription.
+    pub replayed: usize,
+    /// Sequence number of the last event read; pass it as `after_seq` to
+    /// continue. Absent when the range was empty.
+    pub last_seq: Option<i64>,
+}
+
+/// JSON Schema for the data of one of the caller's event types.
+#[derive(Deserialize, ToSchema)]
+pub struct RegisterSchemaRequest {
+    pub schema: Value,
+    /// `reject` (default) refuses mismatching events; `flag` publishes them
+    /// with a `schemaerror` attribute.
+    #[serde(default)]
+    pub mode: SchemaMode,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AckResponse {
+    /// Pending deliveries cleared — 0 if the event was already acknowledged.
+    pub acked: usize,
 }
 
 fn default_pattern() -> String {
@@ -93,8 +160,10 @@ pub struct EventErrorResponse {
     request_body = PublishRequest,
     responses(
         (status = 200, description = "Event published", body = PublishResponse),
-        (status = 400, description = "Invalid event", body = EventErrorResponse),
+        (status = 400, description = "Invalid event, or data not matching the registered schema", body = EventErrorResponse),
         (status = 401, description = "Unauthorized"),
+        (status = 413, description = "Event data over the size limit", body = EventErrorResponse),
+        (status = 429, description = "Publish rate limit exceeded; see the Retry-After header", body = EventErrorResponse),
     )
 )]
 pub async fn publish_event(
@@ -102,14 +171,21 @@ pub async fn publish_event(
     Extension(event_bus): Extension<SharedEventBus>,
     Extension(executor): Extension<RouteActionExecutor>,
     Extension(event_store): Extension<SharedEventStore>,
+    Extension(audit): Extension<AuditWriter>,
     Json(req): Json<PublishRequest>,
-) -> Result<Json<PublishResponse>, (StatusCode, Json<EventErrorResponse>)> {
+) -> Result<Json<PublishResponse>, Response> {
     let source = format!("nexus://plugin/{}", auth.plugin_id);
-    let event = req.into_cloud_event(source);
+    let mut event = req.into_cloud_event(source);
     let event_id = event.id.clone();
 
     let actions = {
         let mut bus = event_bus.write().await;
+        if let Err(exceeded) = bus.check_quota(&auth.plugin_id, &event) {
+            return Err(quota_exceeded(&audit, &auth.plugin_id, &exceeded));
+        }
+        bus.check_schema(&mut event).map_err(|error| {
+            (StatusCode::BAD_REQUEST, Json(EventErrorResponse { error })).into_response()
+        })?;
         bus.publish(event.clone())
     };
 
@@ -120,6 +196,37 @@ pub async fn publish_event(
     Ok(Json(PublishResponse { event_id }))
 }
 
+/// Refuse an event over its publisher's quota, auditing publishers that
+/// keep trying.
+fn quota_exceeded(audit: &AuditWriter, plugin_id: &str, exceeded: &QuotaExceeded) -> Response {
+    log::warn!("Event quota exceeded plugin={}: {}", plugin_id, exceeded);
+    if exceeded.repeated {
+        audit.record(AuditEntry {
+            actor: AuditActor::Plugin {
+                id: plugin_id.to_string(),
+            },
+            source_id: None,
+            severity: AuditSeverity::Warn,
+            action: "event.quota_exceeded".into(),
+            subject: Some(plugin_id.to_string()),
+            result: AuditResult::Failure,
+            details: Some(serde_json::json!({ "reason": exceeded.to_string() })),
+        });
+    }
+    let body = Json(EventErrorResponse {
+        error: exceeded.to_string(),
+    });
+    match &exceeded.violation {
+        QuotaViolation::RateLimited { retry_after } => {
+            let secs = retry_after.as_secs().max(1).to_string();
+            (StatusCode::TOO_MANY_REQUESTS, [(header::RETRY_AFTER, secs)], body).into_response()
+        }
+        QuotaViolation::PayloadTooLarge { .. } => {
+            (StatusCode::PAYLOAD_TOO_LARGE, body).into_response()
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // GET /v1/events/subscribe — SSE stream of matching events
 // ---------------------------------------------------------------------------
@@ -132,10 +239,12 @@ pub async fn publish_event(
     params(
         ("type_pattern" = Option<String>, Query, description = "Glob pattern for event type (default: *)"),
         ("source_pattern" = Option<String>, Query, description = "Glob pattern for event source"),
+        ("ack" = Option<bool>, Query, description = "Require acknowledgements; unacknowledged events are redelivered"),
+        ("filter" = Option<String>, Query, description = "Predicate over event data, e.g. data.ref == \"refs/heads/main\""),
     ),
     responses(
         (status = 200, description = "SSE stream of matching events"),
-        (status = 400, description = "Invalid pattern", body = EventErrorResponse),
+        (status = 400, description = "Invalid pattern or filter", body = EventErrorResponse),
         (status = 401, description = "Unauthorized"),
     )
 )]
@@ -145,21 +254,26 @@ pub async fn subscribe_events(
     Query(params): Query<SubscribeQuery>,
 ) -> Result<Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>>, (StatusCode, Json<EventErrorResponse>)>
 {
+    let bad_request = |error| (StatusCode::BAD_REQUEST, Json(EventErrorResponse { error }));
+    let predicate = params
+        .filter
+        .as_deref()
+        .map(Predicate::parse)
+        .transpose()
+        .map_err(bad_request)?;
+
     let mut bus = event_bus.write().await;
     let (_sub_id, mut rx) = bus
-        .subscribe(
+        .subscribe_with_ack(
             &params.type_pattern,
             params.source_pattern.as_deref(),
+            predicate,
             SubscriberKind::Plugin {
                 plugin_id: auth.plugin_id.clone(),
             },
+            params.ack,
         )
-        .map_err(|e| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(EventErrorResponse { error: e }),
-            )
-        })?;
+        .map_err(bad_request)?;
     drop(bus);
 
     let stream = async_stream::stream! {
@@ -167,6 +281,7 @@ pub async fn subscribe_events(
             let entry = EventLogEntry::from(&event);
             if let Ok(data) = serde_json::to_string(&entry) {
                 yield Ok(Event::default()
+                    .id(event.id.clone())
                     .event(event.event_type.clone())
                     .data(data));
             }
@@ -176,6 +291,206 @@ pub async fn subscribe_events(
     Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
+// ---------------------------------------------------------------------------
+// POST /v1/events/ack — acknowledge a delivered event
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/events/ack",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    request_body = AckRequest,
+    responses(
+        (status = 200, description = "Event acknowledged", body = AckResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Store error", body = EventErrorResponse),
+    )
+)]
+pub async fn ack_event(
+    Extension(auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+    Json(req): Json<AckRequest>,
+) -> Result<Json<AckResponse>, (StatusCode, Json<EventErrorResponse>)> {
+    let bus = event_bus.read().await;
+    let kind = SubscriberKind::Plugin {
+        plugin_id: auth.plugin_id.clone(),
+    };
+    bus.ack(&kind, &req.event_id)
+        .map(|acked| Json(AckResponse { acked }))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(EventErrorResponse { error: e }),
+            )
+        })
+}
+
+// ---------------------------------------------------------------------------
+// POST /v1/events/replay — backfill stored events into the caller's subscriptions
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/events/replay",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    request_body = ReplayRequest,
+    responses(
+        (status = 200, description = "Events replayed", body = ReplayResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "No connected subscription", body = EventErrorResponse),
+        (status = 500, description = "Store error", body = EventErrorResponse),
+    )
+)]
+pub async fn replay_events(
+    Extension(auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+    Extension(event_store): Extension<SharedEventStore>,
+    Json(req): Json<ReplayRequest>,
+) -> Result<Json<ReplayResponse>, (StatusCode, Json<EventErrorResponse>)> {
+    let range = ReplayRange {
+        after_seq: req.after_seq,
+        since: req.since,
+        until: req.until,
+        limit: req.limit,
+    };
+    let stored = event_store.events_in_range(&range).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(EventErrorResponse { error: e }),
+        )
+    })?;
+    let last_seq = stored.last().map(|&(seq, _)| seq);
+    let events: Vec<CloudEvent> = stored.into_iter().map(|(_, e)| e).collect();
+
+    let kind = SubscriberKind::Plugin {
+        plugin_id: auth.plugin_id.clone(),
+    };
+    let bus = event_bus.read().await;
+    bus.replay_to_subscriber(&kind, &events)
+        .map(|replayed| Json(ReplayResponse { replayed, last_seq }))
+        .map_err(|e| (StatusCode::CONFLICT, Json(EventErrorResponse { error: e })))
+}
+
+// ---------------------------------------------------------------------------
+// /v1/events/schemas — event schema registry
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/events/schemas",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Registered event schemas", body = Vec<EventSchema>),
+        (status = 401, description = "Unauthorized"),
+    )
+)]
+pub async fn list_event_schemas(
+    Extension(_auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+) -> Json<Vec<EventSchema>> {
+    Json(event_bus.read().await.list_event_schemas().to_vec())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/events/schemas/{event_type}",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    params(("event_type" = String, Path, description = "Event type")),
+    responses(
+        (status = 200, description = "Schema for the event type", body = EventSchema),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No schema registered", body = EventErrorResponse),
+    )
+)]
+pub async fn get_event_schema(
+    Extension(_auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+    Path(event_type): Path<String>,
+) -> Result<Json<EventSchema>, (StatusCode, Json<EventErrorResponse>)> {
+    event_bus
+        .read()
+        .await
+        .get_event_schema(&event_type)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(EventErrorResponse {
+                    error: format!("No schema registered for '{}'", event_type),
+                }),
+            )
+        })
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/events/schemas/{event_type}",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    params(("event_type" = String, Path, description = "Event type the caller publishes")),
+    request_body = RegisterSchemaRequest,
+    responses(
+        (status = 200, description = "Schema registered", body = EventSchema),
+        (status = 400, description = "Invalid event type or schema", body = EventErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Schema registered by another publisher", body = EventErrorResponse),
+    )
+)]
+pub async fn register_event_schema(
+    Extension(auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+    Path(event_type): Path<String>,
+    Json(req): Json<RegisterSchemaRequest>,
+) -> Result<Json<EventSchema>, (StatusCode, Json<EventErrorResponse>)> {
+    let owner = format!("nexus://plugin/{}", auth.plugin_id);
+    let mut bus = event_bus.write().await;
+    if bus
+        .get_event_schema(&event_type)
+        .is_some_and(|s| s.owner != owner)
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(EventErrorResponse {
+                error: format!("The schema for '{}' belongs to another publisher", event_type),
+            }),
+        ));
+    }
+    bus.register_event_schema(&event_type, req.schema, req.mode, &owner)
+        .map(Json)
+        .map_err(|error| (StatusCode::BAD_REQUEST, Json(EventErrorResponse { error })))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/events/schemas/{event_type}",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    params(("event_type" = String, Path, description = "Event type")),
+    responses(
+        (status = 204, description = "Schema removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not registered by the caller, or not registered", body = EventErrorResponse),
+    )
+)]
+pub async fn delete_event_schema(
+    Extension(auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+    Path(event_type): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<EventErrorResponse>)> {
+    let owner = format!("nexus://plugin/{}", auth.plugin_id);
+    event_bus
+        .write()
+        .await
+        .remove_event_schema(&event_type, Some(&owner))
+        .map(|()| StatusCode::NO_CONTENT)
+        .map_err(|error| (StatusCode::FORBIDDEN, Json(EventErrorResponse { error })))
+}
+
 // ---------------------------------------------------------------------------
 // GET /v1/events/log — query the event log
 // ---------------------------------------------------------------------------
@@ -237,3 +552,36 @@ pub async fn query_event_log(
         total,
     }))
 }
+
+// ---------------------------------------------------------------------------
+// GET /v1/events/log/{event_id}/chain — causal chain of an event
+// ---------------------------------------------------------------------------
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/events/log/{event_id}/chain",
+    tag = "events",
+    security(("bearer_auth" = [])),
+    params(
+        ("event_id" = String, Path, description = "ID of any event in the chain"),
+    ),
+    responses(
+        (status = 200, description = "Events in the chain, oldest first", body = EventLogResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Event not found", body = EventErrorResponse),
+    )
+)]
+pub async fn event_causal_chain(
+    Extension(_auth): Extension<AuthenticatedPlugin>,
+    Extension(event_bus): Extension<SharedEventBus>,
+    Path(event_id): Path<String>,
+) -> Result<Json<EventLogResponse>, (StatusCode, Json<EventErrorResponse>)> {
+    let chain = event_bus
+        .read()
+        .await
+        .causal_chain(&event_id)
+        .map_err(|error| (StatusCode::NOT_FOUND, Json(EventErrorResponse { error })))?;
+    let events: Vec<EventLogEntry> = chain.iter().map(EventLogEntry::from).collect();
+    let total = events.len();
+    Ok(Json(EventLogResponse { events, total }))
+}
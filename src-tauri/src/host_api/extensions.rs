@@ -151,7 +151,8 @@ pub async fn list_extensions(
 /// Three-layer security model:
 /// 1. PERMISSION: Does this plugin have `ext:{ext_id}:{operation}`?
 /// 2. SCOPE: If the operation declares `scope_key`, is the scope value approved?
-/// 3. RISK: If risk_level is high, per-invocation runtime approval.
+/// 3. RISK: If risk_level is high, or the grant is always-prompt, per-invocation
+///    runtime approval.
 #[utoipa::path(
     post,
     path = "/api/v1/extensions/{ext_id}/{operation}",
@@ -202,6 +203,15 @@ pub async fn call_extension(
             )
         })?;
 
+    // Anything above low risk may change host state; read-only mode blocks it
+    if !op_def.risk_level.allowed_in_read_only() && mgr.settings.read_only_mode {
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "Read-only mode",
+            Some(format!("Operation '{}' is blocked while Nexus is in read-only mode", operation)),
+        ));
+    }
+
     // 3. LAYER 1 — PERMISSION: Check "ext:{ext_id}:{operation}" with three-state model
     let perm_string = crate::extensions::registry::ExtensionRegistry::permission_string(&ext_id, &operation);
     let required_perm = Permission::Extension(perm_string.clone());
@@ -256,7 +266,7 @@ pub async fn call_extension(
                     drop(mgr);
 
                     return execute_and_respond(
-                        ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state,
+                        ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state, false,
                     ).await;
                 }
                 super::approval::ApprovalDecision::ApproveOnce => {
@@ -271,7 +281,7 @@ pub async fn call_extension(
                     drop(mgr);
 
                     return execute_and_respond(
-                        ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state,
+                        ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state, false,
                     ).await;
                 }
                 super::approval::ApprovalDecision::Deny => {
@@ -371,7 +381,7 @@ pub async fn call_extension(
                                 drop(mgr);
 
                                 return execute_and_respond(
-                                    ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state,
+                                    ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state, false,
                                 ).await;
                             }
                             super::approval::ApprovalDecision::ApproveOnce => {
@@ -383,7 +393,7 @@ pub async fn call_extension(
                                 drop(mgr);
 
                                 return execute_and_respond(
-                                    ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state,
+                                    ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state, false,
                                 ).await;
                             }
                             super::approval::ApprovalDecision::Deny => {
@@ -403,6 +413,14 @@ pub async fn call_extension(
         }
     }
 
+    // An always-prompt grant confirms every call. Scoped calls were already
+    // confirmed by the scope check above (the grant reports no approved scopes).
+    let scoped_call = op_def
+        .scope_key
+        .as_ref()
+        .is_some_and(|k| body.input.get(k).and_then(|v| v.as_str()).is_some());
+    let confirm = !scoped_call && mgr.permissions.is_always_prompt(&auth.plugin_id, &required_perm);
+
     // 6. LAYER 3 — RISK: Clone Arc, drop lock, execute lock-free
     let ext_arc = mgr.extensions.get_arc(&ext_id).ok_or_else(|| {
         error_response(StatusCode::INTERNAL_SERVER_ERROR, "Extension disappeared", None)
@@ -410,11 +428,12 @@ pub async fn call_extension(
     drop(mgr);
 
     execute_and_respond(
-        ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state,
+        ext_arc, &ext_id, &operation, &auth.plugin_id, body.input, &op_def, &bridge, &state, confirm,
     ).await
 }
 
 /// Execute the extension operation, handling high-risk runtime approval.
+/// `confirm` forces the same per-invocation approval for always-prompt grants.
 /// Takes an Arc so the read lock can be dropped before execution.
 #[allow(clippy::too_many_arguments)]
 async fn execute_and_respond(
@@ -426,15 +445,19 @@ async fn execute_and_respond(
     op_def: &crate::extensions::OperationDef,
     bridge: &Arc<ApprovalBridge>,
     state: &AppState,
+    confirm: bool,
 ) -> Result<Json<CallExtensionResponse>, (StatusCode, Json<ExtensionErrorResponse>)> {
     // High-risk operations need per-invocation approval
-    if op_def.risk_level == RiskLevel::High {
+    let high_risk = op_def.risk_level == RiskLevel::High;
+    if high_risk || confirm {
         let mut context = std::collections::HashMap::new();
         context.insert("extension".to_string(), ext_id.to_string());
         context.insert("extension_display_name".to_string(), ext.display_name().to_string());
         context.insert("operation".to_string(), operation.to_string());
         context.insert("operation_description".to_string(), op_def.description.clone());
-        context.insert("risk_level".to_string(), "high".to_string());
+        if high_risk {
+            context.insert("risk_level".to_string(), "high".to_string());
+        }
         if let Value::Object(map) = &input {
             for (k, v) in map {
                 context.insert(format!("input.{}", k), v.to_string());
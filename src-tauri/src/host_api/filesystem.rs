@@ -8,25 +8,12 @@ use utoipa::{IntoParams, ToSchema};
 
 use super::approval::{ApprovalBridge, ApprovalDecision, ApprovalRequest};
 use super::middleware::AuthenticatedPlugin;
-use crate::permissions::Permission;
+use crate::permissions::{fs_scope, Permission};
 use crate::AppState;
 
 /// Maximum file size for reads (5 MB). Prevents loading huge files into memory.
 const MAX_READ_BYTES: u64 = 5 * 1024 * 1024;
 
-/// Expand `~` or `~/...` to the user's home directory.
-fn expand_tilde(raw: &str) -> PathBuf {
-    if raw == "~" {
-        dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"))
-    } else if let Some(rest) = raw.strip_prefix("~/") {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("/"))
-            .join(rest)
-    } else {
-        PathBuf::from(raw)
-    }
-}
-
 #[derive(Deserialize, IntoParams)]
 pub struct PathQuery {
     pub path: String,
@@ -144,7 +131,7 @@ pub fn normalize_path(path: &Path) -> PathBuf {
 /// Validate safety for a read/list path. Canonicalizes (path must exist) and
 /// blocks access to the Nexus data directory.
 fn validate_read_safety(data_dir: &Path, raw_path: &str) -> Result<PathBuf, StatusCode> {
-    let path = expand_tilde(raw_path);
+    let path = fs_scope::expand_home(raw_path);
     let canonical = path.canonicalize().map_err(|_| StatusCode::FORBIDDEN)?;
 
     if canonical.starts_with(data_dir) {
@@ -157,7 +144,7 @@ fn validate_read_safety(data_dir: &Path, raw_path: &str) -> Result<PathBuf, Stat
 /// Validate safety for a write path. Normalizes (target may not exist) and
 /// blocks access to the Nexus data directory.
 fn validate_write_safety(data_dir: &Path, raw_path: &str) -> Result<PathBuf, StatusCode> {
-    let path = expand_tilde(raw_path);
+    let path = fs_scope::expand_home(raw_path);
 
     if !path.is_absolute() {
         return Err(StatusCode::FORBIDDEN);
@@ -183,7 +170,10 @@ enum PathAccess {
 
 /// Check whether a validated path falls within the plugin's approved_paths.
 /// Returns `Allowed` when the grant is unrestricted (`None`) or the path is
-/// covered by an existing approved directory. Returns `NeedsApproval` otherwise.
+/// covered by an approved directory prefix or glob scope (see
+/// `permissions::fs_scope`). Returns `NeedsApproval` otherwise,
+/// and for paths under a deny rule even if an approved parent covers them —
+/// the approval bridge then refuses without prompting.
 fn check_path_access(
     permissions: &dyn crate::permissions::PermissionService,
     plugin_id: &str,
@@ -191,19 +181,25 @@ fn check_path_access(
     validated_path: &Path,
     use_canonicalize: bool,
 ) -> PathAccess {
+    if permissions.is_denied(plugin_id, permission, Some(&validated_path.to_string_lossy())) {
+        return PathAccess::NeedsApproval;
+    }
+
     let approved = match permissions.get_approved_paths(plugin_id, permission) {
         Some(paths) => paths,
         None => return PathAccess::Allowed, // unrestricted
     };
 
     let allowed = approved.iter().any(|allowed_path| {
-        if use_canonicalize {
-            PathBuf::from(allowed_path)
+        if fs_scope::is_pattern(allowed_path) {
+            fs_scope::pattern_matches(allowed_path, validated_path)
+        } else if use_canonicalize {
+            fs_scope::expand_home(allowed_path)
                 .canonicalize()
                 .map(|ap| validated_path.starts_with(&ap))
                 .unwrap_or(false)
         } else {
-            let ap = normalize_path(Path::new(allowed_path));
+            let ap = normalize_path(&fs_scope::expand_home(allowed_path));
             validated_path.starts_with(&ap)
         }
     });
@@ -5,12 +5,16 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use rmcp::transport::common::server_side_http::SessionId;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::session::SessionManager;
 use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 use std::time::Instant;
+use tokio_util::sync::CancellationToken;
 
-use crate::api_keys::ApiKeyStore;
+use crate::api_keys::{ApiKeyStore, ApiKeyValidation};
 use crate::audit::writer::AuditWriter;
 use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
 use crate::oauth::validation::{validate_bearer, TokenValidation};
@@ -23,10 +27,7 @@ use crate::AppState;
 fn is_loopback(addr: &SocketAddr) -> bool {
     match addr.ip() {
         IpAddr::V4(ip) => ip == Ipv4Addr::LOCALHOST,
-        IpAddr::V6(ip) => {
-            ip == Ipv6Addr::LOCALHOST
-                || ip == Ipv4Addr::LOCALHOST.to_ipv6_mapped()
-        }
+        IpAddr::V6(ip) => ip == Ipv6Addr::LOCALHOST || ip == Ipv4Addr::LOCALHOST.to_ipv6_mapped(),
     }
 }
 
@@ -37,17 +38,91 @@ fn is_loopback(addr: &SocketAddr) -> bool {
 const MCP_SESSION_TTL_SECS: u64 = 24 * 60 * 60; // 24 hours
 const MCP_SESSION_CAP: usize = 1000;
 
+/// Identity of an authenticated MCP client.
+///
+/// Inserted into request extensions by [`gateway_auth_middleware`]; rmcp copies
+/// the HTTP request parts into the `RequestContext`, so tool handlers can
+/// attribute calls to the client that made them.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct McpClientIdentity {
+    /// Stable key for per-client state: `api_key:{id}` or `oauth:{client_id}`.
+    pub client_id: String,
+    /// Human-readable label (API key name or OAuth client name).
+    pub name: String,
+    /// Authentication method: `"api_key"` or `"oauth"`.
+    pub method: String,
+    /// Tool restrictions from a scoped API key. `None` is unrestricted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<crate::api_keys::ApiKeyScope>,
+}
+
+impl McpClientIdentity {
+    pub fn api_key(key: &crate::api_keys::ApiKey) -> Self {
+        Self {
+            client_id: format!("api_key:{}", key.id),
+            name: key.name.clone(),
+            method: "api_key".into(),
+            scope: key.scope.is_restricted().then(|| key.scope.clone()),
+        }
+    }
+
+    pub fn oauth(client_id: &str, client_name: &str) -> Self {
+        Self {
+            client_id: format!("oauth:{}", client_id),
+            name: client_name.to_string(),
+            method: "oauth".into(),
+            scope: None,
+        }
+    }
+}
+
+/// Transport an MCP session is connected over.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpTransport {
+    StreamableHttp,
+    Sse,
+}
+
+#[derive(Debug, Clone)]
+struct McpSessionEntry {
+    authenticated_at: Instant,
+    connected_at: chrono::DateTime<chrono::Utc>,
+    identity: McpClientIdentity,
+    transport: McpTransport,
+    tool_calls: u64,
+    /// Cancelled when the user revokes the session (ends SSE streams).
+    cancel: CancellationToken,
+}
+
+/// A live MCP session, as shown in the settings UI.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct McpSessionInfo {
+    pub session_id: String,
+    pub client_id: String,
+    pub client_name: String,
+    pub auth_method: String,
+    pub transport: McpTransport,
+    pub connected_at: chrono::DateTime<chrono::Utc>,
+    pub tool_calls: u64,
+}
+
 /// Caches authenticated MCP session IDs to avoid re-validating credentials on
-/// every request within a session.
+/// every request within a session. Each session remembers the client identity
+/// it authenticated as, when it connected, and how many tools it has called.
 #[derive(Debug, Clone)]
 pub struct McpSessionStore {
-    authenticated: Arc<RwLock<HashMap<String, Instant>>>,
+    authenticated: Arc<RwLock<HashMap<String, McpSessionEntry>>>,
+    /// rmcp's Streamable HTTP session manager, attached when the server starts,
+    /// so revoking a session also tears down its transport.
+    http_sessions: Arc<OnceLock<Arc<LocalSessionManager>>>,
 }
 
 impl Default for McpSessionStore {
     fn default() -> Self {
         Self {
             authenticated: Arc::new(RwLock::new(HashMap::new())),
+            http_sessions: Arc::new(OnceLock::new()),
         }
     }
 }
@@ -57,35 +132,150 @@ impl McpSessionStore {
         Self::default()
     }
 
-    pub fn mark_authenticated(&self, session_id: &str) {
-        if let Ok(mut map) = self.authenticated.write() {
-            let now = Instant::now();
-            let ttl = std::time::Duration::from_secs(MCP_SESSION_TTL_SECS);
+    /// Attach the Streamable HTTP session manager. Only the first call has effect.
+    pub fn attach_http_sessions(&self, manager: Arc<LocalSessionManager>) {
+        let _ = self.http_sessions.set(manager);
+    }
 
-            map.retain(|_, ts| now.duration_since(*ts) < ttl);
+    pub fn mark_authenticated(&self, session_id: &str, identity: McpClientIdentity) {
+        self.insert(session_id, identity, McpTransport::StreamableHttp);
+    }
 
-            if map.len() >= MCP_SESSION_CAP {
-                log::warn!(
-                    "MCP session store at capacity ({}), session {} not cached",
-                    MCP_SESSION_CAP,
-                    session_id
-                );
-                return;
-            }
+    /// Register a legacy SSE session. The returned token is cancelled if the
+    /// user revokes the session; `None` if the store is at capacity.
+    pub fn register_sse(
+        &self,
+        session_id: &str,
+        identity: McpClientIdentity,
+    ) -> Option<CancellationToken> {
+        self.insert(session_id, identity, McpTransport::Sse)
+    }
+
+    fn insert(
+        &self,
+        session_id: &str,
+        identity: McpClientIdentity,
+        transport: McpTransport,
+    ) -> Option<CancellationToken> {
+        let mut map = self.authenticated.write().ok()?;
+        let now = Instant::now();
+        let ttl = std::time::Duration::from_secs(MCP_SESSION_TTL_SECS);
+
+        map.retain(|_, e| now.duration_since(e.authenticated_at) < ttl);
+
+        // Re-authentication of a known session keeps its history
+        if let Some(entry) = map.get_mut(session_id) {
+            entry.authenticated_at = now;
+            entry.identity = identity;
+            return Some(entry.cancel.clone());
+        }
 
-            map.insert(session_id.to_string(), now);
+        if map.len() >= MCP_SESSION_CAP {
+            log::warn!(
+                "MCP session store at capacity ({}), session {} not cached",
+                MCP_SESSION_CAP,
+                session_id
+            );
+            return None;
         }
+
+        let cancel = CancellationToken::new();
+        map.insert(
+            session_id.to_string(),
+            McpSessionEntry {
+                authenticated_at: now,
+                connected_at: chrono::Utc::now(),
+                identity,
+                transport,
+                tool_calls: 0,
+                cancel: cancel.clone(),
+            },
+        );
+        Some(cancel)
     }
 
     pub fn is_authenticated(&self, session_id: &str) -> bool {
-        self.authenticated
-            .read()
-            .map(|map| {
-                map.get(session_id).is_some_and(|ts| {
-                    ts.elapsed() < std::time::Duration::from_secs(MCP_SESSION_TTL_SECS)
+        self.identity(session_id).is_some()
+    }
+
+    /// The client identity a live session authenticated as, if any.
+    pub fn identity(&self, session_id: &str) -> Option<McpClientIdentity> {
+        self.authenticated.read().ok().and_then(|map| {
+            map.get(session_id)
+                .filter(|e| {
+                    e.authenticated_at.elapsed()
+                        < std::time::Duration::from_secs(MCP_SESSION_TTL_SECS)
                 })
+                .map(|e| e.identity.clone())
+        })
+    }
+
+    /// Count a tool call against a session.
+    pub fn record_call(&self, session_id: &str) {
+        if let Ok(mut map) = self.authenticated.write() {
+            if let Some(entry) = map.get_mut(session_id) {
+                entry.tool_calls += 1;
+            }
+        }
+    }
+
+    /// Session ID of an MCP HTTP request: the `Mcp-Session-Id` header
+    /// (Streamable HTTP) or the `sessionId` query parameter (legacy SSE).
+    pub fn session_id_of(parts: &axum::http::request::Parts) -> Option<String> {
+        if let Some(id) = parts
+            .headers
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+        {
+            return Some(id.to_string());
+        }
+        parts
+            .uri
+            .query()?
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("sessionId=").map(|id| id.to_string()))
+    }
+
+    /// All live sessions, most recently connected first.
+    pub fn list(&self) -> Vec<McpSessionInfo> {
+        let Ok(map) = self.authenticated.read() else {
+            return vec![];
+        };
+        let ttl = std::time::Duration::from_secs(MCP_SESSION_TTL_SECS);
+        let mut sessions: Vec<McpSessionInfo> = map
+            .iter()
+            .filter(|(_, e)| e.authenticated_at.elapsed() < ttl)
+            .map(|(id, e)| McpSessionInfo {
+                session_id: id.clone(),
+                client_id: e.identity.client_id.clone(),
+                client_name: e.identity.name.clone(),
+                auth_method: e.identity.method.clone(),
+                transport: e.transport,
+                connected_at: e.connected_at,
+                tool_calls: e.tool_calls,
             })
-            .unwrap_or(false)
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.connected_at));
+        sessions
+    }
+
+    /// Disconnect a session immediately. The client must re-authenticate and
+    /// open a new session to continue. Returns `false` if the session is unknown.
+    pub async fn revoke(&self, session_id: &str) -> bool {
+        let entry = match self.authenticated.write() {
+            Ok(mut map) => map.remove(session_id),
+            Err(_) => None,
+        };
+        let Some(entry) = entry else { return false };
+        entry.cancel.cancel();
+        if entry.transport == McpTransport::StreamableHttp {
+            if let Some(manager) = self.http_sessions.get() {
+                if let Err(e) = manager.close_session(&SessionId::from(session_id)).await {
+                    log::warn!("Failed to close MCP session {}: {}", session_id, e);
+                }
+            }
+        }
+        true
     }
 
     pub fn remove(&self, session_id: &str) {
@@ -99,10 +289,7 @@ impl McpSessionStore {
 // HTTP request logging middleware
 // ---------------------------------------------------------------------------
 
-pub async fn http_request_logging(
-    req: Request<Body>,
-    next: Next,
-) -> Response {
+pub async fn http_request_logging(req: Request<Body>, next: Next) -> Response {
     let method = req.method().clone();
     let uri = req.uri().clone();
     let session_id = req
@@ -126,7 +313,11 @@ pub async fn http_request_logging(
 
     log::info!(
         "HTTP ← {} {} | session={} type={} accept={}",
-        method, uri, session_id, content_type, accept,
+        method,
+        uri,
+        session_id,
+        content_type,
+        accept,
     );
 
     if log::log_enabled!(log::Level::Debug) {
@@ -139,10 +330,7 @@ pub async fn http_request_logging(
 
     let resp = next.run(req).await;
 
-    log::info!(
-        "HTTP → {} {} | status={}",
-        method, uri, resp.status(),
-    );
+    log::info!("HTTP → {} {} | status={}", method, uri, resp.status(),);
 
     resp
 }
@@ -167,7 +355,7 @@ pub async fn http_request_logging(
 ///    to point the client at the authorization server.
 pub async fn gateway_auth_middleware(
     State(state): State<AppState>,
-    req: Request<Body>,
+    mut req: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
     let audit = req.extensions().get::<AuditWriter>().cloned();
@@ -188,11 +376,25 @@ pub async fn gateway_auth_middleware(
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string())
     {
-        if mcp_sessions.is_authenticated(&session_id) {
+        if let Some(mut identity) = mcp_sessions.identity(&session_id) {
+            // Scope edits apply to live sessions too, not just new connections.
+            if let Some(key_id) = identity.client_id.strip_prefix("api_key:") {
+                if let Some(key) = req
+                    .extensions()
+                    .get::<ApiKeyStore>()
+                    .and_then(|s| s.get(key_id))
+                {
+                    identity.scope = key.scope.is_restricted().then_some(key.scope);
+                }
+            }
+            req.extensions_mut().insert(identity);
             let resp = next.run(req).await;
             // Rewrite 401 → 404 per MCP spec for stale sessions (e.g. after host restart)
             if resp.status() == StatusCode::UNAUTHORIZED {
-                log::info!("MCP session {} stale in rmcp — evicting cache, rewriting 401 → 404", session_id);
+                log::info!(
+                    "MCP session {} stale in rmcp — evicting cache, rewriting 401 → 404",
+                    session_id
+                );
                 mcp_sessions.remove(&session_id);
                 return Ok(Response::builder()
                     .status(StatusCode::NOT_FOUND)
@@ -236,44 +438,85 @@ pub async fn gateway_auth_middleware(
                 }
             }
 
-            if let Some(store) = api_key_store {
-                if let Some(key) = store.validate(token) {
-                    log::info!("MCP authenticated via API key: name={} prefix={}", key.name, key.prefix);
-                    let resp = next.run(req).await;
-
-                    if resp.status() == StatusCode::UNAUTHORIZED {
-                        log::info!("MCP session stale after API key auth — rewriting 401 → 404");
-                        return Ok(Response::builder()
-                            .status(StatusCode::NOT_FOUND)
-                            .body(Body::empty())
-                            .unwrap());
-                    }
+            let validation = api_key_store
+                .map(|store| store.check(token, peer_addr.map(|ci| ci.0.ip())))
+                .unwrap_or(ApiKeyValidation::Invalid);
+
+            if let ApiKeyValidation::Expired(key) = validation {
+                // RFC 6750 §3.1: still invalid_token, with a description so the
+                // user knows to extend or rotate the key rather than re-enter it.
+                log::info!(
+                    "MCP API key expired: name={} prefix={}",
+                    key.name,
+                    key.prefix
+                );
+                if let Some(ref audit) = audit {
+                    audit.record(AuditEntry {
+                        actor: AuditActor::McpClient,
+                        source_id: Some(key.name.clone()),
+                        severity: AuditSeverity::Warn,
+                        action: "mcp.session.auth".into(),
+                        subject: None,
+                        result: AuditResult::Failure,
+                        details: Some(serde_json::json!({
+                            "method": "api_key",
+                            "key_prefix": key.prefix,
+                            "error": "expired_key",
+                        })),
+                    });
+                }
+                return Ok(Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .header(
+                        "www-authenticate",
+                        "Bearer realm=\"nexus-mcp\", error=\"invalid_token\", error_description=\"API key expired\"",
+                    )
+                    .body(Body::empty())
+                    .unwrap());
+            }
 
-                    if let Some(session_id) = resp
-                        .headers()
-                        .get("mcp-session-id")
-                        .and_then(|v| v.to_str().ok())
-                    {
-                        mcp_sessions.mark_authenticated(session_id);
-                        log::info!("MCP session authenticated (API key): {}", session_id);
-                        if let Some(ref audit) = audit {
-                            audit.record(AuditEntry {
-                                actor: AuditActor::McpClient,
-                                source_id: Some(key.name.clone()),
-                                severity: AuditSeverity::Info,
-                                action: "mcp.session.auth".into(),
-                                subject: Some(session_id.to_string()),
-                                result: AuditResult::Success,
-                                details: Some(serde_json::json!({
-                                    "method": "api_key",
-                                    "key_prefix": key.prefix,
-                                })),
-                            });
-                        }
-                    }
+            if let ApiKeyValidation::Valid(key) = validation {
+                log::info!(
+                    "MCP authenticated via API key: name={} prefix={}",
+                    key.name,
+                    key.prefix
+                );
+                let identity = McpClientIdentity::api_key(&key);
+                req.extensions_mut().insert(identity.clone());
+                let resp = next.run(req).await;
+
+                if resp.status() == StatusCode::UNAUTHORIZED {
+                    log::info!("MCP session stale after API key auth — rewriting 401 → 404");
+                    return Ok(Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(Body::empty())
+                        .unwrap());
+                }
 
-                    return Ok(resp);
+                if let Some(session_id) = resp
+                    .headers()
+                    .get("mcp-session-id")
+                    .and_then(|v| v.to_str().ok())
+                {
+                    mcp_sessions.mark_authenticated(session_id, identity);
+                    log::info!("MCP session authenticated (API key): {}", session_id);
+                    if let Some(ref audit) = audit {
+                        audit.record(AuditEntry {
+                            actor: AuditActor::McpClient,
+                            source_id: Some(key.name.clone()),
+                            severity: AuditSeverity::Info,
+                            action: "mcp.session.auth".into(),
+                            subject: Some(session_id.to_string()),
+                            result: AuditResult::Success,
+                            details: Some(serde_json::json!({
+                                "method": "api_key",
+                                "key_prefix": key.prefix,
+                            })),
+                        });
+                    }
                 }
+
+                return Ok(resp);
             }
 
             // nxk_ prefix but invalid key — respond per RFC 6750 §3.1.
@@ -313,15 +556,31 @@ pub async fn gateway_auth_middleware(
 
     match bearer_validation {
         TokenValidation::Valid {
+            client_id,
             plugin_id,
+            scopes,
             authorization_details,
             client_name,
-            ..
         } => {
+            // External clients need the `mcp` scope (RFC 6750 §3.1). The user
+            // can remove it per client from Settings without revoking the client.
+            if plugin_id.is_none() && !scopes.iter().any(|s| s == "mcp") {
+                log::info!("MCP Bearer token lacks mcp scope: client={}", client_name);
+                return Ok(Response::builder()
+                    .status(StatusCode::FORBIDDEN)
+                    .header(
+                        "www-authenticate",
+                        "Bearer realm=\"nexus-mcp\", error=\"insufficient_scope\", scope=\"mcp\"",
+                    )
+                    .body(Body::empty())
+                    .unwrap());
+            }
+
             // Internal plugin auth check: plugins require mcp:call permission.
             // Uses RFC 9396 (Authorization Details) if present on the token.
             if let Some(ref pid) = plugin_id {
-                let has_blanket_mcp = rar::details_satisfy(&authorization_details, &Permission::McpCall);
+                let has_blanket_mcp =
+                    rar::details_satisfy(&authorization_details, &Permission::McpCall);
                 let has_any_mcp_access = authorization_details.iter().any(|d| {
                     d.detail_type == "nexus:mcp" && d.actions.iter().any(|a| a == "access")
                 });
@@ -330,7 +589,8 @@ pub async fn gateway_auth_middleware(
                     let has_perm = mgr.permissions.has_permission(pid, &Permission::McpCall)
                         || mgr.permissions.get_grants(pid).iter().any(|g| {
                             matches!(&g.permission, Permission::McpAccess(_))
-                                && g.state == crate::permissions::PermissionState::Active
+                                && g.effective_state()
+                                    == crate::permissions::PermissionState::Active
                         });
                     if !has_perm {
                         log::warn!(
@@ -344,6 +604,8 @@ pub async fn gateway_auth_middleware(
             }
 
             log::info!("MCP authenticated via OAuth: client={}", client_name);
+            let identity = McpClientIdentity::oauth(&client_id, &client_name);
+            req.extensions_mut().insert(identity.clone());
             let resp = next.run(req).await;
 
             if resp.status() == StatusCode::UNAUTHORIZED {
@@ -359,7 +621,7 @@ pub async fn gateway_auth_middleware(
                 .get("mcp-session-id")
                 .and_then(|v| v.to_str().ok())
             {
-                mcp_sessions.mark_authenticated(session_id);
+                mcp_sessions.mark_authenticated(session_id, identity);
                 log::info!("MCP session authenticated (OAuth): {}", session_id);
                 if let Some(ref audit) = audit {
                     audit.record(AuditEntry {
@@ -424,20 +686,25 @@ pub async fn gateway_auth_middleware(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use axum::{routing::get, Extension, Router, middleware as axum_mw};
+    use axum::{middleware as axum_mw, routing::get, Extension, Router};
     use tower::ServiceExt;
 
     use crate::api_keys::ApiKeyStore;
     use crate::permissions::{DefaultPermissionService, PermissionStore};
-    use crate::runtime::mock::MockRuntime;
     use crate::plugin_manager::PluginManager;
+    use crate::runtime::mock::MockRuntime;
 
     fn gateway_test_app(oauth_store: Arc<OAuthStore>, data_dir: &std::path::Path) -> Router {
         let perm_store = PermissionStore::load(data_dir).unwrap_or_default();
         let permissions: Arc<dyn crate::permissions::service::PermissionService> =
             Arc::new(DefaultPermissionService::new(perm_store));
         let mock = Arc::new(MockRuntime::new());
-        let mgr = PluginManager::new(data_dir.to_path_buf(), mock, permissions, oauth_store.clone());
+        let mgr = PluginManager::new(
+            data_dir.to_path_buf(),
+            mock,
+            permissions,
+            oauth_store.clone(),
+        );
         let state: AppState = Arc::new(tokio::sync::RwLock::new(mgr));
 
         let mcp_sessions = McpSessionStore::new();
@@ -445,25 +712,42 @@ mod tests {
 
         Router::new()
             .route("/mcp", get(|| async { "ok" }))
-            .layer(axum_mw::from_fn_with_state(state.clone(), gateway_auth_middleware))
+            .layer(axum_mw::from_fn_with_state(
+                state.clone(),
+                gateway_auth_middleware,
+            ))
             .layer(Extension(oauth_store))
             .layer(Extension(mcp_sessions))
             .layer(Extension(api_key_store))
             .with_state(state)
     }
 
+    fn test_identity() -> McpClientIdentity {
+        McpClientIdentity::oauth("client-1", "Test Client")
+    }
+
     #[test]
     fn session_store_mark_and_check() {
         let store = McpSessionStore::new();
         assert!(!store.is_authenticated("session-1"));
-        store.mark_authenticated("session-1");
+        store.mark_authenticated("session-1", test_identity());
         assert!(store.is_authenticated("session-1"));
     }
 
+    #[test]
+    fn session_store_remembers_identity() {
+        let store = McpSessionStore::new();
+        store.mark_authenticated("session-1", test_identity());
+        let identity = store.identity("session-1").unwrap();
+        assert_eq!(identity.client_id, "oauth:client-1");
+        assert_eq!(identity.name, "Test Client");
+        assert!(store.identity("session-2").is_none());
+    }
+
     #[test]
     fn session_store_independent_sessions() {
         let store = McpSessionStore::new();
-        store.mark_authenticated("session-1");
+        store.mark_authenticated("session-1", test_identity());
         assert!(!store.is_authenticated("session-2"));
     }
 
@@ -471,22 +755,70 @@ mod tests {
     fn session_store_cap_enforcement() {
         let store = McpSessionStore::new();
         for i in 0..MCP_SESSION_CAP {
-            store.mark_authenticated(&format!("session-{}", i));
+            store.mark_authenticated(&format!("session-{}", i), test_identity());
         }
         assert!(store.is_authenticated("session-0"));
         assert!(store.is_authenticated(&format!("session-{}", MCP_SESSION_CAP - 1)));
-        store.mark_authenticated("overflow-session");
+        store.mark_authenticated("overflow-session", test_identity());
         assert!(!store.is_authenticated("overflow-session"));
     }
 
+    #[tokio::test]
+    async fn session_list_counts_calls_and_revoke_disconnects() {
+        let store = McpSessionStore::new();
+        store.mark_authenticated("session-1", test_identity());
+        let sse_cancel = store.register_sse("session-2", test_identity()).unwrap();
+        store.record_call("session-1");
+        store.record_call("session-1");
+
+        let sessions = store.list();
+        assert_eq!(sessions.len(), 2);
+        let s1 = sessions
+            .iter()
+            .find(|s| s.session_id == "session-1")
+            .unwrap();
+        assert_eq!(s1.tool_calls, 2);
+        assert_eq!(s1.transport, McpTransport::StreamableHttp);
+
+        assert!(store.revoke("session-2").await);
+        assert!(sse_cancel.is_cancelled());
+        assert!(!store.is_authenticated("session-2"));
+        assert!(!store.revoke("session-2").await);
+    }
+
     #[tokio::test]
     async fn no_auth_returns_discovery_challenge() {
         let tmp = tempfile::tempdir().unwrap();
         let oauth_store = Arc::new(OAuthStore::load(tmp.path()));
         let app = gateway_test_app(oauth_store, tmp.path());
 
+        let req = Request::builder().uri("/mcp").body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let www_auth = resp
+            .headers()
+            .get("www-authenticate")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(www_auth.contains("realm=\"nexus-mcp\""));
+        assert!(www_auth.contains("resource_metadata="));
+    }
+
+    #[tokio::test]
+    async fn expired_api_key_returns_clear_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (_, raw) = ApiKeyStore::load(tmp.path()).generate(
+            "Old Agent",
+            Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+        );
+        let oauth_store = Arc::new(OAuthStore::load(tmp.path()));
+        let app = gateway_test_app(oauth_store, tmp.path());
+
         let req = Request::builder()
             .uri("/mcp")
+            .header("authorization", format!("Bearer {}", raw))
             .body(Body::empty())
             .unwrap();
         let resp = app.oneshot(req).await.unwrap();
@@ -498,7 +830,6 @@ mod tests {
             .unwrap()
             .to_str()
             .unwrap();
-        assert!(www_auth.contains("realm=\"nexus-mcp\""));
-        assert!(www_auth.contains("resource_metadata="));
+        assert!(www_auth.contains("error_description=\"API key expired\""));
     }
 }
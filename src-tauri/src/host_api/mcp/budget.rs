@@ -0,0 +1,207 @@
+//! Per-client call budgets for the MCP gateway.
+//!
+//! Protects the host from runaway agent loops by enforcing, per authenticated
+//! client (API key or OAuth client):
+//!
+//! - **Calls per minute** — fixed 60-second window, same scheme as
+//!   [`crate::host_api::rate_limit::RateLimiter`].
+//! - **Max concurrent calls** — in-flight calls are tracked with a [`CallPermit`]
+//!   guard that releases its slot on drop.
+//! - **Daily mutating-call quota** — counts calls to state-changing tools and
+//!   resets at UTC midnight.
+//!
+//! Limits themselves live in [`McpSettings`](crate::plugin_manager::storage::McpSettings);
+//! this module only tracks usage against them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::plugin_manager::storage::McpClientLimits;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Why a call was rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetExceeded {
+    RateLimit { limit: u32 },
+    Concurrency { limit: u32 },
+    DailyQuota { limit: u32 },
+}
+
+impl std::fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetExceeded::RateLimit { limit } => {
+                write!(f, "rate limit exceeded ({} calls per minute)", limit)
+            }
+            BudgetExceeded::Concurrency { limit } => {
+                write!(f, "too many concurrent calls (max {})", limit)
+            }
+            BudgetExceeded::DailyQuota { limit } => {
+                write!(f, "daily quota of {} mutating calls exhausted", limit)
+            }
+        }
+    }
+}
+
+struct ClientBudget {
+    window_start: Instant,
+    window_calls: u32,
+    in_flight: u32,
+    day: chrono::NaiveDate,
+    mutating_today: u32,
+}
+
+impl ClientBudget {
+    fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_calls: 0,
+            in_flight: 0,
+            day: chrono::Utc::now().date_naive(),
+            mutating_today: 0,
+        }
+    }
+}
+
+/// Shared usage tracker for all MCP sessions. Cheap to clone.
+#[derive(Clone, Default)]
+pub struct McpBudgets {
+    clients: Arc<Mutex<HashMap<String, ClientBudget>>>,
+}
+
+/// Holds a concurrency slot for the duration of a tool call.
+pub struct CallPermit {
+    budgets: McpBudgets,
+    client_id: String,
+}
+
+impl Drop for CallPermit {
+    fn drop(&mut self) {
+        let mut clients = self.budgets.clients.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(b) = clients.get_mut(&self.client_id) {
+            b.in_flight = b.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+impl McpBudgets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve budget for one call. Rejected calls consume nothing.
+    pub fn acquire(
+        &self,
+        client_id: &str,
+        limits: &McpClientLimits,
+        mutating: bool,
+    ) -> Result<CallPermit, BudgetExceeded> {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        let b = clients.entry(client_id.to_string()).or_insert_with(ClientBudget::new);
+
+        let now = Instant::now();
+        if now.duration_since(b.window_start) >= WINDOW {
+            b.window_start = now;
+            b.window_calls = 0;
+        }
+        let today = chrono::Utc::now().date_naive();
+        if b.day != today {
+            b.day = today;
+            b.mutating_today = 0;
+        }
+
+        if let Some(limit) = limits.max_concurrent_calls {
+            if b.in_flight >= limit {
+                return Err(BudgetExceeded::Concurrency { limit });
+            }
+        }
+        if let Some(limit) = limits.calls_per_minute {
+            if b.window_calls >= limit {
+                return Err(BudgetExceeded::RateLimit { limit });
+            }
+        }
+        if mutating {
+            if let Some(limit) = limits.daily_mutating_quota {
+                if b.mutating_today >= limit {
+                    return Err(BudgetExceeded::DailyQuota { limit });
+                }
+            }
+        }
+
+        b.window_calls += 1;
+        b.in_flight += 1;
+        if mutating {
+            b.mutating_today += 1;
+        }
+
+        Ok(CallPermit {
+            budgets: self.clone(),
+            client_id: client_id.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(per_min: Option<u32>, concurrent: Option<u32>, daily: Option<u32>) -> McpClientLimits {
+        McpClientLimits {
+            calls_per_minute: per_min,
+            max_concurrent_calls: concurrent,
+            daily_mutating_quota: daily,
+        }
+    }
+
+    #[test]
+    fn rate_limit_blocks_after_limit() {
+        let budgets = McpBudgets::new();
+        let l = limits(Some(2), None, None);
+        drop(budgets.acquire("a", &l, false).unwrap());
+        drop(budgets.acquire("a", &l, false).unwrap());
+        assert_eq!(
+            budgets.acquire("a", &l, false).err(),
+            Some(BudgetExceeded::RateLimit { limit: 2 })
+        );
+        // Other clients are unaffected
+        assert!(budgets.acquire("b", &l, false).is_ok());
+    }
+
+    #[test]
+    fn concurrency_slot_released_on_drop() {
+        let budgets = McpBudgets::new();
+        let l = limits(None, Some(1), None);
+        let permit = budgets.acquire("a", &l, false).unwrap();
+        assert_eq!(
+            budgets.acquire("a", &l, false).err(),
+            Some(BudgetExceeded::Concurrency { limit: 1 })
+        );
+        drop(permit);
+        assert!(budgets.acquire("a", &l, false).is_ok());
+    }
+
+    #[test]
+    fn daily_quota_only_counts_mutating_calls() {
+        let budgets = McpBudgets::new();
+        let l = limits(None, None, Some(1));
+        drop(budgets.acquire("a", &l, false).unwrap());
+        drop(budgets.acquire("a", &l, true).unwrap());
+        assert_eq!(
+            budgets.acquire("a", &l, true).err(),
+            Some(BudgetExceeded::DailyQuota { limit: 1 })
+        );
+        // Read-only calls still go through
+        assert!(budgets.acquire("a", &l, false).is_ok());
+    }
+
+    #[test]
+    fn unlimited_never_blocks() {
+        let budgets = McpBudgets::new();
+        let l = McpClientLimits::unlimited();
+        for _ in 0..1000 {
+            drop(budgets.acquire("a", &l, true).unwrap());
+        }
+    }
+}
@@ -7,9 +7,11 @@ use serde_json::json;
 use crate::host_api::approval::{ApprovalBridge, ApprovalDecision, ApprovalRequest};
 use super::types::{McpCallResponse, McpContent, McpToolEntry};
 use crate::event_bus::SharedEventBus;
+use crate::commands::plugins::{prepare_image, vet_image};
 use crate::extensions::RiskLevel;
 use crate::plugin_manager::storage::McpPluginSettings;
 use crate::AppState;
+use super::auth::McpClientIdentity;
 
 /// Virtual plugin ID for built-in tools.
 pub const NEXUS_PLUGIN_ID: &str = "nexus";
@@ -143,6 +145,59 @@ pub fn builtin_tools() -> Vec<McpToolEntry> {
             enabled: true,
             requires_approval: false,
         },
+        McpToolEntry {
+            name: "nexus.tool_stats".into(),
+            description: "Get MCP tool usage analytics: per-tool call counts, success rate, average and max latency, and last-used timestamps. Use to find tools that are never called (candidates to disable) or tools that keep failing. Optionally filter by plugin ID prefix.".into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "plugin_id": {
+                        "type": "string",
+                        "description": "Only include tools from this plugin or extension (e.g. \"nexus\")."
+                    }
+                },
+                "additionalProperties": false
+            }),
+            plugin_id: NEXUS_PLUGIN_ID.into(),
+            plugin_name: NEXUS_PLUGIN_NAME.into(),
+            required_permissions: vec![],
+            permissions_granted: true,
+            enabled: true,
+            requires_approval: false,
+        },
+        McpToolEntry {
+            name: "nexus.find_tools".into(),
+            description: "Search the available MCP tools by keyword and return the best matches with their descriptions. Use this instead of reading the full tool list when you need a capability (e.g. \"screenshot\", \"database query\") and don't know which tool provides it. Set include_schema to get input schemas for the matches.".into(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Keywords to match against tool names and descriptions. All words must match."
+                    },
+                    "plugin_id": {
+                        "type": "string",
+                        "description": "Only search tools from this plugin or extension."
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Max results (default 10, max 50)."
+                    },
+                    "include_schema": {
+                        "type": "boolean",
+                        "description": "Include each tool's input schema (default false)."
+                    }
+                },
+                "required": ["query"],
+                "additionalProperties": false
+            }),
+            plugin_id: NEXUS_PLUGIN_ID.into(),
+            plugin_name: NEXUS_PLUGIN_NAME.into(),
+            required_permissions: vec![],
+            permissions_granted: true,
+            enabled: true,
+            requires_approval: false,
+        },
         // -- Mutating tools --
         McpToolEntry {
             name: "nexus.plugin_start".into(),
@@ -315,7 +370,7 @@ pub fn builtin_tools() -> Vec<McpToolEntry> {
         // -- Workflow tools --
         McpToolEntry {
             name: "nexus.workflow_list".into(),
-            description: "List all event-driven workflows (routing rules). Each workflow routes CloudEvents to plugin tools, extension operations, or frontend channels. Returns id, name, filters, action, enabled state, and creator. Use to see what automations are configured.".into(),
+            description: "List all event-driven workflows (routing rules). Each workflow routes CloudEvents to plugin tools, extension operations, frontend channels, webhooks, or plugin lifecycle commands. Returns id, name, filters, action, enabled state, and creator. Use to see what automations are configured.".into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -351,7 +406,7 @@ pub fn builtin_tools() -> Vec<McpToolEntry> {
         },
         McpToolEntry {
             name: "nexus.workflow_create".into(),
-            description: "Create a new event-driven workflow. Workflows route CloudEvents matching the given filters to an action (invoke a plugin tool, call an extension operation, or emit a frontend event). Use CloudEvents Subscriptions filter dialects: exact, prefix, suffix. Args templates support {{event.data.field}} template expressions. Requires user approval.".into(),
+            description: "Create a new event-driven workflow. Workflows route CloudEvents matching the given filters to an action (invoke a plugin tool, call an extension operation, emit a frontend event, POST to a webhook, or start/stop/restart a plugin). Use CloudEvents Subscriptions filter dialects: exact, prefix, suffix. Args templates support {{event.data.field}} template expressions. Requires user approval.".into(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -361,12 +416,12 @@ pub fn builtin_tools() -> Vec<McpToolEntry> {
                     },
                     "filters": {
                         "type": "array",
-                        "description": "CloudEvents Subscriptions filters. Each filter is an object with one key: 'exact', 'prefix', or 'suffix', mapping to an object of attribute-value pairs. All filters must match (AND logic). Example: [{\"prefix\": {\"type\": \"com.github.\"}}]",
+                        "description": "CloudEvents Subscriptions filters. Each filter is an object with one key: 'exact', 'prefix', or 'suffix', mapping to an object of attribute-value pairs, or 'where', mapping to a predicate over the event data (e.g. data.ref == \"refs/heads/main\" && data.commits.0.author.name != \"bot\"). All filters must match (AND logic). Example: [{\"prefix\": {\"type\": \"com.github.\"}}, {\"where\": \"data.ref == \\\"refs/heads/main\\\"\"}]",
                         "items": { "type": "object" }
                     },
                     "action": {
                         "type": "object",
-                        "description": "The action to execute. Must have an 'action' field: 'invoke_plugin_tool' (requires plugin_id, tool_name, optional args_template), 'call_extension' (requires extension_id, operation, optional args_template), or 'emit_frontend' (requires channel)."
+                        "description": "The action to execute. Must have an 'action' field: 'invoke_plugin_tool' (requires plugin_id, tool_name, optional args_template), 'call_extension' (requires extension_id, operation, optional args_template), 'emit_frontend' (requires channel), 'webhook' (requires an http(s) url; optional headers object and mode 'structured' or 'binary'), or 'control_plugin' (requires plugin_id and command 'start', 'stop', or 'restart')."
                     }
                 },
                 "required": ["filters", "action"],
@@ -712,6 +767,7 @@ pub async fn handle_call(
     state: &AppState,
     bridge: &Arc<ApprovalBridge>,
     event_bus: &SharedEventBus,
+    client: Option<&McpClientIdentity>,
 ) -> Result<McpCallResponse, StatusCode> {
     match tool_name {
         "list_plugins" => handle_list_plugins(state).await,
@@ -721,6 +777,8 @@ pub async fn handle_call(
         "get_settings" => handle_get_settings(state).await,
         "get_mcp_settings" => handle_get_mcp_settings(state).await,
         "engine_status" => handle_engine_status(state).await,
+        "tool_stats" => handle_tool_stats(arguments, state).await,
+        "find_tools" => handle_find_tools(arguments, state, client).await,
         "workflow_list" => handle_workflow_list(event_bus).await,
         "workflow_get" => handle_workflow_get(arguments, event_bus).await,
         "read_file" => handle_read_file(arguments, state).await,
@@ -734,10 +792,10 @@ pub async fn handle_call(
         "execute_command" | "plugin_start" | "plugin_stop" | "plugin_remove"
         | "plugin_install" | "plugin_install_local" | "extension_enable"
         | "extension_disable" | "extension_install_local" => {
-            handle_mutating(tool_name, arguments, state, bridge).await
+            handle_mutating(tool_name, arguments, state, bridge, client).await
         }
         "workflow_create" | "workflow_update" | "workflow_delete" => {
-            handle_mutating_workflow(tool_name, arguments, state, bridge, event_bus).await
+            handle_mutating_workflow(tool_name, arguments, state, bridge, event_bus, client).await
         }
         _ => Err(StatusCode::NOT_FOUND),
     }
@@ -849,25 +907,93 @@ async fn handle_engine_status(state: &AppState) -> Result<McpCallResponse, Statu
     }
 }
 
+async fn handle_tool_stats(args: &serde_json::Value, state: &AppState) -> Result<McpCallResponse, StatusCode> {
+    let prefix = args.get("plugin_id").and_then(|v| v.as_str()).map(|id| format!("{}.", id));
+    let mgr = state.read().await;
+    let stats: Vec<_> = mgr.mcp_tool_stats.snapshot().into_iter()
+        .filter(|e| match &prefix { Some(p) => e.name.starts_with(p.as_str()), None => true })
+        .collect();
+    ok_json(&stats)
+}
+
+/// Relevance of a tool for the given lowercase search terms, or `None` if
+/// any term matches neither the name nor the description. Name hits weigh
+/// more than description hits.
+fn find_tools_score(name: &str, description: &str, terms: &[String]) -> Option<u32> {
+    let name = name.to_lowercase();
+    let description = description.to_lowercase();
+    terms.iter().try_fold(0, |score, term| {
+        match (name.contains(term.as_str()), description.contains(term.as_str())) {
+            (true, true) => Some(score + 4),
+            (true, false) => Some(score + 3),
+            (false, true) => Some(score + 1),
+            (false, false) => None,
+        }
+    })
+}
+
+async fn handle_find_tools(args: &serde_json::Value, state: &AppState, client: Option<&McpClientIdentity>) -> Result<McpCallResponse, StatusCode> {
+    let query = match args.get("query").and_then(|v| v.as_str()) {
+        Some(q) if !q.trim().is_empty() => q,
+        _ => return ok_error("Missing required parameter: query".into()),
+    };
+    let terms: Vec<String> = query.split_whitespace().map(|t| t.to_lowercase()).collect();
+    let prefix = args.get("plugin_id").and_then(|v| v.as_str()).map(|id| format!("{}.", id));
+    let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10).clamp(1, 50) as usize;
+    let include_schema = args.get("include_schema").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let tools = super::registry::collect_tools(state).await;
+    let mut matches: Vec<(u32, &rmcp::model::Tool)> = tools.iter()
+        .filter(|t| match &prefix { Some(p) => t.name.starts_with(p.as_str()), None => true })
+        .filter(|t| super::registry::client_allows(client, &t.name))
+        .filter_map(|t| find_tools_score(&t.name, t.description.as_deref().unwrap_or(""), &terms).map(|score| (score, t)))
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+
+    let total = matches.len();
+    let results: Vec<serde_json::Value> = matches.into_iter().take(limit).map(|(_, t)| {
+        let mut entry = json!({ "name": t.name, "description": t.description });
+        if include_schema {
+            entry["input_schema"] = serde_json::Value::Object((*t.input_schema).clone());
+        }
+        entry
+    }).collect();
+    ok_json(&json!({ "total_matches": total, "tools": results }))
+}
+
 // ---------------------------------------------------------------------------
 // Mutating handlers (with approval)
 // ---------------------------------------------------------------------------
 
+/// Remember an "Approve" decision for the calling client only. Calls without
+/// an identified client can't be permanently approved — they're treated as
+/// "Approve Once".
+async fn persist_client_approval(state: &AppState, client: Option<&McpClientIdentity>, plugin_id: &str, tool: &str) {
+    let Some(c) = client else { return };
+    let mut mgr = state.write().await;
+    mgr.mcp_settings.approve_tool(&c.client_id, &c.name, plugin_id, tool);
+    let _ = mgr.mcp_settings.save();
+}
+
 async fn handle_mutating(
     tool_name: &str,
     arguments: &serde_json::Value,
     state: &AppState,
     bridge: &Arc<ApprovalBridge>,
+    client: Option<&McpClientIdentity>,
 ) -> Result<McpCallResponse, StatusCode> {
     let already_approved = {
         let mgr = state.read().await;
-        mgr.mcp_settings.plugins.get(NEXUS_PLUGIN_ID).is_some_and(|s| s.approved_tools.contains(&tool_name.to_string()))
+        mgr.mcp_settings.is_tool_approved(client.map(|c| c.client_id.as_str()), NEXUS_PLUGIN_ID, tool_name)
     };
 
     if !already_approved {
         let mut context = std::collections::HashMap::new();
         context.insert("tool_name".to_string(), tool_name.to_string());
         context.insert("plugin_name".to_string(), NEXUS_PLUGIN_NAME.to_string());
+        if let Some(c) = client {
+            context.insert("client_name".to_string(), c.name.clone());
+        }
         context.insert("description".to_string(), describe_mutating_tool(tool_name));
         if let serde_json::Value::Object(map) = arguments {
             for (k, v) in map {
@@ -885,13 +1011,8 @@ async fn handle_mutating(
         };
         match bridge.request_approval(approval_req).await {
             ApprovalDecision::Approve => {
-                let mut mgr = state.write().await;
-                let plugin_settings = mgr.mcp_settings.plugins.entry(NEXUS_PLUGIN_ID.to_string()).or_insert_with(McpPluginSettings::default);
-                if !plugin_settings.approved_tools.contains(&tool_name.to_string()) {
-                    plugin_settings.approved_tools.push(tool_name.to_string());
-                }
-                let _ = mgr.mcp_settings.save();
-                log::info!("AUDIT Nexus MCP tool permanently approved: tool={}", tool_name);
+                persist_client_approval(state, client, NEXUS_PLUGIN_ID, tool_name).await;
+                log::info!("AUDIT Nexus MCP tool permanently approved: tool={} client={}", tool_name, client.map_or("-", |c| c.client_id.as_str()));
             }
             ApprovalDecision::ApproveOnce => { log::info!("AUDIT Nexus MCP tool approved once: tool={}", tool_name); }
             ApprovalDecision::Deny => {
@@ -968,14 +1089,23 @@ async fn exec_plugin_remove(args: &serde_json::Value, state: &AppState) -> Resul
 
 async fn exec_plugin_install(args: &serde_json::Value, state: &AppState) -> Result<McpCallResponse, StatusCode> {
     let manifest_url = require_str(args, "manifest_url")?;
-    let manifest = match crate::plugin_manager::registry::fetch_manifest(&manifest_url).await {
+    let manifest = match crate::plugin_manager::PluginManager::fetch_registry_manifest(state, &manifest_url, false).await {
         Ok(m) => m,
         Err(e) => return ok_error(format!("Failed to fetch manifest: {}", e)),
     };
     if let Err(e) = manifest.validate() { return ok_error(format!("Invalid manifest: {}", e)); }
     let plugin_id = manifest.id.clone();
+    let (locks, runtime) = { let mgr = state.read().await; (mgr.plugin_locks.clone(), mgr.runtime.clone()) };
+    let _plugin_lock = locks.lock(&plugin_id).await;
+    if let Err(e) = prepare_image(None, runtime.as_ref(), &manifest, None).await {
+        return ok_error(format!("Failed to install '{}': {}", plugin_id, e));
+    }
+    let vetted = match vet_image(state, None, &manifest).await {
+        Ok(v) => v,
+        Err(e) => return ok_error(format!("Failed to install '{}': {}", plugin_id, e)),
+    };
     let mut mgr = state.write().await;
-    match mgr.install(manifest, vec![], vec![], Some(&manifest_url), None).await {
+    match mgr.install_vetted(manifest, vetted, vec![], vec![], Some(&manifest_url), None).await {
         Ok(_) => {
             mgr.notify_tools_changed();
             ok_json(&json!({ "status": "installed", "plugin_id": plugin_id }))
@@ -1023,13 +1153,19 @@ async fn exec_plugin_install_local(args: &serde_json::Value, state: &AppState) -
     if let Err(e) = manifest.validate() { return ok_error(format!("Invalid manifest: {}", e)); }
     let plugin_id = manifest.id.clone();
     let manifest_dir = std::path::Path::new(&manifest_path).parent().ok_or(StatusCode::BAD_REQUEST)?;
-    if manifest_dir.join("Dockerfile").exists() {
-        let runtime = { state.read().await.runtime.clone() };
-        if let Err(e) = runtime.build_image(manifest_dir, &manifest.image).await { return ok_error(format!("Docker build failed: {}", e)); }
+    let build_dir = Some(manifest_dir).filter(|dir| dir.join("Dockerfile").exists());
+    let (locks, runtime) = { let mgr = state.read().await; (mgr.plugin_locks.clone(), mgr.runtime.clone()) };
+    let _plugin_lock = locks.lock(&plugin_id).await;
+    if let Err(e) = prepare_image(None, runtime.as_ref(), &manifest, build_dir).await {
+        return ok_error(format!("Failed to install '{}': {}", plugin_id, e));
     }
+    let vetted = match vet_image(state, None, &manifest).await {
+        Ok(v) => v,
+        Err(e) => return ok_error(format!("Failed to install '{}': {}", plugin_id, e)),
+    };
     let was_running = { let mgr = state.read().await; mgr.storage.get(&plugin_id).is_some_and(|p| matches!(p.status, crate::plugin_manager::storage::PluginStatus::Running)) };
     let mut mgr = state.write().await;
-    if let Err(e) = mgr.install(manifest, vec![], vec![], None, Some(manifest_path.clone())).await {
+    if let Err(e) = mgr.install_vetted(manifest, vetted, vec![], vec![], None, Some(manifest_path.clone())).await {
         return ok_error(format!("Failed to install '{}': {}", plugin_id, e));
     }
     if was_running { let _ = mgr.start(&plugin_id).await; }
@@ -1187,7 +1323,7 @@ async fn handle_fetch_url(args: &serde_json::Value) -> Result<McpCallResponse, S
     let method = args.get("method").and_then(|v| v.as_str()).unwrap_or("GET").to_uppercase();
     let url = match reqwest::Url::parse(&url_str) { Ok(u) => u, Err(e) => return ok_error(format!("Invalid URL: {}", e)) };
     if url.scheme() != "http" && url.scheme() != "https" { return ok_error("Only http/https supported".into()); }
-    let client = reqwest::Client::new();
+    let client = match crate::proxy::apply(reqwest::Client::builder()).build() { Ok(c) => c, Err(e) => return ok_error(format!("HTTP client error: {}", e)) };
     let mut rb = match method.as_str() { "POST" => client.post(url), "PUT" => client.put(url), "PATCH" => client.patch(url), "DELETE" => client.delete(url), "HEAD" => client.head(url), _ => client.get(url) };
     if let Some(headers) = args.get("headers").and_then(|v| v.as_object()) { for (k, v) in headers { if let Some(s) = v.as_str() { rb = rb.header(k, s); } } }
     if let Some(body) = args.get("body").and_then(|v| v.as_str()) { rb = rb.body(body.to_string()); }
@@ -1234,6 +1370,8 @@ async fn exec_execute_command(args: &serde_json::Value, _state: &AppState) -> Re
     let cmd_args: Vec<String> = args.get("args").and_then(|v| v.as_array()).map(|a| a.iter().filter_map(|v| v.as_str().map(String::from)).collect()).unwrap_or_default();
     let mut cmd = tokio::process::Command::new(&command);
     cmd.args(&cmd_args);
+    // The gateway cancels calls that exceed their timeout — take the child down with them
+    cmd.kill_on_drop(true);
     cmd.stdout(std::process::Stdio::piped()).stderr(std::process::Stdio::piped());
     if let Some(wd) = args.get("working_dir").and_then(|v| v.as_str()) { cmd.current_dir(wd); }
     match cmd.spawn() {
@@ -1273,16 +1411,20 @@ async fn handle_mutating_workflow(
     state: &AppState,
     bridge: &Arc<ApprovalBridge>,
     event_bus: &SharedEventBus,
+    client: Option<&McpClientIdentity>,
 ) -> Result<McpCallResponse, StatusCode> {
     let already_approved = {
         let mgr = state.read().await;
-        mgr.mcp_settings.plugins.get(NEXUS_PLUGIN_ID).is_some_and(|s| s.approved_tools.contains(&tool_name.to_string()))
+        mgr.mcp_settings.is_tool_approved(client.map(|c| c.client_id.as_str()), NEXUS_PLUGIN_ID, tool_name)
     };
 
     if !already_approved {
         let mut context = std::collections::HashMap::new();
         context.insert("tool_name".to_string(), tool_name.to_string());
         context.insert("plugin_name".to_string(), NEXUS_PLUGIN_NAME.to_string());
+        if let Some(c) = client {
+            context.insert("client_name".to_string(), c.name.clone());
+        }
         context.insert("description".to_string(), describe_workflow_tool(tool_name));
         if let serde_json::Value::Object(map) = arguments {
             for (k, v) in map {
@@ -1300,13 +1442,8 @@ async fn handle_mutating_workflow(
         };
         match bridge.request_approval(approval_req).await {
             ApprovalDecision::Approve => {
-                let mut mgr = state.write().await;
-                let plugin_settings = mgr.mcp_settings.plugins.entry(NEXUS_PLUGIN_ID.to_string()).or_insert_with(McpPluginSettings::default);
-                if !plugin_settings.approved_tools.contains(&tool_name.to_string()) {
-                    plugin_settings.approved_tools.push(tool_name.to_string());
-                }
-                let _ = mgr.mcp_settings.save();
-                log::info!("AUDIT Nexus MCP workflow tool permanently approved: tool={}", tool_name);
+                persist_client_approval(state, client, NEXUS_PLUGIN_ID, tool_name).await;
+                log::info!("AUDIT Nexus MCP workflow tool permanently approved: tool={} client={}", tool_name, client.map_or("-", |c| c.client_id.as_str()));
             }
             ApprovalDecision::ApproveOnce => { log::info!("AUDIT Nexus MCP workflow tool approved once: tool={}", tool_name); }
             ApprovalDecision::Deny => {
@@ -1415,28 +1552,27 @@ pub async fn extension_mcp_tools(state: &AppState) -> Vec<McpToolEntry> {
     tools
 }
 
-pub async fn handle_extension_call(ext_id: &str, operation: &str, arguments: &serde_json::Value, state: &AppState, bridge: &Arc<ApprovalBridge>) -> Result<McpCallResponse, StatusCode> {
+pub async fn handle_extension_call(ext_id: &str, operation: &str, arguments: &serde_json::Value, state: &AppState, bridge: &Arc<ApprovalBridge>, client: Option<&McpClientIdentity>) -> Result<McpCallResponse, StatusCode> {
     let (ext_arc, op_def) = {
         let mgr = state.read().await;
         let ext = mgr.extensions.get_arc(ext_id).ok_or(StatusCode::NOT_FOUND)?;
         let op = ext.operations().into_iter().find(|o| o.name == operation && o.mcp_expose).ok_or(StatusCode::NOT_FOUND)?;
+        if !op.risk_level.allowed_in_read_only() && mgr.settings.read_only_mode {
+            return ok_error(format!("[Nexus] '{}.{}' is blocked while Nexus is in read-only mode.", ext_id, operation));
+        }
         (ext, op)
     };
     if matches!(op_def.risk_level, RiskLevel::Medium | RiskLevel::High) {
-        let already_approved = { let mgr = state.read().await; mgr.mcp_settings.plugins.get(ext_id).is_some_and(|s| s.approved_tools.contains(&operation.to_string())) };
+        let already_approved = { let mgr = state.read().await; mgr.mcp_settings.is_tool_approved(client.map(|c| c.client_id.as_str()), ext_id, operation) };
         if !already_approved {
             let mut context = std::collections::HashMap::new();
             context.insert("tool_name".to_string(), format!("{}.{}", ext_id, operation));
             context.insert("plugin_name".to_string(), ext_id.to_string());
+            if let Some(c) = client { context.insert("client_name".to_string(), c.name.clone()); }
             context.insert("description".to_string(), op_def.description.clone());
             let approval_req = ApprovalRequest { id: uuid::Uuid::new_v4().to_string(), plugin_id: ext_id.to_string(), plugin_name: ext_id.to_string(), category: "mcp_tool".to_string(), permission: format!("mcp:{}:{}", ext_id, operation), context };
             match bridge.request_approval(approval_req).await {
-                ApprovalDecision::Approve => {
-                    let mut mgr = state.write().await;
-                    let s = mgr.mcp_settings.plugins.entry(ext_id.to_string()).or_insert_with(McpPluginSettings::default);
-                    if !s.approved_tools.contains(&operation.to_string()) { s.approved_tools.push(operation.to_string()); }
-                    let _ = mgr.mcp_settings.save();
-                }
+                ApprovalDecision::Approve => persist_client_approval(state, client, ext_id, operation).await,
                 ApprovalDecision::ApproveOnce => {}
                 ApprovalDecision::Deny => return ok_error("Denied by user".to_string())
             }
@@ -2,18 +2,35 @@
 
 use rmcp::model::*;
 use rmcp::service::ServiceExt;
+use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
 use rmcp::transport::StreamableHttpClientTransport;
 
 /// Cached MCP capabilities for a single plugin's native MCP server.
 #[derive(Debug, Clone)]
 pub struct PluginMcpCache {
     pub url: String,
+    /// Bearer token sent to remote endpoints.
+    bearer_token: Option<String>,
     pub tools: Vec<Tool>,
     pub resources: Vec<Resource>,
     pub resource_templates: Vec<ResourceTemplate>,
     pub prompts: Vec<Prompt>,
 }
 
+impl PluginMcpCache {
+    fn config(&self) -> StreamableHttpClientTransportConfig {
+        transport_config(&self.url, self.bearer_token.as_deref())
+    }
+}
+
+pub(crate) fn transport_config(url: &str, bearer_token: Option<&str>) -> StreamableHttpClientTransportConfig {
+    let config = StreamableHttpClientTransportConfig::with_uri(url);
+    match bearer_token {
+        Some(token) => config.auth_header(token),
+        None => config,
+    }
+}
+
 /// Manages MCP client connections to plugin servers.
 pub struct McpClientManager {
     plugins: std::collections::HashMap<String, PluginMcpCache>,
@@ -34,8 +51,18 @@ impl McpClientManager {
     ) -> Result<(), String> {
         let url = format!("http://127.0.0.1:{}{}", host_port, path);
         log::info!("Connecting to native MCP server for plugin '{}' at {}", plugin_id, url);
+        self.connect_url(plugin_id, url, None).await
+    }
+
+    /// Connect to a remote MCP endpoint (containerless plugin), sending
+    /// `bearer_token` as `Authorization: Bearer …` on every request.
+    pub async fn connect_remote(&mut self, plugin_id: &str, url: &str, bearer_token: Option<String>) -> Result<(), String> {
+        log::info!("Connecting to remote MCP server for plugin '{}' at {}", plugin_id, url);
+        self.connect_url(plugin_id, url.to_string(), bearer_token).await
+    }
 
-        let transport = StreamableHttpClientTransport::from_uri(url.as_str());
+    async fn connect_url(&mut self, plugin_id: &str, url: String, bearer_token: Option<String>) -> Result<(), String> {
+        let transport = StreamableHttpClientTransport::from_config(transport_config(&url, bearer_token.as_deref()));
         let service = ().serve(transport).await.map_err(|e| format!("Failed to connect: {}", e))?;
 
         let tools = service.list_tools(Default::default()).await.map(|r| r.tools).unwrap_or_default();
@@ -45,7 +72,7 @@ impl McpClientManager {
 
         let _ = service.cancel().await;
 
-        self.plugins.insert(plugin_id.to_string(), PluginMcpCache { url, tools, resources, resource_templates, prompts });
+        self.plugins.insert(plugin_id.to_string(), PluginMcpCache { url, bearer_token, tools, resources, resource_templates, prompts });
         Ok(())
     }
 
@@ -59,7 +86,7 @@ impl McpClientManager {
 
     pub async fn call_tool(&self, plugin_id: &str, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<CallToolResult, String> {
         let cache = self.plugins.get(plugin_id).ok_or_else(|| format!("No connection for '{}'", plugin_id))?;
-        let transport = StreamableHttpClientTransport::from_uri(cache.url.as_str());
+        let transport = StreamableHttpClientTransport::from_config(cache.config());
         let service = ().serve(transport).await.map_err(|e| format!("Failed to connect: {}", e))?;
         let result = service.call_tool(CallToolRequestParams { name: std::borrow::Cow::Owned(name.to_string()), arguments, task: None, meta: None }).await.map_err(|e| format!("Call failed: {}", e))?;
         let _ = service.cancel().await;
@@ -68,7 +95,7 @@ impl McpClientManager {
 
     pub async fn read_resource(&self, plugin_id: &str, uri: &str) -> Result<ReadResourceResult, String> {
         let cache = self.plugins.get(plugin_id).ok_or_else(|| format!("No connection for '{}'", plugin_id))?;
-        let transport = StreamableHttpClientTransport::from_uri(cache.url.as_str());
+        let transport = StreamableHttpClientTransport::from_config(cache.config());
         let service = ().serve(transport).await.map_err(|e| format!("Failed to connect: {}", e))?;
         let result = service.read_resource(ReadResourceRequestParams { uri: uri.to_string(), meta: None }).await.map_err(|e| format!("Read failed: {}", e))?;
         let _ = service.cancel().await;
@@ -77,7 +104,7 @@ impl McpClientManager {
 
     pub async fn get_prompt(&self, plugin_id: &str, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<GetPromptResult, String> {
         let cache = self.plugins.get(plugin_id).ok_or_else(|| format!("No connection for '{}'", plugin_id))?;
-        let transport = StreamableHttpClientTransport::from_uri(cache.url.as_str());
+        let transport = StreamableHttpClientTransport::from_config(cache.config());
         let service = ().serve(transport).await.map_err(|e| format!("Failed to connect: {}", e))?;
         let result = service.get_prompt(GetPromptRequestParams { name: name.to_string(), arguments, meta: None }).await.map_err(|e| format!("Prompt failed: {}", e))?;
         let _ = service.cancel().await;
@@ -1,12 +1,18 @@
 pub mod auth;
+pub mod budget;
 pub mod builtin;
 pub mod client;
 pub mod registry;
 pub mod server;
+pub mod sse;
+pub mod stats;
 pub mod types;
 
-pub use auth::{gateway_auth_middleware, http_request_logging, McpSessionStore};
+pub use auth::{gateway_auth_middleware, http_request_logging, McpClientIdentity, McpSessionInfo, McpSessionStore};
+pub use budget::McpBudgets;
 pub use client::McpClientManager;
 pub use registry::McpRegistry;
 pub use server::NexusMcpServer;
+pub use sse::McpSseSessions;
+pub use stats::McpToolStats;
 pub use types::{McpCallResponse, McpContent, McpToolEntry};
@@ -13,103 +13,277 @@
 //! For example, `com.nexus.hello-world.greet`. Built-in tools use the `nexus`
 //! namespace (e.g., `nexus.list_plugins`).
 
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use rmcp::model::*;
 use rmcp::ErrorData as McpError;
 use crate::AppState;
 use crate::event_bus::SharedEventBus;
+use super::auth::McpClientIdentity;
+use super::budget::McpBudgets;
 use super::builtin;
 use crate::audit::writer::AuditWriter;
-use crate::plugin_manager::storage::{PluginStatus, McpPluginSettings};
+use crate::audit::{AuditEntry, AuditActor, AuditSeverity, AuditResult as AuditRes};
+use crate::plugin_manager::storage::{PluginStatus, McpSettings};
 use crate::host_api::approval::{ApprovalBridge, ApprovalDecision, ApprovalRequest};
 use crate::permissions::Permission;
 
+/// Audit severity of a tool call, derived from the tool's nature:
+/// execute_command and destructive operations are Critical, mutating tools
+/// are Warn, read-only tools are Info.
+fn tool_severity(name: &str) -> AuditSeverity {
+    match name {
+        n if n.contains("execute_command") => AuditSeverity::Critical,
+        n if n.contains("plugin_remove") || n.contains("extension_remove") => AuditSeverity::Critical,
+        n if n.contains("plugin_install") || n.contains("plugin_start")
+            || n.contains("plugin_stop") || n.contains("extension_enable")
+            || n.contains("extension_disable") || n.contains("extension_install") => AuditSeverity::Warn,
+        // File writes and edits from an external client warrant Warn
+        n if n.contains("write_file") || n.contains("edit_file") => AuditSeverity::Warn,
+        _ => AuditSeverity::Info,
+    }
+}
+
+/// Whether a call changes host state (counts against the daily mutating quota).
+fn is_mutating(name: &str) -> bool {
+    if !matches!(tool_severity(name), AuditSeverity::Info) {
+        return true;
+    }
+    name.starts_with("nexus.") && approval_builtins().contains(name)
+}
+
+/// Whether read-only mode lets `name` run. Built-ins go by [`is_mutating`].
+/// Provider tools count as mutating unless they declare otherwise:
+/// `readOnlyHint` on a plugin's MCP tool, or low risk on an extension op.
+fn allowed_in_read_only(mgr: &crate::plugin_manager::PluginManager, name: &str) -> bool {
+    if name.starts_with("nexus.") {
+        return !is_mutating(name);
+    }
+    let plugin_tool = mgr.mcp_clients.iter().find_map(|(plugin_id, cache)| {
+        let local = name.strip_prefix(plugin_id)?.strip_prefix('.')?;
+        cache.tools.iter().find(|t| t.name == local)
+    });
+    if let Some(tool) = plugin_tool {
+        return declared_read_only(tool);
+    }
+    mgr.extensions.list().iter().any(|ext| {
+        name.strip_prefix(ext.id.as_str())
+            .and_then(|rest| rest.strip_prefix('.'))
+            .is_some_and(|local| {
+                ext.operations.iter().any(|op| op.name == local && op.risk_level.allowed_in_read_only())
+            })
+    })
+}
+
+/// Whether an MCP tool is annotated as read-only.
+fn declared_read_only(tool: &Tool) -> bool {
+    tool.annotations.as_ref().and_then(|a| a.read_only_hint) == Some(true)
+}
+
+/// Names of the built-in tools that need the user's approval.
+fn approval_builtins() -> &'static HashSet<String> {
+    static NAMES: OnceLock<HashSet<String>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        builtin::builtin_tools()
+            .into_iter()
+            .filter(|t| t.requires_approval)
+            .map(|t| t.name)
+            .collect()
+    })
+}
+
+/// Whether a scoped API key lets `client` see and call `name`.
+pub(super) fn client_allows(client: Option<&McpClientIdentity>, name: &str) -> bool {
+    client
+        .and_then(|c| c.scope.as_ref())
+        .map_or(true, |scope| scope.allows(name, is_mutating(name)))
+}
+
+/// Default timeout for read-only built-in tools.
+const READ_ONLY_TIMEOUT_SECS: u64 = 30;
+/// Default timeout for plugin and extension tools.
+const PROVIDER_TIMEOUT_SECS: u64 = 120;
+/// Default timeout for state-changing tools. Covers the 60s approval prompt
+/// plus slow operations like image pulls during `plugin_install`.
+const MUTATING_TIMEOUT_SECS: u64 = 600;
+
+/// Effective timeout for a tool call: the user's override from MCP settings,
+/// otherwise a default based on the tool's category.
+pub fn tool_timeout(settings: &McpSettings, name: &str) -> std::time::Duration {
+    let secs = settings.tool_timeout_override(name).unwrap_or_else(|| {
+        match name {
+            // Approval (60s) + the command's own max runtime (600s)
+            "nexus.execute_command" => 660,
+            n if is_mutating(n) => MUTATING_TIMEOUT_SECS,
+            n if n.starts_with("nexus.") => READ_ONLY_TIMEOUT_SECS,
+            _ => PROVIDER_TIMEOUT_SECS,
+        }
+    });
+    std::time::Duration::from_secs(secs)
+}
+
+/// Built-in tools that create, start, stop or remove plugin containers and
+/// extensions. They run on their own task, so a timeout or a disconnected
+/// client can't cancel one halfway and leave a plugin half-installed.
+fn runs_to_completion(name: &str) -> bool {
+    matches!(
+        name,
+        "nexus.plugin_install"
+            | "nexus.plugin_install_local"
+            | "nexus.plugin_start"
+            | "nexus.plugin_stop"
+            | "nexus.plugin_remove"
+            | "nexus.extension_enable"
+            | "nexus.extension_disable"
+            | "nexus.extension_install_local"
+    )
+}
+
+/// Result of a call that hit its timeout. `cancelled` is false when the tool
+/// keeps running in the background.
+fn timeout_result(name: &str, timeout: std::time::Duration, cancelled: bool) -> CallToolResult {
+    let message = if cancelled {
+        format!("[Nexus] Tool '{}' did not complete within {}s and was cancelled.", name, timeout.as_secs())
+    } else {
+        format!("[Nexus] Tool '{}' did not complete within {}s. It is still running; check the plugin's status before retrying.", name, timeout.as_secs())
+    };
+    CallToolResult::structured_error(serde_json::json!({
+        "error": "timeout",
+        "tool": name,
+        "timeout_secs": timeout.as_secs(),
+        "cancelled": cancelled,
+        "message": message,
+    }))
+}
+
+fn builtin_result(local_name: &str, result: Result<super::types::McpCallResponse, axum::http::StatusCode>) -> Result<CallToolResult, McpError> {
+    match result {
+        Ok(resp) => {
+            let content = resp.content.into_iter().map(|c| Content::text(c.text)).collect();
+            if resp.is_error { Ok(CallToolResult::error(content)) } else { Ok(CallToolResult::success(content)) }
+        }
+        Err(_) => Err(McpError::internal_error(format!("Built-in tool '{}' failed", local_name), None)),
+    }
+}
+
 pub struct McpRegistry {
     state: AppState,
     approval_bridge: Arc<ApprovalBridge>,
     audit: AuditWriter,
     event_bus: SharedEventBus,
+    budgets: McpBudgets,
 }
 
-impl McpRegistry {
-    pub fn new(state: AppState, approval_bridge: Arc<ApprovalBridge>, audit: AuditWriter, event_bus: SharedEventBus) -> Self {
-        Self { state, approval_bridge, audit, event_bus }
-    }
+/// Number of tools returned per `tools/list` page.
+pub const TOOLS_PAGE_SIZE: usize = 100;
 
-    /// Aggregates all available tools from all providers.
-    ///
-    /// This filtering follows a whitelist model:
-    /// - The plugin must be healthy and running.
-    /// - All host-level permissions for the plugin must be granted.
-    /// - The specific tool must be enabled in the Nexus MCP settings.
-    pub async fn list_tools(&self) -> Vec<Tool> {
-        let mgr = self.state.read().await;
-        let mut tools = Vec::new();
-        if !mgr.mcp_settings.enabled { return tools; }
-
-        // 1. Built-in tools (e.g. nexus.list_plugins)
-        for tool_def in builtin::builtin_tools() {
-            let local_name = tool_def.name.strip_prefix("nexus.").unwrap_or(&tool_def.name);
-            if mgr.mcp_settings.plugins.get("nexus").is_some_and(|s| s.enabled && s.enabled_tools.contains(&local_name.to_string())) {
-                tools.push(Tool {
-                    name: Cow::Owned(tool_def.name.clone()),
-                    title: None, description: Some(Cow::Owned(tool_def.description.clone())),
-                    input_schema: Arc::new(match &tool_def.input_schema { serde_json::Value::Object(map) => map.clone(), _ => serde_json::Map::new() }),
-                    output_schema: None, annotations: None, execution: None, icons: None, meta: None,
-                });
-            }
+/// Aggregates all available tools from all providers.
+///
+/// This filtering follows a whitelist model:
+/// - The plugin must be healthy and running.
+/// - All host-level permissions for the plugin must be granted.
+/// - The specific tool must be enabled in the Nexus MCP settings.
+pub async fn collect_tools(state: &AppState) -> Vec<Tool> {
+    let mgr = state.read().await;
+    let mut tools = Vec::new();
+    if !mgr.mcp_settings.enabled { return tools; }
+
+    // 1. Built-in tools (e.g. nexus.list_plugins)
+    for tool_def in builtin::builtin_tools() {
+        let local_name = tool_def.name.strip_prefix("nexus.").unwrap_or(&tool_def.name);
+        if mgr.mcp_settings.plugins.get("nexus").is_some_and(|s| s.enabled && s.enabled_tools.contains(&local_name.to_string())) {
+            tools.push(Tool {
+                name: Cow::Owned(tool_def.name.clone()),
+                title: None, description: Some(Cow::Owned(tool_def.description.clone())),
+                input_schema: Arc::new(match &tool_def.input_schema { serde_json::Value::Object(map) => map.clone(), _ => serde_json::Map::new() }),
+                output_schema: None, annotations: None, execution: None, icons: None, meta: None,
+            });
         }
+    }
 
-        // 2. Extension tools (from native binaries)
-        for ext_info in mgr.extensions.list() {
-            if !mgr.extension_loader.storage.get(&ext_info.id).is_some_and(|e| e.enabled) { continue; }
-            let ext_mcp = mgr.mcp_settings.plugins.get(&ext_info.id);
-            if !ext_mcp.is_some_and(|s| s.enabled) { continue; }
-            for op in &ext_info.operations {
-                if !op.mcp_expose || !ext_mcp.is_some_and(|s| s.enabled_tools.contains(&op.name)) { continue; }
-                tools.push(Tool {
-                    name: Cow::Owned(format!("{}.{}", ext_info.id, op.name)),
-                    title: None, description: Some(Cow::Owned(op.mcp_description.clone().unwrap_or(op.description.clone()))),
-                    input_schema: Arc::new(match &op.input_schema { serde_json::Value::Object(map) => map.clone(), _ => serde_json::Map::new() }),
-                    output_schema: None, annotations: None, icons: None, execution: None, meta: None,
-                });
-            }
+    // 2. Extension tools (from native binaries)
+    for ext_info in mgr.extensions.list() {
+        if !mgr.extension_loader.storage.get(&ext_info.id).is_some_and(|e| e.enabled) { continue; }
+        let ext_mcp = mgr.mcp_settings.plugins.get(&ext_info.id);
+        if !ext_mcp.is_some_and(|s| s.enabled) { continue; }
+        for op in &ext_info.operations {
+            if !op.mcp_expose || !ext_mcp.is_some_and(|s| s.enabled_tools.contains(&op.name)) { continue; }
+            tools.push(Tool {
+                name: Cow::Owned(format!("{}.{}", ext_info.id, op.name)),
+                title: None, description: Some(Cow::Owned(op.mcp_description.clone().unwrap_or(op.description.clone()))),
+                input_schema: Arc::new(match &op.input_schema { serde_json::Value::Object(map) => map.clone(), _ => serde_json::Map::new() }),
+                output_schema: None, annotations: None, icons: None, execution: None, meta: None,
+            });
         }
+    }
 
-        // 3. Plugin tools (Native and Legacy)
-        for plugin in mgr.storage.list() {
-            if plugin.status != PluginStatus::Running { continue; }
-            let plugin_id = &plugin.manifest.id;
-            let plugin_mcp = mgr.mcp_settings.plugins.get(plugin_id);
-            if !plugin_mcp.is_some_and(|s| s.enabled) { continue; }
-            if !plugin.manifest.permissions.iter().all(|perm| mgr.permissions.has_permission(plugin_id, perm)) { continue; }
-
-            if let Some(cache) = mgr.mcp_clients.get(plugin_id) {
-                // Native MCP (preferred)
-                for tool in &cache.tools {
-                    if plugin_mcp.is_some_and(|s| s.enabled_tools.contains(&tool.name.to_string())) {
-                        let mut t = tool.clone();
-                        t.name = Cow::Owned(format!("{}.{}", plugin_id, tool.name));
-                        tools.push(t);
-                    }
+    // 3. Plugin tools (Native and Legacy)
+    for plugin in mgr.storage.list() {
+        if plugin.status != PluginStatus::Running { continue; }
+        let plugin_id = &plugin.manifest.id;
+        let plugin_mcp = mgr.mcp_settings.plugins.get(plugin_id);
+        if !plugin_mcp.is_some_and(|s| s.enabled) { continue; }
+        if !plugin.manifest.permissions.iter().all(|perm| mgr.permissions.has_permission(plugin_id, perm)) { continue; }
+
+        if let Some(cache) = mgr.mcp_clients.get(plugin_id) {
+            // Native MCP (preferred)
+            for tool in &cache.tools {
+                if plugin_mcp.is_some_and(|s| s.enabled_tools.contains(&tool.name.to_string())) {
+                    let mut t = tool.clone();
+                    t.name = Cow::Owned(format!("{}.{}", plugin_id, tool.name));
+                    tools.push(t);
                 }
-            } else if let Some(mcp_config) = &plugin.manifest.mcp {
-                // Legacy HTTP protocol
-                for tool_def in &mcp_config.tools {
-                    if plugin_mcp.is_some_and(|s| s.enabled_tools.contains(&tool_def.name)) {
-                        tools.push(Tool {
-                            name: Cow::Owned(format!("{}.{}", plugin_id, tool_def.name)),
-                            title: None, description: Some(Cow::Owned(tool_def.description.clone())),
-                            input_schema: Arc::new(match &tool_def.input_schema { serde_json::Value::Object(map) => map.clone(), _ => serde_json::Map::new() }),
-                            output_schema: None, annotations: None, icons: None, execution: None, meta: None,
-                        });
-                    }
+            }
+        } else if let Some(mcp_config) = &plugin.manifest.mcp {
+            // Legacy HTTP protocol
+            for tool_def in &mcp_config.tools {
+                if plugin_mcp.is_some_and(|s| s.enabled_tools.contains(&tool_def.name)) {
+                    tools.push(Tool {
+                        name: Cow::Owned(format!("{}.{}", plugin_id, tool_def.name)),
+                        title: None, description: Some(Cow::Owned(tool_def.description.clone())),
+                        input_schema: Arc::new(match &tool_def.input_schema { serde_json::Value::Object(map) => map.clone(), _ => serde_json::Map::new() }),
+                        output_schema: None, annotations: None, icons: None, execution: None, meta: None,
+                    });
                 }
             }
         }
-        tools
+    }
+    tools
+}
+
+/// Slice `items` into a page starting at `cursor` (a decimal offset).
+/// Returns `None` for a malformed or out-of-range cursor.
+fn paginate<T>(items: Vec<T>, cursor: Option<&str>, page_size: usize) -> Option<(Vec<T>, Option<String>)> {
+    let offset = match cursor {
+        Some(c) => c.parse::<usize>().ok().filter(|&o| o <= items.len())?,
+        None => 0,
+    };
+    let end = (offset + page_size).min(items.len());
+    let next = (end < items.len()).then(|| end.to_string());
+    Some((items.into_iter().skip(offset).take(end - offset).collect(), next))
+}
+
+impl McpRegistry {
+    pub fn new(state: AppState, approval_bridge: Arc<ApprovalBridge>, audit: AuditWriter, event_bus: SharedEventBus, budgets: McpBudgets) -> Self {
+        Self { state, approval_bridge, audit, event_bus, budgets }
+    }
+
+    /// Aggregates all available tools from all providers. See [`collect_tools`].
+    pub async fn list_tools(&self) -> Vec<Tool> {
+        collect_tools(&self.state).await
+    }
+
+    /// One page of the tool list for `tools/list`.
+    ///
+    /// The cursor is the opaque offset handed out as `next_cursor` on the
+    /// previous page; `None` starts from the beginning.
+    /// Tools outside a scoped API key's scope are left out.
+    pub async fn list_tools_page(&self, cursor: Option<&str>, client: Option<&McpClientIdentity>) -> Result<(Vec<Tool>, Option<String>), McpError> {
+        let mut tools = self.list_tools().await;
+        tools.retain(|t| client_allows(client, &t.name));
+        paginate(tools, cursor, TOOLS_PAGE_SIZE)
+            .ok_or_else(|| McpError::invalid_params(format!("Invalid cursor: {}", cursor.unwrap_or_default()), None))
     }
 
     /// List resources across all plugins.
@@ -168,7 +342,10 @@ impl McpRegistry {
     /// interface — AI clients can read plugin inventories, filesystem contents, and
     /// system configuration. In a security context, all access must be logged for
     /// compliance and incident investigation.
-    pub async fn call_tool(&self, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<CallToolResult, McpError> {
+    ///
+    /// **Budgets**: calls from an identified client are checked against its
+    /// per-minute, concurrency, and daily mutating-call limits before dispatch.
+    pub async fn call_tool(&self, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>, client: Option<&McpClientIdentity>) -> Result<CallToolResult, McpError> {
         // Extract the primary subject from arguments before dispatch (for the audit trail).
         let subject = arguments.as_ref().and_then(|args| {
             args.get("plugin_id")
@@ -184,30 +361,102 @@ impl McpRegistry {
                 .map(String::from)
         });
 
-        let result = self.dispatch_tool(name, arguments).await;
+        let severity = tool_severity(name);
+        let source_id = client.map(|c| c.name.clone());
 
-        // Security audit: record every MCP tool invocation.
-        // Severity is derived from the tool's nature — execute_command and
-        // destructive operations are Critical, mutating tools are Warn,
-        // read-only tools are Info.
-        use crate::audit::{AuditEntry, AuditActor, AuditSeverity, AuditResult as AuditRes};
-        let severity = match name {
-            n if n.contains("execute_command") => AuditSeverity::Critical,
-            n if n.contains("plugin_remove") || n.contains("extension_remove") => AuditSeverity::Critical,
-            n if n.contains("plugin_install") || n.contains("plugin_start")
-                || n.contains("plugin_stop") || n.contains("extension_enable")
-                || n.contains("extension_disable") || n.contains("extension_install") => AuditSeverity::Warn,
-            // File writes and edits from an external client warrant Warn
-            n if n.contains("write_file") || n.contains("edit_file") => AuditSeverity::Warn,
-            _ => AuditSeverity::Info,
+        if !client_allows(client, name) {
+            log::warn!("MCP call rejected: tool={} is outside the API key's scope", name);
+            self.audit.record(AuditEntry {
+                actor: AuditActor::McpClient,
+                source_id,
+                severity,
+                action: format!("mcp.{}", name),
+                subject,
+                result: AuditRes::Failure,
+                details: Some(serde_json::json!({ "reason": "out_of_scope" })),
+            });
+            return Ok(CallToolResult::error(vec![Content::text(format!("[Nexus] Call to '{}' rejected: this API key is not allowed to use it.", name))]));
+        }
+
+        let read_only_blocked = {
+            let mgr = self.state.read().await;
+            mgr.settings.read_only_mode && !allowed_in_read_only(&mgr, name)
+        };
+        if read_only_blocked {
+            log::warn!("MCP call rejected: tool={} reason=read_only_mode", name);
+            self.audit.record(AuditEntry {
+                actor: AuditActor::McpClient,
+                source_id,
+                severity,
+                action: format!("mcp.{}", name),
+                subject,
+                result: AuditRes::Failure,
+                details: Some(serde_json::json!({ "reason": "read_only_mode" })),
+            });
+            return Ok(CallToolResult::error(vec![Content::text(format!("[Nexus] Call to '{}' rejected: Nexus is in read-only mode, so tools that change host state are disabled.", name))]));
+        }
+
+        let _permit = match client {
+            Some(c) => {
+                let limits = { self.state.read().await.mcp_settings.limits_for(&c.client_id).clone() };
+                match self.budgets.acquire(&c.client_id, &limits, is_mutating(name)) {
+                    Ok(permit) => Some(permit),
+                    Err(exceeded) => {
+                        log::warn!("MCP call rejected: client={} tool={} reason={}", c.client_id, name, exceeded);
+                        self.audit.record(AuditEntry {
+                            actor: AuditActor::McpClient,
+                            source_id,
+                            severity,
+                            action: format!("mcp.{}", name),
+                            subject,
+                            result: AuditRes::Failure,
+                            details: Some(serde_json::json!({ "reason": exceeded.to_string() })),
+                        });
+                        return Ok(CallToolResult::error(vec![Content::text(format!("[Nexus] Call to '{}' rejected: {}. Slow down and retry later.", name, exceeded))]));
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Dropping the dispatch future on timeout cancels the underlying
+        // operation (HTTP request, MCP client call, or spawned command).
+        // Lifecycle tools are detached instead and left to finish.
+        let timeout = { tool_timeout(&self.state.read().await.mcp_settings, name) };
+        let started = std::time::Instant::now();
+        let result = if runs_to_completion(name) {
+            match tokio::time::timeout(timeout, self.spawn_builtin(name, arguments, client)).await {
+                Ok(Ok(r)) => r,
+                Ok(Err(e)) => Err(McpError::internal_error(format!("Built-in tool '{}' failed: {}", name, e), None)),
+                Err(_) => {
+                    log::warn!("MCP tool '{}' still running after {}s; leaving it to finish", name, timeout.as_secs());
+                    Ok(timeout_result(name, timeout, false))
+                }
+            }
+        } else {
+            match tokio::time::timeout(timeout, self.dispatch_tool(name, arguments, client)).await {
+                Ok(r) => r,
+                Err(_) => {
+                    log::warn!("MCP tool '{}' timed out after {}s", name, timeout.as_secs());
+                    Ok(timeout_result(name, timeout, true))
+                }
+            }
         };
+        let elapsed = started.elapsed();
+
+        // Usage analytics: a call counts as successful only if it returned a
+        // non-error result (tool-level errors count as failures too).
+        let succeeded = matches!(&result, Ok(r) if r.is_error != Some(true));
+        self.state.read().await.mcp_tool_stats.record(name, succeeded, elapsed);
+
+        // Security audit: record every MCP tool invocation.
         let audit_result = match &result {
             Ok(_) => AuditRes::Success,
             Err(_) => AuditRes::Failure,
         };
         self.audit.record(AuditEntry {
             actor: AuditActor::McpClient,
-            source_id: None, // TODO: thread MCP session/client identity here
+            source_id,
             severity,
             action: format!("mcp.{}", name),
             subject,
@@ -219,10 +468,10 @@ impl McpRegistry {
     }
 
     /// Internal dispatch — routes to builtin, extension, or plugin handler.
-    async fn dispatch_tool(&self, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<CallToolResult, McpError> {
+    async fn dispatch_tool(&self, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>, client: Option<&McpClientIdentity>) -> Result<CallToolResult, McpError> {
         // 1. Check for built-in namespace
         if let Some(local_name) = name.strip_prefix("nexus.") {
-            return self.call_builtin(local_name, arguments).await;
+            return self.call_builtin(local_name, arguments, client).await;
         }
 
         // 2. Resolve to a specific plugin or extension using longest-prefix matching.
@@ -234,27 +483,37 @@ impl McpRegistry {
             mgr.extensions.get(&plugin_id).is_some()
         };
         if is_ext {
-            return self.call_extension(&plugin_id, &local_name, arguments).await;
+            return self.call_extension(&plugin_id, &local_name, arguments, client).await;
         }
 
         // 4. Plugin dispatch
-        self.call_plugin(&plugin_id, &local_name, arguments).await
+        self.call_plugin(&plugin_id, &local_name, arguments, client).await
     }
 
-    async fn call_builtin(&self, local_name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<CallToolResult, McpError> {
+    async fn call_builtin(&self, local_name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>, client: Option<&McpClientIdentity>) -> Result<CallToolResult, McpError> {
         let args_val = serde_json::Value::Object(arguments.unwrap_or_default());
-        match builtin::handle_call(local_name, &args_val, &self.state, &self.approval_bridge, &self.event_bus).await {
-            Ok(resp) => {
-                let content = resp.content.into_iter().map(|c| Content::text(c.text)).collect();
-                if resp.is_error { Ok(CallToolResult::error(content)) } else { Ok(CallToolResult::success(content)) }
-            }
-            Err(_) => Err(McpError::internal_error(format!("Built-in tool '{}' failed", local_name), None)),
-        }
+        let result = builtin::handle_call(local_name, &args_val, &self.state, &self.approval_bridge, &self.event_bus, client).await;
+        builtin_result(local_name, result)
     }
 
-    async fn call_extension(&self, ext_id: &str, operation: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<CallToolResult, McpError> {
+    /// Run a built-in tool on its own task, which carries on if the caller
+    /// stops waiting.
+    fn spawn_builtin(&self, name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>, client: Option<&McpClientIdentity>) -> tokio::task::JoinHandle<Result<CallToolResult, McpError>> {
+        let local_name = name.strip_prefix("nexus.").unwrap_or(name).to_string();
+        let state = self.state.clone();
+        let bridge = self.approval_bridge.clone();
+        let event_bus = self.event_bus.clone();
+        let client = client.cloned();
+        tokio::spawn(async move {
+            let args_val = serde_json::Value::Object(arguments.unwrap_or_default());
+            let result = builtin::handle_call(&local_name, &args_val, &state, &bridge, &event_bus, client.as_ref()).await;
+            builtin_result(&local_name, result)
+        })
+    }
+
+    async fn call_extension(&self, ext_id: &str, operation: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>, client: Option<&McpClientIdentity>) -> Result<CallToolResult, McpError> {
         let args_val = serde_json::Value::Object(arguments.unwrap_or_default());
-        match builtin::handle_extension_call(ext_id, operation, &args_val, &self.state, &self.approval_bridge).await {
+        match builtin::handle_extension_call(ext_id, operation, &args_val, &self.state, &self.approval_bridge, client).await {
             Ok(resp) => {
                 let content = resp.content.into_iter().map(|c| Content::text(c.text)).collect();
                 if resp.is_error { Ok(CallToolResult::error(content)) } else { Ok(CallToolResult::success(content)) }
@@ -267,9 +526,9 @@ impl McpRegistry {
     ///
     /// This function implements the Nexus **Runtime Approval Flow**:
     /// 1. Checks if the tool requires approval (per manifest).
-    /// 2. Checks if the tool has been permanently approved in the whitelist.
+    /// 2. Checks if the tool has been permanently approved for the calling client.
     /// 3. If not, pauses the execution and prompts the user via the Host UI.
-    async fn call_plugin(&self, plugin_id: &str, local_name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>) -> Result<CallToolResult, McpError> {
+    async fn call_plugin(&self, plugin_id: &str, local_name: &str, arguments: Option<serde_json::Map<String, serde_json::Value>>, client: Option<&McpClientIdentity>) -> Result<CallToolResult, McpError> {
         let mgr = self.state.read().await;
         let plugin = mgr.storage.get(plugin_id).ok_or_else(|| McpError::invalid_request(format!("Plugin '{}' not found", plugin_id), None))?;
         if plugin.status != PluginStatus::Running { return Err(McpError::invalid_request(format!("Plugin '{}' is not running", plugin_id), None)); }
@@ -299,7 +558,7 @@ impl McpRegistry {
 
         let plugin_name = plugin.manifest.name.clone();
         let port = plugin.assigned_port;
-        let already_approved = requires_approval && plugin_mcp.is_some_and(|s| s.approved_tools.contains(&local_name.to_string()));
+        let already_approved = requires_approval && mgr.mcp_settings.is_tool_approved(client.map(|c| c.client_id.as_str()), plugin_id, local_name);
         drop(mgr);
 
         // Runtime approval trigger
@@ -307,16 +566,20 @@ impl McpRegistry {
             let mut context = HashMap::new();
             context.insert("tool_name".to_string(), local_name.to_string());
             context.insert("plugin_name".to_string(), plugin_name.clone());
+            if let Some(c) = client { context.insert("client_name".to_string(), c.name.clone()); }
             if let Some(ref args) = arguments {
                 for (k, v) in args { context.insert(format!("arg.{}", k), match v { serde_json::Value::String(s) => s.clone(), other => other.to_string() }); }
             }
             let approval_req = ApprovalRequest { id: uuid::Uuid::new_v4().to_string(), plugin_id: plugin_id.to_string(), plugin_name: plugin_name.clone(), category: "mcp_tool".to_string(), permission: format!("mcp:{}:{}", plugin_id, local_name), context };
             match self.approval_bridge.request_approval(approval_req).await {
                 ApprovalDecision::Approve => {
-                    let mut mgr = self.state.write().await;
-                    let s = mgr.mcp_settings.plugins.entry(plugin_id.to_string()).or_insert_with(McpPluginSettings::default);
-                    if !s.approved_tools.contains(&local_name.to_string()) { s.approved_tools.push(local_name.to_string()); }
-                    let _ = mgr.mcp_settings.save();
+                    // Permanent approvals are scoped to the calling client; an
+                    // anonymous call can only be approved once.
+                    if let Some(c) = client {
+                        let mut mgr = self.state.write().await;
+                        mgr.mcp_settings.approve_tool(&c.client_id, &c.name, plugin_id, local_name);
+                        let _ = mgr.mcp_settings.save();
+                    }
                 }
                 ApprovalDecision::ApproveOnce => {}
                 ApprovalDecision::Deny => return Ok(CallToolResult::error(vec![Content::text(format!("[Nexus] Tool '{}.{}' was denied by the user.", plugin_id, local_name))])),
@@ -371,3 +634,107 @@ impl McpRegistry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginate_walks_all_pages() {
+        let items: Vec<u32> = (0..5).collect();
+        let (page, next) = paginate(items.clone(), None, 2).unwrap();
+        assert_eq!(page, vec![0, 1]);
+        assert_eq!(next.as_deref(), Some("2"));
+        let (page, next) = paginate(items.clone(), next.as_deref(), 2).unwrap();
+        assert_eq!(page, vec![2, 3]);
+        let (page, next) = paginate(items, next.as_deref(), 2).unwrap();
+        assert_eq!(page, vec![4]);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn paginate_rejects_bad_cursor() {
+        assert!(paginate(vec![1, 2], Some("abc"), 2).is_none());
+        assert!(paginate(vec![1, 2], Some("3"), 2).is_none());
+    }
+
+    #[test]
+    fn read_only_tools_must_be_declared() {
+        let tool = |annotations| {
+            let tool = Tool::new("forecast", "Forecast", Arc::new(serde_json::Map::new()));
+            match annotations {
+                Some(a) => tool.annotate(a),
+                None => tool,
+            }
+        };
+        assert!(declared_read_only(&tool(Some(ToolAnnotations::new().read_only(true)))));
+        assert!(!declared_read_only(&tool(Some(ToolAnnotations::new().read_only(false)))));
+        assert!(!declared_read_only(&tool(Some(ToolAnnotations::new()))));
+        assert!(!declared_read_only(&tool(None)));
+    }
+
+    #[test]
+    fn read_only_mode_blocks_undeclared_provider_tools() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = crate::permissions::PermissionStore::load(tmp.path()).unwrap();
+        let mgr = crate::plugin_manager::PluginManager::new(
+            tmp.path().to_path_buf(),
+            Arc::new(crate::runtime::mock::MockRuntime::new()),
+            Arc::new(crate::permissions::DefaultPermissionService::new(store)),
+            Arc::new(crate::oauth::OAuthStore::load(tmp.path())),
+        );
+        assert!(allowed_in_read_only(&mgr, "nexus.list_plugins"));
+        assert!(!allowed_in_read_only(&mgr, "nexus.plugin_install"));
+        assert!(!allowed_in_read_only(&mgr, "nexus.write_file"));
+        // Unknown provider tools can't prove they're read-only
+        assert!(!allowed_in_read_only(&mgr, "com.example.weather.forecast"));
+    }
+
+    #[test]
+    fn tool_timeout_defaults_by_category() {
+        let settings = McpSettings::default();
+        let secs = |name| tool_timeout(&settings, name).as_secs();
+        assert_eq!(secs("nexus.list_plugins"), READ_ONLY_TIMEOUT_SECS);
+        assert_eq!(secs("nexus.plugin_install"), MUTATING_TIMEOUT_SECS);
+        assert_eq!(secs("nexus.execute_command"), 660);
+        assert_eq!(secs("com.example.weather.forecast"), PROVIDER_TIMEOUT_SECS);
+    }
+
+    #[test]
+    fn tool_timeout_override_wins_for_its_plugin_only() {
+        let mut settings = McpSettings::default();
+        let mut plugin = crate::plugin_manager::storage::McpPluginSettings::default();
+        plugin.tool_timeouts.insert("forecast".into(), 5);
+        settings.plugins.insert("com.example.weather".into(), plugin);
+
+        assert_eq!(tool_timeout(&settings, "com.example.weather.forecast").as_secs(), 5);
+        assert_eq!(
+            tool_timeout(&settings, "com.example.weather.alerts").as_secs(),
+            PROVIDER_TIMEOUT_SECS
+        );
+        assert_eq!(
+            tool_timeout(&settings, "com.example.weatherx.forecast").as_secs(),
+            PROVIDER_TIMEOUT_SECS
+        );
+    }
+
+    #[test]
+    fn timeout_result_says_whether_the_tool_was_cancelled() {
+        let timeout = std::time::Duration::from_secs(30);
+        let cancelled = timeout_result("nexus.read_file", timeout, true);
+        assert_eq!(cancelled.is_error, Some(true));
+        let body = cancelled.structured_content.unwrap();
+        assert_eq!(body["error"], "timeout");
+        assert_eq!(body["tool"], "nexus.read_file");
+        assert_eq!(body["timeout_secs"], 30);
+        assert_eq!(body["cancelled"], true);
+
+        let detached = timeout_result("nexus.plugin_install", timeout, false);
+        let body = detached.structured_content.unwrap();
+        assert_eq!(body["cancelled"], false);
+        assert!(body["message"].as_str().unwrap().contains("still running"));
+
+        assert!(runs_to_completion("nexus.plugin_remove"));
+        assert!(!runs_to_completion("nexus.read_file"));
+    }
+}
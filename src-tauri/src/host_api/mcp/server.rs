@@ -14,8 +14,11 @@ use rmcp::service::{NotificationContext, RequestContext};
 use rmcp::{ErrorData as McpError, RoleServer, ServerHandler};
 
 use crate::AppState;
+use crate::api_keys::ApiKeyStore;
 use crate::audit::writer::AuditWriter;
 use crate::event_bus::SharedEventBus;
+use super::auth::{McpClientIdentity, McpSessionStore};
+use super::budget::McpBudgets;
 use super::registry::McpRegistry;
 use crate::host_api::approval::ApprovalBridge;
 
@@ -29,8 +32,8 @@ pub struct NexusMcpServer {
 }
 
 impl NexusMcpServer {
-    pub fn new(state: AppState, approval_bridge: Arc<ApprovalBridge>, audit: AuditWriter, event_bus: SharedEventBus) -> Self {
-        let registry = Arc::new(McpRegistry::new(state.clone(), approval_bridge, audit, event_bus));
+    pub fn new(state: AppState, approval_bridge: Arc<ApprovalBridge>, audit: AuditWriter, event_bus: SharedEventBus, budgets: McpBudgets) -> Self {
+        let registry = Arc::new(McpRegistry::new(state.clone(), approval_bridge, audit, event_bus, budgets));
         Self { state, registry }
     }
 }
@@ -76,15 +79,41 @@ impl ServerHandler for NexusMcpServer {
 
     /// List all available tools across all plugins and built-in handlers.
     /// Ref: MCP Spec - "Tools" section -> `tools/list`
-    async fn list_tools(&self, _request: Option<PaginatedRequestParams>, _context: RequestContext<RoleServer>) -> Result<ListToolsResult, McpError> {
-        let tools = self.registry.list_tools().await;
-        Ok(ListToolsResult { tools, next_cursor: None, meta: None })
+    /// Paginated: clients follow `next_cursor` to fetch the remaining pages.
+    async fn list_tools(&self, request: Option<PaginatedRequestParams>, context: RequestContext<RoleServer>) -> Result<ListToolsResult, McpError> {
+        let cursor = request.and_then(|r| r.cursor);
+        let client = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.extensions.get::<McpClientIdentity>());
+        let (tools, next_cursor) = self.registry.list_tools_page(cursor.as_deref(), client).await?;
+        Ok(ListToolsResult { tools, next_cursor, meta: None })
     }
 
     /// Dispatch a tool call to the correct provider.
     /// Ref: MCP Spec - "Tools" section -> `tools/call`
-    async fn call_tool(&self, request: CallToolRequestParams, _context: RequestContext<RoleServer>) -> Result<CallToolResult, McpError> {
-        self.registry.call_tool(&request.name, request.arguments).await
+    async fn call_tool(&self, request: CallToolRequestParams, context: RequestContext<RoleServer>) -> Result<CallToolResult, McpError> {
+        // The gateway auth middleware stashes the client identity in the HTTP
+        // request extensions; rmcp forwards the request parts into the context.
+        let parts = context.extensions.get::<axum::http::request::Parts>();
+        let client = parts
+            .and_then(|parts| parts.extensions.get::<McpClientIdentity>())
+            .cloned();
+        // The session store is layered onto the same requests; count the call
+        // against the session for the session list.
+        if let Some(parts) = parts {
+            if let (Some(store), Some(session_id)) = (parts.extensions.get::<McpSessionStore>(), McpSessionStore::session_id_of(parts)) {
+                store.record_call(&session_id);
+            }
+            // Per-key call counts for API key usage reporting.
+            if let (Some(keys), Some(key_id)) = (
+                parts.extensions.get::<ApiKeyStore>(),
+                client.as_ref().and_then(|c| c.client_id.strip_prefix("api_key:")),
+            ) {
+                keys.record_call(key_id);
+            }
+        }
+        self.registry.call_tool(&request.name, request.arguments, client.as_ref()).await
     }
 
     /// List available resources (files, logs, data streams).
@@ -0,0 +1,359 @@
+//! Legacy HTTP+SSE transport (MCP spec 2024-11-05) for the MCP gateway.
+//!
+//! Some clients predate Streamable HTTP and can only speak the older
+//! two-endpoint transport:
+//!
+//! 1. `GET /mcp/sse` opens an event stream. The first event is `endpoint`,
+//!    whose data is the URL the client must POST messages to.
+//! 2. `POST /mcp/message?sessionId=…` carries one JSON-RPC message. The
+//!    response is `202 Accepted`; the JSON-RPC reply arrives as a `message`
+//!    event on the stream.
+//!
+//! Each stream is served by its own [`NexusMcpServer`], exactly like a
+//! Streamable HTTP session, so tools, approvals, budgets, and auditing behave
+//! identically. Both endpoints sit behind [`super::gateway_auth_middleware`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use axum::body::{to_bytes, Body};
+use axum::extract::Query;
+use axum::http::{Request, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::Extension;
+use futures_util::Stream;
+use rmcp::model::{ClientJsonRpcMessage, GetExtensions};
+use rmcp::service::{RxJsonRpcMessage, TxJsonRpcMessage};
+use rmcp::transport::Transport;
+use rmcp::{RoleServer, ServerHandler, ServiceExt};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+use super::auth::{McpClientIdentity, McpSessionStore};
+
+/// Path clients POST JSON-RPC messages to (advertised in the `endpoint` event).
+const MESSAGE_PATH: &str = "/mcp/message";
+
+/// Max size of a single POSTed JSON-RPC message.
+const MAX_MESSAGE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Buffered messages per direction before back-pressure kicks in.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Starts a fresh server on a session's transport.
+type ServeSession = Arc<dyn Fn(SseTransport, String) + Send + Sync>;
+
+struct SseSession {
+    incoming: mpsc::Sender<ClientJsonRpcMessage>,
+    /// Client that opened the stream; POSTs from any other client are rejected.
+    client_id: Option<String>,
+}
+
+/// Open legacy SSE sessions, keyed by session ID. Cheap to clone.
+#[derive(Clone)]
+pub struct McpSseSessions {
+    sessions: Arc<Mutex<HashMap<String, SseSession>>>,
+    serve: ServeSession,
+}
+
+impl McpSseSessions {
+    /// `factory` builds the server for each session, normally a
+    /// [`NexusMcpServer`](super::NexusMcpServer).
+    pub fn new<S: ServerHandler>(factory: impl Fn() -> S + Send + Sync + 'static) -> Self {
+        let serve = move |transport: SseTransport, session_id: String| {
+            let server = factory();
+            tokio::spawn(async move {
+                match server.serve(transport).await {
+                    Ok(running) => {
+                        let _ = running.waiting().await;
+                    }
+                    Err(e) => log::warn!("MCP SSE session {} failed to initialize: {}", session_id, e),
+                }
+            });
+        };
+        Self {
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            serve: Arc::new(serve),
+        }
+    }
+
+    fn remove(&self, session_id: &str) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(session_id);
+    }
+}
+
+/// Removes the session when the SSE stream is dropped (client disconnected).
+/// Dropping the session's sender ends the transport, which stops the server.
+struct SessionGuard {
+    sessions: McpSseSessions,
+    store: McpSessionStore,
+    session_id: String,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        log::info!("MCP SSE session closed: {}", self.session_id);
+        self.sessions.remove(&self.session_id);
+        self.store.remove(&self.session_id);
+    }
+}
+
+/// rmcp transport bridging the SSE stream (outgoing) and POST handler (incoming).
+struct SseTransport {
+    incoming: mpsc::Receiver<ClientJsonRpcMessage>,
+    outgoing: mpsc::Sender<TxJsonRpcMessage<RoleServer>>,
+}
+
+impl Transport<RoleServer> for SseTransport {
+    type Error = std::io::Error;
+
+    fn name() -> Cow<'static, str> {
+        "nexus-sse".into()
+    }
+
+    fn send(
+        &mut self,
+        item: TxJsonRpcMessage<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), Self::Error>> + Send + 'static {
+        let tx = self.outgoing.clone();
+        async move {
+            tx.send(item).await.map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::BrokenPipe, "SSE stream closed")
+            })
+        }
+    }
+
+    fn receive(
+        &mut self,
+    ) -> impl std::future::Future<Output = Option<RxJsonRpcMessage<RoleServer>>> + Send {
+        self.incoming.recv()
+    }
+
+    async fn close(&mut self) -> Result<(), Self::Error> {
+        self.incoming.close();
+        Ok(())
+    }
+}
+
+/// GET /mcp/sse — open a legacy SSE session.
+pub async fn sse_connect(
+    Extension(sessions): Extension<McpSseSessions>,
+    Extension(store): Extension<McpSessionStore>,
+    identity: Option<Extension<McpClientIdentity>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let (in_tx, in_rx) = mpsc::channel(CHANNEL_CAPACITY);
+    let (out_tx, mut out_rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+    let client_id = identity.as_ref().map(|Extension(i)| i.client_id.clone());
+    // Registered sessions show up in the session list and can be revoked.
+    let revoked = identity
+        .and_then(|Extension(i)| store.register_sse(&session_id, i))
+        .unwrap_or_default();
+    log::info!(
+        "MCP SSE session opened: {} (client={})",
+        session_id,
+        client_id.as_deref().unwrap_or("-")
+    );
+    sessions
+        .sessions
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(
+            session_id.clone(),
+            SseSession {
+                incoming: in_tx,
+                client_id,
+            },
+        );
+
+    let transport = SseTransport {
+        incoming: in_rx,
+        outgoing: out_tx,
+    };
+    (sessions.serve)(transport, session_id.clone());
+
+    let guard = SessionGuard {
+        sessions,
+        store,
+        session_id: session_id.clone(),
+    };
+    let stream = async_stream::stream! {
+        let _guard = guard;
+        yield Ok(Event::default()
+            .event("endpoint")
+            .data(format!("{}?sessionId={}", MESSAGE_PATH, session_id)));
+        loop {
+            let message = tokio::select! {
+                m = out_rx.recv() => m,
+                _ = revoked.cancelled() => None,
+            };
+            let Some(message) = message else { break };
+            match serde_json::to_string(&message) {
+                Ok(data) => yield Ok(Event::default().event("message").data(data)),
+                Err(e) => log::warn!("Failed to serialize MCP SSE message: {}", e),
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[derive(Deserialize)]
+pub struct MessageQuery {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+}
+
+/// POST /mcp/message?sessionId=… — deliver one JSON-RPC message to a session.
+pub async fn sse_message(
+    Extension(sessions): Extension<McpSseSessions>,
+    Query(query): Query<MessageQuery>,
+    req: Request<Body>,
+) -> StatusCode {
+    let (parts, body) = req.into_parts();
+
+    let incoming = {
+        let map = sessions.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(session) = map.get(&query.session_id) else {
+            return StatusCode::NOT_FOUND;
+        };
+        let caller = parts
+            .extensions
+            .get::<McpClientIdentity>()
+            .map(|i| i.client_id.as_str());
+        if caller != session.client_id.as_deref() {
+            log::warn!(
+                "MCP SSE message for session {} from a different client — rejected",
+                query.session_id
+            );
+            return StatusCode::FORBIDDEN;
+        }
+        session.incoming.clone()
+    };
+
+    let Ok(bytes) = to_bytes(body, MAX_MESSAGE_BYTES).await else {
+        return StatusCode::PAYLOAD_TOO_LARGE;
+    };
+    let mut message: ClientJsonRpcMessage = match serde_json::from_slice(&bytes) {
+        Ok(m) => m,
+        Err(_) => return StatusCode::BAD_REQUEST,
+    };
+
+    // Forward the request parts (and the client identity in their
+    // extensions) the same way the Streamable HTTP transport does.
+    match &mut message {
+        ClientJsonRpcMessage::Request(r) => {
+            r.request.extensions_mut().insert(parts);
+        }
+        ClientJsonRpcMessage::Notification(n) => {
+            n.notification.extensions_mut().insert(parts);
+        }
+        _ => {}
+    }
+
+    match incoming.send(message).await {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(_) => {
+            sessions.remove(&query.session_id);
+            StatusCode::NOT_FOUND
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing, Router};
+    use futures_util::StreamExt;
+    use tower::ServiceExt as _;
+
+    /// Answers `initialize` and nothing else.
+    struct StubServer;
+
+    impl ServerHandler for StubServer {}
+
+    fn app() -> Router {
+        Router::new()
+            .route("/mcp/sse", routing::get(sse_connect))
+            .route(MESSAGE_PATH, routing::post(sse_message))
+            .layer(Extension(McpSseSessions::new(|| StubServer)))
+            .layer(Extension(McpSessionStore::new()))
+    }
+
+    /// The next SSE event on `body` as (event, data), skipping keep-alives.
+    async fn next_event(body: &mut axum::body::BodyDataStream) -> (String, String) {
+        loop {
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+                .await
+                .expect("no SSE event within 5s")
+                .expect("SSE stream ended")
+                .unwrap();
+            let text = String::from_utf8(chunk.to_vec()).unwrap();
+            let field = |name: &str| {
+                text.lines()
+                    .find_map(|l| l.strip_prefix(name))
+                    .map(|v| v.trim_start().to_string())
+            };
+            if let (Some(event), Some(data)) = (field("event:"), field("data:")) {
+                return (event, data);
+            }
+        }
+    }
+
+    fn post(uri: &str, body: &str) -> Request<Body> {
+        Request::post(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn stream_advertises_endpoint_and_replies_to_posts() {
+        let app = app();
+        let resp = app
+            .clone()
+            .oneshot(Request::get("/mcp/sse").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let mut body = resp.into_body().into_data_stream();
+
+        let (event, endpoint) = next_event(&mut body).await;
+        assert_eq!(event, "endpoint");
+        let session_id = endpoint
+            .strip_prefix(&format!("{}?sessionId=", MESSAGE_PATH))
+            .expect("endpoint carries the session ID");
+        assert!(uuid::Uuid::parse_str(session_id).is_ok());
+
+        let initialize = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{
+            "protocolVersion":"2024-11-05","capabilities":{},
+            "clientInfo":{"name":"test","version":"1.0"}}}"#;
+        let resp = app.clone().oneshot(post(&endpoint, initialize)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let (event, data) = next_event(&mut body).await;
+        assert_eq!(event, "message");
+        let reply: serde_json::Value = serde_json::from_str(&data).unwrap();
+        assert_eq!(reply["id"], 1);
+        assert!(reply["result"]["serverInfo"].is_object());
+
+        let resp = app.oneshot(post(&endpoint, "not json")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn unknown_session_is_not_found() {
+        let uri = format!("{}?sessionId=does-not-exist", MESSAGE_PATH);
+        let resp = app()
+            .oneshot(post(&uri, r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,215 @@
+//! Per-tool usage analytics for the MCP gateway.
+//!
+//! Every `tools/call` routed through [`super::McpRegistry`] is recorded here:
+//! call count, success/failure split, latency, and last-used timestamp. The
+//! aggregates are persisted to `mcp_tool_stats.json` so users can spot tools
+//! that are never called (candidates for disabling) or ones that keep failing.
+//!
+//! Calls only update the counters in memory; [`spawn_flusher`] writes them
+//! out every [`FLUSH_INTERVAL`], and the app flushes once more on exit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often recorded usage is written to disk.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Raw counters for a single namespaced tool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub calls: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub total_latency_ms: u64,
+    pub max_latency_ms: u64,
+    #[serde(default)]
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub last_failure_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Read-only view of a tool's usage, with derived rates.
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolStatsEntry {
+    pub name: String,
+    pub calls: u64,
+    pub successes: u64,
+    pub failures: u64,
+    /// Fraction of calls that succeeded (0.0–1.0). `None` if never called.
+    pub success_rate: Option<f64>,
+    pub avg_latency_ms: Option<u64>,
+    pub max_latency_ms: u64,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_failure_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StatsFile {
+    tools: HashMap<String, ToolUsage>,
+}
+
+/// Shared, internally synchronized tool usage tracker.
+///
+/// Cheap to clone. Recording only needs `&self`, so callers holding a read
+/// lock on the `PluginManager` can record without upgrading to a write lock.
+#[derive(Debug, Clone, Default)]
+pub struct McpToolStats {
+    tools: Arc<Mutex<HashMap<String, ToolUsage>>>,
+    /// Set when the counters changed since the last flush.
+    dirty: Arc<AtomicBool>,
+    /// Held while writing, so overlapping flushes land in order.
+    writing: Arc<Mutex<()>>,
+    path: Option<PathBuf>,
+}
+
+impl McpToolStats {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join("mcp_tool_stats.json");
+        let tools = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<StatsFile>(&data).ok())
+            .map(|f| f.tools)
+            .unwrap_or_default();
+        Self {
+            tools: Arc::new(Mutex::new(tools)),
+            path: Some(path),
+            ..Self::default()
+        }
+    }
+
+    /// Record the outcome of a single tool call, in memory until the next flush.
+    pub fn record(&self, name: &str, success: bool, latency: Duration) {
+        let latency_ms = latency.as_millis() as u64;
+        let now = chrono::Utc::now();
+        let mut tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let usage = tools.entry(name.to_string()).or_default();
+        usage.calls += 1;
+        if success {
+            usage.successes += 1;
+        } else {
+            usage.failures += 1;
+            usage.last_failure_at = Some(now);
+        }
+        usage.total_latency_ms = usage.total_latency_ms.saturating_add(latency_ms);
+        usage.max_latency_ms = usage.max_latency_ms.max(latency_ms);
+        usage.last_used_at = Some(now);
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// All tracked tools, sorted by name.
+    pub fn snapshot(&self) -> Vec<ToolStatsEntry> {
+        let tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries: Vec<ToolStatsEntry> = tools
+            .iter()
+            .map(|(name, u)| ToolStatsEntry {
+                name: name.clone(),
+                calls: u.calls,
+                successes: u.successes,
+                failures: u.failures,
+                success_rate: (u.calls > 0).then(|| u.successes as f64 / u.calls as f64),
+                avg_latency_ms: (u.calls > 0).then(|| u.total_latency_ms / u.calls),
+                max_latency_ms: u.max_latency_ms,
+                last_used_at: u.last_used_at,
+                last_failure_at: u.last_failure_at,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    /// Forget all recorded usage (e.g. after the user has reviewed and pruned tools).
+    pub fn reset(&self) {
+        self.tools.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.dirty.store(true, Ordering::Release);
+        self.flush();
+    }
+
+    /// Write the counters to disk if they changed since the last flush.
+    /// Blocks on file I/O; async callers should go through `spawn_blocking`.
+    pub fn flush(&self) {
+        let Some(ref path) = self.path else { return };
+        let _writing = self.writing.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        let tools = self.tools.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        let file = StatsFile { tools };
+        match serde_json::to_string_pretty(&file) {
+            Ok(data) => {
+                if let Err(e) = crate::util::atomic_write(path, data.as_bytes()) {
+                    log::warn!("Failed to persist MCP tool stats: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize MCP tool stats: {}", e),
+        }
+    }
+}
+
+/// Spawn a background task that flushes `stats` every [`FLUSH_INTERVAL`].
+pub fn spawn_flusher(stats: McpToolStats) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let stats = stats.clone();
+            if let Err(e) = tokio::task::spawn_blocking(move || stats.flush()).await {
+                log::warn!("MCP tool stats flush panicked: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_aggregates_counts_and_latency() {
+        let stats = McpToolStats::default();
+        stats.record("nexus.list_plugins", true, Duration::from_millis(10));
+        stats.record("nexus.list_plugins", true, Duration::from_millis(30));
+        stats.record("nexus.list_plugins", false, Duration::from_millis(50));
+
+        let snap = stats.snapshot();
+        assert_eq!(snap.len(), 1);
+        let e = &snap[0];
+        assert_eq!(e.calls, 3);
+        assert_eq!(e.successes, 2);
+        assert_eq!(e.failures, 1);
+        assert_eq!(e.avg_latency_ms, Some(30));
+        assert_eq!(e.max_latency_ms, 50);
+        assert!((e.success_rate.unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert!(e.last_used_at.is_some());
+        assert!(e.last_failure_at.is_some());
+    }
+
+    #[test]
+    fn stats_persist_across_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = McpToolStats::load(dir.path());
+        stats.record("com.example.greet", true, Duration::from_millis(5));
+        assert!(McpToolStats::load(dir.path()).snapshot().is_empty());
+        stats.flush();
+
+        let reloaded = McpToolStats::load(dir.path());
+        let snap = reloaded.snapshot();
+        assert_eq!(snap.len(), 1);
+        assert_eq!(snap[0].name, "com.example.greet");
+        assert_eq!(snap[0].calls, 1);
+    }
+
+    #[test]
+    fn reset_clears_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = McpToolStats::load(dir.path());
+        stats.record("a.b", true, Duration::from_millis(1));
+        stats.flush();
+        stats.reset();
+        assert!(stats.snapshot().is_empty());
+        assert!(McpToolStats::load(dir.path()).snapshot().is_empty());
+    }
+}
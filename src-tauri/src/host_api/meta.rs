@@ -2,13 +2,16 @@ use std::sync::Arc;
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use utoipa::ToSchema;
 
+use crate::event_bus::metrics::BusMetricsSnapshot;
+use crate::event_bus::Dispatch;
 use crate::extensions::Capability;
 use crate::permissions::{Permission, PermissionState};
 use crate::AppState;
@@ -41,6 +44,9 @@ pub struct MetaSelf {
 #[derive(Serialize, ToSchema)]
 pub struct MetaStats {
     pub container_id: String,
+    /// Event bus throughput. Only the calling plugin's own subscriptions
+    /// are listed.
+    pub event_bus: BusMetricsSnapshot,
     #[serde(flatten)]
     pub stats: Value,
 }
@@ -161,7 +167,7 @@ pub async fn meta_self(
         .into_iter()
         .map(|g| MetaPermission {
             permission: g.permission.as_str().to_string(),
-            state: match g.state {
+            state: match g.effective_state() {
                 PermissionState::Active => "active".to_string(),
                 PermissionState::Revoked => "revoked".to_string(),
                 PermissionState::Deferred => "deferred".to_string(),
@@ -181,7 +187,8 @@ pub async fn meta_self(
 
 /// Plugin container stats.
 ///
-/// Returns CPU, memory, and network statistics for the calling plugin's container.
+/// Returns CPU, memory, and network statistics for the calling plugin's
+/// container, and event bus throughput.
 #[utoipa::path(
     get,
     path = "/api/v1/meta/stats",
@@ -196,6 +203,7 @@ pub async fn meta_self(
 pub async fn meta_stats(
     State(state): State<AppState>,
     Extension(auth): Extension<AuthenticatedPlugin>,
+    Extension(dispatch): Extension<Dispatch>,
 ) -> Result<Json<MetaStats>, (StatusCode, Json<MetaErrorResponse>)> {
     let mgr = state.read().await;
 
@@ -229,12 +237,34 @@ pub async fn meta_stats(
             )
         })?;
 
+    let mut event_bus = dispatch.metrics().await;
+    let own = format!("plugin:{}", auth.plugin_id);
+    event_bus.subscribers.retain(|s| s.subscriber == own);
+
     Ok(Json(MetaStats {
         container_id,
+        event_bus,
         stats,
     }))
 }
 
+/// Event bus metrics in the Prometheus text format, for scraping by a local
+/// Prometheus. Served without authentication, so only when turned on in
+/// settings.
+pub async fn prometheus_metrics(
+    State(state): State<AppState>,
+    Extension(dispatch): Extension<Dispatch>,
+) -> Response {
+    if !state.read().await.settings.prometheus_metrics {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        dispatch.metrics().await.to_prometheus(),
+    )
+        .into_response()
+}
+
 /// List available credential providers.
 ///
 /// Returns all enabled extensions with the `credential_provider` capability
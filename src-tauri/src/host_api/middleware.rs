@@ -8,11 +8,13 @@ use axum::{
     response::Response,
 };
 
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
 use crate::oauth::validation::{validate_bearer, TokenValidation};
 use crate::oauth::OAuthStore;
-use crate::permissions::checker::required_permission_for_endpoint;
+use crate::permissions::checker::{is_mutating_endpoint, prompts_in_handler, required_permission_for_endpoint};
 use crate::permissions::rar;
-use crate::permissions::PermissionState;
+use crate::permissions::{Permission, PermissionState};
 use crate::AppState;
 
 use super::approval::{ApprovalBridge, ApprovalRequest};
@@ -52,6 +54,15 @@ pub async fn auth_middleware(
     let path = req.uri().path().to_string();
     let method = req.method().clone();
 
+    // Read-only mode overrides every grant
+    if is_mutating_endpoint(&path, &method) && state.read().await.settings.read_only_mode {
+        log::warn!(
+            "AUDIT DENIED plugin={} method={} path={} reason=read_only_mode",
+            plugin_id, method, path
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     // Check permission for this endpoint: token fast path → PermissionStore fallback
     if let Some(required_perm) = required_permission_for_endpoint(&path, &method) {
         // Fast path: check authorization_details on the token (zero store lookups)
@@ -59,14 +70,32 @@ pub async fn auth_middleware(
             // Fallback: check PermissionStore (handles stale tokens, Deferred, etc.)
             let mgr = state.read().await;
             let perm_state = mgr.permissions.get_state(&plugin_id, &required_perm);
+            // Always-prompt grants are never on the token, so they always land here
+            let always_prompt = perm_state == Some(PermissionState::Active)
+                && !prompts_in_handler(&required_perm)
+                && mgr.permissions.is_always_prompt(&plugin_id, &required_perm);
+            // Rate-capped grants are never on the token either; count the call
+            let over_cap = perm_state == Some(PermissionState::Active)
+                && !always_prompt
+                && !mgr.permissions.consume_use(&plugin_id, &required_perm);
             drop(mgr);
 
             match perm_state {
-                Some(PermissionState::Active) => {
+                Some(PermissionState::Active) if over_cap => {
+                    let bridge = req
+                        .extensions()
+                        .get::<Arc<ApprovalBridge>>()
+                        .cloned()
+                        .expect("ApprovalBridge must be in request extensions");
+                    let audit = req.extensions().get::<AuditWriter>().cloned();
+                    approve_over_cap(&state, &bridge, audit.as_ref(), &plugin_id, &required_perm).await?;
+                }
+                Some(PermissionState::Active) if !always_prompt => {
                     // Stale token — permission is active in store, proceed
                 }
-                Some(PermissionState::Deferred) => {
-                    // JIT approval for deferred built-in permissions
+                Some(PermissionState::Deferred) | Some(PermissionState::Active) => {
+                    // JIT approval for deferred built-in permissions, and
+                    // per-call confirmation for always-prompt grants
                     let bridge = req
                         .extensions()
                         .get::<Arc<ApprovalBridge>>()
@@ -103,11 +132,15 @@ pub async fn auth_middleware(
                             // Don't persist, just continue this request
                         }
                         super::approval::ApprovalDecision::Deny => {
-                            let mgr = state.read().await;
-                            let _ = mgr.permissions.revoke(&plugin_id, &required_perm);
+                            // Declining one always-prompt call keeps the grant
+                            if !always_prompt {
+                                let mgr = state.read().await;
+                                let _ = mgr.permissions.revoke(&plugin_id, &required_perm);
+                            }
                             log::warn!(
-                                "AUDIT DENIED plugin={} method={} path={} reason=deferred_denied",
-                                plugin_id, method, path
+                                "AUDIT DENIED plugin={} method={} path={} reason={}",
+                                plugin_id, method, path,
+                                if always_prompt { "always_prompt_denied" } else { "deferred_denied" }
                             );
                             return Err(StatusCode::FORBIDDEN);
                         }
@@ -138,6 +171,76 @@ pub async fn auth_middleware(
     Ok(response)
 }
 
+/// A rate-capped grant went over its cap: record it and ask the user again.
+/// "Allow" starts a fresh usage window, "Allow once" lets only this call
+/// through, and "Deny" rejects it with 429 while keeping the grant.
+async fn approve_over_cap(
+    state: &AppState,
+    bridge: &ApprovalBridge,
+    audit: Option<&AuditWriter>,
+    plugin_id: &str,
+    permission: &Permission,
+) -> Result<(), StatusCode> {
+    let (plugin_name, cap) = {
+        let mgr = state.read().await;
+        let name = mgr
+            .storage
+            .get(plugin_id)
+            .map(|p| p.manifest.name.clone())
+            .unwrap_or_else(|| plugin_id.to_string());
+        (name, mgr.permissions.get_rate_cap(plugin_id, permission))
+    };
+
+    if let Some(audit) = audit {
+        audit.record(AuditEntry {
+            actor: AuditActor::Plugin { id: plugin_id.to_string() },
+            source_id: None,
+            severity: AuditSeverity::Warn,
+            action: "permission.rate_cap_exceeded".into(),
+            subject: Some(plugin_id.to_string()),
+            result: AuditResult::Failure,
+            details: Some(serde_json::json!({
+                "permission": permission.as_str(),
+                "rate_cap": cap.map(|c| c.to_string()),
+            })),
+        });
+    }
+
+    let mut context = std::collections::HashMap::new();
+    context.insert("permission".to_string(), permission.as_str().to_string());
+    context.insert("description".to_string(), permission.description().to_string());
+    if let Some(cap) = cap {
+        context.insert("max_calls".to_string(), cap.max_calls.to_string());
+        context.insert("per_secs".to_string(), cap.per_secs.to_string());
+    }
+
+    let request = ApprovalRequest {
+        id: uuid::Uuid::new_v4().to_string(),
+        plugin_id: plugin_id.to_string(),
+        plugin_name,
+        category: "rate_cap".to_string(),
+        permission: permission.as_str().to_string(),
+        context,
+    };
+
+    match bridge.request_approval(request).await {
+        super::approval::ApprovalDecision::Approve => {
+            let mgr = state.read().await;
+            mgr.permissions.reset_usage(plugin_id, permission);
+            Ok(())
+        }
+        super::approval::ApprovalDecision::ApproveOnce => Ok(()),
+        super::approval::ApprovalDecision::Deny => {
+            log::warn!(
+                "AUDIT DENIED plugin={} permission={} reason=rate_cap_exceeded",
+                plugin_id,
+                permission.as_str()
+            );
+            Err(StatusCode::TOO_MANY_REQUESTS)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,6 +418,9 @@ mod tests {
                 approved_scopes: None,
                 state: PermissionState::Active,
                 revoked_at: None,
+                expires_at: None,
+                always_prompt: false,
+                rate_cap: None,
             },
         ]);
         let token = plugin_token(&oauth_store, details);
@@ -444,4 +550,43 @@ mod tests {
         let resp = app.oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
     }
+
+    // =====================================================================
+    // Read-only mode
+    // =====================================================================
+
+    #[tokio::test]
+    async fn read_only_mode_blocks_writes_but_not_reads() {
+        let tmp = tempfile::tempdir().unwrap();
+        let perms: Arc<dyn crate::permissions::service::PermissionService> =
+            Arc::new(DefaultPermissionService::new(PermissionStore::load(tmp.path()).unwrap()));
+        let (state, oauth_store) = test_state(tmp.path(), perms);
+        state.write().await.settings.read_only_mode = true;
+        let token = plugin_token(&oauth_store, vec![]);
+
+        let app = Router::new()
+            .route("/v1/storage/{key}", axum::routing::any(|| async { "ok" }))
+            .route("/v1/events", axum::routing::post(|| async { "ok" }))
+            .route("/v1/network/proxy", axum::routing::post(|| async { "ok" }))
+            .layer(axum_mw::from_fn_with_state(state.clone(), auth_middleware))
+            .layer(Extension(oauth_store))
+            .with_state(state);
+
+        for (method, path, expected) in [
+            ("GET", "/v1/storage/key", StatusCode::OK),
+            ("PUT", "/v1/storage/key", StatusCode::FORBIDDEN),
+            ("DELETE", "/v1/storage/key", StatusCode::FORBIDDEN),
+            ("POST", "/v1/events", StatusCode::FORBIDDEN),
+            ("POST", "/v1/network/proxy", StatusCode::FORBIDDEN),
+        ] {
+            let req = Request::builder()
+                .method(method)
+                .uri(path)
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap();
+            let resp = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), expected, "{} {}", method, path);
+        }
+    }
 }
@@ -1,3 +1,4 @@
+pub mod access_log;
 pub mod approval;
 pub mod containers;
 pub mod events;
@@ -84,6 +85,7 @@ impl Modify for SecurityAddon {
         network::proxy_request,
         settings::get_settings,
         settings::put_settings,
+        settings::get_language,
         extensions::list_extensions,
         extensions::call_extension,
         meta::meta_self,
@@ -92,10 +94,18 @@ impl Modify for SecurityAddon {
         meta::meta_credentials_resolve,
         events::publish_event,
         events::subscribe_events,
+        events::ack_event,
+        events::replay_events,
+        events::list_event_schemas,
+        events::get_event_schema,
+        events::register_event_schema,
+        events::delete_event_schema,
         events::query_event_log,
+        events::event_causal_chain,
     ),
     components(schemas(
         system::SystemInfo,
+        settings::LanguageResponse,
         filesystem::FileContent,
         filesystem::DirEntry,
         filesystem::DirListing,
@@ -126,6 +136,10 @@ impl Modify for SecurityAddon {
         meta::MetaSelf,
         meta::MetaPermission,
         meta::MetaStats,
+        event_bus::metrics::BusMetricsSnapshot,
+        event_bus::metrics::SubscriberQueue,
+        event_bus::metrics::ExecutorLatency,
+        event_bus::metrics::LatencyBucket,
         meta::CredentialProviderList,
         meta::CredentialProvider,
         meta::CredentialScope,
@@ -136,6 +150,13 @@ impl Modify for SecurityAddon {
         events::EventLogEntry,
         events::EventLogResponse,
         events::EventErrorResponse,
+        events::AckRequest,
+        events::AckResponse,
+        events::ReplayRequest,
+        events::ReplayResponse,
+        events::RegisterSchemaRequest,
+        event_bus::schema::EventSchema,
+        event_bus::schema::SchemaMode,
     )),
     modifiers(&SecurityAddon),
     tags(
@@ -152,6 +173,7 @@ impl Modify for SecurityAddon {
 )]
 pub struct ApiDoc;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn start_server(
     state: AppState,
     approvals: Arc<ApprovalBridge>,
@@ -160,6 +182,7 @@ pub async fn start_server(
     api_key_store: ApiKeyStore,
     dispatch: event_bus::Dispatch,
     audit: AuditWriter,
+    mcp_session_store: mcp::McpSessionStore,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let cors = CorsLayer::new()
         .allow_origin(AllowOrigin::predicate(|origin, _| {
@@ -171,6 +194,8 @@ pub async fn start_server(
         .allow_methods(Any)
         .allow_headers(Any);
 
+    let metrics_dispatch = dispatch.clone();
+
     // 100 requests per second per plugin — generous for normal use, blocks abuse
     let limiter = rate_limit::RateLimiter::new(100, std::time::Duration::from_secs(1));
 
@@ -274,12 +299,26 @@ pub async fn start_server(
             "/v1/events/subscribe",
             routing::get(events::subscribe_events),
         )
+        .route("/v1/events/ack", routing::post(events::ack_event))
+        .route("/v1/events/replay", routing::post(events::replay_events))
+        .route("/v1/events/schemas", routing::get(events::list_event_schemas))
+        .route(
+            "/v1/events/schemas/{event_type}",
+            routing::get(events::get_event_schema)
+                .put(events::register_event_schema)
+                .delete(events::delete_event_schema),
+        )
         .route("/v1/events/log", routing::get(events::query_event_log))
+        .route(
+            "/v1/events/log/{event_id}/chain",
+            routing::get(events::event_causal_chain),
+        )
         // Plugin settings (scoped to authenticated plugin)
         .route(
             "/v1/settings",
             routing::get(settings::get_settings).put(settings::put_settings),
         )
+        .route("/v1/settings/language", routing::get(settings::get_language))
         // Plugin key-value storage (scoped to authenticated plugin)
         .route("/v1/storage", routing::get(storage::list_keys))
         .route(
@@ -295,8 +334,15 @@ pub async fn start_server(
             state.clone(),
             middleware::auth_middleware,
         ))
+        // Outside auth so requests it denies are logged too
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            access_log::access_log_middleware,
+        ))
         .layer(Extension(oauth_store.clone()))
         .layer(Extension(approvals.clone()))
+        .layer(Extension(audit.clone()))
+        .layer(Extension(dispatch.clone()))
         .layer(Extension(dispatch.executor))
         .layer(Extension(dispatch.bus.clone()))
         .layer(Extension(dispatch.store))
@@ -318,24 +364,34 @@ pub async fn start_server(
     let audit_for_oauth = audit.clone();
     let audit_for_mcp_auth = audit.clone();
     let mcp_audit_for_factory = audit;
+    // Per-client call budgets are shared across all sessions
+    let mcp_budgets = mcp::McpBudgets::new();
+    let make_mcp_server = move || {
+        mcp::NexusMcpServer::new(
+            mcp_state_for_factory.clone(),
+            mcp_approvals_for_factory.clone(),
+            mcp_audit_for_factory.clone(),
+            mcp_event_bus.clone(),
+            mcp_budgets.clone(),
+        )
+    };
+    // Legacy HTTP+SSE transport for clients that predate Streamable HTTP.
+    // Clients connect via: http://127.0.0.1:9600/mcp/sse
+    let mcp_sse_sessions = mcp::McpSseSessions::new(make_mcp_server.clone());
+    let mcp_http_sessions = Arc::new(rmcp::transport::streamable_http_server::session::local::LocalSessionManager::default());
+    mcp_session_store.attach_http_sessions(mcp_http_sessions.clone());
     let mcp_service = StreamableHttpService::new(
-        move || {
-            Ok(mcp::NexusMcpServer::new(
-                mcp_state_for_factory.clone(),
-                mcp_approvals_for_factory.clone(),
-                mcp_audit_for_factory.clone(),
-                mcp_event_bus.clone(),
-            ))
-        },
-        Arc::new(rmcp::transport::streamable_http_server::session::local::LocalSessionManager::default()),
+        move || Ok(make_mcp_server()),
+        mcp_http_sessions,
         mcp_config,
     );
 
     // Wrap the MCP service as an axum route with gateway auth.
     // McpSessionStore remembers authenticated sessions so subsequent requests
     // (which may not carry the gateway token) are allowed through.
-    let mcp_session_store = mcp::McpSessionStore::new();
     let mcp_native_routes = Router::new()
+        .route("/mcp/sse", routing::get(mcp::sse::sse_connect))
+        .route("/mcp/message", routing::post(mcp::sse::sse_message))
         .nest_service("/mcp", mcp_service)
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
@@ -343,6 +399,7 @@ pub async fn start_server(
         ))
         .layer(Extension(oauth_store.clone()))
         .layer(Extension(mcp_session_store))
+        .layer(Extension(mcp_sse_sessions))
         .layer(Extension(api_key_store))
         .layer(Extension(audit_for_mcp_auth));
 
@@ -367,6 +424,13 @@ pub async fn start_server(
         .route("/oauth/authorize", routing::get(oauth::authorize::authorize))
         .route("/oauth/authorize/poll/{state}", routing::get(oauth::authorize::authorize_poll))
         .route("/oauth/token", routing::post(oauth::token::token_exchange))
+        .route("/oauth/revoke", routing::post(oauth::revocation::revoke_token))
+        .route("/oauth/jwks", routing::get(oauth::metadata::jwks))
+        .route(
+            "/oauth/device_authorization",
+            routing::post(oauth::device::device_authorization),
+        )
+        .route("/oauth/device", routing::get(oauth::device::verification_page))
         .layer(axum_middleware::from_fn(rate_limit::global_rate_limit_middleware))
         .layer(Extension(global_limiter))
         .layer(Extension(oauth_store.clone()))
@@ -384,9 +448,15 @@ pub async fn start_server(
         )
         .layer(Extension(active_theme.clone()));
 
+    // Prometheus scrape endpoint (public, off unless enabled in settings)
+    let metrics_routes = Router::new()
+        .route("/metrics", routing::get(meta::prometheus_metrics))
+        .layer(Extension(metrics_dispatch));
+
     let app = Router::new()
         // Public routes (no auth required) — theme CSS, fonts, and active theme query
         .merge(theme_routes)
+        .merge(metrics_routes)
         // OpenAPI spec
         .route("/api/openapi.json", routing::get(openapi_spec))
         // OAuth 2.1 endpoints (public — discovery, registration, authorization, token)
@@ -2,10 +2,13 @@ use axum::{extract::State, http::StatusCode, Extension, Json};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 use utoipa::ToSchema;
 
+use super::approval::{ApprovalBridge, ApprovalDecision, ApprovalRequest};
 use super::middleware::AuthenticatedPlugin;
-use crate::permissions::Permission;
+use crate::permissions::net_scope::host_matches;
+use crate::permissions::{Permission, PermissionService, PermissionState};
 use crate::AppState;
 
 /// Host API listens on this port — plugins must not proxy to it.
@@ -191,6 +194,112 @@ fn required_network_permission(url: &reqwest::Url) -> Result<Permission, StatusC
     }
 }
 
+/// How a plugin may reach a host.
+#[derive(Debug, PartialEq)]
+enum HostAccess {
+    /// Covered by `network:local` / `network:internet` — any host of that class.
+    Class,
+    /// Covered by an approved host under a `network:{host-pattern}` grant.
+    Host,
+    /// A host-pattern grant covers the host, but it hasn't been approved yet.
+    NeedsApproval(Permission),
+    Denied,
+}
+
+/// Decide whether a plugin may reach `host`. Deny rules win; a class
+/// permission allows any host of its class; otherwise an Active host-pattern
+/// grant must cover the host and list it among its approved scopes (or be
+/// unrestricted).
+fn check_host_access(
+    permissions: &dyn PermissionService,
+    plugin_id: &str,
+    class_perm: &Permission,
+    host: &str,
+) -> HostAccess {
+    let host_perms: Vec<Permission> = permissions
+        .get_grants(plugin_id)
+        .into_iter()
+        .filter(|g| g.effective_state() == PermissionState::Active)
+        .map(|g| g.permission)
+        .filter(|p| p.network_host_pattern().is_some_and(|pattern| host_matches(pattern, host)))
+        .collect();
+
+    if permissions.is_denied(plugin_id, class_perm, Some(host))
+        || host_perms.iter().any(|p| permissions.is_denied(plugin_id, p, Some(host)))
+    {
+        return HostAccess::Denied;
+    }
+    if permissions.has_permission(plugin_id, class_perm) {
+        return HostAccess::Class;
+    }
+
+    // `get_approved_scopes` reports none for always-prompt grants
+    let approved = host_perms.iter().any(|p| match permissions.get_approved_scopes(plugin_id, p) {
+        None => true,
+        Some(hosts) => hosts.iter().any(|h| h.eq_ignore_ascii_case(host)),
+    });
+    match host_perms.into_iter().next() {
+        Some(_) if approved => HostAccess::Host,
+        Some(perm) => HostAccess::NeedsApproval(perm),
+        None => HostAccess::Denied,
+    }
+}
+
+/// Enforce network egress for a plugin, prompting on first use of a host
+/// under a host-pattern grant. Returns whether access is host-scoped, in
+/// which case the caller must not follow the connection to other hosts.
+pub(crate) async fn authorize_host(
+    state: &AppState,
+    bridge: &ApprovalBridge,
+    plugin_id: &str,
+    class_perm: &Permission,
+    url: &str,
+    host: &str,
+) -> Result<bool, StatusCode> {
+    let (access, plugin_name) = {
+        let mgr = state.read().await;
+        let access = check_host_access(&*mgr.permissions, plugin_id, class_perm, host);
+        let name = mgr
+            .storage
+            .get(plugin_id)
+            .map(|p| p.manifest.name.clone())
+            .unwrap_or_else(|| plugin_id.to_string());
+        (access, name)
+    };
+
+    match access {
+        HostAccess::Class => Ok(false),
+        HostAccess::Host => Ok(true),
+        HostAccess::Denied => {
+            log::warn!(
+                "AUDIT DENIED plugin={} host={} reason=network_not_permitted",
+                plugin_id, host
+            );
+            Err(StatusCode::FORBIDDEN)
+        }
+        HostAccess::NeedsApproval(perm) => {
+            let mut context = HashMap::new();
+            context.insert("url".to_string(), url.to_string());
+            context.insert("host".to_string(), host.to_string());
+
+            let request = ApprovalRequest {
+                id: uuid::Uuid::new_v4().to_string(),
+                plugin_id: plugin_id.to_string(),
+                plugin_name,
+                category: "network".to_string(),
+                permission: perm.as_str().to_string(),
+                context,
+            };
+
+            // "Allow" persists the host in runtime_approval_respond
+            match bridge.request_approval(request).await {
+                ApprovalDecision::Approve | ApprovalDecision::ApproveOnce => Ok(true),
+                ApprovalDecision::Deny => Err(StatusCode::FORBIDDEN),
+            }
+        }
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/network/proxy",
@@ -207,6 +316,7 @@ fn required_network_permission(url: &reqwest::Url) -> Result<Permission, StatusC
 pub async fn proxy_request(
     State(state): State<AppState>,
     Extension(auth): Extension<AuthenticatedPlugin>,
+    Extension(bridge): Extension<Arc<ApprovalBridge>>,
     Json(req): Json<ProxyRequest>,
 ) -> Result<Json<ProxyResponse>, StatusCode> {
     // Parse and validate URL
@@ -243,13 +353,9 @@ pub async fn proxy_request(
         Permission::NetworkInternet
     };
 
-    // Check permission
-    {
-        let mgr = state.read().await;
-        if !mgr.permissions.has_permission(&auth.plugin_id, &required_perm) {
-            return Err(StatusCode::FORBIDDEN);
-        }
-    }
+    // Check permission: class permission, or a host-pattern grant (may prompt)
+    let host_scoped =
+        authorize_host(&state, &bridge, &auth.plugin_id, &required_perm, &req.url, &host).await?;
 
     // Pin the hostname to the resolved IP so reqwest connects to exactly
     // the address we validated (no TOCTOU window for DNS rebinding).
@@ -257,8 +363,16 @@ pub async fn proxy_request(
         .timeout(std::time::Duration::from_secs(30))
         .danger_accept_invalid_certs(resolved_is_private)
         .resolve(&host, resolved_addr);
+    // Only internet traffic goes through the configured proxy
+    let client_builder = if resolved_is_private {
+        client_builder
+    } else {
+        crate::proxy::apply(client_builder)
+    };
 
     let initial_is_private = resolved_is_private;
+    // Host-scoped access covers this host only — don't follow redirects elsewhere
+    let pinned_host = host_scoped.then(|| host.clone());
 
     let client = client_builder.redirect(reqwest::redirect::Policy::custom(move |attempt| {
             let url = attempt.url();
@@ -277,6 +391,9 @@ pub async fn proxy_request(
                 if !initial_is_private && is_private_host(host) {
                     return attempt.error("redirect from public to private network blocked");
                 }
+                if pinned_host.as_deref().is_some_and(|pinned| !pinned.eq_ignore_ascii_case(host)) {
+                    return attempt.error("redirect to a host outside the approved scope blocked");
+                }
             }
             if attempt.previous().len() >= 5 {
                 attempt.stop()
@@ -651,6 +768,53 @@ mod tests {
         assert_eq!(result.unwrap(), Permission::NetworkLocal);
     }
 
+    // ── check_host_access ────────────────────────────────────
+
+    fn host_perms() -> (crate::permissions::DefaultPermissionService, tempfile::TempDir) {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = crate::permissions::PermissionStore::load(tmp.path()).unwrap();
+        (crate::permissions::DefaultPermissionService::new(store), tmp)
+    }
+
+    #[test]
+    fn host_access_prompts_until_host_approved() {
+        let (perms, _tmp) = host_perms();
+        let perm = Permission::Network("network:*.github.com".into());
+        perms.grant("plug", perm.clone(), Some(vec![])).unwrap();
+
+        assert_eq!(
+            check_host_access(&perms, "plug", &Permission::NetworkInternet, "api.github.com"),
+            HostAccess::NeedsApproval(perm.clone())
+        );
+        perms.add_approved_scope("plug", &perm, "api.github.com".into()).unwrap();
+        assert_eq!(
+            check_host_access(&perms, "plug", &Permission::NetworkInternet, "api.github.com"),
+            HostAccess::Host
+        );
+        assert_eq!(
+            check_host_access(&perms, "plug", &Permission::NetworkInternet, "example.com"),
+            HostAccess::Denied
+        );
+    }
+
+    #[test]
+    fn host_access_class_permission_and_deny_rule() {
+        let (perms, _tmp) = host_perms();
+        perms.grant("plug", Permission::NetworkInternet, None).unwrap();
+        assert_eq!(
+            check_host_access(&perms, "plug", &Permission::NetworkInternet, "example.com"),
+            HostAccess::Class
+        );
+
+        perms
+            .add_deny("plug", Permission::NetworkInternet, Some("*.tracker.io".into()))
+            .unwrap();
+        assert_eq!(
+            check_host_access(&perms, "plug", &Permission::NetworkInternet, "a.tracker.io"),
+            HostAccess::Denied
+        );
+    }
+
     #[test]
     fn permission_blocks_ipv6_host_api() {
         let result = required_network_permission(
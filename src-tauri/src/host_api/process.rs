@@ -9,7 +9,7 @@ use utoipa::ToSchema;
 
 use super::approval::{ApprovalBridge, ApprovalDecision, ApprovalRequest};
 use super::middleware::AuthenticatedPlugin;
-use crate::permissions::Permission;
+use crate::permissions::{cmd_scope, Permission};
 use crate::AppState;
 
 /// Maximum output size per stream (stdout/stderr) — 1 MB
@@ -117,35 +117,54 @@ pub async fn exec_command(
         }
     }
 
-    // Always require runtime approval for command execution.
-    // This is the highest-risk operation — every call gets a dialog.
-    let plugin_name = {
+    // Commands covered by an approved pattern (`git *`, `npm run *`) run
+    // without asking. Anything else gets a dialog, where "Allow" saves the
+    // pattern the user picks.
+    let command_line = cmd_scope::command_line(&req.command, &req.args);
+    let (plugin_name, approved) = {
         let mgr = state.read().await;
-        mgr.storage
+        let name = mgr
+            .storage
             .get(&auth.plugin_id)
             .map(|p| p.manifest.name.clone())
-            .unwrap_or_else(|| auth.plugin_id.clone())
+            .unwrap_or_else(|| auth.plugin_id.clone());
+        let argv = || std::iter::once(req.command.as_str()).chain(req.args.iter().map(String::as_str));
+        let approved = !mgr
+            .permissions
+            .is_denied(&auth.plugin_id, &Permission::ProcessExec, Some(&command_line))
+            && mgr
+                .permissions
+                .get_approved_scopes(&auth.plugin_id, &Permission::ProcessExec)
+                .is_some_and(|patterns| patterns.iter().any(|p| cmd_scope::covers(p, argv())));
+        (name, approved)
     };
 
-    let mut context = HashMap::new();
-    context.insert("command".to_string(), req.command.clone());
-    context.insert("args".to_string(), req.args.join(" "));
-    if let Some(ref dir) = req.working_dir {
-        context.insert("working_dir".to_string(), dir.clone());
-    }
-
-    let approval_req = ApprovalRequest {
-        id: uuid::Uuid::new_v4().to_string(),
-        plugin_id: auth.plugin_id.clone(),
-        plugin_name: plugin_name.clone(),
-        category: "process_exec".to_string(),
-        permission: Permission::ProcessExec.as_str().to_string(),
-        context,
-    };
+    if !approved {
+        let mut context = HashMap::new();
+        context.insert("command".to_string(), req.command.clone());
+        context.insert("args".to_string(), req.args.join(" "));
+        context.insert("command_line".to_string(), command_line.clone());
+        context.insert(
+            "scope_options".to_string(),
+            serde_json::to_string(&cmd_scope::suggestions(&req.command, &req.args)).unwrap_or_default(),
+        );
+        if let Some(ref dir) = req.working_dir {
+            context.insert("working_dir".to_string(), dir.clone());
+        }
 
-    match bridge.request_approval(approval_req).await {
-        ApprovalDecision::Approve | ApprovalDecision::ApproveOnce => {}
-        ApprovalDecision::Deny => return Err(StatusCode::FORBIDDEN),
+        let approval_req = ApprovalRequest {
+            id: uuid::Uuid::new_v4().to_string(),
+            plugin_id: auth.plugin_id.clone(),
+            plugin_name,
+            category: "process_exec".to_string(),
+            permission: Permission::ProcessExec.as_str().to_string(),
+            context,
+        };
+
+        match bridge.request_approval(approval_req).await {
+            ApprovalDecision::Approve | ApprovalDecision::ApproveOnce => {}
+            ApprovalDecision::Deny => return Err(StatusCode::FORBIDDEN),
+        }
     }
 
     // Build the command
@@ -1,9 +1,46 @@
 use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::Serialize;
 use std::collections::HashMap;
+use utoipa::ToSchema;
 
 use super::middleware::AuthenticatedPlugin;
 use crate::AppState;
 
+/// Published on the event bus when the user changes the UI language, with
+/// `{ "language": "<BCP-47 code>" }` as data. `NEXUS_LANGUAGE` only reaches
+/// containers created afterwards, so running plugins subscribe to this.
+pub const LANGUAGE_CHANGED_EVENT: &str = "nexus.settings.language_changed";
+
+#[derive(Serialize, ToSchema)]
+pub struct LanguageResponse {
+    /// BCP-47 code, e.g. `en` or `zh`.
+    pub language: String,
+}
+
+/// Get the current Nexus UI language.
+///
+/// `NEXUS_LANGUAGE` is fixed when the container is created; poll this, or
+/// subscribe to `nexus.settings.language_changed` events, to follow changes
+/// while running.
+#[utoipa::path(
+    get,
+    path = "/api/v1/settings/language",
+    tag = "settings",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Current UI language", body = LanguageResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn get_language(
+    State(state): State<AppState>,
+    Extension(_auth): Extension<AuthenticatedPlugin>,
+) -> Json<LanguageResponse> {
+    Json(LanguageResponse {
+        language: state.read().await.settings.language.clone(),
+    })
+}
+
 /// Get settings for the authenticated plugin.
 ///
 /// Returns a flat key-value map. Defaults from the manifest are
@@ -106,3 +143,51 @@ pub async fn put_settings(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(StatusCode::OK)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::oauth::OAuthStore;
+    use crate::permissions::{DefaultPermissionService, PermissionStore};
+    use crate::plugin_manager::PluginManager;
+    use crate::runtime::mock::MockRuntime;
+
+    fn test_state(data_dir: &std::path::Path) -> AppState {
+        let permissions = Arc::new(DefaultPermissionService::new(PermissionStore::default()));
+        let mgr = PluginManager::new(
+            data_dir.to_path_buf(),
+            Arc::new(MockRuntime::new()),
+            permissions,
+            Arc::new(OAuthStore::load(data_dir)),
+        );
+        Arc::new(tokio::sync::RwLock::new(mgr))
+    }
+
+    async fn language(state: AppState) -> String {
+        let auth = AuthenticatedPlugin {
+            plugin_id: "com.test.plugin".into(),
+        };
+        get_language(State(state), Extension(auth)).await.0.language
+    }
+
+    #[tokio::test]
+    async fn language_defaults_to_english() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(language(test_state(tmp.path())).await, "en");
+    }
+
+    #[tokio::test]
+    async fn language_is_the_persisted_one() {
+        let tmp = tempfile::tempdir().unwrap();
+        {
+            let state = test_state(tmp.path());
+            let mut mgr = state.write().await;
+            mgr.settings.language = "de".into();
+            mgr.settings.save().unwrap();
+        }
+        // A fresh manager reads it back from the data directory
+        assert_eq!(language(test_state(tmp.path())).await, "de");
+    }
+}
@@ -1,6 +1,10 @@
 pub mod api_keys;
 pub mod audit;
+mod autostart;
+mod backup;
 mod commands;
+mod data_location;
+mod disk_usage;
 mod error;
 pub mod event_bus;
 pub mod extensions;
@@ -11,7 +15,12 @@ mod notification;
 pub mod oauth;
 pub mod permissions;
 mod plugin_manager;
+mod proxy;
+mod tray;
 pub mod runtime;
+mod secrets;
+mod shortcut;
+mod state_db;
 mod update_checker;
 pub(crate) mod util;
 mod version;
@@ -50,7 +59,7 @@ impl ActiveTheme {
 
 /// Show the main window and switch to Regular activation policy (dock icon visible).
 #[cfg(target_os = "macos")]
-fn show_window(app: &tauri::AppHandle) {
+pub(crate) fn show_window(app: &tauri::AppHandle) {
     let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
     if let Some(w) = app.get_webview_window("main") {
         let _ = w.show();
@@ -69,7 +78,7 @@ fn hide_window(app: &tauri::AppHandle) {
 }
 
 #[cfg(not(target_os = "macos"))]
-fn show_window(app: &tauri::AppHandle) {
+pub(crate) fn show_window(app: &tauri::AppHandle) {
     if let Some(w) = app.get_webview_window("main") {
         let _ = w.show();
         let _ = w.unminimize();
@@ -84,6 +93,18 @@ fn hide_window(app: &tauri::AppHandle) {
     }
 }
 
+/// Hide the main window if it's in front, otherwise bring it up.
+pub(crate) fn toggle_window(app: &tauri::AppHandle) {
+    let in_front = app.get_webview_window("main").is_some_and(|w| {
+        w.is_visible().unwrap_or(false) && w.is_focused().unwrap_or(false)
+    });
+    if in_front {
+        hide_window(app);
+    } else {
+        show_window(app);
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let app = tauri::Builder::default()
@@ -99,6 +120,7 @@ pub fn run() {
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(shortcut::plugin())
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -116,18 +138,52 @@ pub fn run() {
             // Request OS notification permission (macOS shows a system dialog on first launch)
             notification::init();
 
+            // Recent lifecycle events, replayed to the webview after a reload
+            app.manage(lifecycle_events::LifecycleBuffer::default());
+
+            // Per-plugin resource samples, filled by the sampler spawned below
+            app.manage(plugin_manager::resource_history::ResourceHistory::default());
+
+            // Wakes the tray status task spawned below
+            app.manage(tray::TrayRefresh::default());
+
             let app_handle = app.handle().clone();
-            let data_dir = app_handle
+            let default_data_dir = app_handle
                 .path()
                 .app_data_dir()
                 .expect("failed to get app data dir");
-            std::fs::create_dir_all(&data_dir).ok();
+            std::fs::create_dir_all(&default_data_dir).ok();
+            // Before anything opens files there: a move may be pending
+            let data_dir = data_location::resolve(&default_data_dir);
+            // Then a restore staged last run, before the stores below load
+            // Read once the restored settings are in place, so they pick the engine
+            let connect_runtime = || -> Arc<dyn runtime::ContainerRuntime> {
+                let engine = plugin_manager::storage::NexusSettings::load(&data_dir)
+                    .map(|s| s.engine)
+                    .unwrap_or_default();
+                let docker_runtime = DockerRuntime::connect(&engine).unwrap_or_else(|e| {
+                    log::error!("Configured container engine unusable, falling back to Docker: {}", e);
+                    DockerRuntime::connect(&runtime::EngineConfig::default())
+                        .expect("failed to connect to Docker daemon")
+                });
+                Arc::new(docker_runtime)
+            };
+
+            match tauri::async_runtime::block_on(backup::apply_pending(&data_dir, connect_runtime)) {
+                Ok(true) => log::info!("Applied restored backup"),
+                Ok(false) => {}
+                Err(e) => log::error!("Failed to apply restored backup: {}", e),
+            }
+
+            // Everything Nexus notified about, with read state
+            app.manage(notification::NotificationHistory::load(&data_dir));
 
-            let docker_runtime = DockerRuntime::new()
-                .expect("failed to connect to Docker daemon");
-            let runtime: Arc<dyn runtime::ContainerRuntime> = Arc::new(docker_runtime);
+            let runtime = connect_runtime();
 
-            let perm_store = permissions::PermissionStore::load(&data_dir).unwrap_or_default();
+            let perm_store = permissions::PermissionStore::load(&data_dir).unwrap_or_else(|e| {
+                util::report_store_problem(state_db::DB_FILE, e.to_string(), false);
+                permissions::PermissionStore::default()
+            });
             let perm_service: Arc<dyn permissions::PermissionService> =
                 Arc::new(permissions::DefaultPermissionService::new(perm_store));
 
@@ -139,7 +195,15 @@ pub fn run() {
             let api_key_store = api_keys::ApiKeyStore::load(&data_dir);
             app.manage(api_key_store.clone());
 
-            let mgr = PluginManager::new(data_dir.clone(), runtime.clone(), perm_service, oauth_store.clone());
+            // Live MCP gateway sessions — listed and revocable from settings
+            let mcp_sessions = host_api::mcp::McpSessionStore::new();
+            app.manage(mcp_sessions.clone());
+
+            let mgr = PluginManager::new(data_dir.clone(), runtime.clone(), perm_service.clone(), oauth_store.clone());
+            proxy::set(&mgr.settings.proxy);
+            if let Err(e) = shortcut::apply(&app_handle, mgr.settings.global_shortcut.as_deref()) {
+                log::warn!("Global shortcut not registered: {}", e);
+            }
 
             let state = Arc::new(RwLock::new(mgr));
             PluginManager::wire_extension_ipc(&state);
@@ -148,6 +212,10 @@ pub fn run() {
             // CloudEvents event bus — single Dispatch facade bundles bus + store + executor
             let dispatch = event_bus::Dispatch::new(&data_dir, state.clone(), app_handle.clone())
                 .expect("failed to initialize event bus");
+            dispatch
+                .bus
+                .blocking_write()
+                .set_publish_quota(state.blocking_read().settings.event_quota.clone());
             app.manage(dispatch.bus.clone());
             app.manage(dispatch.store.clone());
             app.manage(dispatch.clone());
@@ -157,10 +225,26 @@ pub fn run() {
                 audit::store::AuditStore::new(&data_dir)
                     .expect("failed to initialize audit store"),
             );
-            let (audit_writer, audit_future) = audit::writer::create(audit_store.clone());
+            let audit_forwarder = Arc::new(audit::writer::AuditForwarder::new());
+            audit_forwarder.apply(state.blocking_read().settings.audit_forward.clone());
+            let audit_alerts = Arc::new(audit::writer::AlertWatcher::new(
+                app_handle.clone(),
+                &state.blocking_read().settings.audit_alerts,
+            ));
+            let (audit_writer, audit_future) = audit::writer::create(
+                audit_store.clone(),
+                audit_forwarder.clone(),
+                audit_alerts.clone(),
+            );
+            audit_writer.set_redaction(&state.blocking_read().settings.audit_redaction);
             let audit_writer_for_server = audit_writer.clone();
+            let audit_writer_for_expiry = audit_writer.clone();
+            let audit_writer_for_retention = audit_writer.clone();
+            let audit_store_for_retention = audit_store.clone();
             app.manage(audit_store);
             app.manage(audit_writer);
+            app.manage(audit_forwarder);
+            app.manage(audit_alerts);
             tauri::async_runtime::spawn(audit_future);
 
             // Wire dispatch into all registered extensions
@@ -176,6 +260,39 @@ pub fn run() {
             // Spawn background retry worker for durable event delivery
             dispatch.spawn_retry_worker();
 
+            // Mirror events to and from an MQTT broker, when configured
+            let mqtt_bridge = event_bus::mqtt::MqttBridge::new();
+            mqtt_bridge.apply(state.blocking_read().settings.mqtt_bridge.clone(), dispatch.clone());
+            app.manage(mqtt_bridge);
+
+            // Stream events to an external pipeline, when configured
+            let exporter = event_bus::export::EventExporter::new();
+            exporter.apply(state.blocking_read().settings.event_export.clone(), dispatch.clone());
+            app.manage(exporter);
+
+            // Publish user-defined events on cron schedules
+            let timers = event_bus::timer::TimerScheduler::new(&data_dir);
+            timers.start(dispatch.clone());
+            app.manage(timers);
+
+            // Stores found damaged while loading, now that they can be shown
+            for problem in util::take_store_problems() {
+                let title = if problem.recovered {
+                    format!("{} was restored from its backup", problem.file)
+                } else {
+                    format!("{} couldn't be read and was reset", problem.file)
+                };
+                notification::notify(&app_handle, "store.damaged", title, problem.message.clone());
+                lifecycle_events::emit(
+                    Some(&app_handle),
+                    lifecycle_events::LifecycleEvent::StoreDamaged {
+                        file: problem.file,
+                        message: problem.message,
+                        recovered: problem.recovered,
+                    },
+                );
+            }
+
             // Active theme — shared between Tauri UI and Axum (OAuth consent page)
             let theme = {
                 let mgr = state.blocking_read();
@@ -183,7 +300,7 @@ pub fn run() {
             };
             app.manage(theme.clone());
 
-            let approval_bridge = Arc::new(ApprovalBridge::new(app_handle.clone()));
+            let approval_bridge = Arc::new(ApprovalBridge::new(app_handle.clone(), perm_service));
             app.manage(approval_bridge.clone());
 
             // Pending app update — shared between check and download commands
@@ -240,16 +357,29 @@ pub fn run() {
                 }
 
                 // Start the Host API server
-                if let Err(e) = host_api::start_server(state_clone, approval_bridge, oauth_clone, theme_clone, api_keys_clone, dispatch_clone, audit_writer_for_server).await {
+                if let Err(e) = host_api::start_server(state_clone, approval_bridge, oauth_clone, theme_clone, api_keys_clone, dispatch_clone, audit_writer_for_server, mcp_sessions).await {
                     log::error!("Host API server failed: {}", e);
                 }
             });
 
             // Spawn container event watcher for real-time plugin state changes
-            plugin_manager::container_events::spawn(
+            plugin_manager::container_events::spawn(app_handle.clone(), state.clone());
+
+            // Sample per-plugin resource usage for the history sparklines
+            plugin_manager::resource_history::spawn(app_handle.clone(), state.clone());
+
+            // Persist MCP tool usage in the background rather than per call
+            host_api::mcp::stats::spawn_flusher(state.blocking_read().mcp_tool_stats.clone());
+
+            // Revoke time-limited permission grants once they lapse
+            permissions::expiry::spawn(app_handle.clone(), state.clone(), audit_writer_for_expiry);
+
+            // Prune the audit log by the configured age and size limits
+            audit::retention::spawn(
                 app_handle.clone(),
                 state.clone(),
-                runtime.clone(),
+                audit_store_for_retention,
+                audit_writer_for_retention,
             );
 
             // Build system tray with menu (keeps app running when window is closed)
@@ -257,7 +387,7 @@ pub fn run() {
             let quit = MenuItemBuilder::with_id("quit", "Quit Nexus").build(app)?;
             let tray_menu = MenuBuilder::new(app).items(&[&show, &quit]).build()?;
 
-            TrayIconBuilder::new()
+            TrayIconBuilder::with_id(tray::TRAY_ID)
                 .icon(app.default_window_icon().cloned().unwrap())
                 .icon_as_template(true)
                 .tooltip("Nexus")
@@ -279,6 +409,17 @@ pub fn run() {
                 })
                 .build(app)?;
 
+            // Badge the tray icon for plugins in error, updates, and engine trouble
+            tray::spawn(app_handle.clone(), state.clone());
+
+            // Login launches stay in the tray; the gateway doesn't need the window
+            autostart::refresh(&app.config().identifier);
+            if autostart::launched_at_login() {
+                hide_window(app.handle());
+            } else {
+                show_window(app.handle());
+            }
+
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -305,8 +446,11 @@ pub fn run() {
             commands::plugins::plugin_storage_info,
             commands::plugins::plugin_clear_storage,
             commands::plugins::plugin_dev_mode_toggle,
+            commands::plugins::plugin_set_channel,
+            commands::plugins::plugin_set_security_override,
             commands::plugins::plugin_rebuild,
             commands::marketplace::marketplace_search,
+            commands::marketplace::marketplace_collections,
             commands::marketplace::marketplace_refresh,
             commands::marketplace::marketplace_load,
             commands::permissions::permission_grant,
@@ -315,24 +459,66 @@ pub fn run() {
             commands::permissions::permission_list,
             commands::permissions::permission_remove_path,
             commands::system::app_version,
+            commands::system::lifecycle_replay,
             commands::system::check_engine,
+            commands::system::engine_config,
+            commands::system::switch_engine,
             commands::system::container_resource_usage,
+            commands::system::plugin_resource_history,
+            commands::system::system_disk_usage,
+            commands::backup::nexus_backup,
+            commands::backup::nexus_backup_info,
+            commands::backup::nexus_restore,
             commands::system::get_resource_quotas,
             commands::system::save_resource_quotas,
             commands::system::get_update_check_interval,
             commands::system::set_update_check_interval,
             commands::system::check_url_reachable,
+            commands::system::get_data_location,
+            commands::system::move_data_dir,
+            commands::system::cancel_data_dir_move,
+            commands::system::get_proxy_config,
+            commands::system::set_proxy_config,
+            commands::system::get_launch_at_login,
+            commands::system::set_launch_at_login,
+            commands::system::get_global_shortcut,
+            commands::system::set_global_shortcut,
+            commands::system::get_credential_storage,
+            commands::system::set_credential_storage,
+            commands::system::get_image_scan_policy,
+            commands::system::set_image_scan_policy,
+            commands::system::get_root_user_policy,
+            commands::system::set_root_user_policy,
             commands::system::set_language,
             commands::system::set_theme,
+            commands::system::get_read_only_mode,
+            commands::system::set_read_only_mode,
+            commands::system::get_prometheus_metrics,
+            commands::system::set_prometheus_metrics,
             commands::permissions::runtime_approval_respond,
             commands::registries::registry_list,
             commands::registries::registry_add,
             commands::registries::registry_remove,
             commands::registries::registry_toggle,
+            commands::registries::registry_set_auth,
+            commands::registries::registry_reorder,
+            commands::registries::registry_get_conflict_policy,
+            commands::registries::registry_set_conflict_policy,
+            commands::registries::registry_bundle_export,
+            commands::registries::registry_bundle_import,
             commands::mcp::mcp_get_settings,
             commands::mcp::mcp_set_enabled,
             commands::mcp::mcp_list_tools,
             commands::mcp::mcp_config_snippet,
+            commands::mcp::mcp_config_install,
+            commands::mcp::mcp_tool_stats,
+            commands::mcp::mcp_tool_stats_reset,
+            commands::mcp::mcp_set_client_limits,
+            commands::mcp::mcp_set_tool_timeout,
+            commands::mcp::mcp_list_tool_approvals,
+            commands::mcp::mcp_revoke_tool_approval,
+            commands::mcp::mcp_list_sessions,
+            commands::mcp::mcp_revoke_session,
             commands::extensions::extension_list,
             commands::extensions::extension_install,
             commands::extensions::extension_install_local,
@@ -347,43 +533,123 @@ pub fn run() {
             commands::extensions::extension_resource_update,
             commands::extensions::extension_resource_delete,
             commands::events::event_log_query,
+            commands::events::event_log_chain,
             commands::events::event_log_count,
+            commands::events::event_subscribe,
+            commands::events::event_unsubscribe,
+            commands::events::event_publish,
+            commands::events::event_export_get,
+            commands::events::event_export_set,
+            commands::events::event_export_status,
+            commands::events::mqtt_bridge_get,
+            commands::events::mqtt_bridge_set,
+            commands::events::mqtt_bridge_status,
+            commands::events::event_redelivery_get,
+            commands::events::event_redelivery_set,
+            commands::events::timer_list,
+            commands::events::timer_create,
+            commands::events::timer_update,
+            commands::events::timer_delete,
+            commands::events::timer_fire,
+            commands::events::event_quota_get,
+            commands::events::event_quota_set,
+            commands::events::event_retention_get,
+            commands::events::event_retention_set,
+            commands::events::event_log_compact,
+            commands::events::event_log_stats,
+            commands::events::event_bus_metrics,
+            commands::events::event_replay,
+            commands::events::event_schema_list,
+            commands::events::event_schema_delete,
+            commands::events::dead_letter_list,
+            commands::events::dead_letter_retry,
+            commands::events::dead_letter_purge,
             commands::events::routing_rule_list,
             commands::events::routing_rule_get,
             commands::events::routing_rule_create,
             commands::events::routing_rule_update,
             commands::events::routing_rule_delete,
             commands::permissions::permission_remove_scope,
+            commands::permissions::permission_deny_list,
+            commands::permissions::permission_deny_add,
+            commands::permissions::permission_deny_remove,
+            commands::permissions::permission_set_always_prompt,
+            commands::permissions::permission_set_rate_cap,
+            commands::permissions::permission_history,
+            commands::permissions::permission_simulate,
+            commands::permissions::permission_export,
+            commands::permissions::permission_import,
+            commands::permissions::permission_profile_list,
+            commands::permissions::permission_profile_save,
+            commands::permissions::permission_profile_delete,
+            commands::permissions::permission_profile_apply,
             commands::updates::check_updates,
             commands::updates::get_cached_updates,
+            commands::updates::get_yanked_installs,
             commands::updates::dismiss_update,
             commands::updates::update_plugin,
             commands::updates::update_extension,
             commands::updates::update_extension_force_key,
+            commands::updates::update_plugin_force_key,
             commands::updates::last_update_check,
+            commands::updates::get_auto_update_policy,
+            commands::updates::set_auto_update_policy,
             commands::mcp_wrap::mcp_discover_tools,
             commands::mcp_wrap::mcp_suggest_metadata,
             commands::mcp_wrap::mcp_generate_and_install,
+            commands::mcp_wrap::mcp_rewrap,
             commands::oauth::oauth_list_clients,
             commands::oauth::oauth_revoke_client,
+            commands::oauth::oauth_device_lookup,
+            commands::oauth::oauth_device_resolve,
+            commands::oauth::oauth_set_token_format,
+            commands::oauth::oauth_rotate_jwt_key,
+            commands::oauth::oauth_get_client_grants,
+            commands::oauth::oauth_restrict_client_grants,
             commands::api_keys::api_key_list,
+            commands::api_keys::api_key_usage,
             commands::api_keys::api_key_generate,
             commands::api_keys::api_key_revoke,
             commands::api_keys::api_key_get_default,
             commands::api_keys::api_key_regenerate_default,
+            commands::api_keys::api_key_rotate,
+            commands::api_keys::api_key_set_expiry,
+            commands::api_keys::api_key_set_scope,
             commands::app_updater::check_app_update,
             commands::app_updater::download_app_update,
             commands::app_updater::get_update_channel,
             commands::app_updater::set_update_channel,
             commands::audit::audit_query,
             commands::audit::audit_count,
+            commands::audit::audit_stats,
             commands::audit::audit_export,
+            commands::audit::audit_verify,
+            commands::audit::audit_forward_get,
+            commands::audit::audit_forward_set,
+            commands::audit::audit_forward_status,
+            commands::audit::audit_retention_get,
+            commands::audit::audit_retention_set,
+            commands::audit::audit_alerts_get,
+            commands::audit::audit_alerts_set,
+            commands::audit::audit_redaction_get,
+            commands::audit::audit_redaction_set,
+            commands::audit::audit_access_log_get,
+            commands::audit::audit_access_log_set,
             notification::send_notification,
+            notification::notifications_list,
+            notification::notifications_mark_read,
+            notification::notifications_clear,
+            notification::notification_action,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
-    app.run(|_app_handle: &tauri::AppHandle, _event: tauri::RunEvent| {
+    let mcp_tool_stats = app.state::<AppState>().blocking_read().mcp_tool_stats.clone();
+    app.run(move |_app_handle: &tauri::AppHandle, _event: tauri::RunEvent| {
+        if let tauri::RunEvent::Exit = _event {
+            mcp_tool_stats.flush();
+        }
+
         // macOS dock icon click — re-show the hidden window
         #[cfg(target_os = "macos")]
         if let tauri::RunEvent::Reopen {
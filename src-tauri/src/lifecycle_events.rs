@@ -1,8 +1,11 @@
 use crate::commands::extensions::ExtensionStatus;
 use crate::event_bus::cloud_event::CloudEvent;
-use crate::event_bus::SharedEventBus;
+use crate::event_bus::Dispatch;
 use crate::plugin_manager::storage::InstalledPlugin;
+use crate::runtime::Progress;
 use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
 use tauri::{Emitter, Manager};
 
 pub const LIFECYCLE_CHANNEL: &str = "nexus://lifecycle";
@@ -30,7 +33,12 @@ pub enum LifecycleEvent {
     PluginRemoved { plugin_id: String },
 
     #[serde(rename = "plugin:installing")]
-    PluginInstalling { message: String },
+    PluginInstalling {
+        message: String,
+        /// Image pull or build progress, when the runtime reports it.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        progress: Option<Progress>,
+    },
 
     #[serde(rename = "plugin:installed")]
     PluginInstalled { plugin: InstalledPlugin },
@@ -44,7 +52,22 @@ pub enum LifecycleEvent {
 
     // -- Plugin update stages (replaces nexus://plugin-update) --
     #[serde(rename = "plugin:update_stage")]
-    PluginUpdateStage { plugin_id: String, stage: String },
+    PluginUpdateStage {
+        plugin_id: String,
+        stage: String,
+        /// Progress within the stage (`pulling`, `building`), when known.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        progress: Option<Progress>,
+    },
+
+    /// An update failed after the old container was stopped, and the
+    /// previous version was restored.
+    #[serde(rename = "plugin:update_rolled_back")]
+    PluginUpdateRolledBack {
+        plugin: InstalledPlugin,
+        failed_version: String,
+        error: String,
+    },
 
     // -- Plugin dev rebuild (replaces nexus://dev-rebuild) --
     #[serde(rename = "plugin:rebuild")]
@@ -54,6 +77,28 @@ pub enum LifecycleEvent {
         message: String,
     },
 
+    // -- Permission lifecycle --
+    #[serde(rename = "permission:expired")]
+    PermissionExpired {
+        plugin_id: String,
+        permissions: Vec<String>,
+    },
+
+    // -- Audit log --
+    /// The audit log hit its size limit and entries were pruned early.
+    #[serde(rename = "audit:pruned")]
+    AuditPruned { trimmed: usize, max_bytes: u64 },
+
+    // -- Storage --
+    /// A store file was damaged at startup. When `recovered`, its backup
+    /// was loaded instead; otherwise the store started empty.
+    #[serde(rename = "store:damaged")]
+    StoreDamaged {
+        file: String,
+        message: String,
+        recovered: bool,
+    },
+
     // -- Extension lifecycle --
     #[serde(rename = "extension:enabling")]
     ExtensionEnabling { ext_id: String },
@@ -87,37 +132,255 @@ pub enum LifecycleEvent {
     },
 }
 
+/// Lifecycle events kept for replay after a webview reload.
+const BUFFER_LEN: usize = 200;
+
+/// A lifecycle event with its position in the stream. This is the payload
+/// emitted on [`LIFECYCLE_CHANNEL`].
+#[derive(Debug, Clone, Serialize)]
+pub struct BufferedEvent {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: LifecycleEvent,
+}
+
+/// Recent lifecycle events, so a webview that reloaded mid-operation can
+/// catch up on the ones it missed. Managed as Tauri state.
+#[derive(Default)]
+pub struct LifecycleBuffer {
+    inner: Mutex<BufferInner>,
+}
+
+#[derive(Default)]
+struct BufferInner {
+    last_seq: u64,
+    events: VecDeque<BufferedEvent>,
+}
+
+impl LifecycleBuffer {
+    fn push(&self, event: LifecycleEvent) -> BufferedEvent {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.last_seq += 1;
+        let buffered = BufferedEvent {
+            seq: inner.last_seq,
+            event,
+        };
+        if inner.events.len() == BUFFER_LEN {
+            inner.events.pop_front();
+        }
+        inner.events.push_back(buffered.clone());
+        buffered
+    }
+
+    /// Terminal events with a sequence number above `after`, oldest first.
+    pub fn replay(&self, after: u64) -> Vec<BufferedEvent> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .events
+            .iter()
+            .filter(|e| e.seq > after && e.event.is_terminal())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Source of lifecycle events mirrored onto the event bus.
+pub const LIFECYCLE_SOURCE: &str = "nexus://core";
+
+impl LifecycleEvent {
+    /// Whether the event ends an operation, so the UI has to see it to
+    /// leave its busy state.
+    pub fn is_terminal(&self) -> bool {
+        match self {
+            Self::PluginStarted { .. }
+            | Self::PluginStopped { .. }
+            | Self::PluginRemoved { .. }
+            | Self::PluginInstalled { .. }
+            | Self::PluginError { .. }
+            | Self::PluginUpdateRolledBack { .. }
+            | Self::PermissionExpired { .. }
+            | Self::AuditPruned { .. }
+            | Self::StoreDamaged { .. }
+            | Self::ExtensionEnabled { .. }
+            | Self::ExtensionDisabled { .. }
+            | Self::ExtensionRemoved { .. }
+            | Self::ExtensionInstalled { .. }
+            | Self::ExtensionError { .. } => true,
+            Self::PluginRebuild { status, .. } => status == "complete" || status == "error",
+            Self::PluginStarting { .. }
+            | Self::PluginStopping { .. }
+            | Self::PluginRemoving { .. }
+            | Self::PluginInstalling { .. }
+            | Self::PluginUpdateStage { .. }
+            | Self::ExtensionEnabling { .. }
+            | Self::ExtensionDisabling { .. }
+            | Self::ExtensionRemoving { .. }
+            | Self::ExtensionInstalling { .. } => false,
+        }
+    }
+
+    /// The plugin or extension the event is about.
+    pub fn subject(&self) -> Option<&str> {
+        match self {
+            Self::PluginStarting { plugin_id }
+            | Self::PluginStopping { plugin_id }
+            | Self::PluginRemoving { plugin_id }
+            | Self::PluginRemoved { plugin_id }
+            | Self::PluginError { plugin_id, .. }
+            | Self::PluginUpdateStage { plugin_id, .. }
+            | Self::PluginRebuild { plugin_id, .. }
+            | Self::PermissionExpired { plugin_id, .. } => Some(plugin_id),
+            Self::PluginStarted { plugin }
+            | Self::PluginStopped { plugin }
+            | Self::PluginInstalled { plugin }
+            | Self::PluginUpdateRolledBack { plugin, .. } => Some(&plugin.manifest.id),
+            Self::ExtensionEnabling { ext_id }
+            | Self::ExtensionDisabling { ext_id }
+            | Self::ExtensionRemoving { ext_id }
+            | Self::ExtensionRemoved { ext_id }
+            | Self::ExtensionInstalling { ext_id }
+            | Self::ExtensionError { ext_id, .. } => Some(ext_id),
+            Self::ExtensionEnabled { extension }
+            | Self::ExtensionDisabled { extension }
+            | Self::ExtensionInstalled { extension } => Some(&extension.id),
+            Self::PluginInstalling { .. }
+            | Self::AuditPruned { .. }
+            | Self::StoreDamaged { .. } => None,
+        }
+    }
+
+    /// The event as a CloudEvent: `plugin:update_stage` becomes
+    /// `nexus.lifecycle.plugin.update_stage`, with the serialized event as
+    /// data and the plugin or extension ID as subject.
+    pub fn to_cloud_event(&self) -> Result<CloudEvent, String> {
+        let data = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        let kind = data
+            .get("kind")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        let mut builder = CloudEvent::builder()
+            .source(LIFECYCLE_SOURCE)
+            .event_type(format!("nexus.lifecycle.{}", kind.replace(':', ".")));
+        if let Some(subject) = self.subject() {
+            builder = builder.subject(subject);
+        }
+        builder.data(data).build()
+    }
+}
+
 pub fn emit(app: Option<&tauri::AppHandle>, event: LifecycleEvent) {
-    if let Some(app) = app {
-        let _ = app.emit(LIFECYCLE_CHANNEL, &event);
-
-        // Bridge lifecycle events onto the CloudEvents bus.
-        // Serialize the event to extract the `kind` tag and full data payload.
-        if let Ok(data) = serde_json::to_value(&event) {
-            let kind = data
-                .get("kind")
-                .and_then(|v| v.as_str())
-                .unwrap_or("unknown");
-            let event_type = format!(
-                "nexus.lifecycle.{}",
-                kind.replace(':', ".")
-            );
-
-            let cloud_event = CloudEvent::builder()
-                .source("nexus://core")
-                .event_type(&event_type)
-                .data(data)
-                .build();
-
-            if let Ok(ce) = cloud_event {
-                if let Some(bus) = app.try_state::<SharedEventBus>() {
-                    let bus: SharedEventBus = bus.inner().clone();
-                    tauri::async_runtime::spawn(async move {
-                        let mut b = bus.write().await;
-                        b.publish(ce);
-                    });
-                }
+    emit_caused_by(app, event, None);
+}
+
+/// Emit a lifecycle event that happened because of a bus event, e.g. a
+/// routing rule restarting a plugin. The bridged CloudEvent joins the
+/// cause's correlation chain.
+pub fn emit_caused_by(
+    app: Option<&tauri::AppHandle>,
+    event: LifecycleEvent,
+    cause: Option<&CloudEvent>,
+) {
+    let Some(app) = app else {
+        return;
+    };
+    match app.try_state::<LifecycleBuffer>() {
+        Some(buffer) => {
+            let _ = app.emit(LIFECYCLE_CHANNEL, buffer.push(event.clone()));
+        }
+        None => {
+            let _ = app.emit(LIFECYCLE_CHANNEL, &event);
+        }
+    }
+    crate::tray::refresh(app);
+
+    // Mirror onto the CloudEvents bus so subscribers and routing rules can
+    // react without the frontend
+    let mut ce = match event.to_cloud_event() {
+        Ok(ce) => ce,
+        Err(e) => {
+            log::warn!("Failed to bridge lifecycle event: {}", e);
+            return;
+        }
+    };
+    if let Some(cause) = cause {
+        ce.set_cause(cause);
+    }
+    if let Some(dispatch) = app.try_state::<Dispatch>() {
+        let dispatch: Dispatch = dispatch.inner().clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = dispatch.publish(ce).await {
+                log::warn!("Failed to publish lifecycle event: {}", e);
             }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_replays_terminal_events_after_seq() {
+        let buffer = LifecycleBuffer::default();
+        let starting = buffer.push(LifecycleEvent::PluginStarting {
+            plugin_id: "a".into(),
+        });
+        assert_eq!(starting.seq, 1);
+        buffer.push(LifecycleEvent::PluginError {
+            plugin_id: "a".into(),
+            action: "starting".into(),
+            message: "boom".into(),
+        });
+        buffer.push(LifecycleEvent::PluginRemoved {
+            plugin_id: "b".into(),
+        });
+
+        let seqs: Vec<u64> = buffer.replay(0).iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, [2, 3]);
+        let seqs: Vec<u64> = buffer.replay(2).iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, [3]);
+
+        for i in 0..BUFFER_LEN {
+            buffer.push(LifecycleEvent::PluginRemoved {
+                plugin_id: format!("p{}", i),
+            });
+        }
+        let replayed = buffer.replay(0);
+        assert_eq!(replayed.len(), BUFFER_LEN);
+        assert_eq!(replayed[0].seq, 4);
+
+        let json = serde_json::to_value(&replayed[0]).unwrap();
+        assert_eq!(json["seq"], 4);
+        assert_eq!(json["kind"], "plugin:removed");
+    }
+
+    #[test]
+    fn cloud_event_type_and_subject_follow_the_kind() {
+        let ce = LifecycleEvent::PluginUpdateStage {
+            plugin_id: "com.example.notes".into(),
+            stage: "pulling".into(),
+            progress: Some(Progress {
+                current: 30,
+                total: 120,
+                unit: crate::runtime::ProgressUnit::Bytes,
+            }),
+        }
+        .to_cloud_event()
+        .unwrap();
+        assert_eq!(ce.event_type, "nexus.lifecycle.plugin.update_stage");
+        assert_eq!(ce.source, LIFECYCLE_SOURCE);
+        assert_eq!(ce.subject.as_deref(), Some("com.example.notes"));
+        assert_eq!(ce.data["stage"], "pulling");
+        assert_eq!(ce.data["progress"]["unit"], "bytes");
+
+        let ce = LifecycleEvent::PluginInstalling {
+            message: "Pulling image".into(),
+            progress: None,
         }
+        .to_cloud_event()
+        .unwrap();
+        assert_eq!(ce.event_type, "nexus.lifecycle.plugin.installing");
+        assert!(ce.subject.is_none());
+        assert!(ce.data.get("progress").is_none());
     }
 }
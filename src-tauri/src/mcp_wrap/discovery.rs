@@ -1,5 +1,8 @@
 use super::McpWrapError;
+use crate::host_api::mcp::client::transport_config;
+use crate::plugin_manager::storage::REMOTE_MCP_TOKEN_KEY;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::OnceLock;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
@@ -29,25 +32,68 @@ fn user_path() -> &'static str {
     })
 }
 
+/// Max stderr kept for env var detection.
+const MAX_STDERR_BYTES: usize = 64 * 1024;
+
+/// Tools from a successful handshake, plus env vars the server warned about
+/// on stderr that weren't provided.
+pub struct Discovery {
+    pub tools: Vec<DiscoveredTool>,
+    pub missing_env: Vec<String>,
+}
+
 /// Discover tools from an MCP server by running the given command and
 /// performing the JSON-RPC initialize → notifications/initialized → tools/list handshake.
-pub async fn discover_tools(command: &str) -> Result<Vec<DiscoveredTool>, McpWrapError> {
+///
+/// `env` is passed to the server (as `-e` flags for `docker run`). If the
+/// server fails and its stderr names env vars that weren't provided, the
+/// error is [`McpWrapError::MissingEnv`] so the caller can prompt for them.
+pub async fn discover_tools(
+    command: &str,
+    env: &HashMap<String, String>,
+) -> Result<Discovery, McpWrapError> {
     let parts: Vec<&str> = command.split_whitespace().collect();
     if parts.is_empty() {
         return Err(McpWrapError::Other("Empty command".to_string()));
     }
 
-    let binary = parts[0];
-    if binary != "npx" && binary != "node" {
-        return Err(McpWrapError::UnsupportedRuntime(binary.to_string()));
+    if super::McpRuntime::detect(command)? == super::McpRuntime::Remote {
+        return discover_remote(parts[0], env).await;
     }
 
+    let mut args: Vec<&str> = parts[1..].to_vec();
+    let mut missing: Vec<String> = Vec::new();
+    if super::McpRuntime::detect(command)? == super::McpRuntime::Docker {
+        // Run the image ephemerally with stdin attached, whatever the user typed
+        if !args.contains(&"-i") && !args.contains(&"-it") {
+            args.insert(1, "-i");
+        }
+        if !args.contains(&"--rm") {
+            args.insert(1, "--rm");
+        }
+        // Bare `-e KEY` forwards the value set on the docker CLI below
+        for key in env.keys() {
+            args.insert(1, key);
+            args.insert(1, "-e");
+        }
+        if let Some(run) = super::parse_docker_run(command) {
+            missing.extend(
+                run.inherit_env
+                    .into_iter()
+                    .filter(|k| !env.contains_key(k) && std::env::var_os(k).is_none()),
+            );
+        }
+    }
+    let binary = parts[0];
+
     let mut child = Command::new(binary)
-        .args(&parts[1..])
+        .args(&args)
         .env("PATH", user_path())
+        .envs(env)
         .stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::inherit())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
         .spawn()?;
 
     let stdin = child.stdin.take().ok_or_else(|| {
@@ -56,6 +102,23 @@ pub async fn discover_tools(command: &str) -> Result<Vec<DiscoveredTool>, McpWra
     let stdout = child.stdout.take().ok_or_else(|| {
         McpWrapError::Other("Failed to open stdout".to_string())
     })?;
+    let stderr = child.stderr.take().ok_or_else(|| {
+        McpWrapError::Other("Failed to open stderr".to_string())
+    })?;
+
+    // Collect stderr (still logged) so missing-config errors can be parsed
+    let stderr_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut collected = String::new();
+        while let Ok(Some(line)) = lines.next_line().await {
+            log::debug!("[mcp-wrap] {}", line);
+            if collected.len() < MAX_STDERR_BYTES {
+                collected.push_str(&line);
+                collected.push('\n');
+            }
+        }
+        collected
+    });
 
     let mut writer = stdin;
     let mut reader = BufReader::new(stdout);
@@ -73,22 +136,95 @@ pub async fn discover_tools(command: &str) -> Result<Vec<DiscoveredTool>, McpWra
     });
     let mut msg = serde_json::to_string(&init_req)?;
     msg.push('\n');
-    writer.write_all(msg.as_bytes()).await?;
-    writer.flush().await?;
-
-    let result = tokio::time::timeout(
-        std::time::Duration::from_secs(15),
-        run_handshake(&mut reader, &mut writer),
-    )
+    // A server that exits on startup closes stdin; report it via stderr below
+    let sent = async {
+        writer.write_all(msg.as_bytes()).await?;
+        writer.flush().await
+    }
     .await;
 
+    let result = match sent {
+        Ok(()) => tokio::time::timeout(
+            std::time::Duration::from_secs(15),
+            run_handshake(&mut reader, &mut writer),
+        )
+        .await
+        .unwrap_or(Err(McpWrapError::Timeout)),
+        Err(e) => Err(e.into()),
+    };
+
     // Kill the process regardless of outcome
     let _ = child.kill().await;
+    let stderr_text = tokio::time::timeout(std::time::Duration::from_secs(2), stderr_task)
+        .await
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or_default();
+
+    for name in super::detect_env_vars(&stderr_text) {
+        if !env.contains_key(&name) && !missing.contains(&name) {
+            missing.push(name);
+        }
+    }
 
     match result {
-        Ok(Ok(tools)) => Ok(tools),
-        Ok(Err(e)) => Err(e),
-        Err(_) => Err(McpWrapError::Timeout),
+        Ok(tools) => Ok(Discovery {
+            tools,
+            missing_env: missing,
+        }),
+        Err(_) if !missing.is_empty() => Err(McpWrapError::MissingEnv(missing)),
+        Err(e) => Err(e),
+    }
+}
+
+/// Discover tools from a remote Streamable HTTP endpoint. The bearer token,
+/// if any, is `env[REMOTE_MCP_TOKEN_KEY]`; an auth failure without one asks
+/// for it through [`McpWrapError::MissingEnv`].
+async fn discover_remote(
+    url: &str,
+    env: &HashMap<String, String>,
+) -> Result<Discovery, McpWrapError> {
+    use rmcp::service::ServiceExt;
+    use rmcp::transport::StreamableHttpClientTransport;
+
+    let token = env.get(REMOTE_MCP_TOKEN_KEY).map(String::as_str);
+    let transport = StreamableHttpClientTransport::from_config(transport_config(url, token));
+    let listed = tokio::time::timeout(std::time::Duration::from_secs(15), async {
+        let service = ()
+            .serve(transport)
+            .await
+            .map_err(|e| McpWrapError::Other(format!("Failed to connect: {}", e)))?;
+        let tools = service
+            .list_all_tools()
+            .await
+            .map_err(|e| McpWrapError::Other(format!("tools/list failed: {}", e)));
+        let _ = service.cancel().await;
+        tools
+    })
+    .await
+    .unwrap_or(Err(McpWrapError::Timeout));
+
+    match listed {
+        Ok(tools) => Ok(Discovery {
+            tools: tools
+                .into_iter()
+                .map(|t| DiscoveredTool {
+                    name: t.name.to_string(),
+                    description: t.description.map(|d| d.to_string()).unwrap_or_default(),
+                    input_schema: serde_json::Value::Object((*t.input_schema).clone()),
+                })
+                .collect(),
+            missing_env: Vec::new(),
+        }),
+        Err(McpWrapError::Other(msg))
+            if token.is_none()
+                && ["401", "Unauthorized", "Auth required"]
+                    .iter()
+                    .any(|m| msg.contains(m)) =>
+        {
+            Err(McpWrapError::MissingEnv(vec![REMOTE_MCP_TOKEN_KEY.to_string()]))
+        }
+        Err(e) => Err(e),
     }
 }
 
@@ -1,8 +1,14 @@
 use super::classify::ClassifiedTool;
-use super::{McpWrapError, PluginMetadata};
+use super::{
+    extract_python_package, parse_docker_run, McpRuntime, McpWrapError, PluginMetadata,
+    PythonPackage, WrapSpec,
+};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// File in the generated plugin directory recording the [`WrapSpec`].
+pub const WRAP_SPEC_FILE: &str = "nexus-wrap.json";
+
 /// The MCP bridge server source, embedded at compile time.
 const BRIDGE_SERVER_JS: &str = include_str!("../../../tools/mcp-bridge/src/server.js");
 
@@ -47,17 +53,54 @@ fn extract_npm_package(cmd: &str) -> Option<(String, String)> {
 ///
 /// Writes to `{output_dir}/{plugin_id}/`:
 /// - `plugin.json`  — headless manifest
-/// - `package.json` — bridge + MCP server dependencies
+/// - `nexus-wrap.json` — the [`WrapSpec`], for re-wrapping later
+/// - `package.json` — bridge (+ MCP server, for npm packages) dependencies
 /// - `src/server.js` — MCP bridge server
-/// - `Dockerfile`   — Node 20 Alpine
+/// - `Dockerfile`   — Node 20 Alpine, Node 20 slim + uv for Python servers,
+///   or the upstream image with the bridge layered on top for `docker run`
+///
+/// Remote endpoints (`https://…`) get only `plugin.json` and the spec: the
+/// manifest points `mcp.server.url` at the endpoint and nothing is built.
+///
+/// `server_command` is what the bridge spawns inside the container. It equals
+/// `spec.command` except for Docker images, where it is the image's own
+/// entrypoint (see [`super::docker_server_command`]).
+///
+/// `spec.include_tools` limits the plugin to a subset of `tools`: only those are
+/// written to `mcp.tools`, and the bridge hides the rest (`MCP_TOOL_ALLOWLIST`)
+/// so they can't be reached through the live tool list either.
 ///
 /// Returns the path to the generated plugin directory.
 pub fn generate_plugin(
     tools: &[ClassifiedTool],
     metadata: &PluginMetadata,
-    mcp_command: &str,
+    spec: &WrapSpec,
+    server_command: &str,
+    version: &str,
     output_dir: &Path,
 ) -> Result<PathBuf, McpWrapError> {
+    let mcp_command = spec.command.as_str();
+    let include_tools = spec.include_tools.as_deref();
+    let tools: Vec<&ClassifiedTool> = match include_tools {
+        Some(names) => {
+            if let Some(unknown) = names.iter().find(|n| !tools.iter().any(|t| &t.name == *n)) {
+                return Err(McpWrapError::Other(format!("Unknown tool: {}", unknown)));
+            }
+            tools.iter().filter(|t| names.contains(&t.name)).collect()
+        }
+        None => tools.iter().collect(),
+    };
+    if tools.is_empty() {
+        return Err(McpWrapError::Other("No tools selected".to_string()));
+    }
+
+    let runtime = McpRuntime::detect(mcp_command)?;
+    let docker_run = match runtime {
+        McpRuntime::Docker => Some(parse_docker_run(mcp_command).ok_or_else(|| {
+            McpWrapError::Other(format!("Could not determine Docker image from: {}", mcp_command))
+        })?),
+        _ => None,
+    };
     let plugin_dir = output_dir.join(&metadata.id);
 
     // If it already exists, remove and regenerate
@@ -65,8 +108,7 @@ pub fn generate_plugin(
         std::fs::remove_dir_all(&plugin_dir)?;
     }
 
-    let src_dir = plugin_dir.join("src");
-    std::fs::create_dir_all(&src_dir)?;
+    std::fs::create_dir_all(&plugin_dir)?;
 
     // Union of all tool permissions
     let all_permissions: Vec<String> = tools
@@ -76,11 +118,18 @@ pub fn generate_plugin(
         .into_iter()
         .collect();
 
+    let mut env = serde_json::Map::new();
+    env.insert("MCP_SERVER_COMMAND".into(), serde_json::json!(server_command));
+    if include_tools.is_some() {
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        env.insert("MCP_TOOL_ALLOWLIST".into(), serde_json::json!(names.join(",")));
+    }
+
     // plugin.json — headless manifest
-    let manifest = serde_json::json!({
+    let mut manifest = serde_json::json!({
         "id": metadata.id,
         "name": metadata.name,
-        "version": "0.1.0",
+        "version": version,
         "description": metadata.description,
         "author": metadata.author,
         "license": "MIT",
@@ -91,9 +140,7 @@ pub fn generate_plugin(
             "endpoint": "/health",
             "interval_secs": 30
         },
-        "env": {
-            "MCP_SERVER_COMMAND": mcp_command
-        },
+        "env": env,
         "mcp": {
             "tools": tools.iter().map(|t| serde_json::json!({
                 "name": t.name,
@@ -107,13 +154,33 @@ pub fn generate_plugin(
             }
         }
     });
+    if runtime == McpRuntime::Remote {
+        // No container: the gateway connects to the endpoint directly, and
+        // the manifest's tool list is what the gateway exposes
+        manifest["image"] = serde_json::json!("");
+        manifest["health"] = serde_json::Value::Null;
+        manifest["env"] = serde_json::json!({});
+        manifest["mcp"]["server"] = serde_json::json!({ "url": mcp_command.split_whitespace().next() });
+    }
     std::fs::write(
         plugin_dir.join("plugin.json"),
         serde_json::to_string_pretty(&manifest)? + "\n",
     )?;
 
+    std::fs::write(
+        plugin_dir.join(WRAP_SPEC_FILE),
+        serde_json::to_string_pretty(spec)? + "\n",
+    )?;
+
+    if runtime == McpRuntime::Remote {
+        return Ok(plugin_dir);
+    }
+
     // package.json
-    let npm_pkg = extract_npm_package(mcp_command);
+    let npm_pkg = match runtime {
+        McpRuntime::Node => extract_npm_package(mcp_command),
+        McpRuntime::Python | McpRuntime::Docker | McpRuntime::Remote => None,
+    };
     let mut deps = serde_json::Map::new();
     deps.insert(
         "@modelcontextprotocol/sdk".to_string(),
@@ -126,7 +193,7 @@ pub fn generate_plugin(
 
     let package_json = serde_json::json!({
         "name": metadata.id,
-        "version": "0.1.0",
+        "version": version,
         "description": metadata.description,
         "type": "module",
         "main": "src/server.js",
@@ -141,22 +208,181 @@ pub fn generate_plugin(
     )?;
 
     // src/server.js — embedded bridge
+    let src_dir = plugin_dir.join("src");
+    std::fs::create_dir_all(&src_dir)?;
     std::fs::write(src_dir.join("server.js"), BRIDGE_SERVER_JS)?;
 
     // Dockerfile
-    let dockerfile = "FROM node:20-alpine\n\
-        \n\
-        WORKDIR /app\n\
-        \n\
-        COPY package.json package-lock.json* ./\n\
-        RUN npm install --production\n\
-        \n\
-        COPY src/ ./src/\n\
-        \n\
-        EXPOSE 80\n\
-        \n\
-        CMD [\"node\", \"src/server.js\"]\n";
+    let dockerfile = match runtime {
+        McpRuntime::Node => NODE_DOCKERFILE.to_string(),
+        McpRuntime::Python => {
+            let pkg = extract_python_package(mcp_command).ok_or_else(|| {
+                McpWrapError::Other(format!("Could not determine Python package from: {}", mcp_command))
+            })?;
+            python_dockerfile(&pkg)
+        }
+        McpRuntime::Docker => {
+            std::fs::write(plugin_dir.join("start.sh"), DOCKER_BRIDGE_START)?;
+            docker_dockerfile(&docker_run.as_ref().expect("parsed above").image)
+        }
+        McpRuntime::Remote => unreachable!("remote plugins return before the build files"),
+    };
     std::fs::write(plugin_dir.join("Dockerfile"), dockerfile)?;
 
     Ok(plugin_dir)
 }
+
+/// Read the [`WrapSpec`] of a previously generated plugin directory.
+pub fn load_wrap_spec(plugin_dir: &Path) -> Option<WrapSpec> {
+    let data = std::fs::read_to_string(plugin_dir.join(WRAP_SPEC_FILE)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+const NODE_DOCKERFILE: &str = "FROM node:20-alpine\n\
+    \n\
+    WORKDIR /app\n\
+    \n\
+    COPY package.json package-lock.json* ./\n\
+    RUN npm install --production\n\
+    \n\
+    COPY src/ ./src/\n\
+    \n\
+    EXPOSE 80\n\
+    \n\
+    CMD [\"node\", \"src/server.js\"]\n";
+
+/// Dockerfile for Python MCP servers: the Node bridge plus uv, with the
+/// server pre-installed at build time so startup never waits on PyPI.
+///
+/// Installs use uv's default per-user locations and a virtualenv on `PATH`,
+/// so the spawned server resolves without any extra `UV_*` variables.
+fn python_dockerfile(pkg: &PythonPackage) -> String {
+    let install = match pkg {
+        // `uvx` reuses an installed tool instead of resolving it again
+        PythonPackage::UvTool(spec) => format!("RUN uv tool install \"{}\"\n", spec),
+        PythonPackage::Pip(spec) => format!(
+            "RUN uv venv /opt/venv && uv pip install --python /opt/venv/bin/python \"{}\"\n\
+             ENV PATH=\"/opt/venv/bin:$PATH\"\n",
+            spec
+        ),
+    };
+    format!(
+        "FROM node:20-slim\n\
+         \n\
+         COPY --from=ghcr.io/astral-sh/uv:latest /uv /uvx /bin/\n\
+         \n\
+         WORKDIR /app\n\
+         \n\
+         COPY package.json package-lock.json* ./\n\
+         RUN npm install --production\n\
+         \n\
+         {install}\
+         \n\
+         COPY src/ ./src/\n\
+         \n\
+         EXPOSE 80\n\
+         \n\
+         CMD [\"node\", \"src/server.js\"]\n",
+        install = install
+    )
+}
+
+/// Launcher for the bridge inside an upstream image. Prefers the image's own
+/// `node` (it links against the image's libc); falls back to the binary
+/// copied from the glibc-based bridge stage.
+const DOCKER_BRIDGE_START: &str = "#!/bin/sh\n\
+    NODE=\"$(command -v node || echo /opt/nexus-bridge/bin/node)\"\n\
+    exec \"$NODE\" /opt/nexus-bridge/app/src/server.js\n";
+
+/// Dockerfile for `docker run` MCP servers: the upstream image is the base,
+/// so the server runs exactly as published and only the bridge is added.
+///
+/// The image must provide `/bin/sh`; distroless and `scratch` images can't
+/// host the bridge and fail at build time.
+fn docker_dockerfile(image: &str) -> String {
+    format!(
+        "FROM node:20-slim AS bridge\n\
+         \n\
+         WORKDIR /bridge\n\
+         \n\
+         COPY package.json package-lock.json* ./\n\
+         RUN npm install --production\n\
+         \n\
+         COPY src/ ./src/\n\
+         \n\
+         FROM {image}\n\
+         \n\
+         COPY --from=bridge /usr/local/bin/node /opt/nexus-bridge/bin/node\n\
+         COPY --from=bridge /bridge /opt/nexus-bridge/app\n\
+         COPY start.sh /opt/nexus-bridge/start.sh\n\
+         \n\
+         EXPOSE 80\n\
+         \n\
+         ENTRYPOINT [\"/bin/sh\", \"/opt/nexus-bridge/start.sh\"]\n\
+         CMD []\n",
+        image = image
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, permission: &str) -> ClassifiedTool {
+        ClassifiedTool {
+            name: name.into(),
+            description: String::new(),
+            input_schema: serde_json::json!({ "type": "object" }),
+            permissions: vec![permission.into()],
+            requires_approval: false,
+            high_risk: false,
+        }
+    }
+
+    fn metadata() -> PluginMetadata {
+        PluginMetadata {
+            id: "mcp.files".into(),
+            name: "files (MCP)".into(),
+            description: String::new(),
+            author: "test".into(),
+        }
+    }
+
+    fn generate(include_tools: Option<&[&str]>) -> Result<serde_json::Value, McpWrapError> {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = WrapSpec {
+            command: "npx -y @example/files-mcp".into(),
+            include_tools: include_tools.map(|t| t.iter().map(|s| s.to_string()).collect()),
+        };
+        let tools = [tool("read", "filesystem:read"), tool("write", "filesystem:write")];
+        let plugin_dir = generate_plugin(&tools, &metadata(), &spec, &spec.command, "0.1.0", dir.path())?;
+        let manifest = std::fs::read_to_string(plugin_dir.join("plugin.json")).unwrap();
+        Ok(serde_json::from_str(&manifest).unwrap())
+    }
+
+    #[test]
+    fn include_tools_limits_manifest_and_bridge() {
+        let manifest = generate(Some(&["read"])).unwrap();
+        let tools = manifest["mcp"]["tools"].as_array().unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0]["name"], "read");
+        assert_eq!(manifest["permissions"], serde_json::json!(["filesystem:read"]));
+        assert_eq!(manifest["env"]["MCP_TOOL_ALLOWLIST"], "read");
+    }
+
+    #[test]
+    fn all_tools_are_exposed_without_a_subset() {
+        let manifest = generate(None).unwrap();
+        assert_eq!(manifest["mcp"]["tools"].as_array().unwrap().len(), 2);
+        assert!(manifest["env"].get("MCP_TOOL_ALLOWLIST").is_none());
+        assert_eq!(manifest["env"]["MCP_SERVER_COMMAND"], "npx -y @example/files-mcp");
+    }
+
+    #[test]
+    fn unknown_or_empty_subsets_are_rejected() {
+        let err = generate(Some(&["read", "delete"])).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown tool: delete");
+        let err = generate(Some(&[])).unwrap_err();
+        assert_eq!(err.to_string(), "No tools selected");
+    }
+}
@@ -13,6 +13,336 @@ pub struct PluginMetadata {
     pub author: String,
 }
 
+/// How a plugin was wrapped. Saved next to the generated manifest as
+/// [`generate::WRAP_SPEC_FILE`] so `mcp_rewrap` can repeat it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrapSpec {
+    /// The command the user entered (`npx -y pkg`, `docker run ... image`).
+    pub command: String,
+    /// Exposed subset of the server's tools; `None` exposes all of them.
+    #[serde(default)]
+    pub include_tools: Option<Vec<String>>,
+}
+
+impl WrapSpec {
+    /// Spec of a plugin wrapped before the spec file existed, from its
+    /// manifest's `MCP_SERVER_COMMAND`. That is the user's command for every
+    /// runtime except Docker, whose entrypoint replaced it.
+    pub fn legacy(server_command: &str) -> Option<Self> {
+        matches!(
+            McpRuntime::detect(server_command),
+            Ok(McpRuntime::Node | McpRuntime::Python)
+        )
+        .then(|| WrapSpec {
+            command: server_command.to_string(),
+            include_tools: None,
+        })
+    }
+
+    /// Command to re-discover tools with: Docker images are pulled again so
+    /// discovery sees the latest upstream.
+    pub fn discovery_command(&self) -> Result<String, McpWrapError> {
+        Ok(match McpRuntime::detect(&self.command)? {
+            McpRuntime::Docker => self.command.replacen("docker run", "docker run --pull=always", 1),
+            _ => self.command.clone(),
+        })
+    }
+
+    /// Drop tools from the subset that `tools` no longer has. A spec that
+    /// exposes everything keeps doing so.
+    pub fn retain_tools(self, tools: &[classify::ClassifiedTool]) -> Self {
+        WrapSpec {
+            include_tools: self.include_tools.map(|names| {
+                names
+                    .into_iter()
+                    .filter(|n| tools.iter().any(|t| &t.name == n))
+                    .collect()
+            }),
+            ..self
+        }
+    }
+}
+
+/// Version of a re-wrapped plugin: a patch bump, so the update is visible.
+/// Versions that aren't semver are kept.
+pub fn rewrap_version(version: &str) -> String {
+    semver::Version::parse(version)
+        .map(|mut v| {
+            v.patch += 1;
+            v.pre = semver::Prerelease::EMPTY;
+            v.to_string()
+        })
+        .unwrap_or_else(|_| version.to_string())
+}
+
+/// An environment variable a wrapped server appears to need.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpEnvVar {
+    pub name: String,
+    /// Looks like a credential; the UI masks it.
+    pub secret: bool,
+}
+
+impl McpEnvVar {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            secret: is_secret_env(name),
+        }
+    }
+}
+
+/// Result of `mcp_discover_tools`. When the server can't start without
+/// configuration, `tools` is empty and `required_env` says what to ask for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpDiscoveryResult {
+    pub tools: Vec<classify::ClassifiedTool>,
+    pub required_env: Vec<McpEnvVar>,
+}
+
+/// Runtime an MCP server command needs, which decides how it is discovered
+/// and what the generated wrapper image installs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum McpRuntime {
+    /// `npx` / `node` — npm packages.
+    Node,
+    /// `uvx` / `uv tool run` / `python -m` — PyPI packages.
+    Python,
+    /// `docker run ... image` — prebuilt stdio server images.
+    Docker,
+    /// `https://…` — a remote Streamable HTTP endpoint; nothing is built.
+    Remote,
+}
+
+impl McpRuntime {
+    pub fn detect(cmd: &str) -> Result<Self, McpWrapError> {
+        let mut parts = cmd.split_whitespace();
+        match parts.next() {
+            Some("npx" | "node") => Ok(McpRuntime::Node),
+            Some("uvx" | "python" | "python3") => Ok(McpRuntime::Python),
+            Some("uv") if parts.next() == Some("tool") && parts.next() == Some("run") => {
+                Ok(McpRuntime::Python)
+            }
+            Some("docker") if parts.next() == Some("run") => Ok(McpRuntime::Docker),
+            Some(url) if url.starts_with("https://") || url.starts_with("http://") => {
+                Ok(McpRuntime::Remote)
+            }
+            Some(other) => Err(McpWrapError::UnsupportedRuntime(other.to_string())),
+            None => Err(McpWrapError::Other("Empty command".to_string())),
+        }
+    }
+}
+
+/// How a Python MCP server command should be installed into the wrapper image.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PythonPackage {
+    /// `uvx pkg` — installed as a uv tool, so `uvx` resolves it offline.
+    UvTool(String),
+    /// `python -m module` — pip-installed into a virtualenv on `PATH`.
+    Pip(String),
+}
+
+/// Extract the PyPI requirement from a Python MCP server command.
+///
+/// "uvx mcp-server-fetch"                 → UvTool("mcp-server-fetch")
+/// "uvx mcp-server-time@2025.1.0"         → UvTool("mcp-server-time==2025.1.0")
+/// "uvx --from mcp-obsidian obsidian-mcp" → UvTool("mcp-obsidian")
+/// "python -m mcp_server_git"             → Pip("mcp-server-git")
+pub(crate) fn extract_python_package(cmd: &str) -> Option<PythonPackage> {
+    let parts: Vec<&str> = cmd.split_whitespace().collect();
+    let args: &[&str] = match parts.first()? {
+        &"uvx" => &parts[1..],
+        &"uv" if parts.get(1) == Some(&"tool") && parts.get(2) == Some(&"run") => &parts[3..],
+        &"python" | &"python3" => {
+            let pos = parts.iter().position(|p| *p == "-m")?;
+            // Module names use underscores; their distributions conventionally use dashes
+            let module = parts.get(pos + 1)?.split('.').next()?;
+            return Some(PythonPackage::Pip(module.replace('_', "-")));
+        }
+        _ => return None,
+    };
+
+    let mut iter = args.iter();
+    while let Some(part) = iter.next() {
+        match *part {
+            "--from" => return iter.next().map(|spec| PythonPackage::UvTool(normalize_uv_spec(spec))),
+            // Flags that take a value
+            "--python" | "-p" | "--with" | "--index" | "--index-url" => {
+                iter.next();
+            }
+            p if p.starts_with('-') => {}
+            spec => return Some(PythonPackage::UvTool(normalize_uv_spec(spec))),
+        }
+    }
+    None
+}
+
+/// `pkg@1.0` (uvx shorthand) → `pkg==1.0`; `pkg@latest` → `pkg`.
+fn normalize_uv_spec(spec: &str) -> String {
+    match spec.split_once('@') {
+        Some((name, "latest")) => name.to_string(),
+        Some((name, version)) => format!("{}=={}", name, version),
+        None => spec.to_string(),
+    }
+}
+
+/// Bare distribution name of a Python requirement (`pkg==1.0` → `pkg`).
+fn python_package_name(pkg: &PythonPackage) -> &str {
+    let spec = match pkg {
+        PythonPackage::UvTool(s) | PythonPackage::Pip(s) => s.as_str(),
+    };
+    spec.split(['=', '<', '>', '~', '!', '[']).next().unwrap_or(spec)
+}
+
+/// A parsed `docker run` command for an MCP server image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DockerRunCommand {
+    pub image: String,
+    /// `-e KEY=VALUE` pairs.
+    pub env: Vec<(String, String)>,
+    /// Bare `-e KEY` flags, which inherit the value from the host.
+    pub inherit_env: Vec<String>,
+    /// Arguments after the image name (replace the image's `CMD`).
+    pub args: Vec<String>,
+}
+
+/// `docker run` flags that consume the following token as their value.
+const DOCKER_VALUE_FLAGS: &[&str] = &[
+    "-e", "--env", "--env-file", "-v", "--volume", "--mount", "--name", "-p", "--publish",
+    "--network", "--net", "-w", "--workdir", "-u", "--user", "--entrypoint", "--platform",
+    "-l", "--label", "--add-host", "--memory", "-m", "--cpus",
+];
+
+/// Parse `docker run [flags] image [args...]`.
+pub(crate) fn parse_docker_run(cmd: &str) -> Option<DockerRunCommand> {
+    let parts: Vec<&str> = cmd.split_whitespace().collect();
+    if parts.first() != Some(&"docker") || parts.get(1) != Some(&"run") {
+        return None;
+    }
+    let mut env = Vec::new();
+    let mut inherit_env = Vec::new();
+    let mut iter = parts[2..].iter();
+    while let Some(part) = iter.next() {
+        let (flag, inline) = match part.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f, Some(v)),
+            _ => (*part, None),
+        };
+        if DOCKER_VALUE_FLAGS.contains(&flag) {
+            let value = match inline {
+                Some(v) => Some(v),
+                None => iter.next().copied(),
+            };
+            if flag == "-e" || flag == "--env" {
+                match value.map(|v| v.split_once('=').ok_or(v)) {
+                    Some(Ok((k, v))) => env.push((k.to_string(), v.to_string())),
+                    Some(Err(k)) => inherit_env.push(k.to_string()),
+                    None => {}
+                }
+            }
+        } else if !flag.starts_with('-') {
+            return Some(DockerRunCommand {
+                image: part.to_string(),
+                env,
+                inherit_env,
+                args: iter.map(|a| a.to_string()).collect(),
+            });
+        }
+    }
+    None
+}
+
+/// Resolve the command the wrapped image runs, from `docker image inspect`
+/// output: the image's `Entrypoint` followed by the user's args, or the
+/// image's `Cmd` when none were given (same precedence as `docker run`).
+pub fn docker_server_command(
+    run: &DockerRunCommand,
+    inspect: &serde_json::Value,
+) -> Result<String, McpWrapError> {
+    let strings = |key: &str| -> Vec<String> {
+        inspect
+            .pointer(&format!("/Config/{}", key))
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    };
+    let mut parts = strings("Entrypoint");
+    if run.args.is_empty() {
+        parts.extend(strings("Cmd"));
+    } else {
+        parts.extend(run.args.iter().cloned());
+    }
+    if parts.is_empty() {
+        return Err(McpWrapError::Other(format!(
+            "Image {} defines no entrypoint or command",
+            run.image
+        )));
+    }
+    Ok(parts.join(" "))
+}
+
+/// Name fragments that mark an env var as a credential.
+const SECRET_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "PASS", "PAT", "CREDENTIAL", "AUTH"];
+
+/// Suffixes that make an env var name worth prompting for wherever it appears.
+const CREDENTIAL_SUFFIXES: &[&str] = &["_API_KEY", "_TOKEN", "_SECRET", "_ACCESS_KEY", "_PASSWORD"];
+
+/// Words that, on the same line, mark every env-var-shaped token as required.
+const ENV_HINT_WORDS: &[&str] = &[
+    "environment", "env var", "not set", "missing", "required", "must be set", "must provide",
+];
+
+pub fn is_secret_env(name: &str) -> bool {
+    name.split('_').any(|part| {
+        let singular = part.strip_suffix('S').unwrap_or(part);
+        SECRET_MARKERS.contains(&part) || SECRET_MARKERS.contains(&singular)
+    })
+}
+
+/// Find env var names a server complained about on stderr, following the
+/// usual conventions ("GITHUB_TOKEN environment variable is required",
+/// "Missing BRAVE_API_KEY"). Returns names in first-seen order.
+pub(crate) fn detect_env_vars(output: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let lower = line.to_lowercase();
+        let hinted = ENV_HINT_WORDS.iter().any(|w| lower.contains(w));
+        let tokens = line
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .filter(|t| is_env_name(t));
+        for token in tokens {
+            let credential = CREDENTIAL_SUFFIXES.iter().any(|s| token.ends_with(s));
+            if (hinted || credential) && !found.iter().any(|f| f == token) {
+                found.push(token.to_string());
+            }
+        }
+    }
+    found
+}
+
+/// `SCREAMING_SNAKE` with at least one underscore, excluding Node error codes.
+fn is_env_name(token: &str) -> bool {
+    token.contains('_')
+        && !token.starts_with("ERR_")
+        && token.starts_with(|c: char| c.is_ascii_uppercase())
+        && !token.ends_with('_')
+        && token
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Service name from a remote endpoint's host:
+/// "https://mcp.linear.app/sse" → "linear", "https://api.example.com/mcp" → "example".
+fn remote_service_name(cmd: &str) -> Option<String> {
+    let rest = cmd
+        .strip_prefix("https://")
+        .or_else(|| cmd.strip_prefix("http://"))?;
+    let host = rest.split(['/', ':', '?']).next()?;
+    host.split('.')
+        .find(|label| !matches!(*label, "mcp" | "www" | "api"))
+        .filter(|label| !label.is_empty())
+        .map(String::from)
+}
+
 /// Extract an npm package name (without version tag) from an MCP server command.
 ///
 /// "npx -y @modelcontextprotocol/server-everything" → "@modelcontextprotocol/server-everything"
@@ -46,7 +376,18 @@ fn extract_npm_package(cmd: &str) -> Option<String> {
 
 /// Derive sensible default metadata from an MCP server command.
 pub fn suggest_metadata(mcp_command: &str) -> PluginMetadata {
-    let pkg = extract_npm_package(mcp_command);
+    let pkg = extract_npm_package(mcp_command)
+        .or_else(|| {
+            extract_python_package(mcp_command).map(|p| python_package_name(&p).to_string())
+        })
+        .or_else(|| {
+            // "mcp/fetch:latest" → "fetch", "ghcr.io/org/github-mcp-server" → "github-mcp-server"
+            parse_docker_run(mcp_command).map(|d| {
+                let repo = d.image.rsplit('/').next().unwrap_or(&d.image);
+                repo.split([':', '@']).next().unwrap_or(repo).to_string()
+            })
+        })
+        .or_else(|| remote_service_name(mcp_command));
 
     let (id, name) = match pkg {
         Some(ref p) => {
@@ -86,13 +427,16 @@ pub enum McpWrapError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("MCP server needs environment variables: {}", .0.join(", "))]
+    MissingEnv(Vec<String>),
+
     #[error("Timeout waiting for MCP server response")]
     Timeout,
 
     #[error("MCP server exited (code {0}) before returning tools")]
     ServerExited(i32),
 
-    #[error("Unsupported runtime: {0}. Only npx and node are supported.")]
+    #[error("Unsupported runtime: {0}. Supported: npx, node, uvx, uv tool run, python -m, docker run, http(s) URL.")]
     UnsupportedRuntime(String),
 
     #[error("{0}")]
@@ -125,6 +469,154 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_python_package() {
+        assert_eq!(
+            extract_python_package("uvx mcp-server-fetch"),
+            Some(PythonPackage::UvTool("mcp-server-fetch".to_string()))
+        );
+        assert_eq!(
+            extract_python_package("uvx mcp-server-time@2025.1.0 --local-timezone UTC"),
+            Some(PythonPackage::UvTool("mcp-server-time==2025.1.0".to_string()))
+        );
+        assert_eq!(
+            extract_python_package("uvx --python 3.12 --from mcp-obsidian obsidian-mcp"),
+            Some(PythonPackage::UvTool("mcp-obsidian".to_string()))
+        );
+        assert_eq!(
+            extract_python_package("python -m mcp_server_git --repository /repo"),
+            Some(PythonPackage::Pip("mcp-server-git".to_string()))
+        );
+        assert_eq!(extract_python_package("npx -y @scope/pkg"), None);
+    }
+
+    #[test]
+    fn test_parse_docker_run() {
+        let run = parse_docker_run(
+            "docker run -i --rm -e GITHUB_TOKEN=abc -e FROM_HOST --env=MODE=ro ghcr.io/github/github-mcp-server stdio --read-only",
+        )
+        .unwrap();
+        assert_eq!(run.image, "ghcr.io/github/github-mcp-server");
+        assert_eq!(
+            run.env,
+            vec![
+                ("GITHUB_TOKEN".to_string(), "abc".to_string()),
+                ("MODE".to_string(), "ro".to_string()),
+            ]
+        );
+        assert_eq!(run.inherit_env, vec!["FROM_HOST"]);
+        assert_eq!(run.args, vec!["stdio", "--read-only"]);
+
+        let run = parse_docker_run("docker run --rm -v /data:/data -i mcp/fetch").unwrap();
+        assert_eq!(run.image, "mcp/fetch");
+        assert!(run.args.is_empty());
+
+        assert!(parse_docker_run("docker run -i --rm").is_none());
+        assert!(parse_docker_run("npx -y pkg").is_none());
+    }
+
+    #[test]
+    fn test_detect_env_vars() {
+        let stderr = "Starting server...\n\
+            Error: GITHUB_PERSONAL_ACCESS_TOKEN environment variable is required\n\
+            at Object.<anonymous> (ERR_INVALID_ARG_TYPE)\n\
+            Using BRAVE_API_KEY from config\n\
+            LOG_LEVEL=info\n";
+        assert_eq!(
+            detect_env_vars(stderr),
+            vec!["GITHUB_PERSONAL_ACCESS_TOKEN", "BRAVE_API_KEY"]
+        );
+        assert_eq!(detect_env_vars("Missing env: DB_URL, DB_TOKEN"), vec!["DB_URL", "DB_TOKEN"]);
+        assert!(detect_env_vars("Server ready on stdio").is_empty());
+    }
+
+    #[test]
+    fn test_is_secret_env() {
+        assert!(is_secret_env("GITHUB_PERSONAL_ACCESS_TOKEN"));
+        assert!(is_secret_env("OPENAI_API_KEY"));
+        assert!(is_secret_env("DB_PASSWORD"));
+        assert!(is_secret_env("GITLAB_PAT"));
+        assert!(!is_secret_env("LOG_LEVEL"));
+        assert!(!is_secret_env("DB_URL"));
+        assert!(!is_secret_env("KEYBOARD_LAYOUT"));
+        assert!(is_secret_env("GOOGLE_CREDENTIALS"));
+    }
+
+    #[test]
+    fn test_docker_server_command() {
+        let inspect = serde_json::json!({
+            "Config": { "Entrypoint": ["/server/github-mcp-server"], "Cmd": ["stdio"] }
+        });
+        let run = parse_docker_run("docker run -i --rm ghcr.io/github/github-mcp-server").unwrap();
+        assert_eq!(
+            docker_server_command(&run, &inspect).unwrap(),
+            "/server/github-mcp-server stdio"
+        );
+
+        let run = parse_docker_run("docker run -i ghcr.io/github/github-mcp-server stdio --read-only")
+            .unwrap();
+        assert_eq!(
+            docker_server_command(&run, &inspect).unwrap(),
+            "/server/github-mcp-server stdio --read-only"
+        );
+
+        let empty = serde_json::json!({ "Config": { "Entrypoint": null, "Cmd": null } });
+        assert!(docker_server_command(&run, &empty).is_ok());
+        let run = parse_docker_run("docker run -i img").unwrap();
+        assert!(docker_server_command(&run, &empty).is_err());
+    }
+
+    #[test]
+    fn test_detect_runtime() {
+        assert_eq!(McpRuntime::detect("npx -y pkg").unwrap(), McpRuntime::Node);
+        assert_eq!(McpRuntime::detect("uvx mcp-server-fetch").unwrap(), McpRuntime::Python);
+        assert_eq!(McpRuntime::detect("uv tool run pkg").unwrap(), McpRuntime::Python);
+        assert_eq!(McpRuntime::detect("docker run -i --rm mcp/fetch").unwrap(), McpRuntime::Docker);
+        assert!(McpRuntime::detect("docker build .").is_err());
+        assert_eq!(McpRuntime::detect("https://mcp.example.com/mcp").unwrap(), McpRuntime::Remote);
+        assert!(McpRuntime::detect("ruby server.rb").is_err());
+    }
+
+    #[test]
+    fn test_wrap_spec_for_rewrap() {
+        let legacy = WrapSpec::legacy("npx -y @example/files-mcp").unwrap();
+        assert_eq!(legacy.command, "npx -y @example/files-mcp");
+        assert_eq!(legacy.include_tools, None);
+        assert!(WrapSpec::legacy("uvx mcp-server-fetch").is_some());
+        // A Docker plugin's manifest holds the entrypoint, not the user's command
+        assert!(WrapSpec::legacy("node /app/index.js").is_none());
+
+        let docker = WrapSpec {
+            command: "docker run -i --rm mcp/fetch".into(),
+            include_tools: None,
+        };
+        assert_eq!(docker.discovery_command().unwrap(), "docker run --pull=always -i --rm mcp/fetch");
+        assert_eq!(legacy.discovery_command().unwrap(), "npx -y @example/files-mcp");
+
+        let tool = |name: &str| classify::ClassifiedTool {
+            name: name.into(),
+            description: String::new(),
+            input_schema: serde_json::json!({}),
+            permissions: Vec::new(),
+            requires_approval: false,
+            high_risk: false,
+        };
+        let upstream = [tool("read"), tool("search")];
+        let subset = WrapSpec {
+            command: "npx -y pkg".into(),
+            include_tools: Some(vec!["read".into(), "write".into()]),
+        };
+        assert_eq!(subset.retain_tools(&upstream).include_tools, Some(vec!["read".to_string()]));
+        assert_eq!(legacy.retain_tools(&upstream).include_tools, None);
+    }
+
+    #[test]
+    fn test_rewrap_version() {
+        assert_eq!(rewrap_version("0.1.0"), "0.1.1");
+        assert_eq!(rewrap_version("1.2.3-beta.1"), "1.2.4");
+        assert_eq!(rewrap_version("latest"), "latest");
+    }
+
     #[test]
     fn test_suggest_metadata() {
         let meta = suggest_metadata("npx -y @upstash/context7-mcp");
@@ -139,5 +631,15 @@ mod tests {
         let meta3 = suggest_metadata("npx -y shadcn@latest");
         assert_eq!(meta3.id, "mcp.shadcn");
         assert_eq!(meta3.name, "shadcn (MCP)");
+
+        // Python packages are named after the distribution, not the runner
+        let meta4 = suggest_metadata("uvx mcp-server-fetch@2025.1.0");
+        assert_eq!(meta4.id, "mcp.mcp-server-fetch");
+
+        let meta5 = suggest_metadata("docker run -i --rm mcp/fetch:latest");
+        assert_eq!(meta5.id, "mcp.fetch");
+
+        let meta6 = suggest_metadata("https://mcp.linear.app/mcp");
+        assert_eq!(meta6.id, "mcp.linear");
     }
 }
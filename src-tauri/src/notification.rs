@@ -1,8 +1,34 @@
-//! Cross-platform native OS notifications via notify-rust.
+//! Cross-platform native OS notifications via notify-rust, and the history
+//! of everything Nexus notified about.
 //!
 //! - macOS: NSUserNotificationCenter (via mac-notification-sys)
 //! - Windows: WinRT toast notifications
 //! - Linux: freedesktop D-Bus notifications
+//!
+//! OS notifications are easy to miss while the app sits in the tray, so each
+//! one is also kept in a [`NotificationHistory`] with read/unread state.
+//!
+//! Notifications can carry [`NotificationAction`] buttons. Clicks are routed
+//! back into the same Tauri commands the UI uses, or to the webview as
+//! [`ACTION_EVENT`] when the action is about navigation.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+/// Emitted with the new entry whenever the history grows.
+pub const NOTIFICATION_EVENT: &str = "nexus://notification";
+
+/// Emitted with a [`NotificationAction`] the webview carries out.
+pub const ACTION_EVENT: &str = "nexus://notification-action";
+
+const HISTORY_FILE: &str = "notifications.json";
+
+/// Oldest entries are dropped beyond this.
+const MAX_HISTORY: usize = 500;
 
 /// Set the application identity for notifications. Call once at app startup.
 pub fn init() {
@@ -17,12 +43,309 @@ pub fn init() {
     }
 }
 
+/// A button on a notification.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NotificationAction {
+    RestartPlugin { plugin_id: String },
+    OpenLogs { plugin_id: String },
+}
+
+impl NotificationAction {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::RestartPlugin { .. } => "Restart plugin",
+            Self::OpenLogs { .. } => "Open logs",
+        }
+    }
+}
+
+/// Carry out a notification action, from the OS notification or the
+/// history.
+pub async fn run_action(app: &tauri::AppHandle, action: NotificationAction) -> Result<(), String> {
+    match action {
+        NotificationAction::RestartPlugin { plugin_id } => {
+            crate::commands::plugins::plugin_start(app.state(), app.state(), app.clone(), plugin_id).await
+        }
+        NotificationAction::OpenLogs { .. } => {
+            crate::show_window(app);
+            app.emit(ACTION_EVENT, &action).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Show the notification and, when it has actions, block until the user
+/// acts on it or dismisses it. Run it off the async runtime.
+fn show(app: &tauri::AppHandle, title: &str, body: &str, actions: &[NotificationAction]) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification.summary(title).body(body);
+    // Identifiers are indexes into `actions`
+    for (i, action) in actions.iter().enumerate() {
+        notification.action(&i.to_string(), action.label());
+    }
+    let handle = notification.show().map_err(|e| e.to_string())?;
+    if actions.is_empty() {
+        return Ok(());
+    }
+    handle.wait_for_action(|id| {
+        if id == "default" {
+            crate::show_window(app);
+            return;
+        }
+        let Some(action) = clicked_action(id, actions).cloned() else {
+            return;
+        };
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = run_action(&app, action).await {
+                log::warn!("Notification action failed: {}", e);
+            }
+        });
+    });
+    Ok(())
+}
+
+/// The action behind the button the OS reports as `id`, which is an index
+/// into `actions`. The body (`default`) and dismissals (`__closed`) have none.
+fn clicked_action<'a>(id: &str, actions: &'a [NotificationAction]) -> Option<&'a NotificationAction> {
+    id.parse::<usize>().ok().and_then(|i| actions.get(i))
+}
+
+/// Show an OS notification from the webview, and keep it in the history.
+#[tauri::command]
+pub fn send_notification(
+    app: tauri::AppHandle,
+    title: String,
+    body: String,
+    category: Option<String>,
+    actions: Option<Vec<NotificationAction>>,
+) {
+    notify_with_actions(
+        &app,
+        category.as_deref().unwrap_or("app"),
+        title,
+        body,
+        actions.unwrap_or_default(),
+    );
+}
+
+/// Show an OS notification and keep it in the history. `category` is
+/// dot-delimited, like the webview's in-app notifications
+/// (e.g. `plugin.crashed`, `updates.plugins`).
+pub fn notify(app: &tauri::AppHandle, category: &str, title: String, body: String) {
+    notify_with_actions(app, category, title, body, Vec::new());
+}
+
+/// [`notify`], with buttons.
+pub fn notify_with_actions(
+    app: &tauri::AppHandle,
+    category: &str,
+    title: String,
+    body: String,
+    actions: Vec<NotificationAction>,
+) {
+    record_with_actions(app, category, &title, &body, &actions);
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = show(&app, &title, &body, &actions) {
+            log::warn!("Failed to show notification '{}': {}", title, e);
+        }
+    });
+}
+
+/// Keep a notification in the history without showing it.
+pub fn record(app: &tauri::AppHandle, category: &str, title: &str, body: &str) {
+    record_with_actions(app, category, title, body, &[]);
+}
+
+fn record_with_actions(
+    app: &tauri::AppHandle,
+    category: &str,
+    title: &str,
+    body: &str,
+    actions: &[NotificationAction],
+) {
+    let Some(history) = app.try_state::<NotificationHistory>() else {
+        return;
+    };
+    let entry = history.push(category, title, body, actions);
+    let _ = app.emit(NOTIFICATION_EVENT, &entry);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredNotification {
+    pub id: String,
+    pub category: String,
+    pub title: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub read: bool,
+    /// Still offered from the history.
+    #[serde(default)]
+    pub actions: Vec<NotificationAction>,
+}
+
+/// Persisted notification history, newest last. Managed as Tauri state.
+pub struct NotificationHistory {
+    path: PathBuf,
+    entries: Mutex<Vec<StoredNotification>>,
+}
+
+impl NotificationHistory {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(HISTORY_FILE);
+        let entries = crate::util::read_json_store(&path).unwrap_or_default();
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    fn push(
+        &self,
+        category: &str,
+        title: &str,
+        body: &str,
+        actions: &[NotificationAction],
+    ) -> StoredNotification {
+        let entry = StoredNotification {
+            id: uuid::Uuid::new_v4().to_string(),
+            category: category.to_string(),
+            title: title.to_string(),
+            body: body.to_string(),
+            created_at: Utc::now(),
+            read: false,
+            actions: actions.to_vec(),
+        };
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.push(entry.clone());
+        let excess = entries.len().saturating_sub(MAX_HISTORY);
+        entries.drain(..excess);
+        self.save(&entries);
+        entry
+    }
+
+    /// Newest first.
+    pub fn list(&self) -> Vec<StoredNotification> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().rev().cloned().collect()
+    }
+
+    /// Mark `ids` read, or everything when `ids` is None.
+    pub fn mark_read(&self, ids: Option<&[String]>) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        for entry in entries.iter_mut() {
+            if ids.map_or(true, |ids| ids.contains(&entry.id)) {
+                entry.read = true;
+            }
+        }
+        self.save(&entries);
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.clear();
+        self.save(&entries);
+    }
+
+    fn save(&self, entries: &[StoredNotification]) {
+        let result = serde_json::to_string_pretty(entries)
+            .map_err(|e| e.to_string())
+            .and_then(|data| {
+                crate::util::atomic_write_with_backup(&self.path, data.as_bytes()).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            log::warn!("Failed to save notification history: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn notifications_list(history: tauri::State<'_, NotificationHistory>) -> Vec<StoredNotification> {
+    history.list()
+}
+
+#[tauri::command]
+pub fn notifications_mark_read(
+    history: tauri::State<'_, NotificationHistory>,
+    ids: Option<Vec<String>>,
+) {
+    history.mark_read(ids.as_deref());
+}
+
+#[tauri::command]
+pub fn notifications_clear(history: tauri::State<'_, NotificationHistory>) {
+    history.clear();
+}
+
 #[tauri::command]
-pub fn send_notification(title: String, body: String) -> Result<(), String> {
-    notify_rust::Notification::new()
-        .summary(&title)
-        .body(&body)
-        .show()
-        .map(|_| ())
-        .map_err(|e| e.to_string())
+pub async fn notification_action(app: tauri::AppHandle, action: NotificationAction) -> Result<(), String> {
+    run_action(&app, action).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_persists_read_state_and_caps_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = NotificationHistory::load(dir.path());
+        for i in 0..MAX_HISTORY + 5 {
+            history.push("plugin.crashed", &format!("Crash {}", i), "", &[]);
+        }
+        let newest = history.list()[0].clone();
+        assert_eq!(newest.title, format!("Crash {}", MAX_HISTORY + 4));
+        history.mark_read(Some(std::slice::from_ref(&newest.id)));
+
+        let reloaded = NotificationHistory::load(dir.path());
+        let entries = reloaded.list();
+        assert_eq!(entries.len(), MAX_HISTORY);
+        assert_eq!(entries.last().unwrap().title, "Crash 5");
+        assert!(entries[0].read);
+        assert!(!entries[1].read);
+
+        reloaded.mark_read(None);
+        assert!(reloaded.list().iter().all(|n| n.read));
+        reloaded.clear();
+        assert!(NotificationHistory::load(dir.path()).list().is_empty());
+    }
+
+    #[test]
+    fn actions_are_kept_in_history_and_matched_to_buttons() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = NotificationHistory::load(dir.path());
+        let actions = [
+            NotificationAction::RestartPlugin { plugin_id: "com.example.a".into() },
+            NotificationAction::OpenLogs { plugin_id: "com.example.a".into() },
+        ];
+        history.push("plugin.crashed", "Crashed", "", &actions);
+        history.push("app", "No buttons", "", &[]);
+
+        let entries = NotificationHistory::load(dir.path()).list();
+        assert!(entries[0].actions.is_empty());
+        assert_eq!(entries[1].actions, actions);
+
+        assert_eq!(clicked_action("1", &actions), Some(&actions[1]));
+        assert_eq!(clicked_action("2", &actions), None);
+        assert_eq!(clicked_action("default", &actions), None);
+        assert_eq!(clicked_action("__closed", &actions), None);
+    }
+
+    #[test]
+    fn actions_match_the_webview_shape() {
+        let action = NotificationAction::OpenLogs { plugin_id: "com.example.a".into() };
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({ "kind": "open_logs", "plugin_id": "com.example.a" })
+        );
+        // Entries saved before actions existed still load
+        let old: StoredNotification = serde_json::from_value(serde_json::json!({
+            "id": "1", "category": "app", "title": "t", "body": "b",
+            "created_at": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert!(old.actions.is_empty() && !old.read);
+    }
 }
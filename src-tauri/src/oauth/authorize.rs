@@ -109,11 +109,7 @@ pub async fn authorize(
         ));
     }
 
-    let scopes: Vec<String> = if params.scope.is_empty() {
-        vec!["mcp".to_string()]
-    } else {
-        params.scope.split_whitespace().map(String::from).collect()
-    };
+    let scopes = super::store::parse_scope(&params.scope);
 
     // Pre-approved clients skip consent — immediate redirect
     if store.is_client_approved(&params.client_id) {
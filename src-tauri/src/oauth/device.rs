@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::extract::Extension;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::ActiveTheme;
+
+use super::store::{format_user_code, OAuthStore, DEVICE_CODE_TTL, DEVICE_POLL_INTERVAL_SECS};
+use super::token::oauth_error;
+use super::types::{DeviceAuthorizationRequest, DeviceAuthorizationResponse};
+
+/// RFC 8628 §3.1 — Device Authorization Endpoint.
+///
+/// `POST /oauth/device_authorization` (application/x-www-form-urlencoded)
+///
+/// For headless clients that can't open a browser on this machine. The
+/// client shows `user_code`, the user enters it in the Nexus desktop UI, and
+/// the client polls `/oauth/token` with the device code grant meanwhile.
+pub async fn device_authorization(
+    Extension(store): Extension<Arc<OAuthStore>>,
+    audit: Option<Extension<AuditWriter>>,
+    Form(req): Form<DeviceAuthorizationRequest>,
+) -> Response {
+    let scopes = super::store::parse_scope(&req.scope);
+
+    let (device_code, user_code) =
+        match store.create_device_authorization(&req.client_id, scopes, req.resource) {
+            Ok(codes) => codes,
+            Err(e) => {
+                log::warn!("OAuth device authorization failed: client={} error={}", req.client_id, e);
+                return oauth_error(StatusCode::BAD_REQUEST, e, "Device authorization failed");
+            }
+        };
+
+    log::info!("OAuth device authorization started: client={}", req.client_id);
+    if let Some(Extension(audit)) = audit {
+        audit.record(AuditEntry {
+            actor: AuditActor::McpClient,
+            source_id: Some(req.client_id.clone()),
+            severity: AuditSeverity::Info,
+            action: "security.oauth.device_authorization".into(),
+            subject: None,
+            result: AuditResult::Success,
+            details: None,
+        });
+    }
+
+    Json(DeviceAuthorizationResponse {
+        device_code,
+        user_code: format_user_code(&user_code),
+        verification_uri: "http://127.0.0.1:9600/oauth/device".into(),
+        expires_in: DEVICE_CODE_TTL.as_secs(),
+        interval: DEVICE_POLL_INTERVAL_SECS,
+    })
+    .into_response()
+}
+
+/// `GET /oauth/device` — the `verification_uri`. Codes are entered in the
+/// desktop app, not here, so this page only tells the user where to go.
+pub async fn verification_page(Extension(theme): Extension<ActiveTheme>) -> Response {
+    let accent = if theme.get() == "nebula" { "#8b8bf5" } else { "#2DD4A8" };
+    let html = format!(include_str!("device.html"), accent = accent);
+    ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html).into_response()
+}
@@ -0,0 +1,340 @@
+//! JWT access tokens (RFC 9068 profile) signed with Ed25519.
+//!
+//! Opaque tokens can only be checked by asking the `OAuthStore`. JWTs carry
+//! their own claims and signature, so a resource server that fetched
+//! `/oauth/jwks` can validate them offline. Nexus itself still looks every
+//! token up in the store, which keeps revocation instant for its own routes.
+//!
+//! Keys rotate every [`KEY_ROTATION_DAYS`]. Retired keys stay in the JWKS
+//! until every token they signed has expired, then they are pruned.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier};
+use serde::{Deserialize, Serialize};
+
+use crate::permissions::rar::AuthorizationDetail;
+use crate::secrets::StoreCipher;
+
+/// `iss` claim; matches the `issuer` in the authorization server metadata.
+pub const ISSUER: &str = "http://127.0.0.1:9600";
+
+/// Audience used when the client didn't request a specific resource.
+const DEFAULT_AUDIENCE: &str = "http://127.0.0.1:9600/mcp";
+
+const KEY_ROTATION_DAYS: i64 = 30;
+
+/// How long a retired key stays published. Must cover the longest access
+/// token lifetime (24 hours for public clients).
+const RETIRED_KEY_GRACE_SECS: i64 = 24 * 60 * 60;
+
+/// Access token claims (RFC 9068 §2.2).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub client_id: String,
+    pub scope: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub jti: String,
+    /// RFC 9396 authorization details, when the token carries any.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorization_details: Vec<AuthorizationDetail>,
+    /// Nexus plugin the token was issued to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nexus_plugin_id: Option<String>,
+}
+
+impl AccessTokenClaims {
+    pub fn new(
+        client_id: &str,
+        scopes: &[String],
+        resource: &str,
+        expires_at: DateTime<Utc>,
+        plugin_id: Option<String>,
+        authorization_details: Vec<AuthorizationDetail>,
+    ) -> Self {
+        Self {
+            iss: ISSUER.to_string(),
+            sub: client_id.to_string(),
+            aud: if resource.is_empty() {
+                DEFAULT_AUDIENCE.to_string()
+            } else {
+                resource.to_string()
+            },
+            client_id: client_id.to_string(),
+            scope: scopes.join(" "),
+            iat: Utc::now().timestamp(),
+            exp: expires_at.timestamp(),
+            jti: uuid::Uuid::new_v4().to_string(),
+            authorization_details,
+            nexus_plugin_id: plugin_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeyEntry {
+    kid: String,
+    /// Ed25519 secret key, base64url.
+    secret: String,
+    created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retired_at: Option<DateTime<Utc>>,
+}
+
+impl KeyEntry {
+    fn generate() -> Self {
+        let secret: [u8; 32] = rand::random();
+        Self {
+            kid: uuid::Uuid::new_v4().simple().to_string(),
+            secret: URL_SAFE_NO_PAD.encode(secret),
+            created_at: Utc::now(),
+            retired_at: None,
+        }
+    }
+
+    fn signing_key(&self) -> Option<SigningKey> {
+        let bytes: [u8; 32] = URL_SAFE_NO_PAD.decode(&self.secret).ok()?.try_into().ok()?;
+        Some(SigningKey::from_bytes(&bytes))
+    }
+}
+
+/// The signing key ring, persisted encrypted to `oauth_jwt_keys.json`
+/// (mode 0600).
+pub struct JwtKeys {
+    path: PathBuf,
+    keys: Mutex<Vec<KeyEntry>>,
+    cipher: Arc<StoreCipher>,
+}
+
+impl JwtKeys {
+    pub fn load(data_dir: &Path) -> Self {
+        Self::load_with(data_dir, Arc::new(StoreCipher::load(data_dir)))
+    }
+
+    /// [`JwtKeys::load`], sharing the cipher of the store that owns the ring.
+    pub fn load_with(data_dir: &Path, cipher: Arc<StoreCipher>) -> Self {
+        let path = data_dir.join("oauth_jwt_keys.json");
+        let keys: Vec<KeyEntry> = cipher
+            .read(&path)
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            keys: Mutex::new(keys),
+            cipher,
+        }
+    }
+
+    /// Sign `claims` with the current key, rotating first if it is due.
+    pub fn sign(&self, claims: &AccessTokenClaims) -> String {
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        let due = keys
+            .iter()
+            .find(|k| k.retired_at.is_none())
+            .map_or(true, |k| Utc::now() - k.created_at > chrono::Duration::days(KEY_ROTATION_DAYS));
+        if due {
+            rotate_locked(&mut keys);
+            self.save(&keys);
+        }
+        let current = keys
+            .iter()
+            .find(|k| k.retired_at.is_none())
+            .expect("rotation leaves an active key");
+        let signing_key = current.signing_key().expect("generated keys are valid");
+
+        let header = serde_json::json!({ "alg": "EdDSA", "typ": "at+jwt", "kid": current.kid });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(serde_json::to_string(claims).unwrap_or_default()),
+        );
+        let signature = signing_key.sign(signing_input.as_bytes());
+        format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+    }
+
+    /// Verify signature, key id, and expiry. Returns the claims if valid.
+    pub fn verify(&self, token: &str) -> Option<AccessTokenClaims> {
+        let mut parts = token.split('.');
+        let (header_b64, claims_b64, sig_b64) = (parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let header: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header_b64).ok()?).ok()?;
+        if header.get("alg")?.as_str()? != "EdDSA" {
+            return None;
+        }
+        let kid = header.get("kid")?.as_str()?;
+        let verifying_key = {
+            let keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+            keys.iter().find(|k| k.kid == kid)?.signing_key()?.verifying_key()
+        };
+
+        let sig_bytes: [u8; 64] = URL_SAFE_NO_PAD.decode(sig_b64).ok()?.try_into().ok()?;
+        let signing_input = &token[..header_b64.len() + 1 + claims_b64.len()];
+        verifying_key
+            .verify(signing_input.as_bytes(), &Signature::from_bytes(&sig_bytes))
+            .ok()?;
+
+        let claims: AccessTokenClaims =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims_b64).ok()?).ok()?;
+        (claims.exp > Utc::now().timestamp() && claims.iss == ISSUER).then_some(claims)
+    }
+
+    /// Retire the current key and start signing with a new one. Tokens
+    /// signed with the old key stay valid until they expire.
+    pub fn rotate(&self) -> String {
+        let mut keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        let kid = rotate_locked(&mut keys);
+        self.save(&keys);
+        kid
+    }
+
+    /// RFC 7517 JWK Set with the public half of every published key.
+    pub fn jwks(&self) -> serde_json::Value {
+        let keys = self.keys.lock().unwrap_or_else(|e| e.into_inner());
+        let jwks: Vec<serde_json::Value> = keys
+            .iter()
+            .filter_map(|k| {
+                let public = k.signing_key()?.verifying_key();
+                Some(serde_json::json!({
+                    "kty": "OKP",
+                    "crv": "Ed25519",
+                    "use": "sig",
+                    "alg": "EdDSA",
+                    "kid": k.kid,
+                    "x": URL_SAFE_NO_PAD.encode(public.as_bytes()),
+                }))
+            })
+            .collect();
+        serde_json::json!({ "keys": jwks })
+    }
+
+    fn save(&self, keys: &[KeyEntry]) {
+        let json = serde_json::to_string_pretty(keys).unwrap_or_default();
+        if let Err(e) = self.cipher.write(&self.path, json.as_bytes()) {
+            log::error!("Failed to save JWT signing keys: {}", e);
+            return;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Err(e) = std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600)) {
+                log::warn!("Failed to restrict JWT key file permissions: {}", e);
+            }
+        }
+    }
+}
+
+/// Retire the active key, prune keys past their grace period, add a new
+/// active key. Returns the new key id.
+fn rotate_locked(keys: &mut Vec<KeyEntry>) -> String {
+    let now = Utc::now();
+    for key in keys.iter_mut().filter(|k| k.retired_at.is_none()) {
+        key.retired_at = Some(now);
+    }
+    keys.retain(|k| {
+        k.retired_at
+            .map_or(true, |at| now - at < chrono::Duration::seconds(RETIRED_KEY_GRACE_SECS))
+    });
+    let key = KeyEntry::generate();
+    let kid = key.kid.clone();
+    keys.insert(0, key);
+    log::info!("JWT signing key rotated: kid={}", kid);
+    kid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn claims(exp_offset_secs: i64) -> AccessTokenClaims {
+        AccessTokenClaims::new(
+            "client-1",
+            &["mcp".to_string()],
+            "",
+            Utc::now() + chrono::Duration::seconds(exp_offset_secs),
+            None,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let keys = JwtKeys::load(dir.path());
+        let c = claims(3600);
+        let token = keys.sign(&c);
+        assert_eq!(token.split('.').count(), 3);
+        assert_eq!(keys.verify(&token), Some(c));
+    }
+
+    #[test]
+    fn expired_token_rejected() {
+        let dir = TempDir::new().unwrap();
+        let keys = JwtKeys::load(dir.path());
+        let token = keys.sign(&claims(-1));
+        assert!(keys.verify(&token).is_none());
+    }
+
+    #[test]
+    fn tampered_token_rejected() {
+        let dir = TempDir::new().unwrap();
+        let keys = JwtKeys::load(dir.path());
+        let token = keys.sign(&claims(3600));
+        let mut forged = claims(3600);
+        forged.scope = "mcp admin".into();
+        let parts: Vec<&str> = token.split('.').collect();
+        let forged_token = format!(
+            "{}.{}.{}",
+            parts[0],
+            URL_SAFE_NO_PAD.encode(serde_json::to_string(&forged).unwrap()),
+            parts[2]
+        );
+        assert!(keys.verify(&forged_token).is_none());
+    }
+
+    #[test]
+    fn rotation_keeps_old_tokens_valid() {
+        let dir = TempDir::new().unwrap();
+        let keys = JwtKeys::load(dir.path());
+        let old = keys.sign(&claims(3600));
+        keys.rotate();
+        let new = keys.sign(&claims(3600));
+
+        assert!(keys.verify(&old).is_some());
+        assert!(keys.verify(&new).is_some());
+        assert_eq!(keys.jwks()["keys"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn keys_persist_across_reload() {
+        let dir = TempDir::new().unwrap();
+        let token = JwtKeys::load(dir.path()).sign(&claims(3600));
+        let reloaded = JwtKeys::load(dir.path());
+        assert!(reloaded.verify(&token).is_some());
+    }
+
+    #[test]
+    fn jwks_exposes_only_public_keys() {
+        let dir = TempDir::new().unwrap();
+        let keys = JwtKeys::load(dir.path());
+        keys.sign(&claims(3600));
+        let jwks = keys.jwks();
+        let key = &jwks["keys"][0];
+        assert_eq!(key["kty"], "OKP");
+        assert_eq!(key["crv"], "Ed25519");
+        assert!(key.get("d").is_none());
+        assert!(!jwks.to_string().contains(&keys.keys.lock().unwrap()[0].secret));
+    }
+}
@@ -14,9 +14,14 @@
 //! (RFC 9728 §2) is included in authorization requests to bind the token
 //! to this specific resource.
 
+use std::sync::Arc;
+
+use axum::extract::Extension;
 use axum::Json;
 use serde_json::{json, Value};
 
+use super::store::{OAuthStore, SUPPORTED_SCOPES};
+
 /// RFC 9728 §3 — Protected Resource Metadata.
 ///
 /// Tells MCP clients where to find the authorization server and what
@@ -41,7 +46,7 @@ pub async fn protected_resource() -> Json<Value> {
         "resource": "http://127.0.0.1:9600/mcp",
         "authorization_servers": ["http://127.0.0.1:9600"],
         "bearer_methods_supported": ["header"],
-        "scopes_supported": ["mcp"],
+        "scopes_supported": SUPPORTED_SCOPES,
         "authorization_details_types_supported": crate::permissions::rar::SUPPORTED_DETAIL_TYPES,
         "resource_name": "Nexus MCP Server",
         "resource_documentation": "https://github.com/imdanibytes/nexus"
@@ -59,9 +64,15 @@ pub async fn protected_resource() -> Json<Value> {
 /// - `authorization_endpoint` — for authorization code flow (RFC 6749 §3.1)
 /// - `token_endpoint` — token exchange (RFC 6749 §3.2)
 /// - `registration_endpoint` — dynamic client registration (RFC 7591)
+/// - `device_authorization_endpoint` — device code flow (RFC 8628 §4) for
+///   headless clients; the user enters the code in the Nexus desktop UI
+/// - `jwks_uri` — public keys for JWT access tokens (RFC 7517), for clients
+///   registered with `access_token_format: "jwt"`
+/// - `revocation_endpoint` — token revocation (RFC 7009), authenticated the
+///   same way as the token endpoint
 /// - `response_types_supported` — only `code` (authorization code grant)
-/// - `grant_types_supported` — auth code, refresh, and client_credentials
-///   (for plugin machine-to-machine auth)
+/// - `grant_types_supported` — auth code, refresh, client_credentials
+///   (for plugin machine-to-machine auth), and device_code
 /// - `code_challenge_methods_supported` — only S256 (RFC 7636); `plain` is
 ///   intentionally excluded (PKCE downgrade prevention)
 /// - `token_endpoint_auth_methods_supported` — `none` for public clients
@@ -77,12 +88,32 @@ pub async fn authorization_server() -> Json<Value> {
         "authorization_endpoint": "http://127.0.0.1:9600/oauth/authorize",
         "token_endpoint": "http://127.0.0.1:9600/oauth/token",
         "registration_endpoint": "http://127.0.0.1:9600/oauth/register",
+        "device_authorization_endpoint": "http://127.0.0.1:9600/oauth/device_authorization",
+        "jwks_uri": "http://127.0.0.1:9600/oauth/jwks",
+        "revocation_endpoint": "http://127.0.0.1:9600/oauth/revoke",
+        "revocation_endpoint_auth_methods_supported": ["none", "client_secret_post"],
         "response_types_supported": ["code"],
-        "grant_types_supported": ["authorization_code", "refresh_token", "client_credentials"],
+        "grant_types_supported": [
+            "authorization_code",
+            "refresh_token",
+            "client_credentials",
+            super::types::DEVICE_CODE_GRANT
+        ],
         "code_challenge_methods_supported": ["S256"],
         "token_endpoint_auth_methods_supported": ["none", "client_secret_post"],
-        "scopes_supported": ["mcp"],
+        "scopes_supported": SUPPORTED_SCOPES,
         "authorization_details_types_supported": crate::permissions::rar::SUPPORTED_DETAIL_TYPES,
         "service_documentation": "https://github.com/imdanibytes/nexus"
     }))
 }
+
+/// RFC 7517 — JSON Web Key Set.
+///
+/// Public Ed25519 keys (`OKP`) that sign JWT access tokens, including
+/// recently retired keys whose tokens may still be unexpired. Resource
+/// servers match the token's `kid` header against this set.
+///
+/// `GET /oauth/jwks`
+pub async fn jwks(Extension(store): Extension<Arc<OAuthStore>>) -> Json<Value> {
+    Json(store.jwks())
+}
@@ -4,15 +4,21 @@
 //! - RFC 9728 Protected Resource Metadata
 //! - RFC 8414 Authorization Server Metadata
 //! - RFC 7591 Dynamic Client Registration
+//! - RFC 7009 Token Revocation
+//! - RFC 8628 Device Authorization Grant
+//! - RFC 9068 JWT access tokens (opt-in per client) + JWKS
 //! - Authorization Code + PKCE flow
 //!
 //! Generic OAuth infrastructure — not MCP-specific. The MCP gateway is the
 //! first consumer; plugins can migrate to client_credentials in the future.
 
 pub mod authorize;
+pub mod device;
+pub mod jwt;
 pub mod metadata;
 pub mod plugin_auth;
 pub mod registration;
+pub mod revocation;
 pub mod store;
 pub mod token;
 pub mod types;
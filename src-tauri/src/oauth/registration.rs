@@ -6,7 +6,7 @@ use axum::response::IntoResponse;
 use axum::Json;
 
 use super::store::OAuthStore;
-use super::types::{RegistrationRequest, RegistrationResponse};
+use super::types::{RegistrationRequest, RegistrationResponse, DEVICE_CODE_GRANT};
 
 /// RFC 7591 — Dynamic Client Registration.
 ///
@@ -14,6 +14,8 @@ use super::types::{RegistrationRequest, RegistrationResponse};
 ///
 /// MCP clients call this to register themselves before starting the
 /// authorization flow. Public clients only (no client_secret issued).
+/// Clients registering only for the device code grant (RFC 8628) need no
+/// redirect URIs.
 pub async fn register_client(
     Extension(store): Extension<Arc<OAuthStore>>,
     Json(req): Json<RegistrationRequest>,
@@ -21,7 +23,9 @@ pub async fn register_client(
     if req.client_name.is_empty() {
         return Err(StatusCode::BAD_REQUEST);
     }
-    if req.redirect_uris.is_empty() {
+    let device_only = req.grant_types.iter().any(|g| g == DEVICE_CODE_GRANT)
+        && !req.grant_types.iter().any(|g| g == "authorization_code");
+    if req.redirect_uris.is_empty() && !device_only {
         return Err(StatusCode::BAD_REQUEST);
     }
 
@@ -44,6 +48,7 @@ pub async fn register_client(
         redirect_uris: client.redirect_uris,
         grant_types: client.grant_types,
         token_endpoint_auth_method: client.token_endpoint_auth_method,
+        access_token_format: client.access_token_format,
     };
 
     Ok((StatusCode::CREATED, Json(response)))
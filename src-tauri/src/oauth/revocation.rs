@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::extract::Extension;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Form;
+
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+
+use super::store::OAuthStore;
+use super::token::oauth_error;
+use super::types::RevocationRequest;
+
+/// RFC 7009 — Token Revocation.
+///
+/// `POST /oauth/revoke` (application/x-www-form-urlencoded)
+///
+/// Accepts access and refresh tokens. Public clients identify themselves with
+/// `client_id`; plugin clients also send `client_secret`. Unknown or foreign
+/// tokens still get `200 OK` (§2.2), so the response never reveals whether a
+/// token exists.
+pub async fn revoke_token(
+    Extension(store): Extension<Arc<OAuthStore>>,
+    audit: Option<Extension<AuditWriter>>,
+    Form(req): Form<RevocationRequest>,
+) -> Response {
+    let Some(client_id) = req.client_id.as_deref() else {
+        return oauth_error(StatusCode::UNAUTHORIZED, "invalid_client", "Missing 'client_id'");
+    };
+
+    let result = store.revoke_token(
+        &req.token,
+        req.token_type_hint.as_deref(),
+        client_id,
+        req.client_secret.as_deref(),
+    );
+
+    if let Some(Extension(audit)) = audit {
+        let (outcome, details) = match &result {
+            Ok(kind) => (AuditResult::Success, serde_json::json!({ "revoked": kind })),
+            Err(e) => (AuditResult::Failure, serde_json::json!({ "error": e })),
+        };
+        audit.record(AuditEntry {
+            actor: AuditActor::McpClient,
+            source_id: Some(client_id.to_string()),
+            severity: AuditSeverity::Warn,
+            action: "security.oauth.revoke".into(),
+            subject: req.token_type_hint.clone(),
+            result: outcome,
+            details: Some(details),
+        });
+    }
+
+    match result {
+        Ok(Some(kind)) => {
+            log::info!("OAuth token revoked: client={} type={}", client_id, kind);
+            StatusCode::OK.into_response()
+        }
+        Ok(None) => StatusCode::OK.into_response(),
+        Err(e) => {
+            log::warn!("OAuth revocation failed: client={} error={}", client_id, e);
+            oauth_error(StatusCode::UNAUTHORIZED, e, "Client authentication failed")
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -9,13 +9,25 @@ use chrono::Utc;
 use sha2::{Digest, Sha256};
 use subtle::ConstantTimeEq;
 
+use super::jwt::{AccessTokenClaims, JwtKeys};
 use super::types::*;
-use crate::permissions::rar::AuthorizationDetail;
+use crate::permissions::rar::{self, AuthorizationDetail};
+use crate::secrets::StoreCipher;
+
+/// Scopes Nexus understands. `mcp` grants access to the MCP gateway.
+pub const SUPPORTED_SCOPES: &[&str] = &["mcp"];
 
 const AUTH_CODE_TTL: Duration = Duration::from_secs(10 * 60); // 10 minutes
 const PLUGIN_ACCESS_TOKEN_SECS: i64 = 60 * 60; // 1 hour
 const PUBLIC_ACCESS_TOKEN_SECS: i64 = 24 * 60 * 60; // 24 hours
 const REFRESH_TOKEN_DAYS: i64 = 30;
+pub(crate) const DEVICE_CODE_TTL: Duration = Duration::from_secs(10 * 60); // 10 minutes
+pub(crate) const DEVICE_POLL_INTERVAL_SECS: u64 = 5;
+
+/// RFC 8628 §6.1 user code alphabet: consonants only, so codes are easy to
+/// type and can't spell words. 20^8 ≈ 2^34.6 combinations.
+const USER_CODE_CHARS: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+const USER_CODE_LEN: usize = 8;
 
 // ---------------------------------------------------------------------------
 // OAuthStore — the single source of truth for all OAuth state
@@ -25,47 +37,63 @@ pub struct OAuthStore {
     data_dir: PathBuf,
     clients: Mutex<HashMap<String, OAuthClient>>,
     auth_codes: Mutex<HashMap<String, AuthorizationCode>>,
+    /// RFC 8628 device authorizations, keyed by device_code.
+    device_codes: Mutex<HashMap<String, DeviceAuthorization>>,
     access_tokens: Mutex<HashMap<String, AccessToken>>,
     refresh_tokens: Mutex<HashMap<String, RefreshToken>>,
     /// Pre-computed RFC 9396 authorization_details for plugin clients.
     /// Keyed by client_id. Updated by PluginManager on lifecycle events.
     plugin_auth_details: Mutex<HashMap<String, Vec<AuthorizationDetail>>>,
+    /// Signing keys for clients that receive JWT access tokens.
+    jwt_keys: JwtKeys,
+    /// Encrypts the persisted clients, tokens, and signing keys.
+    cipher: Arc<StoreCipher>,
 }
 
 impl OAuthStore {
     /// Load persisted clients, access tokens, and refresh tokens from disk,
-    /// or create empty store. Expired tokens are pruned on load.
+    /// or create empty store. Expired tokens are pruned on load. Files
+    /// written before stores were encrypted are encrypted in place.
     pub fn load(data_dir: &Path) -> Self {
         let clients_path = data_dir.join("oauth_clients.json");
         let access_path = data_dir.join("oauth_access.json");
         let refresh_path = data_dir.join("oauth_refresh.json");
+        let cipher = Arc::new(StoreCipher::load(data_dir));
 
         let clients: HashMap<String, OAuthClient> =
-            std::fs::read_to_string(&clients_path)
-                .ok()
+            cipher
+                .read(&clients_path)
                 .and_then(|s| serde_json::from_str(&s).ok())
                 .unwrap_or_default();
 
         let now = Utc::now();
 
         let access_tokens: HashMap<String, AccessToken> =
-            std::fs::read_to_string(&access_path)
-                .ok()
+            cipher
+                .read(&access_path)
                 .and_then(|s| serde_json::from_str(&s).ok())
                 .unwrap_or_default();
         let access_tokens: HashMap<String, AccessToken> = access_tokens
             .into_iter()
             .filter(|(_, t)| t.expires_at > now)
+            .map(|(k, mut t)| {
+                migrate_legacy_scopes(&mut t.scopes);
+                (k, t)
+            })
             .collect();
 
         let refresh_tokens: HashMap<String, RefreshToken> =
-            std::fs::read_to_string(&refresh_path)
-                .ok()
+            cipher
+                .read(&refresh_path)
                 .and_then(|s| serde_json::from_str(&s).ok())
                 .unwrap_or_default();
         let refresh_tokens: HashMap<String, RefreshToken> = refresh_tokens
             .into_iter()
             .filter(|(_, t)| t.expires_at > now)
+            .map(|(k, mut t)| {
+                migrate_legacy_scopes(&mut t.scopes);
+                (k, t)
+            })
             .collect();
 
         log::info!(
@@ -79,9 +107,12 @@ impl OAuthStore {
             data_dir: data_dir.to_path_buf(),
             clients: Mutex::new(clients),
             auth_codes: Mutex::new(HashMap::new()),
+            device_codes: Mutex::new(HashMap::new()),
             access_tokens: Mutex::new(access_tokens),
             refresh_tokens: Mutex::new(refresh_tokens),
             plugin_auth_details: Mutex::new(HashMap::new()),
+            jwt_keys: JwtKeys::load_with(data_dir, cipher.clone()),
+            cipher,
         }
     }
 
@@ -118,6 +149,7 @@ impl OAuthStore {
             approved: false,
             client_secret_hash: None,
             plugin_id: None,
+            access_token_format: req.access_token_format,
         };
 
         clients.insert(client.client_id.clone(), client.clone());
@@ -271,6 +303,157 @@ impl OAuthStore {
         Ok((access, refresh))
     }
 
+    // ── Device Authorization (RFC 8628) ──────────────────────────
+
+    /// Start a device authorization for a registered public client.
+    /// Returns `(device_code, user_code)`; the user approves it in the
+    /// desktop UI by entering the user code.
+    pub fn create_device_authorization(
+        &self,
+        client_id: &str,
+        scopes: Vec<String>,
+        resource: String,
+    ) -> Result<(String, String), &'static str> {
+        {
+            let clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+            let client = clients.get(client_id).ok_or("invalid_client")?;
+            // Confidential (plugin) clients use client_credentials instead
+            if client.client_secret_hash.is_some() {
+                return Err("unauthorized_client");
+            }
+        }
+
+        let mut codes = self.device_codes.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        codes.retain(|_, d| now < d.expires_at);
+
+        let user_code = loop {
+            let candidate = generate_user_code();
+            if !codes.values().any(|d| d.user_code == candidate) {
+                break candidate;
+            }
+        };
+        let device_code = uuid::Uuid::new_v4().to_string();
+        codes.insert(
+            device_code.clone(),
+            DeviceAuthorization {
+                device_code: device_code.clone(),
+                user_code: user_code.clone(),
+                client_id: client_id.to_string(),
+                scopes,
+                resource,
+                expires_at: now + DEVICE_CODE_TTL,
+                interval: DEVICE_POLL_INTERVAL_SECS,
+                last_poll: None,
+                status: DeviceStatus::Pending,
+            },
+        );
+        Ok((device_code, user_code))
+    }
+
+    /// Look up a pending device authorization by the code the user typed.
+    /// Separators and case are ignored.
+    pub fn find_device_authorization(&self, user_code: &str) -> Option<DeviceAuthorizationInfo> {
+        let user_code = normalize_user_code(user_code);
+        let codes = self.device_codes.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let device = codes.values().find(|d| {
+            d.user_code == user_code && d.status == DeviceStatus::Pending && now < d.expires_at
+        })?;
+        let client_name = self
+            .get_client(&device.client_id)
+            .map(|c| c.client_name)
+            .unwrap_or_default();
+        Some(DeviceAuthorizationInfo {
+            user_code: format_user_code(&device.user_code),
+            client_id: device.client_id.clone(),
+            client_name,
+            scopes: device.scopes.clone(),
+            expires_in: (device.expires_at - now).as_secs(),
+        })
+    }
+
+    /// Approve or deny a pending device authorization. Approval also marks
+    /// the client approved, like "Allow" on the consent page.
+    pub fn resolve_device_authorization(
+        &self,
+        user_code: &str,
+        approve: bool,
+    ) -> Result<DeviceAuthorizationInfo, &'static str> {
+        let info = self.find_device_authorization(user_code).ok_or("invalid_user_code")?;
+        {
+            let normalized = normalize_user_code(user_code);
+            let mut codes = self.device_codes.lock().unwrap_or_else(|e| e.into_inner());
+            let device = codes
+                .values_mut()
+                .find(|d| d.user_code == normalized && d.status == DeviceStatus::Pending)
+                .ok_or("invalid_user_code")?;
+            device.status = if approve {
+                DeviceStatus::Approved
+            } else {
+                DeviceStatus::Denied
+            };
+        }
+        if approve {
+            self.approve_client(&info.client_id);
+        }
+        Ok(info)
+    }
+
+    /// Token request for the device code grant (RFC 8628 §3.4–3.5).
+    ///
+    /// Errors are the §3.5 codes: `authorization_pending` until the user
+    /// decides, `slow_down` when polled faster than the interval,
+    /// `access_denied`, and `expired_token`. The code is single-use.
+    pub fn exchange_device_code(
+        &self,
+        device_code: &str,
+        client_id: &str,
+    ) -> Result<(AccessToken, RefreshToken), &'static str> {
+        let mut codes = self.device_codes.lock().unwrap_or_else(|e| e.into_inner());
+        let device = codes.get_mut(device_code).ok_or("invalid_grant")?;
+        if device.client_id != client_id {
+            return Err("invalid_grant");
+        }
+        let now = Instant::now();
+        if now >= device.expires_at {
+            codes.remove(device_code);
+            return Err("expired_token");
+        }
+        match device.status {
+            DeviceStatus::Denied => {
+                codes.remove(device_code);
+                return Err("access_denied");
+            }
+            DeviceStatus::Pending => {
+                let too_fast = device
+                    .last_poll
+                    .is_some_and(|last| now < last + Duration::from_secs(device.interval));
+                device.last_poll = Some(now);
+                if too_fast {
+                    device.interval += 5;
+                    return Err("slow_down");
+                }
+                return Err("authorization_pending");
+            }
+            DeviceStatus::Approved => {}
+        }
+
+        let device = codes.remove(device_code).ok_or("invalid_grant")?;
+        drop(codes);
+
+        let client_name = self
+            .get_client(client_id)
+            .map(|c| c.client_name)
+            .unwrap_or_default();
+        self.revoke_client_refresh_tokens(client_id);
+        let access = self.create_access_token(
+            client_id.to_string(), client_name, device.scopes.clone(), device.resource.clone(), None, vec![],
+        );
+        let refresh = self.create_refresh_token(client_id.to_string(), device.scopes, device.resource, None, vec![]);
+        Ok((access, refresh))
+    }
+
     // ── Access Tokens ────────────────────────────────────────────
 
     pub(crate) fn create_access_token(
@@ -287,13 +470,29 @@ impl OAuthStore {
         } else {
             PUBLIC_ACCESS_TOKEN_SECS
         };
+        let expires_at = Utc::now() + chrono::Duration::seconds(ttl_secs);
+        let format = self
+            .get_client(&client_id)
+            .map(|c| c.access_token_format)
+            .unwrap_or_default();
+        let token_string = match format {
+            AccessTokenFormat::Opaque => uuid::Uuid::new_v4().to_string(),
+            AccessTokenFormat::Jwt => self.jwt_keys.sign(&AccessTokenClaims::new(
+                &client_id,
+                &scopes,
+                &resource,
+                expires_at,
+                plugin_id.clone(),
+                authorization_details.clone(),
+            )),
+        };
         let token = AccessToken {
-            token: uuid::Uuid::new_v4().to_string(),
+            token: token_string,
             client_id,
             client_name,
             scopes,
             resource,
-            expires_at: Utc::now() + chrono::Duration::seconds(ttl_secs),
+            expires_at,
             plugin_id,
             authorization_details,
         };
@@ -322,6 +521,29 @@ impl OAuthStore {
         })
     }
 
+    /// Public JWK Set for validating JWT access tokens.
+    pub fn jwks(&self) -> serde_json::Value {
+        self.jwt_keys.jwks()
+    }
+
+    /// Rotate the JWT signing key. Returns the new key id.
+    pub fn rotate_jwt_key(&self) -> String {
+        self.jwt_keys.rotate()
+    }
+
+    /// Switch a client between opaque and JWT access tokens. Applies to
+    /// tokens issued from now on. Returns false if the client doesn't exist.
+    pub fn set_access_token_format(&self, client_id: &str, format: AccessTokenFormat) -> bool {
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(client) = clients.get_mut(client_id) else {
+            return false;
+        };
+        client.access_token_format = format;
+        drop(clients);
+        self.save_clients();
+        true
+    }
+
     // ── Refresh Tokens ───────────────────────────────────────────
 
     fn create_refresh_token(
@@ -411,6 +633,81 @@ impl OAuthStore {
         self.save_refresh_tokens();
     }
 
+    /// RFC 7009 token revocation.
+    ///
+    /// Authenticates the client (confidential clients must present their
+    /// secret), then revokes `token` if it was issued to that client.
+    /// `token_type_hint` only changes the lookup order. Revoking a refresh
+    /// token also revokes the client's access tokens, since a client holds a
+    /// single grant (stale refresh tokens are dropped on re-authorization).
+    ///
+    /// Returns the kind of token revoked, or `None` for unknown tokens and
+    /// tokens belonging to another client — per §2.2 both are not errors.
+    pub fn revoke_token(
+        &self,
+        token: &str,
+        token_type_hint: Option<&str>,
+        client_id: &str,
+        client_secret: Option<&str>,
+    ) -> Result<Option<&'static str>, &'static str> {
+        {
+            let clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+            let client = clients.get(client_id).ok_or("invalid_client")?;
+            if let Some(expected_hash) = client.client_secret_hash.as_deref() {
+                let computed = hash_client_secret(client_secret.ok_or("invalid_client")?);
+                if computed.as_bytes().ct_eq(expected_hash.as_bytes()).unwrap_u8() != 1 {
+                    return Err("invalid_client");
+                }
+            }
+        }
+
+        let order = if token_type_hint == Some("refresh_token") {
+            ["refresh_token", "access_token"]
+        } else {
+            ["access_token", "refresh_token"]
+        };
+        for kind in order {
+            let revoked = match kind {
+                "access_token" => self.revoke_access_token(token, client_id),
+                _ => self.revoke_refresh_token(token, client_id),
+            };
+            if revoked {
+                return Ok(Some(kind));
+            }
+        }
+        Ok(None)
+    }
+
+    fn revoke_access_token(&self, token: &str, client_id: &str) -> bool {
+        let mut tokens = self.access_tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if tokens.get(token).map(|t| t.client_id.as_str()) != Some(client_id) {
+            return false;
+        }
+        let removed = tokens.remove(token);
+        drop(tokens);
+        if removed.is_some_and(|t| t.plugin_id.is_none()) {
+            self.save_access_tokens();
+        }
+        true
+    }
+
+    fn revoke_refresh_token(&self, token: &str, client_id: &str) -> bool {
+        {
+            let mut tokens = self.refresh_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            if tokens.get(token).map(|t| t.client_id.as_str()) != Some(client_id) {
+                return false;
+            }
+            tokens.remove(token);
+        }
+        {
+            let mut tokens = self.access_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            tokens.retain(|_, t| t.client_id != client_id);
+        }
+        self.save_access_tokens();
+        self.save_refresh_tokens();
+        true
+    }
+
     // ── Plugin Client Management ──────────────────────────────────
 
     /// Register a plugin as an OAuth confidential client.
@@ -443,6 +740,7 @@ impl OAuthStore {
             approved: true,
             client_secret_hash: Some(hash_client_secret(&secret)),
             plugin_id: Some(plugin_id.to_string()),
+            access_token_format: AccessTokenFormat::Opaque,
         };
 
         clients.insert(client.client_id.clone(), client.clone());
@@ -564,6 +862,106 @@ impl OAuthStore {
         clients.values().find(|c| c.plugin_id.as_deref() == Some(plugin_id)).cloned()
     }
 
+    // ── Per-client grants ────────────────────────────────────────
+
+    /// What a client can currently do: the scopes on its live tokens and its
+    /// authorization details. For plugins the details are the pre-computed
+    /// set from `set_plugin_auth_details`; for other clients, the union of
+    /// what their tokens carry.
+    pub fn client_grants(&self, client_id: &str) -> Option<OAuthClientGrants> {
+        let client = self.get_client(client_id)?;
+        let now = Utc::now();
+        let mut scopes: Vec<String> = Vec::new();
+        let mut details: Vec<AuthorizationDetail> = Vec::new();
+        let mut collect = |token_scopes: &[String], token_details: &[AuthorizationDetail]| {
+            for s in token_scopes {
+                if !scopes.contains(s) {
+                    scopes.push(s.clone());
+                }
+            }
+            for d in token_details {
+                if !details.contains(d) {
+                    details.push(d.clone());
+                }
+            }
+        };
+        {
+            let tokens = self.access_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            for t in tokens.values().filter(|t| t.client_id == client_id && t.expires_at > now) {
+                collect(&t.scopes, &t.authorization_details);
+            }
+        }
+        {
+            let tokens = self.refresh_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            for t in tokens.values().filter(|t| t.client_id == client_id && t.expires_at > now) {
+                collect(&t.scopes, &t.authorization_details);
+            }
+        }
+        if client.plugin_id.is_some() {
+            details = self.get_plugin_auth_details(client_id);
+        }
+
+        Some(OAuthClientGrants {
+            client_id: client.client_id,
+            client_name: client.client_name,
+            plugin_id: client.plugin_id,
+            scopes,
+            authorization_details: details,
+        })
+    }
+
+    /// Narrow what a client's tokens grant, in place. Only removal is
+    /// allowed: `scopes` must be a subset of the current scopes and every
+    /// entry of `details` must fit within a current detail. Each token keeps
+    /// the intersection of its own grant and the new one, so nothing is
+    /// widened even for tokens that had less to begin with.
+    pub fn restrict_client_grants(
+        &self,
+        client_id: &str,
+        scopes: &[String],
+        details: &[AuthorizationDetail],
+    ) -> Result<OAuthClientGrants, &'static str> {
+        let current = self.client_grants(client_id).ok_or("invalid_client")?;
+        if !scopes.iter().all(|s| current.scopes.contains(s)) {
+            return Err("invalid_scope");
+        }
+        if !rar::details_within(details, &current.authorization_details) {
+            return Err("invalid_authorization_details");
+        }
+
+        let narrow = |token_scopes: &mut Vec<String>, token_details: &mut Vec<AuthorizationDetail>| {
+            token_scopes.retain(|s| scopes.contains(s));
+            *token_details = details
+                .iter()
+                .filter(|d| rar::details_within(std::slice::from_ref(*d), token_details))
+                .cloned()
+                .collect();
+        };
+        {
+            let mut tokens = self.access_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            for t in tokens.values_mut().filter(|t| t.client_id == client_id) {
+                narrow(&mut t.scopes, &mut t.authorization_details);
+            }
+        }
+        {
+            let mut tokens = self.refresh_tokens.lock().unwrap_or_else(|e| e.into_inner());
+            for t in tokens.values_mut().filter(|t| t.client_id == client_id) {
+                narrow(&mut t.scopes, &mut t.authorization_details);
+            }
+        }
+        if current.plugin_id.is_some() {
+            self.set_plugin_auth_details(client_id, details.to_vec());
+        }
+        self.save_access_tokens();
+        self.save_refresh_tokens();
+
+        Ok(OAuthClientGrants {
+            scopes: scopes.to_vec(),
+            authorization_details: details.to_vec(),
+            ..current
+        })
+    }
+
     // ── Test Helpers ─────────────────────────────────────────────
 
     /// Force an auth code to expire (test-only).
@@ -590,7 +988,7 @@ impl OAuthStore {
         let clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
         let json = serde_json::to_string_pretty(&*clients).unwrap_or_default();
         let path = self.data_dir.join("oauth_clients.json");
-        if let Err(e) = crate::util::atomic_write(&path, json.as_bytes()) {
+        if let Err(e) = self.cipher.write(&path, json.as_bytes()) {
             log::error!("Failed to save OAuth clients: {}", e);
         }
     }
@@ -605,7 +1003,7 @@ impl OAuthStore {
             .collect();
         let json = serde_json::to_string_pretty(&public_tokens).unwrap_or_default();
         let path = self.data_dir.join("oauth_access.json");
-        if let Err(e) = crate::util::atomic_write(&path, json.as_bytes()) {
+        if let Err(e) = self.cipher.write(&path, json.as_bytes()) {
             log::error!("Failed to save OAuth access tokens: {}", e);
         }
     }
@@ -614,7 +1012,7 @@ impl OAuthStore {
         let tokens = self.refresh_tokens.lock().unwrap_or_else(|e| e.into_inner());
         let json = serde_json::to_string_pretty(&*tokens).unwrap_or_default();
         let path = self.data_dir.join("oauth_refresh.json");
-        match crate::util::atomic_write(&path, json.as_bytes()) {
+        match self.cipher.write(&path, json.as_bytes()) {
             Ok(()) => true,
             Err(e) => {
                 log::warn!("Failed to save OAuth refresh tokens: {}", e);
@@ -642,6 +1040,54 @@ fn verify_pkce(code_verifier: &str, code_challenge: &str) -> bool {
     computed.as_bytes().ct_eq(code_challenge.as_bytes()).unwrap_u8() == 1
 }
 
+/// Parse a space-delimited `scope` parameter, keeping only supported
+/// scopes. Empty or entirely unsupported requests get the default `mcp`.
+pub(crate) fn parse_scope(scope: &str) -> Vec<String> {
+    let scopes: Vec<String> = scope
+        .split_whitespace()
+        .filter(|s| SUPPORTED_SCOPES.contains(s))
+        .map(String::from)
+        .collect();
+    if scopes.is_empty() {
+        vec!["mcp".to_string()]
+    } else {
+        scopes
+    }
+}
+
+/// Tokens issued before scopes were filtered may carry arbitrary client
+/// requested values. Treat those like a fresh request; an empty list means
+/// the user removed every scope and stays empty.
+fn migrate_legacy_scopes(scopes: &mut Vec<String>) {
+    if !scopes.is_empty() && !scopes.iter().any(|s| SUPPORTED_SCOPES.contains(&s.as_str())) {
+        *scopes = vec!["mcp".to_string()];
+    }
+}
+
+/// Generate a random RFC 8628 user code (unformatted, see `format_user_code`).
+fn generate_user_code() -> String {
+    use rand::Rng;
+    let mut rng = rand::rng();
+    (0..USER_CODE_LEN)
+        .map(|_| USER_CODE_CHARS[rng.random_range(0..USER_CODE_CHARS.len())] as char)
+        .collect()
+}
+
+/// Uppercase and drop separators so `bcdf-ghjk`, `BCDF GHJK` and `BCDFGHJK`
+/// all match (RFC 8628 §6.1).
+pub(crate) fn normalize_user_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Display form of a user code: `BCDF-GHJK`.
+pub(crate) fn format_user_code(code: &str) -> String {
+    let (head, tail) = code.split_at(code.len() / 2);
+    format!("{}-{}", head, tail)
+}
+
 /// Normalize localhost variants in redirect URIs using proper URL parsing.
 /// Handles: bare `http://localhost`, ports, paths, trailing slashes, fragments.
 /// RFC 6749 §3.1.2: redirect URI must not include a fragment — stripped here.
@@ -726,6 +1172,7 @@ mod tests {
             redirect_uris: vec!["http://127.0.0.1:3000/callback".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         })
     }
 
@@ -769,6 +1216,7 @@ mod tests {
             redirect_uris: vec!["http://127.0.0.1:9999/callback".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
 
         assert_eq!(client.client_id, client2.client_id);
@@ -832,6 +1280,7 @@ mod tests {
             ],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
         let found = store.get_client(&client.client_id).unwrap();
         assert_eq!(found.redirect_uris.len(), 2);
@@ -988,6 +1437,7 @@ mod tests {
             redirect_uris: vec!["http://localhost:3000/callback".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
         let (verifier, challenge) = pkce_pair("localhost-normalization-test-verifier-at-least-43-chars");
         let code = store.create_authorization_code(
@@ -1222,6 +1672,337 @@ mod tests {
         assert!(store.validate_access_token(&access_b.token).is_some());
     }
 
+    #[test]
+    fn jwt_client_gets_verifiable_jwt() {
+        let (store, _dir) = test_store();
+        let client = register_test_client(&store, "JWT Client");
+        assert!(store.set_access_token_format(&client.client_id, AccessTokenFormat::Jwt));
+        let (verifier, challenge) = pkce_pair("verifier-for-jwt-access-token-test-at-least-43-chars");
+        let code = create_code(&store, &client.client_id, &challenge);
+        let (access, _) = store
+            .exchange_code(&code, verifier, &client.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+
+        let claims = store.jwt_keys.verify(&access.token).expect("signed JWT");
+        assert_eq!(claims.client_id, client.client_id);
+        assert_eq!(claims.exp, access.expires_at.timestamp());
+        assert!(store.validate_access_token(&access.token).is_some());
+
+        // Revocation still applies to Nexus's own validation
+        store.revoke_token(&access.token, None, &client.client_id, None).unwrap();
+        assert!(store.validate_access_token(&access.token).is_none());
+    }
+
+    #[test]
+    fn opaque_is_default_token_format() {
+        let (store, _dir) = test_store();
+        let client = register_test_client(&store, "Opaque Client");
+        assert_eq!(client.access_token_format, AccessTokenFormat::Opaque);
+        let (verifier, challenge) = pkce_pair("verifier-for-opaque-access-token-test-at-least-43-chars");
+        let code = create_code(&store, &client.client_id, &challenge);
+        let (access, _) = store
+            .exchange_code(&code, verifier, &client.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+        assert!(store.jwt_keys.verify(&access.token).is_none());
+        assert!(!store.set_access_token_format("missing", AccessTokenFormat::Jwt));
+    }
+
+    // =====================================================================
+    // Device Authorization (RFC 8628)
+    // =====================================================================
+
+    fn device_client(store: &OAuthStore) -> OAuthClient {
+        store.register_client(RegistrationRequest {
+            client_name: "Headless CLI".into(),
+            redirect_uris: vec![],
+            grant_types: vec![DEVICE_CODE_GRANT.into()],
+            token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
+        })
+    }
+
+    #[test]
+    fn device_flow_pending_then_approved() {
+        let (store, _dir) = test_store();
+        let client = device_client(&store);
+        let (device_code, user_code) = store
+            .create_device_authorization(&client.client_id, vec!["mcp".into()], String::new())
+            .unwrap();
+
+        assert_eq!(
+            store.exchange_device_code(&device_code, &client.client_id).unwrap_err(),
+            "authorization_pending"
+        );
+
+        // User types the code in lowercase with a separator
+        let typed = format_user_code(&user_code).to_lowercase();
+        let info = store.find_device_authorization(&typed).unwrap();
+        assert_eq!(info.client_name, "Headless CLI");
+        store.resolve_device_authorization(&typed, true).unwrap();
+        assert!(store.is_client_approved(&client.client_id));
+        assert!(store.find_device_authorization(&typed).is_none());
+
+        let (access, refresh) = store.exchange_device_code(&device_code, &client.client_id).unwrap();
+        assert!(store.validate_access_token(&access.token).is_some());
+        assert_eq!(access.scopes, vec!["mcp"]);
+        assert!(store.refresh(&refresh.token, &client.client_id).is_ok());
+
+        // Single use
+        assert_eq!(
+            store.exchange_device_code(&device_code, &client.client_id).unwrap_err(),
+            "invalid_grant"
+        );
+    }
+
+    #[test]
+    fn device_flow_denied() {
+        let (store, _dir) = test_store();
+        let client = device_client(&store);
+        let (device_code, user_code) = store
+            .create_device_authorization(&client.client_id, vec!["mcp".into()], String::new())
+            .unwrap();
+
+        store.resolve_device_authorization(&user_code, false).unwrap();
+        assert_eq!(
+            store.exchange_device_code(&device_code, &client.client_id).unwrap_err(),
+            "access_denied"
+        );
+        assert!(!store.is_client_approved(&client.client_id));
+    }
+
+    #[test]
+    fn device_flow_polling_too_fast_slows_down() {
+        let (store, _dir) = test_store();
+        let client = device_client(&store);
+        let (device_code, _) = store
+            .create_device_authorization(&client.client_id, vec!["mcp".into()], String::new())
+            .unwrap();
+
+        assert_eq!(
+            store.exchange_device_code(&device_code, &client.client_id).unwrap_err(),
+            "authorization_pending"
+        );
+        assert_eq!(
+            store.exchange_device_code(&device_code, &client.client_id).unwrap_err(),
+            "slow_down"
+        );
+    }
+
+    #[test]
+    fn device_flow_wrong_client_and_unknown_codes() {
+        let (store, _dir) = test_store();
+        let client = device_client(&store);
+        let other = register_test_client(&store, "Other");
+        let (device_code, _) = store
+            .create_device_authorization(&client.client_id, vec!["mcp".into()], String::new())
+            .unwrap();
+
+        assert_eq!(
+            store.exchange_device_code(&device_code, &other.client_id).unwrap_err(),
+            "invalid_grant"
+        );
+        assert!(store.find_device_authorization("BBBB-BBBB").is_none());
+        assert!(store.resolve_device_authorization("BBBB-BBBB", true).is_err());
+        assert_eq!(
+            store.create_device_authorization("unknown", vec![], String::new()).unwrap_err(),
+            "invalid_client"
+        );
+    }
+
+    #[test]
+    fn device_flow_rejects_plugin_clients() {
+        let (store, _dir) = test_store();
+        let (client, _) = store.register_plugin_client("com.test.plugin", "Test Plugin");
+        assert_eq!(
+            store.create_device_authorization(&client.client_id, vec![], String::new()).unwrap_err(),
+            "unauthorized_client"
+        );
+    }
+
+    #[test]
+    fn user_code_format() {
+        let code = generate_user_code();
+        assert_eq!(code.len(), USER_CODE_LEN);
+        assert!(code.bytes().all(|b| USER_CODE_CHARS.contains(&b)));
+        assert_eq!(format_user_code("BCDFGHJK"), "BCDF-GHJK");
+        assert_eq!(normalize_user_code(" bcdf-ghjk "), "BCDFGHJK");
+    }
+
+    #[test]
+    fn revoke_token_access_token_only() {
+        let (store, _dir) = test_store();
+        let client = register_test_client(&store, "Logout");
+        let (verifier, challenge) = pkce_pair("verifier-for-access-token-revocation-at-least-43-chars");
+        let code = create_code(&store, &client.client_id, &challenge);
+        let (access, refresh) = store
+            .exchange_code(&code, verifier, &client.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+
+        let revoked = store.revoke_token(&access.token, None, &client.client_id, None);
+        assert_eq!(revoked, Ok(Some("access_token")));
+        assert!(store.validate_access_token(&access.token).is_none());
+        // Refresh token still usable
+        assert!(store.refresh(&refresh.unwrap().token, &client.client_id).is_ok());
+    }
+
+    #[test]
+    fn revoke_token_refresh_token_kills_access_tokens() {
+        let (store, _dir) = test_store();
+        let client = register_test_client(&store, "Logout Refresh");
+        let (verifier, challenge) = pkce_pair("verifier-for-refresh-token-revocation-at-least-43-chars");
+        let code = create_code(&store, &client.client_id, &challenge);
+        let (access, refresh) = store
+            .exchange_code(&code, verifier, &client.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+        let refresh = refresh.unwrap();
+
+        // Wrong hint still finds the token
+        let revoked = store.revoke_token(&refresh.token, Some("access_token"), &client.client_id, None);
+        assert_eq!(revoked, Ok(Some("refresh_token")));
+        assert!(store.refresh(&refresh.token, &client.client_id).is_err());
+        assert!(store.validate_access_token(&access.token).is_none());
+        // Client registration is kept
+        assert!(store.get_client(&client.client_id).is_some());
+    }
+
+    #[test]
+    fn revoke_token_unknown_or_foreign_token_is_noop() {
+        let (store, _dir) = test_store();
+        let alice = register_test_client(&store, "Alice");
+        let bob = register_test_client(&store, "Bob");
+        let (verifier, challenge) = pkce_pair("alice-verifier-string-that-is-at-least-43-characters!!");
+        let code = create_code(&store, &alice.client_id, &challenge);
+        let (access, _) = store
+            .exchange_code(&code, verifier, &alice.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+
+        assert_eq!(store.revoke_token("nope", None, &alice.client_id, None), Ok(None));
+        assert_eq!(store.revoke_token(&access.token, None, &bob.client_id, None), Ok(None));
+        assert!(store.validate_access_token(&access.token).is_some());
+    }
+
+    #[test]
+    fn revoke_token_requires_client_authentication() {
+        let (store, _dir) = test_store();
+        let (client, secret) = store.register_plugin_client("com.test.plugin", "Test Plugin");
+        let (access, _) = store
+            .issue_client_credentials(&client.client_id, &secret, "http://127.0.0.1:9600".into(), vec![])
+            .unwrap();
+
+        assert_eq!(store.revoke_token(&access.token, None, "unknown", None), Err("invalid_client"));
+        assert_eq!(store.revoke_token(&access.token, None, &client.client_id, None), Err("invalid_client"));
+        assert_eq!(
+            store.revoke_token(&access.token, None, &client.client_id, Some("wrong")),
+            Err("invalid_client")
+        );
+        assert!(store.validate_access_token(&access.token).is_some());
+
+        let revoked = store.revoke_token(&access.token, None, &client.client_id, Some(&secret));
+        assert_eq!(revoked, Ok(Some("access_token")));
+        assert!(store.validate_access_token(&access.token).is_none());
+    }
+
+    // =====================================================================
+    // Per-client grants
+    // =====================================================================
+
+    fn fs_detail(locations: Option<&[&str]>) -> AuthorizationDetail {
+        AuthorizationDetail {
+            detail_type: "nexus:fs".into(),
+            actions: vec!["read".into()],
+            locations: locations.map(|l| l.iter().map(|s| s.to_string()).collect()),
+            identifier: None,
+        }
+    }
+
+    #[test]
+    fn client_grants_reports_token_scopes() {
+        let (store, _dir) = test_store();
+        let client = register_test_client(&store, "Grants");
+        let (verifier, challenge) = pkce_pair("verifier-for-client-grants-listing-at-least-43-chars");
+        let code = create_code(&store, &client.client_id, &challenge);
+        store
+            .exchange_code(&code, verifier, &client.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+
+        let grants = store.client_grants(&client.client_id).unwrap();
+        assert_eq!(grants.scopes, vec!["mcp".to_string()]);
+        assert!(grants.authorization_details.is_empty());
+        assert!(store.client_grants("unknown").is_none());
+    }
+
+    #[test]
+    fn restrict_client_grants_removes_scope_from_live_tokens() {
+        let (store, _dir) = test_store();
+        let client = register_test_client(&store, "Downgrade");
+        let (verifier, challenge) = pkce_pair("verifier-for-scope-downgrade-that-is-at-least-43-chars");
+        let code = create_code(&store, &client.client_id, &challenge);
+        let (access, refresh) = store
+            .exchange_code(&code, verifier, &client.client_id, "http://127.0.0.1:3000/callback")
+            .unwrap();
+
+        store.restrict_client_grants(&client.client_id, &[], &[]).unwrap();
+        assert!(store.validate_access_token(&access.token).unwrap().scopes.is_empty());
+        // Refreshing must not bring the scope back
+        let (refreshed, _) = store.refresh(&refresh.unwrap().token, &client.client_id).unwrap();
+        assert!(refreshed.scopes.is_empty());
+    }
+
+    #[test]
+    fn restrict_client_grants_cannot_widen() {
+        let (store, _dir) = test_store();
+        let (client, secret) = store.register_plugin_client("com.test.plugin", "Test Plugin");
+        store.set_plugin_auth_details(&client.client_id, vec![fs_detail(Some(&["/home"]))]);
+        store
+            .issue_client_credentials(&client.client_id, &secret, "http://127.0.0.1:9600".into(), vec![])
+            .unwrap();
+
+        assert_eq!(
+            store.restrict_client_grants(&client.client_id, &["mcp".into(), "admin".into()], &[]).err(),
+            Some("invalid_scope")
+        );
+        assert_eq!(
+            store.restrict_client_grants(&client.client_id, &[], &[fs_detail(None)]).err(),
+            Some("invalid_authorization_details")
+        );
+        assert_eq!(
+            store.restrict_client_grants("unknown", &[], &[]).err(),
+            Some("invalid_client")
+        );
+        // Nothing changed
+        assert_eq!(
+            store.get_plugin_auth_details(&client.client_id),
+            vec![fs_detail(Some(&["/home"]))]
+        );
+    }
+
+    #[test]
+    fn restrict_client_grants_narrows_plugin_details() {
+        let (store, _dir) = test_store();
+        let (client, secret) = store.register_plugin_client("com.test.plugin", "Test Plugin");
+        let broad = fs_detail(Some(&["/home", "/tmp"]));
+        store.set_plugin_auth_details(&client.client_id, vec![broad.clone()]);
+        let (access, _) = store
+            .issue_client_credentials(&client.client_id, &secret, "http://127.0.0.1:9600".into(), vec![])
+            .unwrap();
+        assert_eq!(access.authorization_details, vec![broad]);
+
+        let narrow = fs_detail(Some(&["/tmp"]));
+        store
+            .restrict_client_grants(&client.client_id, &[], std::slice::from_ref(&narrow))
+            .unwrap();
+        assert_eq!(store.get_plugin_auth_details(&client.client_id), vec![narrow.clone()]);
+        let token = store.validate_access_token(&access.token).unwrap();
+        assert_eq!(token.authorization_details, vec![narrow]);
+    }
+
+    #[test]
+    fn parse_scope_keeps_supported_scopes() {
+        assert_eq!(parse_scope(""), vec!["mcp".to_string()]);
+        assert_eq!(parse_scope("openid mcp profile"), vec!["mcp".to_string()]);
+        assert_eq!(parse_scope("admin"), vec!["mcp".to_string()]);
+    }
+
     // =====================================================================
     // Persistence
     // =====================================================================
@@ -1539,6 +2320,7 @@ mod tests {
             redirect_uris: vec!["http://127.0.0.1:3000/callback".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
         store.approve_client(&client.client_id);
 
@@ -1548,6 +2330,7 @@ mod tests {
             redirect_uris: vec!["http://evil.com/steal".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
 
         assert_eq!(client.client_id, client2.client_id);
@@ -1565,6 +2348,7 @@ mod tests {
             redirect_uris: vec!["http://127.0.0.1:3000/callback".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
 
         // Second registration with same name, different port (common for reconnections)
@@ -1573,6 +2357,7 @@ mod tests {
             redirect_uris: vec!["http://127.0.0.1:4000/callback".into()],
             grant_types: vec!["authorization_code".into()],
             token_endpoint_auth_method: "none".into(),
+            access_token_format: AccessTokenFormat::Opaque,
         });
 
         let found = store.get_client(&client.client_id).unwrap();
@@ -11,15 +11,17 @@ use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
 use crate::permissions::rar::AuthorizationDetail;
 
 use super::store::OAuthStore;
-use super::types::{AccessToken, TokenRequest, TokenResponse};
+use super::types::{AccessToken, TokenRequest, TokenResponse, DEVICE_CODE_GRANT};
 
 /// OAuth 2.1 Token Endpoint.
 ///
 /// `POST /oauth/token` (application/x-www-form-urlencoded)
 ///
-/// Supports two grant types:
+/// Supports these grant types:
 /// - `authorization_code` — exchange auth code for tokens (with PKCE)
 /// - `refresh_token` — rotate refresh token for new tokens
+/// - `client_credentials` — plugin machine-to-machine auth
+/// - `urn:ietf:params:oauth:grant-type:device_code` — RFC 8628 device polling
 pub async fn token_exchange(
     Extension(store): Extension<Arc<OAuthStore>>,
     audit: Option<Extension<AuditWriter>>,
@@ -30,6 +32,7 @@ pub async fn token_exchange(
         "authorization_code" => handle_authorization_code(store, req, audit.as_ref()),
         "refresh_token" => handle_refresh_token(store, req, audit.as_ref()),
         "client_credentials" => handle_client_credentials(store, req, audit.as_ref()),
+        DEVICE_CODE_GRANT => handle_device_code(store, req, audit.as_ref()),
         _ => oauth_error(
             StatusCode::BAD_REQUEST,
             "unsupported_grant_type",
@@ -239,12 +242,71 @@ fn handle_client_credentials(store: Arc<OAuthStore>, req: TokenRequest, audit: O
     .into_response()
 }
 
+fn handle_device_code(store: Arc<OAuthStore>, req: TokenRequest, audit: Option<&AuditWriter>) -> Response {
+    let Some(device_code) = req.device_code.as_deref() else {
+        return oauth_error(StatusCode::BAD_REQUEST, "invalid_request", "Missing 'device_code'");
+    };
+    let Some(client_id) = req.client_id.as_deref() else {
+        return oauth_error(StatusCode::BAD_REQUEST, "invalid_request", "Missing 'client_id'");
+    };
+
+    let (access, refresh) = match store.exchange_device_code(device_code, client_id) {
+        Ok(pair) => pair,
+        // Expected while the user hasn't entered the code yet — not audited
+        Err(e @ ("authorization_pending" | "slow_down")) => {
+            return oauth_error(StatusCode::BAD_REQUEST, e, "Waiting for the user to enter the code in Nexus");
+        }
+        Err(e) => {
+            log::warn!("OAuth device code exchange failed: {}", e);
+            if let Some(audit) = audit {
+                audit.record(AuditEntry {
+                    actor: AuditActor::McpClient,
+                    source_id: Some(client_id.to_string()),
+                    severity: AuditSeverity::Critical,
+                    action: "security.oauth.token".into(),
+                    subject: Some("device_code".into()),
+                    result: AuditResult::Failure,
+                    details: Some(serde_json::json!({ "error": e })),
+                });
+            }
+            return oauth_error(StatusCode::BAD_REQUEST, e, "Device code exchange failed");
+        }
+    };
+
+    log::info!(
+        "OAuth token issued: client={} grant=device_code",
+        access.client_name
+    );
+
+    if let Some(audit) = audit {
+        audit.record(AuditEntry {
+            actor: AuditActor::McpClient,
+            source_id: Some(client_id.to_string()),
+            severity: AuditSeverity::Critical,
+            action: "security.oauth.token".into(),
+            subject: Some("device_code".into()),
+            result: AuditResult::Success,
+            details: Some(serde_json::json!({ "client_name": access.client_name })),
+        });
+    }
+
+    let ttl = expires_in(&access);
+    Json(TokenResponse {
+        access_token: access.token,
+        token_type: "Bearer".into(),
+        expires_in: ttl,
+        refresh_token: Some(refresh.token),
+        authorization_details: None,
+    })
+    .into_response()
+}
+
 /// Compute the remaining lifetime of an access token in seconds.
 fn expires_in(token: &AccessToken) -> u64 {
     (token.expires_at - Utc::now()).num_seconds().max(0) as u64
 }
 
-fn oauth_error(status: StatusCode, error: &str, description: &str) -> Response {
+pub(super) fn oauth_error(status: StatusCode, error: &str, description: &str) -> Response {
     (
         status,
         Json(serde_json::json!({
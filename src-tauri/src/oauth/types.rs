@@ -26,6 +26,20 @@ pub struct OAuthClient {
     /// Associated Nexus plugin ID (maps OAuth tokens to plugin permissions).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plugin_id: Option<String>,
+    /// Format of access tokens issued to this client.
+    #[serde(default)]
+    pub access_token_format: AccessTokenFormat,
+}
+
+/// Access token format. Opaque tokens are random strings only the
+/// `OAuthStore` can resolve; JWTs are signed with a key from `/oauth/jwks`
+/// so other resource servers can validate them offline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessTokenFormat {
+    #[default]
+    Opaque,
+    Jwt,
 }
 
 /// Inbound registration request body.
@@ -37,6 +51,9 @@ pub struct RegistrationRequest {
     pub grant_types: Vec<String>,
     #[serde(default = "default_auth_method")]
     pub token_endpoint_auth_method: String,
+    /// Nexus extension to RFC 7591 metadata: `"jwt"` opts into JWT access tokens.
+    #[serde(default)]
+    pub access_token_format: AccessTokenFormat,
 }
 
 fn default_grant_types() -> Vec<String> {
@@ -55,6 +72,7 @@ pub struct RegistrationResponse {
     pub redirect_uris: Vec<String>,
     pub grant_types: Vec<String>,
     pub token_endpoint_auth_method: String,
+    pub access_token_format: AccessTokenFormat,
 }
 
 // ---------------------------------------------------------------------------
@@ -69,6 +87,7 @@ pub struct OAuthClientInfo {
     pub approved: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_id: Option<String>,
+    pub access_token_format: AccessTokenFormat,
 }
 
 impl From<&OAuthClient> for OAuthClientInfo {
@@ -79,10 +98,23 @@ impl From<&OAuthClient> for OAuthClientInfo {
             registered_at: c.registered_at,
             approved: c.approved,
             plugin_id: c.plugin_id.clone(),
+            access_token_format: c.access_token_format,
         }
     }
 }
 
+/// Scopes and authorization details a client currently holds, for the
+/// per-client scope editor in Settings.
+#[derive(Debug, Clone, Serialize)]
+pub struct OAuthClientGrants {
+    pub client_id: String,
+    pub client_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_id: Option<String>,
+    pub scopes: Vec<String>,
+    pub authorization_details: Vec<AuthorizationDetail>,
+}
+
 // ---------------------------------------------------------------------------
 // Authorization code (short-lived, in-memory only)
 // ---------------------------------------------------------------------------
@@ -102,6 +134,66 @@ pub struct AuthorizationCode {
     pub no_refresh: bool,
 }
 
+// ---------------------------------------------------------------------------
+// Device authorization (RFC 8628, short-lived, in-memory only)
+// ---------------------------------------------------------------------------
+
+/// Grant type URN for the device code token request (RFC 8628 §3.4).
+pub const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeviceStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+#[derive(Debug)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    /// Normalized (no separator, uppercase) — see `normalize_user_code`.
+    pub user_code: String,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+    pub resource: String,
+    pub expires_at: Instant,
+    /// Minimum seconds between token polls; bumped on `slow_down`.
+    pub interval: u64,
+    pub last_poll: Option<Instant>,
+    pub status: DeviceStatus,
+}
+
+/// Device authorization request body (application/x-www-form-urlencoded).
+#[derive(Debug, Deserialize)]
+pub struct DeviceAuthorizationRequest {
+    pub client_id: String,
+    #[serde(default)]
+    pub scope: String,
+    #[serde(default)]
+    pub resource: String,
+}
+
+/// RFC 8628 §3.2 device authorization response.
+#[derive(Debug, Serialize)]
+pub struct DeviceAuthorizationResponse {
+    pub device_code: String,
+    /// Formatted for display (`XXXX-XXXX`).
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+/// A pending device authorization as shown to the user in the desktop UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceAuthorizationInfo {
+    pub user_code: String,
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    pub expires_in: u64,
+}
+
 // ---------------------------------------------------------------------------
 // Tokens
 // ---------------------------------------------------------------------------
@@ -159,6 +251,18 @@ pub struct TokenRequest {
     pub client_secret: Option<String>,
     /// RFC 9396 authorization_details (JSON string from form body).
     pub authorization_details: Option<String>,
+    // device_code fields (RFC 8628)
+    pub device_code: Option<String>,
+}
+
+/// RFC 7009 revocation request (application/x-www-form-urlencoded).
+#[derive(Debug, Deserialize)]
+pub struct RevocationRequest {
+    pub token: String,
+    /// `access_token` or `refresh_token`; unknown hints are ignored.
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
 }
 
 /// Token endpoint response.
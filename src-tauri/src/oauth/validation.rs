@@ -19,6 +19,7 @@ pub enum TokenValidation {
         client_id: String,
         client_name: String,
         plugin_id: Option<String>,
+        scopes: Vec<String>,
         authorization_details: Vec<AuthorizationDetail>,
     },
     /// A Bearer token was provided but is expired, revoked, or invalid.
@@ -54,6 +55,7 @@ pub fn validate_bearer(headers: &HeaderMap, oauth_store: &OAuthStore) -> TokenVa
             client_id: access_token.client_id,
             client_name: access_token.client_name,
             plugin_id: access_token.plugin_id,
+            scopes: access_token.scopes,
             authorization_details: access_token.authorization_details,
         },
         None => TokenValidation::Invalid,
@@ -1,5 +1,12 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+
 use super::service::PermissionService;
-use super::types::Permission;
+use super::types::{DenyRule, GrantedPermission, Permission, PermissionState, RateCap};
+use super::{cmd_scope, fs_scope, net_scope};
 
 #[allow(dead_code)]
 pub fn check_permission(
@@ -10,6 +17,218 @@ pub fn check_permission(
     store.has_permission(plugin_id, required)
 }
 
+/// Permissions whose handlers run their own per-path or per-command approval.
+/// An always-prompt grant for these is confirmed there, not in the auth middleware.
+pub fn prompts_in_handler(permission: &Permission) -> bool {
+    matches!(
+        permission,
+        Permission::FilesystemRead | Permission::FilesystemWrite | Permission::ProcessExec
+    )
+}
+
+/// Sliding-window use counts for rate-capped grants, keyed by plugin and
+/// permission. Counts live in memory only and start over on restart.
+#[derive(Default)]
+pub struct UsageTracker {
+    uses: Mutex<HashMap<(String, Permission), VecDeque<Instant>>>,
+}
+
+impl UsageTracker {
+    /// Count one use. Returns false when it would go over `cap`; such a use
+    /// is not counted.
+    pub fn try_use(&self, plugin_id: &str, permission: &Permission, cap: RateCap) -> bool {
+        self.try_use_at(plugin_id, permission, cap, Instant::now())
+    }
+
+    fn try_use_at(&self, plugin_id: &str, permission: &Permission, cap: RateCap, now: Instant) -> bool {
+        let mut uses = self.uses.lock().unwrap_or_else(|e| e.into_inner());
+        let window = uses
+            .entry((plugin_id.to_string(), permission.clone()))
+            .or_default();
+        while window
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= cap.window())
+        {
+            window.pop_front();
+        }
+        if window.len() >= cap.max_calls as usize {
+            return false;
+        }
+        window.push_back(now);
+        true
+    }
+
+    /// Forget past uses, starting a fresh window.
+    pub fn reset(&self, plugin_id: &str, permission: &Permission) {
+        let mut uses = self.uses.lock().unwrap_or_else(|e| e.into_inner());
+        uses.remove(&(plugin_id.to_string(), permission.clone()));
+    }
+}
+
+/// What would happen if a plugin used a permission right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedOutcome {
+    Allowed,
+    Denied,
+    /// The user would be prompted.
+    NeedsApproval,
+}
+
+/// Why a simulated check got its outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationReason {
+    /// A "never allow" rule matches; the bridge refuses without prompting.
+    DenyRule,
+    NoGrant,
+    Revoked,
+    Expired,
+    Deferred,
+    AlwaysPrompt,
+    /// The grant has no scope whitelist, or no scope was given.
+    Unrestricted,
+    ScopeApproved,
+    ScopeNotApproved,
+    /// The host is outside the grant's `network:{host-pattern}`.
+    OutsideHostPattern,
+}
+
+/// Result of [`simulate`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Simulation {
+    pub outcome: SimulatedOutcome,
+    pub reason: SimulationReason,
+    /// The grant that decided the outcome. For `network:local` and
+    /// `network:internet` this may be a host-pattern grant covering the scope.
+    pub grant: Option<GrantedPermission>,
+    pub deny_rule: Option<DenyRule>,
+}
+
+impl Simulation {
+    fn new(outcome: SimulatedOutcome, reason: SimulationReason, grant: Option<GrantedPermission>) -> Self {
+        Self { outcome, reason, grant, deny_rule: None }
+    }
+}
+
+/// Run the Host API's access checks for one use of `permission` without
+/// prompting, persisting, or counting the use. `scope` is the path, host,
+/// command line, or extension scope value the call would carry. Rate caps
+/// are not evaluated; the returned grant shows the cap.
+pub fn simulate(
+    service: &dyn PermissionService,
+    plugin_id: &str,
+    permission: &Permission,
+    scope: Option<&str>,
+) -> Simulation {
+    if let Some(rule) = service
+        .list_denies(plugin_id)
+        .into_iter()
+        .find(|r| r.matches(permission, scope))
+    {
+        return Simulation {
+            deny_rule: Some(rule),
+            ..Simulation::new(SimulatedOutcome::Denied, SimulationReason::DenyRule, None)
+        };
+    }
+
+    let grants = service.get_grants(plugin_id);
+    let grant = grants.iter().find(|g| &g.permission == permission).cloned();
+    let active = grant
+        .as_ref()
+        .is_some_and(|g| g.effective_state() == PermissionState::Active);
+
+    // Without a class grant, a host may still be reachable through a
+    // host-pattern grant, as in the network handler
+    if !active && matches!(permission, Permission::NetworkLocal | Permission::NetworkInternet) {
+        if let Some(host) = scope {
+            let host_grant = grants.iter().find(|g| {
+                g.effective_state() == PermissionState::Active
+                    && g.permission
+                        .network_host_pattern()
+                        .is_some_and(|pattern| net_scope::host_matches(pattern, host))
+            });
+            if let Some(host_grant) = host_grant {
+                return simulate(service, plugin_id, &host_grant.permission, scope);
+            }
+        }
+    }
+
+    let Some(grant) = grant else {
+        return Simulation::new(SimulatedOutcome::Denied, SimulationReason::NoGrant, None);
+    };
+    match grant.effective_state() {
+        PermissionState::Active => {}
+        PermissionState::Deferred => {
+            return Simulation::new(SimulatedOutcome::NeedsApproval, SimulationReason::Deferred, Some(grant));
+        }
+        PermissionState::Revoked => {
+            let reason = if grant.is_expired() {
+                SimulationReason::Expired
+            } else {
+                SimulationReason::Revoked
+            };
+            return Simulation::new(SimulatedOutcome::Denied, reason, Some(grant));
+        }
+    }
+    if grant.always_prompt {
+        return Simulation::new(SimulatedOutcome::NeedsApproval, SimulationReason::AlwaysPrompt, Some(grant));
+    }
+
+    let approved = service.get_approved_scopes(plugin_id, permission);
+    if scope.is_none() && permission.always_scoped() {
+        // Without a command line there's nothing an approved pattern could cover
+        return Simulation::new(SimulatedOutcome::NeedsApproval, SimulationReason::ScopeNotApproved, Some(grant));
+    }
+    let (Some(scope), Some(approved)) = (scope, approved) else {
+        if let (Some(host), Some(pattern)) = (scope, permission.network_host_pattern()) {
+            if !net_scope::host_matches(pattern, host) {
+                return Simulation::new(SimulatedOutcome::Denied, SimulationReason::OutsideHostPattern, Some(grant));
+            }
+        }
+        return Simulation::new(SimulatedOutcome::Allowed, SimulationReason::Unrestricted, Some(grant));
+    };
+    let covered = match permission {
+        Permission::FilesystemRead | Permission::FilesystemWrite => {
+            approved.iter().any(|s| fs_scope::covers(s, std::path::Path::new(scope)))
+        }
+        // Class grants aren't scoped; any host of the class is allowed
+        Permission::NetworkLocal | Permission::NetworkInternet => true,
+        Permission::ProcessExec => approved.iter().any(|p| cmd_scope::covers_line(p, scope)),
+        Permission::Network(_) => {
+            let pattern = permission.network_host_pattern().unwrap_or_default();
+            if !net_scope::host_matches(pattern, scope) {
+                return Simulation::new(SimulatedOutcome::Denied, SimulationReason::OutsideHostPattern, Some(grant));
+            }
+            approved.iter().any(|h| h.eq_ignore_ascii_case(scope))
+        }
+        _ => approved.iter().any(|s| s == scope),
+    };
+    if covered {
+        Simulation::new(SimulatedOutcome::Allowed, SimulationReason::ScopeApproved, Some(grant))
+    } else {
+        Simulation::new(SimulatedOutcome::NeedsApproval, SimulationReason::ScopeNotApproved, Some(grant))
+    }
+}
+
+/// Whether a Host API call may change state. Read-only mode rejects these
+/// before any permission check.
+///
+/// This is an allow-list: only GETs and the POSTs known to be reads pass, so
+/// new routes are blocked in read-only mode until they're listed here.
+pub fn is_mutating_endpoint(path: &str, method: &axum::http::Method) -> bool {
+    if method == axum::http::Method::GET || method == axum::http::Method::HEAD {
+        return false;
+    }
+    match path {
+        // Only advances the caller's own delivery cursor
+        "/v1/events/ack" => false,
+        // Checked per operation in the handler: only low-risk ones run
+        p if p.starts_with("/v1/extensions/") => false,
+        _ => true,
+    }
+}
+
 /// Map a request path + HTTP method to its required permission.
 ///
 /// Paths here are as seen inside the nested router (after Axum strips the
@@ -63,8 +282,112 @@ pub fn required_permission_for_endpoint(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::permissions::service::mock::MockPermissionService;
     use axum::http::Method;
 
+    #[test]
+    fn simulate_reports_outcome_and_grant() {
+        let service = MockPermissionService::new()
+            .with_grant("p", Permission::FilesystemRead, Some(vec!["/home/u/proj".into()]))
+            .with_deferred("p", Permission::ProcessExec, None)
+            .with_grant("p", Permission::Network("network:*.github.com".into()), Some(vec!["api.github.com".into()]));
+
+        let sim = simulate(&service, "p", &Permission::FilesystemRead, Some("/home/u/proj/a.txt"));
+        assert_eq!(sim.outcome, SimulatedOutcome::Allowed);
+        assert_eq!(sim.reason, SimulationReason::ScopeApproved);
+        assert_eq!(sim.grant.unwrap().permission, Permission::FilesystemRead);
+
+        let sim = simulate(&service, "p", &Permission::FilesystemRead, Some("/etc/passwd"));
+        assert_eq!(sim.outcome, SimulatedOutcome::NeedsApproval);
+
+        let sim = simulate(&service, "p", &Permission::ProcessExec, None);
+        assert_eq!(sim.reason, SimulationReason::Deferred);
+
+        let sim = simulate(&service, "p", &Permission::FilesystemWrite, None);
+        assert_eq!(sim.outcome, SimulatedOutcome::Denied);
+        assert!(sim.grant.is_none());
+
+        // Class permission falls back to a covering host-pattern grant
+        let sim = simulate(&service, "p", &Permission::NetworkInternet, Some("api.github.com"));
+        assert_eq!(sim.outcome, SimulatedOutcome::Allowed);
+        assert_eq!(
+            sim.grant.unwrap().permission,
+            Permission::Network("network:*.github.com".into())
+        );
+        let sim = simulate(&service, "p", &Permission::NetworkInternet, Some("example.com"));
+        assert_eq!(sim.reason, SimulationReason::NoGrant);
+
+        service
+            .add_deny("p", Permission::FilesystemRead, Some("/home/u/proj/secret".into()))
+            .unwrap();
+        let sim = simulate(&service, "p", &Permission::FilesystemRead, Some("/home/u/proj/secret/key"));
+        assert_eq!(sim.reason, SimulationReason::DenyRule);
+        assert!(sim.deny_rule.is_some());
+        // Nothing was persisted or prompted
+        assert_eq!(service.get_state("p", &Permission::ProcessExec), Some(PermissionState::Deferred));
+
+        service.activate("p", &Permission::ProcessExec).unwrap();
+        service
+            .add_approved_scope("p", &Permission::ProcessExec, "npm run *".into())
+            .unwrap();
+        let sim = simulate(&service, "p", &Permission::ProcessExec, Some("npm run build"));
+        assert_eq!(sim.reason, SimulationReason::ScopeApproved);
+        let sim = simulate(&service, "p", &Permission::ProcessExec, Some("npm install"));
+        assert_eq!(sim.reason, SimulationReason::ScopeNotApproved);
+    }
+
+    #[test]
+    fn usage_tracker_enforces_cap_in_window() {
+        let tracker = UsageTracker::default();
+        let cap = RateCap { max_calls: 2, per_secs: 60 };
+        let start = Instant::now();
+        let perm = Permission::ProcessExec;
+
+        assert!(tracker.try_use_at("p", &perm, cap, start));
+        assert!(tracker.try_use_at("p", &perm, cap, start));
+        assert!(!tracker.try_use_at("p", &perm, cap, start));
+        // Other plugins have their own window
+        assert!(tracker.try_use_at("q", &perm, cap, start));
+        // Old uses fall out of the window
+        assert!(tracker.try_use_at("p", &perm, cap, start + std::time::Duration::from_secs(60)));
+
+        tracker.reset("p", &perm);
+        assert!(tracker.try_use_at("p", &perm, cap, start + std::time::Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn mutating_endpoints() {
+        assert!(is_mutating_endpoint("/v1/fs/write", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/fs/edit", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/process/exec", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/containers/abc123/stop", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/containers/images/abc123", &Method::DELETE));
+        assert!(!is_mutating_endpoint("/v1/containers/abc123", &Method::GET));
+        assert!(!is_mutating_endpoint("/v1/fs/read", &Method::GET));
+        assert!(!is_mutating_endpoint("/v1/process/list", &Method::GET));
+        assert!(!is_mutating_endpoint("/v1/events/subscribe", &Method::GET));
+    }
+
+    #[test]
+    fn unlisted_writes_are_mutating() {
+        assert!(is_mutating_endpoint("/v1/network/proxy", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/events", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/events/replay", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/events/schemas/com.example.thing", &Method::PUT));
+        assert!(is_mutating_endpoint("/v1/meta/credentials/aws", &Method::POST));
+        assert!(is_mutating_endpoint("/v1/settings", &Method::PUT));
+        assert!(is_mutating_endpoint("/v1/storage/key", &Method::PUT));
+        assert!(is_mutating_endpoint("/v1/storage/key", &Method::DELETE));
+        assert!(is_mutating_endpoint("/v1/some/future/route", &Method::POST));
+
+        assert!(!is_mutating_endpoint("/v1/events/ack", &Method::POST));
+        // Extension calls are gated per operation by risk level
+        assert!(!is_mutating_endpoint("/v1/extensions/git/status", &Method::POST));
+        assert!(crate::extensions::RiskLevel::Low.allowed_in_read_only());
+        assert!(!crate::extensions::RiskLevel::Medium.allowed_in_read_only());
+        assert!(!crate::extensions::RiskLevel::High.allowed_in_read_only());
+    }
+
     #[test]
     fn system_endpoints_require_system_info() {
         assert_eq!(
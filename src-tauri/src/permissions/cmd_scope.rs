@@ -0,0 +1,118 @@
+//! Command pattern matching for `process:exec` scopes.
+//!
+//! A scope is a command line split on whitespace: `git *` allows git with any
+//! arguments, `npm run *` any npm script, `cargo build` exactly that. A
+//! trailing `*` matches zero or more remaining arguments; a `*` anywhere else
+//! matches exactly one. Every other token, including the program, compares
+//! literally, so `git *` does not cover `/usr/bin/git`.
+
+/// The command line shown to the user and matched against deny rules.
+pub fn command_line(command: &str, args: &[String]) -> String {
+    std::iter::once(command)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether `pattern` covers the program and its arguments.
+pub fn covers<'a>(pattern: &str, argv: impl IntoIterator<Item = &'a str>) -> bool {
+    let tokens: Vec<&str> = pattern.split_whitespace().collect();
+    let argv: Vec<&str> = argv.into_iter().collect();
+    if tokens.is_empty() {
+        return false;
+    }
+    let (fixed, rest_wildcard) = match tokens.split_last() {
+        Some((&"*", init)) if !init.is_empty() => (init, true),
+        _ => (&tokens[..], false),
+    };
+    if argv.len() < fixed.len() || (!rest_wildcard && argv.len() != fixed.len()) {
+        return false;
+    }
+    fixed
+        .iter()
+        .zip(&argv)
+        .all(|(token, arg)| *token == "*" || token == arg)
+}
+
+/// Match a pattern against a command line string, split on whitespace. Used
+/// for deny rules, where an argument containing spaces may over-match.
+pub fn covers_line(pattern: &str, line: &str) -> bool {
+    covers(pattern, line.split_whitespace())
+}
+
+/// Suggested scopes for a call, narrowest first: the exact command line, the
+/// program and its first argument with any further arguments (`npm run *`),
+/// and the program with any arguments (`git *`). Arguments containing spaces
+/// can't be written literally, so only wildcards cover them.
+pub fn suggestions(command: &str, args: &[String]) -> Vec<String> {
+    let has_space = |a: &String| a.contains(char::is_whitespace);
+    let mut out = Vec::new();
+    if !args.iter().any(has_space) {
+        out.push(command_line(command, args));
+    }
+    if let Some(first) = args.first().filter(|a| !has_space(a)) {
+        if args.len() > 1 {
+            out.push(format!("{} {} *", command, first));
+        }
+    }
+    if !args.is_empty() {
+        out.push(format!("{} *", command));
+    }
+    out
+}
+
+/// Check that a pattern names a program: non-empty, and not starting with a
+/// wildcard, which would allow any command.
+pub fn validate(pattern: &str) -> Result<(), String> {
+    match pattern.split_whitespace().next() {
+        None => Err("command pattern is empty".to_string()),
+        Some(program) if program.contains('*') => {
+            Err(format!("command pattern must start with a program name: {}", pattern))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_wildcard_matches_any_remaining_args() {
+        assert!(covers("git *", ["git", "status"]));
+        assert!(covers("git *", ["git", "commit", "-m", "a b"]));
+        assert!(covers("git *", ["git"]));
+        assert!(covers("npm run *", ["npm", "run", "build"]));
+        assert!(!covers("npm run *", ["npm", "install"]));
+        assert!(!covers("git *", ["/usr/bin/git", "status"]));
+    }
+
+    #[test]
+    fn literal_and_single_wildcards() {
+        assert!(covers("cargo build", ["cargo", "build"]));
+        assert!(!covers("cargo build", ["cargo", "build", "--release"]));
+        assert!(covers("kubectl get *", ["kubectl", "get", "pods"]));
+        assert!(covers("docker * ps", ["docker", "compose", "ps"]));
+        assert!(!covers("docker * ps", ["docker", "compose", "up"]));
+    }
+
+    #[test]
+    fn suggestions_narrowest_first() {
+        let args = vec!["run".to_string(), "build".to_string()];
+        assert_eq!(suggestions("npm", &args), vec!["npm run build", "npm run *", "npm *"]);
+        assert_eq!(suggestions("ls", &[]), vec!["ls"]);
+        let spaced = vec!["-m".to_string(), "a b".to_string()];
+        assert_eq!(suggestions("git", &spaced), vec!["git -m *", "git *"]);
+        for s in suggestions("npm", &args) {
+            assert!(covers(&s, ["npm", "run", "build"]));
+        }
+    }
+
+    #[test]
+    fn validate_requires_program() {
+        assert!(validate("git *").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("*").is_err());
+        assert!(validate("* --version").is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! Background pruner for time-limited permission grants.
+//!
+//! Expired grants are already rejected by the checker (see
+//! `GrantedPermission::effective_state`). This task makes the expiry visible:
+//! it moves them to Revoked, drops the plugin's tokens so the fast path stops
+//! honouring them, and emits a lifecycle event so the UI reloads.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::audit::writer::AuditWriter;
+use crate::audit::{AuditActor, AuditEntry, AuditResult, AuditSeverity};
+use crate::lifecycle_events::{self, LifecycleEvent};
+use crate::AppState;
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Spawn a background task that prunes expired grants every minute.
+pub fn spawn(app: tauri::AppHandle, state: AppState, audit: AuditWriter) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            prune(&app, &state, &audit).await;
+        }
+    });
+}
+
+async fn prune(app: &tauri::AppHandle, state: &AppState, audit: &AuditWriter) {
+    let mgr = state.read().await;
+    let expired = match mgr.permissions.prune_expired() {
+        Ok(expired) if !expired.is_empty() => expired,
+        Ok(_) => return,
+        Err(e) => {
+            log::warn!("Failed to prune expired permissions: {}", e);
+            return;
+        }
+    };
+
+    let mut by_plugin: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for grant in expired {
+        by_plugin
+            .entry(grant.plugin_id)
+            .or_default()
+            .push(grant.permission.as_str().to_string());
+    }
+
+    for (plugin_id, permissions) in by_plugin {
+        log::info!("Permissions expired for plugin={}: {:?}", plugin_id, permissions);
+
+        // Same as a manual revoke: force a fresh token without the lapsed grants
+        if let Some(client) = mgr.oauth_store.get_client_by_plugin_id(&plugin_id) {
+            mgr.oauth_store.revoke_plugin_tokens(&client.client_id);
+            let grants = mgr.permissions.get_grants(&plugin_id);
+            let details = super::rar::build_authorization_details(&grants);
+            mgr.oauth_store.set_plugin_auth_details(&client.client_id, details);
+        }
+
+        audit.record(AuditEntry {
+            actor: AuditActor::System, source_id: None, severity: AuditSeverity::Critical, action: "permission.expire".into(),
+            subject: Some(plugin_id.clone()), result: AuditResult::Success,
+            details: Some(serde_json::json!({"permissions": permissions})),
+        });
+        lifecycle_events::emit(
+            Some(app),
+            LifecycleEvent::PermissionExpired { plugin_id, permissions },
+        );
+    }
+
+    mgr.notify_tools_changed();
+}
@@ -0,0 +1,144 @@
+//! Filesystem scope matching.
+//!
+//! An approved (or denied) filesystem scope is either a directory prefix —
+//! `~/Projects/foo` covers everything below it — or a glob pattern such as
+//! `~/Projects/*/src/**` or `/tmp/*.log`. `*` and `?` stay within one path
+//! component; `**` crosses directories.
+
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+
+const GLOB_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Whether the scope is a glob pattern rather than a directory prefix.
+pub fn is_pattern(scope: &str) -> bool {
+    scope.contains(['*', '?', '['])
+}
+
+/// Expand a leading `~` to the user's home directory.
+pub fn expand_home(scope: &str) -> PathBuf {
+    if scope == "~" {
+        dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"))
+    } else if let Some(rest) = scope.strip_prefix("~/") {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/"))
+            .join(rest)
+    } else {
+        PathBuf::from(scope)
+    }
+}
+
+/// Match a glob scope against a path. Invalid patterns match nothing.
+///
+/// Request paths arrive canonicalized, so the pattern's literal prefix (the
+/// components before the first glob character) is canonicalized too: on
+/// macOS `/tmp/**` has to match `/private/tmp/...`.
+pub fn pattern_matches(scope: &str, path: &Path) -> bool {
+    Pattern::new(&resolve_pattern(&expand_home(scope)))
+        .map(|p| p.matches_path_with(path, GLOB_OPTIONS))
+        .unwrap_or(false)
+}
+
+/// The pattern with its literal prefix resolved through symlinks, if that
+/// prefix exists.
+fn resolve_pattern(pattern: &Path) -> String {
+    let mut components = pattern.components().peekable();
+    let mut prefix = PathBuf::new();
+    while let Some(c) = components.next_if(|c| !is_pattern(&c.as_os_str().to_string_lossy())) {
+        prefix.push(c);
+    }
+    let Ok(resolved) = prefix.canonicalize() else {
+        return pattern.to_string_lossy().into_owned();
+    };
+    let mut out = Pattern::escape(&resolved.to_string_lossy());
+    for c in components {
+        if !out.ends_with('/') {
+            out.push('/');
+        }
+        out.push_str(&c.as_os_str().to_string_lossy());
+    }
+    out
+}
+
+/// Whether `path` falls under `scope`, compared lexically. Callers that need
+/// symlink-aware prefix checks canonicalize the prefix themselves.
+pub fn covers(scope: &str, path: &Path) -> bool {
+    if is_pattern(scope) {
+        pattern_matches(scope, path)
+    } else {
+        path.starts_with(expand_home(scope))
+    }
+}
+
+/// Check that a scope is usable: absolute (after `~` expansion), and a valid
+/// glob if it contains pattern characters.
+pub fn validate(scope: &str) -> Result<(), String> {
+    if !expand_home(scope).is_absolute() {
+        return Err(format!("filesystem scope must be an absolute path: {}", scope));
+    }
+    if is_pattern(scope) {
+        Pattern::new(scope).map_err(|e| format!("invalid glob scope {}: {}", scope, e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_scope_is_directory_prefix() {
+        assert!(covers("/home/user/proj", Path::new("/home/user/proj/a/b.txt")));
+        assert!(covers("/home/user/proj", Path::new("/home/user/proj")));
+        assert!(!covers("/home/user/proj", Path::new("/home/user/project/a.txt")));
+    }
+
+    #[test]
+    fn single_star_stays_in_component() {
+        assert!(covers("/tmp/*.log", Path::new("/tmp/app.log")));
+        assert!(!covers("/tmp/*.log", Path::new("/tmp/sub/app.log")));
+    }
+
+    #[test]
+    fn double_star_crosses_directories() {
+        let scope = "/home/user/*/src/**";
+        assert!(covers(scope, Path::new("/home/user/foo/src/main.rs")));
+        assert!(covers(scope, Path::new("/home/user/foo/src/a/b/c.rs")));
+        assert!(!covers(scope, Path::new("/home/user/foo/docs/a.md")));
+    }
+
+    #[test]
+    fn tilde_is_expanded() {
+        let home = dirs::home_dir().unwrap();
+        assert!(covers("~/proj", &home.join("proj/file")));
+        assert!(covers("~/proj/**", &home.join("proj/a/file")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn pattern_prefix_follows_symlinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let real = dir.path().canonicalize().unwrap().join("real");
+        std::fs::create_dir_all(real.join("sub")).unwrap();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let scope = format!("{}/**", link.display());
+        assert!(covers(&scope, &real.join("sub/a.txt")));
+        assert!(covers(&format!("{}/*.log", link.display()), &real.join("app.log")));
+        assert!(!covers(&scope, &dir.path().canonicalize().unwrap().join("other/a.txt")));
+    }
+
+    #[test]
+    fn validate_rejects_relative_and_bad_globs() {
+        assert!(validate("/home/user").is_ok());
+        assert!(validate("~/Projects/**").is_ok());
+        assert!(validate("relative/dir").is_err());
+        assert!(validate("/tmp/[unclosed").is_err());
+    }
+}
@@ -1,4 +1,8 @@
 pub mod checker;
+pub mod cmd_scope;
+pub mod expiry;
+pub mod fs_scope;
+pub mod net_scope;
 pub mod rar;
 pub mod service;
 pub mod store;
@@ -6,4 +10,7 @@ pub mod types;
 
 pub use service::{DefaultPermissionService, PermissionService};
 pub use store::PermissionStore;
-pub use types::{GrantedPermission, Permission, PermissionState};
+pub use types::{
+    ChangeAction, ChangeActor, DenyRule, ExportedGrant, GrantedPermission, Permission, PermissionChange,
+    PermissionExport, PermissionProfile, PermissionState, PluginPermissions, RateCap, PERMISSION_EXPORT_VERSION,
+};
@@ -0,0 +1,80 @@
+//! Host pattern matching for `network:<host-pattern>` permissions.
+//!
+//! A pattern is an exact host (`api.github.com`), a subdomain wildcard
+//! (`*.github.com` — any subdomain, but not `github.com` itself), or `*` for
+//! any host. Matching is case-insensitive. Approved scopes on these grants are
+//! the concrete hosts the user allowed at first use.
+
+/// Whether `host` is covered by `pattern`.
+pub fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host
+            .strip_suffix(suffix)
+            .is_some_and(|rest| rest.len() > 1 && rest.ends_with('.')),
+        None => host == pattern,
+    }
+}
+
+/// Check that a host pattern is well-formed: no scheme, port, path, or
+/// wildcards other than a single leading `*.` (or a bare `*`).
+pub fn validate(pattern: &str) -> Result<(), String> {
+    if pattern.is_empty() {
+        return Err("host pattern is empty".to_string());
+    }
+    if pattern == "*" {
+        return Ok(());
+    }
+    let host = pattern.strip_prefix("*.").unwrap_or(pattern);
+    let valid = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '[' | ']' | ':'))
+        && !host.starts_with('.')
+        && !host.ends_with('.');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid host pattern: {}", pattern))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_host() {
+        assert!(host_matches("api.github.com", "api.github.com"));
+        assert!(host_matches("API.github.com", "api.GitHub.com"));
+        assert!(!host_matches("api.github.com", "github.com"));
+    }
+
+    #[test]
+    fn subdomain_wildcard() {
+        assert!(host_matches("*.github.com", "api.github.com"));
+        assert!(host_matches("*.github.com", "a.b.github.com"));
+        assert!(!host_matches("*.github.com", "github.com"));
+        assert!(!host_matches("*.github.com", "evilgithub.com"));
+    }
+
+    #[test]
+    fn any_host() {
+        assert!(host_matches("*", "example.org"));
+    }
+
+    #[test]
+    fn validate_patterns() {
+        assert!(validate("*").is_ok());
+        assert!(validate("*.example.com").is_ok());
+        assert!(validate("api.example.com").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("https://example.com").is_err());
+        assert!(validate("example.com/path").is_err());
+        assert!(validate("a.*.example.com").is_err());
+    }
+}
@@ -49,17 +49,21 @@ fn permission_to_type_action(perm: &Permission) -> (&'static str, &'static str)
         Permission::Extension(_) => ("nexus:extension", ""),
         Permission::McpAccess(_) => ("nexus:mcp", "access"),
         Permission::Credential(_) => ("nexus:credential", "resolve"),
+        Permission::Network(_) => ("nexus:network", "host"),
     }
 }
 
 /// Build RFC 9396 `authorization_details` from a set of granted permissions.
 ///
-/// Only Active grants are included — Deferred and Revoked are excluded.
+/// Only Active grants are included — Deferred, Revoked, expired,
+/// always-prompt, and rate-capped grants are excluded.
 /// Each permission becomes one AuthorizationDetail entry.
 pub fn build_authorization_details(grants: &[GrantedPermission]) -> Vec<AuthorizationDetail> {
     grants
         .iter()
-        .filter(|g| g.state == PermissionState::Active)
+        .filter(|g| {
+            g.effective_state() == PermissionState::Active && !g.always_prompt && g.rate_cap.is_none()
+        })
         .map(|g| permission_to_detail(&g.permission, &g.approved_scopes))
         .collect()
 }
@@ -129,6 +133,20 @@ fn permission_to_detail(
                 identifier: Some(ext_id),
             }
         }
+        Permission::Network(_) => {
+            // Identifier is the host pattern; locations are the approved hosts
+            let locations = approved_scopes
+                .as_ref()
+                .filter(|s| !s.is_empty())
+                .cloned();
+
+            AuthorizationDetail {
+                detail_type: "nexus:network".to_string(),
+                actions: vec!["host".to_string()],
+                locations,
+                identifier: perm.network_host_pattern().map(String::from),
+            }
+        }
         _ => {
             let (detail_type, action) = permission_to_type_action(perm);
             let locations = approved_scopes
@@ -146,6 +164,30 @@ fn permission_to_detail(
     }
 }
 
+/// The authorization detail a single grant contributes to a token.
+pub fn grant_detail(grant: &GrantedPermission) -> AuthorizationDetail {
+    permission_to_detail(&grant.permission, &grant.approved_scopes)
+}
+
+/// Whether `narrow` grants nothing beyond `broad`: same type and identifier,
+/// a subset of its actions, and a subset of its locations (no locations on
+/// `broad` means unrestricted).
+pub fn detail_within(narrow: &AuthorizationDetail, broad: &AuthorizationDetail) -> bool {
+    narrow.detail_type == broad.detail_type
+        && narrow.identifier == broad.identifier
+        && narrow.actions.iter().all(|a| broad.actions.contains(a))
+        && match (&narrow.locations, &broad.locations) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(n), Some(b)) => n.iter().all(|l| b.contains(l)),
+        }
+}
+
+/// Whether every entry of `narrow` is within some entry of `broad`.
+pub fn details_within(narrow: &[AuthorizationDetail], broad: &[AuthorizationDetail]) -> bool {
+    narrow.iter().all(|n| broad.iter().any(|b| detail_within(n, b)))
+}
+
 /// Check whether a set of authorization details satisfies a required Permission.
 ///
 /// This is a **coarse** check: type + action match only. Fine-grained scope
@@ -182,6 +224,14 @@ pub fn details_satisfy(details: &[AuthorizationDetail], required: &Permission) -
                     && d.identifier.as_deref() == Some(ext_id)
             })
         }
+        Permission::Network(_) => {
+            let pattern = required.network_host_pattern();
+            details.iter().any(|d| {
+                d.detail_type == "nexus:network"
+                    && d.actions.iter().any(|a| a == "host")
+                    && d.identifier.as_deref() == pattern
+            })
+        }
         _ => {
             let (required_type, required_action) = permission_to_type_action(required);
             details.iter().any(|d| {
@@ -217,6 +267,9 @@ mod tests {
             approved_scopes: scopes,
             state,
             revoked_at: None,
+            expires_at: None,
+            always_prompt: false,
+            rate_cap: None,
         }
     }
 
@@ -278,6 +331,43 @@ mod tests {
         assert!(value.get("detail_type").is_none());
     }
 
+    // ── detail_within ────────────────────────────────────────────
+
+    fn fs_detail(actions: &[&str], locations: Option<&[&str]>) -> AuthorizationDetail {
+        AuthorizationDetail {
+            detail_type: "nexus:fs".to_string(),
+            actions: actions.iter().map(|a| a.to_string()).collect(),
+            locations: locations.map(|l| l.iter().map(|s| s.to_string()).collect()),
+            identifier: None,
+        }
+    }
+
+    #[test]
+    fn detail_within_subsets() {
+        let broad = fs_detail(&["read", "write"], Some(&["/home", "/tmp"]));
+        assert!(detail_within(&fs_detail(&["read"], Some(&["/home"])), &broad));
+        assert!(detail_within(&broad, &broad));
+        assert!(!detail_within(&fs_detail(&["exec"], Some(&["/home"])), &broad));
+        assert!(!detail_within(&fs_detail(&["read"], Some(&["/etc"])), &broad));
+        // Dropping the location restriction widens the grant
+        assert!(!detail_within(&fs_detail(&["read"], None), &broad));
+        assert!(detail_within(&fs_detail(&["read"], Some(&["/etc"])), &fs_detail(&["read"], None)));
+    }
+
+    #[test]
+    fn details_within_requires_every_entry_covered() {
+        let broad = vec![fs_detail(&["read"], None)];
+        let system = AuthorizationDetail {
+            detail_type: "nexus:system".to_string(),
+            actions: vec!["info".to_string()],
+            locations: None,
+            identifier: None,
+        };
+        assert!(details_within(&[], &broad));
+        assert!(details_within(&[fs_detail(&["read"], Some(&["/x"]))], &broad));
+        assert!(!details_within(&[fs_detail(&["read"], None), system], &broad));
+    }
+
     // ── build_authorization_details ──────────────────────────────
 
     #[test]
@@ -359,6 +449,56 @@ mod tests {
         assert_eq!(details[0].detail_type, "nexus:system");
     }
 
+    #[test]
+    fn build_excludes_expired_grants() {
+        let mut expired = grant(Permission::FilesystemRead, PermissionState::Active, None);
+        expired.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        let mut timed = grant(Permission::SystemInfo, PermissionState::Active, None);
+        timed.expires_at = Some(Utc::now() + chrono::Duration::hours(1));
+
+        let details = build_authorization_details(&[expired, timed]);
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].detail_type, "nexus:system");
+    }
+
+    #[test]
+    fn build_excludes_always_prompt_grants() {
+        let mut prompted = grant(Permission::ProcessExec, PermissionState::Active, None);
+        prompted.always_prompt = true;
+        let plain = grant(Permission::SystemInfo, PermissionState::Active, None);
+
+        let details = build_authorization_details(&[prompted, plain]);
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].detail_type, "nexus:system");
+    }
+
+    #[test]
+    fn build_excludes_rate_capped_grants() {
+        let mut capped = grant(Permission::ProcessExec, PermissionState::Active, None);
+        capped.rate_cap = Some(super::super::types::RateCap { max_calls: 10, per_secs: 60 });
+        let plain = grant(Permission::SystemInfo, PermissionState::Active, None);
+
+        let details = build_authorization_details(&[capped, plain]);
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].detail_type, "nexus:system");
+    }
+
+    #[test]
+    fn network_host_detail_roundtrip() {
+        let perm = Permission::Network("network:*.github.com".into());
+        let details = build_authorization_details(&[grant(
+            perm.clone(),
+            PermissionState::Active,
+            Some(vec!["api.github.com".into()]),
+        )]);
+        assert_eq!(details[0].detail_type, "nexus:network");
+        assert_eq!(details[0].identifier.as_deref(), Some("*.github.com"));
+        assert_eq!(details[0].locations, Some(vec!["api.github.com".to_string()]));
+        assert!(details_satisfy(&details, &perm));
+        assert!(!details_satisfy(&details, &Permission::Network("network:*".into())));
+        assert!(!details_satisfy(&details, &Permission::NetworkInternet));
+    }
+
     #[test]
     fn build_empty_grants() {
         let details = build_authorization_details(&[]);
@@ -1,5 +1,9 @@
+use super::checker::UsageTracker;
 use super::store::PermissionStore;
-use super::types::{GrantedPermission, Permission, PermissionState};
+use super::types::{
+    DenyRule, GrantedPermission, Permission, PermissionChange, PermissionExport, PermissionProfile,
+    PermissionState, PluginPermissions, RateCap,
+};
 use crate::error::NexusResult;
 
 /// Trait for permission operations with interior mutability.
@@ -18,6 +22,8 @@ pub trait PermissionService: Send + Sync {
         plugin_id: &str,
         permission: &Permission,
     ) -> Option<Vec<String>>;
+    /// Grant, revoke, defer, and scope changes for a plugin, oldest first.
+    fn history(&self, plugin_id: &str) -> Vec<PermissionChange>;
 
     // Write operations (interior mutability — &self, not &mut self)
     fn grant(
@@ -26,6 +32,14 @@ pub trait PermissionService: Send + Sync {
         permission: Permission,
         approved_scopes: Option<Vec<String>>,
     ) -> NexusResult<()>;
+    fn grant_until(
+        &self,
+        plugin_id: &str,
+        permission: Permission,
+        approved_scopes: Option<Vec<String>>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> NexusResult<()>;
+    fn prune_expired(&self) -> NexusResult<Vec<GrantedPermission>>;
     fn revoke(&self, plugin_id: &str, permission: &Permission) -> NexusResult<()>;
     fn unrevoke(&self, plugin_id: &str, permission: &Permission) -> NexusResult<()>;
     fn revoke_all(&self, plugin_id: &str) -> NexusResult<()>;
@@ -36,6 +50,26 @@ pub trait PermissionService: Send + Sync {
         approved_scopes: Option<Vec<String>>,
     ) -> NexusResult<()>;
     fn activate(&self, plugin_id: &str, permission: &Permission) -> NexusResult<()>;
+    fn is_always_prompt(&self, plugin_id: &str, permission: &Permission) -> bool;
+    fn set_always_prompt(
+        &self,
+        plugin_id: &str,
+        permission: &Permission,
+        always_prompt: bool,
+    ) -> NexusResult<()>;
+    fn get_rate_cap(&self, plugin_id: &str, permission: &Permission) -> Option<RateCap>;
+    /// Set or clear a grant's usage cap. Starts a fresh usage window.
+    fn set_rate_cap(
+        &self,
+        plugin_id: &str,
+        permission: &Permission,
+        rate_cap: Option<RateCap>,
+    ) -> NexusResult<()>;
+    /// Count one use against the grant's cap. Returns false when the cap is
+    /// reached; always true for uncapped grants.
+    fn consume_use(&self, plugin_id: &str, permission: &Permission) -> bool;
+    /// Start a fresh usage window, e.g. after the user approves going over the cap.
+    fn reset_usage(&self, plugin_id: &str, permission: &Permission);
     fn add_approved_scope(
         &self,
         plugin_id: &str,
@@ -49,6 +83,32 @@ pub trait PermissionService: Send + Sync {
         scope: &str,
     ) -> NexusResult<()>;
 
+    // Deny rules
+    fn is_denied(&self, plugin_id: &str, permission: &Permission, scope: Option<&str>) -> bool;
+    fn list_denies(&self, plugin_id: &str) -> Vec<DenyRule>;
+    fn add_deny(
+        &self,
+        plugin_id: &str,
+        permission: Permission,
+        scope: Option<String>,
+    ) -> NexusResult<()>;
+    fn remove_deny(
+        &self,
+        plugin_id: &str,
+        permission: &Permission,
+        scope: Option<&str>,
+    ) -> NexusResult<()>;
+
+    // Profiles
+    fn list_profiles(&self) -> Vec<PermissionProfile>;
+    fn save_profile(&self, profile: PermissionProfile) -> NexusResult<()>;
+    fn delete_profile(&self, name: &str) -> NexusResult<()>;
+    fn apply_profile(&self, plugin_id: &str, name: &str) -> NexusResult<Vec<GrantedPermission>>;
+
+    // Export / import
+    fn export(&self) -> PermissionExport;
+    fn import_plugin(&self, plugin_id: &str, imported: PluginPermissions) -> NexusResult<()>;
+
     // Aliases (default implementations)
     fn get_approved_paths(
         &self,
@@ -65,12 +125,14 @@ pub trait PermissionService: Send + Sync {
 
 pub struct DefaultPermissionService {
     inner: std::sync::RwLock<PermissionStore>,
+    usage: UsageTracker,
 }
 
 impl DefaultPermissionService {
     pub fn new(store: PermissionStore) -> Self {
         Self {
             inner: std::sync::RwLock::new(store),
+            usage: UsageTracker::default(),
         }
     }
 }
@@ -88,6 +150,10 @@ impl PermissionService for DefaultPermissionService {
         self.inner.read().unwrap().get_state(plugin_id, permission)
     }
 
+    fn history(&self, plugin_id: &str) -> Vec<PermissionChange> {
+        self.inner.read().unwrap().history(plugin_id)
+    }
+
     fn get_approved_scopes(
         &self,
         plugin_id: &str,
@@ -111,6 +177,23 @@ impl PermissionService for DefaultPermissionService {
             .grant(plugin_id, permission, approved_scopes)
     }
 
+    fn grant_until(
+        &self,
+        plugin_id: &str,
+        permission: Permission,
+        approved_scopes: Option<Vec<String>>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> NexusResult<()> {
+        self.inner
+            .write()
+            .unwrap()
+            .grant_until(plugin_id, permission, approved_scopes, expires_at)
+    }
+
+    fn prune_expired(&self) -> NexusResult<Vec<GrantedPermission>> {
+        self.inner.write().unwrap().prune_expired()
+    }
+
     fn revoke(&self, plugin_id: &str, permission: &Permission) -> NexusResult<()> {
         self.inner.write().unwrap().revoke(plugin_id, permission)
     }
@@ -139,6 +222,51 @@ impl PermissionService for DefaultPermissionService {
         self.inner.write().unwrap().activate(plugin_id, permission)
     }
 
+    fn is_always_prompt(&self, plugin_id: &str, permission: &Permission) -> bool {
+        self.inner.read().unwrap().is_always_prompt(plugin_id, permission)
+    }
+
+    fn set_always_prompt(
+        &self,
+        plugin_id: &str,
+        permission: &Permission,
+        always_prompt: bool,
+    ) -> NexusResult<()> {
+        self.inner
+            .write()
+            .unwrap()
+            .set_always_prompt(plugin_id, permission, always_prompt)
+    }
+
+    fn get_rate_cap(&self, plugin_id: &str, permission: &Permission) -> Option<RateCap> {
+        self.inner.read().unwrap().get_rate_cap(plugin_id, permission)
+    }
+
+    fn set_rate_cap(
+        &self,
+        plugin_id: &str,
+        permission: &Permission,
+        rate_cap: Option<RateCap>,
+    ) -> NexusResult<()> {
+        self.inner
+            .write()
+            .unwrap()
+            .set_rate_cap(plugin_id, permission, rate_cap)?;
+        self.usage.reset(plugin_id, permission);
+        Ok(())
+    }
+
+    fn consume_use(&self, plugin_id: &str, permission: &Permission) -> bool {
+        match self.get_rate_cap(plugin_id, permission) {
+            Some(cap) => self.usage.try_use(plugin_id, permission, cap),
+            None => true,
+        }
+    }
+
+    fn reset_usage(&self, plugin_id: &str, permission: &Permission) {
+        self.usage.reset(plugin_id, permission);
+    }
+
     fn add_approved_scope(
         &self,
         plugin_id: &str,
@@ -162,6 +290,56 @@ impl PermissionService for DefaultPermissionService {
             .unwrap()
             .remove_approved_scope(plugin_id, permission, scope)
     }
+
+    fn is_denied(&self, plugin_id: &str, permission: &Permission, scope: Option<&str>) -> bool {
+        self.inner.read().unwrap().is_denied(plugin_id, permission, scope)
+    }
+
+    fn list_denies(&self, plugin_id: &str) -> Vec<DenyRule> {
+        self.inner.read().unwrap().list_denies(plugin_id)
+    }
+
+    fn add_deny(
+        &self,
+        plugin_id: &str,
+        permission: Permission,
+        scope: Option<String>,
+    ) -> NexusResult<()> {
+        self.inner.write().unwrap().add_deny(plugin_id, permission, scope)
+    }
+
+    fn remove_deny(
+        &self,
+        plugin_id: &str,
+        permission: &Permission,
+        scope: Option<&str>,
+    ) -> NexusResult<()> {
+        self.inner.write().unwrap().remove_deny(plugin_id, permission, scope)
+    }
+
+    fn list_profiles(&self) -> Vec<PermissionProfile> {
+        self.inner.read().unwrap().list_profiles()
+    }
+
+    fn save_profile(&self, profile: PermissionProfile) -> NexusResult<()> {
+        self.inner.write().unwrap().save_profile(profile)
+    }
+
+    fn delete_profile(&self, name: &str) -> NexusResult<()> {
+        self.inner.write().unwrap().delete_profile(name)
+    }
+
+    fn apply_profile(&self, plugin_id: &str, name: &str) -> NexusResult<Vec<GrantedPermission>> {
+        self.inner.write().unwrap().apply_profile(plugin_id, name)
+    }
+
+    fn export(&self) -> PermissionExport {
+        self.inner.read().unwrap().export()
+    }
+
+    fn import_plugin(&self, plugin_id: &str, imported: PluginPermissions) -> NexusResult<()> {
+        self.inner.write().unwrap().import_plugin(plugin_id, imported)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -179,18 +357,38 @@ pub mod mock {
         GetGrants(String),
         GetState(String, Permission),
         GetApprovedScopes(String, Permission),
+        History(String),
         Grant(String, Permission),
+        GrantUntil(String, Permission),
+        PruneExpired,
         Revoke(String, Permission),
         Unrevoke(String, Permission),
         RevokeAll(String),
         Defer(String, Permission),
         Activate(String, Permission),
+        IsAlwaysPrompt(String, Permission),
+        SetAlwaysPrompt(String, Permission, bool),
+        GetRateCap(String, Permission),
+        SetRateCap(String, Permission, Option<RateCap>),
+        ConsumeUse(String, Permission),
+        ResetUsage(String, Permission),
         AddApprovedScope(String, Permission, String),
         RemoveApprovedScope(String, Permission, String),
+        IsDenied(String, Permission, Option<String>),
+        ListDenies(String),
+        AddDeny(String, Permission, Option<String>),
+        RemoveDeny(String, Permission, Option<String>),
+        ListProfiles,
+        SaveProfile(String),
+        DeleteProfile(String),
+        ApplyProfile(String, String),
+        Export,
+        ImportPlugin(String),
     }
 
     pub struct MockPermissionService {
         store: std::sync::RwLock<PermissionStore>,
+        usage: UsageTracker,
         calls: Mutex<Vec<PermissionCall>>,
         _dir: tempfile::TempDir,
     }
@@ -201,6 +399,7 @@ pub mod mock {
             let store = PermissionStore::load(dir.path()).unwrap();
             Self {
                 store: std::sync::RwLock::new(store),
+                usage: UsageTracker::default(),
                 calls: Mutex::new(Vec::new()),
                 _dir: dir,
             }
@@ -281,6 +480,11 @@ pub mod mock {
             self.store.read().unwrap().get_state(plugin_id, permission)
         }
 
+        fn history(&self, plugin_id: &str) -> Vec<PermissionChange> {
+            self.record(PermissionCall::History(plugin_id.to_string()));
+            self.store.read().unwrap().history(plugin_id)
+        }
+
         fn get_approved_scopes(
             &self,
             plugin_id: &str,
@@ -312,6 +516,28 @@ pub mod mock {
                 .grant(plugin_id, permission, approved_scopes)
         }
 
+        fn grant_until(
+            &self,
+            plugin_id: &str,
+            permission: Permission,
+            approved_scopes: Option<Vec<String>>,
+            expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> NexusResult<()> {
+            self.record(PermissionCall::GrantUntil(
+                plugin_id.to_string(),
+                permission.clone(),
+            ));
+            self.store
+                .write()
+                .unwrap()
+                .grant_until(plugin_id, permission, approved_scopes, expires_at)
+        }
+
+        fn prune_expired(&self) -> NexusResult<Vec<GrantedPermission>> {
+            self.record(PermissionCall::PruneExpired);
+            self.store.write().unwrap().prune_expired()
+        }
+
         fn revoke(&self, plugin_id: &str, permission: &Permission) -> NexusResult<()> {
             self.record(PermissionCall::Revoke(
                 plugin_id.to_string(),
@@ -357,6 +583,80 @@ pub mod mock {
             self.store.write().unwrap().activate(plugin_id, permission)
         }
 
+        fn is_always_prompt(&self, plugin_id: &str, permission: &Permission) -> bool {
+            self.record(PermissionCall::IsAlwaysPrompt(
+                plugin_id.to_string(),
+                permission.clone(),
+            ));
+            self.store
+                .read()
+                .unwrap()
+                .is_always_prompt(plugin_id, permission)
+        }
+
+        fn set_always_prompt(
+            &self,
+            plugin_id: &str,
+            permission: &Permission,
+            always_prompt: bool,
+        ) -> NexusResult<()> {
+            self.record(PermissionCall::SetAlwaysPrompt(
+                plugin_id.to_string(),
+                permission.clone(),
+                always_prompt,
+            ));
+            self.store
+                .write()
+                .unwrap()
+                .set_always_prompt(plugin_id, permission, always_prompt)
+        }
+
+        fn get_rate_cap(&self, plugin_id: &str, permission: &Permission) -> Option<RateCap> {
+            self.record(PermissionCall::GetRateCap(
+                plugin_id.to_string(),
+                permission.clone(),
+            ));
+            self.store.read().unwrap().get_rate_cap(plugin_id, permission)
+        }
+
+        fn set_rate_cap(
+            &self,
+            plugin_id: &str,
+            permission: &Permission,
+            rate_cap: Option<RateCap>,
+        ) -> NexusResult<()> {
+            self.record(PermissionCall::SetRateCap(
+                plugin_id.to_string(),
+                permission.clone(),
+                rate_cap,
+            ));
+            self.store
+                .write()
+                .unwrap()
+                .set_rate_cap(plugin_id, permission, rate_cap)?;
+            self.usage.reset(plugin_id, permission);
+            Ok(())
+        }
+
+        fn consume_use(&self, plugin_id: &str, permission: &Permission) -> bool {
+            self.record(PermissionCall::ConsumeUse(
+                plugin_id.to_string(),
+                permission.clone(),
+            ));
+            match self.store.read().unwrap().get_rate_cap(plugin_id, permission) {
+                Some(cap) => self.usage.try_use(plugin_id, permission, cap),
+                None => true,
+            }
+        }
+
+        fn reset_usage(&self, plugin_id: &str, permission: &Permission) {
+            self.record(PermissionCall::ResetUsage(
+                plugin_id.to_string(),
+                permission.clone(),
+            ));
+            self.usage.reset(plugin_id, permission);
+        }
+
         fn add_approved_scope(
             &self,
             plugin_id: &str,
@@ -390,6 +690,99 @@ pub mod mock {
                 .unwrap()
                 .remove_approved_scope(plugin_id, permission, scope)
         }
+
+        fn is_denied(
+            &self,
+            plugin_id: &str,
+            permission: &Permission,
+            scope: Option<&str>,
+        ) -> bool {
+            self.record(PermissionCall::IsDenied(
+                plugin_id.to_string(),
+                permission.clone(),
+                scope.map(String::from),
+            ));
+            self.store
+                .read()
+                .unwrap()
+                .is_denied(plugin_id, permission, scope)
+        }
+
+        fn list_denies(&self, plugin_id: &str) -> Vec<DenyRule> {
+            self.record(PermissionCall::ListDenies(plugin_id.to_string()));
+            self.store.read().unwrap().list_denies(plugin_id)
+        }
+
+        fn add_deny(
+            &self,
+            plugin_id: &str,
+            permission: Permission,
+            scope: Option<String>,
+        ) -> NexusResult<()> {
+            self.record(PermissionCall::AddDeny(
+                plugin_id.to_string(),
+                permission.clone(),
+                scope.clone(),
+            ));
+            self.store
+                .write()
+                .unwrap()
+                .add_deny(plugin_id, permission, scope)
+        }
+
+        fn remove_deny(
+            &self,
+            plugin_id: &str,
+            permission: &Permission,
+            scope: Option<&str>,
+        ) -> NexusResult<()> {
+            self.record(PermissionCall::RemoveDeny(
+                plugin_id.to_string(),
+                permission.clone(),
+                scope.map(String::from),
+            ));
+            self.store
+                .write()
+                .unwrap()
+                .remove_deny(plugin_id, permission, scope)
+        }
+
+        fn list_profiles(&self) -> Vec<PermissionProfile> {
+            self.record(PermissionCall::ListProfiles);
+            self.store.read().unwrap().list_profiles()
+        }
+
+        fn save_profile(&self, profile: PermissionProfile) -> NexusResult<()> {
+            self.record(PermissionCall::SaveProfile(profile.name.clone()));
+            self.store.write().unwrap().save_profile(profile)
+        }
+
+        fn delete_profile(&self, name: &str) -> NexusResult<()> {
+            self.record(PermissionCall::DeleteProfile(name.to_string()));
+            self.store.write().unwrap().delete_profile(name)
+        }
+
+        fn apply_profile(
+            &self,
+            plugin_id: &str,
+            name: &str,
+        ) -> NexusResult<Vec<GrantedPermission>> {
+            self.record(PermissionCall::ApplyProfile(
+                plugin_id.to_string(),
+                name.to_string(),
+            ));
+            self.store.write().unwrap().apply_profile(plugin_id, name)
+        }
+
+        fn export(&self) -> PermissionExport {
+            self.record(PermissionCall::Export);
+            self.store.read().unwrap().export()
+        }
+
+        fn import_plugin(&self, plugin_id: &str, imported: PluginPermissions) -> NexusResult<()> {
+            self.record(PermissionCall::ImportPlugin(plugin_id.to_string()));
+            self.store.write().unwrap().import_plugin(plugin_id, imported)
+        }
     }
 }
 
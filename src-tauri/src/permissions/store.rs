@@ -1,52 +1,92 @@
-use super::types::{GrantedPermission, Permission, PermissionState};
-use crate::error::NexusResult;
-use std::collections::HashMap;
-use std::path::PathBuf;
-
-#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+use super::types::{
+    ChangeAction, ChangeActor, DenyRule, ExportedGrant, GrantedPermission, Permission, PermissionChange,
+    PermissionExport, PermissionProfile, PermissionState, PluginPermissions, RateCap, PERMISSION_EXPORT_VERSION,
+};
+use crate::error::{NexusError, NexusResult};
+use crate::state_db::{self, StateDb};
+use std::collections::{BTreeMap, HashMap};
+
+/// History entries kept per plugin; the oldest are dropped first.
+const HISTORY_LIMIT: usize = 500;
+
+/// Grants, profiles, deny rules, and history, kept in `state.db` (one
+/// record per plugin or profile in each).
+#[derive(Debug, serde::Deserialize, Default)]
 pub struct PermissionStore {
     grants: HashMap<String, Vec<GrantedPermission>>,
+    /// Named permission profiles, keyed by name.
+    #[serde(default)]
+    profiles: BTreeMap<String, PermissionProfile>,
+    /// Per-plugin "never allow" rules, keyed by plugin ID.
+    #[serde(default)]
+    denies: HashMap<String, Vec<DenyRule>>,
+    /// Per-plugin log of grant, revoke, defer, and scope changes, oldest first.
+    /// Kept after the plugin's grants are removed.
+    #[serde(default)]
+    history: HashMap<String, Vec<PermissionChange>>,
     #[serde(skip)]
-    path: PathBuf,
+    db: Option<StateDb>,
 }
 
 impl PermissionStore {
+    /// Legacy store, imported into `state.db` on first load.
+    const LEGACY_FILE: &'static str = "permissions.json";
+
     pub fn load(data_dir: &std::path::Path) -> NexusResult<Self> {
-        let path = data_dir.join("permissions.json");
-        if path.exists() {
-            let data = std::fs::read_to_string(&path)?;
-            let mut store: PermissionStore = serde_json::from_str(&data)?;
-            store.path = path;
-
-            // One-time migration: reconcile legacy `revoked_at` with the new `state` field.
-            // Old JSON has `state` defaulting to Active even when `revoked_at` is set.
-            let mut migrated = false;
-            for grants in store.grants.values_mut() {
-                for grant in grants.iter_mut() {
-                    if grant.revoked_at.is_some() && grant.state == PermissionState::Active {
-                        grant.state = PermissionState::Revoked;
-                        migrated = true;
-                    }
+        let db = StateDb::open(data_dir)?;
+        let legacy: Option<PermissionStore> = state_db::legacy_json(data_dir, Self::LEGACY_FILE)?;
+        let imported = legacy.is_some();
+        let mut store = match legacy {
+            Some(store) => store,
+            None => PermissionStore {
+                grants: state_db::from_records(db.load("permission_grants")?)?.into_iter().collect(),
+                profiles: state_db::from_records(db.load("permission_profiles")?)?.into_iter().collect(),
+                denies: state_db::from_records(db.load("permission_denies")?)?.into_iter().collect(),
+                history: state_db::from_records(db.load("permission_history")?)?.into_iter().collect(),
+                db: None,
+            },
+        };
+        store.db = Some(db);
+
+        // One-time migration: reconcile legacy `revoked_at` with the new `state` field.
+        // Old JSON has `state` defaulting to Active even when `revoked_at` is set.
+        let mut migrated = false;
+        for grants in store.grants.values_mut() {
+            for grant in grants.iter_mut() {
+                if grant.revoked_at.is_some() && grant.state == PermissionState::Active {
+                    grant.state = PermissionState::Revoked;
+                    migrated = true;
                 }
             }
-            if migrated {
-                store.save()?;
-                log::info!("Migrated permissions.json to three-state model");
-            }
+        }
+        if migrated {
+            log::info!("Migrated permissions to three-state model");
+        }
+        if imported || migrated {
+            store.save()?;
+        }
+        if imported {
+            state_db::retire_legacy(data_dir, Self::LEGACY_FILE)?;
+        }
 
-            Ok(store)
-        } else {
-            Ok(PermissionStore {
-                grants: HashMap::new(),
-                path,
-            })
+        store.seed_builtin_profiles();
+        Ok(store)
+    }
+
+    /// Add any built-in profile the user hasn't got yet. Edited built-ins are kept.
+    fn seed_builtin_profiles(&mut self) {
+        for profile in PermissionProfile::builtins() {
+            self.profiles.entry(profile.name.clone()).or_insert(profile);
         }
     }
 
     pub fn save(&self) -> NexusResult<()> {
-        let data = serde_json::to_string_pretty(self)?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
-        Ok(())
+        state_db::attached(&self.db)?.save(&[
+            ("permission_grants", state_db::to_records(&self.grants)?),
+            ("permission_profiles", state_db::to_records(&self.profiles)?),
+            ("permission_denies", state_db::to_records(&self.denies)?),
+            ("permission_history", state_db::to_records(&self.history)?),
+        ])
     }
 
     pub fn grant(
@@ -55,24 +95,60 @@ impl PermissionStore {
         permission: Permission,
         approved_scopes: Option<Vec<String>>,
     ) -> NexusResult<()> {
+        self.grant_until(plugin_id, permission, approved_scopes, None)
+    }
+
+    /// Like `grant()`, but the grant lapses at `expires_at`. Re-granting an
+    /// existing permission replaces its expiry (`None` makes it permanent).
+    pub fn grant_until(
+        &mut self,
+        plugin_id: &str,
+        permission: Permission,
+        approved_scopes: Option<Vec<String>>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> NexusResult<()> {
+        // An explicit grant overrides an earlier "never allow" for the permission
+        let undenied = self.remove_deny_rule(plugin_id, &permission, None);
+        if undenied {
+            self.record(plugin_id, PermissionChange::new(ChangeAction::Undeny, permission.clone()));
+        }
         let entry = self.grants.entry(plugin_id.to_string()).or_default();
 
-        if let Some(existing) = entry.iter_mut().find(|g| g.permission == permission) {
+        // Some(previous state) when the grant changed
+        let granted = if let Some(existing) = entry.iter_mut().find(|g| g.permission == permission) {
             // If Deferred or Revoked, transition to Active
-            if existing.state != PermissionState::Active {
+            if existing.state != PermissionState::Active || existing.expires_at != expires_at {
+                let from = existing.state;
                 existing.state = PermissionState::Active;
                 existing.revoked_at = None;
-                self.save()?;
+                existing.expires_at = expires_at;
+                Some(Some(from))
+            } else {
+                None
             }
         } else {
             entry.push(GrantedPermission {
                 plugin_id: plugin_id.to_string(),
-                permission,
+                permission: permission.clone(),
                 granted_at: chrono::Utc::now(),
                 approved_scopes,
                 state: PermissionState::Active,
                 revoked_at: None,
+                expires_at,
+                always_prompt: false,
+                rate_cap: None,
             });
+            Some(None)
+        };
+        if let Some(from) = granted {
+            let mut change =
+                PermissionChange::new(ChangeAction::Grant, permission).transition(from, Some(PermissionState::Active));
+            if let Some(at) = expires_at {
+                change = change.detail(format!("expires {}", at.to_rfc3339()));
+            }
+            self.record(plugin_id, change);
+        }
+        if undenied || granted.is_some() {
             self.save()?;
         }
         Ok(())
@@ -83,8 +159,16 @@ impl PermissionStore {
     pub fn revoke(&mut self, plugin_id: &str, permission: &Permission) -> NexusResult<()> {
         if let Some(entry) = self.grants.get_mut(plugin_id) {
             if let Some(grant) = entry.iter_mut().find(|g| &g.permission == permission) {
+                let from = grant.state;
                 grant.state = PermissionState::Revoked;
                 grant.revoked_at = Some(chrono::Utc::now());
+                if from != PermissionState::Revoked {
+                    self.record(
+                        plugin_id,
+                        PermissionChange::new(ChangeAction::Revoke, permission.clone())
+                            .transition(Some(from), Some(PermissionState::Revoked)),
+                    );
+                }
                 self.save()?;
             }
         }
@@ -93,10 +177,22 @@ impl PermissionStore {
 
     /// Restore a previously revoked permission.
     pub fn unrevoke(&mut self, plugin_id: &str, permission: &Permission) -> NexusResult<()> {
+        if self.remove_deny_rule(plugin_id, permission, None) {
+            self.record(plugin_id, PermissionChange::new(ChangeAction::Undeny, permission.clone()));
+            self.save()?;
+        }
         if let Some(entry) = self.grants.get_mut(plugin_id) {
             if let Some(grant) = entry.iter_mut().find(|g| &g.permission == permission) {
+                let from = grant.state;
                 grant.state = PermissionState::Active;
                 grant.revoked_at = None;
+                if from != PermissionState::Active {
+                    self.record(
+                        plugin_id,
+                        PermissionChange::new(ChangeAction::Restore, permission.clone())
+                            .transition(Some(from), Some(PermissionState::Active)),
+                    );
+                }
                 self.save()?;
             }
         }
@@ -104,17 +200,28 @@ impl PermissionStore {
     }
 
     pub fn revoke_all(&mut self, plugin_id: &str) -> NexusResult<()> {
-        self.grants.remove(plugin_id);
+        for grant in self.grants.remove(plugin_id).unwrap_or_default() {
+            self.record(
+                plugin_id,
+                PermissionChange::new(ChangeAction::Remove, grant.permission).transition(Some(grant.state), None),
+            );
+        }
+        self.denies.remove(plugin_id);
         self.save()?;
         Ok(())
     }
 
-    /// Returns true only for Active permissions.
+    /// Returns true only for Active, unexpired permissions.
     pub fn has_permission(&self, plugin_id: &str, permission: &Permission) -> bool {
+        if self.is_denied(plugin_id, permission, None) {
+            return false;
+        }
         self.grants
             .get(plugin_id)
             .is_some_and(|grants| {
-                grants.iter().any(|g| &g.permission == permission && g.state == PermissionState::Active)
+                grants
+                    .iter()
+                    .any(|g| &g.permission == permission && g.effective_state() == PermissionState::Active)
             })
     }
 
@@ -124,14 +231,41 @@ impl PermissionStore {
     }
 
     /// Returns the permission state for a (plugin_id, permission) pair,
-    /// or None if no grant exists at all.
+    /// or None if no grant exists at all. Expired and denied grants report Revoked.
     pub fn get_state(&self, plugin_id: &str, permission: &Permission) -> Option<PermissionState> {
-        self.grants.get(plugin_id).and_then(|grants| {
+        let state = self.grants.get(plugin_id).and_then(|grants| {
             grants
                 .iter()
                 .find(|g| &g.permission == permission)
-                .map(|g| g.state)
-        })
+                .map(|g| g.effective_state())
+        });
+        if state.is_some() && self.is_denied(plugin_id, permission, None) {
+            return Some(PermissionState::Revoked);
+        }
+        state
+    }
+
+    /// Move every Active grant past its expiry to Revoked. Scopes are kept so
+    /// the user can restore the grant. Returns the grants that lapsed.
+    pub fn prune_expired(&mut self) -> NexusResult<Vec<GrantedPermission>> {
+        let mut expired = Vec::new();
+        for grant in self.grants.values_mut().flatten() {
+            if grant.state == PermissionState::Active && grant.is_expired() {
+                grant.state = PermissionState::Revoked;
+                grant.revoked_at = grant.expires_at.take();
+                expired.push(grant.clone());
+            }
+        }
+        for grant in &expired {
+            let mut change = PermissionChange::new(ChangeAction::Expire, grant.permission.clone())
+                .transition(Some(PermissionState::Active), Some(PermissionState::Revoked));
+            change.actor = ChangeActor::System;
+            self.record(&grant.plugin_id, change);
+        }
+        if !expired.is_empty() {
+            self.save()?;
+        }
+        Ok(expired)
     }
 
     /// Create a grant in Deferred state. Same scope defaults as `grant()` but the
@@ -151,12 +285,19 @@ impl PermissionStore {
 
         entry.push(GrantedPermission {
             plugin_id: plugin_id.to_string(),
-            permission,
+            permission: permission.clone(),
             granted_at: chrono::Utc::now(),
             approved_scopes,
             state: PermissionState::Deferred,
             revoked_at: None,
+            expires_at: None,
+            always_prompt: false,
+            rate_cap: None,
         });
+        self.record(
+            plugin_id,
+            PermissionChange::new(ChangeAction::Defer, permission).transition(None, Some(PermissionState::Deferred)),
+        );
         self.save()?;
         Ok(())
     }
@@ -167,6 +308,11 @@ impl PermissionStore {
             if let Some(grant) = entry.iter_mut().find(|g| &g.permission == permission) {
                 if grant.state == PermissionState::Deferred {
                     grant.state = PermissionState::Active;
+                    self.record(
+                        plugin_id,
+                        PermissionChange::new(ChangeAction::Activate, permission.clone())
+                            .transition(Some(PermissionState::Deferred), Some(PermissionState::Active)),
+                    );
                     self.save()?;
                 }
             }
@@ -174,19 +320,103 @@ impl PermissionStore {
         Ok(())
     }
 
+    /// Approved scopes for a grant. An always-prompt grant reports none
+    /// (`Some([])`), so every scoped call goes through runtime approval; so
+    /// does an always-scoped grant without scopes.
     pub fn get_approved_scopes(&self, plugin_id: &str, permission: &Permission) -> Option<Vec<String>> {
         self.grants.get(plugin_id).and_then(|grants| {
             grants
                 .iter()
                 .find(|g| &g.permission == permission)
-                .and_then(|g| g.approved_scopes.clone())
+                .and_then(|g| {
+                    if g.always_prompt {
+                        Some(Vec::new())
+                    } else if permission.always_scoped() {
+                        Some(g.approved_scopes.clone().unwrap_or_default())
+                    } else {
+                        g.approved_scopes.clone()
+                    }
+                })
         })
     }
 
+    /// Whether the plugin's grant for `permission` asks on every use.
+    pub fn is_always_prompt(&self, plugin_id: &str, permission: &Permission) -> bool {
+        self.grants
+            .get(plugin_id)
+            .and_then(|grants| grants.iter().find(|g| &g.permission == permission))
+            .is_some_and(|g| g.always_prompt)
+    }
+
+    /// Turn "ask every time" on or off for an existing grant. Approved scopes
+    /// are kept and apply again once the flag is cleared.
+    pub fn set_always_prompt(
+        &mut self,
+        plugin_id: &str,
+        permission: &Permission,
+        always_prompt: bool,
+    ) -> NexusResult<()> {
+        let grant = self
+            .grants
+            .get_mut(plugin_id)
+            .and_then(|grants| grants.iter_mut().find(|g| &g.permission == permission))
+            .ok_or_else(|| {
+                NexusError::Other(format!("{} has no grant for {}", plugin_id, permission.as_str()))
+            })?;
+        if grant.always_prompt != always_prompt {
+            grant.always_prompt = always_prompt;
+            self.record(
+                plugin_id,
+                PermissionChange::new(ChangeAction::AlwaysPrompt, permission.clone())
+                    .detail(if always_prompt { "on" } else { "off" }),
+            );
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Usage cap on the plugin's grant for `permission`, if any.
+    pub fn get_rate_cap(&self, plugin_id: &str, permission: &Permission) -> Option<RateCap> {
+        self.grants
+            .get(plugin_id)
+            .and_then(|grants| grants.iter().find(|g| &g.permission == permission))
+            .and_then(|g| g.rate_cap)
+    }
+
+    /// Set or clear the usage cap on an existing grant.
+    pub fn set_rate_cap(
+        &mut self,
+        plugin_id: &str,
+        permission: &Permission,
+        rate_cap: Option<RateCap>,
+    ) -> NexusResult<()> {
+        if let Some(cap) = rate_cap {
+            cap.validate().map_err(NexusError::Other)?;
+        }
+        let grant = self
+            .grants
+            .get_mut(plugin_id)
+            .and_then(|grants| grants.iter_mut().find(|g| &g.permission == permission))
+            .ok_or_else(|| {
+                NexusError::Other(format!("{} has no grant for {}", plugin_id, permission.as_str()))
+            })?;
+        if grant.rate_cap != rate_cap {
+            grant.rate_cap = rate_cap;
+            self.record(
+                plugin_id,
+                PermissionChange::new(ChangeAction::RateCap, permission.clone())
+                    .detail(rate_cap.map_or_else(|| "off".to_string(), |cap| cap.to_string())),
+            );
+            self.save()?;
+        }
+        Ok(())
+    }
+
     /// Add a scope value to the approved_scopes list for a specific permission grant.
     ///
     /// No-op when the grant has `approved_scopes: None` (unrestricted) — adding
-    /// a scope to an unrestricted grant would accidentally restrict it.
+    /// a scope to an unrestricted grant would accidentally restrict it. Always-scoped
+    /// grants are never unrestricted, so `None` there starts a new list.
     pub fn add_approved_scope(
         &mut self,
         plugin_id: &str,
@@ -195,9 +425,16 @@ impl PermissionStore {
     ) -> NexusResult<()> {
         if let Some(grants) = self.grants.get_mut(plugin_id) {
             if let Some(grant) = grants.iter_mut().find(|g| &g.permission == permission) {
+                if grant.approved_scopes.is_none() && permission.always_scoped() {
+                    grant.approved_scopes = Some(Vec::new());
+                }
                 if let Some(ref mut scopes) = grant.approved_scopes {
                     if !scopes.contains(&scope) {
-                        scopes.push(scope);
+                        scopes.push(scope.clone());
+                        self.record(
+                            plugin_id,
+                            PermissionChange::new(ChangeAction::ScopeAdd, permission.clone()).scope(scope),
+                        );
                         self.save()?;
                     }
                 }
@@ -217,7 +454,14 @@ impl PermissionStore {
         if let Some(grants) = self.grants.get_mut(plugin_id) {
             if let Some(grant) = grants.iter_mut().find(|g| &g.permission == permission) {
                 if let Some(ref mut scopes) = grant.approved_scopes {
+                    let before = scopes.len();
                     scopes.retain(|s| s != scope);
+                    if scopes.len() != before {
+                        self.record(
+                            plugin_id,
+                            PermissionChange::new(ChangeAction::ScopeRemove, permission.clone()).scope(scope),
+                        );
+                    }
                     self.save()?;
                 }
             }
@@ -225,6 +469,278 @@ impl PermissionStore {
         Ok(())
     }
 
+    /// Whether a deny rule blocks `permission` (at `scope`, if given) for the plugin.
+    pub fn is_denied(&self, plugin_id: &str, permission: &Permission, scope: Option<&str>) -> bool {
+        self.denies
+            .get(plugin_id)
+            .is_some_and(|rules| rules.iter().any(|r| r.matches(permission, scope)))
+    }
+
+    pub fn list_denies(&self, plugin_id: &str) -> Vec<DenyRule> {
+        self.denies.get(plugin_id).cloned().unwrap_or_default()
+    }
+
+    /// Add a "never allow" rule. A permission-level rule also revokes the
+    /// grant; a scoped rule drops the scope from the approved list.
+    pub fn add_deny(
+        &mut self,
+        plugin_id: &str,
+        permission: Permission,
+        scope: Option<String>,
+    ) -> NexusResult<()> {
+        let rules = self.denies.entry(plugin_id.to_string()).or_default();
+        if rules.iter().any(|r| r.permission == permission && r.scope == scope) {
+            return Ok(());
+        }
+
+        let mut change = PermissionChange::new(ChangeAction::Deny, permission.clone());
+        if let Some(ref s) = scope {
+            change = change.scope(s.clone());
+        }
+        if let Some(grant) = self
+            .grants
+            .get_mut(plugin_id)
+            .and_then(|grants| grants.iter_mut().find(|g| g.permission == permission))
+        {
+            match scope {
+                None => {
+                    if grant.state != PermissionState::Revoked {
+                        change = change.transition(Some(grant.state), Some(PermissionState::Revoked));
+                        grant.state = PermissionState::Revoked;
+                        grant.revoked_at = Some(chrono::Utc::now());
+                    }
+                }
+                Some(ref s) => {
+                    if let Some(ref mut scopes) = grant.approved_scopes {
+                        scopes.retain(|approved| approved != s);
+                    }
+                }
+            }
+        }
+
+        rules.push(DenyRule {
+            permission,
+            scope,
+            created_at: chrono::Utc::now(),
+        });
+        self.record(plugin_id, change);
+        self.save()
+    }
+
+    pub fn remove_deny(
+        &mut self,
+        plugin_id: &str,
+        permission: &Permission,
+        scope: Option<&str>,
+    ) -> NexusResult<()> {
+        if self.remove_deny_rule(plugin_id, permission, scope) {
+            let mut change = PermissionChange::new(ChangeAction::Undeny, permission.clone());
+            if let Some(s) = scope {
+                change = change.scope(s);
+            }
+            self.record(plugin_id, change);
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Drop the rule with exactly this permission and scope. Returns whether one existed.
+    fn remove_deny_rule(&mut self, plugin_id: &str, permission: &Permission, scope: Option<&str>) -> bool {
+        let Some(rules) = self.denies.get_mut(plugin_id) else {
+            return false;
+        };
+        let before = rules.len();
+        rules.retain(|r| !(&r.permission == permission && r.scope.as_deref() == scope));
+        let removed = rules.len() != before;
+        if rules.is_empty() {
+            self.denies.remove(plugin_id);
+        }
+        removed
+    }
+
+    /// Append to the plugin's history, dropping the oldest entries past
+    /// `HISTORY_LIMIT`. Callers save the store afterwards.
+    fn record(&mut self, plugin_id: &str, change: PermissionChange) {
+        let log = self.history.entry(plugin_id.to_string()).or_default();
+        log.push(change);
+        if log.len() > HISTORY_LIMIT {
+            let excess = log.len() - HISTORY_LIMIT;
+            log.drain(..excess);
+        }
+    }
+
+    /// A plugin's permission change history, oldest first.
+    pub fn history(&self, plugin_id: &str) -> Vec<PermissionChange> {
+        self.history.get(plugin_id).cloned().unwrap_or_default()
+    }
+
+    /// Snapshot every plugin's grants and deny rules for export.
+    pub fn export(&self) -> PermissionExport {
+        let mut plugins: BTreeMap<String, PluginPermissions> = BTreeMap::new();
+        for (plugin_id, grants) in &self.grants {
+            plugins.entry(plugin_id.clone()).or_default().grants =
+                grants.iter().map(ExportedGrant::from).collect();
+        }
+        for (plugin_id, rules) in &self.denies {
+            plugins.entry(plugin_id.clone()).or_default().denies = rules.clone();
+        }
+        PermissionExport {
+            version: PERMISSION_EXPORT_VERSION,
+            exported_at: chrono::Utc::now(),
+            plugins,
+        }
+    }
+
+    /// Replace a plugin's grants and deny rules with imported ones. Grants
+    /// missing from the import are removed; existing grants keep their
+    /// `granted_at`. Callers validate the entries first.
+    pub fn import_plugin(&mut self, plugin_id: &str, imported: PluginPermissions) -> NexusResult<()> {
+        let now = chrono::Utc::now();
+        let previous = self.grants.remove(plugin_id).unwrap_or_default();
+        let mut changes = Vec::new();
+
+        let grants: Vec<GrantedPermission> = imported
+            .grants
+            .into_iter()
+            .map(|g| {
+                let old = previous.iter().find(|p| p.permission == g.permission);
+                let new = GrantedPermission {
+                    plugin_id: plugin_id.to_string(),
+                    permission: g.permission,
+                    granted_at: old.map_or(now, |p| p.granted_at),
+                    approved_scopes: g.approved_scopes,
+                    state: g.state,
+                    revoked_at: (g.state == PermissionState::Revoked)
+                        .then(|| old.and_then(|p| p.revoked_at).unwrap_or(now)),
+                    expires_at: g.expires_at,
+                    always_prompt: g.always_prompt,
+                    rate_cap: g.rate_cap,
+                };
+                let unchanged = old.is_some_and(|p| {
+                    p.state == new.state
+                        && p.approved_scopes == new.approved_scopes
+                        && p.expires_at == new.expires_at
+                        && p.always_prompt == new.always_prompt
+                        && p.rate_cap == new.rate_cap
+                });
+                if !unchanged {
+                    changes.push(
+                        PermissionChange::new(ChangeAction::Import, new.permission.clone())
+                            .transition(old.map(|p| p.state), Some(new.state)),
+                    );
+                }
+                new
+            })
+            .collect();
+        for old in &previous {
+            if !grants.iter().any(|g| g.permission == old.permission) {
+                changes.push(
+                    PermissionChange::new(ChangeAction::Import, old.permission.clone())
+                        .transition(Some(old.state), None),
+                );
+            }
+        }
+
+        if !grants.is_empty() {
+            self.grants.insert(plugin_id.to_string(), grants);
+        }
+        if imported.denies.is_empty() {
+            self.denies.remove(plugin_id);
+        } else {
+            self.denies.insert(plugin_id.to_string(), imported.denies);
+        }
+        for change in changes {
+            self.record(plugin_id, change);
+        }
+        self.save()
+    }
+
+    pub fn list_profiles(&self) -> Vec<PermissionProfile> {
+        self.profiles.values().cloned().collect()
+    }
+
+    pub fn get_profile(&self, name: &str) -> Option<PermissionProfile> {
+        self.profiles.get(name).cloned()
+    }
+
+    /// Create a profile or replace an existing one with the same name.
+    /// Whether a profile is built-in is decided by the store, not the caller.
+    pub fn save_profile(&mut self, mut profile: PermissionProfile) -> NexusResult<()> {
+        profile.name = profile.name.trim().to_string();
+        if profile.name.is_empty() {
+            return Err(NexusError::Other("Profile name cannot be empty".into()));
+        }
+        profile.builtin = self.profiles.get(&profile.name).is_some_and(|p| p.builtin);
+        self.profiles.insert(profile.name.clone(), profile);
+        self.save()
+    }
+
+    pub fn delete_profile(&mut self, name: &str) -> NexusResult<()> {
+        match self.profiles.get(name) {
+            Some(p) if p.builtin => Err(NexusError::Other(format!(
+                "Built-in profile '{}' cannot be deleted",
+                name
+            ))),
+            Some(_) => {
+                self.profiles.remove(name);
+                self.save()
+            }
+            None => Err(NexusError::Other(format!("Profile '{}' not found", name))),
+        }
+    }
+
+    /// Apply a profile to a plugin's existing grants: permissions in the
+    /// profile become Active, other Active grants go back to Deferred so they
+    /// prompt on next use. Revoked grants outside the profile and grants with a
+    /// permission-level deny rule are left alone. Returns the grants whose state changed.
+    pub fn apply_profile(&mut self, plugin_id: &str, name: &str) -> NexusResult<Vec<GrantedPermission>> {
+        let profile = self
+            .profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| NexusError::Other(format!("Profile '{}' not found", name)))?;
+
+        let denied: Vec<Permission> = self
+            .denies
+            .get(plugin_id)
+            .map(|rules| rules.iter().filter(|r| r.scope.is_none()).map(|r| r.permission.clone()).collect())
+            .unwrap_or_default();
+
+        let mut changed = Vec::new();
+        let mut changes = Vec::new();
+        if let Some(grants) = self.grants.get_mut(plugin_id) {
+            for grant in grants.iter_mut() {
+                if denied.contains(&grant.permission) {
+                    continue;
+                }
+                let target = if profile.permissions.contains(&grant.permission) {
+                    PermissionState::Active
+                } else if grant.state == PermissionState::Active {
+                    PermissionState::Deferred
+                } else {
+                    continue;
+                };
+                if grant.state != target || grant.expires_at.is_some() {
+                    changes.push(
+                        PermissionChange::new(ChangeAction::Profile, grant.permission.clone())
+                            .transition(Some(grant.state), Some(target))
+                            .detail(name),
+                    );
+                    grant.state = target;
+                    grant.revoked_at = None;
+                    grant.expires_at = None;
+                    changed.push(grant.clone());
+                }
+            }
+        }
+        for change in changes {
+            self.record(plugin_id, change);
+        }
+        if !changed.is_empty() {
+            self.save()?;
+        }
+        Ok(changed)
+    }
 }
 
 #[cfg(test)]
@@ -561,4 +1077,431 @@ mod tests {
             assert_eq!(store.get_state("plug-a", &Permission::NetworkLocal), Some(PermissionState::Revoked));
         }
     }
+
+    // --- Deny rules ---
+
+    #[test]
+    fn permission_deny_revokes_and_blocks() {
+        let (mut store, _dir) = temp_store();
+        store.defer("plug-a", Permission::ProcessExec, None).unwrap();
+        store.add_deny("plug-a", Permission::ProcessExec, None).unwrap();
+
+        assert!(store.is_denied("plug-a", &Permission::ProcessExec, None));
+        assert!(store.is_denied("plug-a", &Permission::ProcessExec, Some("anything")));
+        assert_eq!(store.get_state("plug-a", &Permission::ProcessExec), Some(PermissionState::Revoked));
+        assert!(!store.is_denied("plug-b", &Permission::ProcessExec, None));
+    }
+
+    #[test]
+    fn deny_wins_over_active_grant() {
+        let (mut store, _dir) = temp_store();
+        store.add_deny("plug-a", Permission::SystemInfo, None).unwrap();
+        // Grant created after the deny rule would otherwise be Active
+        store.defer("plug-a", Permission::SystemInfo, None).unwrap();
+        store.activate("plug-a", &Permission::SystemInfo).unwrap();
+        assert!(!store.has_permission("plug-a", &Permission::SystemInfo));
+        assert_eq!(store.get_state("plug-a", &Permission::SystemInfo), Some(PermissionState::Revoked));
+    }
+
+    #[test]
+    fn explicit_grant_clears_permission_deny() {
+        let (mut store, _dir) = temp_store();
+        store.grant("plug-a", Permission::SystemInfo, None).unwrap();
+        store.add_deny("plug-a", Permission::SystemInfo, None).unwrap();
+        store.unrevoke("plug-a", &Permission::SystemInfo).unwrap();
+        assert!(store.has_permission("plug-a", &Permission::SystemInfo));
+        assert!(store.list_denies("plug-a").is_empty());
+    }
+
+    #[test]
+    fn scoped_deny_matches_paths_below() {
+        let (mut store, _dir) = temp_store();
+        store
+            .grant("plug-a", Permission::FilesystemRead, Some(vec!["/home".into(), "/home/secret".into()]))
+            .unwrap();
+        store
+            .add_deny("plug-a", Permission::FilesystemRead, Some("/home/secret".into()))
+            .unwrap();
+
+        assert!(store.has_permission("plug-a", &Permission::FilesystemRead));
+        assert!(store.is_denied("plug-a", &Permission::FilesystemRead, Some("/home/secret/key.pem")));
+        assert!(!store.is_denied("plug-a", &Permission::FilesystemRead, Some("/home/secrets")));
+        assert!(!store.is_denied("plug-a", &Permission::FilesystemRead, None));
+        assert_eq!(
+            store.get_approved_scopes("plug-a", &Permission::FilesystemRead),
+            Some(vec!["/home".to_string()])
+        );
+    }
+
+    #[test]
+    fn always_prompt_hides_approved_scopes() {
+        let (mut store, _dir) = temp_store();
+        store
+            .grant("plug-a", Permission::FilesystemRead, Some(vec!["/home".into()]))
+            .unwrap();
+        store.grant("plug-a", Permission::SystemInfo, None).unwrap();
+
+        store.set_always_prompt("plug-a", &Permission::FilesystemRead, true).unwrap();
+        store.set_always_prompt("plug-a", &Permission::SystemInfo, true).unwrap();
+        assert!(store.is_always_prompt("plug-a", &Permission::FilesystemRead));
+        assert!(store.has_permission("plug-a", &Permission::FilesystemRead));
+        assert_eq!(store.get_approved_scopes("plug-a", &Permission::FilesystemRead), Some(vec![]));
+        assert_eq!(store.get_approved_scopes("plug-a", &Permission::SystemInfo), Some(vec![]));
+
+        store.set_always_prompt("plug-a", &Permission::FilesystemRead, false).unwrap();
+        assert_eq!(
+            store.get_approved_scopes("plug-a", &Permission::FilesystemRead),
+            Some(vec!["/home".to_string()])
+        );
+        assert!(store.set_always_prompt("plug-a", &Permission::ProcessExec, true).is_err());
+    }
+
+    #[test]
+    fn scoped_deny_accepts_glob() {
+        let (mut store, _dir) = temp_store();
+        store
+            .add_deny("plug-a", Permission::FilesystemRead, Some("/home/*/.ssh/**".into()))
+            .unwrap();
+        assert!(store.is_denied("plug-a", &Permission::FilesystemRead, Some("/home/alice/.ssh/id_rsa")));
+        assert!(!store.is_denied("plug-a", &Permission::FilesystemRead, Some("/home/alice/notes.txt")));
+    }
+
+    #[test]
+    fn scoped_deny_is_exact_for_extensions() {
+        let (mut store, _dir) = temp_store();
+        let perm = Permission::Extension("ext:git-ops:status".into());
+        store.add_deny("plug-a", perm.clone(), Some("/repo".into())).unwrap();
+        assert!(store.is_denied("plug-a", &perm, Some("/repo")));
+        assert!(!store.is_denied("plug-a", &perm, Some("/repo/sub")));
+    }
+
+    #[test]
+    fn deny_rules_persist_and_can_be_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut store = PermissionStore::load(dir.path()).unwrap();
+            store.add_deny("plug-a", Permission::NetworkInternet, None).unwrap();
+            store.add_deny("plug-a", Permission::NetworkInternet, None).unwrap();
+        }
+        let mut store = PermissionStore::load(dir.path()).unwrap();
+        assert_eq!(store.list_denies("plug-a").len(), 1);
+        store.remove_deny("plug-a", &Permission::NetworkInternet, None).unwrap();
+        assert!(!store.is_denied("plug-a", &Permission::NetworkInternet, None));
+    }
+
+    // --- Profiles ---
+
+    #[test]
+    fn builtin_profiles_are_seeded() {
+        let (store, _dir) = temp_store();
+        let names: Vec<String> = store.list_profiles().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["sandboxed".to_string(), "trusted".to_string()]);
+        assert!(store.get_profile("trusted").unwrap().builtin);
+    }
+
+    #[test]
+    fn save_profile_persists_and_keeps_builtin_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut store = PermissionStore::load(dir.path()).unwrap();
+            store
+                .save_profile(PermissionProfile {
+                    name: " readers ".into(),
+                    description: String::new(),
+                    permissions: vec![Permission::FilesystemRead],
+                    builtin: true,
+                })
+                .unwrap();
+            store
+                .save_profile(PermissionProfile {
+                    name: "sandboxed".into(),
+                    description: "edited".into(),
+                    permissions: vec![],
+                    builtin: false,
+                })
+                .unwrap();
+        }
+
+        let store = PermissionStore::load(dir.path()).unwrap();
+        let readers = store.get_profile("readers").unwrap();
+        assert!(!readers.builtin, "caller cannot mark a profile built-in");
+        assert_eq!(readers.permissions, vec![Permission::FilesystemRead]);
+        let sandboxed = store.get_profile("sandboxed").unwrap();
+        assert!(sandboxed.builtin);
+        assert_eq!(sandboxed.description, "edited");
+        assert!(sandboxed.permissions.is_empty(), "edited built-in is not re-seeded");
+    }
+
+    #[test]
+    fn builtin_profiles_cannot_be_deleted() {
+        let (mut store, _dir) = temp_store();
+        assert!(store.delete_profile("trusted").is_err());
+        assert!(store.delete_profile("missing").is_err());
+
+        store
+            .save_profile(PermissionProfile {
+                name: "custom".into(),
+                description: String::new(),
+                permissions: vec![],
+                builtin: false,
+            })
+            .unwrap();
+        store.delete_profile("custom").unwrap();
+        assert!(store.get_profile("custom").is_none());
+    }
+
+    #[test]
+    fn apply_profile_activates_and_defers() {
+        let (mut store, _dir) = temp_store();
+        store.defer("plug-a", Permission::SystemInfo, None).unwrap();
+        store.grant("plug-a", Permission::ProcessExec, None).unwrap();
+        store.grant("plug-a", Permission::McpCall, None).unwrap();
+        store.revoke("plug-a", &Permission::McpCall).unwrap();
+
+        let changed = store.apply_profile("plug-a", "sandboxed").unwrap();
+        assert_eq!(changed.len(), 2);
+        assert_eq!(store.get_state("plug-a", &Permission::SystemInfo), Some(PermissionState::Active));
+        assert_eq!(store.get_state("plug-a", &Permission::ProcessExec), Some(PermissionState::Deferred));
+        assert_eq!(store.get_state("plug-a", &Permission::McpCall), Some(PermissionState::Revoked));
+
+        assert!(store.apply_profile("plug-a", "sandboxed").unwrap().is_empty());
+        assert!(store.apply_profile("plug-a", "missing").is_err());
+    }
+
+    #[test]
+    fn apply_profile_skips_denied_permissions() {
+        let (mut store, _dir) = temp_store();
+        store.defer("plug-a", Permission::SystemInfo, None).unwrap();
+        store.add_deny("plug-a", Permission::SystemInfo, None).unwrap();
+        assert!(store.apply_profile("plug-a", "sandboxed").unwrap().is_empty());
+        assert_eq!(
+            store.get_grants("plug-a")[0].state,
+            PermissionState::Revoked
+        );
+    }
+
+    // --- Time-limited grants ---
+
+    #[test]
+    fn timed_grant_active_until_expiry() {
+        let (mut store, _dir) = temp_store();
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        store.grant_until("plug-a", Permission::SystemInfo, None, Some(later)).unwrap();
+        assert!(store.has_permission("plug-a", &Permission::SystemInfo));
+        assert_eq!(store.get_grants("plug-a")[0].expires_at, Some(later));
+    }
+
+    #[test]
+    fn expired_grant_is_rejected() {
+        let (mut store, _dir) = temp_store();
+        let past = chrono::Utc::now() - chrono::Duration::seconds(1);
+        store.grant_until("plug-a", Permission::SystemInfo, None, Some(past)).unwrap();
+        assert!(!store.has_permission("plug-a", &Permission::SystemInfo));
+        assert_eq!(store.get_state("plug-a", &Permission::SystemInfo), Some(PermissionState::Revoked));
+    }
+
+    #[test]
+    fn prune_expired_revokes_and_keeps_scopes() {
+        let (mut store, _dir) = temp_store();
+        let past = chrono::Utc::now() - chrono::Duration::seconds(1);
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        store
+            .grant_until("plug-a", Permission::FilesystemRead, Some(vec!["/a".into()]), Some(past))
+            .unwrap();
+        store.grant_until("plug-a", Permission::SystemInfo, None, Some(later)).unwrap();
+        store.grant("plug-b", Permission::NetworkLocal, None).unwrap();
+
+        let expired = store.prune_expired().unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].permission, Permission::FilesystemRead);
+
+        let grant = store
+            .get_grants("plug-a")
+            .into_iter()
+            .find(|g| g.permission == Permission::FilesystemRead)
+            .unwrap();
+        assert_eq!(grant.state, PermissionState::Revoked);
+        assert_eq!(grant.revoked_at, Some(past));
+        assert_eq!(grant.expires_at, None);
+        assert_eq!(grant.approved_scopes, Some(vec!["/a".to_string()]));
+
+        assert!(store.has_permission("plug-a", &Permission::SystemInfo));
+        assert!(store.has_permission("plug-b", &Permission::NetworkLocal));
+        assert!(store.prune_expired().unwrap().is_empty());
+    }
+
+    #[test]
+    fn regrant_without_expiry_makes_permanent() {
+        let (mut store, _dir) = temp_store();
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        store.grant_until("plug-a", Permission::SystemInfo, None, Some(later)).unwrap();
+        store.grant("plug-a", Permission::SystemInfo, None).unwrap();
+        assert_eq!(store.get_grants("plug-a")[0].expires_at, None);
+    }
+
+    #[test]
+    fn history_records_grant_lifecycle() {
+        let (mut store, _dir) = temp_store();
+        store.defer("plug-a", Permission::FilesystemWrite, Some(vec![])).unwrap();
+        store.activate("plug-a", &Permission::FilesystemWrite).unwrap();
+        store
+            .add_approved_scope("plug-a", &Permission::FilesystemWrite, "/home/u/Documents".into())
+            .unwrap();
+        store.revoke("plug-a", &Permission::FilesystemWrite).unwrap();
+        // No-op changes are not recorded
+        store.revoke("plug-a", &Permission::FilesystemWrite).unwrap();
+
+        let history = store.history("plug-a");
+        let actions: Vec<ChangeAction> = history.iter().map(|c| c.action).collect();
+        assert_eq!(
+            actions,
+            vec![ChangeAction::Defer, ChangeAction::Activate, ChangeAction::ScopeAdd, ChangeAction::Revoke]
+        );
+        assert_eq!(history[2].scope.as_deref(), Some("/home/u/Documents"));
+        assert_eq!(history[3].from, Some(PermissionState::Active));
+        assert_eq!(history[3].to, Some(PermissionState::Revoked));
+        assert!(history.iter().all(|c| c.actor == ChangeActor::User));
+    }
+
+    #[test]
+    fn history_survives_revoke_all_and_reload() {
+        let (mut store, dir) = temp_store();
+        store.grant("plug-a", Permission::SystemInfo, None).unwrap();
+        store.revoke_all("plug-a").unwrap();
+
+        let store = PermissionStore::load(dir.path()).unwrap();
+        let history = store.history("plug-a");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].action, ChangeAction::Remove);
+        assert_eq!(history[1].from, Some(PermissionState::Active));
+        assert_eq!(history[1].to, None);
+    }
+
+    #[test]
+    fn history_marks_expiry_as_system() {
+        let (mut store, _dir) = temp_store();
+        let past = chrono::Utc::now() - chrono::Duration::minutes(1);
+        store.grant_until("plug-a", Permission::SystemInfo, None, Some(past)).unwrap();
+        store.prune_expired().unwrap();
+
+        let last = store.history("plug-a").pop().unwrap();
+        assert_eq!(last.action, ChangeAction::Expire);
+        assert_eq!(last.actor, ChangeActor::System);
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let (mut store, _dir) = temp_store();
+        store.grant("plug-a", Permission::SystemInfo, None).unwrap();
+        for _ in 0..HISTORY_LIMIT {
+            store.revoke("plug-a", &Permission::SystemInfo).unwrap();
+            store.unrevoke("plug-a", &Permission::SystemInfo).unwrap();
+        }
+        let history = store.history("plug-a");
+        assert_eq!(history.len(), HISTORY_LIMIT);
+        assert_eq!(history[0].action, ChangeAction::Revoke);
+    }
+
+    #[test]
+    fn export_import_roundtrip() {
+        let (mut source, _dir) = temp_store();
+        source
+            .grant("plug-a", Permission::FilesystemRead, Some(vec!["/home/u/docs".into()]))
+            .unwrap();
+        source.grant("plug-a", Permission::SystemInfo, None).unwrap();
+        source.revoke("plug-a", &Permission::SystemInfo).unwrap();
+        source.add_deny("plug-a", Permission::FilesystemWrite, None).unwrap();
+        let export = source.export();
+        assert_eq!(export.version, PERMISSION_EXPORT_VERSION);
+
+        let json = serde_json::to_string_pretty(&export).unwrap();
+        let parsed: PermissionExport = serde_json::from_str(&json).unwrap();
+
+        let (mut target, _dir2) = temp_store();
+        target.grant("plug-a", Permission::ProcessList, None).unwrap();
+        target
+            .import_plugin("plug-a", parsed.plugins["plug-a"].clone())
+            .unwrap();
+
+        assert!(target.has_permission("plug-a", &Permission::FilesystemRead));
+        assert_eq!(
+            target.get_approved_scopes("plug-a", &Permission::FilesystemRead),
+            Some(vec!["/home/u/docs".to_string()])
+        );
+        assert_eq!(target.get_state("plug-a", &Permission::SystemInfo), Some(PermissionState::Revoked));
+        assert_eq!(target.get_state("plug-a", &Permission::ProcessList), None);
+        assert!(target.is_denied("plug-a", &Permission::FilesystemWrite, None));
+
+        let history = target.history("plug-a");
+        let removed = history
+            .iter()
+            .find(|c| c.action == ChangeAction::Import && c.permission == Permission::ProcessList)
+            .unwrap();
+        assert_eq!(removed.to, None);
+    }
+
+    #[test]
+    fn import_validation_drops_bad_entries() {
+        let mut entry: PluginPermissions = serde_json::from_value(serde_json::json!({
+            "grants": [
+                {"permission": "filesystem:read", "state": "active", "approved_scopes": ["relative/dir"]},
+                {"permission": "system:info", "state": "active", "approved_scopes": null},
+                {"permission": "system:info", "state": "revoked", "approved_scopes": null},
+                {"permission": "container:manage", "state": "active", "approved_scopes": null}
+            ],
+            "denies": [{"permission": "network:internet", "scope": "https://x"}]
+        }))
+        .unwrap();
+        let problems = entry.retain_valid(&[
+            Permission::FilesystemRead,
+            Permission::SystemInfo,
+            Permission::NetworkInternet,
+        ]);
+        assert_eq!(problems.len(), 4);
+        assert_eq!(entry.grants.len(), 1);
+        assert_eq!(entry.grants[0].state, PermissionState::Active);
+        assert!(entry.denies.is_empty());
+    }
+
+    #[test]
+    fn rate_cap_set_and_clear() {
+        let (mut store, _dir) = temp_store();
+        let cap = RateCap { max_calls: 10, per_secs: 60 };
+        assert!(store.set_rate_cap("plug-a", &Permission::ProcessExec, Some(cap)).is_err());
+
+        store.grant("plug-a", Permission::ProcessExec, None).unwrap();
+        assert!(store
+            .set_rate_cap("plug-a", &Permission::ProcessExec, Some(RateCap { max_calls: 0, per_secs: 60 }))
+            .is_err());
+        store.set_rate_cap("plug-a", &Permission::ProcessExec, Some(cap)).unwrap();
+        assert_eq!(store.get_rate_cap("plug-a", &Permission::ProcessExec), Some(cap));
+        assert_eq!(store.history("plug-a").last().unwrap().detail.as_deref(), Some("10/60s"));
+
+        store.set_rate_cap("plug-a", &Permission::ProcessExec, None).unwrap();
+        assert_eq!(store.get_rate_cap("plug-a", &Permission::ProcessExec), None);
+    }
+
+    #[test]
+    fn process_exec_is_never_unrestricted() {
+        let (mut store, _dir) = temp_store();
+        store.grant("plug-a", Permission::ProcessExec, None).unwrap();
+        assert_eq!(
+            store.get_approved_scopes("plug-a", &Permission::ProcessExec),
+            Some(vec![])
+        );
+
+        store
+            .add_approved_scope("plug-a", &Permission::ProcessExec, "git *".into())
+            .unwrap();
+        assert_eq!(
+            store.get_approved_scopes("plug-a", &Permission::ProcessExec),
+            Some(vec!["git *".to_string()])
+        );
+
+        store
+            .add_deny("plug-a", Permission::ProcessExec, Some("git push *".into()))
+            .unwrap();
+        assert!(store.is_denied("plug-a", &Permission::ProcessExec, Some("git push origin main")));
+        assert!(!store.is_denied("plug-a", &Permission::ProcessExec, Some("git status")));
+    }
 }
@@ -42,6 +42,10 @@ pub enum Permission {
     /// Credential vending: "credential:{ext_id}".
     /// Grants access to credentials provided by a credential provider extension.
     Credential(String),
+    /// Host-scoped network egress: "network:{host-pattern}", e.g.
+    /// "network:api.github.com" or "network:*.github.com". Each matching host
+    /// is approved at first use (see `net_scope`).
+    Network(String),
 }
 
 /// All known permission string values (excluding dynamic Extension/McpAccess variants).
@@ -75,9 +79,25 @@ impl Permission {
             Permission::Extension(s) => s.as_str(),
             Permission::McpAccess(s) => s.as_str(),
             Permission::Credential(s) => s.as_str(),
+            Permission::Network(s) => s.as_str(),
         }
     }
 
+    /// The host pattern of a `network:{host-pattern}` permission.
+    pub fn network_host_pattern(&self) -> Option<&str> {
+        match self {
+            Permission::Network(s) => s.strip_prefix("network:"),
+            _ => None,
+        }
+    }
+
+    /// Permissions that are never unrestricted: a grant without approved
+    /// scopes allows nothing until the user approves one. For `process:exec`
+    /// the scopes are command patterns (see `cmd_scope`).
+    pub fn always_scoped(&self) -> bool {
+        matches!(self, Permission::ProcessExec)
+    }
+
     pub fn risk_level(&self) -> &'static str {
         match self {
             Permission::SystemInfo => "low",
@@ -95,6 +115,7 @@ impl Permission {
             Permission::Extension(_) => "medium",
             Permission::McpAccess(_) => "medium",
             Permission::Credential(_) => "high",
+            Permission::Network(_) => "medium",
         }
     }
 
@@ -113,6 +134,7 @@ impl Permission {
             Permission::Extension(s) => s.as_str(),
             Permission::McpAccess(s) => s.as_str(),
             Permission::Credential(s) => s.as_str(),
+            Permission::Network(s) => s.as_str(),
         }
     }
 }
@@ -146,6 +168,10 @@ impl<'de> Deserialize<'de> for Permission {
             _ if s.starts_with("credential:") => Ok(Permission::Credential(s)),
             _ if s.starts_with("ext:") => Ok(Permission::Extension(s)),
             _ if s.starts_with("mcp:") => Ok(Permission::McpAccess(s)),
+            _ if s.starts_with("network:") => {
+                super::net_scope::validate(&s["network:".len()..]).map_err(serde::de::Error::custom)?;
+                Ok(Permission::Network(s))
+            }
             _ => Err(serde::de::Error::unknown_variant(&s, KNOWN_PERMISSIONS)),
         }
     }
@@ -241,6 +267,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn network_host_permission_roundtrip() {
+        let perm: Permission = serde_json::from_value(serde_json::json!("network:*.github.com")).unwrap();
+        assert_eq!(perm, Permission::Network("network:*.github.com".into()));
+        assert_eq!(perm.network_host_pattern(), Some("*.github.com"));
+        assert_eq!(serde_json::to_value(&perm).unwrap(), serde_json::json!("network:*.github.com"));
+
+        // Class permissions keep their own variants
+        let local: Permission = serde_json::from_value(serde_json::json!("network:local")).unwrap();
+        assert_eq!(local, Permission::NetworkLocal);
+
+        assert!(serde_json::from_value::<Permission>(serde_json::json!("network:https://x.com")).is_err());
+    }
+
     #[test]
     fn credential_permission_roundtrip() {
         let perm = Permission::Credential("credential:aws-credentials".to_string());
@@ -354,4 +394,332 @@ pub struct GrantedPermission {
     /// to Revoked, cleared when transitioning to Active. `state` is the source of truth.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time-limited grants stop counting as Active once this passes. The
+    /// background pruner then moves them to Revoked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Ask the user on every use, even while Active and for scopes that were
+    /// already approved. Such grants are left out of token authorization
+    /// details so every request reaches the checker.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub always_prompt: bool,
+    /// Usage cap. Going over it asks the user again. Capped grants are also
+    /// left out of token authorization details so every call is counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_cap: Option<RateCap>,
+}
+
+/// At most `max_calls` uses of a grant in any `per_secs`-second window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateCap {
+    pub max_calls: u32,
+    pub per_secs: u64,
+}
+
+impl RateCap {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_calls == 0 || self.per_secs == 0 {
+            return Err("rate cap needs at least one call per at least one second".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn window(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.per_secs)
+    }
+}
+
+impl std::fmt::Display for RateCap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}s", self.max_calls, self.per_secs)
+    }
+}
+
+impl GrantedPermission {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|at| at <= chrono::Utc::now())
+    }
+
+    /// State as seen by permission checks: an Active grant past its expiry
+    /// is treated as Revoked even before the pruner has run.
+    pub fn effective_state(&self) -> PermissionState {
+        if self.state == PermissionState::Active && self.is_expired() {
+            PermissionState::Revoked
+        } else {
+            self.state
+        }
+    }
+}
+
+/// "Never allow" rule for a plugin. A rule without a scope blocks the whole
+/// permission; a scoped rule blocks one scope value (for filesystem
+/// permissions, a directory and everything under it). Matching requests are
+/// denied without prompting the user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DenyRule {
+    pub permission: Permission,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Defaults to now for hand-written import files.
+    #[serde(default = "chrono::Utc::now")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DenyRule {
+    /// Whether this rule blocks `permission` for the requested scope value.
+    /// Permission-level rules match every scope; scoped rules never match an
+    /// unscoped request.
+    pub fn matches(&self, permission: &Permission, scope: Option<&str>) -> bool {
+        if &self.permission != permission {
+            return false;
+        }
+        match (&self.scope, scope) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(denied), Some(requested)) => match permission {
+                Permission::FilesystemRead | Permission::FilesystemWrite => {
+                    super::fs_scope::covers(denied, std::path::Path::new(requested))
+                }
+                Permission::NetworkLocal | Permission::NetworkInternet | Permission::Network(_) => {
+                    super::net_scope::host_matches(denied, requested)
+                }
+                Permission::ProcessExec => super::cmd_scope::covers_line(denied, requested),
+                _ => denied == requested,
+            },
+        }
+    }
+}
+
+/// Who caused a permission change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeActor {
+    /// The user, from the UI or an approval prompt.
+    User,
+    /// Nexus itself, e.g. a time-limited grant lapsing.
+    System,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Grant,
+    Revoke,
+    Restore,
+    Defer,
+    Activate,
+    Expire,
+    /// The grant was dropped with the rest of the plugin's permissions.
+    Remove,
+    ScopeAdd,
+    ScopeRemove,
+    Deny,
+    Undeny,
+    AlwaysPrompt,
+    RateCap,
+    Profile,
+    /// The grant was replaced by a permission import.
+    Import,
+}
+
+/// One entry in a plugin's permission history. `from` and `to` are the
+/// grant's state before and after the change (`None` = no grant), so the UI
+/// can render a diff.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionChange {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub actor: ChangeActor,
+    pub action: ChangeAction,
+    pub permission: Permission,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<PermissionState>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<PermissionState>,
+    /// Extra context: the new expiry, the profile name, the always-prompt
+    /// flag, or the rate cap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl PermissionChange {
+    pub fn new(action: ChangeAction, permission: Permission) -> Self {
+        PermissionChange {
+            at: chrono::Utc::now(),
+            actor: ChangeActor::User,
+            action,
+            permission,
+            scope: None,
+            from: None,
+            to: None,
+            detail: None,
+        }
+    }
+
+    pub fn transition(mut self, from: Option<PermissionState>, to: Option<PermissionState>) -> Self {
+        self.from = from;
+        self.to = to;
+        self
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Format version written by `permission_export`. Imports reject newer files.
+pub const PERMISSION_EXPORT_VERSION: u32 = 1;
+
+/// Grants, approved scopes, and deny rules for every plugin, in a form meant
+/// to be reviewed by hand and imported on another machine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionExport {
+    pub version: u32,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    /// Keyed by plugin ID.
+    pub plugins: std::collections::BTreeMap<String, PluginPermissions>,
+}
+
+/// One plugin's section of a `PermissionExport`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PluginPermissions {
+    #[serde(default)]
+    pub grants: Vec<ExportedGrant>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denies: Vec<DenyRule>,
+}
+
+/// A grant without its bookkeeping timestamps. `approved_scopes` is always
+/// written out, since `null` (unrestricted) and `[]` (prompt for everything)
+/// mean very different things.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedGrant {
+    pub permission: Permission,
+    #[serde(default)]
+    pub state: PermissionState,
+    pub approved_scopes: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub always_prompt: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_cap: Option<RateCap>,
+}
+
+impl From<&GrantedPermission> for ExportedGrant {
+    fn from(g: &GrantedPermission) -> Self {
+        ExportedGrant {
+            permission: g.permission.clone(),
+            state: g.state,
+            approved_scopes: g.approved_scopes.clone(),
+            expires_at: g.expires_at,
+            always_prompt: g.always_prompt,
+            rate_cap: g.rate_cap,
+        }
+    }
+}
+
+impl PluginPermissions {
+    /// Drop entries that can't be applied to a plugin declaring `declared`:
+    /// grants for permissions it doesn't request, duplicate grants, and
+    /// malformed filesystem or host scopes. Returns why each entry was dropped.
+    pub fn retain_valid(&mut self, declared: &[Permission]) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut seen: Vec<Permission> = Vec::new();
+        self.grants.retain(|g| {
+            let problem = if !declared.contains(&g.permission) {
+                Some("not requested by the plugin".to_string())
+            } else if seen.contains(&g.permission) {
+                Some("duplicate grant".to_string())
+            } else if let Some(Err(e)) = g.rate_cap.map(|cap| cap.validate()) {
+                Some(e)
+            } else {
+                g.approved_scopes
+                    .iter()
+                    .flatten()
+                    .find_map(|s| validate_scope(&g.permission, s).err())
+            };
+            match problem {
+                Some(p) => {
+                    problems.push(format!("{}: {}", g.permission.as_str(), p));
+                    false
+                }
+                None => {
+                    seen.push(g.permission.clone());
+                    true
+                }
+            }
+        });
+        self.denies.retain(|r| {
+            match r.scope.as_deref().map(|s| validate_scope(&r.permission, s)) {
+                Some(Err(e)) => {
+                    problems.push(format!("deny {}: {}", r.permission.as_str(), e));
+                    false
+                }
+                _ => true,
+            }
+        });
+        problems
+    }
+}
+
+/// Check a scope value's syntax for permissions whose scopes have one.
+fn validate_scope(permission: &Permission, scope: &str) -> Result<(), String> {
+    match permission {
+        Permission::FilesystemRead | Permission::FilesystemWrite => super::fs_scope::validate(scope),
+        Permission::NetworkLocal | Permission::NetworkInternet | Permission::Network(_) => {
+            super::net_scope::validate(scope)
+        }
+        Permission::ProcessExec => super::cmd_scope::validate(scope),
+        _ => Ok(()),
+    }
+}
+
+/// A named set of permissions applied in one step, at install time or to an
+/// installed plugin. Requested permissions outside the profile are deferred.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionProfile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub permissions: Vec<Permission>,
+    /// Built-in profiles can be edited but not deleted.
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+impl PermissionProfile {
+    /// Profiles seeded into every permission store.
+    pub fn builtins() -> Vec<PermissionProfile> {
+        vec![
+            PermissionProfile {
+                name: "trusted".into(),
+                description: "Every built-in permission except process execution and blanket MCP access".into(),
+                permissions: vec![
+                    Permission::SystemInfo,
+                    Permission::FilesystemRead,
+                    Permission::FilesystemWrite,
+                    Permission::ProcessList,
+                    Permission::ContainerRead,
+                    Permission::ContainerManage,
+                    Permission::NetworkLocal,
+                    Permission::NetworkInternet,
+                ],
+                builtin: true,
+            },
+            PermissionProfile {
+                name: "sandboxed".into(),
+                description: "System info only; everything else prompts on first use".into(),
+                permissions: vec![Permission::SystemInfo],
+                builtin: true,
+            },
+        ]
+    }
 }
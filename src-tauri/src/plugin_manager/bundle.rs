@@ -0,0 +1,262 @@
+//! Offline registry bundles: the registry entries the marketplace knows
+//! about and the manifests they point at, in one file. Importing a bundle on
+//! an air-gapped machine unpacks it as a local registry, so the marketplace
+//! lists and installs from it without reaching the original registries.
+//! Plugin images and extension binaries are not included; they must be
+//! available on the machine already.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::registry::{
+    self, ExtensionRegistryEntry, Registry, RegistryAuth, RegistryEntry, RegistryKind,
+    RegistryMeta, RegistrySource, RegistryStore, RegistryTrust,
+};
+use crate::error::{NexusError, NexusResult};
+
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Directory under the data dir that bundles are unpacked into.
+const BUNDLES_DIR: &str = "registry_bundles";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub plugins: Vec<RegistryEntry>,
+    #[serde(default)]
+    pub extensions: Vec<ExtensionRegistryEntry>,
+    /// Manifest URL → manifest file as served, byte for byte, so signatures
+    /// and digests still verify after import.
+    pub manifests: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleImportReport {
+    pub registry_id: String,
+    pub plugins: usize,
+    pub extensions: usize,
+    /// IDs of entries left out because the bundle has no manifest for them.
+    pub skipped: Vec<String>,
+}
+
+/// Package registry entries with their manifests. `auth` maps manifest URLs
+/// to the credentials to fetch them with. Entries whose manifest can't be
+/// fetched are left out; their manifest URLs are returned with the bundle.
+pub async fn export(
+    plugins: Vec<RegistryEntry>,
+    extensions: Vec<ExtensionRegistryEntry>,
+    auth: &HashMap<String, RegistryAuth>,
+) -> (RegistryBundle, Vec<String>) {
+    let mut manifests = BTreeMap::new();
+    let mut failed = Vec::new();
+    let urls = plugins
+        .iter()
+        .map(|e| &e.manifest_url)
+        .chain(extensions.iter().map(|e| &e.manifest_url));
+    for url in urls {
+        if manifests.contains_key(url) || failed.contains(url) {
+            continue;
+        }
+        match registry::fetch_manifest_text(url, auth.get(url)).await {
+            Ok(text) => {
+                manifests.insert(url.clone(), text);
+            }
+            Err(e) => {
+                log::warn!("Leaving {} out of the registry bundle: {}", url, e);
+                failed.push(url.clone());
+            }
+        }
+    }
+
+    let bundle = RegistryBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        plugins: plugins
+            .into_iter()
+            .filter(|e| manifests.contains_key(&e.manifest_url))
+            .collect(),
+        extensions: extensions
+            .into_iter()
+            .filter(|e| manifests.contains_key(&e.manifest_url))
+            .collect(),
+        manifests,
+    };
+    (bundle, failed)
+}
+
+/// Unpack a bundle under the data dir and add it as a local registry named
+/// `name`. Importing again under the same name replaces the earlier import.
+pub fn import(
+    bundle: RegistryBundle,
+    name: &str,
+    data_dir: &Path,
+    store: &mut RegistryStore,
+) -> NexusResult<BundleImportReport> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(NexusError::Other(format!(
+            "Registry bundle version {} is newer than supported version {}",
+            bundle.version, BUNDLE_VERSION
+        )));
+    }
+    let slug = registry::source_id(name);
+    if slug.is_empty() {
+        return Err(NexusError::Other("Registry name is required".to_string()));
+    }
+    let id = format!("bundle-{}", slug);
+    let dir = data_dir.join(BUNDLES_DIR).join(&id);
+    let url = dir.display().to_string();
+    let existing = store.list().iter().find(|s| s.id == id);
+    if existing.is_some_and(|s| s.url != url) {
+        return Err(NexusError::Other(format!("Registry '{}' already exists", id)));
+    }
+    let is_new = existing.is_none();
+
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    let manifests_dir = dir.join("manifests");
+    std::fs::create_dir_all(&manifests_dir)?;
+
+    // Manifests are stored under a hash of their URL and the entries point
+    // at them relative to the index, which the local registry resolves
+    let mut files = HashMap::new();
+    for (manifest_url, text) in &bundle.manifests {
+        let digest = format!("{:x}", Sha256::digest(manifest_url.as_bytes()));
+        let file = format!("manifests/{}.json", &digest[..16]);
+        std::fs::write(dir.join(&file), text)?;
+        files.insert(manifest_url.as_str(), file);
+    }
+
+    let mut skipped = Vec::new();
+    let mut plugins = Vec::new();
+    for mut entry in bundle.plugins {
+        let Some(file) = files.get(entry.manifest_url.as_str()) else {
+            skipped.push(entry.id);
+            continue;
+        };
+        entry.manifest_url = file.clone();
+        // Paths on the exporting machine mean nothing here
+        entry.build_context = None;
+        entry.changelog_url = entry.changelog_url.filter(|u| !u.starts_with("file://"));
+        plugins.push(entry);
+    }
+    let mut extensions = Vec::new();
+    for mut entry in bundle.extensions {
+        let Some(file) = files.get(entry.manifest_url.as_str()) else {
+            skipped.push(entry.id);
+            continue;
+        };
+        entry.manifest_url = file.clone();
+        entry.changelog_url = entry.changelog_url.filter(|u| !u.starts_with("file://"));
+        extensions.push(entry);
+    }
+
+    let report = BundleImportReport {
+        registry_id: id.clone(),
+        plugins: plugins.len(),
+        extensions: extensions.len(),
+        skipped,
+    };
+    let index = Registry {
+        version: 2,
+        updated_at: bundle.exported_at.to_rfc3339(),
+        plugins,
+        extensions,
+        registry: Some(RegistryMeta {
+            name: name.to_string(),
+            description: format!(
+                "Offline bundle exported {}",
+                bundle.exported_at.format("%Y-%m-%d")
+            ),
+            homepage: None,
+            maintainer: None,
+        }),
+        collections: Vec::new(),
+    };
+    std::fs::write(dir.join("index.json"), serde_json::to_string_pretty(&index)?)?;
+
+    if is_new {
+        store.add(RegistrySource {
+            id,
+            name: name.to_string(),
+            kind: RegistryKind::Local,
+            url,
+            enabled: true,
+            trust: RegistryTrust::Community,
+        })?;
+    }
+    Ok(report)
+}
+
+/// Remove the unpacked files of an imported bundle, if `id` is one.
+pub fn remove_files(data_dir: &Path, id: &str) {
+    let dir = data_dir.join(BUNDLES_DIR).join(id);
+    if id.starts_with("bundle-") && dir.exists() {
+        if let Err(e) = std::fs::remove_dir_all(&dir) {
+            log::warn!("Failed to remove registry bundle {}: {}", dir.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin_manager::registry::RegistryCache;
+
+    fn entry(id: &str, manifest_url: &str) -> RegistryEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "name": id, "version": "1.0.0", "description": "",
+            "image": "img", "manifest_url": manifest_url,
+            "build_context": "/home/exporter/src",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn exported_bundle_imports_as_a_local_registry() {
+        let src = tempfile::tempdir().unwrap();
+        let manifest = "{ \"id\": \"com.a\" }\n";
+        std::fs::write(src.path().join("a.json"), manifest).unwrap();
+        let present = format!("file://{}", src.path().join("a.json").display());
+        let missing = format!("file://{}", src.path().join("gone.json").display());
+
+        let (bundle, failed) = export(
+            vec![entry("com.a", &present), entry("com.b", &missing)],
+            Vec::new(),
+            &HashMap::new(),
+        )
+        .await;
+        assert_eq!(failed, [missing]);
+        assert_eq!(bundle.plugins.len(), 1);
+        let data = serde_json::to_string(&bundle).unwrap();
+
+        let dest = tempfile::tempdir().unwrap();
+        let mut store = RegistryStore::load(dest.path()).unwrap();
+        store.toggle("nexus-community", false).unwrap();
+        let bundle: RegistryBundle = serde_json::from_str(&data).unwrap();
+        let report = import(bundle, "Lab Mirror", dest.path(), &mut store).unwrap();
+        assert_eq!(report.registry_id, "bundle-lab-mirror");
+        assert_eq!(report.plugins, 1);
+
+        let (result, _) =
+            registry::fetch_all_conditional(&store, &RegistryCache::default()).await;
+        assert_eq!(result.plugins.len(), 1);
+        let imported = &result.plugins[0];
+        assert_eq!(imported.source, "Lab Mirror");
+        assert!(imported.build_context.is_none());
+        let text = registry::fetch_manifest_text(&imported.manifest_url, None)
+            .await
+            .unwrap();
+        assert_eq!(text, manifest);
+
+        // Importing again under the same name replaces the earlier import
+        let bundle: RegistryBundle = serde_json::from_str(&data).unwrap();
+        import(bundle, "Lab Mirror", dest.path(), &mut store).unwrap();
+        assert_eq!(store.list().iter().filter(|s| s.id == "bundle-lab-mirror").count(), 1);
+    }
+}
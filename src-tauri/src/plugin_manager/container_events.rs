@@ -2,9 +2,11 @@
 //!
 //! Subscribes to the container runtime's event stream and emits lifecycle
 //! events when external state changes are detected (e.g. `docker stop`,
-//! OOM kill, crash). Reconnects with exponential backoff on stream errors.
+//! OOM kill, crash). Reconnects with exponential backoff on stream errors,
+//! and resubscribes when the container engine is switched.
 
 use crate::lifecycle_events::{self, LifecycleEvent};
+use crate::notification::NotificationAction;
 use crate::plugin_manager::storage::PluginStatus;
 use crate::runtime::{ContainerEvent, ContainerEventAction, ContainerRuntime, ContainerState};
 use crate::AppState;
@@ -14,12 +16,26 @@ use std::time::Duration;
 
 /// Spawn a background task that subscribes to container events and emits
 /// lifecycle events when external state changes are detected.
-pub fn spawn(app: tauri::AppHandle, state: AppState, runtime: Arc<dyn ContainerRuntime>) {
+pub fn spawn(app: tauri::AppHandle, state: AppState) {
     tauri::async_runtime::spawn(async move {
         let mut backoff = Duration::from_secs(1);
 
         loop {
-            match run_event_loop(&app, &state, &runtime).await {
+            let (runtime, mut engine_changed) = {
+                let mgr = state.read().await;
+                (mgr.runtime.clone(), mgr.engine_version_rx.clone())
+            };
+            engine_changed.borrow_and_update();
+
+            let result = tokio::select! {
+                result = run_event_loop(&app, &state, &runtime) => result,
+                _ = engine_changed.changed() => {
+                    log::info!("Container engine changed, resubscribing to events");
+                    backoff = Duration::from_secs(1);
+                    continue;
+                }
+            };
+            match result {
                 Ok(()) => {
                     log::warn!("Container event stream ended, reconnecting...");
                     backoff = Duration::from_secs(1);
@@ -31,8 +47,13 @@ pub fn spawn(app: tauri::AppHandle, state: AppState, runtime: Arc<dyn ContainerR
                     );
                 }
             }
-            tokio::time::sleep(backoff).await;
-            backoff = (backoff * 2).min(Duration::from_secs(30));
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {
+                    backoff = (backoff * 2).min(Duration::from_secs(30));
+                }
+                // Don't wait out the backoff of an engine that's been replaced
+                _ = engine_changed.changed() => backoff = Duration::from_secs(1),
+            }
         }
     });
 }
@@ -121,12 +142,22 @@ async fn handle_event(
 
         match new_status {
             PluginStatus::Error => {
-                let message = match event.action {
+                let message: String = match event.action {
                     ContainerEventAction::Oom => "Container killed by OOM".into(),
                     ContainerEventAction::Kill => "Container killed externally".into(),
                     ContainerEventAction::Destroy => "Container destroyed externally".into(),
                     _ => "Container stopped or disappeared externally".into(),
                 };
+                crate::notification::notify_with_actions(
+                    app,
+                    "plugin.crashed",
+                    format!("{} stopped unexpectedly", plugin_snapshot.manifest.name),
+                    message.clone(),
+                    vec![
+                        NotificationAction::RestartPlugin { plugin_id: plugin_id.clone() },
+                        NotificationAction::OpenLogs { plugin_id: plugin_id.clone() },
+                    ],
+                );
                 lifecycle_events::emit(
                     Some(app),
                     LifecycleEvent::PluginError {
@@ -171,16 +171,26 @@ pub async fn rebuild_plugin(
         return;
     }
 
-    // Build Docker image
-    let runtime = {
+    // Hold the plugin against concurrent installs and updates until it's back up
+    let (locks, runtime) = {
         let mgr = state.read().await;
-        mgr.runtime.clone()
+        (mgr.plugin_locks.clone(), mgr.runtime.clone())
     };
+    let _plugin_lock = locks.lock(plugin_id).await;
+
+    // Build Docker image
     emit_rebuild(app_handle, plugin_id, "building", format!("Building image {}", manifest.image));
     if let Err(e) = runtime.build_image(source_dir, &manifest.image).await {
         emit_rebuild(app_handle, plugin_id, "error", format!("Docker build failed: {}", e));
         return;
     }
+    let vetted = match crate::commands::plugins::vet_image(state, None, &manifest).await {
+        Ok(vetted) => vetted,
+        Err(e) => {
+            emit_rebuild(app_handle, plugin_id, "error", format!("Image check failed: {}", e));
+            return;
+        }
+    };
 
     // Check current state
     let was_running = {
@@ -207,7 +217,7 @@ pub async fn rebuild_plugin(
         let local_path = Some(manifest_path.display().to_string());
 
         if let Err(e) = mgr
-            .install(manifest, existing_perms, vec![], None, local_path)
+            .install_vetted(manifest, vetted, existing_perms, vec![], None, local_path)
             .await
         {
             emit_rebuild(app_handle, plugin_id, "error", format!("Reinstall failed: {}", e));
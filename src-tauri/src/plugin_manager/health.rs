@@ -12,6 +12,8 @@ pub async fn sync_plugin_states(state: &AppState, app: Option<&tauri::AppHandle>
             .storage
             .list()
             .iter()
+            // Remote MCP plugins have no container to reconcile against
+            .filter(|p| p.manifest.remote_mcp_url().is_none())
             .map(|p| {
                 (
                     p.manifest.id.clone(),
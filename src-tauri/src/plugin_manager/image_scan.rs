@@ -0,0 +1,233 @@
+//! Vulnerability scanning of plugin images before their container is
+//! created, with [Trivy](https://trivy.dev).
+//!
+//! Trivy is looked up next to the Nexus executable, where a bundled sidecar
+//! lands, then on `PATH`. Only critical findings are reported. Scanning is
+//! best effort: a missing scanner or a failed scan is logged and the
+//! install goes ahead; only findings can block it, per [`ImageScanPolicy`].
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NexusError, NexusResult};
+
+/// The first scan downloads Trivy's vulnerability database.
+const SCAN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// What a critical finding does to an install or update.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageScanPolicy {
+    /// Images aren't scanned.
+    Off,
+    /// The install goes ahead and the user is told.
+    #[default]
+    Warn,
+    /// The install fails before the container is created.
+    Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vulnerability {
+    /// CVE or advisory ID.
+    pub id: String,
+    pub package: String,
+    pub installed_version: String,
+    pub fixed_version: Option<String>,
+    pub title: Option<String>,
+}
+
+/// Critical vulnerabilities found in an image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageScan {
+    pub image: String,
+    pub critical: Vec<Vulnerability>,
+    pub scanned_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ImageScan {
+    /// One line for errors and notifications, e.g. `2 critical
+    /// vulnerabilities in img:1 (CVE-1, CVE-2)`.
+    pub fn summary(&self) -> String {
+        const LISTED: usize = 5;
+        let mut seen = std::collections::HashSet::new();
+        let mut ids: Vec<&str> = self.critical.iter().map(|v| v.id.as_str()).collect();
+        ids.retain(|id| seen.insert(*id));
+        let more = ids.len().saturating_sub(LISTED);
+        ids.truncate(LISTED);
+        let mut listed = ids.join(", ");
+        if more > 0 {
+            listed.push_str(&format!(" and {} more", more));
+        }
+        format!(
+            "{} critical {} in {} ({})",
+            self.critical.len(),
+            if self.critical.len() == 1 { "vulnerability" } else { "vulnerabilities" },
+            self.image,
+            listed
+        )
+    }
+}
+
+/// Scan `image` per `policy`. Returns the scan when it ran, or an error when
+/// the policy blocks what it found.
+pub async fn check(image: &str, policy: ImageScanPolicy) -> NexusResult<Option<ImageScan>> {
+    if policy == ImageScanPolicy::Off {
+        return Ok(None);
+    }
+    let scan = match scan(image).await {
+        Ok(scan) => scan,
+        Err(e) => {
+            log::warn!("Skipping vulnerability scan of {}: {}", image, e);
+            return Ok(None);
+        }
+    };
+    if scan.critical.is_empty() {
+        log::info!("No critical vulnerabilities in {}", image);
+        return Ok(Some(scan));
+    }
+    log::warn!("{}", scan.summary());
+    if policy == ImageScanPolicy::Block {
+        return Err(NexusError::Other(format!(
+            "Blocked by the image scan policy: {}",
+            scan.summary()
+        )));
+    }
+    Ok(Some(scan))
+}
+
+fn trivy() -> Option<PathBuf> {
+    let name = if cfg!(target_os = "windows") { "trivy.exe" } else { "trivy" };
+    let bundled = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(name)))
+        .filter(|p| p.is_file());
+    bundled.or_else(|| {
+        std::env::split_paths(&std::env::var_os("PATH")?)
+            .map(|dir| dir.join(name))
+            .find(|p| p.is_file())
+    })
+}
+
+async fn scan(image: &str) -> Result<ImageScan, String> {
+    let trivy = trivy().ok_or("Trivy not found")?;
+    let run = tokio::process::Command::new(&trivy)
+        .args([
+            "image",
+            "--quiet",
+            "--format",
+            "json",
+            "--scanners",
+            "vuln",
+            "--severity",
+            "CRITICAL",
+            image,
+        ])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(SCAN_TIMEOUT, run)
+        .await
+        .map_err(|_| "Trivy timed out".to_string())?
+        .map_err(|e| format!("Failed to run {}: {}", trivy.display(), e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Trivy failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(ImageScan {
+        image: image.to_string(),
+        critical: parse_report(&output.stdout)?,
+        scanned_at: chrono::Utc::now(),
+    })
+}
+
+// Trivy writes `null` rather than an empty list
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Report {
+    results: Option<Vec<ReportResult>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ReportResult {
+    vulnerabilities: Option<Vec<ReportVulnerability>>,
+}
+
+#[derive(Deserialize)]
+struct ReportVulnerability {
+    #[serde(rename = "VulnerabilityID")]
+    id: String,
+    #[serde(rename = "PkgName")]
+    package: String,
+    #[serde(rename = "InstalledVersion", default)]
+    installed_version: String,
+    #[serde(rename = "FixedVersion")]
+    fixed_version: Option<String>,
+    #[serde(rename = "Title")]
+    title: Option<String>,
+    #[serde(rename = "Severity")]
+    severity: String,
+}
+
+/// Critical findings in a `trivy image --format json` report.
+fn parse_report(json: &[u8]) -> Result<Vec<Vulnerability>, String> {
+    let report: Report =
+        serde_json::from_slice(json).map_err(|e| format!("Unreadable Trivy report: {}", e))?;
+    Ok(report
+        .results
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|r| r.vulnerabilities.unwrap_or_default())
+        .filter(|v| v.severity == "CRITICAL")
+        .map(|v| Vulnerability {
+            id: v.id,
+            package: v.package,
+            installed_version: v.installed_version,
+            fixed_version: v.fixed_version.filter(|f| !f.is_empty()),
+            title: v.title,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_keeps_critical_findings() {
+        let report = br#"{
+            "SchemaVersion": 2,
+            "ArtifactName": "example/plugin:1.0",
+            "Results": [
+                {"Target": "example/plugin:1.0 (debian 12.1)", "Vulnerabilities": [
+                    {"VulnerabilityID": "CVE-2024-0001", "PkgName": "openssl",
+                     "InstalledVersion": "3.0.9", "FixedVersion": "3.0.13",
+                     "Severity": "CRITICAL", "Title": "openssl: remote code execution"},
+                    {"VulnerabilityID": "CVE-2024-0002", "PkgName": "zlib",
+                     "InstalledVersion": "1.2.13", "FixedVersion": "",
+                     "Severity": "HIGH"}
+                ]},
+                {"Target": "app/package-lock.json", "Vulnerabilities": null}
+            ]
+        }"#;
+        let critical = parse_report(report).unwrap();
+        assert_eq!(critical.len(), 1);
+        assert_eq!(critical[0].id, "CVE-2024-0001");
+        assert_eq!(critical[0].fixed_version.as_deref(), Some("3.0.13"));
+
+        let scan = ImageScan {
+            image: "example/plugin:1.0".into(),
+            critical,
+            scanned_at: chrono::Utc::now(),
+        };
+        assert_eq!(
+            scan.summary(),
+            "1 critical vulnerability in example/plugin:1.0 (CVE-2024-0001)"
+        );
+        assert!(parse_report(br#"{"SchemaVersion": 2}"#).unwrap().is_empty());
+    }
+}
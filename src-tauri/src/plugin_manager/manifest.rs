@@ -78,6 +78,10 @@ pub struct McpServerConfig {
     /// When true, all tools from this MCP server require user approval.
     #[serde(default)]
     pub requires_approval: bool,
+    /// Remote Streamable HTTP endpoint. When set the plugin has no container:
+    /// the gateway connects to this URL directly and `path` is unused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
 }
 
 fn default_mcp_path() -> String {
@@ -139,6 +143,163 @@ pub struct PluginManifest {
     /// for backward compatibility.
     #[serde(default)]
     pub mcp_access: Vec<String>,
+    /// Confinement profiles the plugin's container runs under.
+    #[serde(default)]
+    pub security: PluginSecurity,
+}
+
+/// Confinement for a plugin's container, declared in the manifest or set by
+/// the user per plugin. Unset profiles keep the engine's defaults; the root
+/// filesystem is read-only unless a reason to write to it is given.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginSecurity {
+    /// Seccomp profile in the engine's JSON format, replacing its default
+    /// profile. User overrides may also give `"unconfined"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seccomp_profile: Option<serde_json::Value>,
+    /// Name of an AppArmor profile loaded on the host. User overrides may
+    /// also give `"unconfined"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apparmor_profile: Option<String>,
+    /// Whether the root filesystem is writable. Manifests give the reason
+    /// the plugin writes outside `/data` and `/tmp`, shown at install; user
+    /// overrides may also give `false` to keep it read-only regardless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub writable_rootfs: Option<RootfsAccess>,
+    /// `user[:group]` to run as in place of the image's user. Manifests
+    /// can't name root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+/// How a plugin's root filesystem is mounted, as `false` or a reason string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RootfsAccess {
+    /// Read-only, with tmpfs at `/tmp` and `/run`.
+    ReadOnly,
+    /// Writable, for the given reason.
+    Writable(String),
+}
+
+impl Serialize for RootfsAccess {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RootfsAccess::ReadOnly => serializer.serialize_bool(false),
+            RootfsAccess::Writable(reason) => serializer.serialize_str(reason),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RootfsAccess {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Flag(bool),
+            Reason(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Flag(false) => Ok(RootfsAccess::ReadOnly),
+            Raw::Flag(true) => Err(serde::de::Error::custom(
+                "writable_rootfs must be false or the reason the plugin writes to it",
+            )),
+            Raw::Reason(reason) => Ok(RootfsAccess::Writable(reason)),
+        }
+    }
+}
+
+const UNCONFINED: &str = "unconfined";
+
+/// Seccomp default actions that let unlisted syscalls through.
+const PERMISSIVE_SECCOMP_ACTIONS: &[&str] = &["SCMP_ACT_ALLOW", "SCMP_ACT_LOG"];
+
+const MAX_SECCOMP_PROFILE_LEN: usize = 64 * 1024;
+
+impl PluginSecurity {
+    /// Manifests may only tighten confinement; `user` overrides may also
+    /// loosen it.
+    pub fn validate(&self, user: bool) -> Result<(), String> {
+        match &self.seccomp_profile {
+            None => {}
+            Some(serde_json::Value::String(s)) if s == UNCONFINED && user => {}
+            Some(profile @ serde_json::Value::Object(fields)) => {
+                if profile.to_string().len() > MAX_SECCOMP_PROFILE_LEN {
+                    return Err("Seccomp profile must be 64 KiB or smaller".to_string());
+                }
+                let action = fields.get("defaultAction").and_then(|v| v.as_str());
+                match action {
+                    None => return Err("Seccomp profile must set defaultAction".to_string()),
+                    Some(a) if !user && PERMISSIVE_SECCOMP_ACTIONS.contains(&a) => {
+                        return Err(format!(
+                            "Seccomp profile defaultAction {} would allow every unlisted syscall",
+                            a
+                        ));
+                    }
+                    Some(_) => {}
+                }
+            }
+            Some(_) => return Err("Seccomp profile must be a JSON object".to_string()),
+        }
+
+        if let Some(name) = &self.apparmor_profile {
+            if name.is_empty() || name.len() > 200 {
+                return Err("AppArmor profile name must be 1-200 characters".to_string());
+            }
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+            {
+                return Err(format!("AppArmor profile name '{}' must match [A-Za-z0-9._/-]", name));
+            }
+            if name == UNCONFINED && !user {
+                return Err("Manifests cannot run a plugin without AppArmor confinement".to_string());
+            }
+        }
+
+        if let Some(name) = &self.user {
+            if !super::non_root::is_valid_user(name) {
+                return Err(format!("User '{}' must be a name or ID, optionally with ':group'", name));
+            }
+            if !user && super::non_root::is_root(Some(name)) {
+                return Err("Manifests cannot run a plugin as root".to_string());
+            }
+        }
+
+        if let Some(RootfsAccess::Writable(reason)) = &self.writable_rootfs {
+            if reason.trim().is_empty() || reason.len() > 500 {
+                return Err("writable_rootfs must give a reason of 1-500 characters".to_string());
+            }
+            if strip_bidi_overrides(reason) {
+                return Err("writable_rootfs must not contain Unicode bidirectional override characters".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the root filesystem stays writable. It's read-only unless a
+    /// reason to write is given.
+    pub fn rootfs_writable(&self) -> bool {
+        matches!(self.writable_rootfs, Some(RootfsAccess::Writable(_)))
+    }
+
+    /// These profiles, with any set in `overrides` taking their place.
+    pub fn overridden_by(&self, overrides: &PluginSecurity) -> PluginSecurity {
+        PluginSecurity {
+            seccomp_profile: overrides
+                .seccomp_profile
+                .clone()
+                .or_else(|| self.seccomp_profile.clone()),
+            apparmor_profile: overrides
+                .apparmor_profile
+                .clone()
+                .or_else(|| self.apparmor_profile.clone()),
+            writable_rootfs: overrides
+                .writable_rootfs
+                .clone()
+                .or_else(|| self.writable_rootfs.clone()),
+            user: overrides.user.clone().or_else(|| self.user.clone()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -194,6 +355,11 @@ impl PluginManifest {
         perms
     }
 
+    /// Endpoint of a containerless remote MCP plugin, if this is one.
+    pub fn remote_mcp_url(&self) -> Option<&str> {
+        self.mcp.as_ref()?.server.as_ref()?.url.as_deref()
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         // Required fields
         if self.id.is_empty() {
@@ -205,10 +371,16 @@ impl PluginManifest {
         if self.version.is_empty() {
             return Err("Plugin version is required".to_string());
         }
-        if self.image.is_empty() {
+        if let Some(url) = self.remote_mcp_url() {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err("Remote MCP url must be an http(s) URL".to_string());
+            }
+            if self.ui.is_some() {
+                return Err("Remote MCP plugins cannot declare a UI".to_string());
+            }
+        } else if self.image.is_empty() {
             return Err("Docker image is required".to_string());
-        }
-        if let Some(ref ui) = self.ui {
+        } else if let Some(ref ui) = self.ui {
             if ui.port == 0 {
                 return Err("UI port must be non-zero".to_string());
             }
@@ -386,6 +558,8 @@ impl PluginManifest {
             }
         }
 
+        self.security.validate(false)?;
+
         Ok(())
     }
 }
@@ -415,6 +589,7 @@ mod tests {
             mcp: None,
             extensions: HashMap::new(),
             mcp_access: vec![],
+            security: PluginSecurity::default(),
         }
     }
 
@@ -423,6 +598,26 @@ mod tests {
         assert!(valid_manifest().validate().is_ok());
     }
 
+    #[test]
+    fn remote_mcp_plugin_needs_no_image() {
+        let mut m = valid_manifest();
+        m.image = String::new();
+        m.ui = None;
+        m.mcp = Some(McpConfig {
+            tools: vec![],
+            server: Some(McpServerConfig {
+                path: default_mcp_path(),
+                requires_approval: false,
+                url: Some("https://mcp.example.com/mcp".into()),
+            }),
+        });
+        assert!(m.validate().is_ok());
+        assert_eq!(m.remote_mcp_url(), Some("https://mcp.example.com/mcp"));
+
+        m.mcp.as_mut().unwrap().server.as_mut().unwrap().url = Some("file:///etc/passwd".into());
+        assert!(m.validate().is_err());
+    }
+
     #[test]
     fn valid_digest_accepted() {
         let mut m = valid_manifest();
@@ -602,4 +797,114 @@ mod tests {
         assert_eq!(m.extensions.get("zenoh").unwrap().operation_names().len(), 2);
         assert!(m.validate().is_ok());
     }
+
+    #[test]
+    fn manifest_security_can_only_tighten() {
+        let mut m = valid_manifest();
+        m.security.seccomp_profile = Some(serde_json::json!({
+            "defaultAction": "SCMP_ACT_ERRNO",
+            "syscalls": [{"names": ["read", "write"], "action": "SCMP_ACT_ALLOW"}]
+        }));
+        m.security.apparmor_profile = Some("nexus-plugin".into());
+        assert!(m.validate().is_ok());
+
+        m.security.apparmor_profile = Some("unconfined".into());
+        assert!(m.validate().is_err());
+        assert!(m.security.validate(true).is_ok());
+
+        m.security.apparmor_profile = Some("bad profile".into());
+        assert!(m.security.validate(true).is_err());
+
+        m.security.apparmor_profile = None;
+        m.security.seccomp_profile = Some(serde_json::json!({"defaultAction": "SCMP_ACT_ALLOW"}));
+        assert!(m.validate().is_err());
+        assert!(m.security.validate(true).is_ok());
+
+        m.security.seccomp_profile = Some(serde_json::json!("unconfined"));
+        assert!(m.validate().is_err());
+        assert!(m.security.validate(true).is_ok());
+
+        m.security.seccomp_profile = Some(serde_json::json!({"syscalls": []}));
+        assert!(m.security.validate(true).is_err());
+
+        m.security.seccomp_profile = None;
+        m.security.writable_rootfs = Some(RootfsAccess::Writable(
+            "Caches compiled templates under /var/cache".into(),
+        ));
+        assert!(m.validate().is_ok());
+        m.security.writable_rootfs = Some(RootfsAccess::Writable("  ".into()));
+        assert!(m.validate().is_err());
+        m.security.writable_rootfs = Some(RootfsAccess::ReadOnly);
+        assert!(m.validate().is_ok());
+
+        m.security.writable_rootfs = None;
+        m.security.user = Some("1000:1000".into());
+        assert!(m.validate().is_ok());
+        m.security.user = Some("root".into());
+        assert!(m.validate().is_err());
+        assert!(m.security.validate(true).is_ok());
+    }
+
+    #[test]
+    fn security_overrides_replace_declared_profiles() {
+        let declared = PluginSecurity {
+            seccomp_profile: Some(serde_json::json!({"defaultAction": "SCMP_ACT_ERRNO"})),
+            apparmor_profile: Some("nexus-plugin".into()),
+            writable_rootfs: None,
+            user: None,
+        };
+        let overrides = PluginSecurity {
+            seccomp_profile: None,
+            apparmor_profile: Some("unconfined".into()),
+            writable_rootfs: None,
+            user: Some("1000".into()),
+        };
+        let merged = declared.overridden_by(&overrides);
+        assert_eq!(merged.seccomp_profile, declared.seccomp_profile);
+        assert_eq!(merged.apparmor_profile.as_deref(), Some("unconfined"));
+        assert_eq!(merged.user.as_deref(), Some("1000"));
+        assert_eq!(declared.overridden_by(&PluginSecurity::default()), declared);
+    }
+
+    #[test]
+    fn rootfs_override_can_loosen_or_tighten() {
+        let writable = |reason: &str| PluginSecurity {
+            writable_rootfs: Some(RootfsAccess::Writable(reason.into())),
+            ..Default::default()
+        };
+        let read_only = PluginSecurity {
+            writable_rootfs: Some(RootfsAccess::ReadOnly),
+            ..Default::default()
+        };
+        let inherit = PluginSecurity::default();
+
+        // Read-only unless something asks to write
+        assert!(!inherit.overridden_by(&inherit).rootfs_writable());
+        // The user lets a read-only plugin write
+        let merged = inherit.overridden_by(&writable("Needs /var/cache"));
+        assert!(merged.rootfs_writable());
+        // The user keeps a plugin read-only although its manifest asks to write
+        let declared = writable("Caches thumbnails");
+        assert!(!declared.overridden_by(&read_only).rootfs_writable());
+        // Without an override, the manifest's reason stands
+        assert_eq!(declared.overridden_by(&inherit), declared);
+    }
+
+    #[test]
+    fn rootfs_access_serializes_as_false_or_a_reason() {
+        let parse = |json: serde_json::Value| serde_json::from_value::<PluginSecurity>(json);
+        let security = parse(serde_json::json!({ "writable_rootfs": false })).unwrap();
+        assert_eq!(security.writable_rootfs, Some(RootfsAccess::ReadOnly));
+        let security = parse(serde_json::json!({ "writable_rootfs": "Caches" })).unwrap();
+        assert_eq!(security.writable_rootfs, Some(RootfsAccess::Writable("Caches".into())));
+        assert_eq!(parse(serde_json::json!({})).unwrap().writable_rootfs, None);
+        assert!(parse(serde_json::json!({ "writable_rootfs": true })).is_err());
+
+        let json = serde_json::to_value(PluginSecurity {
+            writable_rootfs: Some(RootfsAccess::ReadOnly),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(json, serde_json::json!({ "writable_rootfs": false }));
+    }
 }
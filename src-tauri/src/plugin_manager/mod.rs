@@ -1,37 +1,62 @@
 pub(crate) mod container_events;
+pub mod bundle;
 pub mod dev_watcher;
 pub mod health;
+pub mod image_scan;
 pub mod manifest;
+pub mod non_root;
+pub mod plugin_locks;
+pub mod oci;
 pub mod registry;
+pub mod resource_history;
 pub mod storage;
+pub mod vetting;
 
 use crate::error::{NexusError, NexusResult};
 use crate::extensions::ipc::AppIpcRouter;
 use crate::extensions::loader::ExtensionLoader;
 use crate::extensions::registry::ExtensionRegistry;
-use crate::host_api::mcp::McpClientManager;
+use crate::extensions::signing::KeyConsistency;
+use crate::host_api::mcp::{McpClientManager, McpToolStats};
 use crate::oauth::plugin_auth::PluginAuthService;
 use crate::oauth::store::OAuthStore;
 use crate::permissions::service::PermissionService;
-use crate::runtime::{ContainerConfig, ContainerRuntime, ResourceLimits, SecurityConfig};
+use crate::runtime::{ContainerConfig, ContainerRuntime, Progress, ResourceLimits, SecurityConfig};
 use crate::update_checker::UpdateCheckState;
 use crate::AppState;
 use manifest::PluginManifest;
+use plugin_locks::PluginLocks;
 use storage::{
-    InstalledPlugin, McpSettings, NexusSettings, PluginSettingsStore, PluginStatus,
+    InstalledPlugin, McpSettings, NexusSettings, PluginEnvStore, PluginSettingsStore, PluginStatus,
     PluginStorage,
 };
+use vetting::{ImageVetter, VettedImage};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Generate a Docker volume name for a plugin's persistent data.
-fn data_volume_name(plugin_id: &str) -> String {
+pub(crate) fn data_volume_name(plugin_id: &str) -> String {
     format!("nexus-data-{}", plugin_id.replace('.', "-"))
 }
 
+/// Container hardening for a plugin: the default capability set, plus the
+/// manifest's confinement with the user's overrides applied.
+fn security_config(manifest: &PluginManifest, overrides: &manifest::PluginSecurity) -> SecurityConfig {
+    let profiles = manifest.security.overridden_by(overrides);
+    SecurityConfig {
+        read_only_rootfs: !profiles.rootfs_writable(),
+        seccomp_profile: profiles.seccomp_profile.map(|profile| match profile {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        }),
+        apparmor_profile: profiles.apparmor_profile,
+        ..SecurityConfig::default()
+    }
+}
+
 /// Reject install/update if the plugin requires a newer Nexus version.
 /// Dev builds (prerelease tags like `0.0.0-dev`) skip this check.
 fn check_min_nexus_version(manifest: &PluginManifest) -> NexusResult<()> {
@@ -55,16 +80,33 @@ fn check_min_nexus_version(manifest: &PluginManifest) -> NexusResult<()> {
     Ok(())
 }
 
-fn emit_update(app_handle: Option<&tauri::AppHandle>, plugin_id: &str, stage: &'static str) {
+fn emit_update(
+    app_handle: Option<&tauri::AppHandle>,
+    plugin_id: &str,
+    stage: &'static str,
+    progress: Option<Progress>,
+) {
     crate::lifecycle_events::emit(
         app_handle,
         crate::lifecycle_events::LifecycleEvent::PluginUpdateStage {
             plugin_id: plugin_id.to_string(),
             stage: stage.to_string(),
+            progress,
         },
     );
 }
 
+/// What moving plugins to another container engine did.
+#[derive(Debug, Default)]
+pub struct EngineSwitch {
+    /// Plugins that were running, for the caller to start on the new engine.
+    pub was_running: Vec<String>,
+    /// Plugins whose image isn't on the new engine and couldn't be pulled.
+    pub missing_images: Vec<String>,
+    /// Plugins whose data volume couldn't be copied to the new engine.
+    pub volumes_not_copied: Vec<String>,
+}
+
 pub struct PluginManager {
     pub runtime: Arc<dyn ContainerRuntime>,
     pub storage: PluginStorage,
@@ -76,16 +118,31 @@ pub struct PluginManager {
     pub registry_store: registry::RegistryStore,
     pub registry_cache: Vec<registry::RegistryEntry>,
     pub extension_registry_cache: Vec<registry::ExtensionRegistryEntry>,
+    pub collection_cache: Vec<registry::Collection>,
     pub settings: NexusSettings,
     pub plugin_settings: PluginSettingsStore,
+    /// Install-time container env kept outside manifests (secrets).
+    pub plugin_env: PluginEnvStore,
     pub mcp_settings: McpSettings,
     pub update_state: UpdateCheckState,
     pub data_dir: PathBuf,
     tool_version: AtomicU64,
     tool_version_tx: tokio::sync::watch::Sender<u64>,
     pub tool_version_rx: tokio::sync::watch::Receiver<u64>,
+    /// Bumped each time `runtime` is swapped for another engine.
+    pub engine_version_rx: tokio::sync::watch::Receiver<u64>,
+    engine_version_tx: tokio::sync::watch::Sender<u64>,
     /// Native MCP client connections to plugin servers.
     pub mcp_clients: McpClientManager,
+    /// Per-tool usage analytics for the MCP gateway.
+    pub mcp_tool_stats: McpToolStats,
+    /// Critical findings from the last install or update of each plugin.
+    pub image_scans: HashMap<String, image_scan::ImageScan>,
+    /// Plugins found running as root at their last install or update.
+    pub root_plugins: HashSet<String>,
+    /// Serializes installs and updates of a plugin while they run without
+    /// holding `AppState`.
+    pub plugin_locks: PluginLocks,
 }
 
 impl PluginManager {
@@ -95,11 +152,43 @@ impl PluginManager {
         permissions: Arc<dyn PermissionService>,
         oauth_store: Arc<OAuthStore>,
     ) -> Self {
-        let storage = PluginStorage::load(&data_dir).unwrap_or_default();
+        let mut storage = PluginStorage::load(&data_dir).unwrap_or_else(|e| {
+            crate::util::report_store_problem(crate::state_db::DB_FILE, e.to_string(), false);
+            PluginStorage::default()
+        });
+        // Remote MCP connections don't survive a restart; reconnect on start
+        let remote_running: Vec<String> = storage
+            .list()
+            .iter()
+            .filter(|p| p.status == PluginStatus::Running && p.manifest.remote_mcp_url().is_some())
+            .map(|p| p.manifest.id.clone())
+            .collect();
+        for id in remote_running {
+            if let Some(p) = storage.get_mut(&id) {
+                p.status = PluginStatus::Stopped;
+            }
+        }
         let mut registry_store = registry::RegistryStore::load(&data_dir).unwrap_or_default();
-        let settings = NexusSettings::load(&data_dir).unwrap_or_default();
+        let settings = NexusSettings::load(&data_dir).unwrap_or_else(|e| {
+            crate::util::report_store_problem(crate::state_db::DB_FILE, e.to_string(), false);
+            NexusSettings::default()
+        });
         let plugin_settings = PluginSettingsStore::load(&data_dir).unwrap_or_default();
+        let mut plugin_env = PluginEnvStore::load(&data_dir).unwrap_or_default();
+        // Moves credentials stored by older releases, or by the other
+        // storage, to where the settings say. They stay put when the
+        // keychain can't be reached.
+        if let Err(e) = registry_store.set_credential_storage(settings.credential_storage) {
+            log::warn!("Failed to move registry credentials: {}", e);
+        }
+        if let Err(e) = plugin_env.set_credential_storage(settings.credential_storage) {
+            log::warn!("Failed to move plugin secrets: {}", e);
+        }
+        if let Err(e) = crate::secrets::set_key_storage(&data_dir, settings.credential_storage) {
+            log::warn!("Failed to move the store encryption key: {}", e);
+        }
         let mcp_settings = McpSettings::load(&data_dir).unwrap_or_default();
+        let mcp_tool_stats = McpToolStats::load(&data_dir);
         let update_state = crate::update_checker::load_update_state(&data_dir);
 
         // Auto-register local registry for MCP-wrapped plugins
@@ -117,6 +206,7 @@ impl PluginManager {
         }
 
         let (tool_version_tx, tool_version_rx) = tokio::sync::watch::channel(0u64);
+        let (engine_version_tx, engine_version_rx) = tokio::sync::watch::channel(0u64);
 
         let extension_loader = ExtensionLoader::new(&data_dir);
         let mut extensions = ExtensionRegistry::new();
@@ -137,15 +227,23 @@ impl PluginManager {
             registry_store,
             registry_cache: Vec::new(),
             extension_registry_cache: Vec::new(),
+            collection_cache: Vec::new(),
             settings,
             plugin_settings,
+            plugin_env,
             mcp_settings,
             update_state,
             data_dir,
             tool_version: AtomicU64::new(0),
             tool_version_tx,
             tool_version_rx,
+            engine_version_rx,
+            engine_version_tx,
             mcp_clients: McpClientManager::new(),
+            mcp_tool_stats,
+            image_scans: HashMap::new(),
+            root_plugins: HashSet::new(),
+            plugin_locks: PluginLocks::default(),
         }
     }
 
@@ -212,6 +310,9 @@ impl PluginManager {
             entry.enabled_tools.retain(|t| tool_names.contains(t));
             entry.disabled_tools.retain(|t| tool_names.contains(t));
             entry.approved_tools.retain(|t| tool_names.contains(t));
+            self.mcp_settings
+                .client_approvals
+                .retain(|a| a.plugin_id != plugin_id || tool_names.contains(&a.tool));
 
             let _ = self.mcp_settings.save();
         }
@@ -225,6 +326,51 @@ impl PluginManager {
         log::debug!("Tool list changed (version {})", v);
     }
 
+    /// What's needed to vet an image for `plugin_id` without holding the
+    /// manager.
+    pub fn image_vetter(&self, plugin_id: &str) -> ImageVetter {
+        let security_override = self
+            .storage
+            .get(plugin_id)
+            .map(|p| p.security_override.clone())
+            .unwrap_or_default();
+        self.vetter(security_override)
+    }
+
+    fn vetter(&self, security_override: manifest::PluginSecurity) -> ImageVetter {
+        ImageVetter {
+            runtime: self.runtime.clone(),
+            image_scan: self.settings.image_scan,
+            root_user: self.settings.root_user,
+            security_override,
+        }
+    }
+
+    /// Keep what vetting found for the install or update notification.
+    fn record_vetting(&mut self, plugin_id: &str, vetted: &VettedImage) {
+        match &vetted.scan {
+            Some(scan) => self.image_scans.insert(plugin_id.to_string(), scan.clone()),
+            None => self.image_scans.remove(plugin_id),
+        };
+        if vetted.runs_as_root {
+            self.root_plugins.insert(plugin_id.to_string());
+        } else {
+            self.root_plugins.remove(plugin_id);
+        }
+    }
+
+    /// The user to run the plugin's container as in place of the image's.
+    async fn container_user(
+        &self,
+        manifest: &PluginManifest,
+        overrides: &manifest::PluginSecurity,
+    ) -> NexusResult<Option<String>> {
+        let declared = manifest.security.overridden_by(overrides).user;
+        let image_user = self.runtime.get_image_user(&manifest.image).await?;
+        Ok(non_root::run_as(self.settings.root_user, image_user.as_deref(), declared.as_deref()))
+    }
+
+    /// Pull, vet, and install a plugin, holding the manager throughout.
     pub async fn install(
         &mut self,
         manifest: PluginManifest,
@@ -233,12 +379,65 @@ impl PluginManager {
         manifest_url: Option<&str>,
         local_manifest_path: Option<String>,
     ) -> NexusResult<InstalledPlugin> {
+        self.install_inner(
+            manifest,
+            None,
+            approved_permissions,
+            deferred_permissions,
+            manifest_url,
+            local_manifest_path,
+        )
+        .await
+    }
+
+    /// Install a plugin whose image was already pulled and vetted.
+    pub async fn install_vetted(
+        &mut self,
+        manifest: PluginManifest,
+        vetted: VettedImage,
+        approved_permissions: Vec<crate::permissions::Permission>,
+        deferred_permissions: Vec<crate::permissions::Permission>,
+        manifest_url: Option<&str>,
+        local_manifest_path: Option<String>,
+    ) -> NexusResult<InstalledPlugin> {
+        self.install_inner(
+            manifest,
+            Some(vetted),
+            approved_permissions,
+            deferred_permissions,
+            manifest_url,
+            local_manifest_path,
+        )
+        .await
+    }
+
+    async fn install_inner(
+        &mut self,
+        manifest: PluginManifest,
+        vetted: Option<VettedImage>,
+        approved_permissions: Vec<crate::permissions::Permission>,
+        deferred_permissions: Vec<crate::permissions::Permission>,
+        manifest_url: Option<&str>,
+        local_manifest_path: Option<String>,
+    ) -> NexusResult<InstalledPlugin> {
+        self.check_writable()?;
         manifest
             .validate()
             .map_err(NexusError::InvalidManifest)?;
 
         check_min_nexus_version(&manifest)?;
 
+        let prev_channel = self
+            .storage
+            .get(&manifest.id)
+            .map(|p| p.channel)
+            .unwrap_or_default();
+        let prev_security_override = self
+            .storage
+            .get(&manifest.id)
+            .map(|p| p.security_override.clone())
+            .unwrap_or_default();
+
         // Preserve dev_mode across local-to-local reinstalls only.
         // When switching sources (local→registry or registry→local), reset dev_mode
         // and use the new local_manifest_path as-is (don't carry over the old one).
@@ -246,6 +445,7 @@ impl PluginManager {
             let dm = if local_manifest_path.is_some() { existing.dev_mode } else { false };
 
             log::info!("Reinstalling plugin '{}' (replacing existing)", manifest.id);
+            self.mcp_clients.disconnect(&manifest.id);
 
             // Stop and remove old container, but keep volume (data) and permissions.
             // Also remove by name as fallback (container name survives Docker restarts).
@@ -279,46 +479,33 @@ impl PluginManager {
             false
         };
 
-        // Pull the Docker image (skip if already present — e.g. locally built)
-        let image_exists = self.runtime.image_exists(&manifest.image).await.unwrap_or(false);
-        if image_exists {
-            log::info!("Image already exists locally: {}", manifest.image);
-        } else {
-            log::info!("Pulling image: {}", manifest.image);
-            self.runtime.pull_image(&manifest.image).await?;
-        }
-
-        // Verify image digest if declared in manifest
-        if let Some(ref expected_digest) = manifest.image_digest {
-            match self.runtime.get_image_digest(&manifest.image).await? {
-                Some(actual_digest) => {
-                    if &actual_digest != expected_digest {
-                        return Err(NexusError::Other(format!(
-                            "Image digest mismatch for {}. Expected: {}, Got: {}. \
-                             The image may have been tampered with.",
-                            manifest.image, expected_digest, actual_digest
-                        )));
-                    }
-                    log::info!(
-                        "Image digest verified: {} = {}",
-                        manifest.image, actual_digest
-                    );
-                }
-                None => {
-                    log::warn!(
-                        "Image {} has no registry digest (locally built?). \
-                         Skipping digest verification.",
-                        manifest.image
-                    );
-                }
-            }
-        } else {
-            log::warn!(
-                "Plugin {} has no image_digest — skipping content verification",
-                manifest.id
+        if manifest.remote_mcp_url().is_some() {
+            return self.install_remote(
+                manifest,
+                &approved_permissions,
+                &deferred_permissions,
+                manifest_url,
+                local_manifest_path,
             );
         }
 
+        let vetted = match vetted {
+            Some(vetted) => vetted,
+            None => {
+                // Pull the Docker image (skip if already present — e.g. locally built)
+                let image_exists = self.runtime.image_exists(&manifest.image).await.unwrap_or(false);
+                if image_exists {
+                    log::info!("Image already exists locally: {}", manifest.image);
+                } else {
+                    log::info!("Pulling image: {}", manifest.image);
+                    self.runtime.pull_image(&manifest.image).await?;
+                }
+                self.vetter(prev_security_override.clone()).vet(&manifest).await?
+            }
+        };
+        self.record_vetting(&manifest.id, &vetted);
+        let user = vetted.user;
+
         let port = self.storage.allocate_port();
 
         // Register OAuth client for this plugin
@@ -330,6 +517,7 @@ impl PluginManager {
             .iter()
             .map(|(k, v)| format!("{}={}", k, v))
             .collect();
+        env_vars.extend(self.plugin_env.env_vars(&manifest.id));
         env_vars.push(format!("NEXUS_OAUTH_CLIENT_ID={}", oauth_client_id));
         env_vars.push(format!("NEXUS_OAUTH_CLIENT_SECRET={}", oauth_secret));
         // Browser-accessible URL — the iframe JS runs in the host browser, not inside the container
@@ -363,7 +551,8 @@ impl PluginManager {
             limits: self.resource_limits(),
             data_volume: Some(volume_name),
             network: "nexus-bridge".to_string(),
-            security: SecurityConfig::default(),
+            security: security_config(&manifest, &prev_security_override),
+            user,
         })
         .await?;
 
@@ -377,17 +566,64 @@ impl PluginManager {
             manifest_url_origin: manifest_url.and_then(storage::extract_url_host),
             dev_mode: prev_dev_mode,
             local_manifest_path,
+            channel: prev_channel,
+            security_override: prev_security_override,
         };
 
+        self.finish_install(plugin, &approved_permissions, &deferred_permissions)
+    }
+
+    /// Containerless install for remote MCP plugins: no image, port, or
+    /// OAuth client. The gateway connects to the remote URL on start.
+    fn install_remote(
+        &mut self,
+        manifest: PluginManifest,
+        approved_permissions: &[crate::permissions::Permission],
+        deferred_permissions: &[crate::permissions::Permission],
+        manifest_url: Option<&str>,
+        local_manifest_path: Option<String>,
+    ) -> NexusResult<InstalledPlugin> {
+        let channel = self
+            .storage
+            .get(&manifest.id)
+            .map(|p| p.channel)
+            .unwrap_or_default();
+        let plugin = InstalledPlugin {
+            manifest,
+            container_id: None,
+            status: PluginStatus::Stopped,
+            assigned_port: 0,
+            oauth_client_id: String::new(),
+            installed_at: chrono::Utc::now(),
+            manifest_url_origin: manifest_url.and_then(storage::extract_url_host),
+            dev_mode: false,
+            local_manifest_path,
+            channel,
+            security_override: Default::default(),
+        };
+        self.finish_install(plugin, approved_permissions, deferred_permissions)
+    }
+
+    /// Grant permissions, persist, and register MCP tools for a new install.
+    fn finish_install(
+        &mut self,
+        plugin: InstalledPlugin,
+        approved_permissions: &[crate::permissions::Permission],
+        deferred_permissions: &[crate::permissions::Permission],
+    ) -> NexusResult<InstalledPlugin> {
         // Grant only user-approved permissions.
         // Filesystem permissions default to an empty approved_scopes list so that
-        // every path access triggers a runtime approval prompt. Extension permissions
+        // every path access triggers a runtime approval prompt; host-pattern network
+        // permissions likewise prompt on first use of each host, and process
+        // execution on first use of each command. Extension permissions
         // with scope_key also default to empty scopes unless the manifest pre-declares
         // them (rich format). Existing plugins with `None` (unrestricted) are unaffected.
-        for perm in &approved_permissions {
+        for perm in approved_permissions {
             let approved_scopes = match perm {
                 crate::permissions::Permission::FilesystemRead
-                | crate::permissions::Permission::FilesystemWrite => Some(vec![]),
+                | crate::permissions::Permission::FilesystemWrite
+                | crate::permissions::Permission::ProcessExec
+                | crate::permissions::Permission::Network(_) => Some(vec![]),
                 crate::permissions::Permission::Extension(ext_str) => {
                     // Check if the manifest pre-declares scopes for this operation
                     Self::extract_manifest_scopes(&plugin.manifest, ext_str)
@@ -401,10 +637,12 @@ impl PluginManager {
 
         // Deferred permissions: user skipped these at install time.
         // They'll trigger a JIT approval dialog on first use.
-        for perm in &deferred_permissions {
+        for perm in deferred_permissions {
             let approved_scopes = match perm {
                 crate::permissions::Permission::FilesystemRead
-                | crate::permissions::Permission::FilesystemWrite => Some(vec![]),
+                | crate::permissions::Permission::FilesystemWrite
+                | crate::permissions::Permission::ProcessExec
+                | crate::permissions::Permission::Network(_) => Some(vec![]),
                 crate::permissions::Permission::Extension(ext_str) => {
                     Self::extract_manifest_scopes(&plugin.manifest, ext_str)
                 }
@@ -416,7 +654,9 @@ impl PluginManager {
         }
 
         // Pre-compute authorization_details so the plugin's first token carries permissions
-        self.auth.refresh_auth_details(&plugin.manifest.id, &plugin.oauth_client_id);
+        if !plugin.oauth_client_id.is_empty() {
+            self.auth.refresh_auth_details(&plugin.manifest.id, &plugin.oauth_client_id);
+        }
 
         self.storage.add(plugin.clone())?;
 
@@ -435,9 +675,16 @@ impl PluginManager {
             .get(plugin_id)
             .ok_or_else(|| NexusError::PluginNotFound(plugin_id.to_string()))?;
 
+        if let Some(url) = plugin.manifest.remote_mcp_url() {
+            let url = url.to_string();
+            return self.start_remote(plugin_id, &url).await;
+        }
+
         let manifest = plugin.manifest.clone();
         let port = plugin.assigned_port;
         let old_container_id = plugin.container_id.clone();
+        let security_override = plugin.security_override.clone();
+        let security = security_config(&manifest, &security_override);
 
         let ready_path = manifest
             .health
@@ -483,12 +730,14 @@ impl PluginManager {
                 p.oauth_client_id = new_client_id.clone();
             }
         }
+        let user = self.container_user(&manifest, &security_override).await?;
 
         let mut env_vars: Vec<String> = manifest
             .env
             .iter()
             .map(|(k, v)| format!("{}={}", k, v))
             .collect();
+        env_vars.extend(self.plugin_env.env_vars(&manifest.id));
         let active_client_id = if new_client_id != oauth_client_id {
             &new_client_id
         } else {
@@ -522,7 +771,8 @@ impl PluginManager {
             limits: self.resource_limits(),
             data_volume: Some(volume_name),
             network: "nexus-bridge".to_string(),
-            security: SecurityConfig::default(),
+            security,
+            user,
         })
         .await?;
 
@@ -571,12 +821,41 @@ impl PluginManager {
         Ok(())
     }
 
+    /// "Start" a remote MCP plugin: connect the gateway to its endpoint.
+    /// Fails (leaving the plugin stopped) if the endpoint is unreachable.
+    async fn start_remote(&mut self, plugin_id: &str, url: &str) -> NexusResult<()> {
+        let token = self
+            .plugin_env
+            .get(plugin_id)
+            .remove(storage::REMOTE_MCP_TOKEN_KEY);
+        self.mcp_clients
+            .connect_remote(plugin_id, url, token)
+            .await
+            .map_err(|e| NexusError::Other(format!("Remote MCP server unreachable: {}", e)))?;
+
+        if let Some(plugin) = self.storage.get_mut(plugin_id) {
+            plugin.status = PluginStatus::Running;
+        }
+        self.storage.save()?;
+        log::info!("Connected remote MCP plugin={} at {}", plugin_id, url);
+        Ok(())
+    }
+
     pub async fn stop(&mut self, plugin_id: &str) -> NexusResult<()> {
         let plugin = self
             .storage
             .get(plugin_id)
             .ok_or_else(|| NexusError::PluginNotFound(plugin_id.to_string()))?;
 
+        if plugin.manifest.remote_mcp_url().is_some() {
+            self.mcp_clients.disconnect(plugin_id);
+            if let Some(plugin) = self.storage.get_mut(plugin_id) {
+                plugin.status = PluginStatus::Stopped;
+            }
+            self.storage.save()?;
+            return Ok(());
+        }
+
         let container_id = plugin
             .container_id
             .clone()
@@ -600,6 +879,115 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Move plugins to another container engine. Containers on the current
+    /// engine are stopped and removed, each plugin's image is pulled on the
+    /// new engine and its data volume copied over, then `runtime` takes
+    /// over with every container plugin stopped. The old engine keeps its
+    /// images and volumes.
+    pub async fn switch_runtime(&mut self, runtime: Arc<dyn ContainerRuntime>) -> EngineSwitch {
+        let plugins: Vec<(String, String, Option<String>, PluginStatus)> = self
+            .storage
+            .list()
+            .iter()
+            .filter(|p| p.manifest.remote_mcp_url().is_none())
+            .map(|p| {
+                (
+                    p.manifest.id.clone(),
+                    p.manifest.image.clone(),
+                    p.container_id.clone(),
+                    p.status.clone(),
+                )
+            })
+            .collect();
+        let mut report = EngineSwitch::default();
+
+        for (plugin_id, _, container_id, status) in &plugins {
+            if *status == PluginStatus::Running {
+                if let Err(e) = self.stop(plugin_id).await {
+                    log::warn!("Engine switch: failed to stop plugin={}: {}", plugin_id, e);
+                }
+                report.was_running.push(plugin_id.clone());
+            }
+            if let Some(cid) = container_id {
+                if let Err(e) = self.runtime.remove_container(cid).await {
+                    log::warn!("Engine switch: failed to remove container {}: {}", cid, e);
+                }
+            }
+            // Container IDs mean nothing to the new engine
+            if let Some(plugin) = self.storage.get_mut(plugin_id) {
+                plugin.container_id = None;
+                plugin.status = PluginStatus::Stopped;
+            }
+        }
+        if let Err(e) = self.storage.save() {
+            log::warn!("Engine switch: failed to save plugin state: {}", e);
+        }
+
+        log::info!(
+            "Switching container engine: {} -> {} ({})",
+            self.runtime.engine_id(),
+            runtime.engine_id(),
+            runtime.socket_path()
+        );
+        if let Err(e) = runtime.ensure_network("nexus-bridge").await {
+            log::warn!("Engine switch: failed to create network: {}", e);
+        }
+
+        let old_volumes: Vec<String> = match self.runtime.list_volumes().await {
+            Ok(volumes) => volumes.into_iter().map(|v| v.name).collect(),
+            Err(e) => {
+                log::warn!("Engine switch: failed to list volumes: {}", e);
+                Vec::new()
+            }
+        };
+        for (plugin_id, image, _, _) in &plugins {
+            let has_image = runtime.image_exists(image).await.unwrap_or(false)
+                || match runtime.pull_image(image).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log::warn!("Engine switch: failed to pull {}: {}", image, e);
+                        false
+                    }
+                };
+            if !has_image {
+                report.missing_images.push(plugin_id.clone());
+            }
+
+            let volume = data_volume_name(plugin_id);
+            if !old_volumes.contains(&volume) {
+                continue;
+            }
+            // Copying goes through a container of the plugin's image on
+            // each side
+            let copied = has_image && self.copy_volume(&runtime, &volume, image).await;
+            if !copied {
+                report.volumes_not_copied.push(plugin_id.clone());
+            }
+        }
+
+        self.runtime = runtime;
+        self.engine_version_tx.send_modify(|v| *v += 1);
+        report
+    }
+
+    /// Copy `volume` from the current engine to `target`. Failures are
+    /// logged and leave `target` as it was.
+    async fn copy_volume(&self, target: &Arc<dyn ContainerRuntime>, volume: &str, image: &str) -> bool {
+        let scratch = self.data_dir.join(format!("{volume}.engine-switch.tar"));
+        let result = match self.runtime.export_volume(volume, image, &scratch).await {
+            Ok(_) => target.import_volume(volume, image, &scratch).await,
+            Err(e) => Err(e),
+        };
+        let _ = std::fs::remove_file(&scratch);
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Engine switch: failed to copy volume {}: {}", volume, e);
+                false
+            }
+        }
+    }
+
     pub async fn remove(&mut self, plugin_id: &str) -> NexusResult<()> {
         let plugin = self
             .storage
@@ -608,9 +996,20 @@ impl PluginManager {
 
         let image_name = plugin.manifest.image.clone();
         let oauth_client_id = plugin.oauth_client_id.clone();
+        let remote = plugin.manifest.remote_mcp_url().is_some();
 
         // Disconnect native MCP client
         self.mcp_clients.disconnect(plugin_id);
+        self.image_scans.remove(plugin_id);
+        self.root_plugins.remove(plugin_id);
+
+        if remote {
+            crate::host_api::storage::remove_plugin_storage(&self.data_dir, plugin_id);
+            self.storage.remove(plugin_id)?;
+            self.permissions.revoke_all(plugin_id)?;
+            self.plugin_env.remove(plugin_id)?;
+            return Ok(());
+        }
 
         if let Some(container_id) = &plugin.container_id {
             // Stop first if running
@@ -637,6 +1036,7 @@ impl PluginManager {
 
         self.storage.remove(plugin_id)?;
         self.permissions.revoke_all(plugin_id)?;
+        self.plugin_env.remove(plugin_id)?;
 
         Ok(())
     }
@@ -671,6 +1071,7 @@ impl PluginManager {
             );
             self.registry_cache = cache.plugins;
             self.extension_registry_cache = cache.extensions;
+            self.collection_cache = cache.collections;
         } else {
             log::info!("No registry cache found on disk");
         }
@@ -679,35 +1080,177 @@ impl PluginManager {
 
     /// Refresh the registry using conditional GET (ETag / If-None-Match).
     /// Saves the result to disk cache after fetching.
-    pub async fn refresh_registry(&mut self) -> NexusResult<()> {
+    /// Fetch every enabled registry. Needs only a read lock, so the
+    /// network round trips don't hold up installs or plugin starts.
+    pub async fn fetch_registry(&self) -> registry::RegistryCache {
         let existing_cache = registry::load_cache(&self.data_dir)
             .unwrap_or_default();
 
         let (result, new_etags) =
             registry::fetch_all_conditional(&self.registry_store, &existing_cache).await;
 
-        self.registry_cache = result.plugins.clone();
-        self.extension_registry_cache = result.extensions.clone();
-
-        // Persist to disk for future instant loads.
-        let cache = registry::RegistryCache {
+        registry::RegistryCache {
             plugins: result.plugins,
             extensions: result.extensions,
+            collections: result.collections,
             last_refreshed: chrono::Utc::now().to_rfc3339(),
             etags: new_etags,
-        };
+        }
+    }
+
+    /// Swap in a cache from [`fetch_registry`](Self::fetch_registry).
+    pub fn apply_registry(&mut self, cache: registry::RegistryCache) {
+        self.registry_cache = cache.plugins.clone();
+        self.extension_registry_cache = cache.extensions.clone();
+        self.collection_cache = cache.collections.clone();
+
+        // Persist to disk for future instant loads.
         if let Err(e) = registry::save_cache(&self.data_dir, &cache) {
             log::warn!("Failed to save registry cache: {}", e);
         }
-
-        Ok(())
     }
 
     pub fn search_marketplace(&self, query: &str) -> Vec<registry::RegistryEntry> {
-        registry::search_entries(&self.registry_cache, query)
+        let resolved = registry::resolve_conflicts(
+            &self.registry_cache,
+            &self.registry_store,
+            self.registry_store.conflict_policy(),
+        );
+        registry::search_entries(&resolved, query)
+    }
+
+    pub fn browse_marketplace(
+        &self,
+        query: &registry::MarketplaceQuery,
+    ) -> registry::MarketplacePage {
+        let resolved = registry::resolve_conflicts(
+            &self.registry_cache,
+            &self.registry_store,
+            self.registry_store.conflict_policy(),
+        );
+        registry::browse_entries(&resolved, query)
+    }
+
+    /// Registry collections, with their packages resolved the way the
+    /// marketplace lists them.
+    pub fn marketplace_collections(&self) -> Vec<registry::MarketplaceCollection> {
+        let resolved = registry::resolve_conflicts(
+            &self.registry_cache,
+            &self.registry_store,
+            self.registry_store.conflict_policy(),
+        );
+        registry::resolve_collections(
+            &self.collection_cache,
+            &resolved,
+            &self.extension_registry_cache,
+        )
+    }
+
+    /// Signature published for the manifest at `manifest_url`, when a
+    /// registry entry points at it and is signed.
+    pub fn manifest_signature(
+        &self,
+        manifest_url: &str,
+    ) -> NexusResult<Option<registry::ManifestSignature>> {
+        match self
+            .registry_cache
+            .iter()
+            .find(|e| e.manifest_url == manifest_url)
+        {
+            Some(entry) => entry.manifest_signature(),
+            None => Ok(None),
+        }
+    }
+
+    /// Verified publisher identity of the entry listing `manifest_url`, plugin
+    /// or extension, if its registry verified one.
+    pub fn verified_publisher(&self, manifest_url: &str) -> Option<String> {
+        self.registry_cache
+            .iter()
+            .find(|e| e.manifest_url == manifest_url)
+            .map(|e| (e.verified, &e.verified_identity))
+            .or_else(|| {
+                self.extension_registry_cache
+                    .iter()
+                    .find(|e| e.manifest_url == manifest_url)
+                    .map(|e| (e.verified, &e.verified_identity))
+            })
+            .filter(|(verified, _)| *verified)
+            .and_then(|(_, identity)| identity.clone())
+    }
+
+    /// Credentials of the registry listing `manifest_url`, plugin or
+    /// extension, if it has any for that URL's origin.
+    pub fn manifest_auth(&self, manifest_url: &str) -> Option<registry::RegistryAuth> {
+        let source = self
+            .registry_cache
+            .iter()
+            .find(|e| e.manifest_url == manifest_url)
+            .map(|e| &e.source)
+            .or_else(|| {
+                self.extension_registry_cache
+                    .iter()
+                    .find(|e| e.manifest_url == manifest_url)
+                    .map(|e| &e.source)
+            })?;
+        self.registry_store
+            .auth_for_url(source, manifest_url)
+            .cloned()
+    }
+
+    /// Check a manifest author's key against the trusted keys shared with
+    /// extensions: trust it on first use, and refuse a changed key unless
+    /// `force_key` accepts the rotation.
+    pub fn check_author_key(
+        &mut self,
+        author: &str,
+        public_key: &str,
+        force_key: bool,
+    ) -> NexusResult<()> {
+        let keys = &mut self.extension_loader.trusted_keys;
+        let result = match keys.check_key_consistency(author, public_key) {
+            KeyConsistency::NewAuthor => {
+                log::info!("New plugin author '{}', trusting key", author);
+                keys.trust(author, public_key)
+            }
+            KeyConsistency::Matches => Ok(()),
+            KeyConsistency::Changed if force_key => keys.rotate_key(author, public_key),
+            KeyConsistency::Changed => {
+                return Err(NexusError::PermissionDenied(format!(
+                    "Author key changed for '{}'. This could indicate a supply chain attack.",
+                    author
+                )))
+            }
+        };
+        result.map_err(|e| NexusError::Other(e.to_string()))
+    }
+
+    /// Fetch a manifest for install or update, verifying the signature its
+    /// registry entry publishes and the author's key. Unsigned entries and
+    /// URLs outside the registries are fetched as-is.
+    pub async fn fetch_registry_manifest(
+        state: &AppState,
+        manifest_url: &str,
+        force_key: bool,
+    ) -> NexusResult<PluginManifest> {
+        let (signature, auth) = {
+            let mgr = state.read().await;
+            (mgr.manifest_signature(manifest_url)?, mgr.manifest_auth(manifest_url))
+        };
+        let manifest =
+            registry::fetch_verified_manifest(manifest_url, signature.as_ref(), auth.as_ref())
+                .await?;
+        if let Some(sig) = signature {
+            state
+                .write()
+                .await
+                .check_author_key(&manifest.author, &sig.public_key, force_key)?;
+        }
+        Ok(manifest)
     }
 
-    /// Update an installed plugin to a new version from a manifest URL.
+    /// Update an installed plugin to a new version from a manifest URL,
+    /// pulling and vetting the image while holding the manager.
     /// Preserves assigned_port, OAuth client, and permissions.
     pub async fn update_plugin(
         &mut self,
@@ -715,19 +1258,55 @@ impl PluginManager {
         expected_digest: Option<String>,
         app_handle: Option<&tauri::AppHandle>,
     ) -> NexusResult<InstalledPlugin> {
+        self.update_inner(manifest, expected_digest, None, app_handle).await
+    }
+
+    /// [`update_plugin`](Self::update_plugin) with an image that was already
+    /// pulled and vetted.
+    pub async fn update_plugin_vetted(
+        &mut self,
+        manifest: PluginManifest,
+        expected_digest: Option<String>,
+        vetted: VettedImage,
+        app_handle: Option<&tauri::AppHandle>,
+    ) -> NexusResult<InstalledPlugin> {
+        self.update_inner(manifest, expected_digest, Some(vetted), app_handle).await
+    }
+
+    /// Refuse installs and updates while read-only mode is on.
+    pub fn check_writable(&self) -> NexusResult<()> {
+        if self.settings.read_only_mode {
+            return Err(NexusError::PermissionDenied(
+                "Nexus is in read-only mode".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether `manifest` can replace the installed version of its plugin.
+    pub fn check_update(
+        &self,
+        manifest: &PluginManifest,
+        expected_digest: Option<&str>,
+    ) -> NexusResult<()> {
+        self.check_writable()?;
         manifest
             .validate()
             .map_err(NexusError::InvalidManifest)?;
 
-        check_min_nexus_version(&manifest)?;
-
-        let plugin_id = manifest.id.clone();
+        check_min_nexus_version(manifest)?;
 
         let plugin = self
             .storage
             .get(&manifest.id)
             .ok_or_else(|| NexusError::PluginNotFound(manifest.id.clone()))?;
 
+        if plugin.manifest.remote_mcp_url().is_some() != manifest.remote_mcp_url().is_some() {
+            return Err(NexusError::Other(
+                "An update cannot switch a plugin between container and remote MCP".to_string(),
+            ));
+        }
+
         // Security: block digest downgrade
         if plugin.manifest.image_digest.is_some() && manifest.image_digest.is_none() {
             return Err(NexusError::Other(
@@ -746,6 +1325,26 @@ impl PluginManager {
                 }
             }
         }
+        Ok(())
+    }
+
+    async fn update_inner(
+        &mut self,
+        manifest: PluginManifest,
+        expected_digest: Option<String>,
+        vetted: Option<VettedImage>,
+        app_handle: Option<&tauri::AppHandle>,
+    ) -> NexusResult<InstalledPlugin> {
+        self.check_update(&manifest, expected_digest.as_deref())?;
+        if manifest.remote_mcp_url().is_some() {
+            return self.update_remote(manifest).await;
+        }
+
+        let plugin_id = manifest.id.clone();
+        let plugin = self
+            .storage
+            .get(&plugin_id)
+            .ok_or_else(|| NexusError::PluginNotFound(plugin_id.clone()))?;
 
         let was_running = plugin.status == PluginStatus::Running;
         let port = plugin.assigned_port;
@@ -753,9 +1352,12 @@ impl PluginManager {
         let preserved_origin = plugin.manifest_url_origin.clone();
         let preserved_dev_mode = plugin.dev_mode;
         let preserved_local_path = plugin.local_manifest_path.clone();
+        let preserved_channel = plugin.channel;
+        let preserved_security_override = plugin.security_override.clone();
+        let previous = plugin.clone();
 
         // Stop old container (also remove by name as fallback for Docker restarts)
-        emit_update(app_handle, &plugin_id, "stopping");
+        emit_update(app_handle, &plugin_id, "stopping", None);
         if let Some(ref cid) = old_container_id {
             if was_running {
                 if let Err(e) = self.runtime.stop_container(cid).await {
@@ -780,133 +1382,217 @@ impl PluginManager {
             );
         }
 
-        // Pull new image
-        emit_update(app_handle, &plugin_id, "pulling");
-        log::info!("Pulling updated image: {}", manifest.image);
-        self.runtime.pull_image(&manifest.image).await?;
-
-        // Verify digest if present
-        if let Some(ref expected_digest) = manifest.image_digest {
-            match self.runtime.get_image_digest(&manifest.image).await? {
-                Some(actual_digest) => {
-                    if &actual_digest != expected_digest {
-                        return Err(NexusError::Other(format!(
-                            "Image digest mismatch for {}. Expected: {}, Got: {}",
-                            manifest.image, expected_digest, actual_digest
-                        )));
-                    }
-                    log::info!(
-                        "Image digest verified: {} = {}",
-                        manifest.image, actual_digest
-                    );
-                }
+        // From here the old container is gone: undo on failure rather than
+        // leave the plugin without one
+        let new_version = manifest.version.clone();
+        let swapped: NexusResult<InstalledPlugin> = async {
+            let vetted = match vetted {
+                Some(vetted) => vetted,
                 None => {
-                    log::warn!(
-                        "Image {} has no registry digest, skipping digest verification",
-                        manifest.image
-                    );
+                    // Pull new image
+                    emit_update(app_handle, &plugin_id, "pulling", None);
+                    log::info!("Pulling updated image: {}", manifest.image);
+                    let on_progress = |p| emit_update(app_handle, &plugin_id, "pulling", Some(p));
+                    self.runtime
+                        .pull_image_with_progress(&manifest.image, &on_progress)
+                        .await?;
+                    self.vetter(preserved_security_override.clone())
+                        .vet(&manifest)
+                        .await?
                 }
-            }
-        }
+            };
+            self.record_vetting(&plugin_id, &vetted);
+            let user = vetted.user;
 
-        // Rotate secret, revoke old tokens, recompute auth details
-        let oauth_client_id = plugin.oauth_client_id.clone();
-        let (new_client_id, new_secret) =
-            self.auth.prepare_start(&manifest.id, &manifest.name, &oauth_client_id);
+            // Rotate secret, revoke old tokens, recompute auth details
+            let oauth_client_id = previous.oauth_client_id.clone();
+            let (new_client_id, new_secret) =
+                self.auth.prepare_start(&manifest.id, &manifest.name, &oauth_client_id);
 
-        // Create new container
-        let mut env_vars: Vec<String> = manifest
-            .env
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect();
-        let active_client_id = if new_client_id != oauth_client_id {
-            new_client_id.clone()
-        } else {
-            oauth_client_id.clone()
-        };
-        env_vars.push(format!("NEXUS_OAUTH_CLIENT_ID={}", active_client_id));
-        env_vars.push(format!("NEXUS_OAUTH_CLIENT_SECRET={}", new_secret));
-        env_vars.push("NEXUS_API_URL=http://localhost:9600".to_string());
-        env_vars.push(format!(
-            "NEXUS_HOST_URL=http://{}:9600",
-            self.runtime.host_gateway_hostname()
-        ));
-        env_vars.push("NEXUS_DATA_DIR=/data".to_string());
+            // Create new container
+            let mut env_vars: Vec<String> = manifest
+                .env
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            env_vars.extend(self.plugin_env.env_vars(&manifest.id));
+            let active_client_id = if new_client_id != oauth_client_id {
+                new_client_id.clone()
+            } else {
+                oauth_client_id.clone()
+            };
+            env_vars.push(format!("NEXUS_OAUTH_CLIENT_ID={}", active_client_id));
+            env_vars.push(format!("NEXUS_OAUTH_CLIENT_SECRET={}", new_secret));
+            env_vars.push("NEXUS_API_URL=http://localhost:9600".to_string());
+            env_vars.push(format!(
+                "NEXUS_HOST_URL=http://{}:9600",
+                self.runtime.host_gateway_hostname()
+            ));
+            env_vars.push("NEXUS_DATA_DIR=/data".to_string());
+
+            let mut labels = HashMap::new();
+            labels.insert("nexus.plugin.id".to_string(), manifest.id.clone());
+            labels.insert("nexus.plugin.version".to_string(), manifest.version.clone());
+
+            let container_name = format!("nexus-{}", manifest.id.replace('.', "-"));
+            let volume_name = data_volume_name(&manifest.id);
+
+            let container_port = manifest.ui.as_ref().map(|u| u.port).unwrap_or(80);
+            let new_container_id = self.runtime.create_container(ContainerConfig {
+                name: container_name,
+                image: manifest.image.clone(),
+                host_port: port,
+                container_port,
+                env_vars,
+                labels,
+                limits: self.resource_limits(),
+                data_volume: Some(volume_name),
+                network: "nexus-bridge".to_string(),
+                security: security_config(&manifest, &preserved_security_override),
+                user,
+            })
+            .await?;
+
+            let updated_plugin = InstalledPlugin {
+                manifest,
+                container_id: Some(new_container_id.clone()),
+                status: PluginStatus::Stopped,
+                assigned_port: port,
+                oauth_client_id: active_client_id,
+                installed_at: chrono::Utc::now(),
+                manifest_url_origin: preserved_origin,
+                dev_mode: preserved_dev_mode,
+                local_manifest_path: preserved_local_path,
+                channel: preserved_channel,
+                security_override: preserved_security_override,
+            };
 
-        let mut labels = HashMap::new();
-        labels.insert("nexus.plugin.id".to_string(), manifest.id.clone());
-        labels.insert("nexus.plugin.version".to_string(), manifest.version.clone());
+            // Update storage
+            if let Some(existing) = self.storage.get_mut(&updated_plugin.manifest.id) {
+                *existing = updated_plugin.clone();
+            }
 
-        let container_name = format!("nexus-{}", manifest.id.replace('.', "-"));
-        let volume_name = data_volume_name(&manifest.id);
+            // Restart if it was running
+            if was_running {
+                emit_update(app_handle, &plugin_id, "starting", None);
+                let ready_path = updated_plugin
+                    .manifest
+                    .health
+                    .as_ref()
+                    .map(|h| h.endpoint.clone())
+                    .unwrap_or_else(|| {
+                        updated_plugin.manifest.ui.as_ref()
+                            .map(|u| u.path.clone())
+                            .unwrap_or_else(|| "/health".to_string())
+                    });
+
+                self.runtime.start_container(&new_container_id).await?;
+                self.runtime.wait_for_ready(port, &ready_path, std::time::Duration::from_secs(15)).await?;
+
+                if let Some(plugin) = self.storage.get_mut(&updated_plugin.manifest.id) {
+                    plugin.status = PluginStatus::Running;
+                }
+            }
 
-        let container_port = manifest.ui.as_ref().map(|u| u.port).unwrap_or(80);
-        let new_container_id = self.runtime.create_container(ContainerConfig {
-            name: container_name,
-            image: manifest.image.clone(),
-            host_port: port,
-            container_port,
-            env_vars,
-            labels,
-            limits: self.resource_limits(),
-            data_volume: Some(volume_name),
-            network: "nexus-bridge".to_string(),
-            security: SecurityConfig::default(),
-        })
-        .await?;
+            self.storage.save()?;
 
-        let updated_plugin = InstalledPlugin {
-            manifest,
-            container_id: Some(new_container_id.clone()),
-            status: PluginStatus::Stopped,
-            assigned_port: port,
-            oauth_client_id: active_client_id,
-            installed_at: chrono::Utc::now(),
-            manifest_url_origin: preserved_origin,
-            dev_mode: preserved_dev_mode,
-            local_manifest_path: preserved_local_path,
-        };
+            // Reconcile MCP settings so new/removed tools are reflected immediately
+            self.reconcile_mcp_settings(&updated_plugin.manifest.id, &updated_plugin.manifest);
+
+            log::info!(
+                "Updated plugin {} to version {}",
+                updated_plugin.manifest.id,
+                updated_plugin.manifest.version
+            );
 
-        // Update storage
-        if let Some(existing) = self.storage.get_mut(&updated_plugin.manifest.id) {
-            *existing = updated_plugin.clone();
+            Ok(self.storage.get(&updated_plugin.manifest.id).cloned().unwrap())
         }
+        .await;
 
-        // Restart if it was running
-        if was_running {
-            emit_update(app_handle, &plugin_id, "starting");
-            let ready_path = updated_plugin
-                .manifest
-                .health
-                .as_ref()
-                .map(|h| h.endpoint.clone())
-                .unwrap_or_else(|| {
-                    updated_plugin.manifest.ui.as_ref()
-                        .map(|u| u.path.clone())
-                        .unwrap_or_else(|| "/health".to_string())
-                });
-
-            self.runtime.start_container(&new_container_id).await?;
-            self.runtime.wait_for_ready(port, &ready_path, std::time::Duration::from_secs(15)).await?;
-
-            if let Some(plugin) = self.storage.get_mut(&updated_plugin.manifest.id) {
-                plugin.status = PluginStatus::Running;
+        match swapped {
+            Ok(plugin) => Ok(plugin),
+            Err(e) => {
+                log::warn!(
+                    "Update of plugin '{}' to {} failed, rolling back: {}",
+                    plugin_id, new_version, e
+                );
+                emit_update(app_handle, &plugin_id, "rolling_back", None);
+                match self.roll_back_update(previous, was_running).await {
+                    Ok(()) => Err(NexusError::UpdateRolledBack {
+                        version: new_version,
+                        reason: e.to_string(),
+                    }),
+                    Err(rollback_err) => {
+                        log::error!(
+                            "Failed to restore plugin '{}' after a failed update: {}",
+                            plugin_id, rollback_err
+                        );
+                        Err(NexusError::Other(format!(
+                            "Update to {} failed: {}. Restoring the previous version also failed: {}",
+                            new_version, e, rollback_err
+                        )))
+                    }
+                }
             }
         }
+    }
+
+    /// Put a plugin back as it was before a failed update: remove whatever
+    /// container the update created, restore the stored entry, and start the
+    /// previous version again if it was running.
+    async fn roll_back_update(
+        &mut self,
+        previous: InstalledPlugin,
+        was_running: bool,
+    ) -> NexusResult<()> {
+        let plugin_id = previous.manifest.id.clone();
+        let container_name = format!("nexus-{}", plugin_id.replace('.', "-"));
+        if let Err(e) = self.runtime.remove_container(&container_name).await {
+            log::warn!(
+                "Failed to remove container '{}' for plugin '{}' during rollback: {}",
+                container_name, plugin_id, e
+            );
+        }
 
+        if let Some(existing) = self.storage.get_mut(&plugin_id) {
+            *existing = InstalledPlugin {
+                container_id: None,
+                status: PluginStatus::Stopped,
+                ..previous
+            };
+        }
         self.storage.save()?;
 
-        // Reconcile MCP settings so new/removed tools are reflected immediately
-        self.reconcile_mcp_settings(&updated_plugin.manifest.id, &updated_plugin.manifest);
+        if was_running {
+            self.start(&plugin_id).await?;
+        }
+        log::info!("Rolled back plugin {} to its previous version", plugin_id);
+        Ok(())
+    }
 
-        log::info!(
-            "Updated plugin {} to version {}",
-            updated_plugin.manifest.id,
-            updated_plugin.manifest.version
-        );
+    /// Swap a remote MCP plugin's manifest, reconnecting if it was running.
+    async fn update_remote(&mut self, manifest: PluginManifest) -> NexusResult<InstalledPlugin> {
+        let plugin_id = manifest.id.clone();
+        let was_running = self
+            .storage
+            .get(&plugin_id)
+            .is_some_and(|p| p.status == PluginStatus::Running);
+
+        self.mcp_clients.disconnect(&plugin_id);
+        if let Some(existing) = self.storage.get_mut(&plugin_id) {
+            existing.manifest = manifest.clone();
+            existing.status = PluginStatus::Stopped;
+        }
+        self.storage.save()?;
+        self.reconcile_mcp_settings(&plugin_id, &manifest);
 
-        Ok(self.storage.get(&updated_plugin.manifest.id).cloned().unwrap())
+        if was_running {
+            if let Some(url) = manifest.remote_mcp_url() {
+                self.start_remote(&plugin_id, url).await?;
+            }
+        }
+        log::info!("Updated remote MCP plugin {} to version {}", plugin_id, manifest.version);
+        Ok(self.storage.get(&plugin_id).cloned().unwrap())
     }
 
     pub fn search_extension_marketplace(&self, query: &str) -> Vec<registry::ExtensionRegistryEntry> {
@@ -931,6 +1617,8 @@ impl PluginManager {
         force_key: bool,
         manifest_url: Option<&str>,
     ) -> Result<crate::extensions::storage::InstalledExtension, crate::extensions::ExtensionError> {
+        self.check_writable()
+            .map_err(|e| crate::extensions::ExtensionError::Other(e.to_string()))?;
         self.extension_loader
             .update(manifest, &mut self.extensions, force_key, manifest_url)
             .await
@@ -950,6 +1638,8 @@ impl PluginManager {
         manifest_path: &std::path::Path,
         binary_override: Option<&std::path::Path>,
     ) -> Result<crate::extensions::storage::InstalledExtension, crate::extensions::ExtensionError> {
+        self.check_writable()
+            .map_err(|e| crate::extensions::ExtensionError::Other(e.to_string()))?;
         self.extension_loader.install_local(manifest_path, &mut self.extensions, binary_override)
     }
 
@@ -997,6 +1687,7 @@ mod tests {
             mcp: None,
             extensions: HashMap::new(),
             mcp_access: vec![],
+            security: manifest::PluginSecurity::default(),
         }
     }
 
@@ -1008,6 +1699,26 @@ mod tests {
         PluginManager::new(dir.to_path_buf(), mock, permissions, oauth_store)
     }
 
+    #[test]
+    fn security_config_applies_the_rootfs_override() {
+        use manifest::{PluginSecurity, RootfsAccess};
+        let mut manifest = test_manifest("com.test.rootfs");
+        let inherit = PluginSecurity::default();
+        let override_with = |access| PluginSecurity {
+            writable_rootfs: Some(access),
+            ..Default::default()
+        };
+
+        assert!(security_config(&manifest, &inherit).read_only_rootfs);
+        let writable = override_with(RootfsAccess::Writable("Needs /var/cache".into()));
+        assert!(!security_config(&manifest, &writable).read_only_rootfs);
+
+        manifest.security.writable_rootfs = Some(RootfsAccess::Writable("Caches".into()));
+        assert!(!security_config(&manifest, &inherit).read_only_rootfs);
+        let read_only = override_with(RootfsAccess::ReadOnly);
+        assert!(security_config(&manifest, &read_only).read_only_rootfs);
+    }
+
     // -- install --
 
     #[tokio::test]
@@ -1056,6 +1767,29 @@ mod tests {
         )));
     }
 
+    #[tokio::test]
+    async fn read_only_mode_blocks_installs_and_updates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mock = Arc::new(MockRuntime::new());
+        let mut mgr = test_manager(tmp.path(), mock.clone());
+        mgr.install(test_manifest("com.test.ro"), vec![], vec![], None, None)
+            .await
+            .unwrap();
+        mgr.settings.read_only_mode = true;
+
+        let err = mgr
+            .install(test_manifest("com.test.other"), vec![], vec![], None, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("read-only mode"));
+        assert!(!mock.was_called(&RuntimeCall::CreateContainer("nexus-com-test-other".into())));
+
+        let mut update = test_manifest("com.test.ro");
+        update.version = "2.0.0".into();
+        assert!(mgr.update_plugin(update, None, None).await.is_err());
+        assert_eq!(mgr.storage.get("com.test.ro").unwrap().manifest.version, "1.0.0");
+    }
+
     #[tokio::test]
     async fn install_verifies_matching_digest() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1144,6 +1878,49 @@ mod tests {
         );
     }
 
+    // -- switch_runtime --
+
+    #[tokio::test]
+    async fn switch_runtime_moves_plugins_to_new_engine() {
+        let tmp = tempfile::tempdir().unwrap();
+        let old = Arc::new(MockRuntime::new());
+        let old_ref = Arc::clone(&old);
+        let mut mgr = test_manager(tmp.path(), old);
+
+        let m = test_manifest("com.test.switch");
+        let image = m.image.clone();
+        let plugin = mgr.install(m, vec![], vec![], None, None).await.unwrap();
+        let cid = plugin.container_id.clone().unwrap();
+        if let Some(p) = mgr.storage.get_mut("com.test.switch") {
+            p.status = PluginStatus::Running;
+        }
+
+        let new = Arc::new(MockRuntime::new());
+        let new_ref = Arc::clone(&new);
+        let switch = mgr.switch_runtime(new).await;
+
+        assert_eq!(switch.was_running, vec!["com.test.switch".to_string()]);
+        assert!(switch.missing_images.is_empty());
+        assert!(switch.volumes_not_copied.is_empty());
+
+        let volume = data_volume_name("com.test.switch");
+        assert!(old_ref.was_called(&RuntimeCall::StopContainer(cid.clone())));
+        assert!(old_ref.was_called(&RuntimeCall::RemoveContainer(cid)));
+        assert!(old_ref.was_called(&RuntimeCall::ExportVolume(volume.clone())));
+        assert!(new_ref.was_called(&RuntimeCall::EnsureNetwork("nexus-bridge".into())));
+        assert!(new_ref.was_called(&RuntimeCall::PullImage(image)));
+        assert!(new_ref.volume_exists(&volume));
+
+        let plugin = mgr.storage.get("com.test.switch").unwrap();
+        assert_eq!(plugin.status, PluginStatus::Stopped);
+        assert!(plugin.container_id.is_none());
+        assert_eq!(*mgr.engine_version_rx.borrow(), 1);
+
+        // Starting again uses the new engine
+        mgr.start("com.test.switch").await.unwrap();
+        assert_eq!(new_ref.container_count(), 1);
+    }
+
     // -- remove --
 
     #[tokio::test]
@@ -1385,6 +2162,80 @@ mod tests {
         }));
     }
 
+    #[tokio::test]
+    async fn failed_update_restores_previous_version() {
+        let tmp = tempfile::tempdir().unwrap();
+        // The installed image is local; pulling the update fails
+        let mock = Arc::new(
+            MockRuntime::new()
+                .with_image("test-com-test-rollback:latest")
+                .fail_pull(),
+        );
+        let mock_ref = Arc::clone(&mock);
+        let mut mgr = test_manager(tmp.path(), mock);
+
+        let m = test_manifest("com.test.rollback");
+        mgr.install(m, vec![], vec![], None, None).await.unwrap();
+        if let Some(p) = mgr.storage.get_mut("com.test.rollback") {
+            p.status = PluginStatus::Running;
+        }
+
+        let mut m2 = test_manifest("com.test.rollback");
+        m2.version = "2.0.0".into();
+        let err = mgr.update_plugin(m2, None, None).await.unwrap_err();
+        assert!(
+            matches!(err, NexusError::UpdateRolledBack { ref version, .. } if version == "2.0.0"),
+            "{}",
+            err
+        );
+
+        // The previous version is back and running in a fresh container
+        let restored = mgr.storage.get("com.test.rollback").unwrap();
+        assert_eq!(restored.manifest.version, "1.0.0");
+        assert_eq!(restored.status, PluginStatus::Running);
+        let cid = restored.container_id.clone().unwrap();
+        assert!(mock_ref.was_called(&RuntimeCall::StartContainer(cid)));
+    }
+
+    // -- signed manifests --
+
+    #[tokio::test]
+    async fn signed_manifest_must_match_its_signature() {
+        use ed25519_dalek::{Signer, SigningKey};
+        use sha2::{Digest, Sha256};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("plugin.json");
+        let json = serde_json::to_string(&test_manifest("com.test.signed")).unwrap();
+        std::fs::write(&path, &json).unwrap();
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let b64 = |b: &[u8]| base64::Engine::encode(&base64::engine::general_purpose::STANDARD, b);
+        let sig = registry::ManifestSignature {
+            public_key: b64(key.verifying_key().as_bytes()),
+            signature: b64(&key.sign(&Sha256::digest(json.as_bytes())).to_bytes()),
+        };
+        let url = format!("file://{}", path.display());
+        assert!(registry::fetch_verified_manifest(&url, Some(&sig), None).await.is_ok());
+
+        // Any change to the file invalidates the signature
+        std::fs::write(&path, json.replace("1.0.0", "1.0.1")).unwrap();
+        assert!(registry::fetch_verified_manifest(&url, Some(&sig), None).await.is_err());
+        assert!(registry::fetch_verified_manifest(&url, None, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn author_key_change_requires_force() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut mgr = test_manager(tmp.path(), Arc::new(MockRuntime::new()));
+
+        mgr.check_author_key("Alice", "key-one", false).unwrap();
+        mgr.check_author_key("Alice", "key-one", false).unwrap();
+        assert!(mgr.check_author_key("Alice", "key-two", false).is_err());
+        mgr.check_author_key("Alice", "key-two", true).unwrap();
+        mgr.check_author_key("Alice", "key-two", false).unwrap();
+    }
+
     // -- install + remove round-trip --
 
     #[tokio::test]
@@ -0,0 +1,124 @@
+//! Keeping plugin containers off the root user.
+//!
+//! Images that don't declare a `USER` run as root. At install and update the
+//! user a plugin will run as is checked against the [`RootUserPolicy`]; a
+//! manifest or the user can also name a non-root user outright.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{NexusError, NexusResult};
+
+/// `nobody`, used when root images are forced onto a non-root user.
+pub const FORCED_USER: &str = "65534:65534";
+
+/// What an image that runs as root does to an install or update.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RootUserPolicy {
+    /// Root images install without comment.
+    Allow,
+    /// The install goes ahead and the user is told.
+    #[default]
+    Warn,
+    /// The install fails before the container is created.
+    Block,
+    /// Root images run as [`FORCED_USER`]. Plugins that expect to own their
+    /// files may fail.
+    ForceNonRoot,
+}
+
+/// Whether a container `user` (`name`, `uid`, or either with `:group`) is
+/// root. An unset user is root.
+pub fn is_root(user: Option<&str>) -> bool {
+    let Some(user) = user.filter(|u| !u.is_empty()) else {
+        return true;
+    };
+    let name = user.split(':').next().unwrap_or(user);
+    name == "root" || name == "0"
+}
+
+/// Whether `user` is something the engine accepts as a container user.
+pub fn is_valid_user(user: &str) -> bool {
+    let valid = |part: &str| {
+        !part.is_empty()
+            && part.len() <= 32
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+    let mut parts = user.splitn(2, ':');
+    parts.next().is_some_and(valid) && parts.next().map_or(true, valid)
+}
+
+/// The user to run a plugin's container as in place of the image's, or None
+/// to keep the image's. A `declared` user wins over the policy.
+pub fn run_as(
+    policy: RootUserPolicy,
+    image_user: Option<&str>,
+    declared: Option<&str>,
+) -> Option<String> {
+    if let Some(user) = declared {
+        return Some(user.to_string());
+    }
+    (policy == RootUserPolicy::ForceNonRoot && is_root(image_user)).then(|| FORCED_USER.to_string())
+}
+
+/// Check the user a plugin's container will run as against `policy`.
+/// Returns whether it runs as root, or an error when the policy blocks it.
+pub fn check(policy: RootUserPolicy, image: &str, user: Option<&str>) -> NexusResult<bool> {
+    if !is_root(user) {
+        return Ok(false);
+    }
+    match policy {
+        RootUserPolicy::Allow => {}
+        RootUserPolicy::Block => {
+            return Err(NexusError::Other(format!(
+                "Blocked by the root user policy: {} runs as root",
+                image
+            )));
+        }
+        RootUserPolicy::Warn | RootUserPolicy::ForceNonRoot => {
+            log::warn!("{} runs as root", image);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_and_zero_users_are_root() {
+        assert!(is_root(None));
+        assert!(is_root(Some("")));
+        assert!(is_root(Some("root")));
+        assert!(is_root(Some("0:0")));
+        assert!(!is_root(Some("node")));
+        assert!(!is_root(Some("1000:0")));
+
+        assert!(is_valid_user("1000:1000"));
+        assert!(is_valid_user("app"));
+        assert!(!is_valid_user("1000:"));
+        assert!(!is_valid_user("a b"));
+    }
+
+    #[test]
+    fn policy_decides_what_root_images_do() {
+        assert_eq!(run_as(RootUserPolicy::Warn, None, None), None);
+        assert_eq!(
+            run_as(RootUserPolicy::ForceNonRoot, Some("root"), None).as_deref(),
+            Some(FORCED_USER)
+        );
+        assert_eq!(run_as(RootUserPolicy::ForceNonRoot, Some("node"), None), None);
+        assert_eq!(
+            run_as(RootUserPolicy::ForceNonRoot, None, Some("1000")).as_deref(),
+            Some("1000")
+        );
+
+        assert!(check(RootUserPolicy::Warn, "img", None).unwrap());
+        assert!(!check(RootUserPolicy::Block, "img", Some("node")).unwrap());
+        assert!(check(RootUserPolicy::Block, "img", Some("0")).is_err());
+        assert!(check(RootUserPolicy::Allow, "img", None).unwrap());
+    }
+}
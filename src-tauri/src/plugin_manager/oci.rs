@@ -0,0 +1,606 @@
+//! Registries of kind `oci`: a plugin published to an OCI registry (GHCR,
+//! Docker Hub, ...) as its image plus the plugin manifest attached to it as
+//! an artifact (`oras attach --artifact-type` [`MANIFEST_ARTIFACT_TYPE`]), so
+//! one push distributes both. A source is one repository; its semver tags are
+//! the plugin's releases. Every fetch is checked against its digest, and
+//! installs pull the image by the digest the artifact refers to.
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::manifest::PluginManifest;
+use super::registry::{self, Registry, RegistryAuth, RegistryEntry, ReleaseChannel};
+use crate::error::{NexusError, NexusResult};
+
+/// Artifact type, and layer media type, of an attached plugin manifest.
+pub const MANIFEST_ARTIFACT_TYPE: &str = "application/vnd.nexus.plugin.manifest.v1+json";
+
+/// Artifact annotations carrying the author key and manifest signature
+/// checked at install, as in a registry index.
+const ANNOTATION_PUBLIC_KEY: &str = "dev.nexus.author-public-key";
+const ANNOTATION_SIGNATURE: &str = "dev.nexus.manifest-signature";
+
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.index.v1+json, \
+     application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json, \
+     application/vnd.docker.distribution.manifest.v2+json";
+
+/// Older tags tried per channel when the newest one has no manifest attached.
+const MAX_TAGS_PER_CHANNEL: usize = 5;
+
+/// A repository in an OCI registry, optionally at a tag or digest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OciReference {
+    /// Registry host, with the port if any.
+    pub registry: String,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+impl OciReference {
+    /// Parse `oci://ghcr.io/acme/weather`, optionally followed by `:tag` or
+    /// `@sha256:...`. The scheme may be left out; a reference without a
+    /// registry host is on Docker Hub.
+    pub fn parse(url: &str) -> NexusResult<Self> {
+        let invalid = || NexusError::Other(format!("Invalid OCI reference: {}", url));
+        let rest = url.strip_prefix("oci://").unwrap_or(url);
+        let (rest, digest) = match rest.split_once('@') {
+            Some((rest, digest)) => {
+                let hex = digest.strip_prefix("sha256:").ok_or_else(invalid)?;
+                if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(invalid());
+                }
+                (rest, Some(digest.to_string()))
+            }
+            None => (rest, None),
+        };
+        let (rest, tag) = match rest.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, Some(tag.to_string())),
+            _ => (rest, None),
+        };
+
+        let (registry, repository) = match rest.split_once('/') {
+            Some((host, path)) if host.contains(['.', ':']) || host == "localhost" => {
+                (host.to_string(), path.to_string())
+            }
+            Some(_) => ("docker.io".to_string(), rest.to_string()),
+            None => ("docker.io".to_string(), format!("library/{}", rest)),
+        };
+        let valid_repository = !repository.is_empty()
+            && repository.split('/').all(|part| {
+                !part.is_empty()
+                    && part.chars().all(|c| {
+                        c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-')
+                    })
+            });
+        if !valid_repository {
+            return Err(invalid());
+        }
+        Ok(Self {
+            registry,
+            repository,
+            tag,
+            digest,
+        })
+    }
+
+    /// The repository as an `oci://` URL, without tag or digest.
+    pub fn repository_url(&self) -> String {
+        format!("oci://{}/{}", self.registry, self.repository)
+    }
+
+    /// Image reference pinned to `digest`, as passed to the container runtime.
+    pub fn image(&self, digest: &str) -> String {
+        format!("{}/{}@{}", self.registry, self.repository, digest)
+    }
+
+    fn api_base(&self) -> String {
+        let host = self.registry.split(':').next().unwrap_or_default();
+        match self.registry.as_str() {
+            "docker.io" => "https://registry-1.docker.io".to_string(),
+            _ if matches!(host, "localhost" | "127.0.0.1") => format!("http://{}", self.registry),
+            _ => format!("https://{}", self.registry),
+        }
+    }
+}
+
+/// A plugin manifest attached to an image, as served.
+pub struct ManifestArtifact {
+    pub text: String,
+    /// Image reference pinned to the digest the artifact refers to.
+    pub image: String,
+    pub image_digest: String,
+}
+
+#[derive(Deserialize)]
+struct Descriptor {
+    #[serde(rename = "mediaType", default)]
+    media_type: String,
+    digest: String,
+    #[serde(rename = "artifactType", default)]
+    artifact_type: Option<String>,
+    #[serde(default)]
+    annotations: std::collections::HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ImageIndex {
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+struct ArtifactManifest {
+    #[serde(rename = "artifactType", default)]
+    artifact_type: Option<String>,
+    config: Option<Descriptor>,
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    subject: Option<Descriptor>,
+    #[serde(default)]
+    annotations: std::collections::HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct TagList {
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Distribution API client for one repository, handling the bearer token
+/// exchange registries ask for even for anonymous pulls.
+struct OciClient {
+    http: reqwest::Client,
+    reference: OciReference,
+    auth: Option<RegistryAuth>,
+    token: Option<String>,
+}
+
+impl OciClient {
+    fn new(reference: OciReference, auth: Option<&RegistryAuth>) -> NexusResult<Self> {
+        // A bearer token is used as the registry token directly
+        let token = match auth {
+            Some(RegistryAuth::Bearer { token }) => Some(token.clone()),
+            _ => None,
+        };
+        Ok(Self {
+            http: registry::http_client()?,
+            reference,
+            auth: auth.cloned(),
+            token,
+        })
+    }
+
+    async fn get(&mut self, path: &str, accept: Option<&str>) -> NexusResult<reqwest::Response> {
+        let url = format!(
+            "{}/v2/{}/{}",
+            self.reference.api_base(),
+            self.reference.repository,
+            path
+        );
+        let response = self.send(&url, accept).await?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED || self.token.is_some() {
+            return Ok(response);
+        }
+        let challenge = response
+            .headers()
+            .get(reqwest::header::WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_bearer_challenge)
+            .ok_or_else(|| {
+                NexusError::Other(format!(
+                    "OCI registry {} refused access",
+                    self.reference.registry
+                ))
+            })?;
+        self.token = Some(self.fetch_token(&challenge).await?);
+        self.send(&url, accept).await
+    }
+
+    async fn send(&self, url: &str, accept: Option<&str>) -> NexusResult<reqwest::Response> {
+        let mut request = self.http.get(url);
+        if let Some(accept) = accept {
+            request = request.header(reqwest::header::ACCEPT, accept);
+        }
+        request = match (&self.token, &self.auth) {
+            (Some(token), _) => request.bearer_auth(token),
+            (None, Some(auth)) => auth.apply(request),
+            (None, None) => request,
+        };
+        request.send().await.map_err(NexusError::Http)
+    }
+
+    async fn fetch_token(&self, challenge: &BearerChallenge) -> NexusResult<String> {
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            token: Option<String>,
+            access_token: Option<String>,
+        }
+
+        let scope = challenge
+            .scope
+            .clone()
+            .unwrap_or_else(|| format!("repository:{}:pull", self.reference.repository));
+        let mut url = url::Url::parse(&challenge.realm)
+            .map_err(|e| NexusError::Other(format!("Invalid OCI token realm: {}", e)))?;
+        url.query_pairs_mut().append_pair("scope", &scope);
+        if let Some(service) = &challenge.service {
+            url.query_pairs_mut().append_pair("service", service);
+        }
+        let mut request = self.http.get(url);
+        if let Some(RegistryAuth::Basic { username, password }) = &self.auth {
+            request = request.basic_auth(username, Some(password));
+        }
+        let response = request.send().await.map_err(NexusError::Http)?;
+        if !response.status().is_success() {
+            return Err(NexusError::Other(format!(
+                "OCI token request returned status {}",
+                response.status()
+            )));
+        }
+        let body: TokenResponse = response.json().await.map_err(NexusError::Http)?;
+        body.token
+            .or(body.access_token)
+            .ok_or_else(|| NexusError::Other("OCI token response has no token".to_string()))
+    }
+
+    /// Body of a manifest or blob, checked against `digest` when given.
+    async fn fetch(
+        &mut self,
+        path: &str,
+        accept: Option<&str>,
+        digest: Option<&str>,
+    ) -> NexusResult<Option<Vec<u8>>> {
+        let response = self.get(path, accept).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(NexusError::Other(format!(
+                "OCI registry returned status {} for {}",
+                response.status(),
+                path
+            )));
+        }
+        if response.content_length().unwrap_or(0) > registry::MAX_FETCH_BYTES as u64 {
+            return Err(NexusError::Other("Response too large".to_string()));
+        }
+        let bytes = response.bytes().await.map_err(NexusError::Http)?;
+        if bytes.len() > registry::MAX_FETCH_BYTES {
+            return Err(NexusError::Other("Response too large".to_string()));
+        }
+        if let Some(expected) = digest {
+            let actual = sha256_digest(&bytes);
+            if actual != expected {
+                return Err(NexusError::Other(format!(
+                    "OCI content digest mismatch for {}. Expected: {}, Got: {}",
+                    path, expected, actual
+                )));
+            }
+        }
+        Ok(Some(bytes.to_vec()))
+    }
+
+    /// Digest of the image a tag points at: of the index for multi-platform
+    /// images, as the runtime reports after pulling.
+    async fn resolve_tag(&mut self, tag: &str) -> NexusResult<Option<String>> {
+        let body = self
+            .fetch(&format!("manifests/{}", tag), Some(MANIFEST_ACCEPT), None)
+            .await?;
+        Ok(body.map(|b| sha256_digest(&b)))
+    }
+
+    /// The plugin manifest artifact referring to `image_digest`, through the
+    /// referrers API or, where a registry lacks it, the referrers tag.
+    async fn find_artifact(&mut self, image_digest: &str) -> NexusResult<Option<Descriptor>> {
+        let referrers = format!(
+            "referrers/{}?artifactType={}",
+            image_digest,
+            MANIFEST_ARTIFACT_TYPE.replace('+', "%2B")
+        );
+        let index = match self
+            .fetch(&referrers, Some("application/vnd.oci.image.index.v1+json"), None)
+            .await?
+        {
+            Some(body) => body,
+            None => {
+                let fallback_tag = image_digest.replace(':', "-");
+                match self
+                    .fetch(&format!("manifests/{}", fallback_tag), Some(MANIFEST_ACCEPT), None)
+                    .await?
+                {
+                    Some(body) => body,
+                    None => return Ok(None),
+                }
+            }
+        };
+        let index: ImageIndex = serde_json::from_slice(&index)
+            .map_err(|e| NexusError::Other(format!("Invalid OCI referrers index: {}", e)))?;
+        Ok(index
+            .manifests
+            .into_iter()
+            .find(|d| d.artifact_type.as_deref() == Some(MANIFEST_ARTIFACT_TYPE)))
+    }
+
+    /// Fetch the artifact manifest at `digest` and the plugin manifest in it.
+    async fn fetch_artifact(&mut self, digest: &str) -> NexusResult<(ArtifactManifest, String)> {
+        let body = self
+            .fetch(
+                &format!("manifests/{}", digest),
+                Some("application/vnd.oci.image.manifest.v1+json"),
+                Some(digest),
+            )
+            .await?
+            .ok_or_else(|| NexusError::Other(format!("OCI artifact {} not found", digest)))?;
+        let artifact: ArtifactManifest = serde_json::from_slice(&body)
+            .map_err(|e| NexusError::Other(format!("Invalid OCI artifact manifest: {}", e)))?;
+
+        let typed = artifact.artifact_type.as_deref() == Some(MANIFEST_ARTIFACT_TYPE)
+            || artifact
+                .config
+                .as_ref()
+                .is_some_and(|c| c.media_type == MANIFEST_ARTIFACT_TYPE);
+        let layer = artifact
+            .layers
+            .iter()
+            .find(|l| l.media_type == MANIFEST_ARTIFACT_TYPE)
+            .or_else(|| artifact.layers.first().filter(|_| typed))
+            .ok_or_else(|| {
+                NexusError::Other(format!("OCI artifact {} has no plugin manifest", digest))
+            })?;
+        let blob_digest = layer.digest.clone();
+        let blob = self
+            .fetch(&format!("blobs/{}", blob_digest), None, Some(&blob_digest))
+            .await?
+            .ok_or_else(|| NexusError::Other(format!("OCI blob {} not found", blob_digest)))?;
+        let text = String::from_utf8(blob)
+            .map_err(|_| NexusError::Other("Plugin manifest is not valid UTF-8".to_string()))?;
+        Ok((artifact, text))
+    }
+}
+
+struct BearerChallenge {
+    realm: String,
+    service: Option<String>,
+    scope: Option<String>,
+}
+
+/// Parse `Bearer realm="...",service="...",scope="..."`.
+fn parse_bearer_challenge(header: &str) -> Option<BearerChallenge> {
+    let params = header.strip_prefix("Bearer ")?;
+    let mut realm = None;
+    let mut service = None;
+    let mut scope = None;
+    let mut rest = params.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let after = after.trim_start();
+        let (value, next) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        match key.trim() {
+            "realm" => realm = Some(value.to_string()),
+            "service" => service = Some(value.to_string()),
+            "scope" => scope = Some(value.to_string()),
+            _ => {}
+        }
+        rest = next.trim_start_matches([',', ' ']);
+    }
+    Some(BearerChallenge {
+        realm: realm?,
+        service,
+        scope,
+    })
+}
+
+fn sha256_digest(bytes: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(bytes))
+}
+
+/// Release tags, newest first: stable ones and pre-releases. Tags may carry
+/// a leading `v`; tags that aren't versions are ignored.
+fn release_tags(tags: &[String]) -> (Vec<&str>, Vec<&str>) {
+    let mut versions: Vec<(semver::Version, &str)> = tags
+        .iter()
+        .filter_map(|tag| {
+            let version = semver::Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+            Some((version, tag.as_str()))
+        })
+        .collect();
+    versions.sort_by(|a, b| b.0.cmp(&a.0));
+    let (pre, stable): (Vec<_>, Vec<_>) = versions.into_iter().partition(|(v, _)| !v.pre.is_empty());
+    (
+        stable.into_iter().map(|(_, t)| t).collect(),
+        pre.into_iter().map(|(_, t)| t).collect(),
+    )
+}
+
+/// List the plugin releases in the repository at `url`: the newest stable
+/// tag and any newer pre-release that carry a plugin manifest.
+pub async fn fetch_registry(url: &str, auth: Option<&RegistryAuth>) -> NexusResult<Registry> {
+    let reference = OciReference::parse(url)?;
+    let mut client = OciClient::new(reference.clone(), auth)?;
+
+    let tags = client
+        .fetch("tags/list?n=1000", None, None)
+        .await?
+        .ok_or_else(|| NexusError::Other(format!("OCI repository {} not found", url)))?;
+    let tags: TagList = serde_json::from_slice(&tags)
+        .map_err(|e| NexusError::Other(format!("Invalid OCI tag list: {}", e)))?;
+    let (stable, pre) = release_tags(&tags.tags);
+
+    let mut plugins = Vec::new();
+    let mut newest_stable = None;
+    for (candidates, channel) in [(stable, ReleaseChannel::Stable), (pre, ReleaseChannel::Beta)] {
+        for tag in candidates.into_iter().take(MAX_TAGS_PER_CHANNEL) {
+            // A pre-release is only listed while it is ahead of the stable release
+            if channel == ReleaseChannel::Beta
+                && newest_stable.as_ref().is_some_and(|stable: &String| {
+                    crate::version::compare_versions(stable, tag.trim_start_matches('v'))
+                        != Some(std::cmp::Ordering::Less)
+                })
+            {
+                break;
+            }
+            match release_entry(&mut client, &reference, tag, channel).await {
+                Ok(Some(entry)) => {
+                    if channel == ReleaseChannel::Stable {
+                        newest_stable = Some(entry.version.clone());
+                    }
+                    plugins.push(entry);
+                    break;
+                }
+                Ok(None) => log::info!("OCI tag {}:{} has no plugin manifest", url, tag),
+                Err(e) => log::warn!("Skipping OCI tag {}:{}: {}", url, tag, e),
+            }
+        }
+    }
+
+    Ok(Registry {
+        version: 2,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+        plugins,
+        extensions: Vec::new(),
+        registry: None,
+        collections: Vec::new(),
+    })
+}
+
+async fn release_entry(
+    client: &mut OciClient,
+    reference: &OciReference,
+    tag: &str,
+    channel: ReleaseChannel,
+) -> NexusResult<Option<RegistryEntry>> {
+    let Some(image_digest) = client.resolve_tag(tag).await? else {
+        return Ok(None);
+    };
+    let Some(descriptor) = client.find_artifact(&image_digest).await? else {
+        return Ok(None);
+    };
+    let (artifact, text) = client.fetch_artifact(&descriptor.digest).await?;
+    let manifest: PluginManifest = serde_json::from_str(&text)
+        .map_err(|e| NexusError::Other(format!("Invalid manifest JSON: {}", e)))?;
+    let annotation = |key: &str| {
+        artifact
+            .annotations
+            .get(key)
+            .or_else(|| descriptor.annotations.get(key))
+            .cloned()
+    };
+
+    Ok(Some(RegistryEntry {
+        id: manifest.id,
+        name: manifest.name,
+        version: manifest.version,
+        description: manifest.description,
+        image: reference.image(&image_digest),
+        image_digest: Some(image_digest),
+        manifest_url: format!("{}@{}", reference.repository_url(), descriptor.digest),
+        manifest_sha256: artifact
+            .layers
+            .iter()
+            .find(|l| l.media_type == MANIFEST_ARTIFACT_TYPE)
+            .or(artifact.layers.first())
+            .and_then(|l| l.digest.strip_prefix("sha256:"))
+            .map(str::to_string),
+        categories: Vec::new(),
+        source: String::new(),
+        source_trust: None,
+        author: Some(manifest.author),
+        author_url: None,
+        created_at: annotation("org.opencontainers.image.created"),
+        license: manifest.license,
+        homepage: manifest.homepage,
+        icon: manifest.icon,
+        screenshots: Vec::new(),
+        status: None,
+        build_context: None,
+        channel,
+        release_notes: annotation("org.opencontainers.image.description"),
+        changelog_url: None,
+        author_public_key: annotation(ANNOTATION_PUBLIC_KEY),
+        manifest_signature: annotation(ANNOTATION_SIGNATURE),
+        yanked: Vec::new(),
+        install_count: None,
+        publisher: None,
+        verified: false,
+        verified_identity: None,
+        conflicts: Vec::new(),
+    }))
+}
+
+/// Fetch the plugin manifest artifact at `url` (`oci://repo@sha256:...`).
+pub async fn fetch_manifest(url: &str, auth: Option<&RegistryAuth>) -> NexusResult<ManifestArtifact> {
+    let reference = OciReference::parse(url)?;
+    let digest = reference
+        .digest
+        .clone()
+        .ok_or_else(|| NexusError::Other(format!("OCI manifest URL must be pinned by digest: {}", url)))?;
+    let mut client = OciClient::new(reference.clone(), auth)?;
+    let (artifact, text) = client.fetch_artifact(&digest).await?;
+    let image_digest = artifact
+        .subject
+        .map(|s| s.digest)
+        .ok_or_else(|| NexusError::Other(format!("OCI artifact {} refers to no image", digest)))?;
+    Ok(ManifestArtifact {
+        text,
+        image: reference.image(&image_digest),
+        image_digest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_references() {
+        let r = OciReference::parse("oci://ghcr.io/acme/weather").unwrap();
+        assert_eq!((r.registry.as_str(), r.repository.as_str()), ("ghcr.io", "acme/weather"));
+        assert_eq!(r.repository_url(), "oci://ghcr.io/acme/weather");
+
+        let digest = format!("sha256:{}", "ab".repeat(32));
+        let r = OciReference::parse(&format!("localhost:5000/weather@{}", digest)).unwrap();
+        assert_eq!(r.registry, "localhost:5000");
+        assert_eq!(r.tag, None);
+        assert_eq!(r.digest.as_deref(), Some(digest.as_str()));
+        assert_eq!(r.api_base(), "http://localhost:5000");
+
+        let r = OciReference::parse("acme/weather:1.2.0").unwrap();
+        assert_eq!(r.registry, "docker.io");
+        assert_eq!(r.tag.as_deref(), Some("1.2.0"));
+        assert_eq!(r.api_base(), "https://registry-1.docker.io");
+        assert_eq!(OciReference::parse("redis").unwrap().repository, "library/redis");
+
+        assert!(OciReference::parse("ghcr.io/Acme/Weather").is_err());
+        assert!(OciReference::parse("ghcr.io/acme/weather@sha256:short").is_err());
+    }
+
+    #[test]
+    fn parses_bearer_challenges() {
+        let c = parse_bearer_challenge(
+            r#"Bearer realm="https://ghcr.io/token",service="ghcr.io",scope="repository:acme/weather:pull""#,
+        )
+        .unwrap();
+        assert_eq!(c.realm, "https://ghcr.io/token");
+        assert_eq!(c.service.as_deref(), Some("ghcr.io"));
+        assert_eq!(c.scope.as_deref(), Some("repository:acme/weather:pull"));
+        assert!(parse_bearer_challenge(r#"Basic realm="registry""#).is_none());
+    }
+
+    #[test]
+    fn release_tags_are_newest_first() {
+        let tags: Vec<String> = ["latest", "1.2.0", "v1.10.0", "2.0.0-beta.1", "1.9.0", "sha256-abc"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        let (stable, pre) = release_tags(&tags);
+        assert_eq!(stable, ["v1.10.0", "1.9.0", "1.2.0"]);
+        assert_eq!(pre, ["2.0.0-beta.1"]);
+    }
+}
@@ -0,0 +1,51 @@
+//! Per-plugin locks for work done outside the manager's lock.
+//!
+//! Installs and updates pull and vet images without holding `AppState`, so
+//! two of them for the same plugin could otherwise interleave. Each takes
+//! the plugin's lock for its whole run; other plugins aren't held up.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OwnedMutexGuard;
+
+#[derive(Clone, Default)]
+pub struct PluginLocks(Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>);
+
+impl PluginLocks {
+    /// Wait for exclusive use of `plugin_id`, held until the guard drops.
+    pub async fn lock(&self, plugin_id: &str) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.0.lock().unwrap_or_else(|e| e.into_inner());
+            // Drop locks nobody holds or waits on
+            locks.retain(|_, l| Arc::strong_count(l) > 1);
+            locks.entry(plugin_id.to_string()).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn same_plugin_waits_and_others_do_not() {
+        let locks = PluginLocks::default();
+        let held = locks.lock("a").await;
+
+        let other = locks.lock("b").await;
+        let waiter = tokio::spawn({
+            let locks = locks.clone();
+            async move { drop(locks.lock("a").await) }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        drop(held);
+        waiter.await.unwrap();
+        drop(other);
+        drop(locks.lock("c").await);
+        assert_eq!(locks.0.lock().unwrap().len(), 1);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::{NexusError, NexusResult};
+use crate::secrets::{CredentialStorage, SecretMap};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -15,6 +16,8 @@ const DEFAULT_REGISTRY_URL: &str =
 pub enum RegistryKind {
     Remote,
     Local,
+    /// An OCI repository whose tags carry the plugin manifest as an artifact.
+    Oci,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
@@ -37,17 +40,110 @@ pub struct RegistrySource {
     pub trust: RegistryTrust,
 }
 
+/// A registry as listed to the UI: the source and the kind of credentials
+/// it has, never the credentials themselves.
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistrySourceView {
+    #[serde(flatten)]
+    pub source: RegistrySource,
+    pub auth: Option<&'static str>,
+}
+
+/// Credentials for a private remote registry, sent on index and manifest
+/// fetches.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegistryAuth {
+    Bearer { token: String },
+    Basic { username: String, password: String },
+}
+
+impl std::fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RegistryAuth::{}", self.kind())
+    }
+}
+
+impl RegistryAuth {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RegistryAuth::Bearer { .. } => "bearer",
+            RegistryAuth::Basic { .. } => "basic",
+        }
+    }
+
+    fn validate(&self) -> NexusResult<()> {
+        let empty = match self {
+            RegistryAuth::Bearer { token } => token.is_empty(),
+            RegistryAuth::Basic { username, .. } => username.is_empty(),
+        };
+        if empty {
+            return Err(NexusError::Other("Registry credentials are empty".to_string()));
+        }
+        Ok(())
+    }
+
+    pub(super) fn apply(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            RegistryAuth::Bearer { token } => request.bearer_auth(token),
+            RegistryAuth::Basic { username, password } => {
+                request.basic_auth(username, Some(password))
+            }
+        }
+    }
+}
+
+/// Scheme, host, and port of a URL, for matching requests to a registry.
+fn url_origin(url: &str) -> Option<(String, String, Option<u16>)> {
+    let parsed = url::Url::parse(url).ok()?;
+    Some((
+        parsed.scheme().to_string(),
+        parsed.host_str()?.to_string(),
+        parsed.port_or_known_default(),
+    ))
+}
+
 // ---------------------------------------------------------------------------
 // Registry store — persists configured registries to disk
 // ---------------------------------------------------------------------------
 
+/// Slug-style registry ID derived from a display name.
+pub fn source_id(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+/// What the marketplace shows when several registries list the same plugin ID.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Only the entry from the highest-priority registry is listed.
+    #[default]
+    HighestPriority,
+    /// Every registry's entry is listed, flagged, for the user to pick one.
+    Prompt,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RegistryStore {
+    /// In priority order: the first source wins a conflicting plugin ID.
     sources: Vec<RegistrySource>,
+    #[serde(default)]
+    conflict_policy: ConflictPolicy,
     #[serde(skip)]
     path: PathBuf,
+    /// Source ID → credentials, kept in the keychain or their own
+    /// owner-only file.
+    #[serde(skip)]
+    credentials: SecretMap<RegistryAuth>,
 }
 
+const CREDENTIALS_FILE: &str = "registry_credentials.json";
+
 impl Default for RegistryStore {
     fn default() -> Self {
         RegistryStore {
@@ -59,7 +155,9 @@ impl Default for RegistryStore {
                 enabled: true,
                 trust: RegistryTrust::Official,
             }],
+            conflict_policy: ConflictPolicy::default(),
             path: PathBuf::new(),
+            credentials: SecretMap::default(),
         }
     }
 }
@@ -67,14 +165,17 @@ impl Default for RegistryStore {
 impl RegistryStore {
     pub fn load(data_dir: &Path) -> NexusResult<Self> {
         let path = data_dir.join("registries.json");
-        if path.exists() {
-            let data = std::fs::read_to_string(&path)?;
-            let mut store: RegistryStore = serde_json::from_str(&data)?;
+        if let Some(mut store) = crate::util::read_json_store::<RegistryStore>(&path) {
             store.path = path;
+            store.credentials = SecretMap::load(data_dir.join(CREDENTIALS_FILE));
             store.migrate_defaults();
             Ok(store)
         } else {
-            let store = RegistryStore { path, ..Default::default() };
+            let store = RegistryStore {
+                path,
+                credentials: SecretMap::load(data_dir.join(CREDENTIALS_FILE)),
+                ..Default::default()
+            };
             store.save()?;
             Ok(store)
         }
@@ -105,14 +206,78 @@ impl RegistryStore {
 
     pub fn save(&self) -> NexusResult<()> {
         let data = serde_json::to_string_pretty(self)?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
+        crate::util::atomic_write_with_backup(&self.path, data.as_bytes())?;
         Ok(())
     }
 
+    /// Move the credentials to the keychain or to their file.
+    pub fn set_credential_storage(&mut self, storage: CredentialStorage) -> NexusResult<()> {
+        self.credentials.set_storage(storage)
+    }
+
     pub fn list(&self) -> &[RegistrySource] {
         &self.sources
     }
 
+    pub fn views(&self) -> Vec<RegistrySourceView> {
+        self.sources
+            .iter()
+            .map(|source| RegistrySourceView {
+                source: source.clone(),
+                auth: self.credentials.get(&source.id).map(RegistryAuth::kind),
+            })
+            .collect()
+    }
+
+    /// Set or clear the credentials of a remote registry. Credentials are
+    /// only sent over https, except to a registry on localhost.
+    pub fn set_auth(&mut self, id: &str, auth: Option<RegistryAuth>) -> NexusResult<()> {
+        let source = self
+            .sources
+            .iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| NexusError::Other(format!("Registry '{}' not found", id)))?;
+        match auth {
+            Some(auth) => {
+                if source.kind == RegistryKind::Local {
+                    return Err(NexusError::Other(
+                        "Only remote registries take credentials".to_string(),
+                    ));
+                }
+                match url_origin(&source.url) {
+                    Some((scheme, _, _)) if scheme == "https" || scheme == "oci" => {}
+                    Some((scheme, host, _))
+                        if scheme == "http" && matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]") => {}
+                    _ => {
+                        return Err(NexusError::Other(
+                            "Registry credentials require an https URL".to_string(),
+                        ))
+                    }
+                }
+                auth.validate()?;
+                self.credentials.insert(id.to_string(), auth)?;
+            }
+            None => {
+                self.credentials.remove(id)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn auth(&self, id: &str) -> Option<&RegistryAuth> {
+        self.credentials.get(id)
+    }
+
+    /// Credentials to send when fetching `url` for an entry of the registry
+    /// named `source_name`: only when the URL is on the registry's own
+    /// origin, so a listed manifest can't collect another host's token.
+    pub fn auth_for_url(&self, source_name: &str, url: &str) -> Option<&RegistryAuth> {
+        let source = self.sources.iter().find(|s| s.name == source_name)?;
+        let auth = self.credentials.get(&source.id)?;
+        let origin = url_origin(url)?;
+        (url_origin(&source.url)? == origin).then_some(auth)
+    }
+
     pub fn add(&mut self, source: RegistrySource) -> NexusResult<()> {
         if self.sources.iter().any(|s| s.id == source.id) {
             return Err(NexusError::Other(format!(
@@ -130,6 +295,7 @@ impl RegistryStore {
         if self.sources.len() == before {
             return Err(NexusError::Other(format!("Registry '{}' not found", id)));
         }
+        self.credentials.remove(id)?;
         self.save()
     }
 
@@ -147,6 +313,48 @@ impl RegistryStore {
         self.sources.iter().filter(|s| s.enabled).collect()
     }
 
+    /// Put the sources in the given order, highest priority first. `ids`
+    /// must name every configured source exactly once.
+    pub fn reorder(&mut self, ids: &[String]) -> NexusResult<()> {
+        let mut positions = Vec::with_capacity(ids.len());
+        for id in ids {
+            let pos = self
+                .sources
+                .iter()
+                .position(|s| &s.id == id)
+                .ok_or_else(|| NexusError::Other(format!("Registry '{}' not found", id)))?;
+            if positions.contains(&pos) {
+                return Err(NexusError::Other(format!("Registry '{}' listed twice", id)));
+            }
+            positions.push(pos);
+        }
+        if positions.len() != self.sources.len() {
+            return Err(NexusError::Other(
+                "Registry order must list every registry".to_string(),
+            ));
+        }
+        self.sources = positions.into_iter().map(|pos| self.sources[pos].clone()).collect();
+        self.save()
+    }
+
+    /// Position of the registry named `source_name` in the priority order.
+    /// Unknown registries rank last.
+    pub fn priority(&self, source_name: &str) -> usize {
+        self.sources
+            .iter()
+            .position(|s| s.name == source_name)
+            .unwrap_or(usize::MAX)
+    }
+
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+
+    pub fn set_conflict_policy(&mut self, policy: ConflictPolicy) -> NexusResult<()> {
+        self.conflict_policy = policy;
+        self.save()
+    }
+
     /// Look up the trust level for a registry by its name.
     /// Returns `Community` if the source is not found.
     pub fn source_trust(&self, source_name: &str) -> RegistryTrust {
@@ -183,6 +391,24 @@ pub struct Registry {
     /// Registry-level metadata (v2+).
     #[serde(default)]
     pub registry: Option<RegistryMeta>,
+    /// Curated groups of packages, from the registry's `collections.yaml`.
+    #[serde(default)]
+    pub collections: Vec<Collection>,
+}
+
+/// A named group of packages a registry recommends together, such as
+/// "Getting started".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Plugin and extension IDs, in display order.
+    pub packages: Vec<String>,
+    /// Set on fetch, like entry sources.
+    #[serde(default)]
+    pub source: String,
 }
 
 /// A host extension listed in a registry.
@@ -212,6 +438,73 @@ pub struct ExtensionRegistryEntry {
     pub platforms: Vec<String>,
     #[serde(default)]
     pub status: Option<String>,
+    /// What changed in this version, shown before updating.
+    #[serde(default)]
+    pub release_notes: Option<String>,
+    /// Changelog to fetch when the entry has no inline release notes.
+    #[serde(default)]
+    pub changelog_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub yanked: Vec<YankedVersion>,
+    /// Installs reported by the registry, if it publishes them.
+    #[serde(default)]
+    pub install_count: Option<u64>,
+    /// Publisher ID the entry claims, from the registry's `publishers.yaml`.
+    #[serde(default)]
+    pub publisher: Option<String>,
+    /// Set by `nexus-registry build` when the publisher proved control of
+    /// its domain or GitHub account and the ID is in its namespace.
+    #[serde(default)]
+    pub verified: bool,
+    /// The domain or `github.com/<owner>` the publisher was verified by.
+    #[serde(default)]
+    pub verified_identity: Option<String>,
+}
+
+impl ExtensionRegistryEntry {
+    pub fn yanked(&self, version: &str) -> Option<&YankedVersion> {
+        self.yanked.iter().find(|y| y.version == version)
+    }
+}
+
+/// A screenshot shown on a plugin's marketplace page. Registries built with
+/// `nexus-registry build` include its dimensions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Screenshot {
+    pub url: String,
+    #[serde(default)]
+    pub caption: Option<String>,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+}
+
+/// A version the publisher has withdrawn. It is no longer offered for
+/// install or update, and users still running it are warned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YankedVersion {
+    pub version: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Release channel of a registry entry, and the channel an installed plugin
+/// takes updates from. Stable plugins are only offered stable releases; beta
+/// plugins are offered whichever is newer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl ReleaseChannel {
+    /// Whether a plugin following `self` may be offered a release on `release`.
+    pub fn accepts(self, release: ReleaseChannel) -> bool {
+        release == ReleaseChannel::Stable || self == ReleaseChannel::Beta
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -248,6 +541,8 @@ pub struct RegistryEntry {
     pub homepage: Option<String>,
     #[serde(default)]
     pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub screenshots: Vec<Screenshot>,
     #[serde(default)]
     pub status: Option<String>,
     /// Absolute path to a directory containing a Dockerfile.
@@ -255,6 +550,83 @@ pub struct RegistryEntry {
     /// When present, Nexus can build the image from source instead of pulling.
     #[serde(default)]
     pub build_context: Option<String>,
+    /// A registry can list a plugin once per channel; the marketplace shows
+    /// the stable entry when there is one.
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+    /// What changed in this version, shown before updating.
+    #[serde(default)]
+    pub release_notes: Option<String>,
+    /// Changelog to fetch when the entry has no inline release notes.
+    #[serde(default)]
+    pub changelog_url: Option<String>,
+    /// Author public key (base64-encoded Ed25519). When set, the manifest
+    /// must carry a valid `manifest_signature`.
+    #[serde(default)]
+    pub author_public_key: Option<String>,
+    /// Base64 Ed25519 signature over the SHA-256 of the manifest file.
+    #[serde(default)]
+    pub manifest_signature: Option<String>,
+    /// Withdrawn versions of this plugin, which may include the listed one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub yanked: Vec<YankedVersion>,
+    /// Installs reported by the registry, if it publishes them.
+    #[serde(default)]
+    pub install_count: Option<u64>,
+    /// Publisher ID the entry claims, from the registry's `publishers.yaml`.
+    #[serde(default)]
+    pub publisher: Option<String>,
+    /// Set by `nexus-registry build` when the publisher proved control of
+    /// its domain or GitHub account and the ID is in its namespace.
+    #[serde(default)]
+    pub verified: bool,
+    /// The domain or `github.com/<owner>` the publisher was verified by.
+    #[serde(default)]
+    pub verified_identity: Option<String>,
+    /// Other registries listing the same plugin ID, highest priority first.
+    /// Set by [`resolve_conflicts`], not by registries.
+    #[serde(default, skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+}
+
+impl RegistryEntry {
+    pub fn yanked(&self, version: &str) -> Option<&YankedVersion> {
+        self.yanked.iter().find(|y| y.version == version)
+    }
+
+    /// The key and signature the manifest must verify against, if the entry
+    /// is signed. A key without a signature is an error rather than an
+    /// unsigned entry, so a signature can't be stripped in transit.
+    pub fn manifest_signature(&self) -> NexusResult<Option<ManifestSignature>> {
+        match (&self.author_public_key, &self.manifest_signature) {
+            (Some(public_key), Some(signature)) => Ok(Some(ManifestSignature {
+                public_key: public_key.clone(),
+                signature: signature.clone(),
+            })),
+            (Some(_), None) => Err(NexusError::Other(format!(
+                "Registry entry for '{}' has an author key but no manifest signature",
+                self.id
+            ))),
+            (None, _) => Ok(None),
+        }
+    }
+}
+
+/// A manifest shown before install, with the publisher verification of the
+/// registry entry that lists it.
+#[derive(Debug, Serialize)]
+pub struct ManifestPreview<M> {
+    #[serde(flatten)]
+    pub manifest: M,
+    pub verified: bool,
+    pub verified_identity: Option<String>,
+}
+
+/// Author key and signature of a plugin manifest, from its registry entry.
+#[derive(Debug, Clone)]
+pub struct ManifestSignature {
+    pub public_key: String,
+    pub signature: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -262,11 +634,14 @@ pub struct RegistryEntry {
 // ---------------------------------------------------------------------------
 
 /// Maximum response body size for registry/manifest fetches (10 MB).
-const MAX_FETCH_BYTES: usize = 10 * 1024 * 1024;
+pub(super) const MAX_FETCH_BYTES: usize = 10 * 1024 * 1024;
+
+/// Changelogs are cut to this many characters for display.
+const MAX_CHANGELOG_CHARS: usize = 64 * 1024;
 
 /// Build a hardened HTTP client for registry operations.
-fn http_client() -> NexusResult<reqwest::Client> {
-    reqwest::Client::builder()
+pub(super) fn http_client() -> NexusResult<reqwest::Client> {
+    crate::proxy::apply(reqwest::Client::builder())
         .timeout(std::time::Duration::from_secs(30))
         .redirect(reqwest::redirect::Policy::limited(5))
         .build()
@@ -342,9 +717,68 @@ fn fetch_local(path_str: &str) -> NexusResult<Registry> {
         }
     }
 
+    // The webview can't load files, so local icons and screenshots are inlined
+    for entry in &mut registry.plugins {
+        for asset in entry_assets(entry) {
+            if !is_url(asset) {
+                match local_asset_uri(&dir.join(&*asset)) {
+                    Ok(uri) => *asset = uri,
+                    Err(e) => log::warn!("Skipping registry asset {}: {}", asset, e),
+                }
+            }
+        }
+    }
+
+    // Changelogs may sit next to the index, like manifests
+    let changelog_urls = registry
+        .plugins
+        .iter_mut()
+        .map(|e| &mut e.changelog_url)
+        .chain(registry.extensions.iter_mut().map(|e| &mut e.changelog_url));
+    for url in changelog_urls.flatten() {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            *url = format!("file://{}", dir.join(&*url).display());
+        }
+    }
+
     Ok(registry)
 }
 
+/// Icon and screenshot URLs of an entry.
+fn entry_assets(entry: &mut RegistryEntry) -> impl Iterator<Item = &mut String> {
+    entry
+        .icon
+        .iter_mut()
+        .chain(entry.screenshots.iter_mut().map(|s| &mut s.url))
+}
+
+fn is_url(reference: &str) -> bool {
+    ["http://", "https://", "data:"]
+        .iter()
+        .any(|scheme| reference.starts_with(scheme))
+}
+
+/// Largest local asset inlined as a data URI (2 MB, the screenshot limit of
+/// `nexus-registry`).
+const MAX_LOCAL_ASSET_BYTES: u64 = 2 * 1024 * 1024;
+
+fn local_asset_uri(path: &Path) -> NexusResult<String> {
+    use base64::Engine;
+
+    let mime = match path.extension().and_then(|e| e.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        _ => return Err(NexusError::Other("unsupported image type".to_string())),
+    };
+    if std::fs::metadata(path)?.len() > MAX_LOCAL_ASSET_BYTES {
+        return Err(NexusError::Other("file too large".to_string()));
+    }
+    let data = base64::engine::general_purpose::STANDARD.encode(std::fs::read(path)?);
+    Ok(format!("data:{};base64,{}", mime, data))
+}
+
 /// Scan a local directory for YAML-based registry entries.
 /// Expects: registry.yaml (metadata), plugins/*.yaml, extensions/*.yaml
 fn scan_yaml_registry(dir: &Path) -> NexusResult<Registry> {
@@ -368,10 +802,13 @@ fn scan_yaml_registry(dir: &Path) -> NexusResult<Registry> {
                 || path.extension().and_then(|e| e.to_str()) == Some("yml")
             {
                 let data = std::fs::read_to_string(&path)?;
-                let plugin: RegistryEntry = serde_yaml::from_str(&data)
+                let mut plugin: RegistryEntry = serde_yaml::from_str(&data)
                     .map_err(|e| NexusError::Other(format!(
                         "Invalid plugin YAML {}: {}", path.display(), e
                     )))?;
+                // Only `nexus-registry build` can verify a publisher
+                plugin.verified = false;
+                plugin.verified_identity = None;
                 plugins.push(plugin);
             }
         }
@@ -387,21 +824,33 @@ fn scan_yaml_registry(dir: &Path) -> NexusResult<Registry> {
                 || path.extension().and_then(|e| e.to_str()) == Some("yml")
             {
                 let data = std::fs::read_to_string(&path)?;
-                let ext: ExtensionRegistryEntry = serde_yaml::from_str(&data)
+                let mut ext: ExtensionRegistryEntry = serde_yaml::from_str(&data)
                     .map_err(|e| NexusError::Other(format!(
                         "Invalid extension YAML {}: {}", path.display(), e
                     )))?;
+                ext.verified = false;
+                ext.verified_identity = None;
                 extensions.push(ext);
             }
         }
     }
 
+    let collections_file = dir.join("collections.yaml");
+    let collections = if collections_file.exists() {
+        let data = std::fs::read_to_string(&collections_file)?;
+        serde_yaml::from_str(&data)
+            .map_err(|e| NexusError::Other(format!("Invalid collections.yaml: {}", e)))?
+    } else {
+        Vec::new()
+    };
+
     Ok(Registry {
         version: 2,
         updated_at: chrono::Utc::now().to_rfc3339(),
         plugins,
         extensions,
         registry: registry_meta,
+        collections,
     })
 }
 
@@ -410,6 +859,8 @@ fn scan_yaml_registry(dir: &Path) -> NexusResult<Registry> {
 pub struct FetchAllResult {
     pub plugins: Vec<RegistryEntry>,
     pub extensions: Vec<ExtensionRegistryEntry>,
+    #[serde(default)]
+    pub collections: Vec<Collection>,
 }
 
 // ---------------------------------------------------------------------------
@@ -422,6 +873,8 @@ pub struct FetchAllResult {
 pub struct RegistryCache {
     pub plugins: Vec<RegistryEntry>,
     pub extensions: Vec<ExtensionRegistryEntry>,
+    #[serde(default)]
+    pub collections: Vec<Collection>,
     /// ISO-8601 timestamp of last successful remote fetch.
     pub last_refreshed: String,
     /// Per-source ETags for conditional GET (source_id → etag).
@@ -429,7 +882,7 @@ pub struct RegistryCache {
     pub etags: HashMap<String, String>,
 }
 
-const CACHE_FILE: &str = "registry-cache.json";
+pub const CACHE_FILE: &str = "registry-cache.json";
 
 /// Load the persisted registry cache from disk. Returns `None` if the file
 /// doesn't exist or can't be parsed.
@@ -450,15 +903,22 @@ pub fn save_cache(data_dir: &Path, cache: &RegistryCache) -> NexusResult<()> {
 /// Outcome of a conditional fetch against a single remote source.
 pub enum FetchOutcome {
     /// 200 OK — new data + new ETag (if provided by server).
-    Fresh(Registry, Option<String>),
+    Fresh(Box<Registry>, Option<String>),
     /// 304 Not Modified — cached data is still current.
     NotModified,
 }
 
 /// Fetch a remote registry with conditional GET (If-None-Match).
-async fn fetch_remote_conditional(url: &str, etag: Option<&str>) -> NexusResult<FetchOutcome> {
+async fn fetch_remote_conditional(
+    url: &str,
+    etag: Option<&str>,
+    auth: Option<&RegistryAuth>,
+) -> NexusResult<FetchOutcome> {
     let client = http_client()?;
     let mut request = client.get(url);
+    if let Some(auth) = auth {
+        request = auth.apply(request);
+    }
     if let Some(etag_val) = etag {
         request = request.header("If-None-Match", etag_val);
     }
@@ -483,10 +943,21 @@ async fn fetch_remote_conditional(url: &str, etag: Option<&str>) -> NexusResult<
         .map(|s| s.to_string());
 
     let text = fetch_text(response).await?;
-    let registry: Registry = serde_json::from_str(&text)
+    let mut registry: Registry = serde_json::from_str(&text)
         .map_err(|e| NexusError::Other(format!("Invalid registry JSON: {}", e)))?;
 
-    Ok(FetchOutcome::Fresh(registry, new_etag))
+    // Built registries reference their assets relative to the index
+    if let Ok(base) = url::Url::parse(url) {
+        for entry in &mut registry.plugins {
+            for asset in entry_assets(entry) {
+                if let Ok(resolved) = base.join(asset) {
+                    *asset = resolved.to_string();
+                }
+            }
+        }
+    }
+
+    Ok(FetchOutcome::Fresh(Box::new(registry), new_etag))
 }
 
 /// Fetch all enabled registries using conditional GET for remote sources.
@@ -497,6 +968,7 @@ pub async fn fetch_all_conditional(
 ) -> (FetchAllResult, HashMap<String, String>) {
     let mut all_plugins = Vec::new();
     let mut all_extensions = Vec::new();
+    let mut all_collections = Vec::new();
     let mut new_etags = existing_cache.etags.clone();
 
     for source in store.enabled_sources() {
@@ -515,15 +987,37 @@ pub async fn fetch_all_conditional(
                             entry.source = source.name.clone();
                             all_extensions.push(entry);
                         }
+                        for mut collection in registry.collections {
+                            collection.source = source.name.clone();
+                            all_collections.push(collection);
+                        }
                     }
                     Err(e) => {
                         log::warn!("Failed to fetch local registry '{}': {}", source.name, e);
                     }
                 }
             }
+            RegistryKind::Oci => match super::oci::fetch_registry(&source.url, store.auth(&source.id)).await {
+                Ok(registry) => {
+                    let trust_str = format!("{:?}", source.trust).to_lowercase();
+                    for mut entry in registry.plugins {
+                        entry.source = source.name.clone();
+                        entry.source_trust = Some(trust_str.clone());
+                        all_plugins.push(entry);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("Failed to fetch OCI registry '{}': {}", source.name, e);
+                    for entry in &existing_cache.plugins {
+                        if entry.source == source.name {
+                            all_plugins.push(entry.clone());
+                        }
+                    }
+                }
+            },
             RegistryKind::Remote => {
                 let cached_etag = existing_cache.etags.get(&source.id).map(|s| s.as_str());
-                match fetch_remote_conditional(&source.url, cached_etag).await {
+                match fetch_remote_conditional(&source.url, cached_etag, store.auth(&source.id)).await {
                     Ok(FetchOutcome::NotModified) => {
                         log::info!("Registry '{}': 304 Not Modified (cached)", source.name);
                         // Reuse entries from disk cache for this source.
@@ -537,6 +1031,11 @@ pub async fn fetch_all_conditional(
                                 all_extensions.push(entry.clone());
                             }
                         }
+                        for collection in &existing_cache.collections {
+                            if collection.source == source.name {
+                                all_collections.push(collection.clone());
+                            }
+                        }
                     }
                     Ok(FetchOutcome::Fresh(registry, new_etag)) => {
                         log::info!("Registry '{}': 200 OK (fresh data)", source.name);
@@ -553,6 +1052,10 @@ pub async fn fetch_all_conditional(
                             entry.source = source.name.clone();
                             all_extensions.push(entry);
                         }
+                        for mut collection in registry.collections {
+                            collection.source = source.name.clone();
+                            all_collections.push(collection);
+                        }
                     }
                     Err(e) => {
                         log::warn!("Failed to fetch registry '{}': {}", source.name, e);
@@ -567,6 +1070,11 @@ pub async fn fetch_all_conditional(
                                 all_extensions.push(entry.clone());
                             }
                         }
+                        for collection in &existing_cache.collections {
+                            if collection.source == source.name {
+                                all_collections.push(collection.clone());
+                            }
+                        }
                     }
                 }
             }
@@ -577,6 +1085,7 @@ pub async fn fetch_all_conditional(
         FetchAllResult {
             plugins: all_plugins,
             extensions: all_extensions,
+            collections: all_collections,
         },
         new_etags,
     )
@@ -585,16 +1094,52 @@ pub async fn fetch_all_conditional(
 /// Fetch a manifest from a URL or file:// path.
 ///
 /// `file://` is only accepted for local registry sources. Remote manifests
-/// must use `http://` or `https://`.
-pub async fn fetch_manifest(url: &str) -> NexusResult<super::manifest::PluginManifest> {
+/// must use `http://` or `https://`. When given, `signature` is checked
+/// against the file as served, before it is parsed, and `auth` is sent with
+/// the request.
+pub async fn fetch_verified_manifest(
+    url: &str,
+    signature: Option<&ManifestSignature>,
+    auth: Option<&RegistryAuth>,
+) -> NexusResult<super::manifest::PluginManifest> {
+    // Manifests from OCI registries are pinned to the image they're attached to
+    let (text, pinned_image) = if url.starts_with("oci://") {
+        let artifact = super::oci::fetch_manifest(url, auth).await?;
+        (artifact.text, Some((artifact.image, artifact.image_digest)))
+    } else {
+        (fetch_manifest_text(url, auth).await?, None)
+    };
+
+    if let Some(sig) = signature {
+        crate::extensions::signing::verify_signature(&sig.public_key, text.as_bytes(), &sig.signature)
+            .map_err(|e| NexusError::Other(format!("Manifest signature check failed: {}", e)))?;
+        log::info!("Manifest signature verified for {}", url);
+    }
+
+    let mut manifest: super::manifest::PluginManifest = serde_json::from_str(&text)
+        .map_err(|e| NexusError::Other(format!("Invalid manifest JSON: {}", e)))?;
+    if let Some((image, digest)) = pinned_image {
+        manifest.image = image;
+        manifest.image_digest = Some(digest);
+    }
+    manifest.validate().map_err(NexusError::InvalidManifest)?;
+    Ok(manifest)
+}
+
+/// Raw text of a plugin or extension manifest from a file://, http://,
+/// https://, or oci:// URL.
+pub async fn fetch_manifest_text(url: &str, auth: Option<&RegistryAuth>) -> NexusResult<String> {
     if let Some(file_path) = url.strip_prefix("file://") {
-        let data = std::fs::read_to_string(file_path)?;
-        let manifest: super::manifest::PluginManifest = serde_json::from_str(&data)?;
-        manifest.validate().map_err(NexusError::InvalidManifest)?;
-        Ok(manifest)
+        Ok(std::fs::read_to_string(file_path)?)
+    } else if url.starts_with("oci://") {
+        Ok(super::oci::fetch_manifest(url, auth).await?.text)
     } else if url.starts_with("http://") || url.starts_with("https://") {
         let client = http_client()?;
-        let response = client.get(url).send().await.map_err(NexusError::Http)?;
+        let mut request = client.get(url);
+        if let Some(auth) = auth {
+            request = auth.apply(request);
+        }
+        let response = request.send().await.map_err(NexusError::Http)?;
 
         if !response.status().is_success() {
             return Err(NexusError::Other(format!(
@@ -603,12 +1148,7 @@ pub async fn fetch_manifest(url: &str) -> NexusResult<super::manifest::PluginMan
             )));
         }
 
-        let text = fetch_text(response).await?;
-        let manifest: super::manifest::PluginManifest = serde_json::from_str(&text)
-            .map_err(|e| NexusError::Other(format!("Invalid manifest JSON: {}", e)))?;
-
-        manifest.validate().map_err(NexusError::InvalidManifest)?;
-        Ok(manifest)
+        fetch_text(response).await
     } else {
         Err(NexusError::Other(format!(
             "Unsupported URL scheme: {}",
@@ -617,8 +1157,41 @@ pub async fn fetch_manifest(url: &str) -> NexusResult<super::manifest::PluginMan
     }
 }
 
-/// Fetch an extension manifest from a URL.
-pub async fn fetch_extension_manifest(url: &str) -> NexusResult<crate::extensions::manifest::ExtensionManifest> {
+/// Fetch a changelog from a URL or file:// path, as text cut to
+/// [`MAX_CHANGELOG_CHARS`].
+pub async fn fetch_changelog(url: &str) -> NexusResult<String> {
+    let text = if let Some(file_path) = url.strip_prefix("file://") {
+        std::fs::read_to_string(file_path)?
+    } else if url.starts_with("http://") || url.starts_with("https://") {
+        let client = http_client()?;
+        let response = client.get(url).send().await.map_err(NexusError::Http)?;
+
+        if !response.status().is_success() {
+            return Err(NexusError::Other(format!(
+                "Changelog fetch returned status {}",
+                response.status()
+            )));
+        }
+
+        fetch_text(response).await?
+    } else {
+        return Err(NexusError::Other(format!(
+            "Unsupported URL scheme for changelog: {}",
+            url.split(':').next().unwrap_or("unknown")
+        )));
+    };
+
+    Ok(match text.char_indices().nth(MAX_CHANGELOG_CHARS) {
+        Some((end, _)) => text[..end].to_string(),
+        None => text,
+    })
+}
+
+/// Fetch an extension manifest from a URL, sending `auth` if given.
+pub async fn fetch_extension_manifest(
+    url: &str,
+    auth: Option<&RegistryAuth>,
+) -> NexusResult<crate::extensions::manifest::ExtensionManifest> {
     if let Some(file_path) = url.strip_prefix("file://") {
         let data = std::fs::read_to_string(file_path)?;
         let manifest: crate::extensions::manifest::ExtensionManifest = serde_json::from_str(&data)
@@ -627,7 +1200,11 @@ pub async fn fetch_extension_manifest(url: &str) -> NexusResult<crate::extension
         Ok(manifest)
     } else if url.starts_with("http://") || url.starts_with("https://") {
         let client = http_client()?;
-        let response = client.get(url).send().await.map_err(NexusError::Http)?;
+        let mut request = client.get(url);
+        if let Some(auth) = auth {
+            request = auth.apply(request);
+        }
+        let response = request.send().await.map_err(NexusError::Http)?;
 
         if !response.status().is_success() {
             return Err(NexusError::Other(format!(
@@ -652,14 +1229,63 @@ pub async fn fetch_extension_manifest(url: &str) -> NexusResult<crate::extension
     }
 }
 
+/// Settle plugin IDs listed by more than one registry. Each entry's
+/// `conflicts` names the other registries that list its ID; under
+/// [`ConflictPolicy::HighestPriority`] only the entries of the
+/// highest-priority registry are kept.
+pub fn resolve_conflicts(
+    entries: &[RegistryEntry],
+    store: &RegistryStore,
+    policy: ConflictPolicy,
+) -> Vec<RegistryEntry> {
+    let mut sources_by_id: HashMap<&str, Vec<&str>> = HashMap::new();
+    for entry in entries {
+        let sources = sources_by_id.entry(&entry.id).or_default();
+        if !sources.contains(&entry.source.as_str()) {
+            sources.push(&entry.source);
+        }
+    }
+    for sources in sources_by_id.values_mut() {
+        sources.sort_by_key(|name| store.priority(name));
+    }
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let sources = &sources_by_id[entry.id.as_str()];
+            if policy == ConflictPolicy::HighestPriority && sources[0] != entry.source {
+                return None;
+            }
+            let mut entry = entry.clone();
+            entry.conflicts = sources
+                .iter()
+                .filter(|s| **s != entry.source)
+                .map(|s| s.to_string())
+                .collect();
+            Some(entry)
+        })
+        .collect()
+}
+
 pub fn search_entries(entries: &[RegistryEntry], query: &str) -> Vec<RegistryEntry> {
+    // Withdrawn releases are not offered for install
+    let entries: Vec<&RegistryEntry> = entries
+        .iter()
+        .filter(|p| p.yanked(&p.version).is_none())
+        .collect();
+    // Pre-releases are reached through a plugin's channel, not listed twice
+    let listed = entries.iter().filter(|p| {
+        p.channel == ReleaseChannel::Stable
+            || !entries.iter().any(|o| {
+                o.id == p.id && o.source == p.source && o.channel == ReleaseChannel::Stable
+            })
+    });
     if query.is_empty() {
-        return entries.to_vec();
+        return listed.map(|&p| p.clone()).collect();
     }
 
     let query_lower = query.to_lowercase();
-    entries
-        .iter()
+    listed
         .filter(|p| {
             p.name.to_lowercase().contains(&query_lower)
                 || p.description.to_lowercase().contains(&query_lower)
@@ -669,18 +1295,142 @@ pub fn search_entries(entries: &[RegistryEntry], query: &str) -> Vec<RegistryEnt
                 || p.source.to_lowercase().contains(&query_lower)
                 || p.author.as_deref().unwrap_or("").to_lowercase().contains(&query_lower)
         })
-        .cloned()
+        .map(|&p| p.clone())
         .collect()
 }
 
+/// How marketplace results are ordered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketplaceSort {
+    /// Name matches first, then registry order.
+    #[default]
+    Relevance,
+    /// Newest `created_at` first; entries without one last.
+    RecentlyUpdated,
+    Name,
+    /// Most installs first; entries without a count last.
+    Popularity,
+}
+
+pub const DEFAULT_PAGE_SIZE: usize = 24;
+const MAX_PAGE_SIZE: usize = 100;
+
+/// A marketplace search: text, category filter, order, and page.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MarketplaceQuery {
+    pub text: String,
+    /// Entries in any of these categories match. Empty matches all.
+    pub categories: Vec<String>,
+    pub sort: MarketplaceSort,
+    /// Zero-based.
+    pub page: usize,
+    /// Defaults to [`DEFAULT_PAGE_SIZE`]; capped at 100.
+    pub page_size: usize,
+}
+
+/// Number of text matches in a category, before the category filter.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CategoryFacet {
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketplacePage {
+    pub entries: Vec<RegistryEntry>,
+    /// Matches across all pages.
+    pub total: usize,
+    pub page: usize,
+    pub page_size: usize,
+    pub categories: Vec<CategoryFacet>,
+}
+
+/// Search, filter, sort, and page marketplace entries. Category facets count
+/// the text matches, so selecting a category doesn't hide the others.
+pub fn browse_entries(entries: &[RegistryEntry], query: &MarketplaceQuery) -> MarketplacePage {
+    let matches = search_entries(entries, &query.text);
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in &matches {
+        let mut seen = std::collections::HashSet::new();
+        for category in &entry.categories {
+            let name = category.to_lowercase();
+            if seen.insert(name.clone()) {
+                *counts.entry(name).or_default() += 1;
+            }
+        }
+    }
+    let mut categories: Vec<CategoryFacet> = counts
+        .into_iter()
+        .map(|(name, count)| CategoryFacet { name, count })
+        .collect();
+    categories.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+
+    let wanted: Vec<String> = query.categories.iter().map(|c| c.to_lowercase()).collect();
+    let mut filtered: Vec<RegistryEntry> = matches
+        .into_iter()
+        .filter(|e| {
+            wanted.is_empty()
+                || e.categories
+                    .iter()
+                    .any(|c| wanted.contains(&c.to_lowercase()))
+        })
+        .collect();
+
+    match query.sort {
+        MarketplaceSort::Relevance => {
+            let text = query.text.to_lowercase();
+            if !text.is_empty() {
+                filtered.sort_by_key(|e| !e.name.to_lowercase().contains(&text));
+            }
+        }
+        MarketplaceSort::RecentlyUpdated => {
+            let date = |e: &RegistryEntry| {
+                e.created_at
+                    .as_deref()
+                    .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+            };
+            // Newest first; entries without a date sort last
+            filtered.sort_by_key(|e| std::cmp::Reverse(date(e)));
+        }
+        MarketplaceSort::Name => {
+            filtered.sort_by_cached_key(|e| e.name.to_lowercase());
+        }
+        MarketplaceSort::Popularity => {
+            filtered.sort_by_key(|e| std::cmp::Reverse(e.install_count));
+        }
+    }
+
+    let page_size = match query.page_size {
+        0 => DEFAULT_PAGE_SIZE,
+        n => n.min(MAX_PAGE_SIZE),
+    };
+    let total = filtered.len();
+    let entries = filtered
+        .into_iter()
+        .skip(query.page.saturating_mul(page_size))
+        .take(page_size)
+        .collect();
+
+    MarketplacePage {
+        entries,
+        total,
+        page: query.page,
+        page_size,
+        categories,
+    }
+}
+
 pub fn search_extension_entries(entries: &[ExtensionRegistryEntry], query: &str) -> Vec<ExtensionRegistryEntry> {
+    let listed = entries.iter().filter(|e| e.yanked(&e.version).is_none());
     if query.is_empty() {
-        return entries.to_vec();
+        return listed.cloned().collect();
     }
 
     let query_lower = query.to_lowercase();
-    entries
-        .iter()
+    listed
         .filter(|e| {
             e.name.to_lowercase().contains(&query_lower)
                 || e.description.to_lowercase().contains(&query_lower)
@@ -693,3 +1443,308 @@ pub fn search_extension_entries(entries: &[ExtensionRegistryEntry], query: &str)
         .cloned()
         .collect()
 }
+
+/// A collection with its packages looked up, for the marketplace.
+#[derive(Debug, Clone, Serialize)]
+pub struct MarketplaceCollection {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub source: String,
+    pub plugins: Vec<RegistryEntry>,
+    pub extensions: Vec<ExtensionRegistryEntry>,
+}
+
+/// Look up each collection's packages among the listed entries, preferring
+/// the collection's own registry. Packages that aren't listed (unlisted,
+/// yanked, or dropped by conflict resolution) are left out, and so are
+/// collections left empty.
+pub fn resolve_collections(
+    collections: &[Collection],
+    plugins: &[RegistryEntry],
+    extensions: &[ExtensionRegistryEntry],
+) -> Vec<MarketplaceCollection> {
+    let plugins = search_entries(plugins, "");
+    let extensions = search_extension_entries(extensions, "");
+
+    collections
+        .iter()
+        .filter_map(|collection| {
+            let mut resolved = MarketplaceCollection {
+                id: collection.id.clone(),
+                name: collection.name.clone(),
+                description: collection.description.clone(),
+                source: collection.source.clone(),
+                plugins: Vec::new(),
+                extensions: Vec::new(),
+            };
+            for id in &collection.packages {
+                let plugin = plugins
+                    .iter()
+                    .find(|p| p.id == *id && p.source == collection.source)
+                    .or_else(|| plugins.iter().find(|p| p.id == *id));
+                if let Some(plugin) = plugin {
+                    resolved.plugins.push(plugin.clone());
+                    continue;
+                }
+                let extension = extensions
+                    .iter()
+                    .find(|e| e.id == *id && e.source == collection.source)
+                    .or_else(|| extensions.iter().find(|e| e.id == *id));
+                if let Some(extension) = extension {
+                    resolved.extensions.push(extension.clone());
+                }
+            }
+            (!resolved.plugins.is_empty() || !resolved.extensions.is_empty()).then_some(resolved)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_persist_and_stay_on_the_registry_origin() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut store = RegistryStore::load(tmp.path()).unwrap();
+        store
+            .add(RegistrySource {
+                id: "acme".into(),
+                name: "Acme Internal".into(),
+                kind: RegistryKind::Remote,
+                url: "https://plugins.acme.test/index.json".into(),
+                enabled: true,
+                trust: RegistryTrust::Community,
+            })
+            .unwrap();
+        let token = RegistryAuth::Bearer { token: "s3cret".into() };
+        store.set_auth("acme", Some(token.clone())).unwrap();
+        assert!(store
+            .set_auth("nexus-community", Some(RegistryAuth::Bearer { token: String::new() }))
+            .is_err());
+
+        // Not written to the shareable registries file
+        let listed = std::fs::read_to_string(tmp.path().join("registries.json")).unwrap();
+        assert!(!listed.contains("s3cret"));
+
+        let store = RegistryStore::load(tmp.path()).unwrap();
+        assert_eq!(store.views().iter().find(|v| v.source.id == "acme").unwrap().auth, Some("bearer"));
+        assert_eq!(
+            store.auth_for_url("Acme Internal", "https://plugins.acme.test/p/plugin.json"),
+            Some(&token)
+        );
+        assert_eq!(store.auth_for_url("Acme Internal", "https://cdn.example.com/plugin.json"), None);
+        assert_eq!(store.auth_for_url("Acme Internal", "http://plugins.acme.test/plugin.json"), None);
+    }
+
+    #[test]
+    fn highest_priority_registry_wins_conflicting_ids() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut store = RegistryStore::load(tmp.path()).unwrap();
+        store
+            .add(RegistrySource {
+                id: "mirror".into(),
+                name: "Mirror".into(),
+                kind: RegistryKind::Local,
+                url: "/srv/mirror".into(),
+                enabled: true,
+                trust: RegistryTrust::Community,
+            })
+            .unwrap();
+        let entry = |id: &str, source: &str| -> RegistryEntry {
+            let mut entry: RegistryEntry = serde_json::from_value(serde_json::json!({
+                "id": id, "name": id, "version": "1.0.0", "description": "",
+                "image": "img", "manifest_url": "https://example.com/plugin.json",
+            }))
+            .unwrap();
+            entry.source = source.into();
+            entry
+        };
+        let entries = vec![
+            entry("com.a", "Nexus Community"),
+            entry("com.a", "Mirror"),
+            entry("com.b", "Mirror"),
+        ];
+
+        let resolved = resolve_conflicts(&entries, &store, ConflictPolicy::HighestPriority);
+        let listed: Vec<(&str, &str)> =
+            resolved.iter().map(|e| (e.id.as_str(), e.source.as_str())).collect();
+        assert_eq!(listed, [("com.a", "Nexus Community"), ("com.b", "Mirror")]);
+        assert_eq!(resolved[0].conflicts, ["Mirror"]);
+        assert!(resolved[1].conflicts.is_empty());
+
+        assert!(store.reorder(&["mirror".into()]).is_err());
+        assert!(store.reorder(&["mirror".into(), "mirror".into()]).is_err());
+        store
+            .reorder(&["mirror".into(), "nexus-community".into()])
+            .unwrap();
+        store.set_conflict_policy(ConflictPolicy::Prompt).unwrap();
+        let store = RegistryStore::load(tmp.path()).unwrap();
+        assert_eq!(store.conflict_policy(), ConflictPolicy::Prompt);
+
+        let resolved = resolve_conflicts(&entries, &store, ConflictPolicy::HighestPriority);
+        assert_eq!(resolved[0].source, "Mirror");
+        let resolved = resolve_conflicts(&entries, &store, store.conflict_policy());
+        assert_eq!(resolved.len(), 3);
+        assert_eq!(resolved[0].conflicts, ["Mirror"]);
+        assert_eq!(resolved[1].conflicts, ["Nexus Community"]);
+    }
+
+    #[test]
+    fn browse_filters_by_category_sorts_and_pages() {
+        let entry = |name: &str,
+                     categories: &[&str],
+                     created_at: Option<&str>,
+                     install_count: Option<u64>|
+         -> RegistryEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": format!("com.{}", name.to_lowercase()), "name": name,
+                "version": "1.0.0", "description": "", "image": "img",
+                "manifest_url": "https://example.com/plugin.json",
+                "categories": categories, "created_at": created_at,
+                "install_count": install_count,
+            }))
+            .unwrap()
+        };
+        let entries = vec![
+            entry("Notes", &["productivity"], Some("2026-01-01T00:00:00Z"), None),
+            entry("Clock", &["Productivity", "time"], Some("2026-03-01T00:00:00Z"), Some(40)),
+            entry("Ambient", &["media"], None, Some(900)),
+        ];
+
+        let all = browse_entries(&entries, &MarketplaceQuery::default());
+        assert_eq!(all.total, 3);
+        assert_eq!(all.page_size, DEFAULT_PAGE_SIZE);
+        assert_eq!(
+            all.categories[0],
+            CategoryFacet { name: "productivity".into(), count: 2 }
+        );
+
+        let names = |page: &MarketplacePage| -> Vec<String> {
+            page.entries.iter().map(|e| e.name.clone()).collect()
+        };
+        let recent = browse_entries(
+            &entries,
+            &MarketplaceQuery {
+                sort: MarketplaceSort::RecentlyUpdated,
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&recent), ["Clock", "Notes", "Ambient"]);
+
+        let popular = browse_entries(
+            &entries,
+            &MarketplaceQuery {
+                sort: MarketplaceSort::Popularity,
+                ..Default::default()
+            },
+        );
+        assert_eq!(names(&popular), ["Ambient", "Clock", "Notes"]);
+
+        let filtered = browse_entries(
+            &entries,
+            &MarketplaceQuery {
+                categories: vec!["productivity".into()],
+                sort: MarketplaceSort::Name,
+                page: 1,
+                page_size: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(filtered.total, 2);
+        assert_eq!(names(&filtered), ["Notes"]);
+        // Facets still count the other categories
+        assert_eq!(filtered.categories.len(), 3);
+    }
+
+    #[test]
+    fn popularity_ranks_zero_installs_above_unknown_counts() {
+        let entry = |name: &str, install_count: Option<u64>| -> RegistryEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": format!("com.{}", name.to_lowercase()), "name": name,
+                "version": "1.0.0", "description": "", "image": "img",
+                "manifest_url": "https://example.com/plugin.json",
+                "install_count": install_count,
+            }))
+            .unwrap()
+        };
+        let entries = vec![
+            entry("Unknown", None),
+            entry("Zero", Some(0)),
+            entry("Popular", Some(12)),
+        ];
+        let page = browse_entries(
+            &entries,
+            &MarketplaceQuery {
+                sort: MarketplaceSort::Popularity,
+                ..Default::default()
+            },
+        );
+        let names: Vec<&str> = page.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Popular", "Zero", "Unknown"]);
+    }
+
+    #[test]
+    fn collections_resolve_listed_packages_from_their_own_registry_first() {
+        let plugin = |id: &str, source: &str, yanked: bool| -> RegistryEntry {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "name": id, "version": "1.0.0", "description": "",
+                "image": "img", "manifest_url": "https://example.com/plugin.json",
+                "source": source,
+                "yanked": if yanked { serde_json::json!([{ "version": "1.0.0" }]) } else { serde_json::json!([]) },
+            }))
+            .unwrap()
+        };
+        let extension: ExtensionRegistryEntry = serde_json::from_value(serde_json::json!({
+            "id": "com.example.docker", "name": "Docker", "version": "1.0.0",
+            "description": "", "manifest_url": "https://example.com/ext.json",
+            "source": "Lab",
+        }))
+        .unwrap();
+        let plugins = vec![
+            plugin("com.example.notes", "Nexus Community", false),
+            plugin("com.example.notes", "Lab", false),
+            plugin("com.example.broken", "Lab", true),
+        ];
+        let collection = |id: &str, packages: &[&str]| Collection {
+            id: id.into(),
+            name: id.into(),
+            description: String::new(),
+            packages: packages.iter().map(|p| p.to_string()).collect(),
+            source: "Lab".into(),
+        };
+        let collections = vec![
+            collection(
+                "home-lab",
+                &["com.example.docker", "com.example.notes", "com.example.missing"],
+            ),
+            collection("withdrawn", &["com.example.broken"]),
+        ];
+
+        let resolved = resolve_collections(&collections, &plugins, &[extension]);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].plugins.len(), 1);
+        assert_eq!(resolved[0].plugins[0].source, "Lab");
+        assert_eq!(resolved[0].extensions[0].id, "com.example.docker");
+    }
+
+    #[test]
+    fn unbuilt_local_registries_cannot_claim_verified_publishers() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("plugins")).unwrap();
+        std::fs::write(
+            tmp.path().join("plugins/notes.yaml"),
+            "id: com.example.notes\nname: Notes\nversion: 1.0.0\ndescription: d\n\
+             image: img\nmanifest_url: plugin.json\npublisher: example\n\
+             verified: true\nverified_identity: example.com\n",
+        )
+        .unwrap();
+
+        let registry = fetch_local(&tmp.path().display().to_string()).unwrap();
+        let entry = &registry.plugins[0];
+        assert_eq!(entry.publisher.as_deref(), Some("example"));
+        assert!(!entry.verified);
+        assert!(entry.verified_identity.is_none());
+    }
+}
@@ -0,0 +1,201 @@
+//! Per-plugin resource usage history.
+//!
+//! A background task samples each running plugin's container and keeps the
+//! last hour of samples in memory, so the UI can draw sparklines without
+//! polling the container engine itself. History is not persisted; it starts
+//! empty on every launch.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::Manager;
+
+use super::storage::PluginStatus;
+use crate::runtime::ContainerUsage;
+use crate::AppState;
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// One hour of samples at the sampling interval.
+const HISTORY_LEN: usize = 360;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceSample {
+    pub timestamp: DateTime<Utc>,
+    pub cpu_percent: f64,
+    pub memory_mb: f64,
+    /// Bytes received per second since the previous sample.
+    pub net_rx_bps: f64,
+    /// Bytes sent per second since the previous sample.
+    pub net_tx_bps: f64,
+}
+
+#[derive(Default)]
+struct Series {
+    samples: VecDeque<ResourceSample>,
+    /// Cumulative network counters from the previous sample, to turn the
+    /// next one into a rate.
+    last_counters: Option<(DateTime<Utc>, u64, u64)>,
+}
+
+#[derive(Default)]
+pub struct ResourceHistory {
+    inner: Mutex<HashMap<String, Series>>,
+}
+
+impl ResourceHistory {
+    /// Append a sample for `plugin_id`, dropping the oldest once the buffer
+    /// is full.
+    pub fn record(&self, plugin_id: &str, usage: &ContainerUsage, at: DateTime<Utc>) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let series = inner.entry(plugin_id.to_string()).or_default();
+
+        // Counters reset when the container restarts; report zero rather
+        // than a negative rate
+        let (net_rx_bps, net_tx_bps) = match series.last_counters {
+            Some((prev_at, prev_rx, prev_tx)) => {
+                let secs = (at - prev_at).num_milliseconds() as f64 / 1000.0;
+                if secs > 0.0 {
+                    (
+                        usage.net_rx_bytes.saturating_sub(prev_rx) as f64 / secs,
+                        usage.net_tx_bytes.saturating_sub(prev_tx) as f64 / secs,
+                    )
+                } else {
+                    (0.0, 0.0)
+                }
+            }
+            None => (0.0, 0.0),
+        };
+        series.last_counters = Some((at, usage.net_rx_bytes, usage.net_tx_bytes));
+
+        if series.samples.len() == HISTORY_LEN {
+            series.samples.pop_front();
+        }
+        series.samples.push_back(ResourceSample {
+            timestamp: at,
+            cpu_percent: usage.cpu_percent,
+            memory_mb: usage.memory_mb,
+            net_rx_bps: (net_rx_bps * 10.0).round() / 10.0,
+            net_tx_bps: (net_tx_bps * 10.0).round() / 10.0,
+        });
+    }
+
+    /// Forget the network counters of a plugin that stopped, so its first
+    /// sample after a restart doesn't span the downtime.
+    fn pause(&self, plugin_id: &str) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(series) = inner.get_mut(plugin_id) {
+            series.last_counters = None;
+        }
+    }
+
+    /// Drop the history of plugins that are no longer installed.
+    fn retain(&self, installed: &HashSet<String>) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.retain(|id, _| installed.contains(id));
+    }
+
+    /// Samples for `plugin_id`, oldest first.
+    pub fn series(&self, plugin_id: &str) -> Vec<ResourceSample> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner
+            .get(plugin_id)
+            .map(|s| s.samples.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Spawn a background task that samples running plugins every ten seconds
+/// into the managed [`ResourceHistory`].
+pub fn spawn(app: tauri::AppHandle, state: AppState) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+        loop {
+            interval.tick().await;
+            sample(&app, &state).await;
+        }
+    });
+}
+
+async fn sample(app: &tauri::AppHandle, state: &AppState) {
+    let (plugins, runtime) = {
+        let mgr = state.read().await;
+        let plugins: Vec<(String, Option<String>)> = mgr
+            .storage
+            .list()
+            .iter()
+            .map(|p| {
+                let container = (p.status == PluginStatus::Running)
+                    .then(|| p.container_id.clone())
+                    .flatten();
+                (p.manifest.id.clone(), container)
+            })
+            .collect();
+        (plugins, mgr.runtime.clone())
+    };
+
+    let history = app.state::<ResourceHistory>();
+    history.retain(&plugins.iter().map(|(id, _)| id.clone()).collect());
+
+    for (plugin_id, container_id) in plugins {
+        let Some(container_id) = container_id else {
+            history.pause(&plugin_id);
+            continue;
+        };
+        match runtime.container_usage(&container_id).await {
+            Ok(usage) => history.record(&plugin_id, &usage, Utc::now()),
+            Err(e) => log::debug!("No resource sample for plugin={}: {}", plugin_id, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(rx: u64, tx: u64) -> ContainerUsage {
+        ContainerUsage {
+            cpu_percent: 1.5,
+            memory_mb: 64.0,
+            net_rx_bytes: rx,
+            net_tx_bytes: tx,
+        }
+    }
+
+    #[test]
+    fn network_counters_become_rates() {
+        let history = ResourceHistory::default();
+        let t0 = Utc::now();
+        history.record("p", &usage(1000, 500), t0);
+        history.record("p", &usage(3000, 1500), t0 + chrono::Duration::seconds(10));
+        // Counters reset after a restart
+        history.record("p", &usage(100, 100), t0 + chrono::Duration::seconds(20));
+
+        let series = history.series("p");
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0].net_rx_bps, 0.0);
+        assert_eq!(series[1].net_rx_bps, 200.0);
+        assert_eq!(series[1].net_tx_bps, 100.0);
+        assert_eq!(series[2].net_rx_bps, 0.0);
+        assert_eq!(series[2].memory_mb, 64.0);
+        assert!(history.series("other").is_empty());
+    }
+
+    #[test]
+    fn buffer_keeps_the_latest_hour() {
+        let history = ResourceHistory::default();
+        let t0 = Utc::now();
+        for i in 0..HISTORY_LEN + 5 {
+            history.record("p", &usage(0, 0), t0 + chrono::Duration::seconds(i as i64 * 10));
+        }
+        let series = history.series("p");
+        assert_eq!(series.len(), HISTORY_LEN);
+        assert_eq!(series[0].timestamp, t0 + chrono::Duration::seconds(50));
+
+        history.retain(&HashSet::new());
+        assert!(history.series("p").is_empty());
+    }
+}
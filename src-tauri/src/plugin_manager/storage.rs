@@ -1,5 +1,7 @@
-use super::manifest::PluginManifest;
+use super::manifest::{PluginManifest, PluginSecurity};
 use crate::error::NexusResult;
+use crate::secrets::{CredentialStorage, SecretMap};
+use crate::state_db::{self, StateDb};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -46,41 +48,60 @@ pub struct InstalledPlugin {
     /// Absolute path to the plugin.json used for local installs (needed for dev rebuilds).
     #[serde(default)]
     pub local_manifest_path: Option<String>,
+    /// Release channel updates are taken from.
+    #[serde(default)]
+    pub channel: super::registry::ReleaseChannel,
+    /// Confinement profiles the user set in place of the manifest's.
+    #[serde(default)]
+    pub security_override: PluginSecurity,
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+/// Installed plugins, kept in `state.db` (one record per plugin).
+#[derive(Debug, Deserialize, Default)]
 pub struct PluginStorage {
     plugins: HashMap<String, InstalledPlugin>,
     next_port: u16,
     #[serde(skip)]
-    path: PathBuf,
+    db: Option<StateDb>,
 }
 
 impl PluginStorage {
+    /// Legacy store, imported into `state.db` on first load.
+    pub(crate) const LEGACY_FILE: &'static str = "plugins.json";
+
     pub fn load(data_dir: &std::path::Path) -> NexusResult<Self> {
-        let path = data_dir.join("plugins.json");
-        if path.exists() {
-            let data = std::fs::read_to_string(&path)?;
-            let mut storage: PluginStorage = serde_json::from_str(&data)?;
-            storage.path = path;
-            if storage.next_port == 0 {
-                storage.next_port = 9700;
+        let db = StateDb::open(data_dir)?;
+        let legacy: Option<PluginStorage> = state_db::legacy_json(data_dir, Self::LEGACY_FILE)?;
+        let imported = legacy.is_some();
+        let mut storage = match legacy {
+            Some(storage) => storage,
+            None => {
+                let meta: HashMap<String, u16> =
+                    state_db::from_records(db.load("plugin_storage")?)?.into_iter().collect();
+                PluginStorage {
+                    plugins: state_db::from_records(db.load("plugins")?)?.into_iter().collect(),
+                    next_port: meta.get("next_port").copied().unwrap_or(0),
+                    db: None,
+                }
             }
+        };
+        storage.db = Some(db);
+        if storage.next_port == 0 {
+            storage.next_port = 9700;
+        }
 
-            Ok(storage)
-        } else {
-            Ok(PluginStorage {
-                plugins: HashMap::new(),
-                next_port: 9700,
-                path,
-            })
+        if imported {
+            storage.save()?;
+            state_db::retire_legacy(data_dir, Self::LEGACY_FILE)?;
         }
+        Ok(storage)
     }
 
     pub fn save(&self) -> NexusResult<()> {
-        let data = serde_json::to_string_pretty(self)?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
-        Ok(())
+        state_db::attached(&self.db)?.save(&[
+            ("plugins", state_db::to_records(&self.plugins)?),
+            ("plugin_storage", state_db::to_records([("next_port", self.next_port)])?),
+        ])
     }
 
     pub fn add(&mut self, plugin: InstalledPlugin) -> NexusResult<()> {
@@ -188,6 +209,36 @@ mod tests {
     fn extract_url_host_no_scheme() {
         assert_eq!(extract_url_host("just-a-string"), None);
     }
+
+    #[test]
+    fn tool_approvals_are_scoped_per_client() {
+        let mut settings = McpSettings::default();
+        settings.approve_tool("api_key:a", "Client A", "nexus", "execute_command");
+
+        assert!(settings.is_tool_approved(Some("api_key:a"), "nexus", "execute_command"));
+        assert!(!settings.is_tool_approved(Some("api_key:b"), "nexus", "execute_command"));
+        assert!(!settings.is_tool_approved(None, "nexus", "execute_command"));
+
+        // Approving twice doesn't duplicate
+        settings.approve_tool("api_key:a", "Client A", "nexus", "execute_command");
+        assert_eq!(settings.client_approvals.len(), 1);
+
+        assert!(settings.revoke_tool_approval(Some("api_key:a"), "nexus", "execute_command"));
+        assert!(!settings.is_tool_approved(Some("api_key:a"), "nexus", "execute_command"));
+        assert!(!settings.revoke_tool_approval(Some("api_key:a"), "nexus", "execute_command"));
+    }
+
+    #[test]
+    fn legacy_global_approvals_still_honored_until_revoked() {
+        let mut settings = McpSettings::default();
+        settings.plugins.entry("nexus".into()).or_default().approved_tools.push("plugin_stop".into());
+
+        assert!(settings.is_tool_approved(Some("oauth:x"), "nexus", "plugin_stop"));
+        assert!(settings.is_tool_approved(None, "nexus", "plugin_stop"));
+
+        assert!(settings.revoke_tool_approval(None, "nexus", "plugin_stop"));
+        assert!(!settings.is_tool_approved(Some("oauth:x"), "nexus", "plugin_stop"));
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -204,22 +255,15 @@ pub struct PluginSettingsStore {
 impl PluginSettingsStore {
     pub fn load(data_dir: &std::path::Path) -> NexusResult<Self> {
         let path = data_dir.join("plugin_settings.json");
-        if path.exists() {
-            let data = std::fs::read_to_string(&path)?;
-            let mut store: PluginSettingsStore = serde_json::from_str(&data)?;
-            store.path = path;
-            Ok(store)
-        } else {
-            Ok(PluginSettingsStore {
-                settings: HashMap::new(),
-                path,
-            })
-        }
+        let mut store: PluginSettingsStore =
+            crate::util::read_json_store(&path).unwrap_or_default();
+        store.path = path;
+        Ok(store)
     }
 
     pub fn save(&self) -> NexusResult<()> {
         let data = serde_json::to_string_pretty(self)?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
+        crate::util::atomic_write_with_backup(&self.path, data.as_bytes())?;
         Ok(())
     }
 
@@ -237,6 +281,72 @@ impl PluginSettingsStore {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Per-plugin environment storage
+// ---------------------------------------------------------------------------
+
+/// Key under which a remote MCP plugin's bearer token is stored.
+pub const REMOTE_MCP_TOKEN_KEY: &str = "MCP_BEARER_TOKEN";
+
+/// Configuration supplied at install time: container environment (API keys
+/// for wrapped MCP servers and the like), or for remote MCP plugins the
+/// [`REMOTE_MCP_TOKEN_KEY`] credential. Kept out of `plugin.json` so secrets
+/// never land in a manifest that may be shared or republished, and in the
+/// keychain unless the user chose file storage.
+#[derive(Debug, Default)]
+pub struct PluginEnvStore {
+    env: SecretMap<HashMap<String, String>>,
+}
+
+/// `plugin_env.json` as written before credentials could live in the keychain.
+#[derive(Deserialize)]
+struct LegacyPluginEnv {
+    env: HashMap<String, HashMap<String, String>>,
+}
+
+impl PluginEnvStore {
+    pub fn load(data_dir: &std::path::Path) -> NexusResult<Self> {
+        let path = data_dir.join("plugin_env.json");
+        let legacy = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<LegacyPluginEnv>(&data).ok());
+        let env = match legacy {
+            Some(legacy) => SecretMap::from_entries(path, legacy.env),
+            None => SecretMap::load(path),
+        };
+        Ok(PluginEnvStore { env })
+    }
+
+    /// Move the stored env to the keychain or to its file.
+    pub fn set_credential_storage(&mut self, storage: CredentialStorage) -> NexusResult<()> {
+        self.env.set_storage(storage)
+    }
+
+    pub fn get(&self, plugin_id: &str) -> HashMap<String, String> {
+        self.env.get(plugin_id).cloned().unwrap_or_default()
+    }
+
+    /// `KEY=VALUE` strings ready for the container config.
+    pub fn env_vars(&self, plugin_id: &str) -> Vec<String> {
+        self.env
+            .get(plugin_id)
+            .map(|vars| vars.iter().map(|(k, v)| format!("{}={}", k, v)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, plugin_id: &str, vars: HashMap<String, String>) -> NexusResult<()> {
+        if vars.is_empty() {
+            return self.remove(plugin_id);
+        }
+        self.env.insert(plugin_id.to_string(), vars)
+    }
+
+    pub fn remove(&mut self, plugin_id: &str) -> NexusResult<()> {
+        self.env.remove(plugin_id)?;
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // MCP settings storage
 // ---------------------------------------------------------------------------
@@ -256,9 +366,9 @@ pub struct McpPluginSettings {
     /// A tool with `enabled: false` only appears if it's in this list.
     #[serde(default)]
     pub enabled_tools: Vec<String>,
-    /// Tools the user has permanently approved (skips `requires_approval` prompts).
-    /// Populated when the user clicks "Approve" (vs "Approve Once") in the
-    /// runtime approval dialog for an MCP tool.
+    /// Tools permanently approved for *every* client (skips `requires_approval` prompts).
+    /// Legacy: new "Approve" decisions are recorded per client in
+    /// [`McpSettings::client_approvals`]; entries here are still honored until revoked.
     #[serde(default)]
     pub approved_tools: Vec<String>,
     /// Resource URIs disabled by the user (native MCP resources).
@@ -267,6 +377,10 @@ pub struct McpPluginSettings {
     /// Prompt names disabled by the user (native MCP prompts).
     #[serde(default)]
     pub disabled_prompts: Vec<String>,
+    /// Per-tool call timeout overrides in seconds, keyed by local tool name.
+    /// Tools without an entry use the category default.
+    #[serde(default)]
+    pub tool_timeouts: HashMap<String, u64>,
 }
 
 impl Default for McpPluginSettings {
@@ -278,16 +392,72 @@ impl Default for McpPluginSettings {
             approved_tools: vec![],
             disabled_resources: vec![],
             disabled_prompts: vec![],
+            tool_timeouts: HashMap::new(),
         }
     }
 }
 
+/// Call limits for a single MCP client (API key or OAuth client). `None` = unlimited.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpClientLimits {
+    #[serde(default)]
+    pub calls_per_minute: Option<u32>,
+    #[serde(default)]
+    pub max_concurrent_calls: Option<u32>,
+    /// Max calls to state-changing tools per UTC day.
+    #[serde(default)]
+    pub daily_mutating_quota: Option<u32>,
+}
+
+impl McpClientLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            calls_per_minute: None,
+            max_concurrent_calls: None,
+            daily_mutating_quota: None,
+        }
+    }
+}
+
+impl Default for McpClientLimits {
+    /// Generous for interactive use, but stops an agent stuck in a loop.
+    fn default() -> Self {
+        Self {
+            calls_per_minute: Some(120),
+            max_concurrent_calls: Some(8),
+            daily_mutating_quota: Some(500),
+        }
+    }
+}
+
+/// A permanent "always allow" decision for one client and one tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpToolApproval {
+    /// Client ID (`api_key:{id}` or `oauth:{client_id}`).
+    pub client_id: String,
+    /// Display name of the client at the time of approval.
+    pub client_name: String,
+    pub plugin_id: String,
+    /// Local tool name (or extension operation) within `plugin_id`.
+    pub tool: String,
+    pub approved_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct McpSettings {
     #[serde(default = "default_true")]
     pub enabled: bool,
     #[serde(default)]
     pub plugins: HashMap<String, McpPluginSettings>,
+    /// Limits applied to every client without an override.
+    #[serde(default)]
+    pub default_client_limits: McpClientLimits,
+    /// Per-client overrides, keyed by client ID (`api_key:{id}` or `oauth:{client_id}`).
+    #[serde(default)]
+    pub client_limits: HashMap<String, McpClientLimits>,
+    /// Permanent tool approvals, scoped to the client that was approved.
+    #[serde(default)]
+    pub client_approvals: Vec<McpToolApproval>,
     #[serde(skip)]
     path: PathBuf,
 }
@@ -297,6 +467,9 @@ impl Default for McpSettings {
         McpSettings {
             enabled: true,
             plugins: HashMap::new(),
+            default_client_limits: McpClientLimits::default(),
+            client_limits: HashMap::new(),
+            client_approvals: vec![],
             path: PathBuf::new(),
         }
     }
@@ -305,24 +478,81 @@ impl Default for McpSettings {
 impl McpSettings {
     pub fn load(data_dir: &std::path::Path) -> NexusResult<Self> {
         let path = data_dir.join("mcp_settings.json");
-        if path.exists() {
-            let data = std::fs::read_to_string(&path)?;
-            let mut settings: McpSettings = serde_json::from_str(&data)?;
-            settings.path = path;
-            Ok(settings)
-        } else {
-            Ok(McpSettings {
-                path,
-                ..Default::default()
-            })
-        }
+        let mut settings: McpSettings = crate::util::read_json_store(&path).unwrap_or_default();
+        settings.path = path;
+        Ok(settings)
     }
 
     pub fn save(&self) -> NexusResult<()> {
         let data = serde_json::to_string_pretty(self)?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
+        crate::util::atomic_write_with_backup(&self.path, data.as_bytes())?;
         Ok(())
     }
+
+    /// User-configured timeout for a namespaced tool (`{plugin_id}.{tool}`), if any.
+    pub fn tool_timeout_override(&self, namespaced: &str) -> Option<u64> {
+        self.plugins.iter().find_map(|(pid, s)| {
+            let local = namespaced.strip_prefix(pid.as_str())?.strip_prefix('.')?;
+            s.tool_timeouts.get(local).copied()
+        })
+    }
+
+    /// Effective limits for a client: its override if set, otherwise the defaults.
+    pub fn limits_for(&self, client_id: &str) -> &McpClientLimits {
+        self.client_limits
+            .get(client_id)
+            .unwrap_or(&self.default_client_limits)
+    }
+
+    /// Whether `tool` in `plugin_id` is permanently approved for this client,
+    /// either through a legacy global approval or one scoped to the client.
+    pub fn is_tool_approved(&self, client_id: Option<&str>, plugin_id: &str, tool: &str) -> bool {
+        let global = self
+            .plugins
+            .get(plugin_id)
+            .is_some_and(|s| s.approved_tools.iter().any(|t| t == tool));
+        global
+            || client_id.is_some_and(|cid| {
+                self.client_approvals
+                    .iter()
+                    .any(|a| a.client_id == cid && a.plugin_id == plugin_id && a.tool == tool)
+            })
+    }
+
+    /// Record a permanent approval for one client. No-op if already approved.
+    pub fn approve_tool(&mut self, client_id: &str, client_name: &str, plugin_id: &str, tool: &str) {
+        if self.is_tool_approved(Some(client_id), plugin_id, tool) {
+            return;
+        }
+        self.client_approvals.push(McpToolApproval {
+            client_id: client_id.to_string(),
+            client_name: client_name.to_string(),
+            plugin_id: plugin_id.to_string(),
+            tool: tool.to_string(),
+            approved_at: chrono::Utc::now(),
+        });
+    }
+
+    /// Revoke a single approval. `client_id: None` targets the legacy
+    /// all-clients list. Returns `true` if something was removed.
+    pub fn revoke_tool_approval(&mut self, client_id: Option<&str>, plugin_id: &str, tool: &str) -> bool {
+        match client_id {
+            Some(cid) => {
+                let before = self.client_approvals.len();
+                self.client_approvals
+                    .retain(|a| !(a.client_id == cid && a.plugin_id == plugin_id && a.tool == tool));
+                self.client_approvals.len() != before
+            }
+            None => match self.plugins.get_mut(plugin_id) {
+                Some(s) => {
+                    let before = s.approved_tools.len();
+                    s.approved_tools.retain(|t| t != tool);
+                    s.approved_tools.len() != before
+                }
+                None => false,
+            },
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -348,8 +578,68 @@ pub struct NexusSettings {
     /// App update channel: "stable" or "nightly".
     #[serde(default = "default_update_channel")]
     pub update_channel: String,
+    /// Panic switch: the Host API and MCP gateway reject every state-changing
+    /// call (file writes, command execution, container mutations, installs),
+    /// whatever the caller has been granted.
+    #[serde(default)]
+    pub read_only_mode: bool,
+    /// Ack timeout and backoff for event subscribers that acknowledge events.
+    #[serde(default)]
+    pub event_redelivery: crate::event_bus::subscription::RedeliveryPolicy,
+    /// Serve event bus metrics at `/metrics` on the Host API for Prometheus.
+    #[serde(default)]
+    pub prometheus_metrics: bool,
+    /// Rate and payload size limits for plugins publishing events.
+    #[serde(default)]
+    pub event_quota: crate::event_bus::quota::PublishQuota,
+    /// How much event history to keep in memory and in the event store.
+    #[serde(default)]
+    pub event_retention: crate::event_bus::store::RetentionPolicy,
+    /// Connection to an external MQTT broker that events are mirrored to and from.
+    #[serde(default)]
+    pub mqtt_bridge: crate::event_bus::mqtt::MqttBridgeConfig,
+    /// One-way export of events to a NATS subject or Kafka topic.
+    #[serde(default)]
+    pub event_export: crate::event_bus::export::ExportConfig,
+    /// Forwarding of audit entries to a syslog server or HTTPS collector.
+    #[serde(default)]
+    pub audit_forward: crate::audit::forward::ForwardConfig,
+    /// How long audit entries are kept and how large the audit log may grow.
+    #[serde(default)]
+    pub audit_retention: crate::audit::store::AuditRetention,
+    /// Rules that raise a notification and an event on matching audit entries.
+    #[serde(default)]
+    pub audit_alerts: Vec<crate::audit::alerts::AlertRule>,
+    /// Fields masked in audit entry details before they're stored.
+    #[serde(default)]
+    pub audit_redaction: crate::audit::redact::RedactionPolicy,
+    /// Plugins whose Host API requests are each audited, for investigation.
+    #[serde(default)]
+    pub host_api_access_log: crate::host_api::access_log::AccessLogConfig,
+    /// Which plugins are updated automatically when a check finds a new version.
+    #[serde(default)]
+    pub auto_update: crate::update_checker::AutoUpdatePolicy,
+    /// Container engine plugins run on.
+    #[serde(default)]
+    pub engine: crate::runtime::EngineConfig,
+    /// Outbound HTTP proxy for registry, update, and network proxy requests.
+    #[serde(default)]
+    pub proxy: crate::proxy::ProxyConfig,
+    /// Where registry credentials and plugin secrets are kept.
+    #[serde(default)]
+    pub credential_storage: CredentialStorage,
+    /// What critical vulnerabilities in a plugin image do at install time.
+    #[serde(default)]
+    pub image_scan: super::image_scan::ImageScanPolicy,
+    /// What plugin images that run as root do at install time.
+    #[serde(default)]
+    pub root_user: super::non_root::RootUserPolicy,
+    /// Accelerator that shows or hides the main window from anywhere; None
+    /// turns it off.
+    #[serde(default = "default_global_shortcut")]
+    pub global_shortcut: Option<String>,
     #[serde(skip)]
-    path: PathBuf,
+    db: Option<StateDb>,
 }
 
 fn default_update_interval() -> u32 {
@@ -368,42 +658,48 @@ fn default_update_channel() -> String {
     "stable".to_string()
 }
 
+fn default_global_shortcut() -> Option<String> {
+    Some(crate::shortcut::DEFAULT.to_string())
+}
+
 impl NexusSettings {
+    /// Legacy store, imported into `state.db` on first load.
+    const LEGACY_FILE: &'static str = "settings.json";
+
     /// Interval values that were valid in older releases but are now removed.
     /// Migrated to daily (1440) on load.
     const STALE_INTERVALS: &[u32] = &[30, 60, 360];
 
     pub fn load(data_dir: &std::path::Path) -> NexusResult<Self> {
-        let path = data_dir.join("settings.json");
-        if path.exists() {
-            let data = std::fs::read_to_string(&path)?;
-            let mut settings: NexusSettings = serde_json::from_str(&data)?;
-            settings.path = path;
-
-            // Migrate stale auto-check intervals to daily.
-            if Self::STALE_INTERVALS.contains(&settings.update_check_interval_minutes) {
-                log::info!(
-                    "Migrating update_check_interval from {} to 1440 (daily)",
-                    settings.update_check_interval_minutes,
-                );
-                settings.update_check_interval_minutes = 1440;
-                if let Err(e) = settings.save() {
-                    log::warn!("Failed to save migrated settings: {}", e);
-                }
-            }
+        let db = StateDb::open(data_dir)?;
+        let legacy: Option<NexusSettings> = state_db::legacy_json(data_dir, Self::LEGACY_FILE)?;
+        let imported = legacy.is_some();
+        let mut settings = match legacy {
+            Some(settings) => settings,
+            None => state_db::fields_from_records(db.load("settings")?)?,
+        };
+        settings.db = Some(db);
+        if imported {
+            settings.save()?;
+            state_db::retire_legacy(data_dir, Self::LEGACY_FILE)?;
+        }
 
-            Ok(settings)
-        } else {
-            Ok(NexusSettings {
-                path,
-                ..Default::default()
-            })
+        // Migrate stale auto-check intervals to daily.
+        if Self::STALE_INTERVALS.contains(&settings.update_check_interval_minutes) {
+            log::info!(
+                "Migrating update_check_interval from {} to 1440 (daily)",
+                settings.update_check_interval_minutes,
+            );
+            settings.update_check_interval_minutes = 1440;
+            if let Err(e) = settings.save() {
+                log::warn!("Failed to save migrated settings: {}", e);
+            }
         }
+
+        Ok(settings)
     }
 
     pub fn save(&self) -> NexusResult<()> {
-        let data = serde_json::to_string_pretty(self)?;
-        crate::util::atomic_write(&self.path, data.as_bytes())?;
-        Ok(())
+        state_db::attached(&self.db)?.save(&[("settings", state_db::fields_to_records(self)?)])
     }
 }
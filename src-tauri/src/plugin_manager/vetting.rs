@@ -0,0 +1,95 @@
+//! Checks on a plugin's pulled image before its container is created:
+//! digest verification, the vulnerability scan, and the user it runs as.
+//!
+//! They can take minutes (a first Trivy run downloads its database), so
+//! commands copy an [`ImageVetter`] out of the manager and run them without
+//! holding `AppState`, then hand the [`VettedImage`] to the install or update.
+
+use std::sync::Arc;
+
+use super::image_scan::{self, ImageScan, ImageScanPolicy};
+use super::manifest::{PluginManifest, PluginSecurity};
+use super::non_root::{self, RootUserPolicy};
+use crate::error::{NexusError, NexusResult};
+use crate::runtime::ContainerRuntime;
+
+/// What the checks found.
+#[derive(Debug, Clone, Default)]
+pub struct VettedImage {
+    /// Critical findings the scan policy let through.
+    pub scan: Option<ImageScan>,
+    /// The container will run as root.
+    pub runs_as_root: bool,
+    /// User to run the container as in place of the image's.
+    pub user: Option<String>,
+}
+
+/// Everything the checks need, copied out of the manager by
+/// [`PluginManager::image_vetter`](super::PluginManager::image_vetter).
+pub struct ImageVetter {
+    pub(super) runtime: Arc<dyn ContainerRuntime>,
+    pub(super) image_scan: ImageScanPolicy,
+    pub(super) root_user: RootUserPolicy,
+    /// The installed plugin's security overrides, if any.
+    pub(super) security_override: PluginSecurity,
+}
+
+impl ImageVetter {
+    /// Check the manifest's image, which must already be pulled. Fails when
+    /// the digest doesn't match or a policy blocks what was found.
+    pub async fn vet(&self, manifest: &PluginManifest) -> NexusResult<VettedImage> {
+        if manifest.remote_mcp_url().is_some() {
+            return Ok(VettedImage::default());
+        }
+        self.verify_digest(manifest).await?;
+
+        let scan = image_scan::check(&manifest.image, self.image_scan)
+            .await?
+            .filter(|s| !s.critical.is_empty());
+
+        let declared = manifest.security.overridden_by(&self.security_override).user;
+        let image_user = self.runtime.get_image_user(&manifest.image).await?;
+        let user = non_root::run_as(self.root_user, image_user.as_deref(), declared.as_deref());
+        let effective = user.as_deref().or(image_user.as_deref());
+        let runs_as_root = non_root::check(self.root_user, &manifest.image, effective)?;
+
+        Ok(VettedImage {
+            scan,
+            runs_as_root,
+            user,
+        })
+    }
+
+    async fn verify_digest(&self, manifest: &PluginManifest) -> NexusResult<()> {
+        let Some(ref expected_digest) = manifest.image_digest else {
+            log::warn!(
+                "Plugin {} has no image_digest — skipping content verification",
+                manifest.id
+            );
+            return Ok(());
+        };
+        match self.runtime.get_image_digest(&manifest.image).await? {
+            Some(actual_digest) => {
+                if &actual_digest != expected_digest {
+                    return Err(NexusError::Other(format!(
+                        "Image digest mismatch for {}. Expected: {}, Got: {}. \
+                         The image may have been tampered with.",
+                        manifest.image, expected_digest, actual_digest
+                    )));
+                }
+                log::info!(
+                    "Image digest verified: {} = {}",
+                    manifest.image, actual_digest
+                );
+            }
+            None => {
+                log::warn!(
+                    "Image {} has no registry digest (locally built?). \
+                     Skipping digest verification.",
+                    manifest.image
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! Outbound HTTP proxy, for networks that only reach the internet through
+//! one.
+//!
+//! The configuration is stored in settings as `proxy`. The active copy is
+//! kept here so HTTP clients built outside the plugin manager's lock
+//! (registry fetches, image checks, extension downloads) pick it up too.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+
+static ACTIVE: RwLock<Option<ProxyConfig>> = RwLock::new(None);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    /// Hosts, domains (`.corp.example`), and IP ranges that are reached
+    /// directly.
+    pub no_proxy: Vec<String>,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: 3128,
+            no_proxy: vec!["localhost".to_string(), "127.0.0.1".to_string()],
+        }
+    }
+}
+
+impl ProxyConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let host = self.host.trim();
+        if host.is_empty() {
+            return Err("Proxy host is empty".to_string());
+        }
+        if host.contains("://") || host.contains('/') {
+            return Err("Proxy host must be a hostname or IP address, without a scheme".to_string());
+        }
+        if self.port == 0 {
+            return Err("Proxy port must be between 1 and 65535".to_string());
+        }
+        Ok(())
+    }
+
+    /// `http://host:port`, or None when the proxy is off.
+    pub fn url(&self) -> Option<String> {
+        let host = self.host.trim();
+        if !self.enabled || host.is_empty() {
+            return None;
+        }
+        // IPv6 literals need brackets in a URL
+        if host.contains(':') && !host.starts_with('[') {
+            Some(format!("http://[{}]:{}", host, self.port))
+        } else {
+            Some(format!("http://{}:{}", host, self.port))
+        }
+    }
+
+    fn no_proxy_list(&self) -> String {
+        self.no_proxy
+            .iter()
+            .map(|h| h.trim())
+            .filter(|h| !h.is_empty())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Make `config` the proxy for HTTP clients built from now on.
+pub fn set(config: &ProxyConfig) {
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = Some(config.clone());
+}
+
+/// The proxy URL in effect, if any.
+pub fn active_url() -> Option<String> {
+    ACTIVE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .and_then(ProxyConfig::url)
+}
+
+/// Route `builder`'s requests through the configured proxy. With the proxy
+/// off, reqwest's default of honouring `HTTP_PROXY` and friends stays.
+pub fn apply(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let active = ACTIVE.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let Some(config) = active else {
+        return builder;
+    };
+    let Some(url) = config.url() else {
+        return builder;
+    };
+    match reqwest::Proxy::all(&url) {
+        Ok(proxy) => builder.proxy(
+            proxy.no_proxy(reqwest::NoProxy::from_string(&config.no_proxy_list())),
+        ),
+        Err(e) => {
+            log::warn!("Ignoring invalid proxy {}: {}", url, e);
+            builder
+        }
+    }
+}
+
+/// The conventional proxy variables, for image builds. Empty with the
+/// proxy off.
+pub fn env() -> HashMap<String, String> {
+    let active = ACTIVE.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let Some(config) = active else {
+        return HashMap::new();
+    };
+    let Some(url) = config.url() else {
+        return HashMap::new();
+    };
+    let no_proxy = config.no_proxy_list();
+    let mut vars = HashMap::new();
+    // Tools disagree on the case, so set both
+    for (name, value) in [("HTTP_PROXY", &url), ("HTTPS_PROXY", &url), ("NO_PROXY", &no_proxy)] {
+        vars.insert(name.to_string(), value.clone());
+        vars.insert(name.to_lowercase(), value.clone());
+    }
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_requires_enabled_host() {
+        let mut config = ProxyConfig {
+            host: "proxy.corp.example".into(),
+            ..Default::default()
+        };
+        assert_eq!(config.url(), None);
+        config.enabled = true;
+        assert_eq!(config.url().as_deref(), Some("http://proxy.corp.example:3128"));
+        config.host = "::1".into();
+        assert_eq!(config.url().as_deref(), Some("http://[::1]:3128"));
+    }
+
+    #[test]
+    fn validate_rejects_urls_and_zero_port() {
+        let mut config = ProxyConfig {
+            enabled: true,
+            host: "http://proxy".into(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        config.host = "proxy".into();
+        assert!(config.validate().is_ok());
+        config.port = 0;
+        assert!(config.validate().is_err());
+        // Nothing to check while off
+        config.enabled = false;
+        assert!(config.validate().is_ok());
+    }
+}
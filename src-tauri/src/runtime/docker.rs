@@ -1,9 +1,10 @@
 use async_trait::async_trait;
 use bollard::container::LogOutput;
 use bollard::query_parameters::{
-    BuildImageOptions, CreateContainerOptions, CreateImageOptions, ListContainersOptions,
-    ListNetworksOptions, LogsOptions, RemoveContainerOptions, RemoveImageOptions,
-    StartContainerOptions, StatsOptions, StopContainerOptions,
+    BuildImageOptions, CreateContainerOptions, CreateImageOptions, DataUsageOptions,
+    DownloadFromContainerOptions, ListContainersOptions, ListNetworksOptions, LogsOptions,
+    RemoveContainerOptions, RemoveImageOptions, StartContainerOptions, StatsOptions,
+    StopContainerOptions, UploadToContainerOptions,
 };
 use bollard::service::{
     ContainerCreateBody, ContainerSummaryStateEnum, HostConfig, Mount, MountTypeEnum,
@@ -15,24 +16,257 @@ use std::collections::HashMap;
 use std::path::Path;
 
 use super::{
-    ContainerConfig, ContainerEvent, ContainerEventAction, ContainerFilters, ContainerInfo,
-    ContainerRuntime, ContainerState, ResourceUsage, RuntimeError,
+    ContainerConfig, ContainerEvent, ContainerUsage, EngineConfig, EngineKind, ContainerEventAction, ContainerFilters, ContainerInfo,
+    ContainerRuntime, ContainerState, Progress, ProgressFn, ProgressUnit, ResourceUsage,
+    RuntimeError,
 };
 
 // ---------------------------------------------------------------------------
 // DockerRuntime
 // ---------------------------------------------------------------------------
 
+/// Runtime for any engine that serves the Docker Engine API, Podman
+/// included.
 pub struct DockerRuntime {
     docker: Docker,
+    kind: EngineKind,
+    host: String,
 }
 
 impl DockerRuntime {
-    pub fn new() -> Result<Self, RuntimeError> {
-        let docker = Docker::connect_with_local_defaults()
-            .map_err(|e| RuntimeError::Other(format!("Docker connection failed: {e}")))?;
-        Ok(Self { docker })
+    /// Build a client for the configured engine. No connection is made
+    /// until the first call, so an engine that isn't running yet is fine.
+    pub fn connect(config: &EngineConfig) -> Result<Self, RuntimeError> {
+        let host = match &config.host {
+            Some(host) if host.starts_with('/') => format!("unix://{host}"),
+            Some(host) => host.clone(),
+            None => default_host(config.kind),
+        };
+        let docker = Docker::connect_with_host(&host)
+            .map_err(|e| RuntimeError::Other(format!("Engine connection failed ({host}): {e}")))?;
+        Ok(Self {
+            docker,
+            kind: config.kind,
+            host,
+        })
+    }
+
+    /// Create a stopped container of `image` with `volume` mounted at
+    /// `/data`, to copy files in and out of the volume.
+    async fn volume_helper(&self, volume: &str, image: &str) -> Result<String, RuntimeError> {
+        let body = ContainerCreateBody {
+            image: Some(image.to_string()),
+            host_config: Some(HostConfig {
+                mounts: Some(vec![Mount {
+                    target: Some("/data".to_string()),
+                    source: Some(volume.to_string()),
+                    typ: Some(MountTypeEnum::VOLUME),
+                    read_only: Some(false),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let response = self
+            .docker
+            .create_container(None::<CreateContainerOptions>, body)
+            .await
+            .map_err(to_err)?;
+        Ok(response.id)
+    }
+
+    async fn download_data(&self, container: &str, dest: &Path) -> Result<u64, RuntimeError> {
+        use tokio::io::AsyncWriteExt;
+
+        let opts = DownloadFromContainerOptions {
+            path: "/data".to_string(),
+        };
+        let mut stream = self.docker.download_from_container(container, Some(opts));
+        let mut file = tokio::fs::File::create(dest).await?;
+        let mut written = 0_u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(to_err)?;
+            file.write_all(&chunk).await?;
+            written += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(written)
+    }
+
+    async fn upload_data(&self, container: &str, src: &Path) -> Result<(), RuntimeError> {
+        use tokio::io::AsyncReadExt;
+
+        let file = tokio::fs::File::open(src).await?;
+        let chunks = futures_util::stream::try_unfold(file, |mut file| async move {
+            let mut buf = vec![0; 64 * 1024];
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(None);
+            }
+            buf.truncate(n);
+            Ok(Some((buf.into(), file)))
+        });
+        let opts = UploadToContainerOptions {
+            path: "/".to_string(),
+            ..Default::default()
+        };
+        self.docker
+            .upload_to_container(container, Some(opts), bollard::body_try_stream(chunks))
+            .await
+            .map_err(to_err)
+    }
+
+    /// Build `context_dir` as `tag`. `fresh` skips the layer cache and
+    /// re-pulls base images so unpinned dependencies resolve to their latest.
+    async fn build(
+        &self,
+        context_dir: &Path,
+        tag: &str,
+        fresh: bool,
+        on_progress: &ProgressFn<'_>,
+    ) -> Result<(), RuntimeError> {
+        let tar_bytes = create_build_context(context_dir)?;
+
+        let opts = BuildImageOptions {
+            t: Some(tag.to_string()),
+            rm: true,
+            forcerm: true,
+            nocache: fresh,
+            pull: fresh.then(|| "true".to_string()),
+            // Docker passes the proxy variables to RUN steps without an ARG
+            buildargs: Some(crate::proxy::env()).filter(|args| !args.is_empty()),
+            ..Default::default()
+        };
+
+        let body = bollard::body_full(tar_bytes.into());
+        let mut stream = self.docker.build_image(opts, None, Some(body));
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(info) => {
+                    if let Some(stream) = info.stream {
+                        let msg = stream.trim();
+                        if !msg.is_empty() {
+                            log::debug!("Build: {}", msg);
+                        }
+                        if let Some(step) = build_step(msg) {
+                            on_progress(step);
+                        }
+                    }
+                    if let Some(detail) = info.error_detail {
+                        let msg = detail.message.unwrap_or_default();
+                        return Err(RuntimeError::Other(format!("Docker build error: {}", msg)));
+                    }
+                }
+                Err(e) => return Err(to_err(e)),
+            }
+        }
+
+        log::info!("Built image: {}", tag);
+        Ok(())
     }
+
+    async fn pull(&self, image: &str, on_progress: &ProgressFn<'_>) -> Result<(), RuntimeError> {
+        if self.image_exists(image).await? {
+            log::info!("Image {} found locally, skipping pull", image);
+            return Ok(());
+        }
+
+        let (repo, tag) = if let Some((r, t)) = image.rsplit_once(':') {
+            (r, t)
+        } else {
+            (image, "latest")
+        };
+
+        let opts = CreateImageOptions {
+            from_image: Some(repo.to_string()),
+            tag: Some(tag.to_string()),
+            ..Default::default()
+        };
+
+        let mut progress = PullProgress::default();
+        let mut stream = self.docker.create_image(Some(opts), None, None);
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(info) => {
+                    if let Some(status) = info.status {
+                        log::debug!("Pull: {}", status);
+                        let detail = info.progress_detail.unwrap_or_default();
+                        if let Some(layer) = info.id {
+                            if let Some(p) =
+                                progress.update(&layer, &status, detail.current, detail.total)
+                            {
+                                on_progress(p);
+                            }
+                        }
+                    }
+                }
+                Err(e) => return Err(to_err(e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sums layer download progress across a pull's status stream.
+#[derive(Default)]
+struct PullProgress {
+    /// Bytes downloaded and total size per layer.
+    layers: HashMap<String, (u64, u64)>,
+    last_percent: Option<u8>,
+}
+
+impl PullProgress {
+    /// Record one status message. Returns the overall progress when its
+    /// percentage changed, so callers aren't flooded with identical updates.
+    fn update(
+        &mut self,
+        layer: &str,
+        status: &str,
+        current: Option<i64>,
+        total: Option<i64>,
+    ) -> Option<Progress> {
+        match status {
+            "Downloading" => {
+                let total = total.filter(|t| *t > 0)? as u64;
+                let current = current.unwrap_or(0).max(0) as u64;
+                self.layers.insert(layer.to_string(), (current, total));
+            }
+            "Download complete" | "Extracting" | "Pull complete" => {
+                let (current, total) = self.layers.get_mut(layer)?;
+                *current = *total;
+            }
+            _ => return None,
+        }
+
+        let (current, total) = self
+            .layers
+            .values()
+            .fold((0, 0), |(c, t), (lc, lt)| (c + lc, t + lt));
+        let progress = Progress {
+            current,
+            total,
+            unit: ProgressUnit::Bytes,
+        };
+        let percent = progress.percent();
+        if self.last_percent == Some(percent) {
+            return None;
+        }
+        self.last_percent = Some(percent);
+        Some(progress)
+    }
+}
+
+/// Parse a classic builder `Step 3/7 : RUN ...` line.
+fn build_step(line: &str) -> Option<Progress> {
+    let (step, rest) = line.strip_prefix("Step ")?.split_once('/')?;
+    let total = rest.split(|c: char| !c.is_ascii_digit()).next()?;
+    Some(Progress {
+        current: step.parse().ok()?,
+        total: total.parse().ok()?,
+        unit: ProgressUnit::Steps,
+    })
 }
 
 fn to_err(e: bollard::errors::Error) -> RuntimeError {
@@ -44,6 +278,113 @@ fn to_err(e: bollard::errors::Error) -> RuntimeError {
     }
 }
 
+/// CPU usage as a percentage of one core, from the delta between a stats
+/// sample and the one before it.
+fn cpu_percent(stats: &bollard::service::ContainerStatsResponse) -> f64 {
+    let (Some(cpu), Some(precpu)) = (&stats.cpu_stats, &stats.precpu_stats) else {
+        return 0.0;
+    };
+    let (Some(cpu_usage), Some(precpu_usage)) = (&cpu.cpu_usage, &precpu.cpu_usage) else {
+        return 0.0;
+    };
+    let cpu_delta = cpu_usage.total_usage.unwrap_or(0) as f64
+        - precpu_usage.total_usage.unwrap_or(0) as f64;
+    let system_delta = cpu.system_cpu_usage.unwrap_or(0) as f64
+        - precpu.system_cpu_usage.unwrap_or(0) as f64;
+    let num_cpus = cpu.online_cpus.unwrap_or(1) as f64;
+
+    if system_delta > 0.0 {
+        (cpu_delta / system_delta) * num_cpus * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Engine-side settings for a plugin container: its published port, data
+/// volume, confinement, and limits. `gateway_hostname` resolves to the host.
+fn host_config(config: &ContainerConfig, gateway_hostname: &str) -> HostConfig {
+    let port_binding = PortBinding {
+        host_ip: Some("127.0.0.1".to_string()),
+        host_port: Some(config.host_port.to_string()),
+    };
+
+    let mut port_bindings = HashMap::new();
+    port_bindings.insert(format!("{}/tcp", config.container_port), Some(vec![port_binding]));
+
+    let mounts = match config.data_volume {
+        Some(ref vol_name) => vec![Mount {
+            target: Some("/data".to_string()),
+            source: Some(vol_name.to_string()),
+            typ: Some(MountTypeEnum::VOLUME),
+            read_only: Some(false),
+            ..Default::default()
+        }],
+        None => vec![],
+    };
+
+    let tmpfs = config.security.read_only_rootfs.then(|| {
+        ["/tmp", "/run"]
+            .into_iter()
+            .map(|path| (path.to_string(), "rw,nosuid,nodev,size=64m".to_string()))
+            .collect::<HashMap<_, _>>()
+    });
+
+    let mut security_opt = Vec::new();
+    if config.security.no_new_privileges {
+        security_opt.push("no-new-privileges:true".to_string());
+    }
+    if let Some(ref profile) = config.security.seccomp_profile {
+        security_opt.push(format!("seccomp={}", profile));
+    }
+    if let Some(ref profile) = config.security.apparmor_profile {
+        security_opt.push(format!("apparmor={}", profile));
+    }
+
+    HostConfig {
+        port_bindings: Some(port_bindings),
+        network_mode: Some(config.network.clone()),
+        extra_hosts: Some(vec![format!("{}:host-gateway", gateway_hostname)]),
+        cap_drop: Some(config.security.cap_drop.clone()),
+        cap_add: Some(config.security.cap_add.clone()),
+        security_opt: Some(security_opt).filter(|opts| !opts.is_empty()),
+        readonly_rootfs: Some(config.security.read_only_rootfs),
+        tmpfs,
+        binds: Some(vec![]),
+        mounts: Some(mounts),
+        nano_cpus: config.limits.nano_cpus,
+        memory: config.limits.memory_bytes,
+        ..Default::default()
+    }
+}
+
+/// The endpoint an engine's own CLI would use, honouring its environment
+/// variable.
+fn default_host(kind: EngineKind) -> String {
+    match kind {
+        EngineKind::Docker => std::env::var("DOCKER_HOST").unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "npipe:////./pipe/docker_engine".to_string()
+            } else {
+                "unix:///var/run/docker.sock".to_string()
+            }
+        }),
+        EngineKind::Podman => std::env::var("CONTAINER_HOST").unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "npipe:////./pipe/podman-machine-default".to_string()
+            } else if cfg!(target_os = "macos") {
+                let home = std::env::var("HOME").unwrap_or_default();
+                format!("unix://{home}/.local/share/containers/podman/machine/podman.sock")
+            } else {
+                // Rootless socket first, as podman itself prefers
+                match std::env::var("XDG_RUNTIME_DIR") {
+                    Ok(dir) => format!("unix://{dir}/podman/podman.sock"),
+                    Err(_) => "unix:///run/podman/podman.sock".to_string(),
+                }
+            }
+        }),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Trait implementation
 // ---------------------------------------------------------------------------
@@ -51,24 +392,21 @@ fn to_err(e: bollard::errors::Error) -> RuntimeError {
 #[async_trait]
 impl ContainerRuntime for DockerRuntime {
     fn engine_id(&self) -> &str {
-        "docker"
+        match self.kind {
+            EngineKind::Docker => "docker",
+            EngineKind::Podman => "podman",
+        }
     }
 
     fn socket_path(&self) -> String {
-        #[cfg(unix)]
-        {
-            std::env::var("DOCKER_HOST")
-                .unwrap_or_else(|_| "/var/run/docker.sock".to_string())
-        }
-        #[cfg(windows)]
-        {
-            std::env::var("DOCKER_HOST")
-                .unwrap_or_else(|_| r"\\.\pipe\docker_engine".to_string())
-        }
+        self.host.clone()
     }
 
     fn host_gateway_hostname(&self) -> &str {
-        "host.docker.internal"
+        match self.kind {
+            EngineKind::Docker => "host.docker.internal",
+            EngineKind::Podman => "host.containers.internal",
+        }
     }
 
     async fn ping(&self) -> Result<(), RuntimeError> {
@@ -118,70 +456,32 @@ impl ContainerRuntime for DockerRuntime {
     }
 
     async fn pull_image(&self, image: &str) -> Result<(), RuntimeError> {
-        if self.image_exists(image).await? {
-            log::info!("Image {} found locally, skipping pull", image);
-            return Ok(());
-        }
-
-        let (repo, tag) = if let Some((r, t)) = image.rsplit_once(':') {
-            (r, t)
-        } else {
-            (image, "latest")
-        };
-
-        let opts = CreateImageOptions {
-            from_image: Some(repo.to_string()),
-            tag: Some(tag.to_string()),
-            ..Default::default()
-        };
-
-        let mut stream = self.docker.create_image(Some(opts), None, None);
-        while let Some(result) = stream.next().await {
-            match result {
-                Ok(info) => {
-                    if let Some(status) = info.status {
-                        log::debug!("Pull: {}", status);
-                    }
-                }
-                Err(e) => return Err(to_err(e)),
-            }
-        }
+        self.pull(image, &|_| {}).await
+    }
 
-        Ok(())
+    async fn pull_image_with_progress(
+        &self,
+        image: &str,
+        on_progress: &ProgressFn<'_>,
+    ) -> Result<(), RuntimeError> {
+        self.pull(image, on_progress).await
     }
 
     async fn build_image(&self, context_dir: &Path, tag: &str) -> Result<(), RuntimeError> {
-        let tar_bytes = create_build_context(context_dir)?;
-
-        let opts = BuildImageOptions {
-            t: Some(tag.to_string()),
-            rm: true,
-            forcerm: true,
-            ..Default::default()
-        };
+        self.build(context_dir, tag, false, &|_| {}).await
+    }
 
-        let body = bollard::body_full(tar_bytes.into());
-        let mut stream = self.docker.build_image(opts, None, Some(body));
-        while let Some(result) = stream.next().await {
-            match result {
-                Ok(info) => {
-                    if let Some(stream) = info.stream {
-                        let msg = stream.trim();
-                        if !msg.is_empty() {
-                            log::debug!("Build: {}", msg);
-                        }
-                    }
-                    if let Some(detail) = info.error_detail {
-                        let msg = detail.message.unwrap_or_default();
-                        return Err(RuntimeError::Other(format!("Docker build error: {}", msg)));
-                    }
-                }
-                Err(e) => return Err(to_err(e)),
-            }
-        }
+    async fn build_image_with_progress(
+        &self,
+        context_dir: &Path,
+        tag: &str,
+        on_progress: &ProgressFn<'_>,
+    ) -> Result<(), RuntimeError> {
+        self.build(context_dir, tag, false, on_progress).await
+    }
 
-        log::info!("Built image: {}", tag);
-        Ok(())
+    async fn rebuild_image(&self, context_dir: &Path, tag: &str) -> Result<(), RuntimeError> {
+        self.build(context_dir, tag, true, &|_| {}).await
     }
 
     async fn get_image_digest(&self, image: &str) -> Result<Option<String>, RuntimeError> {
@@ -200,6 +500,11 @@ impl ContainerRuntime for DockerRuntime {
         Ok(None)
     }
 
+    async fn get_image_user(&self, image: &str) -> Result<Option<String>, RuntimeError> {
+        let inspect = self.docker.inspect_image(image).await.map_err(to_err)?;
+        Ok(inspect.config.and_then(|c| c.user).filter(|u| !u.is_empty()))
+    }
+
     async fn list_images(&self) -> Result<Vec<super::ImageInfo>, RuntimeError> {
         let images = self
             .docker
@@ -241,51 +546,13 @@ impl ContainerRuntime for DockerRuntime {
     }
 
     async fn create_container(&self, config: ContainerConfig) -> Result<String, RuntimeError> {
-        let port_binding = PortBinding {
-            host_ip: Some("127.0.0.1".to_string()),
-            host_port: Some(config.host_port.to_string()),
-        };
-
-        let container_port_key = format!("{}/tcp", config.container_port);
-
-        let mut port_bindings = HashMap::new();
-        port_bindings.insert(container_port_key.clone(), Some(vec![port_binding]));
-
-        let mounts = match config.data_volume {
-            Some(ref vol_name) => vec![Mount {
-                target: Some("/data".to_string()),
-                source: Some(vol_name.to_string()),
-                typ: Some(MountTypeEnum::VOLUME),
-                read_only: Some(false),
-                ..Default::default()
-            }],
-            None => vec![],
-        };
-
-        let host_config = HostConfig {
-            port_bindings: Some(port_bindings),
-            network_mode: Some(config.network.clone()),
-            extra_hosts: Some(vec![format!("{}:host-gateway", self.host_gateway_hostname())]),
-            cap_drop: Some(config.security.cap_drop.clone()),
-            cap_add: Some(config.security.cap_add.clone()),
-            security_opt: if config.security.no_new_privileges {
-                Some(vec!["no-new-privileges:true".to_string()])
-            } else {
-                None
-            },
-            binds: Some(vec![]),
-            mounts: Some(mounts),
-            nano_cpus: config.limits.nano_cpus,
-            memory: config.limits.memory_bytes,
-            ..Default::default()
-        };
-
         let body = ContainerCreateBody {
             image: Some(config.image.clone()),
+            user: config.user.clone(),
             env: Some(config.env_vars.clone()),
             labels: Some(config.labels.clone()),
-            exposed_ports: Some(vec![container_port_key]),
-            host_config: Some(host_config),
+            exposed_ports: Some(vec![format!("{}/tcp", config.container_port)]),
+            host_config: Some(host_config(&config, self.host_gateway_hostname())),
             ..Default::default()
         };
 
@@ -460,6 +727,35 @@ impl ContainerRuntime for DockerRuntime {
             .map_err(|e| RuntimeError::Other(format!("JSON serialization failed: {e}")))
     }
 
+    async fn container_usage(&self, id: &str) -> Result<ContainerUsage, RuntimeError> {
+        let opts = StatsOptions {
+            stream: false,
+            one_shot: true,
+        };
+        let mut stream = self.docker.stats(id, Some(opts));
+        let stats = stream
+            .next()
+            .await
+            .ok_or_else(|| RuntimeError::NotFound(format!("no stats for container {id}")))?
+            .map_err(to_err)?;
+
+        let memory_bytes = stats.memory_stats.as_ref().and_then(|m| m.usage).unwrap_or(0);
+        let (net_rx_bytes, net_tx_bytes) = stats
+            .networks
+            .iter()
+            .flat_map(|networks| networks.values())
+            .fold((0, 0), |(rx, tx), net| {
+                (rx + net.rx_bytes.unwrap_or(0), tx + net.tx_bytes.unwrap_or(0))
+            });
+
+        Ok(ContainerUsage {
+            cpu_percent: (cpu_percent(&stats) * 10.0).round() / 10.0,
+            memory_mb: (memory_bytes as f64 / 1_048_576.0 * 10.0).round() / 10.0,
+            net_rx_bytes,
+            net_tx_bytes,
+        })
+    }
+
     async fn aggregate_stats(
         &self,
         filters: ContainerFilters,
@@ -481,21 +777,7 @@ impl ContainerRuntime for DockerRuntime {
 
             let mut stream = self.docker.stats(&container.id, Some(opts));
             if let Some(Ok(stats)) = stream.next().await {
-                if let (Some(cpu), Some(precpu)) = (&stats.cpu_stats, &stats.precpu_stats) {
-                    if let (Some(cpu_usage), Some(precpu_usage)) =
-                        (&cpu.cpu_usage, &precpu.cpu_usage)
-                    {
-                        let cpu_delta = cpu_usage.total_usage.unwrap_or(0) as f64
-                            - precpu_usage.total_usage.unwrap_or(0) as f64;
-                        let system_delta = cpu.system_cpu_usage.unwrap_or(0) as f64
-                            - precpu.system_cpu_usage.unwrap_or(0) as f64;
-                        let num_cpus = cpu.online_cpus.unwrap_or(1) as f64;
-
-                        if system_delta > 0.0 {
-                            total_cpu += (cpu_delta / system_delta) * num_cpus * 100.0;
-                        }
-                    }
-                }
+                total_cpu += cpu_percent(&stats);
 
                 if let Some(mem) = &stats.memory_stats {
                     total_memory_bytes += mem.usage.unwrap_or(0);
@@ -509,6 +791,45 @@ impl ContainerRuntime for DockerRuntime {
         })
     }
 
+    async fn volume_sizes(&self) -> Result<HashMap<String, u64>, RuntimeError> {
+        let usage = self
+            .docker
+            .df(None::<DataUsageOptions>)
+            .await
+            .map_err(to_err)?;
+        let items = usage
+            .volumes_disk_usage
+            .and_then(|v| v.items)
+            .unwrap_or_default();
+        Ok(items
+            .iter()
+            .filter_map(|volume| {
+                let name = volume.get("Name")?.as_str()?;
+                // Size is -1 when the engine couldn't compute it
+                let size = volume.get("UsageData")?.get("Size")?.as_i64()?;
+                (size >= 0).then(|| (name.to_string(), size as u64))
+            })
+            .collect())
+    }
+
+    async fn export_volume(&self, volume: &str, image: &str, dest: &Path) -> Result<u64, RuntimeError> {
+        let helper = self.volume_helper(volume, image).await?;
+        let result = self.download_data(&helper, dest).await;
+        if let Err(e) = self.remove_container(&helper).await {
+            log::warn!("Failed to remove volume helper container {}: {}", helper, e);
+        }
+        result
+    }
+
+    async fn import_volume(&self, volume: &str, image: &str, src: &Path) -> Result<(), RuntimeError> {
+        let helper = self.volume_helper(volume, image).await?;
+        let result = self.upload_data(&helper, src).await;
+        if let Err(e) = self.remove_container(&helper).await {
+            log::warn!("Failed to remove volume helper container {}: {}", helper, e);
+        }
+        result
+    }
+
     async fn list_volumes(&self) -> Result<Vec<super::VolumeInfo>, RuntimeError> {
         let response = self
             .docker
@@ -679,7 +1000,7 @@ async fn check_image_available_inner(image: &str) -> Result<bool, Box<dyn std::e
         ),
         other => {
             let base = format!("https://{}", other);
-            let client = reqwest::Client::builder()
+            let client = crate::proxy::apply(reqwest::Client::builder())
                 .timeout(std::time::Duration::from_secs(5))
                 .build()?;
             let resp = client
@@ -694,7 +1015,7 @@ async fn check_image_available_inner(image: &str) -> Result<bool, Box<dyn std::e
         }
     };
 
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::apply(reqwest::Client::builder())
         .timeout(std::time::Duration::from_secs(10))
         .build()?;
 
@@ -887,3 +1208,74 @@ pub async fn wait_for_ready(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{ResourceLimits, SecurityConfig};
+
+    #[test]
+    fn pull_progress_sums_layers() {
+        let mut progress = PullProgress::default();
+        assert_eq!(progress.update("a", "Pulling fs layer", None, None), None);
+
+        let p = progress.update("a", "Downloading", Some(50), Some(100)).unwrap();
+        assert_eq!((p.current, p.total, p.percent()), (50, 100, 50));
+        let p = progress.update("b", "Downloading", Some(0), Some(300)).unwrap();
+        assert_eq!((p.current, p.total), (50, 400));
+
+        // Same percentage again is not reported
+        assert_eq!(progress.update("b", "Downloading", Some(1), Some(300)), None);
+
+        progress.update("a", "Download complete", None, None).unwrap();
+        let p = progress.update("b", "Pull complete", None, None).unwrap();
+        assert_eq!((p.current, p.total, p.percent()), (400, 400, 100));
+    }
+
+    fn container_config(security: SecurityConfig) -> ContainerConfig {
+        ContainerConfig {
+            name: "nexus-com-test-plugin".into(),
+            image: "test:latest".into(),
+            host_port: 9700,
+            container_port: 80,
+            env_vars: vec![],
+            labels: HashMap::new(),
+            limits: ResourceLimits::default(),
+            data_volume: Some("nexus-data-com-test-plugin".into()),
+            network: "nexus-bridge".into(),
+            security,
+            user: None,
+        }
+    }
+
+    #[test]
+    fn read_only_rootfs_gets_tmpfs_scratch_space() {
+        let host = host_config(&container_config(SecurityConfig::default()), "host.docker.internal");
+        assert_eq!(host.readonly_rootfs, Some(true));
+        let tmpfs = host.tmpfs.unwrap();
+        assert_eq!(tmpfs.len(), 2);
+        for path in ["/tmp", "/run"] {
+            assert_eq!(tmpfs[path], "rw,nosuid,nodev,size=64m");
+        }
+        // The data volume stays writable
+        let mounts = host.mounts.unwrap();
+        assert_eq!(mounts[0].target.as_deref(), Some("/data"));
+        assert_eq!(mounts[0].read_only, Some(false));
+
+        let writable = SecurityConfig {
+            read_only_rootfs: false,
+            ..SecurityConfig::default()
+        };
+        let host = host_config(&container_config(writable), "host.docker.internal");
+        assert_eq!(host.readonly_rootfs, Some(false));
+        assert!(host.tmpfs.is_none());
+    }
+
+    #[test]
+    fn parses_build_steps() {
+        let step = build_step("Step 3/7 : RUN npm ci").unwrap();
+        assert_eq!((step.current, step.total, step.unit), (3, 7, ProgressUnit::Steps));
+        assert!(build_step("---> Running in 1a2b3c").is_none());
+        assert!(build_step("Step x/7 : COPY . .").is_none());
+    }
+}
@@ -10,7 +10,7 @@ use std::sync::Mutex;
 
 use super::{
     ContainerConfig, ContainerFilters, ContainerInfo, ContainerRuntime, ContainerState,
-    EngineInfo, ImageInfo, NetworkInfo, ResourceUsage, RuntimeError, VolumeInfo,
+    ContainerUsage, EngineInfo, ImageInfo, NetworkInfo, ResourceUsage, RuntimeError, VolumeInfo,
 };
 
 // ---------------------------------------------------------------------------
@@ -26,6 +26,7 @@ pub enum RuntimeCall {
     PullImage(String),
     BuildImage { context_dir: String, tag: String },
     GetImageDigest(String),
+    GetImageUser(String),
     ListImages,
     InspectImageRaw(String),
     RemoveImage(String),
@@ -38,8 +39,12 @@ pub enum RuntimeCall {
     ListContainers,
     GetLogs { id: String, tail: u32 },
     InspectContainerRaw(String),
+    ContainerUsage(String),   // id
     AggregateStats,
     ListVolumes,
+    VolumeSizes,
+    ExportVolume(String),
+    ImportVolume(String),
     RemoveVolume(String),
     ListNetworks,
     RemoveNetwork(String),
@@ -116,6 +121,12 @@ impl MockRuntime {
         self
     }
 
+    /// Pre-populate with a volume so `export_volume` has something to copy.
+    pub fn with_volume(self, name: &str) -> Self {
+        self.inner.lock().unwrap().volumes.insert(name.to_string(), ());
+        self
+    }
+
     /// Make `pull_image` fail.
     pub fn fail_pull(self) -> Self {
         self.inner.lock().unwrap().fail_pull = true;
@@ -242,6 +253,10 @@ impl ContainerRuntime for MockRuntime {
         Ok(())
     }
 
+    async fn rebuild_image(&self, context_dir: &Path, tag: &str) -> Result<(), RuntimeError> {
+        self.build_image(context_dir, tag).await
+    }
+
     async fn get_image_digest(&self, image: &str) -> Result<Option<String>, RuntimeError> {
         let mut inner = self.inner.lock().unwrap();
         inner
@@ -250,6 +265,12 @@ impl ContainerRuntime for MockRuntime {
         Ok(inner.images.get(image).and_then(|d| d.clone()))
     }
 
+    async fn get_image_user(&self, image: &str) -> Result<Option<String>, RuntimeError> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.calls.push(RuntimeCall::GetImageUser(image.to_string()));
+        Ok(None)
+    }
+
     async fn list_images(&self) -> Result<Vec<ImageInfo>, RuntimeError> {
         let mut inner = self.inner.lock().unwrap();
         inner.calls.push(RuntimeCall::ListImages);
@@ -479,6 +500,21 @@ impl ContainerRuntime for MockRuntime {
         }
     }
 
+    async fn container_usage(&self, id: &str) -> Result<ContainerUsage, RuntimeError> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.calls.push(RuntimeCall::ContainerUsage(id.to_string()));
+        match inner.containers.get(id) {
+            Some(c) if c.running => Ok(ContainerUsage {
+                cpu_percent: 2.0,
+                memory_mb: 50.0,
+                net_rx_bytes: 0,
+                net_tx_bytes: 0,
+            }),
+            Some(_) => Ok(ContainerUsage::default()),
+            None => Err(RuntimeError::NotFound(id.to_string())),
+        }
+    }
+
     async fn aggregate_stats(
         &self,
         _filters: ContainerFilters,
@@ -507,6 +543,36 @@ impl ContainerRuntime for MockRuntime {
             .collect())
     }
 
+    async fn volume_sizes(&self) -> Result<HashMap<String, u64>, RuntimeError> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.calls.push(RuntimeCall::VolumeSizes);
+        Ok(inner
+            .volumes
+            .keys()
+            .map(|name| (name.clone(), 10_000_000))
+            .collect())
+    }
+
+    async fn export_volume(&self, volume: &str, _image: &str, dest: &Path) -> Result<u64, RuntimeError> {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            inner.calls.push(RuntimeCall::ExportVolume(volume.to_string()));
+            if !inner.volumes.contains_key(volume) {
+                return Err(RuntimeError::NotFound(volume.to_string()));
+            }
+        }
+        let archive = b"mock volume archive";
+        std::fs::write(dest, archive)?;
+        Ok(archive.len() as u64)
+    }
+
+    async fn import_volume(&self, volume: &str, _image: &str, _src: &Path) -> Result<(), RuntimeError> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.calls.push(RuntimeCall::ImportVolume(volume.to_string()));
+        inner.volumes.insert(volume.to_string(), ());
+        Ok(())
+    }
+
     async fn remove_volume(&self, name: &str) -> Result<(), RuntimeError> {
         let mut inner = self.inner.lock().unwrap();
         inner
@@ -619,6 +685,7 @@ mod tests {
                 data_volume: None,
                 network: "bridge".into(),
                 security: Default::default(),
+                user: None,
             })
             .await
             .unwrap();
@@ -667,6 +734,7 @@ mod tests {
                 data_volume: None,
                 network: "bridge".into(),
                 security: Default::default(),
+                user: None,
             })
             .await
             .unwrap();
@@ -695,6 +763,7 @@ mod tests {
             data_volume: Some("my-volume".into()),
             network: "bridge".into(),
             security: Default::default(),
+            user: None,
         })
         .await
         .unwrap();
@@ -719,6 +788,7 @@ mod tests {
                 data_volume: None,
                 network: "bridge".into(),
                 security: Default::default(),
+                user: None,
             })
             .await
             .unwrap();
@@ -749,6 +819,7 @@ mod tests {
                 data_volume: None,
                 network: "bridge".into(),
                 security: Default::default(),
+                user: None,
             })
             .await
             .unwrap();
@@ -772,6 +843,7 @@ mod tests {
                 data_volume: None,
                 network: "bridge".into(),
                 security: Default::default(),
+                user: None,
             })
             .await
             .unwrap();
@@ -796,6 +868,7 @@ mod tests {
                 data_volume: None,
                 network: "bridge".into(),
                 security: Default::default(),
+                user: None,
             })
             .await;
         assert!(result.is_err());
@@ -5,7 +5,7 @@ pub mod mock;
 
 use async_trait::async_trait;
 use futures_util::Stream;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 use std::pin::Pin;
@@ -34,6 +34,27 @@ pub enum RuntimeError {
 // Types
 // ---------------------------------------------------------------------------
 
+/// Container engines Nexus can run plugins on. All of them speak the
+/// Docker Engine API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EngineKind {
+    #[default]
+    Docker,
+    Podman,
+}
+
+/// Which engine to connect to, persisted in settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineConfig {
+    #[serde(default)]
+    pub kind: EngineKind,
+    /// Engine endpoint (`unix://`, `npipe://`, or `tcp://`), e.g. the host
+    /// of another Docker context. None uses the engine's default socket.
+    #[serde(default)]
+    pub host: Option<String>,
+}
+
 /// Resource limits applied to plugin containers.
 #[derive(Debug, Clone, Default)]
 pub struct ResourceLimits {
@@ -49,6 +70,13 @@ pub struct SecurityConfig {
     pub cap_drop: Vec<String>,
     pub cap_add: Vec<String>,
     pub no_new_privileges: bool,
+    /// Seccomp profile JSON, or `unconfined`. None keeps the engine default.
+    pub seccomp_profile: Option<String>,
+    /// AppArmor profile name. None keeps the engine default.
+    pub apparmor_profile: Option<String>,
+    /// Mount the image's filesystem read-only, with a tmpfs at `/tmp` and
+    /// `/run`. The data volume stays writable.
+    pub read_only_rootfs: bool,
 }
 
 impl Default for SecurityConfig {
@@ -57,6 +85,9 @@ impl Default for SecurityConfig {
             cap_drop: vec!["ALL".to_string()],
             cap_add: vec!["NET_BIND_SERVICE".to_string()],
             no_new_privileges: true,
+            seccomp_profile: None,
+            apparmor_profile: None,
+            read_only_rootfs: true,
         }
     }
 }
@@ -74,6 +105,8 @@ pub struct ContainerConfig {
     pub data_volume: Option<String>,
     pub network: String,
     pub security: SecurityConfig,
+    /// `user[:group]` to run as in place of the image's user.
+    pub user: Option<String>,
 }
 
 /// High-level container state.
@@ -107,6 +140,46 @@ pub struct ResourceUsage {
     pub memory_mb: f64,
 }
 
+/// Point-in-time resource usage of a single container. Network counters are
+/// cumulative since the container started.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContainerUsage {
+    pub cpu_percent: f64,
+    pub memory_mb: f64,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+}
+
+/// What a [`Progress`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressUnit {
+    /// Image layer bytes downloaded.
+    Bytes,
+    /// Dockerfile steps completed.
+    Steps,
+}
+
+/// How far an image pull or build has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Progress {
+    pub current: u64,
+    pub total: u64,
+    pub unit: ProgressUnit,
+}
+
+impl Progress {
+    pub fn percent(&self) -> u8 {
+        if self.total == 0 {
+            return 0;
+        }
+        (self.current.min(self.total) * 100 / self.total) as u8
+    }
+}
+
+/// Receives progress updates from image pulls and builds.
+pub type ProgressFn<'a> = dyn Fn(Progress) + Send + Sync + 'a;
+
 /// Lightweight image info returned by list operations.
 #[derive(Debug, Clone, Serialize)]
 pub struct ImageInfo {
@@ -194,7 +267,28 @@ pub trait ContainerRuntime: Send + Sync {
     async fn image_exists(&self, image: &str) -> Result<bool, RuntimeError>;
     async fn pull_image(&self, image: &str) -> Result<(), RuntimeError>;
     async fn build_image(&self, context_dir: &Path, tag: &str) -> Result<(), RuntimeError>;
+    /// Like `pull_image`, reporting layer bytes downloaded as they arrive.
+    async fn pull_image_with_progress(
+        &self,
+        image: &str,
+        _on_progress: &ProgressFn<'_>,
+    ) -> Result<(), RuntimeError> {
+        self.pull_image(image).await
+    }
+    /// Like `build_image`, reporting each Dockerfile step as it starts.
+    async fn build_image_with_progress(
+        &self,
+        context_dir: &Path,
+        tag: &str,
+        _on_progress: &ProgressFn<'_>,
+    ) -> Result<(), RuntimeError> {
+        self.build_image(context_dir, tag).await
+    }
+    /// Like `build_image`, but bypasses the layer cache and re-pulls base images.
+    async fn rebuild_image(&self, context_dir: &Path, tag: &str) -> Result<(), RuntimeError>;
     async fn get_image_digest(&self, image: &str) -> Result<Option<String>, RuntimeError>;
+    /// The user the image's config runs as. None or empty means root.
+    async fn get_image_user(&self, image: &str) -> Result<Option<String>, RuntimeError>;
     async fn remove_image(&self, image: &str) -> Result<(), RuntimeError>;
     async fn list_images(&self) -> Result<Vec<ImageInfo>, RuntimeError>;
     async fn inspect_image_raw(&self, id: &str) -> Result<serde_json::Value, RuntimeError>;
@@ -221,6 +315,7 @@ pub trait ContainerRuntime: Send + Sync {
         &self,
         id: &str,
     ) -> Result<serde_json::Value, RuntimeError>;
+    async fn container_usage(&self, id: &str) -> Result<ContainerUsage, RuntimeError>;
     async fn aggregate_stats(
         &self,
         filters: ContainerFilters,
@@ -228,6 +323,16 @@ pub trait ContainerRuntime: Send + Sync {
 
     // Volumes
     async fn list_volumes(&self) -> Result<Vec<VolumeInfo>, RuntimeError>;
+    /// Disk space used by each volume, in bytes, keyed by name. Volumes the
+    /// engine hasn't measured are left out.
+    async fn volume_sizes(&self) -> Result<HashMap<String, u64>, RuntimeError>;
+    /// Write the contents of `volume` to `dest` as a tar archive with
+    /// entries under `data/`, copied through a stopped container of `image`.
+    /// Returns the archive size in bytes.
+    async fn export_volume(&self, volume: &str, image: &str, dest: &Path) -> Result<u64, RuntimeError>;
+    /// Copy an archive written by `export_volume` into `volume`, creating
+    /// the volume if needed.
+    async fn import_volume(&self, volume: &str, image: &str, src: &Path) -> Result<(), RuntimeError>;
     async fn remove_volume(&self, name: &str) -> Result<(), RuntimeError>;
 
     // Networks
@@ -0,0 +1,667 @@
+//! Credentials Nexus keeps for the user: registry tokens and the secrets
+//! plugins are configured with at install time.
+//!
+//! They live in the OS keychain by default (Keychain on macOS, Credential
+//! Manager on Windows, the Secret Service on Linux). Users who prefer it can
+//! keep them in owner-only JSON files instead ([`CredentialStorage::File`]).
+//!
+//! Each store is a [`SecretMap`] backed by one file. In keychain mode the
+//! file only lists the keys, and each value is its own keychain item, so
+//! the file says where the secrets are: a map written by an older release
+//! is read as plaintext and moved into the keychain on the next
+//! [`SecretMap::set_storage`]. Item names start with a hash of the data
+//! directory, so separate installs (a dev build, a second data directory)
+//! never share or overwrite each other's items. Tests use an in-memory
+//! keychain instead of the real one.
+//!
+//! Stores too large or too busy for keychain items (OAuth clients and
+//! tokens, API keys) are encrypted at rest by a [`StoreCipher`] instead,
+//! whose key is kept like any other credential.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{NexusError, NexusResult};
+
+/// Keychain service the items are stored under.
+const SERVICE: &str = "com.nexus-dashboard.desktop";
+
+/// Where credentials are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialStorage {
+    #[default]
+    Keychain,
+    /// Plaintext JSON files in the data directory, readable only by the user.
+    File,
+}
+
+/// On-disk form of a [`SecretMap`].
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Stored<T> {
+    /// Values are keychain items; only the keys are written here.
+    Keychain {
+        keychain: Vec<String>,
+        /// Namespace the items were written under, kept so they're still
+        /// found after the data directory moves.
+        #[serde(default)]
+        namespace: Option<String>,
+    },
+    File(HashMap<String, T>),
+}
+
+fn keychain_error(e: keyring::Error) -> NexusError {
+    NexusError::Other(format!("Keychain error: {}", e))
+}
+
+#[cfg(not(test))]
+fn entry(account: &str) -> NexusResult<keyring::Entry> {
+    keyring::Entry::new(SERVICE, account).map_err(keychain_error)
+}
+
+#[cfg(test)]
+fn entry(account: &str) -> NexusResult<keyring::Entry> {
+    Ok(keyring::Entry::new_with_credential(Box::new(
+        test_keychain::Item(format!("{SERVICE}/{account}")),
+    )))
+}
+
+/// Keychain namespace for the data directory holding `path`.
+fn namespace(path: &Path) -> String {
+    let dir = path.parent().unwrap_or(path);
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let digest = Sha256::digest(dir.to_string_lossy().as_bytes());
+    format!("{:x}", digest)[..16].to_string()
+}
+
+/// Credentials keyed by id (a registry, a plugin), persisted as a whole on
+/// every change.
+#[derive(Debug)]
+pub struct SecretMap<T> {
+    path: PathBuf,
+    /// Prefix of the map's keychain accounts.
+    namespace: String,
+    storage: CredentialStorage,
+    entries: HashMap<String, T>,
+    /// Keys with a keychain item, to remove the ones no longer in `entries`.
+    in_keychain: Vec<String>,
+    /// Listed keys whose keychain item couldn't be read.
+    unreadable: Vec<String>,
+}
+
+impl<T> Default for SecretMap<T> {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::new(),
+            namespace: String::new(),
+            storage: CredentialStorage::File,
+            entries: HashMap::new(),
+            in_keychain: Vec::new(),
+            unreadable: Vec::new(),
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> SecretMap<T> {
+    /// Read the map from `path` and the keychain, wherever the file says the
+    /// values are. A missing or unreadable file is an empty map.
+    pub fn load(path: PathBuf) -> Self {
+        let stored = crate::util::read_json_store(&path)
+            .unwrap_or_else(|| Stored::File(HashMap::new()));
+        let mut map = Self::new(path);
+        match stored {
+            Stored::File(entries) => map.entries = entries,
+            Stored::Keychain { keychain, namespace } => {
+                map.storage = CredentialStorage::Keychain;
+                if let Some(namespace) = namespace {
+                    map.namespace = namespace;
+                }
+                for key in keychain {
+                    // An item that can't be read is left alone, not deleted
+                    match map.read_item(&key) {
+                        Ok(value) => {
+                            map.entries.insert(key.clone(), value);
+                            map.in_keychain.push(key);
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to read '{}' from the keychain: {}", key, e);
+                            map.unreadable.push(key);
+                        }
+                    }
+                }
+            }
+        }
+        map
+    }
+
+    /// Start from `entries`, e.g. ones read from an older file format.
+    pub fn from_entries(path: PathBuf, entries: HashMap<String, T>) -> Self {
+        Self { entries, ..Self::new(path) }
+    }
+
+    fn new(path: PathBuf) -> Self {
+        Self {
+            namespace: namespace(&path),
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Move the credentials to `storage`. The new copy is written before the
+    /// old one is removed, so an interrupted move loses nothing.
+    pub fn set_storage(&mut self, storage: CredentialStorage) -> NexusResult<()> {
+        if storage == self.storage {
+            return Ok(());
+        }
+        let previous = std::mem::replace(&mut self.storage, storage);
+        if let Err(e) = self.save() {
+            self.storage = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&T> {
+        self.entries.get(key)
+    }
+
+    /// Whether `key` is stored but its keychain item couldn't be read, e.g.
+    /// because the keychain is locked.
+    pub fn is_unreadable(&self, key: &str) -> bool {
+        self.unreadable.iter().any(|k| k == key)
+    }
+
+    pub fn insert(&mut self, key: String, value: T) -> NexusResult<()> {
+        self.entries.insert(key, value);
+        self.save()
+    }
+
+    /// Remove `key`, saving only when it was there.
+    pub fn remove(&mut self, key: &str) -> NexusResult<bool> {
+        if self.entries.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    pub fn save(&mut self) -> NexusResult<()> {
+        let stored = match self.storage {
+            CredentialStorage::Keychain => {
+                let mut keys: Vec<String> = self.entries.keys().cloned().collect();
+                keys.sort();
+                for key in &keys {
+                    let value = serde_json::to_string(&self.entries[key])?;
+                    entry(&self.account(key))?
+                        .set_password(&value)
+                        .map_err(keychain_error)?;
+                }
+                Stored::Keychain {
+                    keychain: keys,
+                    namespace: Some(self.namespace.clone()),
+                }
+            }
+            CredentialStorage::File => Stored::File(
+                self.entries
+                    .iter()
+                    .map(|(k, v)| Ok((k.clone(), serde_json::to_value(v)?)))
+                    .collect::<NexusResult<HashMap<String, serde_json::Value>>>()?,
+            ),
+        };
+        let data = serde_json::to_string_pretty(&stored)?;
+        let backup = crate::util::backup_path(&self.path);
+        match self.storage {
+            // The file being replaced may still hold the values, so it
+            // mustn't be kept once they've moved to the keychain
+            CredentialStorage::Keychain => {
+                crate::util::atomic_write(&self.path, data.as_bytes())?;
+                crate::util::atomic_write(&backup, data.as_bytes())?;
+            }
+            CredentialStorage::File => {
+                crate::util::atomic_write_with_backup(&self.path, data.as_bytes())?
+            }
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            for path in [&self.path, &backup] {
+                if path.exists() {
+                    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+                }
+            }
+        }
+
+        // Only now that the file no longer lists them
+        let stale: Vec<String> = match self.storage {
+            CredentialStorage::Keychain => self
+                .in_keychain
+                .iter()
+                .filter(|k| !self.entries.contains_key(*k))
+                .cloned()
+                .collect(),
+            CredentialStorage::File => std::mem::take(&mut self.in_keychain),
+        };
+        for key in &stale {
+            if let Err(e) = self.delete_item(key) {
+                log::warn!("Failed to remove '{}' from the keychain: {}", key, e);
+            }
+        }
+        if self.storage == CredentialStorage::Keychain {
+            self.in_keychain = self.entries.keys().cloned().collect();
+        }
+        Ok(())
+    }
+
+    /// Keychain account of `key`: the data directory's namespace, the
+    /// store's file name and the key, so neither installs nor stores
+    /// collide.
+    fn account(&self, key: &str) -> String {
+        let store = self
+            .path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("{}/{}/{}", self.namespace, store, key)
+    }
+
+    fn read_item(&self, key: &str) -> NexusResult<T> {
+        let value = entry(&self.account(key))?
+            .get_password()
+            .map_err(keychain_error)?;
+        Ok(serde_json::from_str(&value)?)
+    }
+
+    fn delete_item(&self, key: &str) -> NexusResult<()> {
+        match entry(&self.account(key))?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Encryption at rest
+// ---------------------------------------------------------------------------
+
+/// Holds the [`StoreCipher`] key.
+pub(crate) const KEY_FILE: &str = "encryption_key.json";
+const KEY_NAME: &str = "stores";
+/// A key restored from a backup, waiting for the restore to be applied.
+const RESTORED_KEY_NAME: &str = "restored";
+/// Authenticated along with a [`WrappedKey`].
+const WRAPPED_KEY_AAD: &[u8] = b"nexus-store-key";
+
+/// On-disk form of an encrypted store file.
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    /// Format version.
+    nexus_sealed: u32,
+    nonce: String,
+    data: String,
+}
+
+/// Encrypts store files with AES-256-GCM. The file name is authenticated
+/// along with the contents, so sealed files can't be swapped for each other.
+///
+/// When the key can't be read, encrypted files are left untouched and
+/// nothing is written, so a locked keychain costs a session's changes
+/// rather than the stored data.
+pub struct StoreCipher {
+    cipher: Option<Aes256Gcm>,
+}
+
+impl std::fmt::Debug for StoreCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoreCipher")
+            .field("available", &self.cipher.is_some())
+            .finish()
+    }
+}
+
+impl StoreCipher {
+    /// Read the key, generating it on first launch straight into the
+    /// configured credential storage. If that's the keychain and it can't be
+    /// reached, the key goes to its file instead, like other credentials
+    /// that can't be moved there.
+    pub fn load(data_dir: &Path) -> Self {
+        let mut keys: SecretMap<String> = SecretMap::load(data_dir.join(KEY_FILE));
+        if keys.is_unreadable(KEY_NAME) {
+            log::error!("Store encryption key is unavailable; changes won't be saved this session");
+            return Self { cipher: None };
+        }
+        let existing = keys
+            .get(KEY_NAME)
+            .and_then(|k| STANDARD.decode(k).ok())
+            .and_then(|k| Aes256Gcm::new_from_slice(&k).ok());
+        if let Some(cipher) = existing {
+            return Self { cipher: Some(cipher) };
+        }
+        let key: [u8; 32] = rand::random();
+        let storage = crate::plugin_manager::storage::NexusSettings::load(data_dir)
+            .map(|s| s.credential_storage)
+            .unwrap_or_default();
+        let mut saved = keys
+            .set_storage(storage)
+            .and_then(|()| keys.insert(KEY_NAME.to_string(), STANDARD.encode(key)));
+        if let (Err(e), CredentialStorage::Keychain) = (&saved, storage) {
+            log::warn!("Keeping the store encryption key in a file: {}", e);
+            keys.entries.insert(KEY_NAME.to_string(), STANDARD.encode(key));
+            saved = keys.set_storage(CredentialStorage::File);
+        }
+        if let Err(e) = saved {
+            log::error!("Failed to save the store encryption key: {}", e);
+            return Self { cipher: None };
+        }
+        Self {
+            cipher: Aes256Gcm::new_from_slice(&key).ok(),
+        }
+    }
+
+    /// The decrypted contents of `path`. A plaintext file written before
+    /// stores were encrypted is returned as is and encrypted in place. One
+    /// that can't be decrypted is moved aside to `<name>.undecryptable`, and
+    /// its backup used if that decrypts.
+    pub fn read(&self, path: &Path) -> Option<String> {
+        let Some(cipher) = self.cipher.as_ref() else {
+            let contents = std::fs::read_to_string(path).ok()?;
+            return serde_json::from_str::<Sealed>(&contents)
+                .is_err()
+                .then_some(contents);
+        };
+        let (contents, sealed) =
+            crate::util::read_store(path, "undecryptable", |s| open(cipher, path, s))?;
+        // Without a backup: it would keep the plaintext around
+        if !sealed {
+            if let Err(e) = self.seal(path, contents.as_bytes(), false) {
+                log::warn!("Failed to encrypt {}: {}", path.display(), e);
+            }
+        }
+        Some(contents)
+    }
+
+    /// Encrypt `data` and write it to `path` atomically, keeping the
+    /// previous version as its backup.
+    pub fn write(&self, path: &Path, data: &[u8]) -> std::io::Result<()> {
+        self.seal(path, data, true)
+    }
+
+    fn seal(&self, path: &Path, data: &[u8], keep_backup: bool) -> std::io::Result<()> {
+        let cipher = self
+            .cipher
+            .as_ref()
+            .ok_or_else(|| std::io::Error::other("store encryption key is unavailable"))?;
+        let nonce: [u8; 12] = rand::random();
+        let aad = file_name(path);
+        let payload = Payload { msg: data, aad: aad.as_bytes() };
+        let encrypted = cipher
+            .encrypt(Nonce::from_slice(&nonce), payload)
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        let sealed = Sealed {
+            nexus_sealed: 1,
+            nonce: STANDARD.encode(nonce),
+            data: STANDARD.encode(encrypted),
+        };
+        let json = serde_json::to_string(&sealed)?;
+        if keep_backup {
+            crate::util::atomic_write_with_backup(path, json.as_bytes())
+        } else {
+            crate::util::atomic_write(path, json.as_bytes())
+        }
+    }
+}
+
+/// The plaintext of a store file, and whether it was encrypted.
+fn open(cipher: &Aes256Gcm, path: &Path, contents: &str) -> Result<(String, bool), String> {
+    let Ok(sealed) = serde_json::from_str::<Sealed>(contents) else {
+        return Ok((contents.to_string(), false));
+    };
+    let aad = file_name(path);
+    STANDARD
+        .decode(&sealed.nonce)
+        .ok()
+        .filter(|nonce| nonce.len() == 12)
+        .zip(STANDARD.decode(&sealed.data).ok())
+        .and_then(|(nonce, data)| {
+            let payload = Payload { msg: &data, aad: aad.as_bytes() };
+            cipher.decrypt(Nonce::from_slice(&nonce), payload).ok()
+        })
+        .and_then(|data| String::from_utf8(data).ok())
+        .map(|plaintext| (plaintext, true))
+        .ok_or_else(|| "can't be decrypted with this install's key".to_string())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// The [`StoreCipher`] key encrypted with a passphrase, so a backup can
+/// carry it. The passphrase is stretched with Argon2id.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    salt: String,
+    nonce: String,
+    data: String,
+}
+
+/// A [`WrappedKey`] opened with its passphrase, to stage for a restore.
+pub struct RestoredKey(String);
+
+impl WrappedKey {
+    /// Open the key with `passphrase`.
+    pub fn open(&self, passphrase: &str) -> NexusResult<RestoredKey> {
+        let wrong = || NexusError::Other("Wrong passphrase for the backup's encryption key".into());
+        let salt = STANDARD.decode(&self.salt).map_err(|_| wrong())?;
+        let nonce = STANDARD
+            .decode(&self.nonce)
+            .ok()
+            .filter(|nonce| nonce.len() == 12)
+            .ok_or_else(wrong)?;
+        let data = STANDARD.decode(&self.data).map_err(|_| wrong())?;
+        let key = passphrase_cipher(passphrase, &salt)?
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &data, aad: WRAPPED_KEY_AAD })
+            .map_err(|_| wrong())?;
+        Ok(RestoredKey(STANDARD.encode(key)))
+    }
+}
+
+fn passphrase_cipher(passphrase: &str, salt: &[u8]) -> NexusResult<Aes256Gcm> {
+    let mut key = [0_u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| NexusError::Other(format!("Failed to derive a key from the passphrase: {e}")))?;
+    Aes256Gcm::new_from_slice(&key).map_err(|e| NexusError::Other(e.to_string()))
+}
+
+/// The [`StoreCipher`] key of `data_dir`, encrypted with `passphrase`.
+pub fn export_key(data_dir: &Path, passphrase: &str) -> NexusResult<WrappedKey> {
+    let keys: SecretMap<String> = SecretMap::load(data_dir.join(KEY_FILE));
+    let key = keys
+        .get(KEY_NAME)
+        .and_then(|k| STANDARD.decode(k).ok())
+        .ok_or_else(|| NexusError::Other("The store encryption key couldn't be read".into()))?;
+    let salt: [u8; 16] = rand::random();
+    let nonce: [u8; 12] = rand::random();
+    let data = passphrase_cipher(passphrase, &salt)?
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: &key, aad: WRAPPED_KEY_AAD })
+        .map_err(|e| NexusError::Other(e.to_string()))?;
+    Ok(WrappedKey {
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        data: STANDARD.encode(data),
+    })
+}
+
+/// Keep `key` alongside the current key until [`apply_restored_key`], in
+/// the same storage. `None` drops a key staged earlier.
+pub fn stage_restored_key(data_dir: &Path, key: Option<RestoredKey>) -> NexusResult<()> {
+    let mut keys: SecretMap<String> = SecretMap::load(data_dir.join(KEY_FILE));
+    match key {
+        Some(RestoredKey(key)) => keys.insert(RESTORED_KEY_NAME.to_string(), key),
+        None => keys.remove(RESTORED_KEY_NAME).map(|_| ()),
+    }
+}
+
+/// Replace the [`StoreCipher`] key with one staged by
+/// [`stage_restored_key`], if there is one. Returns whether there was.
+pub fn apply_restored_key(data_dir: &Path) -> NexusResult<bool> {
+    let mut keys: SecretMap<String> = SecretMap::load(data_dir.join(KEY_FILE));
+    if keys.is_unreadable(RESTORED_KEY_NAME) {
+        return Err(NexusError::Other("The restored encryption key couldn't be read".into()));
+    }
+    let Some(key) = keys.entries.remove(RESTORED_KEY_NAME) else {
+        return Ok(false);
+    };
+    keys.insert(KEY_NAME.to_string(), key)?;
+    Ok(true)
+}
+
+/// Move the [`StoreCipher`] key to the keychain or to its file.
+pub fn set_key_storage(data_dir: &Path, storage: CredentialStorage) -> NexusResult<()> {
+    let mut keys: SecretMap<String> = SecretMap::load(data_dir.join(KEY_FILE));
+    if keys.is_unreadable(KEY_NAME) {
+        return Err(NexusError::Other("The store encryption key couldn't be read".into()));
+    }
+    keys.set_storage(storage)
+}
+
+/// In-memory keychain for tests, so they never touch the user's items.
+#[cfg(test)]
+mod test_keychain {
+    use std::collections::HashMap;
+    use std::sync::{Mutex, OnceLock};
+
+    use keyring::credential::CredentialApi;
+
+    fn items() -> &'static Mutex<HashMap<String, Vec<u8>>> {
+        static ITEMS: OnceLock<Mutex<HashMap<String, Vec<u8>>>> = OnceLock::new();
+        ITEMS.get_or_init(Default::default)
+    }
+
+    /// The item of one service and account.
+    #[derive(Debug)]
+    pub struct Item(pub String);
+
+    impl CredentialApi for Item {
+        fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+            items().lock().unwrap().insert(self.0.clone(), secret.to_vec());
+            Ok(())
+        }
+
+        fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+            items().lock().unwrap().get(&self.0).cloned().ok_or(keyring::Error::NoEntry)
+        }
+
+        fn delete_credential(&self) -> keyring::Result<()> {
+            items().lock().unwrap().remove(&self.0).map(|_| ()).ok_or(keyring::Error::NoEntry)
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_storage_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens.json");
+        let mut map: SecretMap<String> = SecretMap::load(path.clone());
+        assert_eq!(map.storage, CredentialStorage::File);
+        map.insert("a".into(), "one".into()).unwrap();
+        map.insert("b".into(), "two".into()).unwrap();
+        assert!(map.remove("a").unwrap());
+        assert!(!map.remove("a").unwrap());
+
+        let map: SecretMap<String> = SecretMap::load(path);
+        assert_eq!(map.get("b").map(String::as_str), Some("two"));
+        assert!(map.get("a").is_none());
+    }
+
+    #[test]
+    fn keychain_items_are_namespaced_by_data_dir() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        for (dir, value) in [(&first, "one"), (&second, "two")] {
+            let mut map: SecretMap<String> = SecretMap::load(dir.path().join("tokens.json"));
+            map.set_storage(CredentialStorage::Keychain).unwrap();
+            map.insert("registry".into(), value.into()).unwrap();
+            let on_disk = std::fs::read_to_string(dir.path().join("tokens.json")).unwrap();
+            assert!(!on_disk.contains(value));
+        }
+
+        let map: SecretMap<String> = SecretMap::load(first.path().join("tokens.json"));
+        assert_eq!(map.get("registry").map(String::as_str), Some("one"));
+
+        // Moving the data directory keeps the items it recorded
+        let moved = tempfile::tempdir().unwrap();
+        std::fs::copy(second.path().join("tokens.json"), moved.path().join("tokens.json")).unwrap();
+        let map: SecretMap<String> = SecretMap::load(moved.path().join("tokens.json"));
+        assert_eq!(map.get("registry").map(String::as_str), Some("two"));
+    }
+
+    #[test]
+    fn sealed_files_round_trip_and_legacy_files_are_encrypted_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("oauth_clients.json");
+        std::fs::write(&path, r#"{"client": "secret"}"#).unwrap();
+
+        let cipher = StoreCipher::load(dir.path());
+        assert_eq!(cipher.read(&path).as_deref(), Some(r#"{"client": "secret"}"#));
+        let on_disk = std::fs::read_to_string(&path).unwrap();
+        assert!(on_disk.contains("nexus_sealed"));
+        assert!(!on_disk.contains("secret"));
+
+        // Same key on the next launch
+        let cipher = StoreCipher::load(dir.path());
+        assert_eq!(cipher.read(&path).as_deref(), Some(r#"{"client": "secret"}"#));
+
+        // Bound to its file name
+        let swapped = dir.path().join("oauth_refresh.json");
+        std::fs::copy(&path, &swapped).unwrap();
+        assert_eq!(cipher.read(&swapped), None);
+        assert!(dir.path().join("oauth_refresh.json.undecryptable").exists());
+    }
+
+    #[test]
+    fn new_key_goes_straight_to_the_keychain() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api_keys.json");
+        let cipher = StoreCipher::load(dir.path());
+        cipher.write(&path, b"{}").unwrap();
+
+        let key_file = dir.path().join(KEY_FILE);
+        for file in [crate::util::backup_path(&key_file), key_file] {
+            let on_disk = std::fs::read_to_string(file).unwrap();
+            let stored: Stored<String> = serde_json::from_str(&on_disk).unwrap();
+            assert!(matches!(stored, Stored::Keychain { keychain, .. } if keychain == [KEY_NAME]));
+        }
+        assert_eq!(StoreCipher::load(dir.path()).read(&path).as_deref(), Some("{}"));
+    }
+
+    #[test]
+    fn keychain_index_is_told_apart_from_a_plaintext_map() {
+        let index: Stored<HashMap<String, String>> =
+            serde_json::from_str(r#"{"keychain": ["com.example.plugin"]}"#).unwrap();
+        assert!(matches!(index, Stored::Keychain { keychain, .. } if keychain == ["com.example.plugin"]));
+
+        // A plugin that happens to be called "keychain" is still plaintext
+        let legacy: Stored<HashMap<String, String>> =
+            serde_json::from_str(r#"{"keychain": {"API_KEY": "x"}}"#).unwrap();
+        assert!(matches!(legacy, Stored::File(entries) if entries["keychain"]["API_KEY"] == "x"));
+    }
+}
@@ -0,0 +1,79 @@
+//! Global keyboard shortcut that shows or hides the main window, so Nexus
+//! can be brought up from the tray without reaching for the mouse.
+//!
+//! The accelerator is kept in `NexusSettings::global_shortcut` and uses
+//! Tauri's syntax, e.g. `CmdOrCtrl+Alt+N`. It's registered at startup and
+//! again whenever the setting changes.
+
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+
+/// Shortcut on new installs.
+pub const DEFAULT: &str = "CmdOrCtrl+Alt+N";
+
+/// Parse an accelerator. It needs a modifier other than Shift, so typing
+/// never triggers it system-wide.
+pub fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    let shortcut: Shortcut = accelerator
+        .parse()
+        .map_err(|e| format!("Invalid shortcut \"{accelerator}\": {e}"))?;
+    let required = Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER | Modifiers::META;
+    if !shortcut.mods.intersects(required) {
+        return Err(format!(
+            "Shortcut \"{accelerator}\" needs Ctrl, Alt, or Cmd so it doesn't catch normal typing"
+        ));
+    }
+    Ok(shortcut)
+}
+
+/// The global shortcut plugin, toggling the main window on key press.
+pub fn plugin() -> TauriPlugin<tauri::Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                crate::toggle_window(app);
+            }
+        })
+        .build()
+}
+
+/// Replace the registered shortcut with `accelerator`, or with none.
+pub fn apply<R: Runtime>(app: &AppHandle<R>, accelerator: Option<&str>) -> Result<(), String> {
+    let shortcuts = app.global_shortcut();
+    shortcuts.unregister_all().map_err(|e| e.to_string())?;
+    if let Some(accelerator) = accelerator {
+        shortcuts
+            .register(parse(accelerator)?)
+            .map_err(|e| format!("Couldn't register {accelerator}: {e}"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tauri_plugin_global_shortcut::Code;
+
+    #[test]
+    fn parses_accelerators_with_a_modifier() {
+        let shortcut = parse("Ctrl+Alt+N").unwrap();
+        assert_eq!(shortcut.mods, Modifiers::CONTROL | Modifiers::ALT);
+        assert_eq!(shortcut.key, Code::KeyN);
+        assert!(parse(DEFAULT).is_ok());
+        assert!(parse("Super+Space").is_ok());
+    }
+
+    #[test]
+    fn rejects_shortcuts_that_catch_typing() {
+        assert!(parse("N").unwrap_err().contains("needs Ctrl, Alt, or Cmd"));
+        assert!(parse("Shift+N").unwrap_err().contains("needs Ctrl, Alt, or Cmd"));
+    }
+
+    #[test]
+    fn rejects_malformed_accelerators() {
+        assert!(parse("Ctrl+Alt+Nope").unwrap_err().starts_with("Invalid shortcut"));
+        assert!(parse("Ctrl++N").is_err());
+        assert!(parse("Ctrl+N+M").is_err());
+    }
+}
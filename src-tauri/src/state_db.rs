@@ -0,0 +1,319 @@
+//! SQLite store for state that used to be whole-file JSON: installed
+//! plugins, permissions, and settings.
+//!
+//! Stores keep their data in memory as before and save it here as JSON
+//! records in named collections, one per plugin, grant list, or setting. A
+//! save runs in one transaction and only writes records that changed, so a
+//! crash mid-save leaves the last committed state and a large store doesn't
+//! rewrite everything for a one-field change.
+//!
+//! Schema changes are appended to [`MIGRATIONS`] and applied in order on
+//! open, tracked with `PRAGMA user_version`. A store's legacy JSON file is
+//! imported the first time it's loaded, then renamed with
+//! [`IMPORTED_SUFFIX`] so it isn't imported again.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use rusqlite::{params, Connection, TransactionBehavior};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{NexusError, NexusResult};
+
+pub const DB_FILE: &str = "state.db";
+
+/// Appended to a legacy JSON file once its contents are in the database.
+pub const IMPORTED_SUFFIX: &str = ".imported";
+
+/// Schema migrations, applied in order. Never edit one that has shipped;
+/// append a new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: keyed JSON records
+    "CREATE TABLE records (
+        collection TEXT NOT NULL,
+        key        TEXT NOT NULL,
+        value      TEXT NOT NULL,
+        updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+        PRIMARY KEY (collection, key)
+    ) WITHOUT ROWID;",
+];
+
+/// Records of one collection, by key, as JSON.
+pub type Records = BTreeMap<String, String>;
+
+/// Handle on `state.db`. Clones share one connection.
+#[derive(Clone)]
+pub struct StateDb {
+    db: Arc<Mutex<Connection>>,
+    path: PathBuf,
+}
+
+impl std::fmt::Debug for StateDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateDb").field("path", &self.path).finish()
+    }
+}
+
+impl StateDb {
+    /// Open (or create) the state database in `data_dir`, migrating it to
+    /// the current schema.
+    pub fn open(data_dir: &Path) -> NexusResult<Self> {
+        let path = data_dir.join(DB_FILE);
+        let mut conn = Connection::open(&path)?;
+        conn.busy_timeout(Duration::from_secs(5))?;
+        conn.execute_batch("PRAGMA journal_mode=WAL;")?;
+        migrate(&mut conn)?;
+        Ok(Self {
+            db: Arc::new(Mutex::new(conn)),
+            path,
+        })
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.db.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Every record in `collection`.
+    pub fn load(&self, collection: &str) -> NexusResult<Records> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT key, value FROM records WHERE collection = ?1")?;
+        let rows = stmt.query_map(params![collection], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Make each collection hold exactly the given records, in one
+    /// transaction. Unchanged records aren't written.
+    pub fn save(&self, collections: &[(&str, Records)]) -> NexusResult<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        for (collection, records) in collections {
+            let stored: Records = {
+                let mut stmt =
+                    tx.prepare("SELECT key, value FROM records WHERE collection = ?1")?;
+                let rows =
+                    stmt.query_map(params![collection], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                rows.collect::<Result<_, _>>()?
+            };
+            for key in stored.keys().filter(|k| !records.contains_key(*k)) {
+                tx.execute(
+                    "DELETE FROM records WHERE collection = ?1 AND key = ?2",
+                    params![collection, key],
+                )?;
+            }
+            for (key, value) in records {
+                if stored.get(key) == Some(value) {
+                    continue;
+                }
+                tx.execute(
+                    "INSERT INTO records (collection, key, value) VALUES (?1, ?2, ?3)
+                     ON CONFLICT (collection, key) DO UPDATE SET
+                        value = excluded.value,
+                        updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+                    params![collection, key, value],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Apply `f` to every stored value, writing back the ones it changed.
+    /// Returns how many changed.
+    pub fn rewrite(&self, mut f: impl FnMut(&mut serde_json::Value) -> bool) -> NexusResult<usize> {
+        let mut conn = self.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let rows: Vec<(String, String, String)> = {
+            let mut stmt = tx.prepare("SELECT collection, key, value FROM records")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let mut changed = 0;
+        for (collection, key, value) in rows {
+            let mut value: serde_json::Value = serde_json::from_str(&value)?;
+            if f(&mut value) {
+                tx.execute(
+                    "UPDATE records SET value = ?3 WHERE collection = ?1 AND key = ?2",
+                    params![collection, key, serde_json::to_string(&value)?],
+                )?;
+                changed += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Write a consistent copy of the database to `dest`, which must not
+    /// exist. Safe while the database is in use.
+    pub fn snapshot(&self, dest: &Path) -> NexusResult<()> {
+        let dest = dest
+            .to_str()
+            .ok_or_else(|| NexusError::Other(format!("Unusable snapshot path {}", dest.display())))?;
+        self.conn().execute("VACUUM INTO ?1", params![dest])?;
+        Ok(())
+    }
+
+    /// Replace every record with those in the `state.db` in `snapshot_dir`,
+    /// e.g. one restored from a backup. The snapshot is migrated first.
+    pub fn restore(&self, snapshot_dir: &Path) -> NexusResult<()> {
+        let snapshot = Self::open(snapshot_dir)?;
+        drop(snapshot);
+        let snapshot_path = snapshot_dir.join(DB_FILE);
+        let snapshot_path = snapshot_path.to_str().ok_or_else(|| {
+            NexusError::Other(format!("Unusable snapshot path {}", snapshot_path.display()))
+        })?;
+
+        let conn = self.conn();
+        conn.execute("ATTACH DATABASE ?1 AS snapshot", params![snapshot_path])?;
+        let copied = conn.execute_batch(
+            "BEGIN IMMEDIATE;
+             DELETE FROM main.records;
+             INSERT INTO main.records SELECT * FROM snapshot.records;
+             COMMIT;",
+        );
+        if copied.is_err() {
+            let _ = conn.execute_batch("ROLLBACK;");
+        }
+        conn.execute_batch("DETACH DATABASE snapshot;")?;
+        Ok(copied?)
+    }
+}
+
+fn migrate(conn: &mut Connection) -> NexusResult<()> {
+    let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+    let version: usize = tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version > MIGRATIONS.len() {
+        return Err(NexusError::Other(format!(
+            "{} was written by a newer version of Nexus (schema {}, this version knows {})",
+            DB_FILE,
+            version,
+            MIGRATIONS.len()
+        )));
+    }
+    for (i, sql) in MIGRATIONS.iter().enumerate().skip(version) {
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", i + 1)?;
+        log::info!("State store: migrated schema to version {}", i + 1);
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// The database a store was loaded from, or an error for a store that
+/// wasn't (a `Default` stand-in).
+pub fn attached(db: &Option<StateDb>) -> NexusResult<&StateDb> {
+    db.as_ref()
+        .ok_or_else(|| NexusError::Other("Store was not loaded from the data directory".to_string()))
+}
+
+/// One record per entry.
+pub fn to_records<K: ToString, V: Serialize>(
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> NexusResult<Records> {
+    entries
+        .into_iter()
+        .map(|(k, v)| Ok((k.to_string(), serde_json::to_string(&v)?)))
+        .collect()
+}
+
+/// Parse each record's value.
+pub fn from_records<V: DeserializeOwned>(records: Records) -> NexusResult<Vec<(String, V)>> {
+    records
+        .into_iter()
+        .map(|(k, v)| Ok((k, serde_json::from_str(&v)?)))
+        .collect()
+}
+
+/// One record per top-level field of a struct, for settings-like stores.
+pub fn fields_to_records<T: Serialize>(value: &T) -> NexusResult<Records> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::Object(fields) => to_records(fields),
+        _ => Err(NexusError::Other("Expected a struct".to_string())),
+    }
+}
+
+/// Rebuild a struct from [`fields_to_records`]. Missing fields take their
+/// serde defaults.
+pub fn fields_from_records<T: DeserializeOwned>(records: Records) -> NexusResult<T> {
+    let fields: serde_json::Map<String, serde_json::Value> =
+        from_records(records)?.into_iter().collect();
+    Ok(serde_json::from_value(serde_json::Value::Object(fields))?)
+}
+
+/// The legacy JSON file `name` in `data_dir`, if it hasn't been imported.
+pub fn legacy_json<T: DeserializeOwned>(data_dir: &Path, name: &str) -> NexusResult<Option<T>> {
+    let path = data_dir.join(name);
+    let data = match std::fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(Some(serde_json::from_str(&data)?))
+}
+
+/// Set a legacy JSON file aside once its contents are saved to the database.
+pub fn retire_legacy(data_dir: &Path, name: &str) -> NexusResult<()> {
+    let path = data_dir.join(name);
+    std::fs::rename(&path, data_dir.join(format!("{name}{IMPORTED_SUFFIX}")))?;
+    log::info!("Imported {} into {}", name, DB_FILE);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::OptionalExtension;
+
+    fn stored(db: &StateDb, collection: &str, key: &str) -> Option<String> {
+        db.conn()
+            .query_row(
+                "SELECT value FROM records WHERE collection = ?1 AND key = ?2",
+                params![collection, key],
+                |row| row.get(0),
+            )
+            .optional()
+            .unwrap()
+    }
+
+    #[test]
+    fn save_writes_changes_and_drops_missing_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = StateDb::open(dir.path()).unwrap();
+        db.save(&[("c", to_records([("a", 1), ("b", 2)]).unwrap())]).unwrap();
+        db.save(&[("c", to_records([("a", 1), ("c", 3)]).unwrap())]).unwrap();
+
+        // Reopening runs no migration twice
+        let db = StateDb::open(dir.path()).unwrap();
+        let records: Vec<(String, u32)> = from_records(db.load("c").unwrap()).unwrap();
+        assert_eq!(records, [("a".to_string(), 1), ("c".to_string(), 3)]);
+        assert_eq!(stored(&db, "c", "b"), None);
+        assert!(db.load("other").unwrap().is_empty());
+    }
+
+    #[test]
+    fn snapshot_restores_into_another_database() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = StateDb::open(source_dir.path()).unwrap();
+        source.save(&[("c", to_records([("path", "/old/data/x")]).unwrap())]).unwrap();
+
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        source.snapshot(&snapshot_dir.path().join(DB_FILE)).unwrap();
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let target = StateDb::open(target_dir.path()).unwrap();
+        target.save(&[("c", to_records([("stale", 1)]).unwrap())]).unwrap();
+        target.restore(snapshot_dir.path()).unwrap();
+        assert_eq!(stored(&target, "c", "stale"), None);
+
+        let changed = target
+            .rewrite(|v| {
+                *v = serde_json::json!("/new/data/x");
+                true
+            })
+            .unwrap();
+        assert_eq!(changed, 1);
+        assert_eq!(stored(&target, "c", "path").as_deref(), Some("\"/new/data/x\""));
+    }
+}
@@ -0,0 +1,185 @@
+//! Health at a glance from the tray.
+//!
+//! The app spends most of its time in the tray, so the tray icon carries a
+//! badge and the tooltip a summary when something needs attention: plugins
+//! in error, updates waiting, or a container engine that can't be reached.
+//! A background task recomputes the status when lifecycle events or update
+//! checks poke it, and pings the engine on an interval.
+
+use std::time::Duration;
+
+use tauri::image::Image;
+use tauri::Manager;
+use tokio::sync::Notify;
+
+use crate::plugin_manager::storage::PluginStatus;
+use crate::AppState;
+
+/// Id the tray icon is built with.
+pub const TRAY_ID: &str = "main";
+
+const ENGINE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const BADGE_ERROR: [u8; 3] = [0xef, 0x44, 0x44];
+const BADGE_UPDATE: [u8; 3] = [0x3b, 0x82, 0xf6];
+
+/// Wakes the status task. Managed as app state.
+#[derive(Default)]
+pub struct TrayRefresh(Notify);
+
+/// Recompute the tray status soon. Cheap enough to call on every event.
+pub fn refresh(app: &tauri::AppHandle) {
+    if let Some(refresh) = app.try_state::<TrayRefresh>() {
+        refresh.0.notify_one();
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TrayStatus {
+    plugins_in_error: usize,
+    updates: usize,
+    engine_down: bool,
+}
+
+impl TrayStatus {
+    fn tooltip(&self) -> String {
+        let mut parts = Vec::new();
+        if self.engine_down {
+            parts.push("container engine not reachable".to_string());
+        }
+        match self.plugins_in_error {
+            0 => {}
+            1 => parts.push("1 plugin in error".to_string()),
+            n => parts.push(format!("{} plugins in error", n)),
+        }
+        match self.updates {
+            0 => {}
+            1 => parts.push("1 update available".to_string()),
+            n => parts.push(format!("{} updates available", n)),
+        }
+        if parts.is_empty() {
+            "Nexus".to_string()
+        } else {
+            format!("Nexus — {}", parts.join(", "))
+        }
+    }
+
+    /// Problems outrank updates.
+    fn badge(&self) -> Option<[u8; 3]> {
+        if self.engine_down || self.plugins_in_error > 0 {
+            Some(BADGE_ERROR)
+        } else if self.updates > 0 {
+            Some(BADGE_UPDATE)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn spawn(app: tauri::AppHandle, state: AppState) {
+    tauri::async_runtime::spawn(async move {
+        let Some(base) = app.default_window_icon().cloned() else {
+            return;
+        };
+        let refresh = app.state::<TrayRefresh>();
+        let mut interval = tokio::time::interval(ENGINE_CHECK_INTERVAL);
+        let mut engine_down = false;
+        let mut shown: Option<TrayStatus> = None;
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let runtime = state.read().await.runtime.clone();
+                    engine_down = runtime.ping().await.is_err();
+                }
+                _ = refresh.0.notified() => {}
+            }
+
+            let status = {
+                let mgr = state.read().await;
+                TrayStatus {
+                    plugins_in_error: mgr
+                        .storage
+                        .list()
+                        .iter()
+                        .filter(|p| p.status == PluginStatus::Error)
+                        .count(),
+                    updates: mgr.update_state.available_updates.len(),
+                    engine_down,
+                }
+            };
+            if shown.as_ref() == Some(&status) {
+                continue;
+            }
+            let Some(tray) = app.tray_by_id(TRAY_ID) else {
+                continue;
+            };
+            let _ = tray.set_tooltip(Some(status.tooltip()));
+            match status.badge() {
+                Some(color) => {
+                    let _ = tray.set_icon(Some(badged(&base, color)));
+                    // Template icons are drawn monochrome, which would hide the badge
+                    let _ = tray.set_icon_as_template(false);
+                }
+                None => {
+                    let _ = tray.set_icon(Some(base.clone()));
+                    let _ = tray.set_icon_as_template(true);
+                }
+            }
+            shown = Some(status);
+        }
+    });
+}
+
+/// `icon` with a filled dot in the bottom-right corner.
+fn badged(icon: &Image<'_>, color: [u8; 3]) -> Image<'static> {
+    let (width, height) = (icon.width(), icon.height());
+    let mut rgba = icon.rgba().to_vec();
+    let radius = (width.min(height) as f32 * 0.22).max(2.0);
+    let (cx, cy) = (width as f32 - radius - 1.0, height as f32 - radius - 1.0);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[color[0], color[1], color[2], 0xff]);
+            }
+        }
+    }
+    Image::new_owned(rgba, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_and_badge_follow_the_worst_state() {
+        let mut status = TrayStatus::default();
+        assert_eq!(status.tooltip(), "Nexus");
+        assert_eq!(status.badge(), None);
+
+        status.updates = 2;
+        assert_eq!(status.tooltip(), "Nexus — 2 updates available");
+        assert_eq!(status.badge(), Some(BADGE_UPDATE));
+
+        status.plugins_in_error = 1;
+        status.engine_down = true;
+        assert_eq!(
+            status.tooltip(),
+            "Nexus — container engine not reachable, 1 plugin in error, 2 updates available"
+        );
+        assert_eq!(status.badge(), Some(BADGE_ERROR));
+    }
+
+    #[test]
+    fn badge_is_drawn_in_the_corner() {
+        let icon = Image::new_owned(vec![0; 32 * 32 * 4], 32, 32);
+        let badged = badged(&icon, BADGE_ERROR);
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * 32 + x) * 4) as usize;
+            badged.rgba()[i..i + 4].to_vec()
+        };
+        assert_eq!(pixel(26, 26), vec![0xef, 0x44, 0x44, 0xff]);
+        assert_eq!(pixel(2, 2), vec![0, 0, 0, 0]);
+    }
+}
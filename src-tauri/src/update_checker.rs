@@ -7,7 +7,7 @@ use crate::error::NexusResult;
 use crate::extensions::signing::{KeyConsistency, TrustedKeyStore};
 use crate::extensions::storage::ExtensionStorage;
 use crate::plugin_manager::registry::{
-    ExtensionRegistryEntry, RegistryEntry, RegistryStore, RegistryTrust,
+    self, ExtensionRegistryEntry, RegistryEntry, RegistryStore, RegistryTrust, ReleaseChannel,
 };
 use crate::plugin_manager::storage::{self, PluginStorage};
 use crate::version;
@@ -48,6 +48,26 @@ pub struct AvailableUpdate {
     /// When present, the image should be rebuilt from this directory on update.
     #[serde(default)]
     pub build_context: Option<String>,
+    /// Channel of the offered release. Always stable for extensions.
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+    /// The registry's notes for the new version, or the fetched changelog.
+    #[serde(default)]
+    pub release_notes: Option<String>,
+    #[serde(default)]
+    pub changelog_url: Option<String>,
+}
+
+/// An installed plugin or extension running a version its registry has
+/// withdrawn.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct YankedInstall {
+    pub item_id: String,
+    pub item_type: UpdateItemType,
+    pub item_name: String,
+    pub version: String,
+    pub reason: Option<String>,
+    pub registry_source: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -55,6 +75,75 @@ pub struct UpdateCheckState {
     pub last_checked: Option<chrono::DateTime<chrono::Utc>>,
     pub available_updates: Vec<AvailableUpdate>,
     pub dismissed: HashMap<String, String>,
+    /// Fetched changelogs, keyed by `<item id>@<version>`.
+    #[serde(default)]
+    pub changelogs: HashMap<String, String>,
+    /// Installed versions found withdrawn at the last check.
+    #[serde(default)]
+    pub yanked: Vec<YankedInstall>,
+}
+
+/// Which plugins update automatically. Stored in settings as `auto_update`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct AutoUpdatePolicy {
+    /// Default for plugins without their own setting.
+    pub enabled: bool,
+    /// Per-plugin settings, overriding the default.
+    pub plugins: HashMap<String, bool>,
+}
+
+impl AutoUpdatePolicy {
+    /// Whether `update` should be applied without asking. Updates flagged
+    /// for a changed signing key or manifest domain, or from an untrusted
+    /// registry, always wait for the user.
+    pub fn applies_to(&self, update: &AvailableUpdate) -> bool {
+        let opted_in = self
+            .plugins
+            .get(&update.item_id)
+            .copied()
+            .unwrap_or(self.enabled);
+        update.item_type == UpdateItemType::Plugin
+            && opted_in
+            && !update.security.iter().any(|s| {
+                matches!(
+                    s,
+                    UpdateSecurity::KeyChanged
+                        | UpdateSecurity::UntrustedSource
+                        | UpdateSecurity::ManifestDomainChanged
+                )
+            })
+    }
+}
+
+/// Fill in release notes for updates whose registry entry only links a
+/// changelog. Each changelog is fetched once per version and kept in
+/// `cache`; entries for versions no longer offered are dropped. A failed
+/// fetch leaves the notes empty, and the link is still shown.
+pub async fn fetch_changelogs(
+    updates: &mut [AvailableUpdate],
+    cache: &mut HashMap<String, String>,
+) {
+    let key = |u: &AvailableUpdate| format!("{}@{}", u.item_id, u.available_version);
+    cache.retain(|k, _| updates.iter().any(|u| key(u) == *k));
+
+    for update in updates.iter_mut().filter(|u| u.release_notes.is_none()) {
+        let Some(url) = update.changelog_url.clone() else {
+            continue;
+        };
+        let key = key(update);
+        if let Some(notes) = cache.get(&key) {
+            update.release_notes = Some(notes.clone());
+            continue;
+        }
+        match registry::fetch_changelog(&url).await {
+            Ok(notes) => {
+                cache.insert(key, notes.clone());
+                update.release_notes = Some(notes);
+            }
+            Err(e) => log::warn!("Failed to fetch changelog for {}: {}", update.item_id, e),
+        }
+    }
 }
 
 /// Load persisted update state from disk.
@@ -78,6 +167,24 @@ pub fn save_update_state(data_dir: &Path, state: &UpdateCheckState) -> NexusResu
     Ok(())
 }
 
+/// Newest registry release of `id` that a plugin following `channel` may be
+/// offered. Withdrawn releases are never offered.
+fn latest_entry<'a>(
+    entries: &'a [RegistryEntry],
+    id: &str,
+    channel: ReleaseChannel,
+) -> Option<&'a RegistryEntry> {
+    entries
+        .iter()
+        .filter(|e| e.id == id && channel.accepts(e.channel) && e.yanked(&e.version).is_none())
+        .reduce(|best, e| {
+            match version::compare_versions(&best.version, &e.version) {
+                Some(std::cmp::Ordering::Less) => e,
+                _ => best,
+            }
+        })
+}
+
 /// Scan installed plugins and extensions against registry caches to find available updates.
 #[allow(clippy::too_many_arguments)]
 pub fn check_for_updates(
@@ -91,13 +198,21 @@ pub fn check_for_updates(
 ) -> Vec<AvailableUpdate> {
     let mut updates = Vec::new();
 
+    // Updates come from the highest-priority registry listing a plugin, so a
+    // lower-priority one can't take it over by publishing a higher version
+    let plugin_registry = registry::resolve_conflicts(
+        plugin_registry,
+        registry_store,
+        registry::ConflictPolicy::HighestPriority,
+    );
+
     // Check plugins
     for installed in plugin_storage.list() {
         let plugin_id = &installed.manifest.id;
         let installed_version = &installed.manifest.version;
 
-        // Find matching registry entry
-        let reg_entry = match plugin_registry.iter().find(|e| &e.id == plugin_id) {
+        // Find matching registry entry on the plugin's channel
+        let reg_entry = match latest_entry(&plugin_registry, plugin_id, installed.channel) {
             Some(entry) => entry,
             None => continue,
         };
@@ -131,6 +246,15 @@ pub fn check_for_updates(
             security.push(UpdateSecurity::UntrustedSource);
         }
 
+        // Key consistency check for signed manifests
+        if let Some(ref reg_key) = reg_entry.author_public_key {
+            match trusted_keys.check_key_consistency(&installed.manifest.author, reg_key) {
+                KeyConsistency::Matches => security.push(UpdateSecurity::KeyMatch),
+                KeyConsistency::Changed => security.push(UpdateSecurity::KeyChanged),
+                KeyConsistency::NewAuthor => security.push(UpdateSecurity::Verified),
+            }
+        }
+
         // Domain pinning: flag if manifest URL hostname changed from install time
         if let Some(ref pinned_origin) = installed.manifest_url_origin {
             if let Some(current_origin) = storage::extract_url_host(&reg_entry.manifest_url) {
@@ -150,8 +274,11 @@ pub fn check_for_updates(
             registry_source: reg_entry.source.clone(),
             security,
             new_image_digest: reg_entry.image_digest.clone(),
-            author_public_key: None,
+            author_public_key: reg_entry.author_public_key.clone(),
             build_context: reg_entry.build_context.clone(),
+            channel: reg_entry.channel,
+            release_notes: reg_entry.release_notes.clone(),
+            changelog_url: reg_entry.changelog_url.clone(),
         });
     }
 
@@ -160,7 +287,10 @@ pub fn check_for_updates(
         let ext_id = &installed.manifest.id;
         let installed_version = &installed.manifest.version;
 
-        let reg_entry = match extension_registry.iter().find(|e| e.id == *ext_id) {
+        let reg_entry = match extension_registry
+            .iter()
+            .find(|e| e.id == *ext_id && e.yanked(&e.version).is_none())
+        {
             Some(entry) => entry,
             None => continue,
         };
@@ -206,12 +336,71 @@ pub fn check_for_updates(
             new_image_digest: None,
             author_public_key: reg_entry.author_public_key.clone(),
             build_context: None,
+            channel: ReleaseChannel::Stable,
+            release_notes: reg_entry.release_notes.clone(),
+            changelog_url: reg_entry.changelog_url.clone(),
         });
     }
 
     updates
 }
 
+/// Find installed plugins and extensions whose version a registry has
+/// withdrawn. For plugins, only the highest-priority registry listing them
+/// is consulted, as for updates.
+pub fn find_yanked_installs(
+    plugin_storage: &PluginStorage,
+    extension_storage: &ExtensionStorage,
+    plugin_registry: &[RegistryEntry],
+    extension_registry: &[ExtensionRegistryEntry],
+    registry_store: &RegistryStore,
+) -> Vec<YankedInstall> {
+    let plugin_registry = registry::resolve_conflicts(
+        plugin_registry,
+        registry_store,
+        registry::ConflictPolicy::HighestPriority,
+    );
+    let mut yanked = Vec::new();
+
+    for installed in plugin_storage.list() {
+        let manifest = &installed.manifest;
+        let found = plugin_registry
+            .iter()
+            .filter(|e| e.id == manifest.id)
+            .find_map(|e| e.yanked(&manifest.version).map(|y| (e, y)));
+        if let Some((entry, yank)) = found {
+            yanked.push(YankedInstall {
+                item_id: manifest.id.clone(),
+                item_type: UpdateItemType::Plugin,
+                item_name: manifest.name.clone(),
+                version: manifest.version.clone(),
+                reason: yank.reason.clone(),
+                registry_source: entry.source.clone(),
+            });
+        }
+    }
+
+    for installed in extension_storage.list() {
+        let manifest = &installed.manifest;
+        let found = extension_registry
+            .iter()
+            .filter(|e| e.id == manifest.id)
+            .find_map(|e| e.yanked(&manifest.version).map(|y| (e, y)));
+        if let Some((entry, yank)) = found {
+            yanked.push(YankedInstall {
+                item_id: manifest.id.clone(),
+                item_type: UpdateItemType::Extension,
+                item_name: manifest.display_name.clone(),
+                version: manifest.version.clone(),
+                reason: yank.reason.clone(),
+                registry_source: entry.source.clone(),
+            });
+        }
+    }
+
+    yanked
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,6 +431,7 @@ mod tests {
                 mcp: None,
                 extensions: HashMap::new(),
                 mcp_access: vec![],
+                security: Default::default(),
             },
             container_id: None,
             status: PluginStatus::Stopped,
@@ -251,6 +441,8 @@ mod tests {
             manifest_url_origin: origin.map(|o| o.to_string()),
             dev_mode: false,
             local_manifest_path: None,
+            channel: ReleaseChannel::Stable,
+            security_override: Default::default(),
         }
     }
 
@@ -273,8 +465,20 @@ mod tests {
             license: None,
             homepage: None,
             icon: None,
+            screenshots: vec![],
             status: None,
             build_context: None,
+            channel: ReleaseChannel::Stable,
+            release_notes: None,
+            changelog_url: None,
+            author_public_key: None,
+            manifest_signature: None,
+            yanked: vec![],
+            install_count: None,
+            publisher: None,
+            verified: false,
+            verified_identity: None,
+            conflicts: vec![],
         }
     }
 
@@ -294,6 +498,13 @@ mod tests {
             created_at: None,
             platforms: vec![],
             status: None,
+            release_notes: None,
+            changelog_url: None,
+            yanked: vec![],
+            install_count: None,
+            publisher: None,
+            verified: false,
+            verified_identity: None,
         }
     }
 
@@ -532,4 +743,171 @@ mod tests {
         assert_eq!(updates.len(), 1);
         assert!(updates[0].security.contains(&UpdateSecurity::KeyChanged));
     }
+
+    #[test]
+    fn beta_releases_only_offered_to_beta_plugins() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = crate::plugin_manager::storage::PluginStorage::load(dir.path()).unwrap();
+        storage.add(make_plugin("foo", "1.0.0", None, None)).unwrap();
+        let mut beta_plugin = make_plugin("bar", "1.0.0", None, None);
+        beta_plugin.channel = ReleaseChannel::Beta;
+        storage.add(beta_plugin).unwrap();
+        let ext_storage = ExtensionStorage::load(dir.path());
+        let trusted = TrustedKeyStore::load(dir.path());
+        let reg_store = default_registry_store();
+        let dismissed = HashMap::new();
+
+        let beta = |id: &str, version: &str| RegistryEntry {
+            channel: ReleaseChannel::Beta,
+            ..make_registry_entry(id, version, None, "https://example.com/beta.json")
+        };
+        let registry = vec![
+            make_registry_entry("foo", "1.1.0", None, "https://example.com/foo.json"),
+            beta("foo", "2.0.0-beta.1"),
+            make_registry_entry("bar", "1.1.0", None, "https://example.com/bar.json"),
+            beta("bar", "2.0.0-beta.1"),
+        ];
+
+        let updates = check_for_updates(
+            &storage, &ext_storage, &registry, &[], &trusted, &reg_store, &dismissed,
+        );
+        let offered = |id: &str| {
+            let u = updates.iter().find(|u| u.item_id == id).unwrap();
+            (u.available_version.clone(), u.channel)
+        };
+        assert_eq!(offered("foo"), ("1.1.0".to_string(), ReleaseChannel::Stable));
+        assert_eq!(offered("bar"), ("2.0.0-beta.1".to_string(), ReleaseChannel::Beta));
+    }
+
+    #[test]
+    fn updates_come_from_the_highest_priority_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = crate::plugin_manager::storage::PluginStorage::load(dir.path()).unwrap();
+        storage.add(make_plugin("foo", "1.0.0", None, None)).unwrap();
+        let ext_storage = ExtensionStorage::load(dir.path());
+        let trusted = TrustedKeyStore::load(dir.path());
+        let reg_store = default_registry_store();
+        let dismissed = HashMap::new();
+
+        let registry = vec![
+            RegistryEntry {
+                source: "Elsewhere".to_string(),
+                ..make_registry_entry("foo", "9.0.0", None, "https://elsewhere.test/foo.json")
+            },
+            make_registry_entry("foo", "1.1.0", None, "https://example.com/foo.json"),
+        ];
+
+        let updates = check_for_updates(
+            &storage, &ext_storage, &registry, &[], &trusted, &reg_store, &dismissed,
+        );
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].available_version, "1.1.0");
+        assert_eq!(updates[0].registry_source, "Nexus Community");
+    }
+
+    #[test]
+    fn yanked_versions_are_not_offered_and_installs_are_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = crate::plugin_manager::storage::PluginStorage::load(dir.path()).unwrap();
+        storage.add(make_plugin("foo", "1.0.0", None, None)).unwrap();
+        storage.add(make_plugin("bar", "1.0.0", None, None)).unwrap();
+        let ext_storage = ExtensionStorage::load(dir.path());
+        let trusted = TrustedKeyStore::load(dir.path());
+        let reg_store = default_registry_store();
+        let dismissed = HashMap::new();
+
+        let yank = |version: &str| registry::YankedVersion {
+            version: version.to_string(),
+            reason: Some("data loss".to_string()),
+        };
+        let registry = vec![
+            RegistryEntry {
+                yanked: vec![yank("1.1.0")],
+                ..make_registry_entry("foo", "1.1.0", None, "https://example.com/foo.json")
+            },
+            RegistryEntry {
+                yanked: vec![yank("1.0.0")],
+                ..make_registry_entry("bar", "1.0.1", None, "https://example.com/bar.json")
+            },
+        ];
+
+        let updates = check_for_updates(
+            &storage, &ext_storage, &registry, &[], &trusted, &reg_store, &dismissed,
+        );
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].item_id, "bar");
+
+        let yanked = find_yanked_installs(&storage, &ext_storage, &registry, &[], &reg_store);
+        assert_eq!(yanked.len(), 1);
+        assert_eq!(yanked[0].item_id, "bar");
+        assert_eq!(yanked[0].reason.as_deref(), Some("data loss"));
+    }
+
+    fn available(id: &str, item_type: UpdateItemType, security: Vec<UpdateSecurity>) -> AvailableUpdate {
+        AvailableUpdate {
+            item_id: id.to_string(),
+            item_type,
+            item_name: id.to_string(),
+            installed_version: "1.0.0".to_string(),
+            available_version: "1.1.0".to_string(),
+            manifest_url: format!("https://example.com/{}.json", id),
+            registry_source: "official".to_string(),
+            security,
+            new_image_digest: None,
+            author_public_key: None,
+            build_context: None,
+            channel: ReleaseChannel::Stable,
+            release_notes: None,
+            changelog_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn changelogs_are_fetched_once_per_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CHANGELOG.md");
+        std::fs::write(&path, "## 1.1.0\n- Faster sync").unwrap();
+
+        let mut linked = available("com.a", UpdateItemType::Plugin, vec![]);
+        linked.changelog_url = Some(format!("file://{}", path.display()));
+        let mut inline = available("com.b", UpdateItemType::Plugin, vec![]);
+        inline.release_notes = Some("Bug fixes".to_string());
+        inline.changelog_url = Some("https://example.invalid/CHANGELOG.md".to_string());
+        let mut updates = vec![linked, inline];
+
+        let mut cache = HashMap::from([("com.a@1.0.5".to_string(), "stale".to_string())]);
+        fetch_changelogs(&mut updates, &mut cache).await;
+        assert_eq!(updates[0].release_notes.as_deref(), Some("## 1.1.0\n- Faster sync"));
+        assert_eq!(updates[1].release_notes.as_deref(), Some("Bug fixes"));
+        assert_eq!(cache.keys().collect::<Vec<_>>(), ["com.a@1.1.0"]);
+
+        // Served from the cache once fetched
+        std::fs::remove_file(&path).unwrap();
+        updates[0].release_notes = None;
+        fetch_changelogs(&mut updates, &mut cache).await;
+        assert_eq!(updates[0].release_notes.as_deref(), Some("## 1.1.0\n- Faster sync"));
+    }
+
+    #[test]
+    fn auto_update_policy_respects_overrides_and_security() {
+        let mut policy = AutoUpdatePolicy::default();
+        let plain = available("com.a", UpdateItemType::Plugin, vec![UpdateSecurity::DigestAvailable]);
+        assert!(!policy.applies_to(&plain));
+
+        policy.plugins.insert("com.a".to_string(), true);
+        assert!(policy.applies_to(&plain));
+
+        policy.enabled = true;
+        policy.plugins.insert("com.a".to_string(), false);
+        assert!(!policy.applies_to(&plain));
+        assert!(policy.applies_to(&available("com.b", UpdateItemType::Plugin, vec![])));
+
+        let moved = available(
+            "com.b",
+            UpdateItemType::Plugin,
+            vec![UpdateSecurity::ManifestDomainChanged],
+        );
+        assert!(!policy.applies_to(&moved));
+        assert!(!policy.applies_to(&available("com.b", UpdateItemType::Extension, vec![])));
+    }
 }
@@ -1,15 +1,139 @@
-use std::io;
-use std::path::Path;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 /// Atomic file write: writes data to a temporary sibling file, then renames
 /// into place. Rename is atomic on POSIX when src and dst are on the same
-/// filesystem (guaranteed here — sibling file).
+/// filesystem (guaranteed here — sibling file). The data is flushed to disk
+/// first, so a crash can't leave the renamed file empty.
 pub fn atomic_write(path: &Path, data: &[u8]) -> io::Result<()> {
     let tmp = path.with_extension("tmp");
-    std::fs::write(&tmp, data)?;
+    let mut file = std::fs::File::create(&tmp)?;
+    file.write_all(data)?;
+    file.sync_all()?;
     std::fs::rename(&tmp, path)
 }
 
+/// `path` with `.{suffix}` appended, e.g. `settings.json.bak`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Where [`atomic_write_with_backup`] keeps the previous version of `path`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, "bak")
+}
+
+/// [`atomic_write`] for store files: the version being replaced is kept as
+/// its backup, for [`read_store`] to fall back to.
+pub fn atomic_write_with_backup(path: &Path, data: &[u8]) -> io::Result<()> {
+    if path.is_file() {
+        std::fs::copy(path, backup_path(path))?;
+    }
+    atomic_write(path, data)
+}
+
+/// A store file that was damaged when Nexus loaded it.
+#[derive(Debug, Clone)]
+pub struct StoreProblem {
+    pub file: String,
+    pub message: String,
+    /// The store's backup was used in its place.
+    pub recovered: bool,
+}
+
+/// Problems found before the UI could hear about them, drained at startup.
+static STORE_PROBLEMS: Mutex<Vec<StoreProblem>> = Mutex::new(Vec::new());
+
+/// Log a damaged store and keep it for [`take_store_problems`].
+pub fn report_store_problem(file: &str, message: String, recovered: bool) {
+    if recovered {
+        log::warn!("{} was damaged, restored its backup: {}", file, message);
+    } else {
+        log::error!("{} couldn't be loaded: {}", file, message);
+    }
+    STORE_PROBLEMS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(StoreProblem {
+            file: file.to_string(),
+            message,
+            recovered,
+        });
+}
+
+/// Store problems reported since the last call.
+pub fn take_store_problems() -> Vec<StoreProblem> {
+    std::mem::take(&mut *STORE_PROBLEMS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Read a store written by [`atomic_write_with_backup`]. `None` when it
+/// doesn't exist; a damaged one goes through [`recover_store`].
+pub fn read_store<T>(
+    path: &Path,
+    aside: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Option<T> {
+    let error = match std::fs::read_to_string(path) {
+        Ok(contents) => match parse(&contents) {
+            Ok(value) => return Some(value),
+            Err(e) => e,
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound && !backup_path(path).exists() => {
+            return None
+        }
+        Err(e) => e.to_string(),
+    };
+    recover_store(path, error, aside, parse)
+}
+
+/// [`read_store`] for JSON.
+pub fn read_json_store<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    read_store(path, "corrupt", |s| {
+        serde_json::from_str(s).map_err(|e| e.to_string())
+    })
+}
+
+/// Deal with a store that failed to load with `error`: the damaged file is
+/// moved aside to `<name>.{aside}`, and its backup put in its place if
+/// `parse` accepts it. Reported either way, rather than silently starting
+/// empty.
+pub fn recover_store<T>(
+    path: &Path,
+    error: String,
+    aside: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Option<T> {
+    let file = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let backup = backup_path(path);
+    let recovered = std::fs::read_to_string(&backup)
+        .ok()
+        .and_then(|contents| parse(&contents).ok());
+    if path.exists() {
+        if let Err(e) = std::fs::rename(path, with_suffix(path, aside)) {
+            log::warn!("Failed to move damaged {} aside: {}", file, e);
+        }
+    }
+    let Some(value) = recovered else {
+        report_store_problem(&file, error, false);
+        return None;
+    };
+    // Copy, not rename, so the backup survives the next save; copying keeps
+    // the backup's permissions
+    let tmp = path.with_extension("tmp");
+    if let Err(e) = std::fs::copy(&backup, &tmp).and_then(|_| std::fs::rename(&tmp, path)) {
+        log::warn!("Failed to restore {} from its backup: {}", file, e);
+    }
+    report_store_problem(&file, error, true);
+    Some(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,4 +163,28 @@ mod tests {
         let content = std::fs::read_to_string(&path).unwrap();
         assert_eq!(content, "new");
     }
+
+    #[test]
+    fn damaged_store_falls_back_to_its_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.json");
+        assert_eq!(read_json_store::<Vec<u32>>(&path), None);
+
+        atomic_write_with_backup(&path, b"[1]").unwrap();
+        atomic_write_with_backup(&path, b"[1, 2]").unwrap();
+        assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), "[1]");
+
+        std::fs::write(&path, "[1, 2").unwrap();
+        assert_eq!(read_json_store::<Vec<u32>>(&path), Some(vec![1]));
+        assert_eq!(read_json_store::<Vec<u32>>(&path), Some(vec![1]));
+        assert!(dir.path().join("store.json.corrupt").exists());
+        assert!(take_store_problems()
+            .iter()
+            .any(|p| p.file == "store.json" && p.recovered));
+
+        std::fs::write(&path, "x").unwrap();
+        std::fs::write(backup_path(&path), "y").unwrap();
+        assert_eq!(read_json_store::<Vec<u32>>(&path), None);
+        assert!(!path.exists());
+    }
 }
@@ -12,6 +12,7 @@ import { useAppStore } from "./stores/appStore";
 import { usePluginActions, usePluginSync } from "./hooks/usePlugins";
 import { useExtensionActions, useExtensionSync } from "./hooks/useExtensions";
 import { useLifecycleEvents } from "./hooks/useLifecycleEvents";
+import { useNotificationActions } from "./hooks/useNotificationActions";
 import { useUpdateScheduler } from "./hooks/useUpdateScheduler";
 import { checkEngine, pluginLogs } from "./lib/tauri";
 import { Package } from "lucide-react";
@@ -140,6 +141,7 @@ function App() {
   const { refresh } = usePluginActions();
   const { refresh: extensionRefresh } = useExtensionActions();
   useLifecycleEvents();
+  useNotificationActions();
   usePluginSync();
   useExtensionSync();
   useUpdateScheduler();
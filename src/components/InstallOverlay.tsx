@@ -1,6 +1,6 @@
 import { Loader2 } from "lucide-react";
 import { useAppStore } from "../stores/appStore";
-import { Modal, ModalContent, ModalBody } from "@heroui/react";
+import { Modal, ModalContent, ModalBody, Progress } from "@heroui/react";
 
 export function InstallOverlay() {
   const installStatus = useAppStore((s) => s.installStatus);
@@ -19,6 +19,15 @@ export function InstallOverlay() {
           <p className="text-[13px] font-medium text-center">
             {installStatus.message}
           </p>
+          {installStatus.progress !== null && (
+            <Progress
+              aria-label={installStatus.message}
+              size="sm"
+              value={installStatus.progress}
+              showValueLabel
+              className="w-full"
+            />
+          )}
         </ModalBody>
       </ModalContent>
     </Modal>
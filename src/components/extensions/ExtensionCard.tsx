@@ -2,7 +2,10 @@ import { useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import type { ExtensionRegistryEntry } from "../../types/extension";
 import { timeAgo } from "../../lib/timeAgo";
+import { formatInstalls } from "../../lib/installCount";
+import { VerifiedBadge } from "../marketplace/VerifiedBadge";
 import { Card, CardBody, Chip } from "@heroui/react";
+import { Download } from "lucide-react";
 
 interface Props {
   entry: ExtensionRegistryEntry;
@@ -22,8 +25,9 @@ export function ExtensionRegistryCard({ entry, onSelect }: Props) {
       <CardBody className="p-4">
       <div className="flex items-start justify-between mb-2">
         <div>
-          <h3 className="text-[13px] font-semibold">
+          <h3 className="text-[13px] font-semibold flex items-center gap-1">
             {entry.name}
+            {entry.verified && <VerifiedBadge identity={entry.verified_identity} size={12} />}
           </h3>
           <p className="text-[11px] text-default-500 font-mono">
             v{entry.version}
@@ -67,6 +71,12 @@ export function ExtensionRegistryCard({ entry, onSelect }: Props) {
             {cat}
           </Chip>
         ))}
+        {entry.install_count != null && (
+          <span className="flex items-center gap-0.5 text-[10px] text-default-400">
+            <Download size={10} strokeWidth={1.5} />
+            {formatInstalls(entry.install_count)}
+          </span>
+        )}
         {entry.created_at && (
           <span className="text-[10px] text-default-400 ml-auto">
             {timeAgo(entry.created_at)}
@@ -22,6 +22,7 @@ import {
   Clock,
 } from "lucide-react";
 import { timeAgo } from "../../lib/timeAgo";
+import { VerifiedBadge } from "../marketplace/VerifiedBadge";
 import { Button, Card, CardBody, Chip } from "@heroui/react";
 
 const RISK_CHIP_COLORS: Record<string, "success" | "warning" | "danger"> = {
@@ -117,7 +118,10 @@ export function ExtensionDetail({ entry, onBack }: Props) {
       <Card><CardBody className="p-6">
         <div className="flex items-start justify-between mb-4">
           <div>
-            <h2 className="text-[18px] font-bold">{entry.name}</h2>
+            <h2 className="text-[18px] font-bold flex items-center gap-1.5">
+              {entry.name}
+              {entry.verified && <VerifiedBadge identity={entry.verified_identity} size={16} />}
+            </h2>
             <p className="text-[12px] text-default-500 mt-1 font-mono">
               v{entry.version} &middot; {entry.id}
             </p>
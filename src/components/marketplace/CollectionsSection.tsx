@@ -0,0 +1,55 @@
+import { useAppStore } from "../../stores/appStore";
+import { RegistryPluginCard } from "../plugins/PluginCard";
+import { ExtensionRegistryCard } from "../extensions/ExtensionCard";
+import type { MarketplaceCollection } from "../../types/plugin";
+
+interface Props {
+  collections: MarketplaceCollection[];
+  installedIds: Set<string>;
+}
+
+/** Registry-curated groups of plugins and extensions, above the full listing. */
+export function CollectionsSection({ collections, installedIds }: Props) {
+  return (
+    <div className="space-y-6 mb-8">
+      {collections.map((collection) => (
+        <section key={`${collection.source}:${collection.id}`}>
+          <div className="mb-3">
+            <div className="flex items-baseline gap-2">
+              <h3 className="text-[14px] font-semibold">{collection.name}</h3>
+              <span className="text-[10px] text-default-400">{collection.source}</span>
+            </div>
+            {collection.description && (
+              <p className="text-[12px] text-default-500">{collection.description}</p>
+            )}
+          </div>
+          <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
+            {collection.plugins.map((entry) => (
+              <RegistryPluginCard
+                key={`${entry.source}:${entry.id}`}
+                entry={entry}
+                isInstalled={installedIds.has(entry.id)}
+                // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                onSelect={() => {
+                  useAppStore.getState().selectRegistryEntry(entry);
+                  useAppStore.getState().setView("plugin-detail");
+                }}
+              />
+            ))}
+            {collection.extensions.map((entry) => (
+              <ExtensionRegistryCard
+                key={`${entry.source}:${entry.id}`}
+                entry={entry}
+                // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                onSelect={() => {
+                  useAppStore.getState().selectExtensionEntry(entry);
+                  useAppStore.getState().setView("extension-detail");
+                }}
+              />
+            ))}
+          </div>
+        </section>
+      ))}
+    </div>
+  );
+}
@@ -6,16 +6,31 @@ import { usePluginActions } from "../../hooks/usePlugins";
 import { useAppStore } from "../../stores/appStore";
 import { RegistryPluginCard } from "../plugins/PluginCard";
 import { SearchBar } from "./SearchBar";
+import { CollectionsSection } from "./CollectionsSection";
 import { PermissionDialog } from "../permissions/PermissionDialog";
-import type { PluginManifest } from "../../types/plugin";
+import type { MarketplaceSort, PluginManifest } from "../../types/plugin";
 import type { Permission } from "../../types/permissions";
 import { FolderOpen, RefreshCw, Package, Wand2 } from "lucide-react";
 import { McpWrapWizard } from "./McpWrapWizard";
-import { Button } from "@heroui/react";
+import { Button, Select, SelectItem } from "@heroui/react";
+
+const SORT_OPTIONS: MarketplaceSort[] = ["relevance", "popularity", "recently_updated", "name"];
 
 export function MarketplacePage() {
   const { t } = useTranslation("plugins");
-  const { plugins, isLoading, refresh, search } = useMarketplace();
+  const {
+    plugins,
+    total,
+    categories,
+    query,
+    collections,
+    isLoading,
+    refresh,
+    search,
+    toggleCategory,
+    setSort,
+    setPage,
+  } = useMarketplace();
   const { previewLocal, installLocal } = usePluginActions();
   const installedPlugins = useAppStore((s) => s.installedPlugins);
   const [installing, setInstalling] = useState(false);
@@ -26,6 +41,10 @@ export function MarketplacePage() {
   const [pendingPath, setPendingPath] = useState<string | null>(null);
 
   const installedIds = new Set(installedPlugins.map((p) => p.manifest.id));
+  const pageCount = Math.max(1, Math.ceil(total / query.page_size));
+  // Collections are for browsing, so they give way to any search or filter
+  const showCollections =
+    collections.length > 0 && !query.text && query.categories.length === 0 && query.page === 0;
 
   useEffect(() => {
     refresh();
@@ -102,8 +121,46 @@ export function MarketplacePage() {
         </div>
       </div>
 
-      <div className="mb-6">
-        <SearchBar onSearch={search} />
+      <div className="mb-4">
+        <SearchBar onSearch={search} initialQuery={query.text} />
+      </div>
+
+      {showCollections && (
+        <CollectionsSection collections={collections} installedIds={installedIds} />
+      )}
+
+      <div className="flex items-start justify-between gap-4 mb-6">
+        <div className="flex flex-wrap gap-1.5">
+          {categories.map((facet) => (
+            <Button
+              key={facet.name}
+              size="sm"
+              variant={query.categories.includes(facet.name) ? "solid" : "flat"}
+              color={query.categories.includes(facet.name) ? "primary" : "default"}
+              // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+              onPress={() => toggleCategory(facet.name)}
+            >
+              {facet.name}
+              <span className="text-[11px] opacity-60">{facet.count}</span>
+            </Button>
+          ))}
+        </div>
+        <Select
+          aria-label={t("marketplace.sortBy")}
+          size="sm"
+          className="w-48 shrink-0"
+          // eslint-disable-next-line react-perf/jsx-no-new-array-as-prop
+          selectedKeys={[query.sort]}
+          // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+          onSelectionChange={(keys) => {
+            const selected = Array.from(keys)[0];
+            if (selected) setSort(selected as MarketplaceSort);
+          }}
+        >
+          {SORT_OPTIONS.map((sort) => (
+            <SelectItem key={sort}>{t(`marketplace.sort.${sort}`)}</SelectItem>
+          ))}
+        </Select>
       </div>
 
       {plugins.length === 0 ? (
@@ -133,7 +190,7 @@ export function MarketplacePage() {
         <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
           {plugins.map((entry) => (
             <RegistryPluginCard
-              key={entry.id}
+              key={`${entry.source}:${entry.id}`}
               entry={entry}
               isInstalled={installedIds.has(entry.id)}
               // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
@@ -146,6 +203,32 @@ export function MarketplacePage() {
         </div>
       )}
 
+      {pageCount > 1 && (
+        <div className="flex items-center justify-center gap-3 mt-6">
+          <Button
+            size="sm"
+            variant="flat"
+            isDisabled={query.page === 0}
+            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+            onPress={() => setPage(query.page - 1)}
+          >
+            {t("marketplace.previousPage")}
+          </Button>
+          <span className="text-[12px] text-default-500">
+            {t("marketplace.pageOf", { page: query.page + 1, count: pageCount })}
+          </span>
+          <Button
+            size="sm"
+            variant="flat"
+            isDisabled={query.page + 1 >= pageCount}
+            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+            onPress={() => setPage(query.page + 1)}
+          >
+            {t("marketplace.nextPage")}
+          </Button>
+        </div>
+      )}
+
       {pendingManifest && (
         <PermissionDialog
           manifest={pendingManifest}
@@ -1,6 +1,10 @@
 import { useState, useCallback, useMemo } from "react";
 import { useTranslation } from "react-i18next";
-import type { ClassifiedTool, PluginMetadata } from "../../types/mcp_wrap";
+import type {
+  ClassifiedTool,
+  McpEnvVar,
+  PluginMetadata,
+} from "../../types/mcp_wrap";
 import type { Permission } from "../../types/permissions";
 import { getPermissionInfo } from "../../types/permissions";
 import {
@@ -52,6 +56,8 @@ export function McpWrapWizard({ onClose, onInstalled }: Props) {
   const [command, setCommand] = useState("");
   const [discovering, setDiscovering] = useState(false);
   const [discoverError, setDiscoverError] = useState<string | null>(null);
+  const [envVars, setEnvVars] = useState<McpEnvVar[]>([]);
+  const [envValues, setEnvValues] = useState<Record<string, string>>({});
 
   // Step 2: Tools
   const [tools, setTools] = useState<ClassifiedTool[]>([]);
@@ -80,10 +86,23 @@ export function McpWrapWizard({ onClose, onInstalled }: Props) {
     setDiscoverError(null);
     setDiscovering(true);
     try {
-      const [classified, suggested] = await Promise.all([
-        mcpDiscoverTools(command),
+      const [discovery, suggested] = await Promise.all([
+        mcpDiscoverTools(command, providedEnv()),
         mcpSuggestMetadata(command),
       ]);
+      const classified = discovery.tools;
+      if (discovery.required_env.length > 0) {
+        setEnvVars((prev) => [
+          ...prev,
+          ...discovery.required_env.filter(
+            (v) => !prev.some((p) => p.name === v.name)
+          ),
+        ]);
+      }
+      if (classified.length === 0 && discovery.required_env.length > 0) {
+        setDiscoverError(t("mcpWrap.envRequired"));
+        return;
+      }
       if (classified.length === 0) {
         setDiscoverError(t("mcpWrap.zeroTools"));
         return;
@@ -109,6 +128,16 @@ export function McpWrapWizard({ onClose, onInstalled }: Props) {
     }
   }
 
+  function providedEnv(): Record<string, string> {
+    return Object.fromEntries(
+      Object.entries(envValues).filter(([, v]) => v.trim() !== "")
+    );
+  }
+
+  const setEnvValue = useCallback((name: string, value: string) => {
+    setEnvValues((prev) => ({ ...prev, [name]: value }));
+  }, []);
+
   // -- Step 5: Build --
 
   async function handleBuild() {
@@ -132,10 +161,14 @@ export function McpWrapWizard({ onClose, onInstalled }: Props) {
       setBuildPhase(t("mcpWrap.buildingContainer"));
       await mcpGenerateAndInstall(
         command,
-        selectedTools,
+        tools,
+        selectedTools.length === tools.length
+          ? null
+          : selectedTools.map((t) => t.name),
         metadata,
         approved,
-        deferred
+        deferred,
+        providedEnv()
       );
 
       setBuildPhase(t("mcpWrap.done"));
@@ -225,6 +258,9 @@ export function McpWrapWizard({ onClose, onInstalled }: Props) {
                   command={command}
                   setCommand={setCommand}
                   discovering={discovering}
+                  envVars={envVars}
+                  envValues={envValues}
+                  setEnvValue={setEnvValue}
                   error={discoverError}
                   onDiscover={handleDiscover}
                   onClose={onClose}
@@ -279,6 +315,9 @@ function CommandStep({
   command,
   setCommand,
   discovering,
+  envVars,
+  envValues,
+  setEnvValue,
   error,
   onDiscover,
   onClose,
@@ -286,6 +325,9 @@ function CommandStep({
   command: string;
   setCommand: (v: string) => void;
   discovering: boolean;
+  envVars: McpEnvVar[];
+  envValues: Record<string, string>;
+  setEnvValue: (name: string, value: string) => void;
   error: string | null;
   onDiscover: () => void;
   onClose: () => void;
@@ -332,6 +374,28 @@ function CommandStep({
         })}
       </p>
 
+      {envVars.length > 0 && (
+        <div className="mb-4 space-y-2">
+          <label className="block text-[11px] font-medium text-default-500 uppercase tracking-wider">
+            {t("mcpWrap.environment")}
+          </label>
+          {envVars.map((v) => (
+            <Input
+              key={v.name}
+              label={v.name}
+              size="sm"
+              type={v.secret ? "password" : "text"}
+              value={envValues[v.name] ?? ""}
+              onValueChange={(value) => setEnvValue(v.name, value)}
+              variant="bordered"
+            />
+          ))}
+          <p className="text-[11px] text-default-400">
+            {t("mcpWrap.envStoredNote")}
+          </p>
+        </div>
+      )}
+
       {error && (
         <div className="mb-4 p-3 rounded-[8px] bg-danger-50/50 border border-danger/20">
           <p className="text-[12px] text-danger">{error}</p>
@@ -7,6 +7,8 @@ import { usePluginActions } from "../../hooks/usePlugins";
 import { checkImageAvailable } from "../../lib/tauri";
 import { ArrowLeft, Download, Loader2, AlertTriangle, ExternalLink, User, Clock, Scale, Hammer, RefreshCw, HardDrive, Cloud } from "lucide-react";
 import { timeAgo } from "../../lib/timeAgo";
+import { formatInstalls } from "../../lib/installCount";
+import { VerifiedBadge } from "./VerifiedBadge";
 import { Button, Card, CardBody, Chip } from "@heroui/react";
 
 interface Props {
@@ -68,7 +70,10 @@ export function PluginDetail({ entry, installedPlugin, onBack }: Props) {
       <Card><CardBody className="p-6">
         <div className="flex items-start justify-between mb-4">
           <div>
-            <h2 className="text-[18px] font-bold">{entry.name}</h2>
+            <h2 className="text-[18px] font-bold flex items-center gap-1.5">
+              {entry.name}
+              {entry.verified && <VerifiedBadge identity={entry.verified_identity} size={16} />}
+            </h2>
             <p className="text-[12px] text-default-500 mt-1 font-mono">
               v{entry.version} &middot; {entry.id}
             </p>
@@ -149,6 +154,12 @@ export function PluginDetail({ entry, installedPlugin, onBack }: Props) {
               {t("marketplace.published", { time: timeAgo(entry.created_at) })}
             </span>
           )}
+          {entry.install_count != null && (
+            <span className="flex items-center gap-1">
+              <Download size={11} strokeWidth={1.5} />
+              {formatInstalls(entry.install_count)}
+            </span>
+          )}
           {entry.license && (
             <span className="flex items-center gap-1">
               <Scale size={11} strokeWidth={1.5} />
@@ -170,6 +181,26 @@ export function PluginDetail({ entry, installedPlugin, onBack }: Props) {
 
         <p className="text-default-500 text-[13px] mb-6 leading-relaxed">{entry.description}</p>
 
+        {entry.screenshots && entry.screenshots.length > 0 && (
+          <div className="flex gap-3 overflow-x-auto mb-6 pb-1">
+            {entry.screenshots.map((shot) => (
+              <figure key={shot.url} className="flex-shrink-0">
+                <img
+                  src={shot.url}
+                  alt={shot.caption ?? entry.name}
+                  width={shot.width}
+                  height={shot.height}
+                  loading="lazy"
+                  className="h-48 w-auto rounded-[8px] border border-default-100 object-contain"
+                />
+                {shot.caption && (
+                  <figcaption className="mt-1 text-[11px] text-default-400">{shot.caption}</figcaption>
+                )}
+              </figure>
+            ))}
+          </div>
+        )}
+
         <div className="space-y-4">
           <div>
             <h4 className="text-[10px] font-semibold text-default-500 uppercase tracking-wider mb-2">
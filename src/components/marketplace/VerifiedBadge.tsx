@@ -0,0 +1,20 @@
+import { useTranslation } from "react-i18next";
+import { BadgeCheck } from "lucide-react";
+
+interface Props {
+  /** Domain or `github.com/<owner>` the publisher was verified by. */
+  identity?: string;
+  size?: number;
+}
+
+/** Marks a package whose registry verified the publisher's identity. */
+export function VerifiedBadge({ identity, size = 13 }: Props) {
+  const { t } = useTranslation("plugins");
+  const label = t("card.verifiedPublisher", { identity: identity ?? "" });
+
+  return (
+    <span title={label} aria-label={label} className="inline-flex text-primary flex-shrink-0">
+      <BadgeCheck size={size} strokeWidth={2} />
+    </span>
+  );
+}
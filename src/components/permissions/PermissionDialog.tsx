@@ -1,13 +1,15 @@
-import { useCallback, useState } from "react";
+import { useCallback, useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
-import type { Permission } from "../../types/permissions";
-import type { PluginManifest } from "../../types/plugin";
+import type { Permission, PermissionProfile } from "../../types/permissions";
+import type { PluginManifest, PublisherVerification } from "../../types/plugin";
 import { getPermissionInfo, allPermissions, getManifestScopes } from "../../types/permissions";
 import { useAppStore } from "../../stores/appStore";
+import { permissionProfileList } from "../../lib/tauri";
 import { Modal, ModalContent, Switch, Button, Chip } from "@heroui/react";
 import {
   ShieldCheck,
   ShieldX,
+  BadgeCheck,
   ArrowLeft,
   ArrowRight,
   Package,
@@ -29,7 +31,8 @@ const riskChipColors: Record<string, "success" | "warning" | "danger"> = {
 type Step = "info" | "permissions" | "mcp_tools";
 
 interface Props {
-  manifest: PluginManifest;
+  /** Registry previews carry publisher verification; local manifests don't. */
+  manifest: PluginManifest & Partial<PublisherVerification>;
   onApprove: (approved: Permission[], deferred: Permission[]) => void;
   onDeny: () => void;
 }
@@ -144,7 +147,7 @@ function InfoStep({
   onNext,
   onDeny,
 }: {
-  manifest: PluginManifest;
+  manifest: PluginManifest & Partial<PublisherVerification>;
   hasMoreSteps: boolean;
   onNext: () => void;
   onDeny: () => void;
@@ -188,6 +191,18 @@ function InfoStep({
             </a>
           </div>
         )}
+        {manifest.verified !== undefined && (
+          <div className="flex items-center justify-between py-2 border-b border-default-100">
+            <span className="text-[12px] text-default-500">{t("dialog.publisher")}</span>
+            {manifest.verified ? (
+              <Chip size="sm" variant="flat" color="primary" startContent={<BadgeCheck size={11} strokeWidth={1.5} />}>
+                {manifest.verified_identity}
+              </Chip>
+            ) : (
+              <Chip size="sm" variant="flat">{t("dialog.unverifiedPublisher")}</Chip>
+            )}
+          </div>
+        )}
         {/* TODO: Signature verification status */}
         <div className="flex items-center justify-between py-2">
           <span className="text-[12px] text-default-500">{t("common:status.verified")}</span>
@@ -197,6 +212,18 @@ function InfoStep({
         </div>
       </div>
 
+      {manifest.security?.writable_rootfs && (
+        <div className="mb-6 p-2.5 rounded-[8px] bg-warning-50/50 border border-warning/20">
+          <p className="flex items-center gap-1.5 text-[11px] font-semibold text-warning mb-1">
+            <AlertTriangle size={11} strokeWidth={1.5} />
+            {t("dialog.writableRootfs")}
+          </p>
+          <p className="text-[11px] text-default-500 leading-relaxed">
+            {manifest.security.writable_rootfs}
+          </p>
+        </div>
+      )}
+
       <div className="flex gap-3 justify-end">
         <Button variant="flat" onPress={onDeny}>
           {t("common:action.cancel")}
@@ -268,6 +295,12 @@ function PermissionsStep({
     }
     return initial;
   });
+  const [profiles, setProfiles] = useState<PermissionProfile[]>([]);
+  const [activeProfile, setActiveProfile] = useState<string | null>(null);
+
+  useEffect(() => {
+    permissionProfileList().then(setProfiles).catch(() => setProfiles([]));
+  }, []);
 
   // Extension availability — read from centralized store (polled by useExtensions)
   const storeExtensions = useAppStore((s) => s.installedExtensions);
@@ -292,9 +325,19 @@ function PermissionsStep({
   }
 
   function togglePerm(perm: string) {
+    setActiveProfile(null);
     setToggles((prev) => ({ ...prev, [perm]: !prev[perm] }));
   }
 
+  function applyProfile(profile: PermissionProfile) {
+    const next: Record<string, boolean> = {};
+    for (const perm of permissions) {
+      next[perm] = profile.permissions.includes(perm);
+    }
+    setToggles(next);
+    setActiveProfile(profile.name);
+  }
+
   function computeApprovedDeferred(): [Permission[], Permission[]] {
     const approved: Permission[] = [];
     const deferred: Permission[] = [];
@@ -337,6 +380,25 @@ function PermissionsStep({
       )}
       {deferredCount === 0 && <div className="mb-4" />}
 
+      {profiles.length > 0 && (
+        <div className="flex items-center gap-1.5 mb-3 flex-wrap">
+          <span className="text-[11px] text-default-500">{t("dialog.applyProfile")}</span>
+          {profiles.map((profile) => (
+            <Button
+              key={profile.name}
+              size="sm"
+              variant={activeProfile === profile.name ? "solid" : "flat"}
+              color={activeProfile === profile.name ? "primary" : "default"}
+              title={profile.description}
+              // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+              onPress={() => applyProfile(profile)}
+            >
+              {profile.name}
+            </Button>
+          ))}
+        </div>
+      )}
+
       <div
         ref={listRef}
         onScroll={handleScroll}
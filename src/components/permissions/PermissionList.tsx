@@ -1,24 +1,83 @@
-import { useCallback, useEffect, useState } from "react";
+import { useCallback, useEffect, useMemo, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { usePermissions } from "../../hooks/usePermissions";
+import i18n from "../../i18n";
+import { permissionSimulate } from "../../lib/tauri";
+import { useAppStore } from "../../stores/appStore";
 import { getPermissionInfo } from "../../types/permissions";
-import type { Permission, GrantedPermission } from "../../types/permissions";
-import { ChevronDown, FolderOpen, RotateCcw, X, ShieldCheck, Clock } from "lucide-react";
-import { Button, Modal, ModalContent, ModalHeader, ModalBody, ModalFooter, Chip } from "@heroui/react";
+import type {
+  Permission,
+  GrantedPermission,
+  PermissionChange,
+  PermissionSimulation,
+  RateCap,
+  SimulatedOutcome,
+} from "../../types/permissions";
+import {
+  ChevronDown,
+  FlaskConical,
+  FolderOpen,
+  Globe,
+  History,
+  RotateCcw,
+  X,
+  ShieldCheck,
+  ShieldBan,
+  Clock,
+  Terminal,
+} from "lucide-react";
+import {
+  Button,
+  Input,
+  Modal,
+  ModalContent,
+  ModalHeader,
+  ModalBody,
+  ModalFooter,
+  Chip,
+  Select,
+  SelectItem,
+  Switch,
+} from "@heroui/react";
 
 interface Props {
   pluginId: string;
 }
 
+/** Rate cap presets offered per grant. */
+const RATE_CAP_PRESETS: RateCap[] = [
+  { max_calls: 10, per_secs: 60 },
+  { max_calls: 60, per_secs: 60 },
+  { max_calls: 100, per_secs: 3600 },
+  { max_calls: 1000, per_secs: 86400 },
+];
+
+/** Select key for a cap: "max/secs", or "none". */
+function rateCapKey(cap: RateCap | null | undefined): string {
+  return cap ? `${cap.max_calls}/${cap.per_secs}` : "none";
+}
+
 export function PermissionList({ pluginId }: Props) {
   const { t } = useTranslation("permissions");
-  const { grants, loadGrants, revoke, unrevoke, removePath } = usePermissions();
+  const {
+    grants,
+    denies,
+    history,
+    loadGrants,
+    revoke,
+    unrevoke,
+    removePath,
+    setAlwaysPrompt,
+    setRateCap,
+    removeDeny,
+  } = usePermissions();
+  const permissionsVersion = useAppStore((s) => s.permissionsVersion);
   const [expandedPerms, setExpandedPerms] = useState<Set<string>>(new Set());
   const [confirmRestore, setConfirmRestore] = useState<string | null>(null);
 
   useEffect(() => {
     loadGrants(pluginId);
-  }, [pluginId, loadGrants]);
+  }, [pluginId, loadGrants, permissionsVersion]);
 
   function togglePerm(perm: string) {
     setExpandedPerms((prev) => {
@@ -35,7 +94,7 @@ export function PermissionList({ pluginId }: Props) {
     setConfirmRestore(null);
   }, [unrevoke, pluginId, confirmRestore]);
 
-  if (grants.length === 0) {
+  if (grants.length === 0 && denies.length === 0) {
     return (
       <p className="text-[11px] text-default-400">{t("list.noPermissions")}</p>
     );
@@ -60,6 +119,8 @@ export function PermissionList({ pluginId }: Props) {
           onToggle={togglePerm}
           onRevoke={revoke}
           onRemovePath={removePath}
+          onAlwaysPromptChange={setAlwaysPrompt}
+          onRateCapChange={setRateCap}
         />
       ))}
 
@@ -183,6 +244,53 @@ export function PermissionList({ pluginId }: Props) {
         </>
       )}
 
+      {/* Deny rules */}
+      {denies.length > 0 && (
+        <>
+          {grants.length > 0 && (
+            <div className="flex items-center gap-2 pt-2 pb-0.5">
+              <div className="flex-1 h-px bg-default-100" />
+              <span className="text-[10px] text-danger font-medium uppercase tracking-wide">
+                {t("list.denied")}
+              </span>
+              <div className="flex-1 h-px bg-default-100" />
+            </div>
+          )}
+
+          {denies.map((rule) => (
+            <div
+              key={`deny-${rule.permission}-${rule.scope ?? ""}`}
+              className="flex items-center justify-between p-2.5 rounded-[8px] border border-danger/20 bg-background"
+            >
+              <div className="flex items-center gap-2 min-w-0">
+                <ShieldBan size={12} strokeWidth={1.5} className="text-danger flex-shrink-0" />
+                <p className="text-[12px] font-medium font-mono">{rule.permission}</p>
+                <span className="text-[10px] text-default-400 font-mono truncate">
+                  {rule.scope ?? t("list.allScopes")}
+                </span>
+              </div>
+              <Button
+                isIconOnly
+                size="sm"
+                variant="light"
+                // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                onPress={() => removeDeny(pluginId, rule)}
+                title={t("list.removeDeny")}
+                className="flex-shrink-0 ml-2"
+              >
+                <X size={12} strokeWidth={1.5} />
+              </Button>
+            </div>
+          ))}
+        </>
+      )}
+
+      {/* Change history */}
+      <PermissionHistory changes={history} />
+
+      {/* Dry-run checker */}
+      <PermissionSimulator pluginId={pluginId} />
+
       {/* Confirm restore modal */}
       <Modal
         isOpen={confirmRestore !== null}
@@ -221,6 +329,143 @@ export function PermissionList({ pluginId }: Props) {
   );
 }
 
+/** "10 / min", "100 / hour", or "5 / 90s" for windows that aren't a whole minute, hour, or day. */
+function formatRateCap(cap: RateCap): string {
+  const t = i18n.t.bind(i18n);
+  if (cap.per_secs === 86400) return t("permissions:list.rateCapPerDay", { count: cap.max_calls });
+  if (cap.per_secs === 3600) return t("permissions:list.rateCapPerHour", { count: cap.max_calls });
+  if (cap.per_secs === 60) return t("permissions:list.rateCapPerMinute", { count: cap.max_calls });
+  return t("permissions:list.rateCapPerSeconds", { count: cap.max_calls, seconds: cap.per_secs });
+}
+
+/** Collapsible log of grant, revoke, and scope changes, newest first. */
+function PermissionHistory({ changes }: { changes: PermissionChange[] }) {
+  const { t } = useTranslation("permissions");
+  const [open, setOpen] = useState(false);
+  const handleToggle = useCallback(() => setOpen((o) => !o), []);
+  const entries = [...changes].reverse();
+
+  return (
+    <div className="pt-2">
+      <Button size="sm" variant="light" onPress={handleToggle}>
+        <History size={11} strokeWidth={1.5} />
+        {open ? t("history.hide") : t("history.show")}
+      </Button>
+      {open && (
+        <div className="mt-1 space-y-1 max-h-64 overflow-y-auto">
+          {entries.length === 0 ? (
+            <p className="text-[11px] text-default-400">{t("history.empty")}</p>
+          ) : (
+            entries.map((c, i) => (
+              <div
+                key={`${c.at}-${i}`}
+                className="flex items-center gap-2 px-2 py-1.5 rounded-[6px] bg-default-100 text-[11px]"
+              >
+                <span className="text-default-400 flex-shrink-0">{new Date(c.at).toLocaleString()}</span>
+                <span className="font-medium flex-shrink-0">{t(`history.action.${c.action}`)}</span>
+                <span className="font-mono truncate">{c.permission}</span>
+                {c.scope && <span className="font-mono text-default-500 truncate">{c.scope}</span>}
+                {(c.from || c.to) && (
+                  <span className="text-default-500 flex-shrink-0">
+                    {t(`history.state.${c.from ?? "none"}`)} → {t(`history.state.${c.to ?? "none"}`)}
+                  </span>
+                )}
+                {c.detail && <span className="text-default-400 truncate">{c.detail}</span>}
+                {c.actor === "system" && (
+                  <Chip size="sm" variant="flat">
+                    {t("history.automatic")}
+                  </Chip>
+                )}
+              </div>
+            ))
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+const OUTCOME_COLOR: Record<SimulatedOutcome, "success" | "danger" | "warning"> = {
+  allowed: "success",
+  denied: "danger",
+  needs_approval: "warning",
+};
+
+/** Dry-run a permission check to debug scope issues. Nothing is prompted or saved. */
+function PermissionSimulator({ pluginId }: { pluginId: string }) {
+  const { t } = useTranslation("permissions");
+  const [open, setOpen] = useState(false);
+  const [permission, setPermission] = useState("");
+  const [scope, setScope] = useState("");
+  const [result, setResult] = useState<PermissionSimulation | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const handleToggle = useCallback(() => setOpen((o) => !o), []);
+
+  const handleRun = useCallback(async () => {
+    try {
+      setResult(await permissionSimulate(pluginId, permission.trim(), scope.trim() || null));
+      setError(null);
+    } catch (e) {
+      setResult(null);
+      setError(String(e));
+    }
+  }, [pluginId, permission, scope]);
+
+  return (
+    <div>
+      <Button size="sm" variant="light" onPress={handleToggle}>
+        <FlaskConical size={11} strokeWidth={1.5} />
+        {open ? t("simulate.hide") : t("simulate.show")}
+      </Button>
+      {open && (
+        <div className="mt-1 space-y-2 p-2.5 rounded-[8px] bg-default-100">
+          <p className="text-[11px] text-default-500">{t("simulate.hint")}</p>
+          <div className="flex items-center gap-2">
+            <Input
+              size="sm"
+              variant="bordered"
+              aria-label={t("simulate.permission")}
+              placeholder={t("simulate.permissionPlaceholder")}
+              value={permission}
+              onValueChange={setPermission}
+            />
+            <Input
+              size="sm"
+              variant="bordered"
+              aria-label={t("simulate.scope")}
+              placeholder={t("simulate.scopePlaceholder")}
+              value={scope}
+              onValueChange={setScope}
+            />
+            <Button size="sm" color="primary" onPress={handleRun} isDisabled={!permission.trim()}>
+              {t("simulate.run")}
+            </Button>
+          </div>
+          {error && <p className="text-[11px] text-danger">{error}</p>}
+          {result && (
+            <div className="flex items-center gap-2 flex-wrap text-[11px]">
+              <Chip size="sm" variant="flat" color={OUTCOME_COLOR[result.outcome]}>
+                {t(`simulate.outcome.${result.outcome}`)}
+              </Chip>
+              <span className="text-default-500">{t(`simulate.reason.${result.reason}`)}</span>
+              {result.grant && (
+                <span className="font-mono text-default-400 truncate">
+                  {t("simulate.matchedGrant", { permission: result.grant.permission, state: result.grant.state })}
+                </span>
+              )}
+              {result.deny_rule && (
+                <span className="font-mono text-default-400 truncate">
+                  {t("simulate.matchedDeny", { scope: result.deny_rule.scope ?? t("list.allScopes") })}
+                </span>
+              )}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
 /** Active permission row with scope expansion support. */
 function ActivePermissionRow({
   grant,
@@ -230,6 +475,8 @@ function ActivePermissionRow({
   onToggle,
   onRevoke,
   onRemovePath,
+  onAlwaysPromptChange,
+  onRateCapChange,
 }: {
   grant: GrantedPermission;
   pluginId: string;
@@ -238,14 +485,24 @@ function ActivePermissionRow({
   onToggle: (perm: string) => void;
   onRevoke: (pluginId: string, permissions: Permission[]) => void;
   onRemovePath: (pluginId: string, permission: Permission, path: string) => void;
+  onAlwaysPromptChange: (pluginId: string, permission: Permission, alwaysPrompt: boolean) => void;
+  onRateCapChange: (pluginId: string, permission: Permission, rateCap: RateCap | null) => void;
 }) {
   const { t } = useTranslation("permissions");
   const info = getPermissionInfo(grant.permission);
   const isFs = fsPermissions.includes(grant.permission);
+  // network:{host-pattern} grants list the hosts approved at first use
+  const isNetworkHost =
+    grant.permission.startsWith("network:") &&
+    grant.permission !== "network:local" &&
+    grant.permission !== "network:internet";
+  // process:exec grants list the command patterns approved at first use
+  const isProcessExec = grant.permission === "process:exec";
   const hasPaths =
-    isFs &&
+    (isFs || isNetworkHost || isProcessExec) &&
     grant.approved_scopes !== null &&
     grant.approved_scopes !== undefined;
+  const ScopeIcon = isNetworkHost ? Globe : isProcessExec ? Terminal : FolderOpen;
   const paths = grant.approved_scopes ?? [];
   const isExpanded = expandedPerms.has(grant.permission);
 
@@ -253,6 +510,31 @@ function ActivePermissionRow({
   const handleRevoke = useCallback(() => {
     onRevoke(pluginId, [grant.permission as Permission]);
   }, [onRevoke, pluginId, grant.permission]);
+  const handleAlwaysPrompt = useCallback(
+    (value: boolean) => onAlwaysPromptChange(pluginId, grant.permission as Permission, value),
+    [onAlwaysPromptChange, pluginId, grant.permission]
+  );
+  // Presets plus the grant's own cap if it was set some other way (e.g. an import)
+  const rateCapOptions = useMemo(() => {
+    const caps = [...RATE_CAP_PRESETS];
+    if (grant.rate_cap && !caps.some((c) => rateCapKey(c) === rateCapKey(grant.rate_cap))) {
+      caps.push(grant.rate_cap);
+    }
+    return [
+      { key: "none", label: t("list.rateCapNone"), cap: null },
+      ...caps.map((cap) => ({ key: rateCapKey(cap), label: formatRateCap(cap), cap })),
+    ];
+  }, [grant.rate_cap, t]);
+  const rateCapSelection = useMemo(() => [rateCapKey(grant.rate_cap)], [grant.rate_cap]);
+  const handleRateCap = useCallback(
+    (keys: "all" | Set<React.Key>) => {
+      const key = keys === "all" ? undefined : String(Array.from(keys)[0] ?? "");
+      if (!key) return;
+      const option = rateCapOptions.find((o) => o.key === key);
+      if (option) onRateCapChange(pluginId, grant.permission as Permission, option.cap);
+    },
+    [onRateCapChange, pluginId, grant.permission, rateCapOptions]
+  );
 
   return (
     <div className="rounded-[8px] border border-default-100 bg-background overflow-hidden">
@@ -278,9 +560,17 @@ function ActivePermissionRow({
               </p>
               {hasPaths && (
                 <span className="text-[10px] text-default-400 font-mono flex-shrink-0">
-                  {paths.length === 0
-                    ? t("list.noPathsApproved")
-                    : t("list.pathCount", { count: paths.length })}
+                  {isNetworkHost
+                    ? paths.length === 0
+                      ? t("list.noHostsApproved")
+                      : t("list.hostCount", { count: paths.length })
+                    : isProcessExec
+                      ? paths.length === 0
+                        ? t("list.noCommandsApproved")
+                        : t("list.commandCount", { count: paths.length })
+                      : paths.length === 0
+                      ? t("list.noPathsApproved")
+                      : t("list.pathCount", { count: paths.length })}
                 </span>
               )}
               {isFs && grant.approved_scopes === null && (
@@ -288,19 +578,42 @@ function ActivePermissionRow({
                   {t("common:status.unrestricted")}
                 </Chip>
               )}
+              {grant.expires_at && (
+                <Chip size="sm" variant="flat" startContent={<Clock size={10} strokeWidth={1.5} />}>
+                  {t("list.expires", { time: new Date(grant.expires_at).toLocaleString() })}
+                </Chip>
+              )}
             </div>
             <p className="text-[11px] text-default-500 mt-0.5">
               {info?.description ?? t("permissions:meta.unknown")}
             </p>
           </div>
         </div>
-        <Button
-          color="danger"
-          onPress={handleRevoke}
-          className="flex-shrink-0 ml-2"
-        >
-          {t("list.revoke")}
-        </Button>
+        <div className="flex items-center gap-2 flex-shrink-0 ml-2">
+          <Select
+            size="sm"
+            aria-label={t("list.rateCap")}
+            title={t("list.rateCapTooltip")}
+            selectedKeys={rateCapSelection}
+            onSelectionChange={handleRateCap}
+            className="w-36"
+          >
+            {rateCapOptions.map((option) => (
+              <SelectItem key={option.key}>{option.label}</SelectItem>
+            ))}
+          </Select>
+          <Switch
+            size="sm"
+            isSelected={grant.always_prompt ?? false}
+            onValueChange={handleAlwaysPrompt}
+            title={t("list.alwaysPromptTooltip")}
+          >
+            <span className="text-[11px] text-default-500">{t("list.alwaysPrompt")}</span>
+          </Switch>
+          <Button color="danger" onPress={handleRevoke}>
+            {t("list.revoke")}
+          </Button>
+        </div>
       </div>
 
       {/* Approved paths (expanded) */}
@@ -308,7 +621,11 @@ function ActivePermissionRow({
         <div className="px-2.5 pb-2.5 border-t border-default-100">
           {paths.length === 0 ? (
             <p className="text-[11px] text-default-400 pt-2">
-              {t("list.noDirectoriesApproved")}
+              {isNetworkHost
+                ? t("list.noHostsApprovedHelp")
+                : isProcessExec
+                  ? t("list.noCommandsApprovedHelp")
+                  : t("list.noDirectoriesApproved")}
             </p>
           ) : (
             <div className="pt-2 space-y-1">
@@ -318,7 +635,7 @@ function ActivePermissionRow({
                   className="flex items-center justify-between gap-2 px-2 py-1.5 rounded-[6px] bg-default-100"
                 >
                   <div className="flex items-center gap-2 min-w-0">
-                    <FolderOpen
+                    <ScopeIcon
                       size={12}
                       strokeWidth={1.5}
                       className="text-primary flex-shrink-0"
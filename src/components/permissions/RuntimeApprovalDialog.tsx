@@ -9,15 +9,17 @@ import {
   Puzzle,
   AlertTriangle,
   Link,
+  Terminal,
 } from "lucide-react";
-import { runtimeApprovalRespond } from "../../lib/tauri";
+import { permissionDenyAdd, runtimeApprovalRespond } from "../../lib/tauri";
 import { useOsNotification } from "../../hooks/useOsNotification";
 import { getPermissionInfo } from "../../types/permissions";
 import type {
   ApprovalDecision,
+  RuntimeApprovalBatchItem,
   RuntimeApprovalRequest,
 } from "../../types/permissions";
-import { Modal, ModalContent, Button, Chip } from "@heroui/react";
+import { Modal, ModalContent, Button, Checkbox, CheckboxGroup, Chip, Select, SelectItem } from "@heroui/react";
 import i18n from "../../i18n";
 
 /** Derive a human-readable header from the approval category. */
@@ -43,6 +45,22 @@ function resolveHeader(req: RuntimeApprovalRequest): {
     };
   }
 
+  // Rate-capped grant went over its cap
+  if (req.category === "rate_cap") {
+    return {
+      icon: ShieldAlert,
+      title: t("permissions:runtime.rateCapTitle"),
+      subtitle: t("permissions:runtime.rateCapSubtitle", {
+        pluginName: req.plugin_name,
+        permission: req.permission,
+        count: Number(ctx.max_calls ?? 0),
+        seconds: ctx.per_secs ?? "?",
+      }),
+      iconBg: "bg-warning-50",
+      iconColor: "text-warning",
+    };
+  }
+
   // Extension high-risk operation
   if (req.category.startsWith("extension:")) {
     const extName = ctx.extension_display_name ?? ctx.extension ?? req.category;
@@ -107,6 +125,17 @@ function resolveHeader(req: RuntimeApprovalRequest): {
     };
   }
 
+  // Command execution
+  if (req.category === "process_exec") {
+    return {
+      icon: Terminal,
+      title: t("permissions:runtime.commandExecution"),
+      subtitle: t("permissions:runtime.commandSubtitle", { pluginName: req.plugin_name }),
+      iconBg: "bg-red-500/10",
+      iconColor: "text-red-400",
+    };
+  }
+
   // MCP tool invocation
   if (req.category === "mcp_tool") {
     const toolName = ctx.tool_name ?? "a tool";
@@ -129,6 +158,14 @@ function resolveHeader(req: RuntimeApprovalRequest): {
   };
 }
 
+/** The scope value a request is about — what "Never allow" blocks and what a batch item is labelled with. */
+function requestScope(category: string, ctx: Record<string, string>): string | undefined {
+  if (category === "filesystem") return ctx.parent_dir;
+  if (category === "network") return ctx.host;
+  if (category === "process_exec") return ctx.command_line;
+  return ctx.scope_value;
+}
+
 /** Cooldown duration in seconds based on risk level. */
 function cooldownFor(riskLevel: string | undefined): number {
   switch (riskLevel) {
@@ -159,7 +196,7 @@ export function RuntimeApprovalDialog() {
 
         setQueue((prev) => [...prev, event.payload]);
         const header = resolveHeader(event.payload);
-        notify(i18n.t("permissions:runtime.notificationTitle"), header.subtitle, 1);
+        notify(i18n.t("permissions:runtime.notificationTitle"), header.subtitle, 1, "permissions.approval");
       }
     );
     return () => {
@@ -186,26 +223,55 @@ export function RuntimeApprovalDialog() {
     return () => clearTimeout(timer);
   }, [cooldown]);
 
-  async function respond(decision: ApprovalDecision) {
+  /**
+   * Answer the current request. For a coalesced batch every item gets its own
+   * response; items not in `selected` are denied.
+   */
+  async function respond(
+    decision: ApprovalDecision,
+    extra?: Record<string, string>,
+    selected?: Set<string>
+  ) {
     if (!current) return;
-    try {
-      await runtimeApprovalRespond(
-        current.id,
-        decision,
-        current.plugin_id,
-        current.category,
-        {
-          ...current.context,
-          permission: current.permission,
-        }
-      );
-    } catch (err) {
-      console.error("[RuntimeApproval] respond failed:", err);
+    const items = current.batch ?? [{ id: current.id, context: current.context }];
+    for (const item of items) {
+      try {
+        await runtimeApprovalRespond(
+          item.id,
+          selected && !selected.has(item.id) ? "deny" : decision,
+          current.plugin_id,
+          current.category,
+          {
+            ...item.context,
+            ...extra,
+            permission: current.permission,
+          }
+        );
+      } catch (err) {
+        console.error("[RuntimeApproval] respond failed:", err);
+      }
     }
     seenIds.current.delete(current.id);
     setQueue((prev) => prev.slice(1));
   }
 
+  /** Deny and add a rule so matching requests are refused without asking. */
+  async function neverAllow(selected?: Set<string>) {
+    if (!current) return;
+    const items = (current.batch ?? [{ id: current.id, context: current.context }]).filter(
+      (item) => !selected || selected.has(item.id)
+    );
+    const scopes = new Set(items.map((item) => requestScope(current.category, item.context) ?? null));
+    for (const scope of scopes) {
+      try {
+        await permissionDenyAdd(current.plugin_id, current.permission, scope);
+      } catch (err) {
+        console.error("[RuntimeApproval] deny rule failed:", err);
+      }
+    }
+    await respond("deny");
+  }
+
   const handleOpenChange = useCallback(
     (open: boolean) => { if (!open) respond("deny"); },
     // eslint-disable-next-line react-hooks/exhaustive-deps
@@ -227,10 +293,12 @@ export function RuntimeApprovalDialog() {
       <ModalContent>
         {current && (
           <RuntimeApprovalContent
+            key={current.id}
             current={current}
             queue={queue}
             cooldown={cooldown}
             respond={respond}
+            neverAllow={neverAllow}
           />
         )}
       </ModalContent>
@@ -244,25 +312,51 @@ function RuntimeApprovalContent({
   queue,
   cooldown,
   respond,
+  neverAllow,
 }: {
   current: RuntimeApprovalRequest;
   queue: RuntimeApprovalRequest[];
   cooldown: number;
-  respond: (decision: ApprovalDecision) => void;
+  respond: (decision: ApprovalDecision, extra?: Record<string, string>, selected?: Set<string>) => void;
+  neverAllow: (selected?: Set<string>) => void;
 }) {
   const { t } = useTranslation("permissions");
   const header = resolveHeader(current);
   const HeaderIcon = header.icon;
   const isHighRisk = current.context.risk_level === "high";
+  // Always-prompt grants can only be approved for this one call
+  const allowOnceOnly = isHighRisk || current.context.always_prompt === "true";
   const isDeferred = current.category === "deferred_permission";
+  // Deferred and rate-cap requests carry no per-request scope
+  const isWholePermission = isDeferred || current.category === "rate_cap";
   const isExtension =
     current.category.startsWith("extension:") ||
     current.category.startsWith("extension_scope:");
-  const approveDisabled = cooldown > 0;
+  const [fsScope, setFsScope] = useState(current.context.parent_dir ?? "");
+  const [cmdScope, setCmdScope] = useState(() => commandScopeOptions(current.context)[0] ?? "");
+  // Coalesced requests: every item starts selected; deferred and rate-cap
+  // batches are identical requests, so there is nothing to choose between
+  const batch = current.batch && current.batch.length > 1 ? current.batch : null;
+  const showBatchList = batch !== null && !isWholePermission;
+  const [selectedIds, setSelectedIds] = useState<string[]>(() => batch?.map((item) => item.id) ?? []);
+  const selected = useMemo(() => (showBatchList ? new Set(selectedIds) : undefined), [showBatchList, selectedIds]);
+  const approveDisabled = cooldown > 0 || (showBatchList && selectedIds.length === 0);
 
   const handleDeny = useCallback(() => respond("deny"), [respond]);
-  const handleApproveOnce = useCallback(() => respond("approve_once"), [respond]);
-  const handleApprove = useCallback(() => respond("approve"), [respond]);
+  const handleApproveOnce = useCallback(
+    () => respond("approve_once", undefined, selected),
+    [respond, selected]
+  );
+  const handleApprove = useCallback(
+    // A widened filesystem scope or command pattern only applies to a single request;
+    // batch items persist their own parent directory
+    () => {
+      const scope = current.category === "process_exec" ? cmdScope : fsScope;
+      respond("approve", scope && !batch ? { approved_scope: scope } : undefined, selected);
+    },
+    [respond, current.category, cmdScope, fsScope, batch, selected]
+  );
+  const handleNeverAllow = useCallback(() => neverAllow(selected), [neverAllow, selected]);
 
   return (
     <>
@@ -289,12 +383,21 @@ function RuntimeApprovalContent({
 
       {/* Category-specific content */}
       <div className="px-6 pb-4">
-        {isDeferred ? (
+        {isWholePermission ? (
           <DeferredPermissionDetail context={current.context} permission={current.permission} />
         ) : current.category === "oauth_authorize" ? (
           <OAuthConsentDetail context={current.context} />
+        ) : batch && !isWholePermission ? (
+          <BatchDetail
+            category={current.category}
+            items={batch}
+            selectedIds={selectedIds}
+            onSelectionChange={setSelectedIds}
+          />
         ) : current.category === "filesystem" ? (
-          <FilesystemDetail context={current.context} />
+          <FilesystemDetail context={current.context} scope={fsScope} onScopeChange={setFsScope} />
+        ) : current.category === "process_exec" ? (
+          <ProcessExecDetail context={current.context} scope={cmdScope} onScopeChange={setCmdScope} />
         ) : isExtension ? (
           <ExtensionDetail
             context={current.context}
@@ -318,11 +421,16 @@ function RuntimeApprovalContent({
 
       {/* Actions */}
       <div className="flex gap-3 justify-end px-6 pb-6">
+        {current.category !== "oauth_authorize" && (
+          <Button variant="light" color="danger" onPress={handleNeverAllow}>
+            {t("runtime.neverAllow")}
+          </Button>
+        )}
         <Button onPress={handleDeny}>
           <ShieldX size={14} strokeWidth={1.5} />
           {t("common:action.deny")}
         </Button>
-        {isHighRisk ? (
+        {allowOnceOnly ? (
           <Button
             color="primary"
             isDisabled={approveDisabled}
@@ -355,6 +463,44 @@ function RuntimeApprovalContent({
   );
 }
 
+/** Multi-select list of coalesced requests. Unselected items are denied. */
+function BatchDetail({
+  category,
+  items,
+  selectedIds,
+  onSelectionChange,
+}: {
+  category: string;
+  items: RuntimeApprovalBatchItem[];
+  selectedIds: string[];
+  onSelectionChange: (ids: string[]) => void;
+}) {
+  const { t } = useTranslation("permissions");
+  return (
+    <div className="p-3 rounded-[8px] bg-background border border-default-100">
+      <p className="text-[11px] text-default-500 mb-1">
+        {t("runtime.batchCount", { count: items.length })}
+      </p>
+      <p className="text-[11px] text-default-400 mb-2">{t("runtime.batchHint")}</p>
+      <CheckboxGroup
+        size="sm"
+        aria-label={t("runtime.batchCount", { count: items.length })}
+        value={selectedIds}
+        onValueChange={onSelectionChange}
+        className="max-h-[30vh] overflow-y-auto"
+      >
+        {items.map((item) => (
+          <Checkbox key={item.id} value={item.id}>
+            <span className="text-[12px] font-mono break-all">
+              {item.context.path ?? requestScope(category, item.context) ?? item.id}
+            </span>
+          </Checkbox>
+        ))}
+      </CheckboxGroup>
+    </div>
+  );
+}
+
 function DeferredPermissionDetail({
   context,
   permission,
@@ -410,8 +556,38 @@ function DeferredPermissionDetail({
   );
 }
 
-function FilesystemDetail({ context }: { context: Record<string, string> }) {
+/**
+ * Scopes the user can persist on "Allow": the parent directory, up to three
+ * ancestors (never the filesystem root), and a glob for files of the same type.
+ */
+function filesystemScopeOptions(path: string | undefined, parentDir: string): string[] {
+  const options = [parentDir];
+  let dir = parentDir;
+  for (let i = 0; i < 3; i++) {
+    const idx = dir.lastIndexOf("/");
+    if (idx <= 0) break;
+    dir = dir.slice(0, idx);
+    options.push(dir);
+  }
+  const ext = path?.match(/\.([A-Za-z0-9]+)$/)?.[1];
+  if (ext) options.push(`${parentDir}/**/*.${ext}`);
+  return options;
+}
+
+function FilesystemDetail({
+  context,
+  scope,
+  onScopeChange,
+}: {
+  context: Record<string, string>;
+  scope: string;
+  onScopeChange: (scope: string) => void;
+}) {
   const { t } = useTranslation("permissions");
+  const options = useMemo(
+    () => (context.parent_dir ? filesystemScopeOptions(context.path, context.parent_dir) : []),
+    [context.path, context.parent_dir]
+  );
   return (
     <div className="space-y-2">
       <div className="p-3 rounded-[8px] bg-background border border-default-100">
@@ -425,9 +601,87 @@ function FilesystemDetail({ context }: { context: Record<string, string> }) {
           <p className="text-[11px] text-default-500 mb-1">
             {t("runtime.allowGrantsAccess")}
           </p>
-          <p className="text-[12px] text-primary font-mono break-all leading-relaxed">
-            {context.parent_dir}
+          <Select
+            size="sm"
+            aria-label={t("runtime.allowGrantsAccess")}
+            // eslint-disable-next-line react-perf/jsx-no-new-array-as-prop
+            selectedKeys={[scope]}
+            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+            onSelectionChange={(keys) => {
+              const selected = Array.from(keys)[0];
+              if (selected) onScopeChange(String(selected));
+            }}
+            // eslint-disable-next-line react-perf/jsx-no-new-object-as-prop
+            classNames={{ value: "text-[12px] font-mono text-primary" }}
+          >
+            {options.map((opt) => (
+              <SelectItem key={opt} className="font-mono">{opt}</SelectItem>
+            ))}
+          </Select>
+        </div>
+      )}
+    </div>
+  );
+}
+
+/** Command patterns the user can persist on "Allow", narrowest first, as suggested by the host. */
+function commandScopeOptions(context: Record<string, string>): string[] {
+  try {
+    const options: unknown = JSON.parse(context.scope_options ?? "[]");
+    if (Array.isArray(options) && options.length > 0) return options.map(String);
+  } catch {
+    // Fall through to the exact command line
+  }
+  return context.command_line ? [context.command_line] : [];
+}
+
+function ProcessExecDetail({
+  context,
+  scope,
+  onScopeChange,
+}: {
+  context: Record<string, string>;
+  scope: string;
+  onScopeChange: (scope: string) => void;
+}) {
+  const { t } = useTranslation("permissions");
+  const options = useMemo(() => commandScopeOptions(context), [context]);
+  return (
+    <div className="space-y-2">
+      <div className="p-3 rounded-[8px] bg-background border border-default-100">
+        <p className="text-[11px] text-default-500 mb-1">{t("runtime.command")}</p>
+        <p className="text-[12px] font-mono break-all leading-relaxed">
+          {context.command_line ?? context.command ?? "unknown"}
+        </p>
+        {context.working_dir && (
+          <>
+            <p className="text-[11px] text-default-500 mt-2 mb-1">{t("runtime.workingDir")}</p>
+            <p className="text-[12px] font-mono break-all">{context.working_dir}</p>
+          </>
+        )}
+      </div>
+      {options.length > 0 && context.always_prompt !== "true" && (
+        <div className="p-3 rounded-[8px] bg-background border border-default-100">
+          <p className="text-[11px] text-default-500 mb-1">
+            {t("runtime.allowGrantsCommands")}
           </p>
+          <Select
+            size="sm"
+            aria-label={t("runtime.allowGrantsCommands")}
+            // eslint-disable-next-line react-perf/jsx-no-new-array-as-prop
+            selectedKeys={[scope]}
+            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+            onSelectionChange={(keys) => {
+              const selected = Array.from(keys)[0];
+              if (selected) onScopeChange(String(selected));
+            }}
+            // eslint-disable-next-line react-perf/jsx-no-new-object-as-prop
+            classNames={{ value: "text-[12px] font-mono text-primary" }}
+          >
+            {options.map((opt) => (
+              <SelectItem key={opt} className="font-mono">{opt}</SelectItem>
+            ))}
+          </Select>
         </div>
       )}
     </div>
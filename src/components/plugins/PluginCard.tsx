@@ -3,8 +3,10 @@ import { useTranslation } from "react-i18next";
 import type { InstalledPlugin, RegistryEntry } from "../../types/plugin";
 import type { PluginStatus } from "../../types/plugin";
 import { timeAgo } from "../../lib/timeAgo";
+import { formatInstalls } from "../../lib/installCount";
+import { VerifiedBadge } from "../marketplace/VerifiedBadge";
 import { Card, CardBody, Chip } from "@heroui/react";
-import { HardDrive, Cloud } from "lucide-react";
+import { HardDrive, Cloud, Download } from "lucide-react";
 
 const statusColor: Record<PluginStatus, "success" | "default" | "danger" | "warning"> = {
   running: "success",
@@ -95,7 +97,10 @@ export function RegistryPluginCard({
             </div>
           )}
           <div>
-            <h3 className="text-[13px] font-semibold">{entry.name}</h3>
+            <h3 className="text-[13px] font-semibold flex items-center gap-1">
+              {entry.name}
+              {entry.verified && <VerifiedBadge identity={entry.verified_identity} size={12} />}
+            </h3>
             <p className="text-[11px] text-default-500 font-mono">
               v{entry.version}
               {entry.author_url ? (
@@ -130,9 +135,20 @@ export function RegistryPluginCard({
         {entry.source && (
           <Chip size="sm" variant="flat" color="secondary">{entry.source}</Chip>
         )}
+        {entry.conflicts && entry.conflicts.length > 0 && (
+          <Chip size="sm" variant="flat" color="warning">
+            {t("card.alsoIn", { sources: entry.conflicts.join(", ") })}
+          </Chip>
+        )}
         {entry.categories.map((cat) => (
           <Chip key={cat} size="sm" variant="flat">{cat}</Chip>
         ))}
+        {entry.install_count != null && (
+          <span className="flex items-center gap-0.5 text-[10px] text-default-400">
+            <Download size={10} strokeWidth={1.5} />
+            {formatInstalls(entry.install_count)}
+          </span>
+        )}
         {entry.created_at && (
           <span className="text-[10px] text-default-400 ml-auto">
             {timeAgo(entry.created_at)}
@@ -50,6 +50,7 @@ import {
   ModalHeader,
   ModalBody,
   ModalFooter,
+  Progress,
   useDisclosure,
 } from "@heroui/react";
 
@@ -151,7 +152,7 @@ export const PluginViewport = memo(function PluginViewport({ pluginId }: Props)
       </div>
 
       {busyAction && (
-        <BusyOverlay action={busyAction} pluginName={plugin.manifest.name} />
+        <BusyOverlay action={busyAction} pluginId={pluginId} pluginName={plugin.manifest.name} />
       )}
     </div>
   );
@@ -527,8 +528,9 @@ function HeadlessPluginStatus({ plugin }: { plugin: InstalledPlugin }) {
   );
 }
 
-function BusyOverlay({ action, pluginName }: { action: PluginAction; pluginName: string }) {
+function BusyOverlay({ action, pluginId, pluginName }: { action: PluginAction; pluginId: string; pluginName: string }) {
   const { t } = useTranslation("plugins");
+  const progress = useAppStore((s) => s.busyProgress[pluginId] ?? null);
 
   const overlayConfig: Record<
     PluginAction,
@@ -588,7 +590,17 @@ function BusyOverlay({ action, pluginName }: { action: PluginAction; pluginName:
             {config.sub}
           </p>
         </div>
-        <Loader2 size={20} strokeWidth={1.5} className="text-default-500 animate-spin" />
+        {progress !== null ? (
+          <Progress
+            aria-label={config.label}
+            size="sm"
+            value={progress}
+            showValueLabel
+            className="w-48"
+          />
+        ) : (
+          <Loader2 size={20} strokeWidth={1.5} className="text-default-500 animate-spin" />
+        )}
       </div>
     </div>
   );
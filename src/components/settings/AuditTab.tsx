@@ -1,10 +1,12 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
 import { useTranslation } from "react-i18next";
-import { auditQuery, auditCount, auditExport } from "../../lib/tauri";
-import type { AuditLogRow } from "../../types/audit";
+import { auditQuery, auditCount, auditExport, auditVerify } from "../../lib/tauri";
+import { useAppStore } from "../../stores/appStore";
+import type { AuditChainIssue, AuditLogRow } from "../../types/audit";
 import {
   ScrollText,
   Download,
+  ShieldCheck,
   ChevronDown,
   Check,
   X,
@@ -195,6 +197,31 @@ export function AuditTab() {
     });
   }
 
+  function describeIssue(issue: AuditChainIssue): string {
+    switch (issue.kind) {
+      case "modified":
+        return t("auditTab.issueModified", { id: issue.id });
+      case "missing":
+        return t("auditTab.issueMissing", { id: issue.before_id });
+      case "truncated":
+        return t("auditTab.issueTruncated");
+    }
+  }
+
+  async function handleVerify() {
+    const { addNotification } = useAppStore.getState();
+    try {
+      const report = await auditVerify();
+      if (report.issue) {
+        addNotification(t("auditTab.verifyFailed", { detail: describeIssue(report.issue) }), "error");
+      } else {
+        addNotification(t("auditTab.verifyOk", { count: report.verified }), "success");
+      }
+    } catch (e) {
+      addNotification(String(e), "error");
+    }
+  }
+
   async function handleExport() {
     try {
       const json = await auditExport({});
@@ -219,14 +246,24 @@ export function AuditTab() {
               <ScrollText size={15} strokeWidth={1.5} className="text-default-500" />
               <h3 className="text-[14px] font-semibold">{t("auditTab.title")}</h3>
             </div>
-            <Button
-              size="sm"
-              variant="flat"
-              startContent={<Download size={12} strokeWidth={1.5} />}
-              onPress={handleExport}
-            >
-              {t("auditTab.export")}
-            </Button>
+            <div className="flex items-center gap-2">
+              <Button
+                size="sm"
+                variant="flat"
+                startContent={<ShieldCheck size={12} strokeWidth={1.5} />}
+                onPress={handleVerify}
+              >
+                {t("auditTab.verify")}
+              </Button>
+              <Button
+                size="sm"
+                variant="flat"
+                startContent={<Download size={12} strokeWidth={1.5} />}
+                onPress={handleExport}
+              >
+                {t("auditTab.export")}
+              </Button>
+            </div>
           </div>
 
           <p className="text-[11px] text-default-400 mb-4">
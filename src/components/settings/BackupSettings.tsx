@@ -0,0 +1,172 @@
+import { useCallback, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { relaunch } from "@tauri-apps/plugin-process";
+import { Archive, Download, Upload } from "lucide-react";
+import { Button, Card, CardBody, Input, Switch } from "@heroui/react";
+import { nexusBackup, nexusBackupInfo, nexusRestore } from "../../lib/tauri";
+import type { BackupInfo } from "../../lib/tauri";
+import { formatBytes } from "../../lib/formatBytes";
+import { useAppStore } from "../../stores/appStore";
+
+const BACKUP_FILTERS = [{ name: "Nexus backup", extensions: ["gz"] }];
+
+export function BackupSettings() {
+  const { t } = useTranslation("settings");
+  const [includeVolumes, setIncludeVolumes] = useState(true);
+  const [passphrase, setPassphrase] = useState("");
+  const [busy, setBusy] = useState<"backup" | "restore" | null>(null);
+  const [restorePath, setRestorePath] = useState<string | null>(null);
+  const [restoreInfo, setRestoreInfo] = useState<BackupInfo | null>(null);
+  const [restorePassphrase, setRestorePassphrase] = useState("");
+
+  const handleBackup = useCallback(async () => {
+    const { addNotification } = useAppStore.getState();
+    const path = await save({
+      defaultPath: `nexus-backup-${new Date().toISOString().slice(0, 10)}.tar.gz`,
+      filters: BACKUP_FILTERS,
+    });
+    if (!path) return;
+    setBusy("backup");
+    try {
+      const report = await nexusBackup(path, includeVolumes, passphrase);
+      addNotification(
+        t("backup.created", { count: report.files + report.volumes, size: formatBytes(report.bytes) }),
+        "success"
+      );
+      if (report.skipped_volumes.length > 0) {
+        addNotification(t("backup.volumesSkipped", { plugins: report.skipped_volumes.join(", ") }), "info");
+      }
+      if (!report.store_key) {
+        addNotification(t("backup.storeKeyNotIncluded"), "info");
+      }
+    } catch (e) {
+      addNotification(t("backup.failed", { error: String(e) }), "error");
+    } finally {
+      setBusy(null);
+    }
+  }, [includeVolumes, passphrase, t]);
+
+  const handleChooseRestore = useCallback(async () => {
+    const selected = await open({ multiple: false, filters: BACKUP_FILTERS });
+    if (!selected) return;
+    setBusy("restore");
+    try {
+      // Checked up front, so the warning below says what this backup brings back
+      setRestoreInfo(await nexusBackupInfo(selected));
+      setRestorePassphrase("");
+      setRestorePath(selected);
+    } catch (e) {
+      useAppStore.getState().addNotification(t("backup.restoreFailed", { error: String(e) }), "error");
+    } finally {
+      setBusy(null);
+    }
+  }, [t]);
+
+  const handleCancelRestore = useCallback(() => {
+    setRestorePath(null);
+    setRestoreInfo(null);
+  }, []);
+
+  const handleConfirmRestore = useCallback(async () => {
+    if (!restorePath) return;
+    const { addNotification } = useAppStore.getState();
+    setBusy("restore");
+    try {
+      const report = await nexusRestore(restorePath, restorePassphrase);
+      if (report.missing_images.length > 0) {
+        addNotification(t("backup.imagesMissing", { plugins: report.missing_images.join(", ") }), "info");
+      }
+      if (report.skipped_volumes.length > 0) {
+        addNotification(t("backup.volumesNotRestored", { plugins: report.skipped_volumes.join(", ") }), "info");
+      }
+      if (!report.store_key) {
+        addNotification(t("backup.storeKeyNotRestored"), "info");
+      }
+      addNotification(t("backup.restored"), "success");
+      // The restored state is loaded at startup
+      await relaunch();
+    } catch (e) {
+      addNotification(t("backup.restoreFailed", { error: String(e) }), "error");
+      setBusy(null);
+      setRestorePath(null);
+      setRestoreInfo(null);
+    }
+  }, [restorePath, restorePassphrase, t]);
+
+  return (
+    <Card>
+      <CardBody>
+        <div className="flex items-center gap-2 mb-1">
+          <Archive size={16} className="text-default-500" />
+          <h3 className="text-sm font-semibold">{t("backup.title")}</h3>
+        </div>
+        <p className="text-xs text-default-400 mb-4">{t("backup.description")}</p>
+
+        <div className="flex items-center justify-between mb-4">
+          <div>
+            <p className="text-sm">{t("backup.includeVolumes")}</p>
+            <p className="text-xs text-default-400 mt-1">{t("backup.includeVolumesHint")}</p>
+          </div>
+          <Switch isSelected={includeVolumes} onValueChange={setIncludeVolumes} isDisabled={busy !== null} />
+        </div>
+
+        <div className="mb-4">
+          <p className="text-sm">{t("backup.passphrase")}</p>
+          <p className="text-xs text-default-400 mt-1 mb-2">{t("backup.passphraseHint")}</p>
+          <Input
+            type="password"
+            value={passphrase}
+            onValueChange={setPassphrase}
+            placeholder={t("backup.passphrasePlaceholder")}
+            variant="bordered"
+            isDisabled={busy !== null}
+          />
+        </div>
+
+        {restorePath ? (
+          <div className="space-y-3">
+            <p className="text-xs text-warning">{t("backup.restoreWarning", { path: restorePath })}</p>
+            {restoreInfo?.store_key ? (
+              <>
+                <Input
+                  type="password"
+                  value={restorePassphrase}
+                  onValueChange={setRestorePassphrase}
+                  placeholder={t("backup.passphrasePlaceholder")}
+                  variant="bordered"
+                  isDisabled={busy !== null}
+                />
+                {!restorePassphrase && <p className="text-xs text-warning">{t("backup.storeKeyNeedsPassphrase")}</p>}
+              </>
+            ) : (
+              <p className="text-xs text-warning">{t("backup.noStoreKey")}</p>
+            )}
+            <div className="flex items-center gap-2">
+              <Button color="danger" onPress={handleConfirmRestore} isDisabled={busy !== null}>
+                {busy === "restore" ? t("backup.restoring") : t("backup.restoreAndRelaunch")}
+              </Button>
+              <Button onPress={handleCancelRestore} isDisabled={busy !== null}>
+                {t("common:action.cancel")}
+              </Button>
+            </div>
+          </div>
+        ) : (
+          <div className="flex items-center gap-2">
+            <Button
+              color="primary"
+              onPress={handleBackup}
+              isDisabled={busy !== null}
+              startContent={<Download size={14} />}
+            >
+              {busy === "backup" ? t("backup.backingUp") : t("backup.backUp")}
+            </Button>
+            <Button onPress={handleChooseRestore} isDisabled={busy !== null} startContent={<Upload size={14} />}>
+              {t("backup.restore")}
+            </Button>
+          </div>
+        )}
+      </CardBody>
+    </Card>
+  );
+}
@@ -0,0 +1,135 @@
+import { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { open } from "@tauri-apps/plugin-dialog";
+import { relaunch } from "@tauri-apps/plugin-process";
+import { FolderOpen } from "lucide-react";
+import { Button, Card, CardBody } from "@heroui/react";
+import {
+  cancelDataDirMove,
+  getDataLocation,
+  moveDataDir,
+  type DataLocation,
+} from "../../lib/tauri";
+
+/** Where Nexus keeps its state, and moving it elsewhere. */
+export function DataDirectoryCard() {
+  const { t } = useTranslation("settings");
+  const [location, setLocation] = useState<DataLocation | null>(null);
+  const [target, setTarget] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      setLocation(await getDataLocation());
+    } catch {
+      // ignore
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  const handleChoose = useCallback(async () => {
+    const selected = await open({ directory: true, multiple: false });
+    if (selected) {
+      setTarget(selected);
+      setError(null);
+    }
+  }, []);
+
+  const handleUseDefault = useCallback(() => {
+    if (location) {
+      setTarget(location.default);
+      setError(null);
+    }
+  }, [location]);
+
+  const handleCancelChoice = useCallback(() => setTarget(null), []);
+
+  const handleMove = useCallback(async () => {
+    if (!target) return;
+    setBusy(true);
+    try {
+      await moveDataDir(target);
+      // The move happens at startup, before anything opens the data
+      await relaunch();
+    } catch (e) {
+      setError(String(e));
+      setBusy(false);
+    }
+  }, [target]);
+
+  const handleCancelPending = useCallback(async () => {
+    await cancelDataDirMove();
+    refresh();
+  }, [refresh]);
+
+  const handleRelaunch = useCallback(() => {
+    relaunch();
+  }, []);
+
+  if (!location) return null;
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+        <div className="flex items-center gap-2 mb-4">
+          <FolderOpen size={15} strokeWidth={1.5} className="text-default-500" />
+          <h3 className="text-[14px] font-semibold">{t("dataDir.title")}</h3>
+        </div>
+
+        <div className="flex items-center justify-between gap-4 mb-3">
+          <span className="text-[12px] text-default-500">{t("dataDir.current")}</span>
+          <span className="text-[11px] text-default-400 font-mono truncate" title={location.current}>
+            {location.current}
+          </span>
+        </div>
+
+        {location.last_error && (
+          <p className="text-[11px] text-danger mb-3">
+            {t("dataDir.lastError", { error: location.last_error })}
+          </p>
+        )}
+
+        {location.pending_move ? (
+          <div className="space-y-2">
+            <p className="text-[11px] text-warning">
+              {t("dataDir.pending", { path: location.pending_move })}
+            </p>
+            <div className="flex items-center gap-2">
+              <Button color="primary" onPress={handleRelaunch}>
+                {t("dataDir.relaunchNow")}
+              </Button>
+              <Button onPress={handleCancelPending}>{t("common:action.cancel")}</Button>
+            </div>
+          </div>
+        ) : target ? (
+          <div className="space-y-2">
+            <p className="text-[11px] text-warning">{t("dataDir.confirm", { path: target })}</p>
+            {error && <p className="text-[11px] text-danger">{error}</p>}
+            <div className="flex items-center gap-2">
+              <Button color="primary" onPress={handleMove} isDisabled={busy}>
+                {t("dataDir.moveAndRelaunch")}
+              </Button>
+              <Button onPress={handleCancelChoice} isDisabled={busy}>
+                {t("common:action.cancel")}
+              </Button>
+            </div>
+          </div>
+        ) : (
+          <>
+            <p className="text-[11px] text-default-400 mb-3">{t("dataDir.hint")}</p>
+            <div className="flex items-center gap-2">
+              <Button onPress={handleChoose}>{t("dataDir.move")}</Button>
+              {location.current !== location.default && (
+                <Button onPress={handleUseDefault}>{t("dataDir.useDefault")}</Button>
+              )}
+            </div>
+          </>
+        )}
+      </CardBody>
+    </Card>
+  );
+}
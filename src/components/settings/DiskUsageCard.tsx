@@ -0,0 +1,96 @@
+import { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { HardDrive, RefreshCw } from "lucide-react";
+import { Button, Card, CardBody, Divider } from "@heroui/react";
+import { systemDiskUsage, type DiskUsage, type PluginDiskUsage } from "../../lib/tauri";
+import { formatBytes } from "../../lib/formatBytes";
+
+function Row({ label, bytes, hint }: { label: string; bytes: number; hint?: string }) {
+  return (
+    <div className="flex items-center justify-between gap-4">
+      <span className="text-[12px] text-default-500 truncate" title={hint}>
+        {label}
+      </span>
+      <span className="text-[12px] font-mono flex-shrink-0">{formatBytes(bytes)}</span>
+    </div>
+  );
+}
+
+function PluginRow({ plugin }: { plugin: PluginDiskUsage }) {
+  const { t } = useTranslation("settings");
+  const parts = [
+    t("diskUsage.image", { size: formatBytes(plugin.image_bytes) }),
+    plugin.volume_bytes !== null && t("diskUsage.volume", { size: formatBytes(plugin.volume_bytes) }),
+    t("diskUsage.storage", { size: formatBytes(plugin.storage_bytes) }),
+    t("diskUsage.audit", { size: formatBytes(plugin.audit_bytes) }),
+  ].filter(Boolean);
+
+  return (
+    <div>
+      <Row label={plugin.name} bytes={plugin.total_bytes} hint={plugin.plugin_id} />
+      <p className="text-[11px] text-default-400 mt-0.5">{parts.join(" · ")}</p>
+    </div>
+  );
+}
+
+/** What's using disk space, so users know what to prune. */
+export function DiskUsageCard() {
+  const { t } = useTranslation("settings");
+  const [usage, setUsage] = useState<DiskUsage | null>(null);
+  const [loading, setLoading] = useState(false);
+
+  const refresh = useCallback(async () => {
+    setLoading(true);
+    try {
+      setUsage(await systemDiskUsage());
+    } catch {
+      // Shown as still measuring
+    } finally {
+      setLoading(false);
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh]);
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <HardDrive size={15} strokeWidth={1.5} className="text-default-500" />
+            <h3 className="text-[14px] font-semibold">{t("diskUsage.title")}</h3>
+          </div>
+          <Button
+            onPress={refresh}
+            isDisabled={loading}
+            startContent={
+              <RefreshCw size={12} strokeWidth={1.5} className={loading ? "animate-spin" : ""} />
+            }
+          >
+            {t("common:action.refresh")}
+          </Button>
+        </div>
+
+        {usage === null ? (
+          <p className="text-[12px] text-default-400">{t("diskUsage.measuring")}</p>
+        ) : (
+          <div className="space-y-3">
+            <Row label={t("diskUsage.total")} bytes={usage.total_bytes} />
+            <Divider />
+            {usage.plugins.length === 0 ? (
+              <p className="text-[11px] text-default-400">{t("pluginsTab.noPlugins")}</p>
+            ) : (
+              usage.plugins.map((plugin) => <PluginRow key={plugin.plugin_id} plugin={plugin} />)
+            )}
+            <Divider />
+            <Row label={t("diskUsage.auditDb")} bytes={usage.audit_db_bytes} />
+            <Row label={t("diskUsage.registryCache")} bytes={usage.registry_cache_bytes} />
+            <Row label={t("diskUsage.logs")} bytes={usage.logs_bytes} />
+          </div>
+        )}
+      </CardBody>
+    </Card>
+  );
+}
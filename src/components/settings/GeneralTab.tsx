@@ -1,16 +1,26 @@
 import { useCallback, useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
-import { appVersion, type AppVersionInfo } from "../../lib/tauri";
+import {
+  appVersion,
+  getGlobalShortcut,
+  getLaunchAtLogin,
+  setGlobalShortcut,
+  setLaunchAtLogin,
+  type AppVersionInfo,
+} from "../../lib/tauri";
 import { RegistrySettings } from "./RegistrySettings";
 import { UpdateCheck } from "./UpdateCheck";
-import { Info, Bug, Bell, BellOff, Globe, Check, Sun, Moon, Monitor } from "lucide-react";
-import { Switch, Autocomplete, AutocompleteItem, Button, Card, CardBody, Divider, Tabs, Tab } from "@heroui/react";
+import { BackupSettings } from "./BackupSettings";
+import { NotificationHistory } from "./NotificationHistory";
+import { Info, Bug, Bell, BellOff, Globe, Check, Sun, Moon, Monitor, Power } from "lucide-react";
+import { Switch, Autocomplete, AutocompleteItem, Button, Card, CardBody, Divider, Input, Tabs, Tab } from "@heroui/react";
 import {
   notificationsEnabled,
   setNotificationsEnabled,
 } from "../../hooks/useOsNotification";
 import { LANGUAGES } from "../../i18n";
 import { cn } from "../../lib/utils";
+import { useAppStore } from "../../stores/appStore";
 import { getColorMode, applyColorMode, type ColorMode } from "../../lib/theme";
 
 const COLOR_MODES: { id: ColorMode; icon: typeof Sun; labelKey: string }[] = [
@@ -24,11 +34,54 @@ export function GeneralTab() {
   const [version, setVersion] = useState<AppVersionInfo | null>(null);
   const [notifEnabled, setNotifEnabled] = useState(notificationsEnabled);
   const [colorMode, setColorMode] = useState<ColorMode>(getColorMode);
+  const [launchAtLogin, setLaunchAtLoginState] = useState(false);
+  // Saved shortcut, and the one being edited
+  const [shortcut, setShortcut] = useState("");
+  const [shortcutDraft, setShortcutDraft] = useState("");
 
   useEffect(() => {
     appVersion().then(setVersion).catch(() => {});
+    getLaunchAtLogin().then(setLaunchAtLoginState).catch(() => {});
+    getGlobalShortcut()
+      .then((s) => {
+        setShortcut(s ?? "");
+        setShortcutDraft(s ?? "");
+      })
+      .catch(() => {});
   }, []);
 
+  const saveShortcut = useCallback(async () => {
+    const next = shortcutDraft.trim();
+    if (next === shortcut) return;
+    try {
+      await setGlobalShortcut(next || null);
+      setShortcut(next);
+    } catch (e) {
+      setShortcutDraft(shortcut);
+      useAppStore.getState().addNotification(t("general.globalShortcutFailed", { error: String(e) }), "error");
+    }
+  }, [shortcut, shortcutDraft, t]);
+
+  const handleShortcutKeyDown = useCallback(
+    (e: React.KeyboardEvent<HTMLInputElement>) => {
+      if (e.key === "Enter") saveShortcut();
+    },
+    [saveShortcut],
+  );
+
+  const handleLaunchAtLogin = useCallback(
+    async (enabled: boolean) => {
+      setLaunchAtLoginState(enabled);
+      try {
+        await setLaunchAtLogin(enabled);
+      } catch (e) {
+        setLaunchAtLoginState(!enabled);
+        useAppStore.getState().addNotification(t("general.launchAtLoginFailed", { error: String(e) }), "error");
+      }
+    },
+    [t],
+  );
+
   function handleNotifToggle(checked: boolean) {
     setNotifEnabled(checked);
     setNotificationsEnabled(checked);
@@ -150,6 +203,42 @@ export function GeneralTab() {
               </div>
             </>
           )}
+          <Divider className="my-4" />
+          <NotificationHistory />
+        </CardBody>
+      </Card>
+
+      {/* Startup */}
+      <Card>
+        <CardBody>
+          <div className="flex items-center gap-2 mb-4">
+            <Power size={16} className="text-default-500" />
+            <h3 className="text-sm font-semibold">{t("general.startup")}</h3>
+          </div>
+          <div className="flex items-center justify-between">
+            <div>
+              <p className="text-sm">{t("general.launchAtLogin")}</p>
+              <p className="text-xs text-default-400 mt-1">{t("general.launchAtLoginHint")}</p>
+            </div>
+            <Switch isSelected={launchAtLogin} onValueChange={handleLaunchAtLogin} />
+          </div>
+          <Divider className="my-4" />
+          <div className="flex items-center justify-between gap-4">
+            <div>
+              <p className="text-sm">{t("general.globalShortcut")}</p>
+              <p className="text-xs text-default-400 mt-1">{t("general.globalShortcutHint")}</p>
+            </div>
+            <Input
+              aria-label={t("general.globalShortcut")}
+              placeholder={t("general.globalShortcutOff")}
+              value={shortcutDraft}
+              onValueChange={setShortcutDraft}
+              onBlur={saveShortcut}
+              onKeyDown={handleShortcutKeyDown}
+              variant="bordered"
+              className="w-[200px] shrink-0"
+            />
+          </div>
         </CardBody>
       </Card>
 
@@ -188,6 +277,9 @@ export function GeneralTab() {
 
       {/* Registries */}
       <RegistrySettings />
+
+      {/* Backup */}
+      <BackupSettings />
     </div>
   );
 }
@@ -67,7 +67,7 @@ export function KeyChangeWarningDialog({
               {/* Key details */}
               <div className="bg-danger-50 rounded-[14px] p-3 text-[11px] font-mono text-default-500 space-y-1">
                 <p>
-                  <span className="text-default-500">{t("keyChange.extension")}</span>{" "}
+                  <span className="text-default-500">{update.item_type === "plugin" ? t("keyChange.plugin") : t("keyChange.extension")}</span>{" "}
                   {update.item_id}
                 </p>
                 <p>
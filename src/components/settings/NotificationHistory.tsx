@@ -0,0 +1,161 @@
+import { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { CheckCheck, Trash2 } from "lucide-react";
+import { Button } from "@heroui/react";
+import {
+  notificationAction,
+  notificationsClear,
+  notificationsList,
+  notificationsMarkRead,
+  type NotificationAction,
+  type StoredNotification,
+} from "../../lib/tauri";
+import { timeAgo } from "../../lib/timeAgo";
+import { cn } from "../../lib/utils";
+import { useAppStore } from "../../stores/appStore";
+
+/** Past notifications, so ones missed while Nexus sat in the tray aren't lost. */
+export function NotificationHistory() {
+  const { t } = useTranslation("settings");
+  const [entries, setEntries] = useState<StoredNotification[]>([]);
+
+  const refresh = useCallback(() => {
+    notificationsList().then(setEntries).catch(() => {});
+  }, []);
+
+  useEffect(() => {
+    refresh();
+    const unlisten = listen<StoredNotification>("nexus://notification", (event) => {
+      setEntries((prev) => [event.payload, ...prev]);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [refresh]);
+
+  const handleMarkAllRead = useCallback(async () => {
+    await notificationsMarkRead();
+    refresh();
+  }, [refresh]);
+
+  const handleClear = useCallback(async () => {
+    await notificationsClear();
+    setEntries([]);
+  }, []);
+
+  const handleMarkRead = useCallback(
+    async (id: string) => {
+      await notificationsMarkRead([id]);
+      refresh();
+    },
+    [refresh],
+  );
+
+  const unread = entries.filter((n) => !n.read).length;
+
+  return (
+    <div>
+      <div className="flex items-center justify-between mb-3">
+        <div>
+          <p className="text-sm">{t("notificationHistory.title")}</p>
+          <p className="text-xs text-default-400 mt-1">
+            {unread > 0
+              ? t("notificationHistory.unread", { count: unread })
+              : t("notificationHistory.hint")}
+          </p>
+        </div>
+        <div className="flex items-center gap-2">
+          <Button
+            size="sm"
+            onPress={handleMarkAllRead}
+            isDisabled={unread === 0}
+            startContent={<CheckCheck size={14} />}
+          >
+            {t("notificationHistory.markAllRead")}
+          </Button>
+          <Button
+            size="sm"
+            onPress={handleClear}
+            isDisabled={entries.length === 0}
+            startContent={<Trash2 size={14} />}
+          >
+            {t("notificationHistory.clear")}
+          </Button>
+        </div>
+      </div>
+
+      {entries.length === 0 ? (
+        <p className="text-xs text-default-400">{t("notificationHistory.empty")}</p>
+      ) : (
+        <div className="max-h-72 overflow-y-auto space-y-1">
+          {entries.map((n) => (
+            <NotificationRow key={n.id} entry={n} onMarkRead={handleMarkRead} />
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
+function NotificationRow({
+  entry,
+  onMarkRead,
+}: {
+  entry: StoredNotification;
+  onMarkRead: (id: string) => void;
+}) {
+  const handlePress = useCallback(() => {
+    if (!entry.read) onMarkRead(entry.id);
+  }, [entry.id, entry.read, onMarkRead]);
+
+  return (
+    <div
+      role="button"
+      tabIndex={0}
+      onClick={handlePress}
+      className="w-full flex items-start gap-2 rounded-lg px-2 py-1.5 text-left hover:bg-default-100"
+    >
+      <span
+        className={cn(
+          "mt-1.5 h-2 w-2 flex-shrink-0 rounded-full",
+          entry.read ? "bg-transparent" : "bg-primary",
+        )}
+      />
+      <div className="min-w-0 flex-1">
+        <div className="flex items-center justify-between gap-2">
+          <p className={cn("text-sm truncate", !entry.read && "font-medium")}>{entry.title}</p>
+          <span className="text-xs text-default-400 flex-shrink-0">{timeAgo(entry.created_at)}</span>
+        </div>
+        {entry.body && (
+          <p className="text-xs text-default-500 whitespace-pre-line line-clamp-3">{entry.body}</p>
+        )}
+        {entry.actions.length > 0 && (
+          <div className="flex items-center gap-2 mt-1.5">
+            {entry.actions.map((action) => (
+              <ActionButton key={action.kind} action={action} />
+            ))}
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
+
+function ActionButton({ action }: { action: NotificationAction }) {
+  const { t } = useTranslation("settings");
+
+  const handlePress = useCallback(async () => {
+    try {
+      await notificationAction(action);
+    } catch (e) {
+      useAppStore.getState().addNotification(String(e), "error");
+    }
+  }, [action]);
+
+  return (
+    <Button size="sm" variant="flat" onPress={handlePress}>
+      {t(`notificationHistory.action.${action.kind}`)}
+    </Button>
+  );
+}
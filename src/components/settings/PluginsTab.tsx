@@ -7,6 +7,8 @@ import type { InstalledPlugin, SettingDef } from "../../types/plugin";
 import { Puzzle, Save, Check, Square, Trash2, Database, HardDrive, Cloud } from "lucide-react";
 import { Switch, Button, Input, Select, SelectItem, Card, CardBody, Chip, Divider } from "@heroui/react";
 import { ErrorBoundary } from "../ErrorBoundary";
+import { formatBytes } from "../../lib/formatBytes";
+import { ResourceHistory } from "./ResourceHistory";
 
 function SettingField({
   def,
@@ -105,13 +107,6 @@ function SettingField({
   }
 }
 
-function formatBytes(bytes: number): string {
-  if (bytes === 0) return "0 B";
-  if (bytes < 1024) return `${bytes} B`;
-  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
-  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
-}
-
 function StorageInfo({ pluginId }: { pluginId: string }) {
   const { t } = useTranslation("settings");
   const [bytes, setBytes] = useState<number | null>(null);
@@ -284,6 +279,7 @@ function PluginSettingsCard({
           <p className="text-[11px] text-default-400">
             {t("pluginsTab.noConfigurable")}
           </p>
+          <ResourceHistory pluginId={plugin.manifest.id} />
           <StorageInfo pluginId={plugin.manifest.id} />
         </CardBody>
       </Card>
@@ -343,6 +339,7 @@ function PluginSettingsCard({
           </Button>
         </div>
 
+        <ResourceHistory pluginId={plugin.manifest.id} />
         <StorageInfo pluginId={plugin.manifest.id} />
       </CardBody>
     </Card>
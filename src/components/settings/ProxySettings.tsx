@@ -0,0 +1,137 @@
+import { useCallback, useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Check, Globe, Save } from "lucide-react";
+import { Button, Card, CardBody, Divider, Input, Switch } from "@heroui/react";
+import { getProxyConfig, setProxyConfig, type ProxyConfig } from "../../lib/tauri";
+
+/** Outbound HTTP proxy for registry fetches, update checks, and the network proxy. */
+export function ProxySettings() {
+  const { t } = useTranslation("settings");
+  const [config, setConfig] = useState<ProxyConfig | null>(null);
+  const [noProxy, setNoProxy] = useState("");
+  const [dirty, setDirty] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [saved, setSaved] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const savedTimer = useRef<ReturnType<typeof setTimeout>>(undefined);
+
+  useEffect(() => {
+    getProxyConfig()
+      .then((c) => {
+        setConfig(c);
+        setNoProxy(c.no_proxy.join(", "));
+      })
+      .catch(() => {});
+  }, []);
+
+  const update = useCallback((patch: Partial<ProxyConfig>) => {
+    setConfig((c) => (c ? { ...c, ...patch } : c));
+    setDirty(true);
+    setError(null);
+  }, []);
+
+  const handleEnabled = useCallback((enabled: boolean) => update({ enabled }), [update]);
+  const handleHost = useCallback((host: string) => update({ host }), [update]);
+  const handlePort = useCallback(
+    (value: string) => update({ port: parseInt(value, 10) || 0 }),
+    [update]
+  );
+  const handleNoProxy = useCallback((value: string) => {
+    setNoProxy(value);
+    setDirty(true);
+    setError(null);
+  }, []);
+
+  async function handleSave() {
+    if (!config) return;
+    const next: ProxyConfig = {
+      ...config,
+      host: config.host.trim(),
+      no_proxy: noProxy
+        .split(/[\s,]+/)
+        .map((h) => h.trim())
+        .filter(Boolean),
+    };
+    setSaving(true);
+    try {
+      await setProxyConfig(next);
+      setConfig(next);
+      setDirty(false);
+      setSaved(true);
+      clearTimeout(savedTimer.current);
+      savedTimer.current = setTimeout(() => setSaved(false), 2000);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setSaving(false);
+    }
+  }
+
+  if (!config) return null;
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Globe size={15} strokeWidth={1.5} className="text-default-500" />
+            <h3 className="text-[14px] font-semibold">{t("proxy.title")}</h3>
+          </div>
+          <Button
+            onPress={handleSave}
+            isDisabled={!dirty || saving}
+            color={saved ? "success" : dirty ? "primary" : undefined}
+            startContent={saved ? <Check size={12} strokeWidth={1.5} /> : <Save size={12} strokeWidth={1.5} />}
+          >
+            {saved ? t("common:action.saved") : saving ? t("common:action.saving") : t("common:action.save")}
+          </Button>
+        </div>
+
+        <div className="flex items-center justify-between mb-4">
+          <div>
+            <p className="text-[13px]">{t("proxy.enabled")}</p>
+            <p className="text-[11px] text-default-400 mt-1">{t("proxy.enabledHint")}</p>
+          </div>
+          <Switch isSelected={config.enabled} onValueChange={handleEnabled} />
+        </div>
+
+        <div className="space-y-3">
+          <div className="flex gap-3">
+            <Input
+              label={t("proxy.host")}
+              placeholder="proxy.example.com"
+              value={config.host}
+              onValueChange={handleHost}
+              isDisabled={!config.enabled}
+              variant="bordered"
+              className="flex-1"
+            />
+            <Input
+              label={t("proxy.port")}
+              type="number"
+              min={1}
+              max={65535}
+              value={String(config.port || "")}
+              onValueChange={handlePort}
+              isDisabled={!config.enabled}
+              variant="bordered"
+              className="w-28"
+            />
+          </div>
+          <Input
+            label={t("proxy.noProxy")}
+            description={t("proxy.noProxyHint")}
+            value={noProxy}
+            onValueChange={handleNoProxy}
+            isDisabled={!config.enabled}
+            variant="bordered"
+          />
+          {error && <p className="text-[11px] text-danger">{error}</p>}
+        </div>
+
+        <Divider className="my-4" />
+        <p className="text-[11px] text-default-400">{t("proxy.engineNote")}</p>
+      </CardBody>
+    </Card>
+  );
+}
@@ -1,25 +1,39 @@
-import { useCallback, useEffect, useState } from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
 import { useTranslation } from "react-i18next";
-import type { RegistryKind, RegistrySource } from "../../types/plugin";
+import type { ConflictPolicy, RegistryAuth, RegistryKind, RegistrySource } from "../../types/plugin";
 import * as api from "../../lib/tauri";
-import { Database, FolderOpen, Globe, Plus, Trash2 } from "lucide-react";
+import { useAppStore } from "../../stores/appStore";
+import { Boxes, ChevronDown, ChevronUp, Database, Download, FolderOpen, Globe, Lock, Plus, Trash2, Upload } from "lucide-react";
 import { Switch, Button, Input, Card, CardBody, Chip } from "@heroui/react";
 
 const PROTECTED_REGISTRIES = new Set(["nexus-community", "nexus-mcp-local"]);
 
+type AuthKind = "none" | RegistryAuth["kind"];
+
 export function RegistrySettings() {
   const { t } = useTranslation("settings");
   const [registries, setRegistries] = useState<RegistrySource[]>([]);
+  const [conflictPolicy, setConflictPolicy] = useState<ConflictPolicy>("highest_priority");
   const [showAdd, setShowAdd] = useState(false);
   const [newName, setNewName] = useState("");
   const [newKind, setNewKind] = useState<RegistryKind>("local");
   const [newUrl, setNewUrl] = useState("");
+  const [authKind, setAuthKind] = useState<AuthKind>("none");
+  const [token, setToken] = useState("");
+  const [username, setUsername] = useState("");
+  const [password, setPassword] = useState("");
   const [adding, setAdding] = useState(false);
+  const [exporting, setExporting] = useState(false);
+  const importInputRef = useRef<HTMLInputElement>(null);
 
   const refresh = useCallback(async () => {
     try {
-      const list = await api.registryList();
+      const [list, policy] = await Promise.all([
+        api.registryList(),
+        api.registryGetConflictPolicy(),
+      ]);
       setRegistries(list);
+      setConflictPolicy(policy);
     } catch {
       // silently fail
     }
@@ -32,14 +46,24 @@ export function RegistrySettings() {
   async function handleAdd() {
     if (!newName.trim() || !newUrl.trim()) return;
     setAdding(true);
+    let auth: RegistryAuth | null = null;
+    if (newKind !== "local" && authKind === "bearer") {
+      auth = { kind: "bearer", token };
+    } else if (newKind !== "local" && authKind === "basic") {
+      auth = { kind: "basic", username, password };
+    }
     try {
-      await api.registryAdd(newName.trim(), newKind, newUrl.trim());
+      await api.registryAdd(newName.trim(), newKind, newUrl.trim(), auth);
       setNewName("");
       setNewUrl("");
+      setAuthKind("none");
+      setToken("");
+      setUsername("");
+      setPassword("");
       setShowAdd(false);
       await refresh();
-    } catch {
-      // TODO: show error
+    } catch (e) {
+      useAppStore.getState().addNotification(String(e), "error");
     } finally {
       setAdding(false);
     }
@@ -63,9 +87,75 @@ export function RegistrySettings() {
     }
   }
 
+  async function handleMove(index: number, offset: number) {
+    const ids = registries.map((r) => r.id);
+    const target = index + offset;
+    if (target < 0 || target >= ids.length) return;
+    [ids[index], ids[target]] = [ids[target], ids[index]];
+    try {
+      await api.registryReorder(ids);
+      await refresh();
+    } catch (e) {
+      useAppStore.getState().addNotification(String(e), "error");
+    }
+  }
+
+  async function handleConflictPolicy(policy: ConflictPolicy) {
+    try {
+      await api.registrySetConflictPolicy(policy);
+      setConflictPolicy(policy);
+    } catch (e) {
+      useAppStore.getState().addNotification(String(e), "error");
+    }
+  }
+
+  async function handleExportBundle() {
+    const { addNotification } = useAppStore.getState();
+    setExporting(true);
+    try {
+      const result = await api.registryBundleExport();
+      const blob = new Blob([result.data], { type: "application/json" });
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement("a");
+      a.href = url;
+      a.download = `nexus-registry-${new Date().toISOString().slice(0, 10)}.json`;
+      a.click();
+      URL.revokeObjectURL(url);
+      addNotification(t("registries.bundleExported", { count: result.plugins + result.extensions }), "success");
+      if (result.failed.length > 0) {
+        addNotification(t("registries.bundleFailed", { count: result.failed.length }), "info");
+      }
+    } catch (e) {
+      addNotification(String(e), "error");
+    } finally {
+      setExporting(false);
+    }
+  }
+
+  const handleImportClick = useCallback(() => importInputRef.current?.click(), []);
+
+  async function handleImportBundle(e: React.ChangeEvent<HTMLInputElement>) {
+    const file = e.target.files?.[0];
+    e.target.value = "";
+    if (!file) return;
+    const { addNotification } = useAppStore.getState();
+    try {
+      const name = file.name.replace(/\.json$/i, "");
+      const report = await api.registryBundleImport(await file.text(), name);
+      addNotification(t("registries.bundleImported", { count: report.plugins + report.extensions }), "success");
+      await refresh();
+    } catch (err) {
+      addNotification(String(err), "error");
+    }
+  }
+
   const handleToggleShowAdd = useCallback(() => setShowAdd((v) => !v), []);
   const handleSetKindLocal = useCallback(() => setNewKind("local"), []);
   const handleSetKindRemote = useCallback(() => setNewKind("remote"), []);
+  const handleSetKindOci = useCallback(() => setNewKind("oci"), []);
+  const handleSetAuthNone = useCallback(() => setAuthKind("none"), []);
+  const handleSetAuthBearer = useCallback(() => setAuthKind("bearer"), []);
+  const handleSetAuthBasic = useCallback(() => setAuthKind("basic"), []);
 
   return (
     <Card><CardBody className="p-5">
@@ -79,12 +169,40 @@ export function RegistrySettings() {
             </p>
           </div>
         </div>
-        <Button
-          onPress={handleToggleShowAdd}
-        >
-          <Plus size={12} strokeWidth={1.5} />
-          {showAdd ? t("common:action.cancel") : t("registries.addRegistry")}
-        </Button>
+        <div className="flex items-center gap-2">
+          <Button
+            size="sm"
+            variant="flat"
+            startContent={<Download size={12} strokeWidth={1.5} />}
+            onPress={handleExportBundle}
+            isDisabled={exporting}
+            title={t("registries.exportBundleTooltip")}
+          >
+            {t("registries.exportBundle")}
+          </Button>
+          <Button
+            size="sm"
+            variant="flat"
+            startContent={<Upload size={12} strokeWidth={1.5} />}
+            onPress={handleImportClick}
+            title={t("registries.importBundleTooltip")}
+          >
+            {t("registries.importBundle")}
+          </Button>
+          <input
+            ref={importInputRef}
+            type="file"
+            accept="application/json,.json"
+            className="hidden"
+            onChange={handleImportBundle}
+          />
+          <Button
+            onPress={handleToggleShowAdd}
+          >
+            <Plus size={12} strokeWidth={1.5} />
+            {showAdd ? t("common:action.cancel") : t("registries.addRegistry")}
+          </Button>
+        </div>
       </div>
 
       {/* Add form */}
@@ -118,11 +236,21 @@ export function RegistrySettings() {
                 <Globe size={12} strokeWidth={1.5} />
                 {t("registries.remoteUrl")}
               </Button>
+              <Button
+                onPress={handleSetKindOci}
+              >
+                <Boxes size={12} strokeWidth={1.5} />
+                {t("registries.ociRepository")}
+              </Button>
             </div>
           </div>
           <div>
             <label className="block text-[11px] font-medium text-default-500 mb-1.5">
-              {newKind === "local" ? t("registries.directoryPath") : t("registries.registryUrl")}
+              {newKind === "local"
+                ? t("registries.directoryPath")
+                : newKind === "oci"
+                  ? t("registries.repository")
+                  : t("registries.registryUrl")}
             </label>
             <Input
               value={newUrl}
@@ -130,16 +258,62 @@ export function RegistrySettings() {
               placeholder={
                 newKind === "local"
                   ? t("registries.localPathPlaceholder")
-                  : t("registries.remoteUrlPlaceholder")
+                  : newKind === "oci"
+                    ? t("registries.ociPlaceholder")
+                    : t("registries.remoteUrlPlaceholder")
               }
               variant="bordered"
             />
             <p className="text-[11px] text-default-400 mt-1.5">
               {newKind === "local"
                 ? t("registries.localHint")
-                : t("registries.remoteHint")}
+                : newKind === "oci"
+                  ? t("registries.ociHint")
+                  : t("registries.remoteHint")}
             </p>
           </div>
+          {newKind !== "local" && (
+            <div>
+              <label className="block text-[11px] font-medium text-default-500 mb-1.5">
+                {t("registries.auth")}
+              </label>
+              <div className="flex gap-2">
+                <Button onPress={handleSetAuthNone}>{t("registries.authNone")}</Button>
+                <Button onPress={handleSetAuthBearer}>{t("registries.authBearer")}</Button>
+                <Button onPress={handleSetAuthBasic}>{t("registries.authBasic")}</Button>
+              </div>
+              {authKind === "bearer" && (
+                <Input
+                  className="mt-2"
+                  type="password"
+                  value={token}
+                  onValueChange={setToken}
+                  placeholder={t("registries.token")}
+                  variant="bordered"
+                />
+              )}
+              {authKind === "basic" && (
+                <div className="mt-2 flex gap-2">
+                  <Input
+                    value={username}
+                    onValueChange={setUsername}
+                    placeholder={t("registries.username")}
+                    variant="bordered"
+                  />
+                  <Input
+                    type="password"
+                    value={password}
+                    onValueChange={setPassword}
+                    placeholder={t("registries.password")}
+                    variant="bordered"
+                  />
+                </div>
+              )}
+              <p className="text-[11px] text-default-400 mt-1.5">
+                {t("registries.authHint")}
+              </p>
+            </div>
+          )}
           <Button
             onPress={handleAdd}
             isDisabled={adding || !newName.trim() || !newUrl.trim()}
@@ -149,17 +323,42 @@ export function RegistrySettings() {
         </div>
       )}
 
-      {/* Registry list */}
+      {/* Registry list, in priority order */}
+      <p className="text-[11px] text-default-400 mb-2">{t("registries.priorityHint")}</p>
       <div className="space-y-2">
         {registries.length === 0 ? (
           <p className="text-[11px] text-default-400">{t("registries.noRegistries")}</p>
         ) : (
-          registries.map((reg) => (
+          registries.map((reg, index) => (
             <div
               key={reg.id}
               className="flex items-center justify-between p-3 rounded-[8px] bg-background border border-default-100 hover:border-divider transition-colors duration-150"
             >
               <div className="flex items-center gap-3 min-w-0">
+                <div className="flex flex-col flex-shrink-0">
+                  <Button
+                    isIconOnly
+                    size="sm"
+                    variant="light"
+                    isDisabled={index === 0}
+                    // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                    onPress={() => handleMove(index, -1)}
+                    title={t("registries.moveUp")}
+                  >
+                    <ChevronUp size={12} strokeWidth={1.5} />
+                  </Button>
+                  <Button
+                    isIconOnly
+                    size="sm"
+                    variant="light"
+                    isDisabled={index === registries.length - 1}
+                    // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                    onPress={() => handleMove(index, 1)}
+                    title={t("registries.moveDown")}
+                  >
+                    <ChevronDown size={12} strokeWidth={1.5} />
+                  </Button>
+                </div>
                 <Switch
                   isSelected={reg.enabled}
                   // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
@@ -174,8 +373,17 @@ export function RegistrySettings() {
                       size="sm"
                       variant="flat"
                     >
-                      {reg.kind === "local" ? t("registries.local") : t("registries.remote")}
+                      {t(`registries.${reg.kind}`)}
                     </Chip>
+                    {reg.auth && (
+                      <Chip
+                        size="sm"
+                        variant="flat"
+                        startContent={<Lock size={10} strokeWidth={1.5} />}
+                      >
+                        {t("registries.private")}
+                      </Chip>
+                    )}
                   </div>
                   <p className="text-[11px] text-default-400 truncate font-mono mt-0.5">
                     {reg.url}
@@ -198,6 +406,31 @@ export function RegistrySettings() {
           ))
         )}
       </div>
+
+      <div className="mt-4">
+        <label className="block text-[11px] font-medium text-default-500 mb-1.5">
+          {t("registries.conflictPolicy")}
+        </label>
+        <div className="flex gap-2">
+          <Button
+            color={conflictPolicy === "highest_priority" ? "primary" : "default"}
+            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+            onPress={() => handleConflictPolicy("highest_priority")}
+          >
+            {t("registries.conflictHighest")}
+          </Button>
+          <Button
+            color={conflictPolicy === "prompt" ? "primary" : "default"}
+            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+            onPress={() => handleConflictPolicy("prompt")}
+          >
+            {t("registries.conflictPrompt")}
+          </Button>
+        </div>
+        <p className="text-[11px] text-default-400 mt-1.5">
+          {t("registries.conflictHint")}
+        </p>
+      </div>
     </CardBody></Card>
   );
 }
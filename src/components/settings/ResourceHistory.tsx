@@ -0,0 +1,112 @@
+import { useCallback, useEffect, useMemo, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Activity } from "lucide-react";
+import { Divider } from "@heroui/react";
+import { pluginResourceHistory, type ResourceSample } from "../../lib/tauri";
+
+const REFRESH_MS = 10_000;
+const WIDTH = 120;
+const HEIGHT = 28;
+
+function Sparkline({ values, className }: { values: number[]; className: string }) {
+  const max = Math.max(...values, 0);
+  const step = WIDTH / Math.max(values.length - 1, 1);
+  const points = values
+    .map((v, i) => `${(i * step).toFixed(1)},${(HEIGHT - (max > 0 ? (v / max) * (HEIGHT - 2) : 0) - 1).toFixed(1)}`)
+    .join(" ");
+  return (
+    <svg width={WIDTH} height={HEIGHT} viewBox={`0 0 ${WIDTH} ${HEIGHT}`} className={className}>
+      <polyline points={points} fill="none" stroke="currentColor" strokeWidth={1.25} strokeLinejoin="round" />
+    </svg>
+  );
+}
+
+function formatRate(bps: number): string {
+  if (bps < 1024) return `${bps.toFixed(0)} B/s`;
+  if (bps < 1024 * 1024) return `${(bps / 1024).toFixed(1)} KB/s`;
+  return `${(bps / (1024 * 1024)).toFixed(1)} MB/s`;
+}
+
+function Metric({
+  label,
+  value,
+  values,
+  className,
+}: {
+  label: string;
+  value: string;
+  values: number[];
+  className: string;
+}) {
+  return (
+    <div className="flex-1 min-w-0">
+      <div className="flex items-baseline justify-between gap-2 mb-1">
+        <span className="text-[11px] text-default-400">{label}</span>
+        <span className="text-[11px] font-mono">{value}</span>
+      </div>
+      <Sparkline values={values} className={className} />
+    </div>
+  );
+}
+
+/** CPU, memory, and network sparklines over the last hour. */
+export function ResourceHistory({ pluginId }: { pluginId: string }) {
+  const { t } = useTranslation("settings");
+  const [samples, setSamples] = useState<ResourceSample[]>([]);
+
+  const load = useCallback(() => {
+    pluginResourceHistory(pluginId)
+      .then(setSamples)
+      .catch(() => {});
+  }, [pluginId]);
+
+  useEffect(() => {
+    load();
+    const interval = setInterval(load, REFRESH_MS);
+    return () => clearInterval(interval);
+  }, [load]);
+
+  const series = useMemo(
+    () => ({
+      cpu: samples.map((s) => s.cpu_percent),
+      memory: samples.map((s) => s.memory_mb),
+      network: samples.map((s) => s.net_rx_bps + s.net_tx_bps),
+    }),
+    [samples]
+  );
+
+  // A single sample can't draw a line
+  if (samples.length < 2) return null;
+
+  const latest = samples[samples.length - 1];
+
+  return (
+    <>
+      <Divider className="my-3" />
+      <div className="flex items-center gap-1.5 mb-2">
+        <Activity size={11} strokeWidth={1.5} className="text-default-400" />
+        <span className="text-[11px] text-default-400">{t("pluginsTab.resourceHistory")}</span>
+      </div>
+      <div className="flex gap-4">
+        <Metric
+          label={t("system.cpu")}
+          value={`${latest.cpu_percent.toFixed(1)}%`}
+          values={series.cpu}
+          className="text-primary"
+        />
+        <Metric
+          label={t("system.memory")}
+          value={t("system.mb", { value: latest.memory_mb.toFixed(0) })}
+          values={series.memory}
+          className="text-secondary"
+        />
+        <Metric
+          label={t("pluginsTab.network")}
+          value={formatRate(latest.net_rx_bps + latest.net_tx_bps)}
+          values={series.network}
+          className="text-success"
+        />
+      </div>
+    </>
+  );
+}
@@ -1,9 +1,48 @@
-import { useCallback, useEffect, useState } from "react";
+import { useCallback, useEffect, useRef, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { useAppStore } from "../../stores/appStore";
-import { oauthListClients, oauthRevokeClient } from "../../lib/tauri";
-import type { OAuthClientInfo } from "../../types/oauth";
-import { Shield, KeyRound, Search, ChevronDown, Trash2 } from "lucide-react";
+import {
+  getCredentialStorage,
+  getImageScanPolicy,
+  getReadOnlyMode,
+  getRootUserPolicy,
+  oauthDeviceLookup,
+  oauthDeviceResolve,
+  oauthGetClientGrants,
+  oauthListClients,
+  oauthRestrictClientGrants,
+  oauthRevokeClient,
+  oauthSetTokenFormat,
+  permissionExport,
+  permissionImport,
+  setCredentialStorage,
+  setImageScanPolicy,
+  setReadOnlyMode,
+  setRootUserPolicy,
+  type CredentialStorage as CredentialStorageKind,
+  type ImageScanPolicy,
+  type RootUserPolicy,
+} from "../../lib/tauri";
+import type {
+  AuthorizationDetail,
+  DeviceAuthorizationInfo,
+  OAuthClientGrants,
+  OAuthClientInfo,
+} from "../../types/oauth";
+import {
+  Shield,
+  KeyRound,
+  Search,
+  ChevronDown,
+  Trash2,
+  MonitorSmartphone,
+  SlidersHorizontal,
+  Download,
+  Upload,
+  Lock,
+  ScanSearch,
+  UserX,
+} from "lucide-react";
 import {
   Button,
   Input,
@@ -11,6 +50,9 @@ import {
   Card,
   CardBody,
   Divider,
+  Switch,
+  RadioGroup,
+  Radio,
   Modal,
   ModalContent,
   ModalHeader,
@@ -19,10 +61,163 @@ import {
 } from "@heroui/react";
 import { PermissionList } from "../permissions/PermissionList";
 
+function detailLabel(detail: AuthorizationDetail): string {
+  const target = detail.identifier ? ` ${detail.identifier}` : "";
+  return `${detail.type}${target}: ${detail.actions.join(", ")}`;
+}
+
+/** Removal-only editor for a client's scopes and authorization details. */
+function ClientScopesModal({
+  client,
+  onClose,
+}: {
+  client: OAuthClientInfo | null;
+  onClose: () => void;
+}) {
+  const { t } = useTranslation("settings");
+  const [grants, setGrants] = useState<OAuthClientGrants | null>(null);
+  const [scopes, setScopes] = useState<string[]>([]);
+  const [details, setDetails] = useState<AuthorizationDetail[]>([]);
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    setGrants(null);
+    setError(null);
+    if (!client) return;
+    oauthGetClientGrants(client.client_id)
+      .then((g) => {
+        setGrants(g);
+        setScopes(g.scopes);
+        setDetails(g.authorization_details);
+      })
+      .catch((e) => setError(String(e)));
+  }, [client]);
+
+  const removeLocation = useCallback((index: number, location: string) => {
+    setDetails((prev) =>
+      prev.map((d, i) =>
+        i === index ? { ...d, locations: d.locations?.filter((l) => l !== location) } : d
+      )
+    );
+  }, []);
+
+  async function handleSave() {
+    if (!client) return;
+    setBusy(true);
+    try {
+      // A detail whose last location was removed grants nothing; drop it
+      // rather than sending an empty list, which would read as unrestricted.
+      const kept = details.filter((d) => !d.locations || d.locations.length > 0);
+      await oauthRestrictClientGrants(client.client_id, scopes, kept);
+      onClose();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  const handleOpenChange = useCallback(
+    (open: boolean) => {
+      if (!open) onClose();
+    },
+    [onClose]
+  );
+
+  return (
+    <Modal isOpen={client !== null} onOpenChange={handleOpenChange} size="lg">
+      <ModalContent>
+        {(close) => (
+          <>
+            <ModalHeader className="text-[14px]">
+              {t("securityTab.clientScopesTitle", { clientName: client?.client_name ?? "" })}
+            </ModalHeader>
+            <ModalBody>
+              <p className="text-[11px] text-default-400">
+                {t("securityTab.clientScopesDesc")}
+              </p>
+              {grants && (
+                <>
+                  <h4 className="text-[12px] font-semibold">{t("securityTab.scopes")}</h4>
+                  {scopes.length === 0 ? (
+                    <p className="text-[11px] text-default-400">{t("securityTab.noScopes")}</p>
+                  ) : (
+                    <div className="flex flex-wrap gap-1.5">
+                      {scopes.map((scope) => (
+                        <Chip
+                          key={scope}
+                          size="sm"
+                          variant="flat"
+                          // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                          onClose={() => setScopes((prev) => prev.filter((s) => s !== scope))}
+                        >
+                          <span className="font-mono">{scope}</span>
+                        </Chip>
+                      ))}
+                    </div>
+                  )}
+                  <h4 className="text-[12px] font-semibold">
+                    {t("securityTab.authorizationDetails")}
+                  </h4>
+                  {details.length === 0 ? (
+                    <p className="text-[11px] text-default-400">
+                      {t("securityTab.noAuthorizationDetails")}
+                    </p>
+                  ) : (
+                    <div className="space-y-2">
+                      {details.map((detail, index) => (
+                        <div key={`${detailLabel(detail)}-${index}`} className="space-y-1">
+                          <Chip
+                            size="sm"
+                            variant="flat"
+                            color="primary"
+                            // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                            onClose={() => setDetails((prev) => prev.filter((_, i) => i !== index))}
+                          >
+                            <span className="font-mono">{detailLabel(detail)}</span>
+                          </Chip>
+                          {detail.locations && detail.locations.length > 0 && (
+                            <div className="flex flex-wrap gap-1 pl-3">
+                              {detail.locations.map((location) => (
+                                <Chip
+                                  key={location}
+                                  size="sm"
+                                  variant="bordered"
+                                  // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                                  onClose={() => removeLocation(index, location)}
+                                >
+                                  <span className="font-mono text-[10px]">{location}</span>
+                                </Chip>
+                              ))}
+                            </div>
+                          )}
+                        </div>
+                      ))}
+                    </div>
+                  )}
+                </>
+              )}
+              {error && <p className="text-[11px] text-danger">{error}</p>}
+            </ModalBody>
+            <ModalFooter>
+              <Button onPress={close}>{t("common:action.cancel")}</Button>
+              <Button color="primary" onPress={handleSave} isDisabled={!grants || busy}>
+                {t("securityTab.applyRestrictions")}
+              </Button>
+            </ModalFooter>
+          </>
+        )}
+      </ModalContent>
+    </Modal>
+  );
+}
+
 function ConnectedClients() {
   const { t } = useTranslation("settings");
   const [clients, setClients] = useState<OAuthClientInfo[]>([]);
   const [revokeTarget, setRevokeTarget] = useState<OAuthClientInfo | null>(null);
+  const [scopesTarget, setScopesTarget] = useState<OAuthClientInfo | null>(null);
 
   const load = useCallback(() => {
     oauthListClients().then(setClients).catch(() => {});
@@ -44,6 +239,17 @@ function ConnectedClients() {
     }
   }
 
+  async function handleTokenFormat(clientId: string, jwt: boolean) {
+    try {
+      await oauthSetTokenFormat(clientId, jwt ? "jwt" : "opaque");
+      load();
+    } catch {
+      /* ignore */
+    }
+  }
+
+  const handleCloseScopesModal = useCallback(() => setScopesTarget(null), []);
+
   const handleCloseRevokeModal = useCallback((open: boolean) => {
     if (!open) setRevokeTarget(null);
   }, []);
@@ -93,6 +299,24 @@ function ConnectedClients() {
                   {new Date(client.registered_at).toLocaleDateString()}
                 </span>
               </div>
+              <div className="flex items-center gap-3 flex-shrink-0 ml-2">
+              <Switch
+                size="sm"
+                isSelected={client.access_token_format === "jwt"}
+                // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                onValueChange={(jwt) => handleTokenFormat(client.client_id, jwt)}
+              >
+                <span className="text-[11px] text-default-500">
+                  {t("securityTab.jwtTokens")}
+                </span>
+              </Switch>
+              <Button
+                // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                onPress={() => setScopesTarget(client)}
+                startContent={<SlidersHorizontal size={10} strokeWidth={2} />}
+              >
+                {t("securityTab.editScopes")}
+              </Button>
               <Button
                 color="danger"
                 // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
@@ -101,12 +325,15 @@ function ConnectedClients() {
               >
                 {t("securityTab.revoke")}
               </Button>
+              </div>
               </CardBody>
             </Card>
           ))}
         </div>
       )}
 
+      <ClientScopesModal client={scopesTarget} onClose={handleCloseScopesModal} />
+
       {/* Revoke confirmation dialog */}
       <Modal
         isOpen={revokeTarget !== null}
@@ -143,11 +370,340 @@ function ConnectedClients() {
   );
 }
 
+function AuthorizeDevice() {
+  const { t } = useTranslation("settings");
+  const [code, setCode] = useState("");
+  const [pending, setPending] = useState<DeviceAuthorizationInfo | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const handleLookup = useCallback(async () => {
+    setBusy(true);
+    setMessage(null);
+    try {
+      setPending(await oauthDeviceLookup(code));
+    } catch {
+      setPending(null);
+      setMessage(t("securityTab.deviceCodeInvalid"));
+    } finally {
+      setBusy(false);
+    }
+  }, [code, t]);
+
+  const resolve = useCallback(
+    async (approve: boolean) => {
+      if (!pending) return;
+      setBusy(true);
+      try {
+        await oauthDeviceResolve(pending.user_code, approve);
+        setMessage(
+          approve ? t("securityTab.deviceApproved") : t("securityTab.deviceDenied")
+        );
+        setCode("");
+      } catch {
+        setMessage(t("securityTab.deviceCodeInvalid"));
+      } finally {
+        setPending(null);
+        setBusy(false);
+      }
+    },
+    [pending, t]
+  );
+
+  const handleAllow = useCallback(() => resolve(true), [resolve]);
+  const handleDeny = useCallback(() => resolve(false), [resolve]);
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+      <div className="flex items-center gap-2 mb-4">
+        <MonitorSmartphone size={15} strokeWidth={1.5} className="text-default-500" />
+        <h3 className="text-[14px] font-semibold">
+          {t("securityTab.authorizeDevice")}
+        </h3>
+      </div>
+
+      <p className="text-[11px] text-default-400 mb-4">
+        {t("securityTab.authorizeDeviceDesc")}
+      </p>
+
+      {pending ? (
+        <Card>
+          <CardBody className="p-3 flex-row items-center justify-between">
+            <div className="min-w-0">
+              <p className="text-[13px] font-medium truncate">
+                {t("securityTab.deviceRequest", { clientName: pending.client_name })}
+              </p>
+              <p className="text-[11px] text-default-400 font-mono">
+                {t("securityTab.deviceScopes", { scopes: pending.scopes.join(", ") })}
+              </p>
+            </div>
+            <div className="flex items-center gap-2 flex-shrink-0 ml-2">
+              <Button onPress={handleDeny} isDisabled={busy}>
+                {t("securityTab.deviceDeny")}
+              </Button>
+              <Button color="primary" onPress={handleAllow} isDisabled={busy}>
+                {t("securityTab.deviceAllow")}
+              </Button>
+            </div>
+          </CardBody>
+        </Card>
+      ) : (
+        <div className="flex items-center gap-2">
+          <Input
+            type="text"
+            value={code}
+            onValueChange={setCode}
+            placeholder={t("securityTab.deviceCodePlaceholder")}
+            classNames={{ input: "font-mono uppercase" }}
+            variant="bordered"
+          />
+          <Button
+            onPress={handleLookup}
+            isDisabled={busy || code.trim().length === 0}
+          >
+            {t("securityTab.deviceContinue")}
+          </Button>
+        </div>
+      )}
+
+      {message && (
+        <p className="text-[11px] text-default-400 mt-3">{message}</p>
+      )}
+      </CardBody>
+    </Card>
+  );
+}
+
+/** Panic switch: reject every state-changing Host API and MCP call. */
+function ReadOnlyMode() {
+  const { t } = useTranslation("settings");
+  const [enabled, setEnabled] = useState(false);
+
+  useEffect(() => {
+    getReadOnlyMode().then(setEnabled).catch(() => {});
+  }, []);
+
+  const handleToggle = useCallback(async (next: boolean) => {
+    setEnabled(next);
+    try {
+      await setReadOnlyMode(next);
+    } catch {
+      setEnabled(!next);
+    }
+  }, []);
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+      <div className="flex items-center justify-between mb-2">
+        <div className="flex items-center gap-2">
+          <Lock size={15} strokeWidth={1.5} className="text-default-500" />
+          <h3 className="text-[14px] font-semibold">
+            {t("securityTab.readOnlyMode")}
+          </h3>
+          {enabled && (
+            <Chip size="sm" variant="flat" color="warning">
+              {t("securityTab.readOnlyActive")}
+            </Chip>
+          )}
+        </div>
+        <Switch isSelected={enabled} onValueChange={handleToggle} color="warning" />
+      </div>
+      <p className="text-[11px] text-default-400">
+        {t("securityTab.readOnlyModeDesc")}
+      </p>
+      </CardBody>
+    </Card>
+  );
+}
+
+/** Whether registry credentials and plugin secrets live in the OS keychain. */
+function CredentialStorage() {
+  const { t } = useTranslation("settings");
+  const [storage, setStorage] = useState<CredentialStorageKind | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    getCredentialStorage().then(setStorage).catch(() => {});
+  }, []);
+
+  const handleToggle = useCallback(
+    async (useKeychain: boolean) => {
+      const next: CredentialStorageKind = useKeychain ? "keychain" : "file";
+      setBusy(true);
+      try {
+        await setCredentialStorage(next);
+        setStorage(next);
+      } catch (e) {
+        useAppStore
+          .getState()
+          .addNotification(t("securityTab.credentialStorageFailed", { error: String(e) }), "error");
+      } finally {
+        setBusy(false);
+      }
+    },
+    [t]
+  );
+
+  if (!storage) return null;
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+      <div className="flex items-center justify-between mb-2">
+        <div className="flex items-center gap-2">
+          <KeyRound size={15} strokeWidth={1.5} className="text-default-500" />
+          <h3 className="text-[14px] font-semibold">
+            {t("securityTab.credentialStorage")}
+          </h3>
+        </div>
+        <Switch
+          isSelected={storage === "keychain"}
+          onValueChange={handleToggle}
+          isDisabled={busy}
+        />
+      </div>
+      <p className="text-[11px] text-default-400">
+        {storage === "keychain"
+          ? t("securityTab.credentialStorageKeychain")
+          : t("securityTab.credentialStorageFile")}
+      </p>
+      </CardBody>
+    </Card>
+  );
+}
+
+const RADIO_GROUP_CLASSNAMES = { wrapper: "gap-4" };
+
+/** What critical vulnerabilities in a plugin image do at install and update. */
+function ImageScan() {
+  const { t } = useTranslation("settings");
+  const [policy, setPolicy] = useState<ImageScanPolicy | null>(null);
+
+  useEffect(() => {
+    getImageScanPolicy().then(setPolicy).catch(() => {});
+  }, []);
+
+  const handleChange = useCallback(
+    async (value: string) => {
+      const next = value as ImageScanPolicy;
+      try {
+        await setImageScanPolicy(next);
+        setPolicy(next);
+      } catch (e) {
+        useAppStore.getState().addNotification(String(e), "error");
+      }
+    },
+    []
+  );
+
+  if (!policy) return null;
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+      <div className="flex items-center gap-2 mb-2">
+        <ScanSearch size={15} strokeWidth={1.5} className="text-default-500" />
+        <h3 className="text-[14px] font-semibold">
+          {t("securityTab.imageScan")}
+        </h3>
+      </div>
+      <p className="text-[11px] text-default-400 mb-3">
+        {t("securityTab.imageScanDesc")}
+      </p>
+      <RadioGroup
+        orientation="horizontal"
+        value={policy}
+        onValueChange={handleChange}
+        classNames={RADIO_GROUP_CLASSNAMES}
+      >
+        <Radio value="off" size="sm">
+          {t("securityTab.imageScanOff")}
+        </Radio>
+        <Radio value="warn" size="sm">
+          {t("securityTab.imageScanWarn")}
+        </Radio>
+        <Radio value="block" size="sm">
+          {t("securityTab.imageScanBlock")}
+        </Radio>
+      </RadioGroup>
+      </CardBody>
+    </Card>
+  );
+}
+
+/** What plugin images that run as root do at install and update. */
+function RootUser() {
+  const { t } = useTranslation("settings");
+  const [policy, setPolicy] = useState<RootUserPolicy | null>(null);
+
+  useEffect(() => {
+    getRootUserPolicy().then(setPolicy).catch(() => {});
+  }, []);
+
+  const handleChange = useCallback(
+    async (value: string) => {
+      const next = value as RootUserPolicy;
+      try {
+        await setRootUserPolicy(next);
+        setPolicy(next);
+      } catch (e) {
+        useAppStore.getState().addNotification(String(e), "error");
+      }
+    },
+    []
+  );
+
+  if (!policy) return null;
+
+  return (
+    <Card>
+      <CardBody className="p-5">
+      <div className="flex items-center gap-2 mb-2">
+        <UserX size={15} strokeWidth={1.5} className="text-default-500" />
+        <h3 className="text-[14px] font-semibold">
+          {t("securityTab.rootUser")}
+        </h3>
+      </div>
+      <p className="text-[11px] text-default-400 mb-3">
+        {t("securityTab.rootUserDesc")}
+      </p>
+      <RadioGroup
+        orientation="horizontal"
+        value={policy}
+        onValueChange={handleChange}
+        classNames={RADIO_GROUP_CLASSNAMES}
+      >
+        <Radio value="allow" size="sm">
+          {t("securityTab.rootUserAllow")}
+        </Radio>
+        <Radio value="warn" size="sm">
+          {t("securityTab.rootUserWarn")}
+        </Radio>
+        <Radio value="block" size="sm">
+          {t("securityTab.rootUserBlock")}
+        </Radio>
+        <Radio value="force_non_root" size="sm">
+          {t("securityTab.rootUserForce")}
+        </Radio>
+      </RadioGroup>
+      {policy === "force_non_root" && (
+        <p className="text-[11px] text-warning mt-2">
+          {t("securityTab.rootUserForceHint")}
+        </p>
+      )}
+      </CardBody>
+    </Card>
+  );
+}
+
 export function SecurityTab() {
   const { t } = useTranslation("settings");
   const installedPlugins = useAppStore((s) => s.installedPlugins);
   const [permSearch, setPermSearch] = useState("");
   const [permExpanded, setPermExpanded] = useState<Set<string>>(new Set());
+  const importInputRef = useRef<HTMLInputElement>(null);
 
   const filtered = installedPlugins.filter(
     (p) =>
@@ -164,19 +720,100 @@ export function SecurityTab() {
     });
   }
 
+  async function handleExport() {
+    try {
+      const json = await permissionExport();
+      const blob = new Blob([json], { type: "application/json" });
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement("a");
+      a.href = url;
+      a.download = `nexus-permissions-${new Date().toISOString().slice(0, 10)}.json`;
+      a.click();
+      URL.revokeObjectURL(url);
+    } catch {
+      /* ignore */
+    }
+  }
+
+  const handleImportClick = useCallback(() => importInputRef.current?.click(), []);
+
+  async function handleImportFile(e: React.ChangeEvent<HTMLInputElement>) {
+    const file = e.target.files?.[0];
+    e.target.value = "";
+    if (!file) return;
+    const { addNotification, bumpPermissionsVersion } = useAppStore.getState();
+    try {
+      const report = await permissionImport(await file.text());
+      addNotification(t("pluginsTab.importedCount", { count: report.imported.length }), "success");
+      if (report.skipped_plugins.length > 0) {
+        addNotification(t("pluginsTab.skippedCount", { count: report.skipped_plugins.length }), "info");
+      }
+      if (report.dropped.length > 0) {
+        addNotification(t("pluginsTab.droppedCount", { count: report.dropped.length }), "info");
+      }
+      bumpPermissionsVersion();
+    } catch (err) {
+      addNotification(t("pluginsTab.importFailed", { error: String(err) }), "error");
+    }
+  }
+
   return (
     <div className="space-y-6">
+      {/* Read-only mode */}
+      <ReadOnlyMode />
+
+      {/* Credential storage */}
+      <CredentialStorage />
+
+      {/* Image vulnerability scanning */}
+      <ImageScan />
+
+      {/* Root user policy */}
+      <RootUser />
+
       {/* Connected Clients */}
       <ConnectedClients />
 
+      {/* Device Authorization (RFC 8628) */}
+      <AuthorizeDevice />
+
       {/* Plugin Permissions */}
       <Card>
         <CardBody className="p-5">
-        <div className="flex items-center gap-2 mb-4">
-          <Shield size={15} strokeWidth={1.5} className="text-default-500" />
-          <h3 className="text-[14px] font-semibold">
-            {t("pluginsTab.permissions")}
-          </h3>
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Shield size={15} strokeWidth={1.5} className="text-default-500" />
+            <h3 className="text-[14px] font-semibold">
+              {t("pluginsTab.permissions")}
+            </h3>
+          </div>
+          <div className="flex items-center gap-2">
+            <Button
+              size="sm"
+              variant="flat"
+              startContent={<Download size={12} strokeWidth={1.5} />}
+              onPress={handleExport}
+              title={t("pluginsTab.exportPermissionsTooltip")}
+            >
+              {t("pluginsTab.exportPermissions")}
+            </Button>
+            <Button
+              size="sm"
+              variant="flat"
+              startContent={<Upload size={12} strokeWidth={1.5} />}
+              onPress={handleImportClick}
+              title={t("pluginsTab.importPermissionsTooltip")}
+            >
+              {t("pluginsTab.importPermissions")}
+            </Button>
+            <input
+              ref={importInputRef}
+              type="file"
+              accept="application/json,.json"
+              className="hidden"
+              onChange={handleImportFile}
+            />
+          </div>
         </div>
 
         {installedPlugins.length === 0 ? (
@@ -3,20 +3,26 @@ import { useTranslation } from "react-i18next";
 import {
   checkEngine,
   containerResourceUsage,
+  engineConfig,
   getResourceQuotas,
   saveResourceQuotas,
+  switchEngine,
+  type EngineConfig,
+  type EngineKind,
   type EngineStatus,
   type ResourceUsage,
   type ResourceQuotas,
 } from "../../lib/tauri";
 import { Container, RefreshCw, Gauge, Save, Check } from "lucide-react";
 import { Button, Input, Card, CardBody, Chip, Divider } from "@heroui/react";
+import { DiskUsageCard } from "./DiskUsageCard";
+import { DataDirectoryCard } from "./DataDirectoryCard";
+import { ProxySettings } from "./ProxySettings";
+import { useAppStore } from "../../stores/appStore";
 
-type RuntimeEngine = "docker" | "podman" | "finch";
-
-const ENGINES: { id: RuntimeEngine; label: string; available: boolean }[] = [
+const ENGINES: { id: EngineKind | "finch"; label: string; available: boolean }[] = [
   { id: "docker", label: "Docker", available: true },
-  { id: "podman", label: "Podman", available: false },
+  { id: "podman", label: "Podman", available: true },
   { id: "finch", label: "Finch", available: false },
 ];
 
@@ -24,7 +30,10 @@ export function SystemTab() {
   const { t } = useTranslation("settings");
 
   // --- Runtime state ---
-  const [engine, setEngine] = useState<RuntimeEngine>("docker");
+  const [config, setConfig] = useState<EngineConfig | null>(null);
+  const [selected, setSelected] = useState<EngineKind>("docker");
+  const [host, setHost] = useState("");
+  const [switching, setSwitching] = useState(false);
   const [status, setStatus] = useState<EngineStatus | null>(null);
   const [checking, setChecking] = useState(false);
 
@@ -47,6 +56,29 @@ export function SystemTab() {
     }
   }, [t]);
 
+  const loadEngineConfig = useCallback(async () => {
+    try {
+      const c = await engineConfig();
+      setConfig(c);
+      setSelected(c.kind);
+      setHost(c.host ?? "");
+    } catch {
+      // ignore
+    }
+  }, []);
+
+  const selectEngine = useCallback(
+    (kind: EngineKind) => {
+      setSelected(kind);
+      // An endpoint belongs to one engine; keep it only for the current one
+      setHost(kind === config?.kind ? (config.host ?? "") : "");
+    },
+    [config]
+  );
+
+  const changed =
+    config !== null && (selected !== config.kind || host.trim() !== (config.host ?? ""));
+
   // --- Resources state ---
   const [usage, setUsage] = useState<ResourceUsage | null>(null);
   const [quotas, setQuotas] = useState<ResourceQuotas>({
@@ -78,11 +110,44 @@ export function SystemTab() {
 
   useEffect(() => {
     refreshEngine();
+    loadEngineConfig();
     refreshUsage();
     loadQuotas();
     const interval = setInterval(refreshUsage, 5000);
     return () => clearInterval(interval);
-  }, [refreshEngine, refreshUsage, loadQuotas]);
+  }, [refreshEngine, loadEngineConfig, refreshUsage, loadQuotas]);
+
+  async function handleSwitch() {
+    const { addNotification } = useAppStore.getState();
+    setSwitching(true);
+    try {
+      const report = await switchEngine(selected, host.trim() || null);
+      addNotification(
+        t("system.switched", {
+          engine: ENGINES.find((e) => e.id === selected)?.label,
+          count: report.restarted.length,
+        }),
+        "success"
+      );
+      const problems: [string[], string][] = [
+        [report.failed_to_start, "system.failedToStart"],
+        [report.missing_images, "system.imagesMissing"],
+        [report.volumes_not_copied, "system.volumesNotCopied"],
+      ];
+      for (const [plugins, key] of problems) {
+        if (plugins.length > 0) {
+          addNotification(t(key, { plugins: plugins.join(", ") }), "error");
+        }
+      }
+      await loadEngineConfig();
+    } catch (e) {
+      addNotification(t("system.switchFailed", { error: String(e) }), "error");
+    } finally {
+      setSwitching(false);
+      refreshEngine();
+      refreshUsage();
+    }
+  }
 
   async function handleSave() {
     setSaving(true);
@@ -162,9 +227,10 @@ export function SystemTab() {
             {ENGINES.map((e) => (
               <Button
                 key={e.id}
+                color={selected === e.id ? "primary" : "default"}
                 // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
-                onPress={() => e.available && setEngine(e.id)}
-                isDisabled={!e.available}
+                onPress={() => e.available && selectEngine(e.id as EngineKind)}
+                isDisabled={!e.available || switching}
               >
                 {e.label}
                 {!e.available && (
@@ -176,88 +242,93 @@ export function SystemTab() {
             ))}
           </div>
 
-          {/* Docker status */}
-          {engine === "docker" && (
-            <div className="space-y-4">
-              <div className="flex items-center justify-between">
-                <span className="text-[12px] text-default-500">{t("system.status")}</span>
-                <Button
-                  onPress={refreshEngine}
-                  isDisabled={checking}
-                  startContent={
-                    <RefreshCw
-                      size={12}
-                      strokeWidth={1.5}
-                      className={checking ? "animate-spin" : ""}
-                    />
-                  }
-                >
-                  {checking ? t("common:action.checking") : t("common:action.refresh")}
+          <div className="mb-5">
+            <Input
+              label={t("system.endpoint")}
+              placeholder={t("system.endpointPlaceholder")}
+              description={t("system.endpointHint")}
+              value={host}
+              onValueChange={setHost}
+              isDisabled={switching}
+            />
+            {changed && (
+              <div className="mt-3 space-y-2">
+                <p className="text-[11px] text-warning">{t("system.switchWarning")}</p>
+                <Button color="primary" onPress={handleSwitch} isDisabled={switching}>
+                  {switching ? t("system.switching") : t("system.switchEngine")}
                 </Button>
               </div>
+            )}
+          </div>
 
-              {status === null ? (
-                <div className="text-[13px] text-default-500">
-                  {t("system.checkingEngine")}
-                </div>
-              ) : (
-                <div className="space-y-3">
-                  <div className="flex items-center justify-between">
-                    <div className="flex items-center gap-2.5">
-                      <span
-                        className={`w-1.5 h-1.5 rounded-full ${
-                          status.running
-                            ? "bg-success"
-                            : status.installed
-                              ? "bg-warning"
-                              : "bg-danger"
-                        }`}
-                        style={engineStatusStyle}
-                      />
-                      <span className="text-[13px] text-default-500">
-                        {t("system.engine")}
-                      </span>
-                    </div>
-                    <Chip
-                      size="sm"
-                      variant="flat"
-                      color={status.running ? "success" : status.installed ? "warning" : "danger"}
-                    >
-                      {status.running
-                        ? status.version
-                          ? t("system.runningVersion", { version: status.version })
-                          : t("common:status.running")
-                        : status.installed
-                          ? t("system.stopped")
-                          : t("system.notFound")}
-                    </Chip>
-                  </div>
+          <div className="space-y-4">
+            <div className="flex items-center justify-between">
+              <span className="text-[12px] text-default-500">{t("system.status")}</span>
+              <Button
+                onPress={refreshEngine}
+                isDisabled={checking}
+                startContent={
+                  <RefreshCw
+                    size={12}
+                    strokeWidth={1.5}
+                    className={checking ? "animate-spin" : ""}
+                  />
+                }
+              >
+                {checking ? t("common:action.checking") : t("common:action.refresh")}
+              </Button>
+            </div>
 
-                  <div className="flex items-center justify-between">
-                    <span className="text-[12px] text-default-500">{t("system.socket")}</span>
-                    <span className="text-[11px] text-default-400 font-mono truncate ml-4">
-                      {status.socket}
+            {status === null ? (
+              <div className="text-[13px] text-default-500">
+                {t("system.checkingEngine")}
+              </div>
+            ) : (
+              <div className="space-y-3">
+                <div className="flex items-center justify-between">
+                  <div className="flex items-center gap-2.5">
+                    <span
+                      className={`w-1.5 h-1.5 rounded-full ${
+                        status.running
+                          ? "bg-success"
+                          : status.installed
+                            ? "bg-warning"
+                            : "bg-danger"
+                      }`}
+                      style={engineStatusStyle}
+                    />
+                    <span className="text-[13px] text-default-500">
+                      {t("system.engine")}
                     </span>
                   </div>
+                  <Chip
+                    size="sm"
+                    variant="flat"
+                    color={status.running ? "success" : status.installed ? "warning" : "danger"}
+                  >
+                    {status.running
+                      ? status.version
+                        ? t("system.runningVersion", { version: status.version })
+                        : t("common:status.running")
+                      : status.installed
+                        ? t("system.stopped")
+                        : t("system.notFound")}
+                  </Chip>
+                </div>
 
-                  <p className="text-[11px] text-default-400">
-                    {status.message}
-                  </p>
+                <div className="flex items-center justify-between">
+                  <span className="text-[12px] text-default-500">{t("system.socket")}</span>
+                  <span className="text-[11px] text-default-400 font-mono truncate ml-4">
+                    {status.socket}
+                  </span>
                 </div>
-              )}
-            </div>
-          )}
 
-          {engine !== "docker" && (
-            <div className="text-center py-8">
-              <p className="text-[13px] text-default-500">
-                {t("system.engineSoon", { engine: engine === "podman" ? "Podman" : "Finch" })}
-              </p>
-              <p className="text-[11px] text-default-400 mt-1">
-                {t("system.engineFuture")}
-              </p>
-            </div>
-          )}
+                <p className="text-[11px] text-default-400">
+                  {status.message}
+                </p>
+              </div>
+            )}
+          </div>
         </CardBody>
       </Card>
 
@@ -315,6 +386,12 @@ export function SystemTab() {
         </CardBody>
       </Card>
 
+      <ProxySettings />
+
+      <DiskUsageCard />
+
+      <DataDirectoryCard />
+
       {/* Quotas */}
       <Card>
         <CardBody className="p-5">
@@ -8,12 +8,21 @@ import {
   updatePlugin,
   updateExtension,
   updateExtensionForceKey,
+  updatePluginForceKey,
   lastUpdateCheck,
   setUpdateCheckInterval,
+  getAutoUpdatePolicy,
+  setAutoUpdatePolicy,
+  getYankedInstalls,
 } from "../../lib/tauri";
 import { useAppStore } from "../../stores/appStore";
 import { usePluginActions } from "../../hooks/usePlugins";
-import type { AvailableUpdate, UpdateSecurity } from "../../types/updates";
+import type {
+  AutoUpdatePolicy,
+  AvailableUpdate,
+  UpdateSecurity,
+  YankedInstall,
+} from "../../types/updates";
 import { KeyChangeWarningDialog } from "./KeyChangeWarningDialog";
 import {
   ArrowUpCircle,
@@ -25,8 +34,11 @@ import {
   ShieldAlert,
   ShieldX,
   Clock,
+  Zap,
+  ExternalLink,
+  AlertTriangle,
 } from "lucide-react";
-import { Button, Chip, Card, CardBody, Select, SelectItem } from "@heroui/react";
+import { Button, Chip, Card, CardBody, Select, SelectItem, Switch } from "@heroui/react";
 
 function useCheckIntervalOptions() {
   const { t } = useTranslation("settings");
@@ -109,6 +121,8 @@ export function UpdatesTab() {
   const [updatingId, setUpdatingId] = useState<string | null>(null);
   const [lastChecked, setLastChecked] = useState<string | null>(null);
   const [keyChangeUpdate, setKeyChangeUpdate] = useState<AvailableUpdate | null>(null);
+  const [autoUpdate, setAutoUpdate] = useState<AutoUpdatePolicy | null>(null);
+  const [yanked, setYanked] = useState<YankedInstall[]>([]);
 
   const loadLastChecked = useCallback(async () => {
     try {
@@ -123,6 +137,22 @@ export function UpdatesTab() {
     loadLastChecked();
   }, [loadLastChecked]);
 
+  useEffect(() => {
+    getAutoUpdatePolicy().then(setAutoUpdate).catch(() => {});
+    getYankedInstalls().then(setYanked).catch(() => {});
+  }, []);
+
+  async function handleAutoUpdateToggle(enabled: boolean) {
+    if (!autoUpdate) return;
+    const policy = { ...autoUpdate, enabled };
+    setAutoUpdate(policy);
+    try {
+      await setAutoUpdatePolicy(policy);
+    } catch {
+      setAutoUpdate(autoUpdate);
+    }
+  }
+
   async function handleIntervalChange(minutes: number) {
     useAppStore.getState().setUpdateCheckInterval(minutes);
     try {
@@ -138,6 +168,7 @@ export function UpdatesTab() {
       await marketplaceRefresh();
       const updates = await checkUpdates();
       useAppStore.getState().setAvailableUpdates(updates);
+      setYanked(await getYankedInstalls());
       await loadLastChecked();
       if (updates.length === 0) {
         useAppStore.getState().addNotification(i18n.t("common:notification.allUpToDate"), "success");
@@ -189,7 +220,11 @@ export function UpdatesTab() {
     setKeyChangeUpdate(null);
     setUpdatingId(update.item_id);
     try {
-      await updateExtensionForceKey(update.manifest_url);
+      if (update.item_type === "plugin") {
+        await updatePluginForceKey(update.manifest_url, update.new_image_digest, update.build_context);
+      } else {
+        await updateExtensionForceKey(update.manifest_url);
+      }
       useAppStore.getState().addNotification(
         i18n.t("common:notification.updatedToKeyChange", { name: update.item_name, version: update.available_version }),
         "success"
@@ -294,6 +329,49 @@ export function UpdatesTab() {
         </div>
       </CardBody></Card>
 
+      {/* Automatic updates */}
+      <Card><CardBody className="p-5">
+        <div className="flex items-center justify-between">
+          <div className="flex items-center gap-2">
+            <Zap size={15} strokeWidth={1.5} className="text-default-500" />
+            <div>
+              <h3 className="text-[13px] font-semibold">
+                {t("updates.autoUpdate")}
+              </h3>
+              <p className="text-[11px] text-default-400">
+                {t("updates.autoUpdateDesc")}
+              </p>
+            </div>
+          </div>
+          <Switch
+            isSelected={autoUpdate?.enabled ?? false}
+            isDisabled={!autoUpdate}
+            onValueChange={handleAutoUpdateToggle}
+          />
+        </div>
+      </CardBody></Card>
+
+      {/* Installed versions withdrawn by their registry */}
+      {yanked.map((item) => (
+        <Card key={`yanked:${item.item_id}`}>
+          <CardBody className="p-5">
+            <div className="flex items-start gap-2">
+              <AlertTriangle size={15} strokeWidth={1.5} className="text-danger mt-0.5 flex-shrink-0" />
+              <div className="min-w-0">
+                <h4 className="text-[13px] font-semibold">
+                  {t("updates.yankedTitle", { name: item.item_name, version: item.version })}
+                </h4>
+                <p className="text-[11px] text-default-500">
+                  {item.reason
+                    ? t("updates.yankedReason", { source: item.registry_source, reason: item.reason })
+                    : t("updates.yankedNoReason", { source: item.registry_source })}
+                </p>
+              </div>
+            </div>
+          </CardBody>
+        </Card>
+      ))}
+
       {/* Empty state */}
       {availableUpdates.length === 0 && (
         <Card><CardBody className="p-5">
@@ -335,6 +413,27 @@ export function UpdatesTab() {
                   </span>
                 </p>
                 <SecurityBadges security={update.security} />
+                {update.release_notes && (
+                  <details className="mt-2">
+                    <summary className="text-[11px] text-default-500 cursor-pointer">
+                      {t("updates.whatsNew")}
+                    </summary>
+                    <pre className="mt-1.5 max-h-48 overflow-auto whitespace-pre-wrap font-sans text-[11px] text-default-500">
+                      {update.release_notes}
+                    </pre>
+                  </details>
+                )}
+                {update.changelog_url?.startsWith("https://") && (
+                  <a
+                    href={update.changelog_url}
+                    target="_blank"
+                    rel="noopener noreferrer"
+                    className="mt-1.5 inline-flex items-center gap-1 text-[11px] text-primary"
+                  >
+                    <ExternalLink size={11} strokeWidth={1.5} />
+                    {t("updates.fullChangelog")}
+                  </a>
+                )}
               </div>
 
               <div className="flex items-center gap-2 flex-shrink-0 ml-4">
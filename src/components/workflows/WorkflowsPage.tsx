@@ -25,10 +25,20 @@ import {
   ChevronUp,
   Info,
   X,
+  AlertTriangle,
+  RotateCcw,
+  History,
 } from "lucide-react";
 import { Surface } from "@imdanibytes/nexus-ui";
 import * as api from "../../lib/tauri";
-import type { RoutingRule, Filter, RouteAction } from "../../types/workflows";
+import type {
+  RoutingRule,
+  Filter,
+  RouteAction,
+  DeadLetter,
+  WebhookMode,
+  PluginCommand,
+} from "../../types/workflows";
 import type { McpToolStatus } from "../../types/mcp";
 import { useAppStore } from "../../stores/appStore";
 
@@ -63,8 +73,20 @@ function fromSimpleFilters(simples: SimpleFilter[]): Filter[] {
     .map((s) => ({ [s.dialect]: { [s.attribute]: s.value } }) as Filter);
 }
 
+const CONDITION_PLACEHOLDER = 'data.ref == "refs/heads/main"';
+
+/** The data condition (`where` filter) of a rule, if any. */
+function whereCondition(filters: Filter[]): string {
+  for (const f of filters) {
+    if ("where" in f) return f.where;
+  }
+  return "";
+}
+
 /* ─── Action helpers ─── */
 
+const HEADERS_PLACEHOLDER = '{ "Authorization": "Bearer ..." }';
+
 function actionSummary(action: RouteAction, t: (key: string) => string): string {
   if (action.action === "invoke_plugin_tool") {
     return `${t("plugins:workflows.actionPluginTool")}: ${action.plugin_id} / ${action.tool_name}`;
@@ -72,15 +94,23 @@ function actionSummary(action: RouteAction, t: (key: string) => string): string
   if (action.action === "call_extension") {
     return `${t("plugins:workflows.actionExtensionOp")}: ${action.extension_id} / ${action.operation}`;
   }
+  if (action.action === "webhook") {
+    return `${t("plugins:workflows.actionWebhook")}: ${action.url}`;
+  }
+  if (action.action === "control_plugin") {
+    return `${t("plugins:workflows.actionControlPlugin")}: ${t(`plugins:workflows.command_${action.command}`)} ${action.plugin_id}`;
+  }
   return `${t("plugins:workflows.actionFrontendEvent")}: ${action.channel}`;
 }
 
 function filterSummary(filters: Filter[], t: (key: string) => string): string {
   if (filters.length === 0) return t("plugins:workflows.matchesAll");
-  const simple = toSimpleFilters(filters);
-  return simple
-    .map((s) => `${s.attribute} ${s.dialect} "${s.value}"`)
-    .join(", ");
+  const parts = toSimpleFilters(filters)
+    .filter((s) => s.value)
+    .map((s) => `${s.attribute} ${s.dialect} "${s.value}"`);
+  const condition = whereCondition(filters);
+  if (condition) parts.push(`where ${condition}`);
+  return parts.join(", ");
 }
 
 /* ─── Main page ─── */
@@ -185,6 +215,122 @@ export function WorkflowsPage() {
           ))}
         </div>
       )}
+
+      <DeadLetterQueue />
+    </div>
+  );
+}
+
+/* ─── Dead letter queue ─── */
+
+function DeadLetterQueue() {
+  const { t } = useTranslation(["plugins", "common"]);
+  const [entries, setEntries] = useState<DeadLetter[]>([]);
+
+  const load = useCallback(async () => {
+    try {
+      setEntries(await api.deadLetterList());
+    } catch {
+      // silent
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  const retry = useCallback(
+    async (ids: number[]) => {
+      try {
+        await api.deadLetterRetry(ids);
+      } catch {
+        // silent
+      }
+      load();
+    },
+    [load],
+  );
+
+  const purge = useCallback(
+    async (ids?: number[]) => {
+      try {
+        await api.deadLetterPurge(ids);
+      } catch {
+        // silent
+      }
+      load();
+    },
+    [load],
+  );
+
+  const handleRetryAll = useCallback(() => retry(entries.map((e) => e.id)), [retry, entries]);
+  const handlePurgeAll = useCallback(() => purge(), [purge]);
+
+  if (entries.length === 0) return null;
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center justify-between">
+        <div>
+          <h2 className="text-lg font-semibold flex items-center gap-2">
+            <AlertTriangle size={16} className="text-warning" />
+            {t("plugins:workflows.deadLetters", { count: entries.length })}
+          </h2>
+          <p className="text-xs text-default-500 mt-0.5">
+            {t("plugins:workflows.deadLettersHint")}
+          </p>
+        </div>
+        <div className="flex items-center gap-2">
+          <Button size="sm" variant="flat" startContent={<RotateCcw size={14} />} onPress={handleRetryAll}>
+            {t("plugins:workflows.retryAll")}
+          </Button>
+          <Button size="sm" variant="flat" color="danger" onPress={handlePurgeAll}>
+            {t("plugins:workflows.discardAll")}
+          </Button>
+        </div>
+      </div>
+      {entries.map((entry) => (
+        <Surface key={entry.id} className="p-4">
+          <div className="flex items-start justify-between gap-4">
+            <div className="flex-1 min-w-0">
+              <p className="text-sm font-medium truncate">
+                {entry.event_type}{" "}
+                <span className="text-xs text-default-400 font-normal">{entry.source}</span>
+              </p>
+              <p className="text-xs text-default-500 truncate">{actionSummary(entry.action, t)}</p>
+              <p className="text-xs text-danger mt-1 break-all">
+                {entry.last_error ?? t("plugins:workflows.noErrorRecorded")}
+              </p>
+              <p className="text-[11px] text-default-400 mt-1">
+                {t("plugins:workflows.deadLetterMeta", {
+                  count: entry.attempts,
+                  time: new Date(entry.failed_at).toLocaleString(),
+                })}
+              </p>
+            </div>
+            <div className="flex items-center gap-1 shrink-0">
+              <Tooltip content={t("plugins:workflows.retry")}>
+                {/* eslint-disable-next-line react-perf/jsx-no-new-function-as-prop */}
+                <Button isIconOnly size="sm" variant="light" onPress={() => retry([entry.id])}>
+                  <RotateCcw size={14} />
+                </Button>
+              </Tooltip>
+              <Tooltip content={t("plugins:workflows.discard")}>
+                <Button
+                  isIconOnly
+                  size="sm"
+                  variant="light"
+                  color="danger"
+                  // eslint-disable-next-line react-perf/jsx-no-new-function-as-prop
+                  onPress={() => purge([entry.id])}
+                >
+                  <Trash2 size={14} />
+                </Button>
+              </Tooltip>
+            </div>
+          </div>
+        </Surface>
+      ))}
     </div>
   );
 }
@@ -213,6 +359,12 @@ function EmptyState({ onCreate }: { onCreate: () => void }) {
 
 /* ─── Workflow card ─── */
 
+/** `datetime-local` input value for a point in the local timezone. */
+function toLocalInput(date: Date): string {
+  const offset = date.getTimezoneOffset() * 60_000;
+  return new Date(date.getTime() - offset).toISOString().slice(0, 16);
+}
+
 function WorkflowCard({
   rule,
   onEdit,
@@ -227,6 +379,12 @@ function WorkflowCard({
   t: (key: string) => string;
 }) {
   const deleteModal = useDisclosure();
+  const replayModal = useDisclosure();
+  const [replaySince, setReplaySince] = useState(() =>
+    toLocalInput(new Date(Date.now() - 24 * 3600_000)),
+  );
+  const [replaying, setReplaying] = useState(false);
+  const [replayResult, setReplayResult] = useState<string | null>(null);
 
   const handleDelete = useCallback(async () => {
     deleteModal.onClose();
@@ -238,6 +396,26 @@ function WorkflowCard({
     }
   }, [rule.id, onDeleted, deleteModal]);
 
+  const openReplay = useCallback(() => {
+    setReplayResult(null);
+    replayModal.onOpen();
+  }, [replayModal]);
+
+  const handleReplay = useCallback(async () => {
+    setReplaying(true);
+    try {
+      const since = replaySince ? new Date(replaySince).toISOString() : undefined;
+      const report = await api.eventReplay({ target: "rule", rule_id: rule.id }, { since });
+      setReplayResult(
+        t("plugins:workflows.replayDone").replace("{{count}}", String(report.replayed)),
+      );
+    } catch (e) {
+      setReplayResult(String(e));
+    } finally {
+      setReplaying(false);
+    }
+  }, [rule.id, replaySince, t]);
+
   const handleEdit = useCallback(() => onEdit(rule), [onEdit, rule]);
   const handleToggle = useCallback(() => onToggle(rule), [onToggle, rule]);
 
@@ -269,6 +447,17 @@ function WorkflowCard({
             isSelected={rule.enabled}
             onValueChange={handleToggle}
           />
+          <Tooltip content={t("plugins:workflows.replayHistory")}>
+            <Button
+              isIconOnly
+              size="sm"
+              variant="light"
+              isDisabled={!rule.enabled}
+              onPress={openReplay}
+            >
+              <History size={14} />
+            </Button>
+          </Tooltip>
           <Tooltip content={t("plugins:workflows.editWorkflow")}>
             <Button
               isIconOnly
@@ -320,6 +509,37 @@ function WorkflowCard({
           )}
         </ModalContent>
       </Modal>
+
+      <Modal isOpen={replayModal.isOpen} onOpenChange={replayModal.onOpenChange}>
+        <ModalContent>
+          {(onClose) => (
+            <>
+              <ModalHeader>
+                {t("plugins:workflows.replayTitle").replace("{{name}}", rule.name || rule.id)}
+              </ModalHeader>
+              <ModalBody>
+                <p className="text-sm text-default-500">{t("plugins:workflows.replayHint")}</p>
+                <Input
+                  type="datetime-local"
+                  label={t("plugins:workflows.replaySince")}
+                  value={replaySince}
+                  onValueChange={setReplaySince}
+                  size="sm"
+                />
+                {replayResult && <p className="text-sm text-default-600">{replayResult}</p>}
+              </ModalBody>
+              <ModalFooter>
+                <Button variant="flat" onPress={onClose}>
+                  {t("common:action.dismiss")}
+                </Button>
+                <Button color="primary" onPress={handleReplay} isLoading={replaying}>
+                  {t("plugins:workflows.replay")}
+                </Button>
+              </ModalFooter>
+            </>
+          )}
+        </ModalContent>
+      </Modal>
     </Surface>
   );
 }
@@ -343,6 +563,7 @@ function WorkflowEditor({
   const [filters, setFilters] = useState<SimpleFilter[]>(
     rule ? toSimpleFilters(rule.filters) : [{ dialect: "exact", attribute: "type", value: "" }],
   );
+  const [condition, setCondition] = useState(rule ? whereCondition(rule.filters) : "");
   const [actionType, setActionType] = useState<RouteAction["action"]>(
     rule?.action.action ?? "invoke_plugin_tool",
   );
@@ -361,6 +582,24 @@ function WorkflowEditor({
   const [channel, setChannel] = useState(
     rule?.action.action === "emit_frontend" ? rule.action.channel : "",
   );
+  const [targetPluginId, setTargetPluginId] = useState(
+    rule?.action.action === "control_plugin" ? rule.action.plugin_id : "",
+  );
+  const [pluginCommand, setPluginCommand] = useState<PluginCommand>(
+    rule?.action.action === "control_plugin" ? rule.action.command : "stop",
+  );
+  const [webhookUrl, setWebhookUrl] = useState(
+    rule?.action.action === "webhook" ? rule.action.url : "",
+  );
+  const [webhookMode, setWebhookMode] = useState<WebhookMode>(
+    rule?.action.action === "webhook" ? (rule.action.mode ?? "structured") : "structured",
+  );
+  const [webhookHeaders, setWebhookHeaders] = useState(() => {
+    if (rule?.action.action !== "webhook" || !rule.action.headers) return "";
+    return Object.keys(rule.action.headers).length > 0
+      ? JSON.stringify(rule.action.headers, null, 2)
+      : "";
+  });
   const [argsTemplate, setArgsTemplate] = useState(() => {
     if (!rule) return "";
     const a = rule.action;
@@ -371,11 +610,13 @@ function WorkflowEditor({
   });
 
   const [saving, setSaving] = useState(false);
+  const [saveError, setSaveError] = useState<string | null>(null);
   const [showTemplateHelp, setShowTemplateHelp] = useState(false);
 
   // Load available plugins/tools/extensions
   const [mcpTools, setMcpTools] = useState<McpToolStatus[]>([]);
   const installedExtensions = useAppStore((s) => s.installedExtensions);
+  const installedPlugins = useAppStore((s) => s.installedPlugins);
 
   useEffect(() => {
     api.mcpListTools().then(setMcpTools).catch(() => {});
@@ -458,17 +699,53 @@ function WorkflowEditor({
         ...(parsedArgs ? { args_template: parsedArgs } : {}),
       };
     }
+    if (actionType === "control_plugin") {
+      if (!targetPluginId) return null;
+      return { action: "control_plugin", plugin_id: targetPluginId, command: pluginCommand };
+    }
+    if (actionType === "webhook") {
+      if (!/^https?:\/\/\S+$/.test(webhookUrl.trim())) return null;
+      let headers: Record<string, string> | undefined;
+      if (webhookHeaders.trim()) {
+        try {
+          headers = JSON.parse(webhookHeaders);
+        } catch {
+          return null;
+        }
+      }
+      return {
+        action: "webhook",
+        url: webhookUrl.trim(),
+        mode: webhookMode,
+        ...(headers ? { headers } : {}),
+      };
+    }
     if (!channel) return null;
     return { action: "emit_frontend", channel };
-  }, [actionType, pluginId, toolName, extensionId, operation, channel, argsTemplate]);
+  }, [
+    actionType,
+    pluginId,
+    toolName,
+    extensionId,
+    operation,
+    channel,
+    argsTemplate,
+    webhookUrl,
+    webhookMode,
+    webhookHeaders,
+    targetPluginId,
+    pluginCommand,
+  ]);
 
   const handleSave = useCallback(async () => {
     const action = buildAction();
     if (!action) return;
 
     setSaving(true);
+    setSaveError(null);
     try {
       const ceFilters = fromSimpleFilters(filters);
+      if (condition.trim()) ceFilters.push({ where: condition.trim() });
       if (isEdit && rule) {
         await api.workflowUpdate({
           ruleId: rule.id,
@@ -484,12 +761,12 @@ function WorkflowEditor({
         });
       }
       onSave();
-    } catch {
-      // silent
+    } catch (e) {
+      setSaveError(String(e));
     } finally {
       setSaving(false);
     }
-  }, [buildAction, filters, isEdit, rule, name, onSave]);
+  }, [buildAction, filters, condition, isEdit, rule, name, onSave]);
 
   const canSave = !!buildAction();
 
@@ -520,6 +797,27 @@ function WorkflowEditor({
     setOperation("");
   }, []);
 
+  const targetPluginKeys = useMemo(
+    () => (targetPluginId ? [targetPluginId] : []),
+    [targetPluginId],
+  );
+  const targetPluginChange = useCallback((keys: "all" | Set<string | number>) => {
+    const val = keys === "all" ? undefined : (Array.from(keys)[0] as string);
+    setTargetPluginId(val ?? "");
+  }, []);
+
+  const pluginCommandKeys = useMemo(() => [pluginCommand], [pluginCommand]);
+  const pluginCommandChange = useCallback((keys: "all" | Set<string | number>) => {
+    const val = keys === "all" ? undefined : (Array.from(keys)[0] as string);
+    if (val) setPluginCommand(val as PluginCommand);
+  }, []);
+
+  const webhookModeKeys = useMemo(() => [webhookMode], [webhookMode]);
+  const webhookModeChange = useCallback((keys: "all" | Set<string | number>) => {
+    const val = keys === "all" ? undefined : (Array.from(keys)[0] as string);
+    if (val) setWebhookMode(val as WebhookMode);
+  }, []);
+
   const operationKeys = useMemo(() => (operation ? [operation] : []), [operation]);
   const operationChange = useCallback((keys: "all" | Set<string | number>) => {
     const val = keys === "all" ? undefined : (Array.from(keys)[0] as string);
@@ -611,6 +909,15 @@ function WorkflowEditor({
         >
           {t("plugins:workflows.addFilter")}
         </Button>
+        <Input
+          label={t("plugins:workflows.dataCondition")}
+          placeholder={CONDITION_PLACEHOLDER}
+          description={t("plugins:workflows.dataConditionHint")}
+          value={condition}
+          onValueChange={setCondition}
+          size="sm"
+          classNames={textareaClassNames}
+        />
       </Surface>
 
       {/* Action */}
@@ -632,8 +939,41 @@ function WorkflowEditor({
           <SelectItem key="emit_frontend">
             {t("plugins:workflows.actionFrontendEvent")}
           </SelectItem>
+          <SelectItem key="webhook">
+            {t("plugins:workflows.actionWebhook")}
+          </SelectItem>
+          <SelectItem key="control_plugin">
+            {t("plugins:workflows.actionControlPlugin")}
+          </SelectItem>
         </Select>
 
+        {actionType === "control_plugin" && (
+          <div className="flex gap-2">
+            <Select
+              label={t("plugins:workflows.pluginCommand")}
+              selectedKeys={pluginCommandKeys}
+              onSelectionChange={pluginCommandChange}
+              size="sm"
+              className="w-40"
+            >
+              <SelectItem key="start">{t("plugins:workflows.command_start")}</SelectItem>
+              <SelectItem key="stop">{t("plugins:workflows.command_stop")}</SelectItem>
+              <SelectItem key="restart">{t("plugins:workflows.command_restart")}</SelectItem>
+            </Select>
+            <Select
+              label={t("plugins:workflows.pluginId")}
+              selectedKeys={targetPluginKeys}
+              onSelectionChange={targetPluginChange}
+              size="sm"
+              className="flex-1"
+            >
+              {installedPlugins.map((p) => (
+                <SelectItem key={p.manifest.id}>{p.manifest.name}</SelectItem>
+              ))}
+            </Select>
+          </div>
+        )}
+
         {actionType === "invoke_plugin_tool" && (
           <>
             <Select
@@ -700,8 +1040,38 @@ function WorkflowEditor({
           />
         )}
 
+        {actionType === "webhook" && (
+          <>
+            <Input
+              label={t("plugins:workflows.webhookUrl")}
+              placeholder="https://hooks.slack.com/services/..."
+              value={webhookUrl}
+              onValueChange={setWebhookUrl}
+              size="sm"
+            />
+            <Select
+              label={t("plugins:workflows.webhookMode")}
+              selectedKeys={webhookModeKeys}
+              onSelectionChange={webhookModeChange}
+              size="sm"
+            >
+              <SelectItem key="structured">{t("plugins:workflows.webhookStructured")}</SelectItem>
+              <SelectItem key="binary">{t("plugins:workflows.webhookBinary")}</SelectItem>
+            </Select>
+            <Textarea
+              label={t("plugins:workflows.webhookHeaders")}
+              placeholder={HEADERS_PLACEHOLDER}
+              value={webhookHeaders}
+              onValueChange={setWebhookHeaders}
+              size="sm"
+              minRows={2}
+              classNames={textareaClassNames}
+            />
+          </>
+        )}
+
         {/* Args template for plugin tool and extension actions */}
-        {actionType !== "emit_frontend" && (
+        {(actionType === "invoke_plugin_tool" || actionType === "call_extension") && (
           <>
             <Textarea
               label={t("plugins:workflows.argsTemplate")}
@@ -729,6 +1099,8 @@ function WorkflowEditor({
         )}
       </Surface>
 
+      {saveError && <p className="text-xs text-danger">{saveError}</p>}
+
       {/* Save / Cancel */}
       <div className="flex justify-end gap-3">
         <Button variant="flat" onPress={onCancel}>
@@ -0,0 +1,44 @@
+import { useEffect, useRef } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { eventSubscribe, eventUnsubscribe } from "../lib/tauri";
+import type { EventLogEntry } from "../types/workflows";
+
+/**
+ * Subscribe to event bus events matching a type pattern (glob, e.g.
+ * "com.github.*") for as long as the component is mounted. `filter` is a
+ * predicate over event data, e.g. `data.ref == "refs/heads/main"`.
+ *
+ * The handler may change between renders without resubscribing.
+ */
+export function useEventBus(
+  typePattern: string,
+  onEvent: (event: EventLogEntry) => void,
+  options?: { sourcePattern?: string; filter?: string },
+) {
+  const handler = useRef(onEvent);
+  handler.current = onEvent;
+
+  const sourcePattern = options?.sourcePattern;
+  const filter = options?.filter;
+
+  useEffect(() => {
+    let cancelled = false;
+    let cleanup: (() => void) | undefined;
+
+    eventSubscribe({ typePattern, sourcePattern, filter })
+      .then(async ({ subscription_id, channel }) => {
+        const unlisten = await listen<EventLogEntry>(channel, (e) => handler.current(e.payload));
+        cleanup = () => {
+          unlisten();
+          eventUnsubscribe(subscription_id).catch(() => {});
+        };
+        if (cancelled) cleanup();
+      })
+      .catch((e) => console.error("Event bus subscription failed:", e));
+
+    return () => {
+      cancelled = true;
+      cleanup?.();
+    };
+  }, [typePattern, sourcePattern, filter]);
+}
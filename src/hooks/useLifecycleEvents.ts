@@ -1,7 +1,183 @@
 import { useEffect } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { useAppStore } from "../stores/appStore";
-import type { LifecycleEvent } from "../types/lifecycle";
+import { lifecycleReplay } from "../lib/tauri";
+import type { BufferedLifecycleEvent, ImageProgress, LifecycleEvent } from "../types/lifecycle";
+
+/** Last event seen, kept across webview reloads so missed events can be replayed. */
+const SEQ_KEY = "nexus:lifecycle-seq";
+
+function percent(progress?: ImageProgress): number | null {
+  if (!progress || progress.total === 0) return null;
+  return Math.min(100, Math.round((progress.current / progress.total) * 100));
+}
+
+function lastSeen(): number | null {
+  const seq = sessionStorage.getItem(SEQ_KEY);
+  return seq === null ? null : Number(seq);
+}
+
+function markSeen(seq: number) {
+  sessionStorage.setItem(SEQ_KEY, String(seq));
+}
+
+function handleEvent(e: LifecycleEvent) {
+  const {
+    setBusy,
+    setBusyProgress,
+    setExtensionBusy,
+    updatePlugin,
+    removePlugin,
+    updateExtension,
+    removeExtension,
+    setInstallStatus,
+    addNotification,
+    bumpPermissionsVersion,
+  } = useAppStore.getState();
+
+  switch (e.kind) {
+    // -- Plugin lifecycle --
+    case "plugin:starting":
+      setBusy(e.plugin_id, "starting");
+      break;
+    case "plugin:started":
+      setBusy(e.plugin.manifest.id, null);
+      updatePlugin(e.plugin);
+      break;
+    case "plugin:stopping":
+      setBusy(e.plugin_id, "stopping");
+      break;
+    case "plugin:stopped":
+      setBusy(e.plugin.manifest.id, null);
+      updatePlugin(e.plugin);
+      break;
+    case "plugin:removing":
+      setBusy(e.plugin_id, "removing");
+      break;
+    case "plugin:removed":
+      setBusy(e.plugin_id, null);
+      removePlugin(e.plugin_id);
+      break;
+    case "plugin:installing":
+      setInstallStatus(e.message, percent(e.progress));
+      break;
+    case "plugin:installed":
+      setInstallStatus(null);
+      updatePlugin(e.plugin);
+      break;
+    case "plugin:error":
+      setBusy(e.plugin_id, null);
+      setInstallStatus(null);
+      addNotification(`${e.action} failed: ${e.message}`, "error");
+      break;
+
+    // -- Plugin update stages --
+    case "plugin:update_stage":
+      switch (e.stage) {
+        case "stopping":
+          setBusy(e.plugin_id, "stopping");
+          break;
+        case "pulling":
+        case "building":
+          setBusy(e.plugin_id, "updating");
+          setBusyProgress(e.plugin_id, percent(e.progress));
+          break;
+        case "starting":
+          setBusy(e.plugin_id, "starting");
+          break;
+      }
+      break;
+    case "plugin:update_rolled_back":
+      setBusy(e.plugin.manifest.id, null);
+      updatePlugin(e.plugin);
+      addNotification(
+        `Updating ${e.plugin.manifest.name} to ${e.failed_version} failed, restored ${e.plugin.manifest.version}: ${e.error}`,
+        "error"
+      );
+      break;
+
+    // -- Plugin dev rebuild --
+    case "plugin:rebuild":
+      switch (e.status) {
+        case "started":
+        case "building":
+        case "restarting":
+          setBusy(e.plugin_id, "rebuilding");
+          break;
+        case "complete":
+          setBusy(e.plugin_id, null);
+          addNotification("Dev rebuild complete", "success");
+          break;
+        case "error":
+          setBusy(e.plugin_id, null);
+          addNotification(`Dev rebuild failed: ${e.message}`, "error");
+          break;
+      }
+      break;
+
+    // -- Permission lifecycle --
+    case "permission:expired":
+      bumpPermissionsVersion();
+      addNotification(
+        `Permission expired for ${e.plugin_id}: ${e.permissions.join(", ")}`,
+        "info"
+      );
+      break;
+
+    // -- Audit log --
+    case "audit:pruned":
+      addNotification(
+        `Audit log reached its ${Math.round(e.max_bytes / (1024 * 1024))} MB limit: ${e.trimmed} oldest entries were pruned early`,
+        "error"
+      );
+      break;
+
+    // -- Storage --
+    case "store:damaged":
+      addNotification(
+        e.recovered
+          ? `${e.file} was damaged and has been restored from its backup: ${e.message}`
+          : `${e.file} couldn't be read and was reset: ${e.message}`,
+        "error"
+      );
+      break;
+
+    // -- Extension lifecycle --
+    case "extension:enabling":
+      setExtensionBusy(e.ext_id, "enabling");
+      break;
+    case "extension:enabled":
+      setExtensionBusy(e.extension.id, null);
+      updateExtension(e.extension);
+      break;
+    case "extension:disabling":
+      setExtensionBusy(e.ext_id, "disabling");
+      break;
+    case "extension:disabled":
+      setExtensionBusy(e.extension.id, null);
+      updateExtension(e.extension);
+      break;
+    case "extension:removing":
+      setExtensionBusy(e.ext_id, "removing");
+      break;
+    case "extension:removed":
+      setExtensionBusy(e.ext_id, null);
+      removeExtension(e.ext_id);
+      break;
+    case "extension:installing":
+      setExtensionBusy(e.ext_id, "enabling");
+      break;
+    case "extension:installed":
+      setExtensionBusy(e.extension.id, null);
+      updateExtension(e.extension);
+      break;
+    case "extension:error":
+      setExtensionBusy(e.ext_id, null);
+      setInstallStatus(null);
+      addNotification(`Extension ${e.action} failed: ${e.message}`, "error");
+      break;
+  }
+}
 
 /**
  * Single lifecycle event listener. Dispatches all backend state-change events
@@ -10,133 +186,38 @@ import type { LifecycleEvent } from "../types/lifecycle";
  * Uses getState() inside the listener so this hook creates ZERO store
  * subscriptions and never triggers re-renders.
  *
+ * On mount, replays terminal events the backend emitted while the webview
+ * was reloading, so busy states and overlays don't get stuck.
+ *
  * To add a new operation:
  * 1. Add variants to LifecycleEvent (both Rust enum and TS type)
- * 2. Add cases to the switch below
+ * 2. Add cases to the switch in handleEvent()
  * 3. Backend emits via lifecycle_events::emit()
  */
 export function useLifecycleEvents() {
   useEffect(() => {
-    const unlisten = listen<LifecycleEvent>("nexus://lifecycle", (event) => {
-      const {
-        setBusy,
-        setExtensionBusy,
-        updatePlugin,
-        removePlugin,
-        updateExtension,
-        removeExtension,
-        setInstallStatus,
-        addNotification,
-      } = useAppStore.getState();
-
-      const e = event.payload;
-
-      switch (e.kind) {
-        // -- Plugin lifecycle --
-        case "plugin:starting":
-          setBusy(e.plugin_id, "starting");
-          break;
-        case "plugin:started":
-          setBusy(e.plugin.manifest.id, null);
-          updatePlugin(e.plugin);
-          break;
-        case "plugin:stopping":
-          setBusy(e.plugin_id, "stopping");
-          break;
-        case "plugin:stopped":
-          setBusy(e.plugin.manifest.id, null);
-          updatePlugin(e.plugin);
-          break;
-        case "plugin:removing":
-          setBusy(e.plugin_id, "removing");
-          break;
-        case "plugin:removed":
-          setBusy(e.plugin_id, null);
-          removePlugin(e.plugin_id);
-          break;
-        case "plugin:installing":
-          setInstallStatus(e.message);
-          break;
-        case "plugin:installed":
-          setInstallStatus(null);
-          updatePlugin(e.plugin);
-          break;
-        case "plugin:error":
-          setBusy(e.plugin_id, null);
-          setInstallStatus(null);
-          addNotification(`${e.action} failed: ${e.message}`, "error");
-          break;
+    const after = lastSeen();
+    // Seqs the live listener handled while the replay was in flight
+    let handled: Set<number> | null = after === null ? null : new Set();
 
-        // -- Plugin update stages --
-        case "plugin:update_stage":
-          switch (e.stage) {
-            case "stopping":
-              setBusy(e.plugin_id, "stopping");
-              break;
-            case "pulling":
-              setBusy(e.plugin_id, "updating");
-              break;
-            case "starting":
-              setBusy(e.plugin_id, "starting");
-              break;
-          }
-          break;
+    const unlisten = listen<BufferedLifecycleEvent>("nexus://lifecycle", (event) => {
+      handled?.add(event.payload.seq);
+      markSeen(event.payload.seq);
+      handleEvent(event.payload);
+    });
 
-        // -- Plugin dev rebuild --
-        case "plugin:rebuild":
-          switch (e.status) {
-            case "started":
-            case "building":
-            case "restarting":
-              setBusy(e.plugin_id, "rebuilding");
-              break;
-            case "complete":
-              setBusy(e.plugin_id, null);
-              addNotification("Dev rebuild complete", "success");
-              break;
-            case "error":
-              setBusy(e.plugin_id, null);
-              addNotification(`Dev rebuild failed: ${e.message}`, "error");
-              break;
+    if (after !== null) {
+      lifecycleReplay(after)
+        .then((missed) => {
+          for (const e of missed) {
+            if (!handled?.has(e.seq)) handleEvent(e);
           }
-          break;
-
-        // -- Extension lifecycle --
-        case "extension:enabling":
-          setExtensionBusy(e.ext_id, "enabling");
-          break;
-        case "extension:enabled":
-          setExtensionBusy(e.extension.id, null);
-          updateExtension(e.extension);
-          break;
-        case "extension:disabling":
-          setExtensionBusy(e.ext_id, "disabling");
-          break;
-        case "extension:disabled":
-          setExtensionBusy(e.extension.id, null);
-          updateExtension(e.extension);
-          break;
-        case "extension:removing":
-          setExtensionBusy(e.ext_id, "removing");
-          break;
-        case "extension:removed":
-          setExtensionBusy(e.ext_id, null);
-          removeExtension(e.ext_id);
-          break;
-        case "extension:installing":
-          setExtensionBusy(e.ext_id, "enabling");
-          break;
-        case "extension:installed":
-          setExtensionBusy(e.extension.id, null);
-          updateExtension(e.extension);
-          break;
-        case "extension:error":
-          setExtensionBusy(e.ext_id, null);
-          setInstallStatus(null);
-          addNotification(`Extension ${e.action} failed: ${e.message}`, "error");
-          break;
-      }
-    });
+        })
+        .catch(() => {})
+        .finally(() => {
+          handled = null;
+        });
+    }
 
     return () => {
       unlisten.then((fn) => fn());
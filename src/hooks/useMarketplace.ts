@@ -2,26 +2,33 @@ import { useCallback } from "react";
 import { useAppStore } from "../stores/appStore";
 import * as api from "../lib/tauri";
 import i18n from "../i18n";
+import type { MarketplaceQuery, MarketplaceSort } from "../types/plugin";
 
 export function useMarketplace() {
   const marketplacePlugins = useAppStore((s) => s.marketplacePlugins);
-  const searchQuery = useAppStore((s) => s.searchQuery);
+  const total = useAppStore((s) => s.marketplaceTotal);
+  const categories = useAppStore((s) => s.marketplaceCategories);
+  const query = useAppStore((s) => s.marketplaceQuery);
+  const collections = useAppStore((s) => s.marketplaceCollections);
   const isLoading = useAppStore((s) => s.isLoading);
 
   const refresh = useCallback(async () => {
     useAppStore.getState().setLoading(true);
+    const current = useAppStore.getState().marketplaceQuery;
     try {
       // Load from disk cache first (instant, no network).
       // The update scheduler handles network refreshes in the background.
       await api.marketplaceLoad();
-      const results = await api.marketplaceSearch("");
+      const results = await api.marketplaceSearch(current);
       useAppStore.getState().setMarketplace(results);
+      useAppStore.getState().setMarketplaceCollections(await api.marketplaceCollections());
     } catch {
       // No cache yet — fall back to network fetch
       try {
         await api.marketplaceRefresh();
-        const results = await api.marketplaceSearch("");
+        const results = await api.marketplaceSearch(current);
         useAppStore.getState().setMarketplace(results);
+        useAppStore.getState().setMarketplaceCollections(await api.marketplaceCollections());
       } catch (e) {
         useAppStore.getState().addNotification(i18n.t("error.loadMarketplace", { error: e }), "error");
       }
@@ -30,24 +37,47 @@ export function useMarketplace() {
     }
   }, []);
 
-  const search = useCallback(
-    async (query: string) => {
-      useAppStore.getState().setSearchQuery(query);
-      try {
-        const results = await api.marketplaceSearch(query);
-        useAppStore.getState().setMarketplace(results);
-      } catch (e) {
-        useAppStore.getState().addNotification(i18n.t("error.searchFailed", { error: e }), "error");
-      }
+  /** Apply a change to the query and fetch. Changes other than paging go back to the first page. */
+  const browse = useCallback(async (change: Partial<MarketplaceQuery>) => {
+    const next = { ...useAppStore.getState().marketplaceQuery, page: 0, ...change };
+    useAppStore.getState().setMarketplaceQuery(next);
+    try {
+      const results = await api.marketplaceSearch(next);
+      useAppStore.getState().setMarketplace(results);
+    } catch (e) {
+      useAppStore.getState().addNotification(i18n.t("error.searchFailed", { error: e }), "error");
+    }
+  }, []);
+
+  const search = useCallback((text: string) => browse({ text }), [browse]);
+
+  const toggleCategory = useCallback(
+    (category: string) => {
+      const selected = useAppStore.getState().marketplaceQuery.categories;
+      browse({
+        categories: selected.includes(category)
+          ? selected.filter((c) => c !== category)
+          : [...selected, category],
+      });
     },
-    []
+    [browse]
   );
 
+  const setSort = useCallback((sort: MarketplaceSort) => browse({ sort }), [browse]);
+
+  const setPage = useCallback((page: number) => browse({ page }), [browse]);
+
   return {
     plugins: marketplacePlugins,
-    searchQuery,
+    total,
+    categories,
+    query,
+    collections,
     isLoading,
     refresh,
     search,
+    toggleCategory,
+    setSort,
+    setPage,
   };
 }
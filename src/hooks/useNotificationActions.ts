@@ -0,0 +1,22 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { useAppStore } from "../stores/appStore";
+import type { NotificationAction } from "../lib/tauri";
+
+/** Carry out notification buttons that navigate, e.g. "Open logs". */
+export function useNotificationActions() {
+  useEffect(() => {
+    const unlisten = listen<NotificationAction>("nexus://notification-action", (event) => {
+      const action = event.payload;
+      if (action.kind === "open_logs") {
+        const { selectPlugin, setView, setShowLogs } = useAppStore.getState();
+        selectPlugin(action.plugin_id);
+        setView("plugins");
+        setShowLogs(action.plugin_id);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+}
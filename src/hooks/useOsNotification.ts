@@ -6,9 +6,10 @@ const STORAGE_KEY = "nexus:notifications-enabled";
 /** Minimum interval between OS notifications (ms). */
 const THROTTLE_MS = 15_000;
 
-/** Send a native OS notification via our cross-platform Rust command. */
-export async function nativeNotify(title: string, body: string) {
-  await invoke("send_notification", { title, body });
+/** Send a native OS notification via our cross-platform Rust command.
+ *  It's also kept in the notification history under `category`. */
+export async function nativeNotify(title: string, body: string, category?: string) {
+  await invoke("send_notification", { title, body, category: category ?? null });
 }
 
 /** Check whether the user has disabled notifications in app settings. */
@@ -24,7 +25,7 @@ export function setNotificationsEnabled(enabled: boolean) {
 export function useOsNotification() {
   const lastAt = useRef(0);
 
-  function notify(title: string, body: string, queueSize: number) {
+  function notify(title: string, body: string, queueSize: number, category?: string) {
     if (document.hasFocus() || !notificationsEnabled()) return;
 
     const now = Date.now();
@@ -33,7 +34,7 @@ export function useOsNotification() {
 
     if (isFirst || throttleOk) {
       lastAt.current = now;
-      nativeNotify(title, body);
+      nativeNotify(title, body, category);
     }
   }
 
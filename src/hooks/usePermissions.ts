@@ -1,16 +1,24 @@
 import { useCallback, useState } from "react";
 import * as api from "../lib/tauri";
-import type { GrantedPermission, Permission } from "../types/permissions";
+import type { DenyRule, GrantedPermission, Permission, PermissionChange, RateCap } from "../types/permissions";
 import { useAppStore } from "../stores/appStore";
 
 export function usePermissions() {
   const [grants, setGrants] = useState<GrantedPermission[]>([]);
+  const [denies, setDenies] = useState<DenyRule[]>([]);
+  const [history, setHistory] = useState<PermissionChange[]>([]);
 
   const loadGrants = useCallback(
     async (pluginId: string) => {
       try {
-        const result = await api.permissionList(pluginId);
+        const [result, rules, changes] = await Promise.all([
+          api.permissionList(pluginId),
+          api.permissionDenyList(pluginId),
+          api.permissionHistory(pluginId),
+        ]);
         setGrants(result);
+        setDenies(rules);
+        setHistory(changes);
         return result;
       } catch (e) {
         useAppStore.getState().addNotification(`Failed to load permissions: ${e}`, "error");
@@ -21,9 +29,9 @@ export function usePermissions() {
   );
 
   const grant = useCallback(
-    async (pluginId: string, permissions: Permission[]) => {
+    async (pluginId: string, permissions: Permission[], durationSecs?: number) => {
       try {
-        await api.permissionGrant(pluginId, permissions);
+        await api.permissionGrant(pluginId, permissions, durationSecs);
         useAppStore.getState().addNotification("Permissions granted", "success");
         await loadGrants(pluginId);
       } catch (e) {
@@ -72,5 +80,54 @@ export function usePermissions() {
     [loadGrants]
   );
 
-  return { grants, loadGrants, grant, revoke, unrevoke, removePath };
+  const setAlwaysPrompt = useCallback(
+    async (pluginId: string, permission: Permission, alwaysPrompt: boolean) => {
+      try {
+        await api.permissionSetAlwaysPrompt(pluginId, permission, alwaysPrompt);
+        await loadGrants(pluginId);
+      } catch (e) {
+        useAppStore.getState().addNotification(`Failed to update permission: ${e}`, "error");
+      }
+    },
+    [loadGrants]
+  );
+
+  const setRateCap = useCallback(
+    async (pluginId: string, permission: Permission, rateCap: RateCap | null) => {
+      try {
+        await api.permissionSetRateCap(pluginId, permission, rateCap);
+        await loadGrants(pluginId);
+      } catch (e) {
+        useAppStore.getState().addNotification(`Failed to update permission: ${e}`, "error");
+      }
+    },
+    [loadGrants]
+  );
+
+  const removeDeny = useCallback(
+    async (pluginId: string, rule: DenyRule) => {
+      try {
+        await api.permissionDenyRemove(pluginId, rule.permission, rule.scope ?? null);
+        useAppStore.getState().addNotification("Deny rule removed", "info");
+        await loadGrants(pluginId);
+      } catch (e) {
+        useAppStore.getState().addNotification(`Failed to remove deny rule: ${e}`, "error");
+      }
+    },
+    [loadGrants]
+  );
+
+  return {
+    grants,
+    denies,
+    history,
+    loadGrants,
+    grant,
+    revoke,
+    unrevoke,
+    removePath,
+    setAlwaysPrompt,
+    setRateCap,
+    removeDeny,
+  };
 }
@@ -1,7 +1,7 @@
 import { useCallback, useEffect, useRef } from "react";
 import { useAppStore } from "../stores/appStore";
 import type { Permission } from "../types/permissions";
-import type { PluginManifest } from "../types/plugin";
+import type { PluginManifest, PublisherVerification } from "../types/plugin";
 import * as api from "../lib/tauri";
 import i18n from "../i18n";
 
@@ -41,7 +41,7 @@ export function usePluginActions() {
   );
 
   const previewRemote = useCallback(
-    async (manifestUrl: string): Promise<PluginManifest | null> => {
+    async (manifestUrl: string): Promise<(PluginManifest & PublisherVerification) | null> => {
       try {
         return await api.pluginPreviewRemote(manifestUrl);
       } catch (e) {
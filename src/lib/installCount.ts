@@ -0,0 +1,7 @@
+import i18n from "../i18n";
+
+/** Format a registry install count compactly (e.g. "1.2K installs"). */
+export function formatInstalls(count: number): string {
+  const formatted = new Intl.NumberFormat(i18n.language, { notation: "compact" }).format(count);
+  return i18n.t("plugins:card.installs", { count, formatted });
+}
@@ -1,9 +1,20 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { InstalledPlugin, PluginManifest, RegistryEntry, RegistrySource } from "../types/plugin";
-import type { ApprovalDecision, GrantedPermission, Permission } from "../types/permissions";
-import type { McpSettings, McpToolStatus } from "../types/mcp";
-import type { AvailableUpdate } from "../types/updates";
-import type { ClassifiedTool, PluginMetadata } from "../types/mcp_wrap";
+import type { BundleImportReport, ConflictPolicy, InstalledPlugin, MarketplaceCollection, MarketplacePage, MarketplaceQuery, PluginManifest, PluginSecurity, PublisherVerification, RegistryAuth, RegistryBundleExport, RegistrySource, ReleaseChannel } from "../types/plugin";
+import type {
+  ApprovalDecision,
+  DenyRule,
+  GrantedPermission,
+  Permission,
+  PermissionChange,
+  PermissionImportReport,
+  PermissionProfile,
+  PermissionSimulation,
+  RateCap,
+} from "../types/permissions";
+import type { McpClientLimits, McpConfigClient, McpConfigInstallResult, McpSessionInfo, McpSettings, McpToolApprovalView, McpToolStats, McpToolStatus } from "../types/mcp";
+import type { AutoUpdatePolicy, AvailableUpdate, YankedInstall } from "../types/updates";
+import type { BufferedLifecycleEvent } from "../types/lifecycle";
+import type { ClassifiedTool, McpDiscoveryResult, PluginMetadata } from "../types/mcp_wrap";
 
 export async function pluginList(): Promise<InstalledPlugin[]> {
   return invoke("plugin_list");
@@ -11,7 +22,7 @@ export async function pluginList(): Promise<InstalledPlugin[]> {
 
 export async function pluginPreviewRemote(
   manifestUrl: string
-): Promise<PluginManifest> {
+): Promise<PluginManifest & PublisherVerification> {
   return invoke("plugin_preview_remote", { manifestUrl });
 }
 
@@ -92,11 +103,15 @@ export async function pluginSaveSettings(
 }
 
 export async function marketplaceSearch(
-  query: string
-): Promise<RegistryEntry[]> {
+  query: MarketplaceQuery
+): Promise<MarketplacePage> {
   return invoke("marketplace_search", { query });
 }
 
+export async function marketplaceCollections(): Promise<MarketplaceCollection[]> {
+  return invoke("marketplace_collections");
+}
+
 export async function marketplaceRefresh(): Promise<void> {
   return invoke("marketplace_refresh");
 }
@@ -107,9 +122,14 @@ export async function marketplaceLoad(): Promise<void> {
 
 export async function permissionGrant(
   pluginId: string,
-  permissions: Permission[]
+  permissions: Permission[],
+  durationSecs?: number
 ): Promise<void> {
-  return invoke("permission_grant", { pluginId, permissions });
+  return invoke("permission_grant", {
+    pluginId,
+    permissions,
+    durationSecs: durationSecs ?? null,
+  });
 }
 
 export async function permissionRevoke(
@@ -166,6 +186,11 @@ export async function appVersion(): Promise<AppVersionInfo> {
   return invoke("app_version");
 }
 
+/** Terminal lifecycle events emitted after `after`, oldest first. */
+export async function lifecycleReplay(after: number): Promise<BufferedLifecycleEvent[]> {
+  return invoke("lifecycle_replay", { after });
+}
+
 export interface EngineStatus {
   engine_id: string;
   installed: boolean;
@@ -179,6 +204,117 @@ export async function checkEngine(): Promise<EngineStatus> {
   return invoke("check_engine");
 }
 
+export type EngineKind = "docker" | "podman";
+
+export interface EngineConfig {
+  kind: EngineKind;
+  /** Engine endpoint; null uses the engine's default socket. */
+  host: string | null;
+}
+
+export interface EngineSwitchReport {
+  restarted: string[];
+  failed_to_start: string[];
+  missing_images: string[];
+  volumes_not_copied: string[];
+}
+
+export async function engineConfig(): Promise<EngineConfig> {
+  return invoke("engine_config");
+}
+
+export async function switchEngine(kind: EngineKind, host: string | null): Promise<EngineSwitchReport> {
+  return invoke("switch_engine", { kind, host });
+}
+
+export interface DataLocation {
+  current: string;
+  default: string;
+  /** Destination of a move that happens on the next launch. */
+  pending_move: string | null;
+  last_error: string | null;
+}
+
+export async function getDataLocation(): Promise<DataLocation> {
+  return invoke("get_data_location");
+}
+
+export async function moveDataDir(path: string): Promise<void> {
+  return invoke("move_data_dir", { path });
+}
+
+export async function cancelDataDirMove(): Promise<void> {
+  return invoke("cancel_data_dir_move");
+}
+
+export interface ProxyConfig {
+  enabled: boolean;
+  host: string;
+  port: number;
+  /** Hosts, domains, and IP ranges reached without the proxy. */
+  no_proxy: string[];
+}
+
+export async function getProxyConfig(): Promise<ProxyConfig> {
+  return invoke("get_proxy_config");
+}
+
+export async function setProxyConfig(config: ProxyConfig): Promise<void> {
+  return invoke("set_proxy_config", { config });
+}
+
+// Notification history
+
+/** A button on a notification. */
+export type NotificationAction =
+  | { kind: "restart_plugin"; plugin_id: string }
+  | { kind: "open_logs"; plugin_id: string };
+
+export interface StoredNotification {
+  id: string;
+  /** Dot-delimited, like in-app notification categories. */
+  category: string;
+  title: string;
+  body: string;
+  created_at: string;
+  read: boolean;
+  actions: NotificationAction[];
+}
+
+export async function notificationsList(): Promise<StoredNotification[]> {
+  return invoke("notifications_list");
+}
+
+/** Mark `ids` read, or everything when omitted. */
+export async function notificationsMarkRead(ids?: string[]): Promise<void> {
+  return invoke("notifications_mark_read", { ids: ids ?? null });
+}
+
+export async function notificationsClear(): Promise<void> {
+  return invoke("notifications_clear");
+}
+
+export async function notificationAction(action: NotificationAction): Promise<void> {
+  return invoke("notification_action", { action });
+}
+
+export async function getLaunchAtLogin(): Promise<boolean> {
+  return invoke("get_launch_at_login");
+}
+
+export async function setLaunchAtLogin(enabled: boolean): Promise<void> {
+  return invoke("set_launch_at_login", { enabled });
+}
+
+/** Accelerator that shows or hides the window, e.g. "CmdOrCtrl+Alt+N"; null when off. */
+export async function getGlobalShortcut(): Promise<string | null> {
+  return invoke("get_global_shortcut");
+}
+
+export async function setGlobalShortcut(shortcut: string | null): Promise<void> {
+  return invoke("set_global_shortcut", { shortcut });
+}
+
 // Resources
 
 export interface ResourceUsage {
@@ -190,6 +326,43 @@ export async function containerResourceUsage(): Promise<ResourceUsage> {
   return invoke("container_resource_usage");
 }
 
+export interface PluginDiskUsage {
+  plugin_id: string;
+  name: string;
+  image_bytes: number;
+  /** Null when the engine doesn't report volume sizes. */
+  volume_bytes: number | null;
+  storage_bytes: number;
+  /** Share of the audit database, already counted in `audit_db_bytes`. */
+  audit_bytes: number;
+  total_bytes: number;
+}
+
+export interface DiskUsage {
+  plugins: PluginDiskUsage[];
+  registry_cache_bytes: number;
+  audit_db_bytes: number;
+  logs_bytes: number;
+  total_bytes: number;
+}
+
+export async function systemDiskUsage(): Promise<DiskUsage> {
+  return invoke("system_disk_usage");
+}
+
+export interface ResourceSample {
+  timestamp: string;
+  cpu_percent: number;
+  memory_mb: number;
+  net_rx_bps: number;
+  net_tx_bps: number;
+}
+
+/** The last hour of resource samples for a plugin, oldest first. */
+export async function pluginResourceHistory(pluginId: string): Promise<ResourceSample[]> {
+  return invoke("plugin_resource_history", { pluginId });
+}
+
 export interface ResourceQuotas {
   cpu_percent: number | null;
   memory_mb: number | null;
@@ -209,6 +382,60 @@ export async function saveResourceQuotas(
   });
 }
 
+// Backup
+
+export interface BackupReport {
+  path: string;
+  files: number;
+  volumes: number;
+  bytes: number;
+  /** Plugins whose data volume couldn't be exported. */
+  skipped_volumes: string[];
+  /** Whether the store encryption key is included. */
+  store_key: boolean;
+}
+
+export interface BackupInfo {
+  nexus_version: string;
+  created_at: string;
+  files: number;
+  volumes: number;
+  /** Whether it carries the store encryption key, which needs its passphrase. */
+  store_key: boolean;
+}
+
+export interface RestoreReport {
+  /** Nexus version that wrote the backup. */
+  nexus_version: string;
+  created_at: string;
+  files: number;
+  volumes: number;
+  skipped_volumes: string[];
+  /** Plugins whose image couldn't be pulled; they need reinstalling. */
+  missing_images: string[];
+  /** Whether the store encryption key comes back too. */
+  store_key: boolean;
+}
+
+/** Back up to `path`. With a passphrase, the store encryption key is included. */
+export async function nexusBackup(
+  path: string,
+  includeVolumes: boolean,
+  passphrase?: string
+): Promise<BackupReport> {
+  return invoke("nexus_backup", { path, includeVolumes, passphrase: passphrase || null });
+}
+
+/** Check a backup and describe what it holds, before restoring it. */
+export async function nexusBackupInfo(path: string): Promise<BackupInfo> {
+  return invoke("nexus_backup_info", { path });
+}
+
+/** Restore a backup. Nexus has to relaunch afterwards to load it. */
+export async function nexusRestore(path: string, passphrase?: string): Promise<RestoreReport> {
+  return invoke("nexus_restore", { path, passphrase: passphrase || null });
+}
+
 // Registries
 export async function registryList(): Promise<RegistrySource[]> {
   return invoke("registry_list");
@@ -217,9 +444,17 @@ export async function registryList(): Promise<RegistrySource[]> {
 export async function registryAdd(
   name: string,
   kind: string,
-  url: string
+  url: string,
+  auth?: RegistryAuth | null
 ): Promise<RegistrySource> {
-  return invoke("registry_add", { name, kind, url });
+  return invoke("registry_add", { name, kind, url, auth: auth ?? null });
+}
+
+export async function registrySetAuth(
+  id: string,
+  auth: RegistryAuth | null
+): Promise<void> {
+  return invoke("registry_set_auth", { id, auth });
 }
 
 export async function registryRemove(id: string): Promise<void> {
@@ -233,6 +468,34 @@ export async function registryToggle(
   return invoke("registry_toggle", { id, enabled });
 }
 
+/** Set the registry priority order, highest first. Must list every registry. */
+export async function registryReorder(ids: string[]): Promise<void> {
+  return invoke("registry_reorder", { ids });
+}
+
+export async function registryGetConflictPolicy(): Promise<ConflictPolicy> {
+  return invoke("registry_get_conflict_policy");
+}
+
+export async function registrySetConflictPolicy(
+  policy: ConflictPolicy
+): Promise<void> {
+  return invoke("registry_set_conflict_policy", { policy });
+}
+
+/** Package the cached registry entries and their manifests for offline use. */
+export async function registryBundleExport(): Promise<RegistryBundleExport> {
+  return invoke("registry_bundle_export");
+}
+
+/** Import a registry bundle as a local registry named `name`. */
+export async function registryBundleImport(
+  data: string,
+  name: string
+): Promise<BundleImportReport> {
+  return invoke("registry_bundle_import", { data, name });
+}
+
 // Extensions
 
 import type {
@@ -272,7 +535,7 @@ export async function extensionRemove(extId: string): Promise<void> {
 
 export async function extensionPreview(
   manifestUrl: string
-): Promise<ExtensionManifest> {
+): Promise<ExtensionManifest & PublisherVerification> {
   return invoke("extension_preview", { manifestUrl });
 }
 
@@ -290,6 +553,83 @@ export async function permissionRemoveScope(
   return invoke("permission_remove_scope", { pluginId, permission, scope });
 }
 
+export async function permissionSetAlwaysPrompt(
+  pluginId: string,
+  permission: Permission,
+  alwaysPrompt: boolean
+): Promise<void> {
+  return invoke("permission_set_always_prompt", { pluginId, permission, alwaysPrompt });
+}
+
+export async function permissionSetRateCap(
+  pluginId: string,
+  permission: Permission,
+  rateCap: RateCap | null
+): Promise<void> {
+  return invoke("permission_set_rate_cap", { pluginId, permission, rateCap });
+}
+
+export async function permissionHistory(pluginId: string): Promise<PermissionChange[]> {
+  return invoke("permission_history", { pluginId });
+}
+
+/** Dry-run a permission check for one use with an optional path, host, or extension scope. */
+export async function permissionSimulate(
+  pluginId: string,
+  permission: string,
+  scope: string | null,
+): Promise<PermissionSimulation> {
+  return invoke("permission_simulate", { pluginId, permission, scope });
+}
+
+/** Export all plugins' grants, scopes, and deny rules as a JSON string (for download). */
+export async function permissionExport(): Promise<string> {
+  return invoke("permission_export");
+}
+
+export async function permissionImport(data: string): Promise<PermissionImportReport> {
+  return invoke("permission_import", { data });
+}
+
+export async function permissionDenyList(pluginId: string): Promise<DenyRule[]> {
+  return invoke("permission_deny_list", { pluginId });
+}
+
+export async function permissionDenyAdd(
+  pluginId: string,
+  permission: Permission,
+  scope: string | null
+): Promise<void> {
+  return invoke("permission_deny_add", { pluginId, permission, scope });
+}
+
+export async function permissionDenyRemove(
+  pluginId: string,
+  permission: Permission,
+  scope: string | null
+): Promise<void> {
+  return invoke("permission_deny_remove", { pluginId, permission, scope });
+}
+
+export async function permissionProfileList(): Promise<PermissionProfile[]> {
+  return invoke("permission_profile_list");
+}
+
+export async function permissionProfileSave(profile: PermissionProfile): Promise<void> {
+  return invoke("permission_profile_save", { profile });
+}
+
+export async function permissionProfileDelete(name: string): Promise<void> {
+  return invoke("permission_profile_delete", { name });
+}
+
+export async function permissionProfileApply(
+  pluginId: string,
+  name: string
+): Promise<GrantedPermission[]> {
+  return invoke("permission_profile_apply", { pluginId, name });
+}
+
 // MCP Gateway
 
 export async function mcpGetSettings(): Promise<McpSettings> {
@@ -311,6 +651,54 @@ export async function mcpConfigSnippet(): Promise<Record<string, unknown>> {
   return invoke("mcp_config_snippet");
 }
 
+export async function mcpConfigInstall(
+  client: McpConfigClient
+): Promise<McpConfigInstallResult> {
+  return invoke("mcp_config_install", { client });
+}
+
+export async function mcpSetToolTimeout(
+  tool: string,
+  timeoutSecs: number | null
+): Promise<void> {
+  return invoke("mcp_set_tool_timeout", { tool, timeoutSecs });
+}
+
+export async function mcpSetClientLimits(
+  clientId: string | null,
+  limits: McpClientLimits | null
+): Promise<void> {
+  return invoke("mcp_set_client_limits", { clientId, limits });
+}
+
+export async function mcpListToolApprovals(): Promise<McpToolApprovalView[]> {
+  return invoke("mcp_list_tool_approvals");
+}
+
+export async function mcpRevokeToolApproval(
+  clientId: string | null,
+  pluginId: string,
+  tool: string
+): Promise<void> {
+  return invoke("mcp_revoke_tool_approval", { clientId, pluginId, tool });
+}
+
+export async function mcpListSessions(): Promise<McpSessionInfo[]> {
+  return invoke("mcp_list_sessions");
+}
+
+export async function mcpRevokeSession(sessionId: string): Promise<void> {
+  return invoke("mcp_revoke_session", { sessionId });
+}
+
+export async function mcpToolStats(): Promise<McpToolStats[]> {
+  return invoke("mcp_tool_stats");
+}
+
+export async function mcpToolStatsReset(): Promise<void> {
+  return invoke("mcp_tool_stats_reset");
+}
+
 // Updates
 
 export async function checkUpdates(): Promise<AvailableUpdate[]> {
@@ -321,6 +709,10 @@ export async function getCachedUpdates(): Promise<AvailableUpdate[]> {
   return invoke("get_cached_updates");
 }
 
+export async function getYankedInstalls(): Promise<YankedInstall[]> {
+  return invoke("get_yanked_installs");
+}
+
 export async function dismissUpdate(itemId: string, version: string): Promise<void> {
   return invoke("dismiss_update", { itemId, version });
 }
@@ -337,6 +729,18 @@ export async function updatePlugin(
   });
 }
 
+export async function updatePluginForceKey(
+  manifestUrl: string,
+  expectedDigest: string | null,
+  buildContext?: string | null
+): Promise<InstalledPlugin> {
+  return invoke("update_plugin_force_key", {
+    manifestUrl,
+    expectedDigest,
+    buildContext: buildContext ?? null,
+  });
+}
+
 export async function updateExtension(
   manifestUrl: string
 ): Promise<InstalledExtension> {
@@ -361,6 +765,57 @@ export async function setTheme(theme: string): Promise<void> {
   return invoke("set_theme", { theme });
 }
 
+export async function getReadOnlyMode(): Promise<boolean> {
+  return invoke("get_read_only_mode");
+}
+
+/** Make the Host API and MCP gateway reject every state-changing call, whatever is granted. */
+export async function setReadOnlyMode(enabled: boolean): Promise<void> {
+  return invoke("set_read_only_mode", { enabled });
+}
+
+export type CredentialStorage = "keychain" | "file";
+
+export async function getCredentialStorage(): Promise<CredentialStorage> {
+  return invoke("get_credential_storage");
+}
+
+/** Move registry credentials and plugin secrets between the OS keychain and owner-only files. */
+export async function setCredentialStorage(storage: CredentialStorage): Promise<void> {
+  return invoke("set_credential_storage", { storage });
+}
+
+export type ImageScanPolicy = "off" | "warn" | "block";
+
+export async function getImageScanPolicy(): Promise<ImageScanPolicy> {
+  return invoke("get_image_scan_policy");
+}
+
+/** What critical vulnerabilities found by Trivy in a plugin image do at install and update. */
+export async function setImageScanPolicy(policy: ImageScanPolicy): Promise<void> {
+  return invoke("set_image_scan_policy", { policy });
+}
+
+export type RootUserPolicy = "allow" | "warn" | "block" | "force_non_root";
+
+export async function getRootUserPolicy(): Promise<RootUserPolicy> {
+  return invoke("get_root_user_policy");
+}
+
+/** What plugin images that run as root do at install and update. */
+export async function setRootUserPolicy(policy: RootUserPolicy): Promise<void> {
+  return invoke("set_root_user_policy", { policy });
+}
+
+export async function getPrometheusMetrics(): Promise<boolean> {
+  return invoke("get_prometheus_metrics");
+}
+
+/** Serve event bus metrics for Prometheus at http://127.0.0.1:9600/metrics, without authentication. */
+export async function setPrometheusMetrics(enabled: boolean): Promise<void> {
+  return invoke("set_prometheus_metrics", { enabled });
+}
+
 export async function getUpdateCheckInterval(): Promise<number> {
   return invoke("get_update_check_interval");
 }
@@ -369,6 +824,14 @@ export async function setUpdateCheckInterval(minutes: number): Promise<void> {
   return invoke("set_update_check_interval", { minutes });
 }
 
+export async function getAutoUpdatePolicy(): Promise<AutoUpdatePolicy> {
+  return invoke("get_auto_update_policy");
+}
+
+export async function setAutoUpdatePolicy(policy: AutoUpdatePolicy): Promise<void> {
+  return invoke("set_auto_update_policy", { policy });
+}
+
 // Plugin storage
 
 export async function pluginStorageInfo(pluginId: string): Promise<number> {
@@ -386,6 +849,21 @@ export async function pluginDevModeToggle(
   return invoke("plugin_dev_mode_toggle", { pluginId, enabled });
 }
 
+export async function pluginSetChannel(
+  pluginId: string,
+  channel: ReleaseChannel
+): Promise<void> {
+  return invoke("plugin_set_channel", { pluginId, channel });
+}
+
+/** Replace the plugin's declared confinement profiles. Applies on its next start. */
+export async function pluginSetSecurityOverride(
+  pluginId: string,
+  security: PluginSecurity
+): Promise<void> {
+  return invoke("plugin_set_security_override", { pluginId, security });
+}
+
 export async function pluginRebuild(pluginId: string): Promise<void> {
   return invoke("plugin_rebuild", { pluginId });
 }
@@ -416,7 +894,13 @@ export async function setUpdateChannel(channel: string): Promise<void> {
 
 // OAuth
 
-import type { OAuthClientInfo } from "../types/oauth";
+import type {
+  AccessTokenFormat,
+  AuthorizationDetail,
+  DeviceAuthorizationInfo,
+  OAuthClientGrants,
+  OAuthClientInfo,
+} from "../types/oauth";
 
 export async function oauthListClients(): Promise<OAuthClientInfo[]> {
   return invoke("oauth_list_clients");
@@ -426,6 +910,37 @@ export async function oauthRevokeClient(clientId: string): Promise<void> {
   return invoke("oauth_revoke_client", { clientId });
 }
 
+export async function oauthSetTokenFormat(
+  clientId: string,
+  format: AccessTokenFormat
+): Promise<void> {
+  return invoke("oauth_set_token_format", { clientId, format });
+}
+
+export async function oauthRotateJwtKey(): Promise<string> {
+  return invoke("oauth_rotate_jwt_key");
+}
+
+export async function oauthGetClientGrants(clientId: string): Promise<OAuthClientGrants> {
+  return invoke("oauth_get_client_grants", { clientId });
+}
+
+export async function oauthRestrictClientGrants(
+  clientId: string,
+  scopes: string[],
+  authorizationDetails: AuthorizationDetail[]
+): Promise<OAuthClientGrants> {
+  return invoke("oauth_restrict_client_grants", { clientId, scopes, authorizationDetails });
+}
+
+export async function oauthDeviceLookup(userCode: string): Promise<DeviceAuthorizationInfo> {
+  return invoke("oauth_device_lookup", { userCode });
+}
+
+export async function oauthDeviceResolve(userCode: string, approve: boolean): Promise<void> {
+  return invoke("oauth_device_resolve", { userCode, approve });
+}
+
 // API Keys
 
 export interface ApiKey {
@@ -435,6 +950,29 @@ export interface ApiKey {
   prefix: string;
   created_at: string;
   last_used_at: string | null;
+  last_used_ip: string | null;
+  call_count: number;
+  expires_at: string | null;
+  rotated_at: string | null;
+  scope: ApiKeyScope;
+}
+
+/** Tool restrictions for an API key. Empty `namespaces` allows all. */
+export interface ApiKeyScope {
+  namespaces: string[];
+  read_only: boolean;
+}
+
+export interface ApiKeyUsage {
+  id: string;
+  name: string;
+  prefix: string;
+  created_at: string;
+  last_used_at: string | null;
+  last_used_ip: string | null;
+  call_count: number;
+  idle_days: number;
+  calls_per_day: number;
 }
 
 export interface GeneratedApiKey {
@@ -446,8 +984,28 @@ export async function apiKeyList(): Promise<ApiKey[]> {
   return invoke("api_key_list");
 }
 
-export async function apiKeyGenerate(name: string): Promise<GeneratedApiKey> {
-  return invoke("api_key_generate", { name });
+export async function apiKeyUsage(): Promise<ApiKeyUsage[]> {
+  return invoke("api_key_usage");
+}
+
+export async function apiKeyGenerate(
+  name: string,
+  expiresAt: string | null = null,
+  scope: ApiKeyScope | null = null
+): Promise<GeneratedApiKey> {
+  return invoke("api_key_generate", { name, expiresAt, scope });
+}
+
+export async function apiKeyRotate(id: string): Promise<GeneratedApiKey> {
+  return invoke("api_key_rotate", { id });
+}
+
+export async function apiKeySetScope(id: string, scope: ApiKeyScope): Promise<void> {
+  return invoke("api_key_set_scope", { id, scope });
+}
+
+export async function apiKeySetExpiry(id: string, expiresAt: string | null): Promise<void> {
+  return invoke("api_key_set_expiry", { id, expiresAt });
 }
 
 export async function apiKeyRevoke(id: string): Promise<void> {
@@ -464,34 +1022,31 @@ export async function apiKeyRegenerateDefault(): Promise<GeneratedApiKey> {
 
 // Audit
 
-import type { AuditLogRow } from "../types/audit";
+import type {
+  AccessLogPolicy,
+  AuditAlertRule,
+  AuditForwardConfig,
+  AuditForwardStatus,
+  AuditLogRow,
+  AuditQuery,
+  AuditRedactionPolicy,
+  AuditRetention,
+  AuditStats,
+  AuditVerification,
+} from "../types/audit";
 
-export async function auditQuery(params: {
-  action?: string;
-  actor?: string;
-  source_id?: string;
-  severity?: string;
-  subject?: string;
-  result?: string;
-  since?: string;
-  until?: string;
-  limit?: number;
-  offset?: number;
-}): Promise<AuditLogRow[]> {
-  return invoke("audit_query", params);
-}
-
-export async function auditCount(params: {
-  action?: string;
-  actor?: string;
-  source_id?: string;
-  severity?: string;
-  subject?: string;
-  result?: string;
-  since?: string;
-  until?: string;
-}): Promise<number> {
-  return invoke("audit_count", params);
+export async function auditQuery(query: AuditQuery): Promise<AuditLogRow[]> {
+  return invoke("audit_query", { query });
+}
+
+export async function auditCount(query: Omit<AuditQuery, "limit" | "offset">): Promise<number> {
+  return invoke("audit_count", { query });
+}
+
+export async function auditStats(
+  query: Omit<AuditQuery, "limit" | "offset"> = {}
+): Promise<AuditStats> {
+  return invoke("audit_stats", { query });
 }
 
 export async function auditExport(params: {
@@ -501,6 +1056,58 @@ export async function auditExport(params: {
   return invoke("audit_export", params);
 }
 
+export async function auditVerify(): Promise<AuditVerification> {
+  return invoke("audit_verify");
+}
+
+export async function auditForwardGet(): Promise<AuditForwardConfig> {
+  return invoke("audit_forward_get");
+}
+
+export async function auditForwardSet(config: AuditForwardConfig): Promise<void> {
+  return invoke("audit_forward_set", { config });
+}
+
+export async function auditForwardStatus(): Promise<AuditForwardStatus> {
+  return invoke("audit_forward_status");
+}
+
+export async function auditAlertsGet(): Promise<AuditAlertRule[]> {
+  return invoke("audit_alerts_get");
+}
+
+export async function auditAlertsSet(rules: AuditAlertRule[]): Promise<void> {
+  return invoke("audit_alerts_set", { rules });
+}
+
+export async function auditRedactionGet(): Promise<AuditRedactionPolicy> {
+  return invoke("audit_redaction_get");
+}
+
+export async function auditRedactionSet(policy: AuditRedactionPolicy): Promise<void> {
+  return invoke("audit_redaction_set", { policy });
+}
+
+export async function auditAccessLogGet(): Promise<Record<string, AccessLogPolicy>> {
+  return invoke("audit_access_log_get");
+}
+
+/** Pass `null` to stop auditing the plugin's requests. */
+export async function auditAccessLogSet(
+  pluginId: string,
+  policy: AccessLogPolicy | null
+): Promise<void> {
+  return invoke("audit_access_log_set", { pluginId, policy });
+}
+
+export async function auditRetentionGet(): Promise<AuditRetention> {
+  return invoke("audit_retention_get");
+}
+
+export async function auditRetentionSet(policy: AuditRetention): Promise<void> {
+  return invoke("audit_retention_set", { policy });
+}
+
 // Extension Resources
 
 export async function extensionResourceList(
@@ -546,7 +1153,30 @@ export async function extensionResourceDelete(
 
 // Workflows (Event Bus Routing Rules)
 
-import type { EventLogEntry, Filter, RouteAction, RoutingRule } from "../types/workflows";
+import type {
+  CompactionReport,
+  BusMetrics,
+  DeadLetter,
+  EventLogEntry,
+  EventLogStats,
+  EventSchema,
+  ExportConfig,
+  ExportStatus,
+  FrontendSubscription,
+  MqttBridgeConfig,
+  MqttStatus,
+  PublishEvent,
+  Filter,
+  EventTimer,
+  PublishQuota,
+  RedeliveryPolicy,
+  ReplayRange,
+  ReplayReport,
+  ReplayTarget,
+  RetentionPolicy,
+  RouteAction,
+  RoutingRule,
+} from "../types/workflows";
 
 export async function workflowList(): Promise<RoutingRule[]> {
   return invoke("routing_rule_list");
@@ -587,16 +1217,163 @@ export async function eventLogQuery(params?: {
   return invoke("event_log_query", params ?? {});
 }
 
+/** Every event in the causal chain of an event, oldest first. */
+export async function eventLogChain(eventId: string): Promise<EventLogEntry[]> {
+  return invoke("event_log_chain", { eventId });
+}
+
 export async function eventLogCount(): Promise<number> {
   return invoke("event_log_count");
 }
 
+/** Subscribe to event bus events. Matches are emitted as Tauri events on the returned channel. */
+export async function eventSubscribe(params: {
+  typePattern: string;
+  sourcePattern?: string;
+  filter?: string;
+}): Promise<FrontendSubscription> {
+  return invoke("event_subscribe", params);
+}
+
+export async function eventUnsubscribe(subscriptionId: string): Promise<void> {
+  return invoke("event_unsubscribe", { subscriptionId });
+}
+
+/** Publish an event as the user (source `nexus://user`). Returns the event ID. */
+export async function eventPublish(event: PublishEvent): Promise<string> {
+  return invoke("event_publish", { event });
+}
+
+export async function eventExportGet(): Promise<ExportConfig> {
+  return invoke("event_export_get");
+}
+
+/** Save the export settings and restart the exporter. */
+export async function eventExportSet(config: ExportConfig): Promise<void> {
+  return invoke("event_export_set", { config });
+}
+
+export async function eventExportStatus(): Promise<ExportStatus> {
+  return invoke("event_export_status");
+}
+
+export async function mqttBridgeGet(): Promise<MqttBridgeConfig> {
+  return invoke("mqtt_bridge_get");
+}
+
+/** Save the MQTT bridge settings and reconnect. */
+export async function mqttBridgeSet(config: MqttBridgeConfig): Promise<void> {
+  return invoke("mqtt_bridge_set", { config });
+}
+
+export async function mqttBridgeStatus(): Promise<MqttStatus> {
+  return invoke("mqtt_bridge_status");
+}
+
+/** Replay stored events into a subscriber or workflow. */
+export async function eventReplay(
+  target: ReplayTarget,
+  range: ReplayRange = {},
+): Promise<ReplayReport> {
+  return invoke("event_replay", { target, range });
+}
+
+export async function eventSchemaList(): Promise<EventSchema[]> {
+  return invoke("event_schema_list");
+}
+
+export async function eventSchemaDelete(eventType: string): Promise<void> {
+  return invoke("event_schema_delete", { eventType });
+}
+
+export async function deadLetterList(limit?: number): Promise<DeadLetter[]> {
+  return invoke("dead_letter_list", { limit });
+}
+
+export async function deadLetterRetry(ids: number[]): Promise<number> {
+  return invoke("dead_letter_retry", { ids });
+}
+
+/** Discard the given dead letters, or all of them when `ids` is omitted. */
+export async function deadLetterPurge(ids?: number[]): Promise<number> {
+  return invoke("dead_letter_purge", { ids });
+}
+
+export async function eventRedeliveryGet(): Promise<RedeliveryPolicy> {
+  return invoke("event_redelivery_get");
+}
+
+export async function eventRedeliverySet(policy: RedeliveryPolicy): Promise<void> {
+  return invoke("event_redelivery_set", { policy });
+}
+
+export async function timerList(): Promise<EventTimer[]> {
+  return invoke("timer_list");
+}
+
+export async function timerCreate(params: {
+  name: string;
+  schedule: string;
+  data?: unknown;
+}): Promise<string> {
+  return invoke("timer_create", params);
+}
+
+export async function timerUpdate(params: {
+  timerId: string;
+  name?: string;
+  schedule?: string;
+  data?: unknown;
+  enabled?: boolean;
+}): Promise<void> {
+  return invoke("timer_update", params);
+}
+
+export async function timerDelete(timerId: string): Promise<void> {
+  return invoke("timer_delete", { timerId });
+}
+
+/** Publish a timer's event now, outside its schedule. Returns the event ID. */
+export async function timerFire(timerId: string): Promise<string> {
+  return invoke("timer_fire", { timerId });
+}
+
+export async function eventQuotaGet(): Promise<PublishQuota> {
+  return invoke("event_quota_get");
+}
+
+export async function eventQuotaSet(quota: PublishQuota): Promise<void> {
+  return invoke("event_quota_set", { quota });
+}
+
+export async function eventRetentionGet(): Promise<RetentionPolicy> {
+  return invoke("event_retention_get");
+}
+
+export async function eventRetentionSet(policy: RetentionPolicy): Promise<void> {
+  return invoke("event_retention_set", { policy });
+}
+
+/** Apply the retention policy now instead of waiting for the hourly pass. */
+export async function eventLogCompact(): Promise<CompactionReport> {
+  return invoke("event_log_compact");
+}
+
+export async function eventLogStats(): Promise<EventLogStats> {
+  return invoke("event_log_stats");
+}
+
+export async function eventBusMetrics(): Promise<BusMetrics> {
+  return invoke("event_bus_metrics");
+}
+
 // MCP Wrap
 
 export async function mcpDiscoverTools(
-  command: string
-): Promise<ClassifiedTool[]> {
-  return invoke("mcp_discover_tools", { command });
+  command: string,
+  env?: Record<string, string>
+): Promise<McpDiscoveryResult> {
+  return invoke("mcp_discover_tools", { command, env });
 }
 
 export async function mcpSuggestMetadata(
@@ -608,15 +1385,23 @@ export async function mcpSuggestMetadata(
 export async function mcpGenerateAndInstall(
   command: string,
   tools: ClassifiedTool[],
+  includeTools: string[] | null,
   metadata: PluginMetadata,
   approvedPermissions: Permission[],
-  deferredPermissions: Permission[]
+  deferredPermissions: Permission[],
+  env?: Record<string, string>
 ): Promise<InstalledPlugin> {
   return invoke("mcp_generate_and_install", {
     command,
     tools,
+    includeTools,
     metadata,
     approvedPermissions,
     deferredPermissions,
+    env,
   });
 }
+
+export async function mcpRewrap(pluginId: string): Promise<InstalledPlugin> {
+  return invoke("mcp_rewrap", { pluginId });
+}
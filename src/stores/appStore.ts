@@ -11,7 +11,7 @@
 import { create } from "zustand";
 import { persist } from "zustand/middleware";
 import { toast } from "sonner";
-import type { InstalledPlugin, RegistryEntry } from "../types/plugin";
+import type { CategoryFacet, InstalledPlugin, MarketplaceCollection, MarketplacePage, MarketplaceQuery, RegistryEntry } from "../types/plugin";
 import type { ExtensionRegistryEntry, ExtensionStatus } from "../types/extension";
 import type { AvailableUpdate } from "../types/updates";
 
@@ -22,6 +22,8 @@ export type ExtensionAction = "enabling" | "disabling" | "removing" | "updating"
 interface InstallStatus {
   active: boolean;
   message: string;
+  /** 0-100 when the backend reports pull or build progress. */
+  progress: number | null;
 }
 
 export interface NotificationMeta {
@@ -45,9 +47,15 @@ interface AppState {
   installedPlugins: InstalledPlugin[];
   selectedPluginId: string | null;
   busyPlugins: Record<string, PluginAction>;
+  /** 0-100 progress of a busy plugin's current step, when known. */
+  busyProgress: Record<string, number>;
   marketplacePlugins: RegistryEntry[];
+  /** Entries matching the marketplace query across all pages. */
+  marketplaceTotal: number;
+  marketplaceCategories: CategoryFacet[];
+  marketplaceQuery: MarketplaceQuery;
+  marketplaceCollections: MarketplaceCollection[];
   selectedRegistryEntry: RegistryEntry | null;
-  searchQuery: string;
   isLoading: boolean;
   installedExtensions: ExtensionStatus[];
   busyExtensions: Record<string, ExtensionAction>;
@@ -62,6 +70,8 @@ interface AppState {
   warmViewports: Record<string, true>;
   updateChannel: "stable" | "nightly";
   notifications: Notification[];
+  /** Bumped when grants change outside a user action (e.g. expiry) so permission views reload. */
+  permissionsVersion: number;
 
   setView: (view: View) => void;
   setPlugins: (plugins: InstalledPlugin[]) => void;
@@ -70,9 +80,11 @@ interface AppState {
   removePlugin: (pluginId: string) => void;
   selectPlugin: (pluginId: string | null) => void;
   setBusy: (pluginId: string, action: PluginAction | null) => void;
-  setMarketplace: (plugins: RegistryEntry[]) => void;
+  setBusyProgress: (pluginId: string, percent: number | null) => void;
+  setMarketplace: (page: MarketplacePage) => void;
   selectRegistryEntry: (entry: RegistryEntry | null) => void;
-  setSearchQuery: (query: string) => void;
+  setMarketplaceQuery: (query: MarketplaceQuery) => void;
+  setMarketplaceCollections: (collections: MarketplaceCollection[]) => void;
   setLoading: (loading: boolean) => void;
   addNotification: (message: string, type: "info" | "success" | "error") => void;
   setExtensions: (extensions: ExtensionStatus[]) => void;
@@ -82,12 +94,13 @@ interface AppState {
   selectExtensionEntry: (entry: ExtensionRegistryEntry | null) => void;
   setAvailableUpdates: (updates: AvailableUpdate[]) => void;
   setUpdateCheckInterval: (minutes: number) => void;
-  setInstallStatus: (message: string | null) => void;
+  setInstallStatus: (message: string | null, progress?: number | null) => void;
   setShowLogs: (pluginId: string | null) => void;
   setSettingsTab: (tab: string) => void;
   setFocusExtensionId: (id: string | null) => void;
   setUpdateChannel: (channel: "stable" | "nightly") => void;
   setWarmViewports: (ids: string[]) => void;
+  bumpPermissionsVersion: () => void;
   notify: (
     category: string,
     message: string,
@@ -103,9 +116,13 @@ export const useAppStore = create<AppState>()(persist((set) => ({
   installedPlugins: [],
   selectedPluginId: null,
   busyPlugins: {},
+  busyProgress: {},
   marketplacePlugins: [],
+  marketplaceTotal: 0,
+  marketplaceCategories: [],
+  marketplaceQuery: { text: "", categories: [], sort: "relevance", page: 0, page_size: 24 },
+  marketplaceCollections: [],
   selectedRegistryEntry: null,
-  searchQuery: "",
   isLoading: false,
   installedExtensions: [],
   busyExtensions: {},
@@ -113,12 +130,13 @@ export const useAppStore = create<AppState>()(persist((set) => ({
   selectedExtensionEntry: null,
   availableUpdates: [],
   updateCheckInterval: 1440,
-  installStatus: { active: false, message: "" },
+  installStatus: { active: false, message: "", progress: null },
   showLogsPluginId: null,
   settingsTab: "general",
   focusExtensionId: null,
   warmViewports: {},
   updateChannel: "stable",
+  permissionsVersion: 0,
 
   setView: (view) => set({ currentView: view }),
   notifications: [],
@@ -158,14 +176,31 @@ export const useAppStore = create<AppState>()(persist((set) => ({
   setBusy: (pluginId, action) =>
     set((state) => {
       const next = { ...state.busyPlugins };
+      const progress = { ...state.busyProgress };
+      delete progress[pluginId];
       if (action) {
         next[pluginId] = action;
       } else {
         delete next[pluginId];
       }
-      return { busyPlugins: next };
+      return { busyPlugins: next, busyProgress: progress };
+    }),
+  setBusyProgress: (pluginId, percent) =>
+    set((state) => {
+      const next = { ...state.busyProgress };
+      if (percent === null) {
+        delete next[pluginId];
+      } else {
+        next[pluginId] = percent;
+      }
+      return { busyProgress: next };
+    }),
+  setMarketplace: (page) =>
+    set({
+      marketplacePlugins: page.entries,
+      marketplaceTotal: page.total,
+      marketplaceCategories: page.categories,
     }),
-  setMarketplace: (plugins) => set({ marketplacePlugins: plugins }),
   selectRegistryEntry: (entry) => set({ selectedRegistryEntry: entry }),
   setExtensions: (extensions) => set({ installedExtensions: extensions }),
   removeExtension: (extId) =>
@@ -186,18 +221,19 @@ export const useAppStore = create<AppState>()(persist((set) => ({
   selectExtensionEntry: (entry) => set({ selectedExtensionEntry: entry }),
   setAvailableUpdates: (updates) => set({ availableUpdates: updates }),
   setUpdateCheckInterval: (minutes) => set({ updateCheckInterval: minutes }),
-  setSearchQuery: (query) => set({ searchQuery: query }),
+  setMarketplaceQuery: (query) => set({ marketplaceQuery: query }),
+  setMarketplaceCollections: (collections) => set({ marketplaceCollections: collections }),
   setLoading: (loading) => set({ isLoading: loading }),
   addNotification: (message, type) => {
     if (type === "success") toast.success(message);
     else if (type === "error") toast.error(message);
     else toast.info(message);
   },
-  setInstallStatus: (message) =>
+  setInstallStatus: (message, progress = null) =>
     set({
       installStatus: message
-        ? { active: true, message }
-        : { active: false, message: "" },
+        ? { active: true, message, progress }
+        : { active: false, message: "", progress: null },
     }),
   setShowLogs: (pluginId) => set({ showLogsPluginId: pluginId }),
   setSettingsTab: (tab) => set({ settingsTab: tab }),
@@ -208,6 +244,8 @@ export const useAppStore = create<AppState>()(persist((set) => ({
     for (const id of ids) next[id] = true;
     return set({ warmViewports: next });
   },
+  bumpPermissionsVersion: () =>
+    set((state) => ({ permissionsVersion: state.permissionsVersion + 1 })),
 
   notify: (category, message, opts) => {
     const id = crypto.randomUUID();
@@ -9,3 +9,115 @@ export interface AuditLogRow {
   result: string;
   details: Record<string, unknown> | null;
 }
+
+export type AuditChainIssue =
+  | { kind: "modified"; id: number }
+  | { kind: "missing"; before_id: number }
+  | { kind: "truncated"; after_id: number | null };
+
+/** Result of checking the audit log's hash chain. */
+export interface AuditVerification {
+  ok: boolean;
+  verified: number;
+  /** Entries from before chaining was introduced. */
+  unchained: number;
+  issue: AuditChainIssue | null;
+}
+
+export type AuditForwardSink =
+  | { kind: "syslog"; address: string; protocol: "udp" | "tcp" }
+  | { kind: "http"; url: string; bearer_token?: string };
+
+/** Forwarding of audit entries to a syslog server or HTTPS collector. */
+export interface AuditForwardConfig {
+  enabled: boolean;
+  sink: AuditForwardSink;
+  max_pending: number;
+}
+
+export interface AuditForwardStatus {
+  running: boolean;
+  forwarded: number;
+  pending: number;
+  dropped: number;
+  last_error: string | null;
+}
+
+/** Stored in settings as `audit_retention`. Zero means no limit. */
+export interface AuditRetention {
+  max_age_days: number;
+  max_bytes: number;
+}
+
+/** Filters for the audit log. `action`, `actor`, `source_id`, and `subject` accept `*` globs. */
+export interface AuditQuery {
+  action?: string;
+  actor?: string;
+  source_id?: string;
+  severity?: string;
+  subject?: string;
+  result?: string;
+  since?: string;
+  until?: string;
+  limit?: number;
+  offset?: number;
+}
+
+export interface AuditBucket {
+  key: string;
+  count: number;
+}
+
+export interface AuditStats {
+  total: number;
+  oldest: string | null;
+  newest: string | null;
+  by_severity: AuditBucket[];
+  by_result: AuditBucket[];
+  top_actions: AuditBucket[];
+  top_actors: AuditBucket[];
+  /** Entries per UTC day (`YYYY-MM-DD`), oldest first. */
+  per_day: AuditBucket[];
+}
+
+/** Entries that count toward an alert rule. Text fields accept `*` globs. */
+export interface AuditAlertFilter {
+  action?: string;
+  actor?: string;
+  source_id?: string;
+  subject?: string;
+  /** Entries at this severity or above. */
+  min_severity?: "info" | "warn" | "critical";
+  result?: "success" | "failure";
+}
+
+/** Stored in settings as `audit_alerts`. Fires an OS notification and a `nexus.audit.alert` event. */
+export interface AuditAlertRule {
+  name: string;
+  enabled: boolean;
+  filter: AuditAlertFilter;
+  /** Matching entries needed to fire. */
+  threshold: number;
+  /** Window the threshold is counted over; ignored when the threshold is 1. */
+  window_secs: number;
+  group_by?: "actor" | "source_id" | "subject";
+}
+
+/**
+ * Stored in settings as `audit_redaction`. A field name masks the value under
+ * that key at any depth; `name.*` masks each child value but keeps the keys.
+ */
+export interface AuditRedactionPolicy {
+  /** Masked in every entry. */
+  fields: string[];
+  /** Extra fields masked in entries whose action matches the key (a `*` glob). */
+  actions: Record<string, string[]>;
+}
+
+/** Verbose auditing of one plugin's Host API requests, as `host_api.request` entries. */
+export interface AccessLogPolicy {
+  /** Fraction of requests audited, from 0 to 1. */
+  sample_rate: number;
+  /** ISO timestamp after which logging stops. */
+  expires_at?: string;
+}
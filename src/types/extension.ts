@@ -1,3 +1,5 @@
+import type { YankedVersion } from "./plugin";
+
 export interface ExtensionOperation {
   name: string;
   description: string;
@@ -100,4 +102,14 @@ export interface ExtensionRegistryEntry {
   categories: string[];
   status?: string;
   source?: string;
+  release_notes?: string;
+  changelog_url?: string;
+  yanked?: YankedVersion[];
+  /** Installs reported by the registry, if it publishes them. */
+  install_count?: number;
+  /** Publisher ID the entry claims. */
+  publisher?: string;
+  /** Whether the registry verified the publisher's domain or GitHub account. */
+  verified?: boolean;
+  verified_identity?: string;
 }
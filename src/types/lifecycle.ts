@@ -1,6 +1,13 @@
 import type { InstalledPlugin } from "./plugin";
 import type { ExtensionStatus } from "./extension";
 
+/** Image pull (bytes) or build (Dockerfile steps) progress. */
+export interface ImageProgress {
+  current: number;
+  total: number;
+  unit: "bytes" | "steps";
+}
+
 // Plugin lifecycle
 interface PluginStarting {
   kind: "plugin:starting";
@@ -35,6 +42,7 @@ interface PluginRemoved {
 interface PluginInstalling {
   kind: "plugin:installing";
   message: string;
+  progress?: ImageProgress;
 }
 
 interface PluginInstalled {
@@ -53,6 +61,14 @@ interface PluginUpdateStage {
   kind: "plugin:update_stage";
   plugin_id: string;
   stage: string;
+  progress?: ImageProgress;
+}
+
+interface PluginUpdateRolledBack {
+  kind: "plugin:update_rolled_back";
+  plugin: InstalledPlugin;
+  failed_version: string;
+  error: string;
 }
 
 interface PluginRebuild {
@@ -62,6 +78,28 @@ interface PluginRebuild {
   message: string;
 }
 
+// Permission lifecycle
+interface PermissionExpired {
+  kind: "permission:expired";
+  plugin_id: string;
+  permissions: string[];
+}
+
+// Audit log
+interface AuditPruned {
+  kind: "audit:pruned";
+  trimmed: number;
+  max_bytes: number;
+}
+
+// Storage
+interface StoreDamaged {
+  kind: "store:damaged";
+  file: string;
+  message: string;
+  recovered: boolean;
+}
+
 // Extension lifecycle
 interface ExtensionEnabling {
   kind: "extension:enabling";
@@ -121,7 +159,11 @@ export type LifecycleEvent =
   | PluginInstalled
   | PluginError
   | PluginUpdateStage
+  | PluginUpdateRolledBack
   | PluginRebuild
+  | PermissionExpired
+  | AuditPruned
+  | StoreDamaged
   | ExtensionEnabling
   | ExtensionEnabled
   | ExtensionDisabling
@@ -131,3 +173,6 @@ export type LifecycleEvent =
   | ExtensionInstalling
   | ExtensionInstalled
   | ExtensionError;
+
+/** Payload on the lifecycle channel: the event plus its position in the stream. */
+export type BufferedLifecycleEvent = LifecycleEvent & { seq: number };
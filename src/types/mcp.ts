@@ -12,11 +12,56 @@ export interface McpConfig {
 export interface McpPluginSettings {
   enabled: boolean;
   disabled_tools: string[];
+  tool_timeouts: Record<string, number>;
+}
+
+export interface McpClientLimits {
+  calls_per_minute: number | null;
+  max_concurrent_calls: number | null;
+  daily_mutating_quota: number | null;
+}
+
+export interface McpToolApproval {
+  client_id: string;
+  client_name: string;
+  plugin_id: string;
+  tool: string;
+  approved_at: string;
+}
+
+export type McpConfigClient = "claude_desktop" | "claude_code" | "cursor" | "vscode";
+
+export interface McpConfigInstallResult {
+  path: string;
+  backup_path: string | null;
+}
+
+export interface McpSessionInfo {
+  session_id: string;
+  client_id: string;
+  client_name: string;
+  auth_method: string;
+  transport: "streamable_http" | "sse";
+  connected_at: string;
+  tool_calls: number;
 }
 
 export interface McpSettings {
   enabled: boolean;
   plugins: Record<string, McpPluginSettings>;
+  default_client_limits: McpClientLimits;
+  client_limits: Record<string, McpClientLimits>;
+  client_approvals: McpToolApproval[];
+}
+
+/** A permanent approval; `client_id: null` means it applies to every client. */
+export interface McpToolApprovalView {
+  client_id: string | null;
+  client_name: string | null;
+  plugin_id: string;
+  tool: string;
+  name: string;
+  approved_at: string | null;
 }
 
 export interface McpToolStatus {
@@ -31,4 +76,17 @@ export interface McpToolStatus {
   tool_enabled: boolean;
   required_permissions: string[];
   permissions_granted: boolean;
+  timeout_secs: number;
+}
+
+export interface McpToolStats {
+  name: string;
+  calls: number;
+  successes: number;
+  failures: number;
+  success_rate: number | null;
+  avg_latency_ms: number | null;
+  max_latency_ms: number;
+  last_used_at: string | null;
+  last_failure_at: string | null;
 }
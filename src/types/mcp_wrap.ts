@@ -13,3 +13,13 @@ export interface PluginMetadata {
   description: string;
   author: string;
 }
+
+export interface McpEnvVar {
+  name: string;
+  secret: boolean;
+}
+
+export interface McpDiscoveryResult {
+  tools: ClassifiedTool[];
+  required_env: McpEnvVar[];
+}
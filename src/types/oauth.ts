@@ -3,4 +3,32 @@ export interface OAuthClientInfo {
   client_name: string;
   registered_at: string;
   approved: boolean;
+  plugin_id?: string;
+  access_token_format: AccessTokenFormat;
+}
+
+export type AccessTokenFormat = "opaque" | "jwt";
+
+export interface DeviceAuthorizationInfo {
+  user_code: string;
+  client_id: string;
+  client_name: string;
+  scopes: string[];
+  expires_in: number;
+}
+
+/** RFC 9396 authorization detail carried on a token. */
+export interface AuthorizationDetail {
+  type: string;
+  actions: string[];
+  locations?: string[];
+  identifier?: string;
+}
+
+export interface OAuthClientGrants {
+  client_id: string;
+  client_name: string;
+  plugin_id?: string;
+  scopes: string[];
+  authorization_details: AuthorizationDetail[];
 }
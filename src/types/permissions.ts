@@ -6,12 +6,13 @@ export type BuiltinPermission =
   | "filesystem:read"
   | "filesystem:write"
   | "process:list"
+  | "process:exec"
   | "container:read"
   | "container:manage"
   | "network:local"
   | "network:internet";
 
-/** A permission string — either a built-in scope or an extension scope (ext:*). */
+/** A permission string — a built-in scope, an extension scope (ext:*), or a host-scoped network permission (network:{host-pattern}). */
 export type Permission = BuiltinPermission | (string & {});
 
 export type PermissionState = "active" | "revoked" | "deferred";
@@ -26,6 +27,100 @@ export interface GrantedPermission {
   state: PermissionState;
   /** Legacy timestamp preserved for revoked state. `state` is the source of truth. */
   revoked_at: string | null;
+  /** Time-limited grants lapse to revoked at this instant. Absent for permanent grants. */
+  expires_at?: string | null;
+  /** Prompt on every use, even for already-approved scopes. */
+  always_prompt?: boolean;
+  /** Usage cap. Going over it prompts the user again. */
+  rate_cap?: RateCap | null;
+}
+
+/** At most `max_calls` uses in any `per_secs`-second window. */
+export interface RateCap {
+  max_calls: number;
+  per_secs: number;
+}
+
+/** "Never allow" rule. Without a scope the whole permission is denied. */
+export interface DenyRule {
+  permission: Permission;
+  /** Denied scope value — a directory (and everything below) or glob for filesystem permissions. */
+  scope?: string | null;
+  created_at: string;
+}
+
+export type PermissionChangeAction =
+  | "grant"
+  | "revoke"
+  | "restore"
+  | "defer"
+  | "activate"
+  | "expire"
+  | "remove"
+  | "scope_add"
+  | "scope_remove"
+  | "deny"
+  | "undeny"
+  | "always_prompt"
+  | "rate_cap"
+  | "profile";
+
+/** One entry in a plugin's permission history. */
+export interface PermissionChange {
+  at: string;
+  /** "system" for changes Nexus made on its own, such as an expiring grant. */
+  actor: "user" | "system";
+  action: PermissionChangeAction;
+  permission: Permission;
+  scope?: string | null;
+  /** Grant state before the change. Absent when there was no grant. */
+  from?: PermissionState | null;
+  /** Grant state after the change. Absent when the grant was removed. */
+  to?: PermissionState | null;
+  /** New expiry, profile name, "on"/"off" for always_prompt, or the rate cap ("10/60s"). */
+  detail?: string | null;
+}
+
+export type SimulatedOutcome = "allowed" | "denied" | "needs_approval";
+
+export type SimulationReason =
+  | "deny_rule"
+  | "no_grant"
+  | "revoked"
+  | "expired"
+  | "deferred"
+  | "always_prompt"
+  | "unrestricted"
+  | "scope_approved"
+  | "scope_not_approved"
+  | "outside_host_pattern";
+
+/** Dry-run result of a permission check. Rate caps are not evaluated. */
+export interface PermissionSimulation {
+  outcome: SimulatedOutcome;
+  reason: SimulationReason;
+  /** The grant that decided the outcome — possibly a host-pattern grant for network:local/internet. */
+  grant: GrantedPermission | null;
+  deny_rule: DenyRule | null;
+}
+
+/** Result of importing a permission file. */
+export interface PermissionImportReport {
+  /** Plugins whose grants and deny rules were replaced. */
+  imported: string[];
+  /** Plugins in the file that aren't installed. */
+  skipped_plugins: string[];
+  /** Entries dropped during validation, prefixed with the plugin ID. */
+  dropped: string[];
+}
+
+/** A named set of permissions applied in one step. Requested permissions outside it are deferred. */
+export interface PermissionProfile {
+  name: string;
+  description: string;
+  permissions: Permission[];
+  /** Built-in profiles ("trusted", "sandboxed") can be edited but not deleted. */
+  builtin: boolean;
 }
 
 export type ApprovalDecision = "approve" | "approve_once" | "deny";
@@ -37,6 +132,14 @@ export interface RuntimeApprovalRequest {
   category: string;
   permission: string;
   context: Record<string, string>;
+  /** Present when several requests for the same plugin and permission were coalesced. Includes this request. */
+  batch?: RuntimeApprovalBatchItem[];
+}
+
+/** One request in a coalesced approval dialog, answered by its own `id`. */
+export interface RuntimeApprovalBatchItem {
+  id: string;
+  context: Record<string, string>;
 }
 
 export interface PermissionMeta {
@@ -71,6 +174,14 @@ export function getPermissionInfo(perm: string): PermissionMeta {
       risk: "high",
     };
   }
+  // Host-scoped network egress: network:{host-pattern}
+  if (perm.startsWith("network:")) {
+    const host = perm.slice(8);
+    return {
+      description: i18n.t("permissions:meta.networkHost", { host, defaultValue: `HTTP requests to ${host}` }),
+      risk: "medium",
+    };
+  }
   // Per-plugin MCP access: mcp:{target_plugin_id}
   if (perm.startsWith("mcp:")) {
     const target = perm.slice(4);
@@ -135,6 +246,7 @@ const PERMISSION_RISK: Record<string, "low" | "medium" | "high"> = {
   "filesystem:read": "medium",
   "filesystem:write": "high",
   "process:list": "medium",
+  "process:exec": "high",
   "container:read": "medium",
   "container:manage": "high",
   "network:local": "medium",
@@ -1,4 +1,5 @@
 import type { McpConfig } from "./mcp";
+import type { ExtensionRegistryEntry } from "./extension";
 
 export type PluginStatus = "installing" | "running" | "stopped" | "error";
 
@@ -41,6 +42,22 @@ export interface PluginManifest {
   mcp?: McpConfig;
   extensions?: Record<string, string[] | Record<string, { scopes?: string[] }>>;
   mcp_access?: string[];
+  security?: PluginSecurity;
+}
+
+/** Confinement for a plugin's container. Unset profiles keep the engine defaults. */
+export interface PluginSecurity {
+  /** Seccomp profile in the engine's JSON format, or "unconfined". */
+  seccomp_profile?: Record<string, unknown> | "unconfined";
+  /** Name of an AppArmor profile loaded on the host, or "unconfined". */
+  apparmor_profile?: string;
+  /**
+   * Why the plugin writes outside /data and /tmp, which keeps the root
+   * filesystem writable. Overrides may give `false` to keep it read-only.
+   */
+  writable_rootfs?: string | false;
+  /** `user[:group]` to run as in place of the image's user. */
+  user?: string;
 }
 
 export interface InstalledPlugin {
@@ -52,8 +69,14 @@ export interface InstalledPlugin {
   installed_at: string;
   dev_mode: boolean;
   local_manifest_path: string | null;
+  /** Channel updates are taken from. */
+  channel: ReleaseChannel;
+  /** Profiles used in place of the manifest's `security`. */
+  security_override: PluginSecurity;
 }
 
+export type ReleaseChannel = "stable" | "beta";
+
 export interface RegistryEntry {
   id: string;
   name: string;
@@ -65,6 +88,7 @@ export interface RegistryEntry {
   license?: string;
   homepage?: string;
   icon?: string;
+  screenshots?: Screenshot[];
   image: string;
   image_digest?: string;
   manifest_url: string;
@@ -72,9 +96,103 @@ export interface RegistryEntry {
   status?: string;
   source?: string;
   build_context?: string;
+  channel: ReleaseChannel;
+  release_notes?: string;
+  changelog_url?: string;
+  /** Base64 Ed25519 key the manifest signature is checked against. */
+  author_public_key?: string;
+  manifest_signature?: string;
+  /** Withdrawn versions of this plugin, which may include the listed one. */
+  yanked?: YankedVersion[];
+  /** Installs reported by the registry, if it publishes them. */
+  install_count?: number;
+  /** Publisher ID the entry claims. */
+  publisher?: string;
+  /** Whether the registry verified the publisher's domain or GitHub account. */
+  verified?: boolean;
+  verified_identity?: string;
+  /** Other registries listing the same plugin ID, highest priority first. */
+  conflicts?: string[];
+}
+
+export interface Screenshot {
+  url: string;
+  caption?: string;
+  width?: number;
+  height?: number;
+}
+
+export type MarketplaceSort = "relevance" | "recently_updated" | "name" | "popularity";
+
+export interface MarketplaceQuery {
+  text: string;
+  /** Entries in any of these categories; empty for all. */
+  categories: string[];
+  sort: MarketplaceSort;
+  /** Zero-based. */
+  page: number;
+  page_size: number;
+}
+
+export interface CategoryFacet {
+  name: string;
+  count: number;
+}
+
+export interface MarketplacePage {
+  entries: RegistryEntry[];
+  /** Entries matching the query across all pages. */
+  total: number;
+  page: number;
+  page_size: number;
+  /** Categories of entries matching the text, ignoring the category filter. */
+  categories: CategoryFacet[];
 }
 
-export type RegistryKind = "remote" | "local";
+/** What the registry listing a manifest verified about its publisher. */
+export interface PublisherVerification {
+  verified: boolean;
+  verified_identity?: string;
+}
+
+/** A registry's curated group of packages, with the packages looked up. */
+export interface MarketplaceCollection {
+  id: string;
+  name: string;
+  description: string;
+  /** Registry the collection comes from. */
+  source: string;
+  plugins: RegistryEntry[];
+  extensions: ExtensionRegistryEntry[];
+}
+
+/** A version its publisher has withdrawn from a registry. */
+export interface YankedVersion {
+  version: string;
+  reason?: string;
+}
+
+export type RegistryKind = "remote" | "local" | "oci";
+
+export interface RegistryBundleExport {
+  /** The bundle file, as JSON. */
+  data: string;
+  plugins: number;
+  extensions: number;
+  /** Manifest URLs that couldn't be fetched; their entries are left out. */
+  failed: string[];
+}
+
+export interface BundleImportReport {
+  registry_id: string;
+  plugins: number;
+  extensions: number;
+  /** IDs of entries left out because the bundle has no manifest for them. */
+  skipped: string[];
+}
+
+/** What the marketplace shows when several registries list the same plugin ID. */
+export type ConflictPolicy = "highest_priority" | "prompt";
 
 export interface RegistrySource {
   id: string;
@@ -82,4 +200,11 @@ export interface RegistrySource {
   kind: RegistryKind;
   url: string;
   enabled: boolean;
+  /** Kind of credentials configured, if the registry is private. */
+  auth?: RegistryAuth["kind"] | null;
 }
+
+/** Credentials for a private registry. Write-only: never returned. */
+export type RegistryAuth =
+  | { kind: "bearer"; token: string }
+  | { kind: "basic"; username: string; password: string };
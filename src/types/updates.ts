@@ -1,3 +1,5 @@
+import type { ReleaseChannel } from "./plugin";
+
 export type UpdateSecurity =
   | "verified"
   | "key_match"
@@ -21,4 +23,26 @@ export interface AvailableUpdate {
   new_image_digest: string | null;
   author_public_key: string | null;
   build_context: string | null;
+  channel: ReleaseChannel;
+  /** Registry notes for the new version, or the fetched changelog. */
+  release_notes: string | null;
+  changelog_url: string | null;
+}
+
+/** An installed plugin or extension running a version its registry withdrew. */
+export interface YankedInstall {
+  item_id: string;
+  item_type: UpdateItemType;
+  item_name: string;
+  version: string;
+  reason: string | null;
+  registry_source: string;
+}
+
+/** Which plugins update automatically when a check finds a new version. */
+export interface AutoUpdatePolicy {
+  /** Default for plugins without their own setting. */
+  enabled: boolean;
+  /** Per-plugin settings, overriding the default. */
+  plugins: Record<string, boolean>;
 }
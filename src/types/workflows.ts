@@ -5,7 +5,9 @@ export type Filter =
   | { suffix: Record<string, string> }
   | { all: Filter[] }
   | { any: Filter[] }
-  | { not: Filter };
+  | { not: Filter }
+  /** Nexus extension: predicate over the event data, e.g. `data.ref == "refs/heads/main"`. */
+  | { where: string };
 
 /** What happens when a routing rule matches an event. */
 export type RouteAction =
@@ -21,7 +23,21 @@ export type RouteAction =
       operation: string;
       args_template?: Record<string, unknown>;
     }
-  | { action: "emit_frontend"; channel: string };
+  | { action: "emit_frontend"; channel: string }
+  | {
+      action: "webhook";
+      url: string;
+      headers?: Record<string, string>;
+      /** CloudEvents HTTP content mode. Defaults to "structured". */
+      mode?: WebhookMode;
+    }
+  | { action: "control_plugin"; plugin_id: string; command: PluginCommand };
+
+/** Structured: the whole event as JSON. Binary: data as the body, attributes as ce-* headers. */
+export type WebhookMode = "structured" | "binary";
+
+/** Restart stops the plugin if it's running, then starts it. */
+export type PluginCommand = "start" | "stop" | "restart";
 
 /** A routing rule that matches events by CE filters and triggers an action. */
 export interface RoutingRule {
@@ -41,4 +57,237 @@ export interface EventLogEntry {
   time: string;
   subject?: string;
   data: unknown;
+  /** ID of the event that started this event's causal chain. */
+  correlationid?: string;
+  /** ID of the event that directly caused this one. */
+  causationid?: string;
+}
+
+/** A frontend subscription to the event bus. */
+export interface FrontendSubscription {
+  subscription_id: string;
+  /** Tauri event name matching events are emitted on, as `EventLogEntry` payloads. */
+  channel: string;
+}
+
+/** An event to publish. The host fills in the ID, source, and time. */
+export interface PublishEvent {
+  type: string;
+  data?: unknown;
+  subject?: string;
+  datacontenttype?: string;
+  extensions?: Record<string, unknown>;
+}
+
+/** A workflow action that failed on every attempt, held for retry or discard. */
+export interface DeadLetter {
+  id: number;
+  event_id: string;
+  event_type: string;
+  source: string;
+  action: RouteAction;
+  attempts: number;
+  last_error: string | null;
+  failed_at: string;
+}
+
+/** Redelivery of events to subscribers that acknowledge them (`ack` subscriptions). */
+export interface RedeliveryPolicy {
+  /** Seconds to wait for an ack before redelivering. Doubles with each attempt. */
+  ack_timeout_secs: number;
+  /** Upper bound on the wait between attempts. */
+  max_backoff_secs: number;
+  /** Deliveries, including the first, before the event is given up on. */
+  max_attempts: number;
+}
+
+/** A user-defined event published as `nexus.timer.<name>` on a cron schedule. */
+export interface EventTimer {
+  id: string;
+  /** Dot-separated lowercase words. */
+  name: string;
+  /** Cron expression in local time: five fields, six with seconds first, or e.g. `@hourly`. */
+  schedule: string;
+  data: unknown;
+  enabled: boolean;
+  created_at: string;
+  /** Absent when disabled. */
+  next_fire?: string;
+}
+
+/** Limits on how fast and how much each plugin can publish. */
+export interface PublishQuota {
+  /** Events a plugin may publish per window. */
+  max_events: number;
+  window_secs: number;
+  /** Largest serialized event data accepted, in bytes. */
+  max_payload_bytes: number;
+  /** Rejections within one window before the plugin is recorded in the audit log. */
+  audit_after: number;
+}
+
+/** JSON Schema a plugin or extension registered for the data of one of its event types. */
+export interface EventSchema {
+  type: string;
+  schema: Record<string, unknown>;
+  /** "reject" refuses mismatching events; "flag" publishes them with a `schemaerror` attribute. */
+  mode: "reject" | "flag";
+  /** Source URI of the registering plugin or extension. */
+  owner: string;
+  registered_at: string;
+}
+
+/** Destination of exported events. */
+export type ExportSink =
+  | {
+      kind: "nats";
+      url: string;
+      /** `{type}` becomes the event type. */
+      subject: string;
+      token?: string | null;
+    }
+  | {
+      kind: "kafka";
+      /** Kafka REST Proxy base URL. */
+      rest_url: string;
+      topic: string;
+    };
+
+/** One-way export of matching events to a NATS subject or Kafka topic. */
+export interface ExportConfig {
+  enabled: boolean;
+  /** Event type glob to export. */
+  event_type: string;
+  /** Event source glob. All sources when null. */
+  source: string | null;
+  sink: ExportSink;
+  batch_size: number;
+  flush_interval_ms: number;
+  /** Attempts after the first before a failed batch is dropped. */
+  max_retries: number;
+}
+
+export interface ExportStatus {
+  running: boolean;
+  exported: number;
+  dropped: number;
+  last_error: string | null;
+}
+
+/** Which way events flow between the event bus and an MQTT topic. */
+export type MqttRoute =
+  | {
+      direction: "outbound";
+      /** Event type glob to publish to the broker. */
+      event_type: string;
+      /** `{type}` becomes the event type with dots as slashes. */
+      topic: string;
+      qos?: 0 | 1 | 2;
+      retain?: boolean;
+    }
+  | {
+      direction: "inbound";
+      /** MQTT topic filter, with `+` and `#` wildcards. */
+      topic: string;
+      /** Type of the events published for matching messages. */
+      event_type: string;
+      qos?: 0 | 1 | 2;
+    };
+
+/** Connection to an external MQTT broker, e.g. Home Assistant's. */
+export interface MqttBridgeConfig {
+  enabled: boolean;
+  host: string;
+  port: number;
+  tls: boolean;
+  client_id: string;
+  username: string | null;
+  password: string | null;
+  routes: MqttRoute[];
+}
+
+export type MqttStatus =
+  | { state: "disabled" | "connecting" | "connected" }
+  | { state: "error"; message: string };
+
+/** How much event history to keep. A zero limit on disk means no limit. */
+export interface RetentionPolicy {
+  /** Days to keep stored events. */
+  max_age_days: number;
+  /** Most stored events to keep; the oldest are dropped first. */
+  max_events: number;
+  /** Upper bound on the size of stored events, in bytes. */
+  max_bytes: number;
+  /** Recent events held in memory for the live event log. */
+  memory_events: number;
+}
+
+/** What a compaction pass removed from the event store. */
+export interface CompactionReport {
+  /** Events older than the age limit. */
+  expired: number;
+  /** Events dropped to get under the count and size limits. */
+  trimmed: number;
+}
+
+export interface EventLogStats {
+  memory_events: number;
+  memory_capacity: number;
+  store: {
+    events: number;
+    event_bytes: number;
+    /** Database file size, including free pages left by deletions. */
+    file_bytes: number;
+    oldest: string | null;
+    newest: string | null;
+    pending_deliveries: number;
+    dead_letters: number;
+  };
+}
+
+/** Event bus throughput since startup. */
+export interface BusMetrics {
+  published: number;
+  delivered: number;
+  /** Deliveries that failed because the subscriber had disconnected. */
+  dropped: number;
+  subscribers: { subscription_id: string; subscriber: string; queue_depth: number }[];
+  /** Route actions waiting to run or be retried. */
+  pending_deliveries: number;
+  executor: {
+    executed: number;
+    failed: number;
+    mean_ms: number;
+    max_ms: number;
+    total_secs: number;
+    /** Cumulative: actions that took at most `le_ms`. */
+    buckets: { le_ms: number; count: number }[];
+  };
+}
+
+/** Stored events to replay. Bounds combine; all are optional. */
+export interface ReplayRange {
+  /** Only events after this sequence number. */
+  after_seq?: number;
+  /** Only events at or after this time (RFC 3339). */
+  since?: string;
+  /** Only events before this time (RFC 3339). */
+  until?: string;
+  /** At most this many events (default 1000, max 10000). */
+  limit?: number;
+}
+
+/** Where replayed events go: a subscriber's connected subscriptions or a workflow. */
+export type ReplayTarget =
+  | {
+      target: "subscriber";
+      subscriber: { kind: "plugin"; plugin_id: string } | { kind: "extension"; ext_id: string };
+    }
+  | { target: "rule"; rule_id: string };
+
+export interface ReplayReport {
+  /** Events delivered to the subscriber, or actions queued for the workflow. */
+  replayed: number;
+  /** Sequence number of the last stored event read, to continue from. */
+  last_seq: number | null;
 }
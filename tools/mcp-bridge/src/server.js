@@ -29,6 +29,15 @@ function parseCommand(cmd) {
 
 const { command, args } = parseCommand(MCP_SERVER_COMMAND);
 
+// Optional comma-separated subset of the child's tools to expose
+const TOOL_ALLOWLIST = process.env.MCP_TOOL_ALLOWLIST
+  ? new Set(process.env.MCP_TOOL_ALLOWLIST.split(",").map((t) => t.trim()).filter(Boolean))
+  : null;
+
+function isToolExposed(name) {
+  return !TOOL_ALLOWLIST || TOOL_ALLOWLIST.has(name);
+}
+
 // ── Child MCP Client (stdio → child server) ──────────────────
 
 let childClient = null;
@@ -37,7 +46,8 @@ let childTransport = null;
 async function initChildClient() {
   console.log(`Spawning MCP server: ${command} ${args.join(" ")}`);
 
-  childTransport = new StdioClientTransport({ command, args });
+  // Pass the plugin's env through so manifest-configured variables reach the server
+  childTransport = new StdioClientTransport({ command, args, env: process.env });
   childClient = new Client(
     { name: "nexus-mcp-bridge", version: "0.1.0" },
     { capabilities: {} },
@@ -68,7 +78,8 @@ function createBridgeServer() {
     ListToolsRequestSchema,
     async () => {
       if (!childClient) throw new Error("Child MCP client not initialized");
-      return childClient.listTools();
+      const result = await childClient.listTools();
+      return { ...result, tools: (result.tools || []).filter((t) => isToolExposed(t.name)) };
     },
   );
 
@@ -77,6 +88,9 @@ function createBridgeServer() {
     CallToolRequestSchema,
     async (request) => {
       if (!childClient) throw new Error("Child MCP client not initialized");
+      if (!isToolExposed(request.params.name)) {
+        throw new Error(`Tool not exposed by this plugin: ${request.params.name}`);
+      }
       return childClient.callTool(request.params);
     },
   );